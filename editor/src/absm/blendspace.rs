@@ -32,11 +32,12 @@ use fyrox::{
         message::{CursorIcon, MessageDirection, MouseButton, UiMessage},
         popup::{Placement, PopupBuilder, PopupMessage},
         stack_panel::StackPanelBuilder,
+        style::Style,
         text::{TextBuilder, TextMessage},
         widget::{Widget, WidgetBuilder, WidgetMessage},
         window::{WindowBuilder, WindowMessage, WindowTitle},
         BuildContext, Control, HorizontalAlignment, RcUiNodeHandle, Thickness, UiNode,
-        UserInterface, VerticalAlignment, BRUSH_DARK, BRUSH_LIGHT, BRUSH_LIGHTEST,
+        UserInterface, VerticalAlignment, BRUSH_LIGHT,
     },
 };
 use std::fmt::{Debug, Formatter};
@@ -152,7 +153,7 @@ fn make_points<P: Iterator<Item = Vector2<f32>>>(
             BlendSpaceFieldPointBuilder::new(
                 WidgetBuilder::new()
                     .with_context_menu(context_menu.clone())
-                    .with_background(BRUSH_LIGHTEST)
+                    .with_background_style(Style::BRUSH_LIGHTEST)
                     .with_foreground(Brush::Solid(Color::WHITE))
                     .with_desired_position(p),
                 i,
@@ -649,8 +650,8 @@ impl BlendSpaceEditor {
                 .on_row(0)
                 .on_column(1)
                 .with_margin(Thickness::uniform(15.0))
-                .with_foreground(BRUSH_LIGHTEST)
-                .with_background(BRUSH_DARK),
+                .with_foreground_style(Style::BRUSH_LIGHTEST)
+                .with_background_style(Style::BRUSH_DARK),
         )
         .build(ctx);
 
@@ -22,7 +22,8 @@ use fyrox::{
         brush::Brush,
         define_constructor, define_widget_deref,
         draw::{CommandTexture, Draw, DrawingContext},
-        message::{MessageDirection, MouseButton, UiMessage},
+        message::{KeyCode, MessageDirection, MouseButton, UiMessage},
+        text_box::TextBox,
         widget::{Widget, WidgetBuilder, WidgetMessage},
         BuildContext, Control, UiNode, UserInterface,
     },
@@ -33,6 +34,13 @@ use std::{
     ops::{Deref, DerefMut},
 };
 
+const MIN_ZOOM: f32 = 0.2;
+const MAX_ZOOM: f32 = 2.0;
+/// How many times the visible area should be larger than a framed rectangle.
+const FRAME_SCALE: f32 = 4.0;
+/// How many times the visible area should be larger than the bounds of all nodes on zoom-to-fit.
+const FIT_SCALE: f32 = 1.1;
+
 #[derive(Debug, Clone, PartialEq, Visit, Reflect, Default)]
 pub(super) struct Entry {
     pub node: Handle<UiNode>,
@@ -61,6 +69,11 @@ pub(super) enum Mode {
         source_pos: Vector2<f32>,
         dest_pos: Vector2<f32>,
     },
+    RectSelection {
+        initial_selection: Vec<Handle<UiNode>>,
+        start: Vector2<f32>,
+        end: Vector2<f32>,
+    },
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -79,6 +92,20 @@ pub(super) enum AbsmCanvasMessage {
     },
     SelectionChanged(Vec<Handle<UiNode>>),
     ForceSyncDependentObjects,
+    /// Moves the view so the given point (in local coordinates of the canvas) is in the center of the
+    /// visible area of the canvas.
+    FocusOn(Vector2<f32>),
+    /// Zooms and moves the view so the given rectangle (in local coordinates of the canvas) is in the
+    /// center of the visible area of the canvas and occupies a reasonable part of it.
+    Frame(Rect<f32>),
+    /// Zooms and moves the view so every node of the canvas is visible.
+    ZoomToFit,
+    /// Sets position and zoom of the view when sent to the canvas, the canvas sends this message back
+    /// every time when the view was changed by the user.
+    View {
+        position: Vector2<f32>,
+        zoom: f32,
+    },
 }
 
 impl AbsmCanvasMessage {
@@ -88,6 +115,10 @@ impl AbsmCanvasMessage {
     define_constructor!(AbsmCanvasMessage:CommitDrag => fn commit_drag(entries: Vec<Entry>), layout: false);
     define_constructor!(AbsmCanvasMessage:SelectionChanged => fn selection_changed(Vec<Handle<UiNode>>), layout: false);
     define_constructor!(AbsmCanvasMessage:ForceSyncDependentObjects => fn force_sync_dependent_objects(), layout: true);
+    define_constructor!(AbsmCanvasMessage:FocusOn => fn focus_on(Vector2<f32>), layout: false);
+    define_constructor!(AbsmCanvasMessage:Frame => fn frame(Rect<f32>), layout: false);
+    define_constructor!(AbsmCanvasMessage:ZoomToFit => fn zoom_to_fit(), layout: false);
+    define_constructor!(AbsmCanvasMessage:View => fn view(position: Vector2<f32>, zoom: f32), layout: false);
 }
 
 #[derive(Clone, Visit, Reflect, Debug)]
@@ -290,12 +321,138 @@ impl AbsmCanvas {
         }
     }
 
+    /// Returns the area of the canvas (in its local coordinates) that is currently visible.
+    pub fn visible_area(&self, ui: &UserInterface) -> Rect<f32> {
+        let screen_bounds = ui.node(self.parent()).screen_bounds();
+        let left_top = self.point_to_local_space(screen_bounds.left_top_corner());
+        let right_bottom = self.point_to_local_space(screen_bounds.right_bottom_corner());
+        Rect::new(
+            left_top.x,
+            left_top.y,
+            right_bottom.x - left_top.x,
+            right_bottom.y - left_top.y,
+        )
+    }
+
+    /// Returns an offset of the canvas caused by the zoom: when the canvas is zoomed out, it is centered
+    /// in its visible area.
+    fn zoom_offset(&self, zoom: f32, ui: &UserInterface) -> Vector2<f32> {
+        ui.node(self.parent())
+            .actual_local_size()
+            .scale((1.0 - zoom.min(1.0)) * 0.5)
+    }
+
+    fn focus_on(&mut self, point: Vector2<f32>, new_zoom: f32, ui: &UserInterface) {
+        let view_bounds = ui.node(self.parent()).screen_bounds();
+
+        // Visual transform is updated only on next layout pass, so take the change of the zoom into
+        // account manually.
+        let current = self
+            .visual_transform()
+            .transform_point(&Point2::from(point))
+            .coords
+            + point.scale(new_zoom - self.zoom)
+            + self.zoom_offset(new_zoom, ui)
+            - self.zoom_offset(self.zoom, ui);
+
+        self.zoom = new_zoom;
+        self.view_position += view_bounds.center() - current;
+        self.update_transform(ui);
+        self.commit_view(ui);
+    }
+
+    /// Changes the zoom so the point of the canvas under the given screen position stays in place.
+    fn zoom_at(&mut self, screen_position: Vector2<f32>, new_zoom: f32, ui: &UserInterface) {
+        let point = self.point_to_local_space(screen_position);
+
+        self.view_position += point.scale(self.zoom - new_zoom) + self.zoom_offset(self.zoom, ui)
+            - self.zoom_offset(new_zoom, ui);
+        self.zoom = new_zoom;
+        self.update_transform(ui);
+        self.commit_view(ui);
+    }
+
+    fn frame(&mut self, rect: Rect<f32>, ui: &UserInterface) {
+        let view_size = ui.node(self.parent()).screen_bounds().size;
+        let zoom = (view_size.x / (rect.w() * FRAME_SCALE).max(1.0))
+            .min(view_size.y / (rect.h() * FRAME_SCALE).max(1.0))
+            .clamp(MIN_ZOOM, 1.0);
+        self.focus_on(rect.center(), zoom, ui);
+    }
+
+    fn zoom_to_fit(&mut self, ui: &UserInterface) {
+        let mut bounds: Option<Rect<f32>> = None;
+        for node in self.children().iter().map(|c| ui.node(*c)) {
+            if node.has_component::<AbsmBaseNode>() {
+                let position = node.actual_local_position();
+                let size = node.actual_local_size();
+                let rect = Rect::new(position.x, position.y, size.x, size.y);
+                match bounds.as_mut() {
+                    Some(bounds) => bounds.extend_to_contain(rect),
+                    None => bounds = Some(rect),
+                }
+            }
+        }
+
+        if let Some(bounds) = bounds {
+            let view_size = ui.node(self.parent()).screen_bounds().size;
+            let zoom = (view_size.x / (bounds.w() * FIT_SCALE).max(1.0))
+                .min(view_size.y / (bounds.h() * FIT_SCALE).max(1.0))
+                .clamp(MIN_ZOOM, 1.0);
+            self.focus_on(bounds.center(), zoom, ui);
+        }
+    }
+
+    fn commit_view(&self, ui: &UserInterface) {
+        ui.send_message(AbsmCanvasMessage::view(
+            self.handle(),
+            MessageDirection::FromWidget,
+            self.view_position,
+            self.zoom,
+        ));
+    }
+
+    /// Selects every node that intersects the rectangle between the given points (in local coordinates of
+    /// the canvas), the nodes are added to the initial selection.
+    fn select_in_rect(
+        &mut self,
+        initial_selection: &[Handle<UiNode>],
+        start: Vector2<f32>,
+        end: Vector2<f32>,
+        ui: &UserInterface,
+    ) {
+        let rect = make_rect(start, end);
+
+        let mut new_selection = initial_selection.to_vec();
+        for &child in self.children() {
+            let child_ref = ui.node(child);
+            if child_ref.has_component::<AbsmBaseNode>() && !new_selection.contains(&child) {
+                let position = child_ref.actual_local_position();
+                let size = child_ref.actual_local_size();
+                if rect.intersects(Rect::new(position.x, position.y, size.x, size.y)) {
+                    new_selection.push(child);
+                }
+            }
+        }
+
+        self.set_selection(&new_selection, ui);
+    }
+
     fn force_sync_dependent_objects(&self, ui: &UserInterface) {
         self.sync_transitions_ends(Handle::NONE, ui, true);
         self.sync_connections_ends(Handle::NONE, ui, true);
     }
 }
 
+fn make_rect(a: Vector2<f32>, b: Vector2<f32>) -> Rect<f32> {
+    Rect::new(
+        a.x.min(b.x),
+        a.y.min(b.y),
+        (a.x - b.x).abs(),
+        (a.y - b.y).abs(),
+    )
+}
+
 impl Control for AbsmCanvas {
     fn query_component(&self, type_id: TypeId) -> Option<&dyn Any> {
         if type_id == TypeId::of::<Self>() {
@@ -390,6 +547,25 @@ impl Control for AbsmCanvas {
                     Brush::Solid(Color::WHITE),
                 );
             }
+            Mode::RectSelection { start, end, .. } => {
+                let rect = make_rect(start, end);
+
+                ctx.push_rect_filled(&rect, None);
+                ctx.commit(
+                    self.clip_bounds(),
+                    Brush::Solid(Color::from_rgba(80, 118, 178, 50)),
+                    CommandTexture::None,
+                    None,
+                );
+
+                ctx.push_rect(&rect, 1.0 / self.zoom);
+                ctx.commit(
+                    self.clip_bounds(),
+                    Brush::Solid(Color::opaque(80, 118, 178)),
+                    CommandTexture::None,
+                    None,
+                );
+            }
             _ => {}
         }
     }
@@ -439,6 +615,17 @@ impl Control for AbsmCanvas {
                 self.set_selection(&new_selection, ui);
             }
         } else if let Some(WidgetMessage::MouseDown { pos, button }) = message.data() {
+            // Keep keyboard focus on the canvas itself, node views are re-created on every change
+            // of the machine and the focus would be lost together with them. Key presses then bubble
+            // up to the editor and trigger undo/redo/delete hotkeys. Text boxes (used to rename nodes)
+            // keep the focus.
+            if !ui.node(message.destination()).has_component::<TextBox>() {
+                ui.send_message(WidgetMessage::focus(
+                    self.handle(),
+                    MessageDirection::ToWidget,
+                ));
+            }
+
             if *button == MouseButton::Middle {
                 self.is_dragging_view = true;
                 self.click_position = *pos;
@@ -468,6 +655,24 @@ impl Control for AbsmCanvas {
                             self.mode = Mode::Drag {
                                 drag_context: self.make_drag_context(ui),
                             }
+                        } else if message.destination() == self.handle() {
+                            // Start rectangular selection on empty space, holding Ctrl adds nodes
+                            // to the current selection.
+                            let initial_selection = if ui.keyboard_modifiers().control {
+                                self.selection.clone()
+                            } else {
+                                self.set_selection(&[], ui);
+                                Vec::new()
+                            };
+
+                            let start = self.point_to_local_space(*pos);
+                            self.mode = Mode::RectSelection {
+                                initial_selection,
+                                start,
+                                end: start,
+                            };
+
+                            ui.capture_mouse(self.handle());
                         } else {
                             self.set_selection(&[], ui);
                         }
@@ -477,7 +682,10 @@ impl Control for AbsmCanvas {
             }
         } else if let Some(WidgetMessage::MouseUp { button, pos }) = message.data() {
             if *button == MouseButton::Middle {
-                self.is_dragging_view = false;
+                if self.is_dragging_view {
+                    self.is_dragging_view = false;
+                    self.commit_view(ui);
+                }
 
                 ui.release_mouse_capture();
             } else if *button == MouseButton::Left {
@@ -493,6 +701,17 @@ impl Control for AbsmCanvas {
 
                         self.mode = Mode::Normal;
                     }
+                    Mode::RectSelection {
+                        ref initial_selection,
+                        start,
+                        end,
+                    } => {
+                        let initial_selection = initial_selection.clone();
+                        self.select_in_rect(&initial_selection, start, end, ui);
+                        self.mode = Mode::Normal;
+
+                        ui.release_mouse_capture();
+                    }
                     Mode::CreateConnection { source, .. } => {
                         let dest_socket_handle = self
                             .fetch_dest_node_component::<Socket>(self.lmb_released_node.get(), ui);
@@ -566,18 +785,19 @@ impl Control for AbsmCanvas {
                 } => {
                     *dest_pos = local_cursor_position;
                 }
+                Mode::RectSelection { ref mut end, .. } => {
+                    *end = local_cursor_position;
+                }
                 _ => (),
             }
         } else if let Some(WidgetMessage::MouseWheel { amount, pos }) = message.data() {
-            let cursor_pos = (*pos - self.screen_position()).scale(self.zoom);
-
-            self.zoom = (self.zoom + 0.1 * amount).clamp(0.2, 2.0);
-
-            let new_cursor_pos = (*pos - self.screen_position()).scale(self.zoom);
-
-            self.view_position -= (new_cursor_pos - cursor_pos).scale(self.zoom);
-
-            self.update_transform(ui);
+            let new_zoom = (self.zoom + 0.1 * amount).clamp(MIN_ZOOM, MAX_ZOOM);
+            self.zoom_at(*pos, new_zoom, ui);
+        } else if let Some(WidgetMessage::KeyDown(KeyCode::Home)) = message.data() {
+            if !message.handled() {
+                self.zoom_to_fit(ui);
+                message.set_handled(true);
+            }
         } else if let Some(msg) = message.data::<AbsmCanvasMessage>() {
             if message.direction() == MessageDirection::ToWidget
                 && message.destination() == self.handle()
@@ -593,6 +813,20 @@ impl Control for AbsmCanvas {
                     AbsmCanvasMessage::ForceSyncDependentObjects => {
                         self.force_sync_dependent_objects(ui);
                     }
+                    AbsmCanvasMessage::FocusOn(point) => {
+                        self.focus_on(*point, self.zoom, ui);
+                    }
+                    AbsmCanvasMessage::Frame(rect) => {
+                        self.frame(*rect, ui);
+                    }
+                    AbsmCanvasMessage::ZoomToFit => {
+                        self.zoom_to_fit(ui);
+                    }
+                    AbsmCanvasMessage::View { position, zoom } => {
+                        self.view_position = *position;
+                        self.zoom = zoom.clamp(MIN_ZOOM, MAX_ZOOM);
+                        self.update_transform(ui);
+                    }
                     _ => (),
                 }
             }
@@ -636,26 +870,42 @@ impl Control for AbsmCanvas {
 
 pub struct AbsmCanvasBuilder {
     widget_builder: WidgetBuilder,
+    view_position: Vector2<f32>,
+    zoom: f32,
 }
 
 impl AbsmCanvasBuilder {
     pub fn new(widget_builder: WidgetBuilder) -> Self {
-        Self { widget_builder }
+        Self {
+            widget_builder,
+            view_position: Default::default(),
+            zoom: 1.0,
+        }
+    }
+
+    pub fn with_view(mut self, position: Vector2<f32>, zoom: f32) -> Self {
+        self.view_position = position;
+        self.zoom = zoom.clamp(MIN_ZOOM, MAX_ZOOM);
+        self
     }
 
     pub fn build(self, ctx: &mut BuildContext) -> Handle<UiNode> {
         let canvas = AbsmCanvas {
             widget: self
                 .widget_builder
+                .with_layout_transform(
+                    Matrix3::new_translation(&-self.view_position)
+                        * Matrix3::new_scaling(self.zoom),
+                )
                 .with_preview_messages(true)
                 .with_clip_to_bounds(false)
                 .build(),
             selection: Default::default(),
-            view_position: Default::default(),
+            view_position: self.view_position,
             initial_view_position: Default::default(),
             click_position: Default::default(),
             is_dragging_view: false,
-            zoom: 1.0,
+            zoom: self.zoom,
             mode: Mode::Normal,
             lmb_released_node: Default::default(),
         };
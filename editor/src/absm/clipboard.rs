@@ -0,0 +1,102 @@
+use crate::absm::{
+    command::PasteStatesCommand,
+    selection::{AbsmSelection, SelectedEntity},
+};
+use fyrox::{
+    animation::machine::{MachineLayer, PoseNode, State, Transition},
+    core::{algebra::Vector2, pool::Handle},
+    scene::node::Node,
+};
+
+/// A copy of a set of states with their pose nodes and transitions between the states.
+#[derive(Default, Debug, Clone)]
+pub struct AbsmClipboard {
+    states: Vec<(Handle<State>, State)>,
+    nodes: Vec<(Handle<PoseNode>, PoseNode)>,
+    transitions: Vec<Transition>,
+}
+
+impl AbsmClipboard {
+    pub fn fill_from_selection(&mut self, selection: &AbsmSelection, layer: &MachineLayer) {
+        self.clear();
+
+        for entity in selection.entities.iter() {
+            if let SelectedEntity::State(handle) = entity {
+                if let Some(state) = layer.states().try_borrow(*handle) {
+                    self.states.push((*handle, state.clone()));
+                }
+            }
+        }
+
+        let contains = |state: Handle<State>| self.states.iter().any(|(s, _)| *s == state);
+
+        self.nodes = layer
+            .nodes()
+            .pair_iter()
+            .filter(|(_, node)| contains(node.parent_state))
+            .map(|(handle, node)| (handle, node.clone()))
+            .collect();
+
+        // Only transitions between copied states are copied.
+        self.transitions = layer
+            .transitions()
+            .iter()
+            .filter(|transition| contains(transition.source()) && contains(transition.dest()))
+            .cloned()
+            .collect();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.states.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.states.clear();
+        self.nodes.clear();
+        self.transitions.clear();
+    }
+
+    /// Returns top-left corner of the bounds of the copied states.
+    pub fn origin(&self) -> Vector2<f32> {
+        self.states
+            .iter()
+            .map(|(_, state)| state.position)
+            .reduce(|a, b| a.inf(&b))
+            .unwrap_or_default()
+    }
+
+    /// Creates a command, that pastes the copied states to the given layer and moves them by the given offset.
+    pub fn make_paste_command(
+        &self,
+        absm_node_handle: Handle<Node>,
+        layer_index: usize,
+        offset: Vector2<f32>,
+    ) -> PasteStatesCommand {
+        PasteStatesCommand::new(
+            absm_node_handle,
+            layer_index,
+            self.states
+                .iter()
+                .map(|(handle, state)| {
+                    let mut state = state.clone();
+                    state.position += offset;
+                    (*handle, state)
+                })
+                .collect(),
+            self.nodes.clone(),
+            self.transitions
+                .iter()
+                .map(|transition| {
+                    let mut transition = transition.clone();
+                    let reroute_points = transition
+                        .reroute_points()
+                        .iter()
+                        .map(|point| point + offset)
+                        .collect();
+                    transition.set_reroute_points(reroute_points);
+                    transition
+                })
+                .collect(),
+        )
+    }
+}
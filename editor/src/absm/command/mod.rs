@@ -1,10 +1,18 @@
-use crate::{command::Command, scene::commands::SceneContext};
+use crate::{
+    absm::selection::{AbsmSelection, SelectedEntity},
+    command::Command,
+    scene::{
+        commands::{ChangeSelectionCommand, CommandGroup, SceneCommand, SceneContext},
+        Selection,
+    },
+};
 use fyrox::{
     animation::machine::{LayerMask, Machine, MachineLayer, PoseNode, State, Transition},
     core::{
         algebra::Vector2,
         pool::{Handle, Ticket},
     },
+    fxhash::FxHashMap,
     scene::{animation::absm::AnimationBlendingStateMachine, node::Node},
 };
 use std::fmt::Debug;
@@ -403,6 +411,242 @@ impl Command for AddPoseNodeCommand {
     }
 }
 
+fn remap_pose_sources(
+    node: &mut PoseNode,
+    node_map: &FxHashMap<Handle<PoseNode>, Handle<PoseNode>>,
+) {
+    let remap = |handle: &mut Handle<PoseNode>| {
+        *handle = node_map.get(handle).cloned().unwrap_or_default();
+    };
+
+    match node {
+        PoseNode::PlayAnimation(_) => (),
+        PoseNode::BlendAnimations(blend_animations) => {
+            for pose in blend_animations.pose_sources.iter_mut() {
+                remap(&mut pose.pose_source);
+            }
+        }
+        PoseNode::BlendAnimationsByIndex(blend_by_index) => {
+            for input in blend_by_index.inputs.iter_mut() {
+                remap(&mut input.pose_source);
+            }
+        }
+        PoseNode::BlendSpace(blend_space) => {
+            for point in blend_space.points_mut().iter_mut() {
+                remap(&mut point.pose_source);
+            }
+        }
+    }
+}
+
+/// Adds a copy of a set of states with their pose nodes and transitions between them to a layer. Handles
+/// of the copied entities are remapped to the handles of the new entities.
+#[derive(Debug)]
+pub enum PasteStatesCommand {
+    Unknown,
+    NonExecuted {
+        node_handle: Handle<Node>,
+        layer_index: usize,
+        states: Vec<(Handle<State>, State)>,
+        nodes: Vec<(Handle<PoseNode>, PoseNode)>,
+        transitions: Vec<Transition>,
+    },
+    Executed {
+        node_handle: Handle<Node>,
+        layer_index: usize,
+        states: Vec<Handle<State>>,
+        nodes: Vec<Handle<PoseNode>>,
+        transitions: Vec<Handle<Transition>>,
+    },
+    Reverted {
+        node_handle: Handle<Node>,
+        layer_index: usize,
+        states: Vec<(Ticket<State>, State)>,
+        nodes: Vec<(Ticket<PoseNode>, PoseNode)>,
+        transitions: Vec<(Ticket<Transition>, Transition)>,
+    },
+}
+
+impl PasteStatesCommand {
+    pub fn new(
+        node_handle: Handle<Node>,
+        layer_index: usize,
+        states: Vec<(Handle<State>, State)>,
+        nodes: Vec<(Handle<PoseNode>, PoseNode)>,
+        transitions: Vec<Transition>,
+    ) -> Self {
+        Self::NonExecuted {
+            node_handle,
+            layer_index,
+            states,
+            nodes,
+            transitions,
+        }
+    }
+}
+
+impl Command for PasteStatesCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Paste States".to_string()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        match std::mem::replace(self, PasteStatesCommand::Unknown) {
+            PasteStatesCommand::NonExecuted {
+                node_handle,
+                layer_index,
+                states,
+                nodes,
+                transitions,
+            } => {
+                let layer = &mut fetch_machine(context, node_handle).layers_mut()[layer_index];
+
+                let state_map = states
+                    .into_iter()
+                    .map(|(old_handle, state)| (old_handle, layer.states_mut().spawn(state)))
+                    .collect::<FxHashMap<_, _>>();
+
+                let node_map = nodes
+                    .into_iter()
+                    .map(|(old_handle, mut node)| {
+                        node.parent_state = state_map
+                            .get(&node.parent_state)
+                            .cloned()
+                            .unwrap_or_default();
+                        (old_handle, layer.nodes_mut().spawn(node))
+                    })
+                    .collect::<FxHashMap<_, _>>();
+
+                for &new_node in node_map.values() {
+                    remap_pose_sources(&mut layer.nodes_mut()[new_node], &node_map);
+                }
+
+                for &new_state in state_map.values() {
+                    let state = &mut layer.states_mut()[new_state];
+                    state.root = node_map.get(&state.root).cloned().unwrap_or_default();
+                }
+
+                let transitions = transitions
+                    .into_iter()
+                    .filter_map(|transition| {
+                        let source = *state_map.get(&transition.source())?;
+                        let dest = *state_map.get(&transition.dest())?;
+                        let mut new_transition = Transition::new(
+                            transition.name(),
+                            source,
+                            dest,
+                            transition.transition_time(),
+                            "",
+                        );
+                        new_transition.set_condition(transition.condition().clone());
+                        new_transition.set_priority(transition.priority());
+                        new_transition.set_can_be_interrupted(transition.can_be_interrupted());
+                        new_transition.set_exit_time(transition.exit_time());
+                        new_transition.set_reroute_points(transition.reroute_points().to_vec());
+                        Some(layer.transitions_mut().spawn(new_transition))
+                    })
+                    .collect();
+
+                *self = PasteStatesCommand::Executed {
+                    node_handle,
+                    layer_index,
+                    states: state_map.into_values().collect(),
+                    nodes: node_map.into_values().collect(),
+                    transitions,
+                };
+            }
+            PasteStatesCommand::Reverted {
+                node_handle,
+                layer_index,
+                states,
+                nodes,
+                transitions,
+            } => {
+                let layer = &mut fetch_machine(context, node_handle).layers_mut()[layer_index];
+
+                *self = PasteStatesCommand::Executed {
+                    node_handle,
+                    layer_index,
+                    states: states
+                        .into_iter()
+                        .map(|(ticket, state)| layer.states_mut().put_back(ticket, state))
+                        .collect(),
+                    nodes: nodes
+                        .into_iter()
+                        .map(|(ticket, node)| layer.nodes_mut().put_back(ticket, node))
+                        .collect(),
+                    transitions: transitions
+                        .into_iter()
+                        .map(|(ticket, transition)| {
+                            layer.transitions_mut().put_back(ticket, transition)
+                        })
+                        .collect(),
+                };
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        match std::mem::replace(self, PasteStatesCommand::Unknown) {
+            PasteStatesCommand::Executed {
+                node_handle,
+                layer_index,
+                states,
+                nodes,
+                transitions,
+            } => {
+                let layer = &mut fetch_machine(context, node_handle).layers_mut()[layer_index];
+
+                // Transitions must be removed first, because they're referencing the states.
+                let transitions = transitions
+                    .into_iter()
+                    .map(|handle| layer.transitions_mut().take_reserve(handle))
+                    .collect();
+                let nodes = nodes
+                    .into_iter()
+                    .map(|handle| layer.nodes_mut().take_reserve(handle))
+                    .collect();
+                let states = states
+                    .into_iter()
+                    .map(|handle| layer.states_mut().take_reserve(handle))
+                    .collect();
+
+                *self = PasteStatesCommand::Reverted {
+                    node_handle,
+                    layer_index,
+                    states,
+                    nodes,
+                    transitions,
+                };
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    fn finalize(&mut self, context: &mut SceneContext) {
+        if let PasteStatesCommand::Reverted {
+            node_handle,
+            layer_index,
+            states,
+            nodes,
+            transitions,
+        } = std::mem::replace(self, PasteStatesCommand::Unknown)
+        {
+            let layer = &mut fetch_machine(context, node_handle).layers_mut()[layer_index];
+            for (ticket, _) in transitions {
+                layer.transitions_mut().forget_ticket(ticket);
+            }
+            for (ticket, _) in nodes {
+                layer.nodes_mut().forget_ticket(ticket);
+            }
+            for (ticket, _) in states {
+                layer.states_mut().forget_ticket(ticket);
+            }
+        }
+    }
+}
+
 macro_rules! define_move_command {
     ($name:ident, $ent_type:ty, $container:ident) => {
         #[derive(Debug)]
@@ -725,6 +969,70 @@ impl Command for SetMachineEntryStateCommand {
     }
 }
 
+#[derive(Debug)]
+pub struct SetMachineAnyStateCommand {
+    pub node_handle: Handle<Node>,
+    pub layer: usize,
+    pub any_state: Handle<State>,
+}
+
+impl SetMachineAnyStateCommand {
+    fn swap(&mut self, context: &mut SceneContext) {
+        let machine = fetch_machine(context, self.node_handle);
+        let layer = &mut machine.layers_mut()[self.layer];
+
+        let prev = layer.any_state();
+        layer.set_any_state(self.any_state);
+        self.any_state = prev;
+    }
+}
+
+impl Command for SetMachineAnyStateCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Set Any State".to_string()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        self.swap(context)
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        self.swap(context)
+    }
+}
+
+#[derive(Debug)]
+pub struct SetMachineExitStateCommand {
+    pub node_handle: Handle<Node>,
+    pub layer: usize,
+    pub exit: Handle<State>,
+}
+
+impl SetMachineExitStateCommand {
+    fn swap(&mut self, context: &mut SceneContext) {
+        let machine = fetch_machine(context, self.node_handle);
+        let layer = &mut machine.layers_mut()[self.layer];
+
+        let prev = layer.exit_state();
+        layer.set_exit_state(self.exit);
+        self.exit = prev;
+    }
+}
+
+impl Command for SetMachineExitStateCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Set Exit State".to_string()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        self.swap(context)
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        self.swap(context)
+    }
+}
+
 #[macro_export]
 macro_rules! define_absm_swap_command {
     ($name:ident<$model_type:ty, $value_type:ty>[$($field_name:ident:$field_type:ty),*]($self:ident, $context:ident) $get_field:block) => {
@@ -767,6 +1075,44 @@ define_absm_swap_command!(SetStateRootPoseCommand<Handle<State>, Handle<PoseNode
     &mut machine.layers_mut()[self.layer_index].states_mut()[self.handle].root
 });
 
+define_absm_swap_command!(RenameStateCommand<Handle<State>, String>[layer_index: usize](self, context) {
+    let machine = fetch_machine(context, self.node_handle);
+    &mut machine.layers_mut()[self.layer_index].states_mut()[self.handle].name
+});
+
+#[derive(Debug)]
+pub struct SetTransitionReroutePointsCommand {
+    pub node_handle: Handle<Node>,
+    pub layer_index: usize,
+    pub handle: Handle<Transition>,
+    pub value: Vec<Vector2<f32>>,
+}
+
+impl SetTransitionReroutePointsCommand {
+    fn swap(&mut self, context: &mut SceneContext) {
+        let machine = fetch_machine(context, self.node_handle);
+        let transition = &mut machine.layers_mut()[self.layer_index].transitions_mut()[self.handle];
+
+        let prev = transition.reroute_points().to_vec();
+        transition.set_reroute_points(std::mem::take(&mut self.value));
+        self.value = prev;
+    }
+}
+
+impl Command for SetTransitionReroutePointsCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Set Transition Reroute Points".to_string()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        self.swap(context)
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        self.swap(context)
+    }
+}
+
 #[derive(Debug)]
 pub struct SetLayerNameCommand {
     pub absm_node_handle: Handle<Node>,
@@ -880,3 +1226,105 @@ impl Command for SetLayerMaskCommand {
         self.swap(context)
     }
 }
+
+/// Creates a command that removes selected states and transitions of a layer. Every transition, that
+/// leads from/to any of the removed states, is removed as well, so undo restores the graph correctly.
+/// Returns `None` if there is nothing to remove.
+pub fn make_delete_selection_command(
+    selection: &AbsmSelection,
+    layer: &MachineLayer,
+    current_selection: &Selection,
+) -> Option<CommandGroup> {
+    let layer_index = selection.layer?;
+
+    let states_to_remove = selection
+        .entities
+        .iter()
+        .filter_map(|e| {
+            if let SelectedEntity::State(handle) = e {
+                Some(*handle)
+            } else {
+                None
+            }
+        })
+        .collect::<Vec<_>>();
+
+    // Gather every transition that is selected or leads from/to any of states to remove.
+    let transitions_to_remove = layer
+        .transitions()
+        .pair_iter()
+        .filter_map(|(handle, transition)| {
+            if selection
+                .entities
+                .contains(&SelectedEntity::Transition(handle))
+                || states_to_remove.iter().any(|state_to_remove| {
+                    *state_to_remove == transition.source() || *state_to_remove == transition.dest()
+                })
+            {
+                Some(handle)
+            } else {
+                None
+            }
+        })
+        .collect::<Vec<_>>();
+
+    if states_to_remove.is_empty() && transitions_to_remove.is_empty() {
+        return None;
+    }
+
+    let mut new_selection = selection.clone();
+    new_selection.entities.clear();
+
+    let mut group = vec![SceneCommand::new(ChangeSelectionCommand::new(
+        Selection::Absm(new_selection),
+        current_selection.clone(),
+    ))];
+
+    group.extend(transitions_to_remove.into_iter().map(|transition| {
+        SceneCommand::new(DeleteTransitionCommand::new(
+            selection.absm_node_handle,
+            layer_index,
+            transition,
+        ))
+    }));
+
+    group.extend(states_to_remove.into_iter().map(|state| {
+        SceneCommand::new(DeleteStateCommand::new(
+            selection.absm_node_handle,
+            layer_index,
+            state,
+        ))
+    }));
+
+    Some(CommandGroup::from(group))
+}
+
+/// Replaces the whole machine of an ABSM node, it is used to load machines from files.
+#[derive(Debug)]
+pub struct SetMachineCommand {
+    pub absm_node_handle: Handle<Node>,
+    pub machine: Machine,
+}
+
+impl SetMachineCommand {
+    fn swap(&mut self, context: &mut SceneContext) {
+        std::mem::swap(
+            fetch_machine(context, self.absm_node_handle),
+            &mut self.machine,
+        );
+    }
+}
+
+impl Command for SetMachineCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Set Machine".to_string()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        self.swap(context)
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        self.swap(context)
+    }
+}
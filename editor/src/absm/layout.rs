@@ -0,0 +1,196 @@
+use crate::{
+    absm::command::MoveStateNodeCommand,
+    scene::commands::{CommandGroup, SceneCommand},
+};
+use fyrox::{
+    animation::machine::{MachineLayer, State},
+    core::{algebra::Vector2, pool::Handle},
+    fxhash::FxHashMap,
+    scene::node::Node,
+};
+use std::collections::VecDeque;
+
+/// Horizontal distance between columns of the arranged states.
+const COLUMN_SPACING: f32 = 220.0;
+/// Vertical distance between states in a column.
+const ROW_SPACING: f32 = 90.0;
+
+/// Calculates new positions of the states of the layer using simple layered layout. States are put in
+/// columns by their distance (in transitions) from the entry state, states that cannot be reached from
+/// the entry state start their own chains. The layout is anchored at the current position of the entry
+/// state, so the arranged graph stays in the same place on the canvas.
+pub fn arrange_states(layer: &MachineLayer) -> Vec<(Handle<State>, Vector2<f32>)> {
+    let mut roots = Vec::new();
+    if layer.states().is_valid_handle(layer.entry_state()) {
+        roots.push(layer.entry_state());
+    }
+    roots.extend(layer.states().pair_iter().map(|(handle, _)| handle));
+
+    let mut depths = FxHashMap::<Handle<State>, usize>::default();
+    let mut columns = Vec::<Vec<Handle<State>>>::new();
+    let mut queue = VecDeque::new();
+
+    for root in roots {
+        if depths.contains_key(&root) {
+            continue;
+        }
+
+        depths.insert(root, 0);
+        queue.push_back(root);
+
+        while let Some(state) = queue.pop_front() {
+            let depth = depths[&state];
+
+            if columns.len() <= depth {
+                columns.resize(depth + 1, Vec::new());
+            }
+            columns[depth].push(state);
+
+            for transition in layer.transitions().iter() {
+                if transition.source() == state
+                    && layer.states().is_valid_handle(transition.dest())
+                    && !depths.contains_key(&transition.dest())
+                {
+                    depths.insert(transition.dest(), depth + 1);
+                    queue.push_back(transition.dest());
+                }
+            }
+        }
+    }
+
+    let origin = layer
+        .states()
+        .try_borrow(layer.entry_state())
+        .map(|state| state.position)
+        .unwrap_or_default();
+
+    let mut positions = Vec::new();
+    for (column_index, column) in columns.iter().enumerate() {
+        // Center each column vertically relative to the origin.
+        let top = -(column.len() as f32 - 1.0) * ROW_SPACING * 0.5;
+        for (row_index, state) in column.iter().enumerate() {
+            positions.push((
+                *state,
+                origin
+                    + Vector2::new(
+                        column_index as f32 * COLUMN_SPACING,
+                        top + row_index as f32 * ROW_SPACING,
+                    ),
+            ));
+        }
+    }
+
+    positions
+}
+
+/// Moves given states so their left sides are at the left side of the leftmost state.
+pub fn align_states_left(
+    layer: &MachineLayer,
+    states: &[Handle<State>],
+) -> Vec<(Handle<State>, Vector2<f32>)> {
+    let positions = fetch_positions(layer, states);
+    let left = positions
+        .iter()
+        .map(|(_, position)| position.x)
+        .fold(f32::INFINITY, f32::min);
+    positions
+        .into_iter()
+        .map(|(handle, position)| (handle, Vector2::new(left, position.y)))
+        .collect()
+}
+
+/// Moves given states so their top sides are at the top side of the topmost state.
+pub fn align_states_top(
+    layer: &MachineLayer,
+    states: &[Handle<State>],
+) -> Vec<(Handle<State>, Vector2<f32>)> {
+    let positions = fetch_positions(layer, states);
+    let top = positions
+        .iter()
+        .map(|(_, position)| position.y)
+        .fold(f32::INFINITY, f32::min);
+    positions
+        .into_iter()
+        .map(|(handle, position)| (handle, Vector2::new(position.x, top)))
+        .collect()
+}
+
+/// Spreads given states evenly between the leftmost and the rightmost ones, the order of the states
+/// along the horizontal axis is preserved.
+pub fn distribute_states_horizontally(
+    layer: &MachineLayer,
+    states: &[Handle<State>],
+) -> Vec<(Handle<State>, Vector2<f32>)> {
+    let mut positions = fetch_positions(layer, states);
+    if positions.len() < 3 {
+        return positions;
+    }
+
+    positions.sort_by(|(_, a), (_, b)| a.x.total_cmp(&b.x));
+
+    let left = positions[0].1.x;
+    let step = (positions[positions.len() - 1].1.x - left) / (positions.len() - 1) as f32;
+    for (i, (_, position)) in positions.iter_mut().enumerate() {
+        position.x = left + step * i as f32;
+    }
+
+    positions
+}
+
+fn fetch_positions(
+    layer: &MachineLayer,
+    states: &[Handle<State>],
+) -> Vec<(Handle<State>, Vector2<f32>)> {
+    states
+        .iter()
+        .filter_map(|handle| {
+            layer
+                .states()
+                .try_borrow(*handle)
+                .map(|state| (*handle, state.position))
+        })
+        .collect()
+}
+
+/// Creates a command that moves states of the layer to the positions calculated by [`arrange_states`].
+/// Returns `None` if every state is already at its place.
+pub fn make_arrange_states_command(
+    absm_node_handle: Handle<Node>,
+    layer_index: usize,
+    layer: &MachineLayer,
+) -> Option<CommandGroup> {
+    make_move_states_command(absm_node_handle, layer_index, layer, arrange_states(layer))
+}
+
+/// Creates a command that moves the states of the layer to the given positions. Returns `None` if every
+/// state is already at its place.
+pub fn make_move_states_command(
+    absm_node_handle: Handle<Node>,
+    layer_index: usize,
+    layer: &MachineLayer,
+    positions: Vec<(Handle<State>, Vector2<f32>)>,
+) -> Option<CommandGroup> {
+    let commands = positions
+        .into_iter()
+        .filter_map(|(handle, new_position)| {
+            let old_position = layer.states()[handle].position;
+            if old_position != new_position {
+                Some(SceneCommand::new(MoveStateNodeCommand::new(
+                    absm_node_handle,
+                    handle,
+                    layer_index,
+                    old_position,
+                    new_position,
+                )))
+            } else {
+                None
+            }
+        })
+        .collect::<Vec<_>>();
+
+    if commands.is_empty() {
+        None
+    } else {
+        Some(CommandGroup::from(commands))
+    }
+}
@@ -0,0 +1,208 @@
+use crate::absm::canvas::AbsmCanvasMessage;
+use fyrox::{
+    core::{
+        algebra::Vector2, color::Color, math::Rect, pool::Handle, reflect::prelude::*,
+        visitor::prelude::*,
+    },
+    gui::{
+        brush::Brush,
+        define_constructor, define_widget_deref,
+        draw::{CommandTexture, Draw, DrawingContext},
+        message::{MessageDirection, MouseButton, UiMessage},
+        widget::{Widget, WidgetBuilder, WidgetMessage},
+        BuildContext, Control, UiNode, UserInterface,
+    },
+};
+use std::{
+    any::{Any, TypeId},
+    ops::{Deref, DerefMut},
+};
+
+/// Free space around the content of the minimap (in minimap units).
+const PADDING: f32 = 4.0;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AbsmMinimapMessage {
+    /// Sets new bounds of the nodes of the canvas and the visible area of the canvas (both in local
+    /// coordinates of the canvas).
+    Sync {
+        nodes: Vec<Rect<f32>>,
+        view: Rect<f32>,
+    },
+}
+
+impl AbsmMinimapMessage {
+    define_constructor!(AbsmMinimapMessage:Sync => fn sync(nodes: Vec<Rect<f32>>, view: Rect<f32>), layout: false);
+}
+
+/// A scaled-down overview of an [`super::canvas::AbsmCanvas`]. Shows the bounds of every node of the canvas
+/// and the current visible area of it, clicking or dragging on the minimap moves the view of the canvas.
+#[derive(Clone, Visit, Reflect, Debug)]
+pub struct AbsmMinimap {
+    widget: Widget,
+    canvas: Handle<UiNode>,
+    nodes: Vec<Rect<f32>>,
+    view: Rect<f32>,
+    is_dragging: bool,
+}
+
+define_widget_deref!(AbsmMinimap);
+
+impl AbsmMinimap {
+    pub fn nodes(&self) -> &[Rect<f32>] {
+        &self.nodes
+    }
+
+    pub fn view(&self) -> Rect<f32> {
+        self.view
+    }
+
+    /// Returns bounds of the content in the canvas space and a scale that fits them into the minimap.
+    fn content_bounds(&self) -> (Rect<f32>, f32) {
+        let mut bounds = self.view;
+        for node in self.nodes.iter() {
+            bounds.extend_to_contain(*node);
+        }
+
+        let size = self.actual_local_size();
+        let scale = ((size.x - 2.0 * PADDING) / bounds.w().max(f32::EPSILON))
+            .min((size.y - 2.0 * PADDING) / bounds.h().max(f32::EPSILON))
+            .max(0.0);
+
+        (bounds, scale)
+    }
+
+    fn canvas_to_minimap(&self, bounds: &Rect<f32>, scale: f32, rect: &Rect<f32>) -> Rect<f32> {
+        Rect::new(
+            PADDING + (rect.x() - bounds.x()) * scale,
+            PADDING + (rect.y() - bounds.y()) * scale,
+            rect.w() * scale,
+            rect.h() * scale,
+        )
+    }
+
+    fn focus_canvas(&self, screen_position: Vector2<f32>, ui: &UserInterface) {
+        let (bounds, scale) = self.content_bounds();
+        if scale > 0.0 {
+            let local_position = self.screen_to_local(screen_position);
+            let canvas_position = bounds.left_top_corner()
+                + (local_position - Vector2::repeat(PADDING)).scale(1.0 / scale);
+
+            ui.send_message(AbsmCanvasMessage::focus_on(
+                self.canvas,
+                MessageDirection::ToWidget,
+                canvas_position,
+            ));
+        }
+    }
+}
+
+impl Control for AbsmMinimap {
+    fn query_component(&self, type_id: TypeId) -> Option<&dyn Any> {
+        if type_id == TypeId::of::<Self>() {
+            Some(self)
+        } else {
+            None
+        }
+    }
+
+    fn draw(&self, ctx: &mut DrawingContext) {
+        ctx.push_rect_filled(&self.bounding_rect(), None);
+        ctx.commit(
+            self.clip_bounds(),
+            self.widget.background(),
+            CommandTexture::None,
+            None,
+        );
+
+        let (bounds, scale) = self.content_bounds();
+
+        for node in self.nodes.iter() {
+            ctx.push_rect_filled(&self.canvas_to_minimap(&bounds, scale, node), None);
+        }
+        ctx.commit(
+            self.clip_bounds(),
+            Brush::Solid(Color::opaque(120, 120, 120)),
+            CommandTexture::None,
+            None,
+        );
+
+        ctx.push_rect(&self.canvas_to_minimap(&bounds, scale, &self.view), 1.0);
+        ctx.commit(
+            self.clip_bounds(),
+            self.widget.foreground(),
+            CommandTexture::None,
+            None,
+        );
+    }
+
+    fn handle_routed_message(&mut self, ui: &mut UserInterface, message: &mut UiMessage) {
+        self.widget.handle_routed_message(ui, message);
+
+        if let Some(msg) = message.data::<WidgetMessage>() {
+            if message.destination() == self.handle() {
+                match msg {
+                    WidgetMessage::MouseDown { pos, button } if *button == MouseButton::Left => {
+                        self.is_dragging = true;
+                        ui.capture_mouse(self.handle());
+                        self.focus_canvas(*pos, ui);
+                        message.set_handled(true);
+                    }
+                    WidgetMessage::MouseMove { pos, .. } if self.is_dragging => {
+                        self.focus_canvas(*pos, ui);
+                    }
+                    WidgetMessage::MouseUp { button, .. }
+                        if *button == MouseButton::Left && self.is_dragging =>
+                    {
+                        self.is_dragging = false;
+                        ui.release_mouse_capture();
+                        message.set_handled(true);
+                    }
+                    _ => (),
+                }
+            }
+        } else if let Some(AbsmMinimapMessage::Sync { nodes, view }) = message.data() {
+            if message.destination() == self.handle()
+                && message.direction() == MessageDirection::ToWidget
+            {
+                self.nodes = nodes.clone();
+                self.view = *view;
+            }
+        }
+    }
+}
+
+pub struct AbsmMinimapBuilder {
+    widget_builder: WidgetBuilder,
+    canvas: Handle<UiNode>,
+}
+
+impl AbsmMinimapBuilder {
+    pub fn new(widget_builder: WidgetBuilder) -> Self {
+        Self {
+            widget_builder,
+            canvas: Default::default(),
+        }
+    }
+
+    pub fn with_canvas(mut self, canvas: Handle<UiNode>) -> Self {
+        self.canvas = canvas;
+        self
+    }
+
+    pub fn build(self, ctx: &mut BuildContext) -> Handle<UiNode> {
+        let minimap = AbsmMinimap {
+            widget: self
+                .widget_builder
+                .with_background(Brush::Solid(Color::from_rgba(30, 30, 30, 200)))
+                .with_foreground(Brush::Solid(Color::opaque(220, 220, 220)))
+                .build(),
+            canvas: self.canvas,
+            nodes: Default::default(),
+            view: Default::default(),
+            is_dragging: false,
+        };
+
+        ctx.add_node(UiNode::new(minimap))
+    }
+}
@@ -1,9 +1,12 @@
 use crate::{
     absm::{
         blendspace::BlendSpaceEditor,
+        canvas::AbsmCanvasMessage,
+        clipboard::AbsmClipboard,
         command::blend::{AddBlendSpacePointCommand, AddInputCommand, AddPoseSourceCommand},
         node::{AbsmNode, AbsmNodeMessage},
         parameter::ParameterPanel,
+        preview::{PreviewAction, PreviewPanel},
         selection::AbsmSelection,
         state_graph::StateGraphViewer,
         state_viewer::StateViewer,
@@ -11,12 +14,16 @@ use crate::{
     },
     message::MessageSender,
     scene::{EditorScene, Selection},
+    settings::{absm::AbsmCanvasViewSettings, Settings},
     Message,
 };
 use fyrox::{
-    animation::machine::{
-        node::blendspace::BlendSpacePoint, BlendPose, Event, IndexedBlendInput, Machine, PoseNode,
-        State,
+    animation::{
+        machine::{
+            node::blendspace::BlendSpacePoint, BlendPose, Event, IndexedBlendInput, Machine,
+            ParameterContainer, PoseNode, State,
+        },
+        AnimationContainer,
     },
     core::{color::Color, pool::Handle},
     engine::Engine,
@@ -26,12 +33,14 @@ use fyrox::{
         dock::{DockingManagerBuilder, TileBuilder, TileContent},
         grid::{Column, GridBuilder, Row},
         message::{MessageDirection, UiMessage},
-        widget::WidgetBuilder,
+        numeric::NumericUpDownMessage,
+        widget::{WidgetBuilder, WidgetMessage},
         window::{WindowBuilder, WindowMessage, WindowTitle},
         BuildContext, UiNode, UserInterface,
     },
     scene::{
         animation::{absm::AnimationBlendingStateMachine, AnimationPlayer},
+        graph::Graph,
         node::Node,
         Scene,
     },
@@ -39,16 +48,21 @@ use fyrox::{
 
 mod blendspace;
 mod canvas;
+mod clipboard;
 pub mod command;
 mod connection;
+mod layout;
+mod minimap;
 mod node;
 mod parameter;
+mod preview;
 mod segment;
 mod selectable;
 pub mod selection;
 mod socket;
 mod state_graph;
 mod state_viewer;
+mod template;
 mod toolbar;
 mod transition;
 
@@ -57,10 +71,38 @@ const SELECTED_BACKGROUND: Color = Color::opaque(80, 80, 80);
 const BORDER_COLOR: Color = Color::opaque(70, 70, 70);
 const NORMAL_ROOT_COLOR: Color = Color::opaque(40, 80, 0);
 const SELECTED_ROOT_COLOR: Color = Color::opaque(60, 100, 0);
+const NORMAL_ANY_STATE_COLOR: Color = Color::opaque(30, 60, 100);
+const SELECTED_ANY_STATE_COLOR: Color = Color::opaque(50, 80, 120);
+const NORMAL_EXIT_STATE_COLOR: Color = Color::opaque(100, 30, 30);
+const SELECTED_EXIT_STATE_COLOR: Color = Color::opaque(120, 50, 50);
 
 struct PreviewModeData {
     machine: Machine,
+    animations: AnimationContainer,
     nodes: Vec<(Handle<Node>, Node)>,
+    speed: f32,
+    /// Values of the parameters that were shown in the parameter panel last time.
+    live_parameters: ParameterContainer,
+    /// Time (in seconds) since the start of the preview.
+    time: f32,
+    paused: bool,
+}
+
+/// Step (in seconds) that is used to replay the preview up to a selected time.
+const SEEK_STEP: f32 = 1.0 / 60.0;
+
+/// Sets speed of every animation to its original speed (before the preview mode) multiplied by the
+/// given scale.
+fn apply_preview_speed(
+    original_animations: &AnimationContainer,
+    animations: &mut AnimationContainer,
+    speed: f32,
+) {
+    for (handle, animation) in animations.pair_iter_mut() {
+        if let Some(original) = original_animations.try_get(handle) {
+            animation.set_speed(original.speed() * speed);
+        }
+    }
 }
 
 fn fetch_selection(editor_selection: &Selection) -> AbsmSelection {
@@ -96,15 +138,18 @@ pub struct AbsmEditor {
     prev_absm: Handle<Node>,
     toolbar: Toolbar,
     preview_mode_data: Option<PreviewModeData>,
+    preview_panel: PreviewPanel,
     blend_space_editor: BlendSpaceEditor,
+    clipboard: AbsmClipboard,
 }
 
 impl AbsmEditor {
-    pub fn new(ctx: &mut BuildContext, sender: MessageSender) -> Self {
-        let state_graph_viewer = StateGraphViewer::new(ctx);
-        let state_viewer = StateViewer::new(ctx);
+    pub fn new(ctx: &mut BuildContext, sender: MessageSender, settings: &Settings) -> Self {
+        let state_graph_viewer = StateGraphViewer::new(ctx, &settings.absm.state_graph_view);
+        let state_viewer = StateViewer::new(ctx, &settings.absm.state_viewer_view);
         let parameter_panel = ParameterPanel::new(ctx, sender);
         let blend_space_editor = BlendSpaceEditor::new(ctx);
+        let preview_panel = PreviewPanel::new(ctx);
 
         let docking_manager = DockingManagerBuilder::new(
             WidgetBuilder::new().on_row(1).with_child(
@@ -135,7 +180,7 @@ impl AbsmEditor {
                     .build(ctx),
             ),
         )
-        .with_floating_windows(vec![blend_space_editor.window])
+        .with_floating_windows(vec![blend_space_editor.window, preview_panel.window])
         .build(ctx);
 
         let toolbar = Toolbar::new(ctx);
@@ -169,25 +214,36 @@ impl AbsmEditor {
             prev_absm: Default::default(),
             toolbar,
             preview_mode_data: None,
+            preview_panel,
             blend_space_editor,
+            clipboard: Default::default(),
         }
     }
 
     fn enter_preview_mode(
         &mut self,
         machine: Machine,
+        animations: AnimationContainer,
         animation_targets: FxHashSet<Handle<Node>>,
         scene: &Scene,
-        ui: &UserInterface,
+        ui: &mut UserInterface,
         node_overrides: &mut FxHashSet<Handle<Node>>,
     ) {
         assert!(self.preview_mode_data.is_none());
 
+        self.preview_panel.open(ui, machine.parameters());
+
         ui.send_message(CheckBoxMessage::checked(
             self.toolbar.preview,
             MessageDirection::ToWidget,
             Some(true),
         ));
+        self.set_preview_controls_enabled(ui, true);
+        ui.send_message(NumericUpDownMessage::value(
+            self.toolbar.preview_speed,
+            MessageDirection::ToWidget,
+            1.0f32,
+        ));
 
         // Allow the engine to update the nodes affected by animations.
         for &target in &animation_targets {
@@ -197,13 +253,153 @@ impl AbsmEditor {
         // Save state of affected nodes.
         self.preview_mode_data = Some(PreviewModeData {
             machine,
+            animations,
             nodes: animation_targets
                 .into_iter()
                 .map(|t| (t, scene.graph[t].clone_box()))
                 .collect(),
+            speed: 1.0,
+            live_parameters: Default::default(),
+            time: 0.0,
+            paused: false,
         });
     }
 
+    /// Pauses or resumes the preview. Paused preview is excluded from the update of the scene, so the
+    /// machine and its animations stay exactly in the same state.
+    fn set_preview_paused(
+        &mut self,
+        ui: &UserInterface,
+        node_overrides: &mut FxHashSet<Handle<Node>>,
+        absm: Handle<Node>,
+        animation_player: Handle<Node>,
+        paused: bool,
+    ) {
+        if let Some(preview_data) = self.preview_mode_data.as_mut() {
+            if preview_data.paused != paused {
+                preview_data.paused = paused;
+
+                if paused {
+                    assert!(node_overrides.remove(&absm));
+                    assert!(node_overrides.remove(&animation_player));
+                } else {
+                    assert!(node_overrides.insert(absm));
+                    assert!(node_overrides.insert(animation_player));
+                }
+
+                self.preview_panel.set_paused(ui, paused);
+            }
+        }
+    }
+
+    /// Rewinds the preview and replays it up to the given time with fixed time step, the preview is paused
+    /// at the end, so it is possible to inspect the state of the machine at the given time.
+    fn seek_preview(&mut self, scene: &mut Scene, absm: Handle<Node>, time: f32) {
+        self.rewind_preview(scene, absm);
+
+        let Some(preview_data) = self.preview_mode_data.as_mut() else {
+            return;
+        };
+        preview_data.time = time;
+
+        let Some(absm_node) = scene
+            .graph
+            .try_get_mut_of_type::<AnimationBlendingStateMachine>(absm)
+        else {
+            return;
+        };
+        let mut machine = (**absm_node.machine()).clone();
+
+        let animation_player = absm_node.animation_player();
+        let Some(animation_player) = scene
+            .graph
+            .try_get_mut_of_type::<AnimationPlayer>(animation_player)
+        else {
+            return;
+        };
+        let animations = animation_player.animations_mut().get_value_mut_silent();
+
+        let mut elapsed = 0.0;
+        while elapsed < time {
+            let dt = (time - elapsed).min(SEEK_STEP);
+            for animation in animations.iter_mut().filter(|a| a.is_enabled()) {
+                animation.tick(dt);
+            }
+            machine.evaluate_pose(animations, dt);
+            elapsed += dt;
+        }
+        let pose = machine.evaluate_pose(animations, 0.0).clone();
+
+        if let Some(absm_node) = scene
+            .graph
+            .try_get_mut_of_type::<AnimationBlendingStateMachine>(absm)
+        {
+            *absm_node.machine_mut().get_value_mut_silent() = machine;
+        }
+
+        pose.apply(&mut scene.graph);
+    }
+
+    fn set_preview_controls_enabled(&self, ui: &UserInterface, enabled: bool) {
+        for widget in [self.toolbar.preview_speed, self.toolbar.rewind] {
+            ui.send_message(WidgetMessage::enabled(
+                widget,
+                MessageDirection::ToWidget,
+                enabled,
+            ));
+        }
+    }
+
+    fn set_preview_speed(&mut self, scene: &mut Scene, absm: Handle<Node>, speed: f32) {
+        if let Some(preview_data) = self.preview_mode_data.as_mut() {
+            preview_data.speed = speed;
+
+            if let Some(animation_player) = scene
+                .graph
+                .try_get_of_type::<AnimationBlendingStateMachine>(absm)
+                .map(|absm_node| absm_node.animation_player())
+                .and_then(|player| scene.graph.try_get_mut_of_type::<AnimationPlayer>(player))
+            {
+                apply_preview_speed(
+                    &preview_data.animations,
+                    animation_player.animations_mut().get_value_mut_silent(),
+                    speed,
+                );
+            }
+        }
+    }
+
+    /// Restarts the preview: the machine and the animations are reverted to the state they had when the
+    /// preview mode was entered, but current values of the parameters are kept, so it is possible to
+    /// check transitions from the entry states over and over again.
+    fn rewind_preview(&mut self, scene: &mut Scene, absm: Handle<Node>) {
+        if let Some(preview_data) = self.preview_mode_data.as_ref() {
+            for (handle, node) in preview_data.nodes.iter() {
+                scene.graph[*handle] = node.clone_box();
+            }
+
+            if let Some(absm_node) = scene
+                .graph
+                .try_get_mut_of_type::<AnimationBlendingStateMachine>(absm)
+            {
+                let machine = absm_node.machine_mut().get_value_mut_silent();
+                let parameters = machine.parameters().clone();
+                *machine = preview_data.machine.clone();
+                *machine.parameters_mut() = parameters;
+
+                let animation_player = absm_node.animation_player();
+                if let Some(animation_player) = scene
+                    .graph
+                    .try_get_mut_of_type::<AnimationPlayer>(animation_player)
+                {
+                    let animations = animation_player.animations_mut().get_value_mut_silent();
+                    *animations = preview_data.animations.clone();
+                    apply_preview_speed(&preview_data.animations, animations, preview_data.speed);
+                }
+            }
+        }
+    }
+
     fn leave_preview_mode(
         &mut self,
         scene: &mut Scene,
@@ -216,6 +412,8 @@ impl AbsmEditor {
             MessageDirection::ToWidget,
             Some(false),
         ));
+        self.set_preview_controls_enabled(ui, false);
+        self.preview_panel.close(ui);
 
         let preview_data = self
             .preview_mode_data
@@ -234,7 +432,20 @@ impl AbsmEditor {
 
         *absm_node.machine_mut().get_value_mut_silent() = preview_data.machine;
 
+        let animation_player = absm_node.animation_player();
+        if let Some(animation_player) = scene
+            .graph
+            .try_get_mut_of_type::<AnimationPlayer>(animation_player)
+        {
+            *animation_player.animations_mut().get_value_mut_silent() = preview_data.animations;
+        }
+
+        let absm_node = scene.graph[absm]
+            .query_component_mut::<AnimationBlendingStateMachine>()
+            .unwrap();
+
         self.parameter_panel.sync_to_model(ui, absm_node);
+        self.state_graph_viewer.reset_live_state(ui);
     }
 
     pub fn try_leave_preview_mode(&mut self, editor_scene: &mut EditorScene, engine: &mut Engine) {
@@ -249,6 +460,13 @@ impl AbsmEditor {
                 .and_then(|n| n.query_component_mut::<AnimationBlendingStateMachine>())
             {
                 let node_overrides = editor_scene.graph_switches.node_overrides.as_mut().unwrap();
+                self.set_preview_paused(
+                    &engine.user_interface,
+                    node_overrides,
+                    selection.absm_node_handle,
+                    absm.animation_player(),
+                    false,
+                );
                 assert!(node_overrides.remove(&selection.absm_node_handle));
                 assert!(node_overrides.remove(&absm.animation_player()));
 
@@ -262,6 +480,32 @@ impl AbsmEditor {
         }
     }
 
+    pub fn copy_selection(&mut self, selection: &AbsmSelection, graph: &Graph) {
+        if let Some(layer) = graph
+            .try_get_of_type::<AnimationBlendingStateMachine>(selection.absm_node_handle)
+            .zip(selection.layer)
+            .and_then(|(absm, layer_index)| absm.machine().layers().get(layer_index))
+        {
+            self.clipboard.fill_from_selection(selection, layer);
+        }
+    }
+
+    /// Pastes copied states at the cursor position on the state graph canvas.
+    pub fn paste(&self, selection: &AbsmSelection, ui: &UserInterface, sender: &MessageSender) {
+        if self.clipboard.is_empty() || self.is_in_preview_mode() {
+            return;
+        }
+
+        if let Some(layer_index) = selection.layer {
+            let offset = self.state_graph_viewer.cursor_position(ui) - self.clipboard.origin();
+            sender.do_scene_command(self.clipboard.make_paste_command(
+                selection.absm_node_handle,
+                layer_index,
+                offset,
+            ));
+        }
+    }
+
     pub fn is_in_preview_mode(&self) -> bool {
         self.preview_mode_data.is_some()
     }
@@ -295,6 +539,7 @@ impl AbsmEditor {
 
         if selection.absm_node_handle != prev_absm {
             self.parameter_panel.on_selection_changed(ui, absm_node);
+            self.toolbar.on_absm_changed();
             self.prev_absm = selection.absm_node_handle;
         }
 
@@ -340,8 +585,54 @@ impl AbsmEditor {
         ));
     }
 
-    pub fn update(&mut self, editor_scene: &EditorScene, engine: &mut Engine) {
+    pub fn update(&mut self, editor_scene: &EditorScene, engine: &mut Engine, dt: f32) {
+        if let Some(preview_data) = self.preview_mode_data.as_mut() {
+            if !preview_data.paused {
+                preview_data.time += dt;
+            }
+        }
+
         self.handle_machine_events(editor_scene, engine);
+        self.sync_live_state(editor_scene, engine);
+        self.state_graph_viewer
+            .update_minimap(&engine.user_interface);
+    }
+
+    /// Shows the live state of the running machine (active state, progress of active transition and values
+    /// of the parameters) while the preview mode is active.
+    fn sync_live_state(&mut self, editor_scene: &EditorScene, engine: &mut Engine) {
+        let preview_data = match self.preview_mode_data.as_mut() {
+            Some(preview_data) => preview_data,
+            None => return,
+        };
+
+        let scene = &engine.scenes[editor_scene.scene];
+        let ui = &mut engine.user_interface;
+        let selection = fetch_selection(&editor_scene.selection);
+
+        if let Some(absm) = scene
+            .graph
+            .try_get_of_type::<AnimationBlendingStateMachine>(selection.absm_node_handle)
+        {
+            let machine = absm.machine();
+
+            if let Some(layer) = selection
+                .layer
+                .and_then(|layer_index| machine.layers().get(layer_index))
+            {
+                self.state_graph_viewer.sync_live_state(ui, layer);
+            }
+
+            if &preview_data.live_parameters != machine.parameters() {
+                preview_data.live_parameters = machine.parameters().clone();
+                self.parameter_panel.sync_to_model(ui, absm);
+                self.preview_panel
+                    .sync_to_model(ui, preview_data.time, Some(machine.parameters()));
+            } else {
+                self.preview_panel
+                    .sync_to_model(ui, preview_data.time, None);
+            }
+        }
     }
 
     pub fn handle_machine_events(&self, editor_scene: &EditorScene, engine: &mut Engine) {
@@ -381,11 +672,34 @@ impl AbsmEditor {
         engine: &mut Engine,
         sender: &MessageSender,
         editor_scene: &mut EditorScene,
+        settings: &mut Settings,
     ) {
         let scene = &mut engine.scenes[editor_scene.scene];
         let ui = &mut engine.user_interface;
         let selection = fetch_selection(&editor_scene.selection);
 
+        // Remember the views of the canvases, so they will be restored on next run of the editor.
+        if let Some(AbsmCanvasMessage::View { position, zoom }) = message.data() {
+            let is_state_graph = message.destination() == self.state_graph_viewer.canvas;
+            let is_state_viewer = message.destination() == self.state_viewer.canvas;
+
+            if message.direction() == MessageDirection::FromWidget
+                && (is_state_graph || is_state_viewer)
+            {
+                let new_view = AbsmCanvasViewSettings {
+                    position: *position,
+                    zoom: *zoom,
+                };
+
+                // Settings are saved on every mutable access, so change them only if needed.
+                if is_state_graph && settings.absm.state_graph_view != new_view {
+                    settings.absm.state_graph_view = new_view;
+                } else if is_state_viewer && settings.absm.state_viewer_view != new_view {
+                    settings.absm.state_viewer_view = new_view;
+                }
+            }
+        }
+
         if let Some(absm_node) = scene
             .graph
             .try_get_mut(selection.absm_node_handle)
@@ -436,6 +750,7 @@ impl AbsmEditor {
                 .try_get_mut(selection.absm_node_handle)
                 .and_then(|n| n.query_component_mut::<AnimationBlendingStateMachine>())
                 .unwrap();
+            let animation_player = absm_node.animation_player();
 
             match action {
                 ToolbarAction::None => {}
@@ -454,6 +769,8 @@ impl AbsmEditor {
                         .try_get_mut(animation_player)
                         .and_then(|n| n.query_component_mut::<AnimationPlayer>())
                     {
+                        let animations = (**animation_player.animations()).clone();
+
                         let mut animation_targets = FxHashSet::default();
                         for animation in animation_player.animations_mut().iter_mut() {
                             for track in animation.tracks() {
@@ -463,6 +780,7 @@ impl AbsmEditor {
 
                         self.enter_preview_mode(
                             machine,
+                            animations,
                             animation_targets,
                             scene,
                             ui,
@@ -474,6 +792,13 @@ impl AbsmEditor {
                     if self.preview_mode_data.is_some() {
                         let node_overrides =
                             editor_scene.graph_switches.node_overrides.as_mut().unwrap();
+                        self.set_preview_paused(
+                            ui,
+                            node_overrides,
+                            selection.absm_node_handle,
+                            absm_node.animation_player(),
+                            false,
+                        );
                         assert!(node_overrides.remove(&selection.absm_node_handle));
                         assert!(node_overrides.remove(&absm_node.animation_player()));
 
@@ -485,6 +810,59 @@ impl AbsmEditor {
                         );
                     }
                 }
+                ToolbarAction::SetPreviewSpeed(speed) => {
+                    self.set_preview_speed(scene, selection.absm_node_handle, speed);
+                }
+                ToolbarAction::RewindPreview => {
+                    self.rewind_preview(scene, selection.absm_node_handle);
+                }
+                ToolbarAction::SearchStates(text) => {
+                    self.state_graph_viewer.set_search_text(ui, &text);
+                }
+                ToolbarAction::FocusFoundState => {
+                    self.state_graph_viewer.focus_found_state(ui);
+                }
+            }
+
+            match self.preview_panel.handle_ui_message(message) {
+                PreviewAction::None => {}
+                PreviewAction::Seek(time) => {
+                    let node_overrides =
+                        editor_scene.graph_switches.node_overrides.as_mut().unwrap();
+                    self.set_preview_paused(
+                        ui,
+                        node_overrides,
+                        selection.absm_node_handle,
+                        animation_player,
+                        true,
+                    );
+                    self.seek_preview(scene, selection.absm_node_handle, time);
+                }
+                PreviewAction::SetPaused(paused) => {
+                    let node_overrides =
+                        editor_scene.graph_switches.node_overrides.as_mut().unwrap();
+                    self.set_preview_paused(
+                        ui,
+                        node_overrides,
+                        selection.absm_node_handle,
+                        animation_player,
+                        paused,
+                    );
+                }
+                PreviewAction::SetParameter { name, value } => {
+                    if let Some(absm_node) = scene
+                        .graph
+                        .try_get_mut_of_type::<AnimationBlendingStateMachine>(
+                            selection.absm_node_handle,
+                        )
+                        .filter(|_| self.preview_mode_data.is_some())
+                    {
+                        absm_node
+                            .machine_mut()
+                            .get_value_mut_silent()
+                            .set_parameter(&name, value);
+                    }
+                }
             }
         }
 
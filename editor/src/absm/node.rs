@@ -10,9 +10,10 @@ use fyrox::{
         button::{ButtonBuilder, ButtonMessage},
         define_constructor,
         grid::{Column, GridBuilder, Row},
-        message::{MessageDirection, MouseButton, UiMessage},
+        message::{KeyCode, MessageDirection, MouseButton, UiMessage},
         stack_panel::StackPanelBuilder,
         text::{TextBuilder, TextMessage},
+        text_box::TextBoxBuilder,
         widget::{Widget, WidgetBuilder, WidgetMessage},
         BuildContext, Control, HorizontalAlignment, Thickness, UiNode, UserInterface,
         VerticalAlignment,
@@ -46,6 +47,9 @@ where
     normal_color: Color,
     selected_color: Color,
     name: Handle<UiNode>,
+    name_editor: Handle<UiNode>,
+    renamable: bool,
+    is_renaming: bool,
     edit: Handle<UiNode>,
 }
 
@@ -72,6 +76,9 @@ where
             normal_color: self.normal_color,
             selected_color: self.selected_color,
             name: self.name,
+            name_editor: self.name_editor,
+            renamable: self.renamable,
+            is_renaming: self.is_renaming,
             edit: self.edit,
         }
     }
@@ -112,10 +119,55 @@ where
             }),
         ));
     }
+
+    fn begin_renaming(&mut self, ui: &UserInterface) {
+        self.is_renaming = true;
+
+        ui.send_message(TextMessage::text(
+            self.name_editor,
+            MessageDirection::ToWidget,
+            self.name_value.clone(),
+        ));
+        ui.send_message(WidgetMessage::visibility(
+            self.name,
+            MessageDirection::ToWidget,
+            false,
+        ));
+        ui.send_message(WidgetMessage::visibility(
+            self.name_editor,
+            MessageDirection::ToWidget,
+            true,
+        ));
+        ui.send_message(WidgetMessage::focus(
+            self.name_editor,
+            MessageDirection::ToWidget,
+        ));
+    }
+
+    fn end_renaming(&mut self, ui: &UserInterface) {
+        self.is_renaming = false;
+
+        ui.send_message(WidgetMessage::visibility(
+            self.name,
+            MessageDirection::ToWidget,
+            true,
+        ));
+        ui.send_message(WidgetMessage::visibility(
+            self.name_editor,
+            MessageDirection::ToWidget,
+            false,
+        ));
+        // Move the focus out of the hidden text box, so it won't eat keyboard input.
+        ui.send_message(WidgetMessage::focus(
+            self.handle(),
+            MessageDirection::ToWidget,
+        ));
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum AbsmNodeMessage {
+    /// When sent to a node, changes its name. A node sends this message back when a user renamed it.
     Name(String),
     Enter,
     AddInput,
@@ -171,11 +223,38 @@ where
                 }
             }
         } else if let Some(WidgetMessage::DoubleClick { button }) = message.data() {
-            if !message.handled() && *button == MouseButton::Left {
-                ui.send_message(AbsmNodeMessage::enter(
-                    self.handle(),
-                    MessageDirection::FromWidget,
-                ));
+            if !message.handled()
+                && *button == MouseButton::Left
+                && message.destination() != self.name_editor
+            {
+                if self.renamable && message.destination() == self.name {
+                    self.begin_renaming(ui);
+                } else {
+                    ui.send_message(AbsmNodeMessage::enter(
+                        self.handle(),
+                        MessageDirection::FromWidget,
+                    ));
+                }
+            }
+        } else if let Some(TextMessage::Text(text)) = message.data() {
+            if message.destination() == self.name_editor
+                && message.direction() == MessageDirection::FromWidget
+                && self.is_renaming
+            {
+                self.end_renaming(ui);
+
+                let text = text.trim();
+                if !text.is_empty() && text != self.name_value {
+                    ui.send_message(AbsmNodeMessage::name(
+                        self.handle(),
+                        MessageDirection::FromWidget,
+                        text.to_string(),
+                    ));
+                }
+            }
+        } else if let Some(WidgetMessage::KeyDown(KeyCode::Escape)) = message.data() {
+            if message.destination() == self.name_editor && self.is_renaming {
+                self.end_renaming(ui);
             }
         } else if let Some(ButtonMessage::Click) = message.data() {
             if message.destination() == self.add_input {
@@ -276,6 +355,7 @@ where
     normal_color: Color,
     selected_color: Color,
     editable: bool,
+    renamable: bool,
 }
 
 impl<T> AbsmNodeBuilder<T>
@@ -294,6 +374,7 @@ where
             normal_color: NORMAL_BACKGROUND,
             selected_color: SELECTED_BACKGROUND,
             editable: false,
+            renamable: false,
         }
     }
 
@@ -342,10 +423,17 @@ where
         self
     }
 
+    /// Allows a user to rename the node by double-clicking on its name.
+    pub fn with_renamable(mut self, renamable: bool) -> Self {
+        self.renamable = renamable;
+        self
+    }
+
     pub fn build(self, ctx: &mut BuildContext) -> Handle<UiNode> {
         let input_sockets_panel;
         let add_input;
         let name;
+        let name_editor;
         let mut edit = Handle::NONE;
         let grid = GridBuilder::new(
             WidgetBuilder::new()
@@ -390,16 +478,47 @@ where
                     StackPanelBuilder::new(
                         WidgetBuilder::new()
                             .on_column(1)
-                            .with_child({
-                                name = TextBuilder::new(
-                                    WidgetBuilder::new().with_width(150.0).with_height(75.0),
+                            .with_child(
+                                GridBuilder::new(
+                                    WidgetBuilder::new()
+                                        .with_width(150.0)
+                                        .with_height(75.0)
+                                        .with_child({
+                                            name = TextBuilder::new(
+                                                WidgetBuilder::new()
+                                                    .with_vertical_alignment(
+                                                        VerticalAlignment::Center,
+                                                    )
+                                                    .with_horizontal_alignment(
+                                                        HorizontalAlignment::Center,
+                                                    ),
+                                            )
+                                            .with_text(format!(
+                                                "{} ({})",
+                                                self.name, self.model_handle
+                                            ))
+                                            .build(ctx);
+                                            name
+                                        })
+                                        .with_child({
+                                            name_editor = TextBoxBuilder::new(
+                                                WidgetBuilder::new()
+                                                    .with_visibility(false)
+                                                    .with_margin(Thickness::uniform(4.0))
+                                                    .with_height(22.0)
+                                                    .with_vertical_alignment(
+                                                        VerticalAlignment::Center,
+                                                    ),
+                                            )
+                                            .with_vertical_text_alignment(VerticalAlignment::Center)
+                                            .build(ctx);
+                                            name_editor
+                                        }),
                                 )
-                                .with_vertical_text_alignment(VerticalAlignment::Center)
-                                .with_horizontal_text_alignment(HorizontalAlignment::Center)
-                                .with_text(format!("{} ({})", self.name, self.model_handle))
-                                .build(ctx);
-                                name
-                            })
+                                .add_row(Row::stretch())
+                                .add_column(Column::stretch())
+                                .build(ctx),
+                            )
                             .with_child(if self.editable {
                                 edit = ButtonBuilder::new(
                                     WidgetBuilder::new().with_margin(Thickness::uniform(1.0)),
@@ -484,6 +603,9 @@ where
             normal_color: self.normal_color,
             selected_color: self.selected_color,
             name,
+            name_editor,
+            renamable: self.renamable,
+            is_renaming: false,
             edit,
         };
 
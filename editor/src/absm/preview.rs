@@ -0,0 +1,397 @@
+use crate::send_sync_message;
+use fyrox::{
+    animation::machine::{Parameter, ParameterContainer},
+    core::{algebra::Vector2, pool::Handle},
+    gui::{
+        button::{ButtonBuilder, ButtonMessage},
+        check_box::{CheckBoxBuilder, CheckBoxMessage},
+        grid::{Column, GridBuilder, Row},
+        message::{MessageDirection, UiMessage},
+        numeric::{NumericUpDownBuilder, NumericUpDownMessage},
+        scroll_bar::{ScrollBarBuilder, ScrollBarMessage},
+        scroll_viewer::ScrollViewerBuilder,
+        stack_panel::StackPanelBuilder,
+        text::TextBuilder,
+        utils::make_simple_tooltip,
+        vec::{Vec2EditorBuilder, Vec2EditorMessage},
+        widget::{WidgetBuilder, WidgetMessage},
+        window::{WindowBuilder, WindowMessage, WindowTitle},
+        BuildContext, Orientation, Thickness, UiNode, UserInterface, VerticalAlignment,
+    },
+};
+
+/// Initial length (in seconds) of the time line of the preview, it grows if the preview runs longer.
+const TIMELINE_LENGTH: f32 = 10.0;
+
+pub enum PreviewAction {
+    None,
+    Seek(f32),
+    SetPaused(bool),
+    SetParameter { name: String, value: Parameter },
+}
+
+/// A widget that controls a single parameter of the machine.
+enum ParameterEditor {
+    Weight(Handle<UiNode>),
+    Rule(Handle<UiNode>),
+    Trigger(Handle<UiNode>),
+    Index(Handle<UiNode>),
+    SamplingPoint(Handle<UiNode>),
+}
+
+impl ParameterEditor {
+    fn handle(&self) -> Handle<UiNode> {
+        match self {
+            ParameterEditor::Weight(handle)
+            | ParameterEditor::Rule(handle)
+            | ParameterEditor::Trigger(handle)
+            | ParameterEditor::Index(handle)
+            | ParameterEditor::SamplingPoint(handle) => *handle,
+        }
+    }
+}
+
+/// Preview controls of the ABSM editor: a time scrubber and sliders/toggles for every parameter of the
+/// machine, the changes are applied to the running machine immediately.
+pub struct PreviewPanel {
+    pub window: Handle<UiNode>,
+    time: Handle<UiNode>,
+    pause: Handle<UiNode>,
+    parameters_panel: Handle<UiNode>,
+    parameters: Vec<(String, ParameterEditor)>,
+    timeline_length: f32,
+}
+
+fn make_parameter_editor(value: &Parameter, ctx: &mut BuildContext) -> ParameterEditor {
+    let widget_builder = || {
+        WidgetBuilder::new()
+            .on_column(1)
+            .with_margin(Thickness::uniform(1.0))
+    };
+    match value {
+        Parameter::Weight(weight) => ParameterEditor::Weight(
+            ScrollBarBuilder::new(widget_builder().with_height(20.0))
+                .with_orientation(Orientation::Horizontal)
+                .with_min(weight.min(0.0))
+                .with_max(weight.max(1.0))
+                .with_step(0.01)
+                .with_value(*weight)
+                .show_value(true)
+                .with_value_precision(2)
+                .build(ctx),
+        ),
+        Parameter::Rule(rule) => ParameterEditor::Rule(
+            CheckBoxBuilder::new(widget_builder())
+                .checked(Some(*rule))
+                .build(ctx),
+        ),
+        Parameter::Trigger(_) => ParameterEditor::Trigger(
+            ButtonBuilder::new(widget_builder().with_tooltip(make_simple_tooltip(
+                ctx,
+                "Sets the trigger, it will be reset when a transition that uses it is activated.",
+            )))
+            .with_text("Fire")
+            .build(ctx),
+        ),
+        Parameter::Index(index) => ParameterEditor::Index(
+            NumericUpDownBuilder::<u32>::new(widget_builder())
+                .with_value(*index)
+                .build(ctx),
+        ),
+        Parameter::SamplingPoint(point) => ParameterEditor::SamplingPoint(
+            Vec2EditorBuilder::<f32>::new(widget_builder())
+                .with_value(*point)
+                .build(ctx),
+        ),
+    }
+}
+
+impl PreviewPanel {
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        let time;
+        let pause;
+        let parameters_panel;
+        let content = GridBuilder::new(
+            WidgetBuilder::new()
+                .with_child(
+                    GridBuilder::new(
+                        WidgetBuilder::new()
+                            .with_child(
+                                TextBuilder::new(WidgetBuilder::new())
+                                    .with_text("Time")
+                                    .with_vertical_text_alignment(VerticalAlignment::Center)
+                                    .build(ctx),
+                            )
+                            .with_child({
+                                time = ScrollBarBuilder::new(
+                                    WidgetBuilder::new()
+                                        .on_column(1)
+                                        .with_height(20.0)
+                                        .with_margin(Thickness::uniform(1.0))
+                                        .with_tooltip(make_simple_tooltip(
+                                            ctx,
+                                            "Time since the start of the preview. Drag it to \
+                                            replay the preview up to the selected time.",
+                                        )),
+                                )
+                                .with_orientation(Orientation::Horizontal)
+                                .with_min(0.0)
+                                .with_max(TIMELINE_LENGTH)
+                                .with_step(0.1)
+                                .with_value(0.0)
+                                .show_value(true)
+                                .with_value_precision(2)
+                                .build(ctx);
+                                time
+                            })
+                            .with_child({
+                                pause = CheckBoxBuilder::new(
+                                    WidgetBuilder::new()
+                                        .on_column(2)
+                                        .with_margin(Thickness::uniform(1.0))
+                                        .with_tooltip(make_simple_tooltip(
+                                            ctx,
+                                            "Pause the preview.",
+                                        )),
+                                )
+                                .with_content(
+                                    TextBuilder::new(WidgetBuilder::new())
+                                        .with_text("Pause")
+                                        .with_vertical_text_alignment(VerticalAlignment::Center)
+                                        .build(ctx),
+                                )
+                                .checked(Some(false))
+                                .build(ctx);
+                                pause
+                            }),
+                    )
+                    .add_row(Row::strict(24.0))
+                    .add_column(Column::strict(100.0))
+                    .add_column(Column::stretch())
+                    .add_column(Column::auto())
+                    .build(ctx),
+                )
+                .with_child(
+                    ScrollViewerBuilder::new(WidgetBuilder::new().on_row(1))
+                        .with_content({
+                            parameters_panel =
+                                StackPanelBuilder::new(WidgetBuilder::new()).build(ctx);
+                            parameters_panel
+                        })
+                        .build(ctx),
+                ),
+        )
+        .add_row(Row::strict(26.0))
+        .add_row(Row::stretch())
+        .add_column(Column::stretch())
+        .build(ctx);
+
+        let window = WindowBuilder::new(WidgetBuilder::new().with_width(350.0).with_height(250.0))
+            .open(false)
+            .with_content(content)
+            .with_title(WindowTitle::text("Preview"))
+            .build(ctx);
+
+        Self {
+            window,
+            time,
+            pause,
+            parameters_panel,
+            parameters: Default::default(),
+            timeline_length: TIMELINE_LENGTH,
+        }
+    }
+
+    pub fn open(&mut self, ui: &mut UserInterface, parameters: &ParameterContainer) {
+        self.timeline_length = TIMELINE_LENGTH;
+        send_sync_message(
+            ui,
+            ScrollBarMessage::max_value(self.time, MessageDirection::ToWidget, TIMELINE_LENGTH),
+        );
+        send_sync_message(
+            ui,
+            ScrollBarMessage::value(self.time, MessageDirection::ToWidget, 0.0),
+        );
+        send_sync_message(
+            ui,
+            CheckBoxMessage::checked(self.pause, MessageDirection::ToWidget, Some(false)),
+        );
+        self.rebuild_parameters(ui, parameters);
+
+        ui.send_message(WindowMessage::open(
+            self.window,
+            MessageDirection::ToWidget,
+            true,
+        ));
+    }
+
+    pub fn close(&mut self, ui: &UserInterface) {
+        ui.send_message(WindowMessage::close(
+            self.window,
+            MessageDirection::ToWidget,
+        ));
+    }
+
+    fn rebuild_parameters(&mut self, ui: &mut UserInterface, parameters: &ParameterContainer) {
+        for (_, editor) in self.parameters.drain(..) {
+            ui.send_message(WidgetMessage::remove(
+                editor.handle(),
+                MessageDirection::ToWidget,
+            ));
+        }
+        for &row in ui.node(self.parameters_panel).children() {
+            ui.send_message(WidgetMessage::remove(row, MessageDirection::ToWidget));
+        }
+
+        for definition in parameters.iter() {
+            let ctx = &mut ui.build_ctx();
+            let editor = make_parameter_editor(&definition.value, ctx);
+            let row = GridBuilder::new(
+                WidgetBuilder::new()
+                    .with_child(
+                        TextBuilder::new(WidgetBuilder::new().with_margin(Thickness::left(2.0)))
+                            .with_text(&definition.name)
+                            .with_vertical_text_alignment(VerticalAlignment::Center)
+                            .build(ctx),
+                    )
+                    .with_child(editor.handle()),
+            )
+            .add_row(Row::strict(24.0))
+            .add_column(Column::strict(100.0))
+            .add_column(Column::stretch())
+            .build(ctx);
+            ui.send_message(WidgetMessage::link(
+                row,
+                MessageDirection::ToWidget,
+                self.parameters_panel,
+            ));
+            self.parameters.push((definition.name.clone(), editor));
+        }
+    }
+
+    /// Syncs the controls with the live state of the preview.
+    pub fn sync_to_model(
+        &mut self,
+        ui: &mut UserInterface,
+        time: f32,
+        parameters: Option<&ParameterContainer>,
+    ) {
+        if time > self.timeline_length {
+            self.timeline_length = time.ceil();
+            send_sync_message(
+                ui,
+                ScrollBarMessage::max_value(
+                    self.time,
+                    MessageDirection::ToWidget,
+                    self.timeline_length,
+                ),
+            );
+        }
+        send_sync_message(
+            ui,
+            ScrollBarMessage::value(self.time, MessageDirection::ToWidget, time),
+        );
+
+        let Some(parameters) = parameters else {
+            return;
+        };
+
+        if parameters.iter().count() != self.parameters.len()
+            || parameters
+                .iter()
+                .zip(self.parameters.iter())
+                .any(|(definition, (name, _))| &definition.name != name)
+        {
+            self.rebuild_parameters(ui, parameters);
+            return;
+        }
+
+        for (definition, (_, editor)) in parameters.iter().zip(self.parameters.iter()) {
+            let message = match (&definition.value, editor) {
+                (Parameter::Weight(weight), ParameterEditor::Weight(handle)) => {
+                    ScrollBarMessage::value(*handle, MessageDirection::ToWidget, *weight)
+                }
+                (Parameter::Rule(rule), ParameterEditor::Rule(handle)) => {
+                    CheckBoxMessage::checked(*handle, MessageDirection::ToWidget, Some(*rule))
+                }
+                (Parameter::Trigger(_), ParameterEditor::Trigger(_)) => continue,
+                (Parameter::Index(index), ParameterEditor::Index(handle)) => {
+                    NumericUpDownMessage::value(*handle, MessageDirection::ToWidget, *index)
+                }
+                (Parameter::SamplingPoint(point), ParameterEditor::SamplingPoint(handle)) => {
+                    Vec2EditorMessage::value(*handle, MessageDirection::ToWidget, *point)
+                }
+                // Kind of the parameter was changed.
+                _ => {
+                    self.rebuild_parameters(ui, parameters);
+                    return;
+                }
+            };
+            send_sync_message(ui, message);
+        }
+    }
+
+    pub fn handle_ui_message(&mut self, message: &UiMessage) -> PreviewAction {
+        if message.direction() != MessageDirection::FromWidget
+            || message.flags == crate::MSG_SYNC_FLAG
+        {
+            return PreviewAction::None;
+        }
+
+        if message.destination() == self.time {
+            if let Some(ScrollBarMessage::Value(time)) = message.data() {
+                return PreviewAction::Seek(*time);
+            }
+        } else if message.destination() == self.pause {
+            if let Some(CheckBoxMessage::Check(Some(paused))) = message.data() {
+                return PreviewAction::SetPaused(*paused);
+            }
+        }
+
+        for (name, editor) in self.parameters.iter() {
+            if message.destination() != editor.handle() {
+                continue;
+            }
+
+            let value = match editor {
+                ParameterEditor::Weight(_) => match message.data() {
+                    Some(ScrollBarMessage::Value(weight)) => Parameter::Weight(*weight),
+                    _ => continue,
+                },
+                ParameterEditor::Rule(_) => match message.data() {
+                    Some(CheckBoxMessage::Check(Some(rule))) => Parameter::Rule(*rule),
+                    _ => continue,
+                },
+                ParameterEditor::Trigger(_) => match message.data() {
+                    Some(ButtonMessage::Click) => Parameter::Trigger(true),
+                    _ => continue,
+                },
+                ParameterEditor::Index(_) => match message.data() {
+                    Some(NumericUpDownMessage::<u32>::Value(index)) => Parameter::Index(*index),
+                    _ => continue,
+                },
+                ParameterEditor::SamplingPoint(_) => match message.data() {
+                    Some(Vec2EditorMessage::<f32>::Value(point)) => {
+                        Parameter::SamplingPoint(Vector2::new(point.x, point.y))
+                    }
+                    _ => continue,
+                },
+            };
+
+            return PreviewAction::SetParameter {
+                name: name.clone(),
+                value,
+            };
+        }
+
+        PreviewAction::None
+    }
+
+    /// Sets the state of the pause check box without emitting a message back.
+    pub fn set_paused(&self, ui: &UserInterface, paused: bool) {
+        send_sync_message(
+            ui,
+            CheckBoxMessage::checked(self.pause, MessageDirection::ToWidget, Some(paused)),
+        );
+    }
+}
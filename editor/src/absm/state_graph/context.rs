@@ -1,9 +1,15 @@
 use crate::{
     absm::{
         canvas::{AbsmCanvasMessage, Mode},
+        clipboard::AbsmClipboard,
         command::{
-            AddStateCommand, DeleteStateCommand, DeleteTransitionCommand,
-            SetMachineEntryStateCommand,
+            make_delete_selection_command, AddStateCommand, DeleteTransitionCommand,
+            SetMachineAnyStateCommand, SetMachineEntryStateCommand, SetMachineExitStateCommand,
+            SetTransitionReroutePointsCommand,
+        },
+        layout::{
+            align_states_left, align_states_top, distribute_states_horizontally,
+            make_arrange_states_command, make_move_states_command,
         },
         node::{AbsmNode, AbsmNodeMessage},
         selection::SelectedEntity,
@@ -18,7 +24,7 @@ use crate::{
 };
 use fyrox::{
     animation::machine::State,
-    core::pool::Handle,
+    core::{algebra::Vector2, math::Rect, pool::Handle},
     gui::{
         menu::MenuItemMessage,
         message::{MessageDirection, UiMessage},
@@ -30,8 +36,13 @@ use fyrox::{
     scene::{animation::absm::AnimationBlendingStateMachine, node::Node},
 };
 
+/// An offset of duplicated states relative to the original ones.
+const DUPLICATE_OFFSET: f32 = 20.0;
+
 pub struct CanvasContextMenu {
     create_state: Handle<UiNode>,
+    arrange_states: Handle<UiNode>,
+    zoom_to_fit: Handle<UiNode>,
     pub menu: RcUiNodeHandle,
     pub canvas: Handle<UiNode>,
     pub node_context_menu: Option<RcUiNodeHandle>,
@@ -40,12 +51,25 @@ pub struct CanvasContextMenu {
 impl CanvasContextMenu {
     pub fn new(ctx: &mut BuildContext) -> Self {
         let create_state;
+        let arrange_states;
+        let zoom_to_fit;
         let menu = PopupBuilder::new(WidgetBuilder::new().with_visibility(false))
             .with_content(
-                StackPanelBuilder::new(WidgetBuilder::new().with_child({
-                    create_state = create_menu_item("Create State", vec![], ctx);
-                    create_state
-                }))
+                StackPanelBuilder::new(
+                    WidgetBuilder::new()
+                        .with_child({
+                            create_state = create_menu_item("Create State", vec![], ctx);
+                            create_state
+                        })
+                        .with_child({
+                            arrange_states = create_menu_item("Arrange States", vec![], ctx);
+                            arrange_states
+                        })
+                        .with_child({
+                            zoom_to_fit = create_menu_item("Zoom To Fit", vec![], ctx);
+                            zoom_to_fit
+                        }),
+                )
                 .build(ctx),
             )
             .build(ctx);
@@ -53,6 +77,8 @@ impl CanvasContextMenu {
 
         Self {
             create_state,
+            arrange_states,
+            zoom_to_fit,
             menu,
             canvas: Default::default(),
             node_context_menu: Default::default(),
@@ -65,10 +91,24 @@ impl CanvasContextMenu {
         message: &UiMessage,
         ui: &mut UserInterface,
         absm_node_handle: Handle<Node>,
+        absm_node: &AnimationBlendingStateMachine,
         layer_index: usize,
     ) {
         if let Some(MenuItemMessage::Click) = message.data() {
-            if message.destination() == self.create_state {
+            if message.destination() == self.arrange_states {
+                if let Some(command) = make_arrange_states_command(
+                    absm_node_handle,
+                    layer_index,
+                    &absm_node.machine().layers()[layer_index],
+                ) {
+                    sender.do_scene_command(command);
+                }
+            } else if message.destination() == self.zoom_to_fit {
+                ui.send_message(AbsmCanvasMessage::zoom_to_fit(
+                    self.canvas,
+                    MessageDirection::ToWidget,
+                ));
+            } else if message.destination() == self.create_state {
                 let screen_position = ui.node(*self.menu).screen_position();
 
                 sender.do_scene_command(AddStateCommand::new(
@@ -79,6 +119,10 @@ impl CanvasContextMenu {
                         name: "New State".to_string(),
                         on_enter_actions: Default::default(),
                         on_leave_actions: Default::default(),
+                        events: Default::default(),
+                        root_motion: None,
+                        sync_markers: Default::default(),
+                        speed: 1.0,
                         root: Default::default(),
                     },
                 ));
@@ -90,8 +134,14 @@ impl CanvasContextMenu {
 pub struct NodeContextMenu {
     create_transition: Handle<UiNode>,
     remove: Handle<UiNode>,
+    duplicate: Handle<UiNode>,
     set_as_entry_state: Handle<UiNode>,
+    set_as_any_state: Handle<UiNode>,
+    set_as_exit_state: Handle<UiNode>,
     enter_state: Handle<UiNode>,
+    align_left: Handle<UiNode>,
+    align_top: Handle<UiNode>,
+    distribute_horizontally: Handle<UiNode>,
     pub menu: RcUiNodeHandle,
     pub canvas: Handle<UiNode>,
     placement_target: Handle<UiNode>,
@@ -101,8 +151,14 @@ impl NodeContextMenu {
     pub fn new(ctx: &mut BuildContext) -> Self {
         let create_transition;
         let remove;
+        let duplicate;
         let set_as_entry_state;
+        let set_as_any_state;
+        let set_as_exit_state;
         let enter_state;
+        let align_left;
+        let align_top;
+        let distribute_horizontally;
         let menu = PopupBuilder::new(WidgetBuilder::new().with_visibility(false))
             .with_content(
                 StackPanelBuilder::new(
@@ -115,14 +171,39 @@ impl NodeContextMenu {
                             remove = create_menu_item("Remove", vec![], ctx);
                             remove
                         })
+                        .with_child({
+                            duplicate = create_menu_item("Duplicate", vec![], ctx);
+                            duplicate
+                        })
                         .with_child({
                             set_as_entry_state =
                                 create_menu_item("Set As Entry State", vec![], ctx);
                             set_as_entry_state
                         })
+                        .with_child({
+                            set_as_any_state = create_menu_item("Set As Any State", vec![], ctx);
+                            set_as_any_state
+                        })
+                        .with_child({
+                            set_as_exit_state = create_menu_item("Set As Exit State", vec![], ctx);
+                            set_as_exit_state
+                        })
                         .with_child({
                             enter_state = create_menu_item("Enter State", vec![], ctx);
                             enter_state
+                        })
+                        .with_child({
+                            align_left = create_menu_item("Align Left", vec![], ctx);
+                            align_left
+                        })
+                        .with_child({
+                            align_top = create_menu_item("Align Top", vec![], ctx);
+                            align_top
+                        })
+                        .with_child({
+                            distribute_horizontally =
+                                create_menu_item("Distribute Horizontally", vec![], ctx);
+                            distribute_horizontally
                         }),
                 )
                 .build(ctx),
@@ -134,10 +215,16 @@ impl NodeContextMenu {
             create_transition,
             menu,
             remove,
+            duplicate,
             canvas: Default::default(),
             placement_target: Default::default(),
             set_as_entry_state,
+            set_as_any_state,
+            set_as_exit_state,
             enter_state,
+            align_left,
+            align_top,
+            distribute_horizontally,
         }
     }
 
@@ -165,59 +252,25 @@ impl NodeContextMenu {
                 ))
             } else if message.destination == self.remove {
                 if let Selection::Absm(ref selection) = editor_scene.selection {
-                    let states_to_remove = selection
-                        .entities
-                        .iter()
-                        .cloned()
-                        .filter_map(|e| {
-                            if let SelectedEntity::State(handle) = e {
-                                Some(handle)
-                            } else {
-                                None
-                            }
-                        })
-                        .collect::<Vec<_>>();
-
-                    // Gather every transition that leads from/to any of states to remove.
-                    let transitions_to_remove = machine.layers()[layer_index]
-                        .transitions()
-                        .pair_iter()
-                        .filter_map(|(handle, transition)| {
-                            if states_to_remove.iter().cloned().any(|state_to_remove| {
-                                state_to_remove == transition.source()
-                                    || state_to_remove == transition.dest()
-                            }) {
-                                Some(handle)
-                            } else {
-                                None
-                            }
-                        });
-
-                    let mut new_selection = selection.clone();
-                    new_selection.entities.clear();
-
-                    let mut group = vec![SceneCommand::new(ChangeSelectionCommand::new(
-                        Selection::Absm(new_selection),
-                        editor_scene.selection.clone(),
-                    ))];
-
-                    group.extend(transitions_to_remove.map(|transition| {
-                        SceneCommand::new(DeleteTransitionCommand::new(
-                            absm_node_handle,
-                            layer_index,
-                            transition,
-                        ))
-                    }));
-
-                    group.extend(states_to_remove.into_iter().map(|state| {
-                        SceneCommand::new(DeleteStateCommand::new(
+                    if let Some(command) = make_delete_selection_command(
+                        selection,
+                        &machine.layers()[layer_index],
+                        &editor_scene.selection,
+                    ) {
+                        sender.do_scene_command(command);
+                    }
+                }
+            } else if message.destination() == self.duplicate {
+                if let Selection::Absm(ref selection) = editor_scene.selection {
+                    let mut clipboard = AbsmClipboard::default();
+                    clipboard.fill_from_selection(selection, &machine.layers()[layer_index]);
+                    if !clipboard.is_empty() {
+                        sender.do_scene_command(clipboard.make_paste_command(
                             absm_node_handle,
                             layer_index,
-                            state,
-                        ))
-                    }));
-
-                    sender.do_scene_command(CommandGroup::from(group));
+                            Vector2::repeat(DUPLICATE_OFFSET),
+                        ));
+                    }
                 }
             } else if message.destination() == self.set_as_entry_state {
                 sender.do_scene_command(SetMachineEntryStateCommand {
@@ -229,11 +282,61 @@ impl NodeContextMenu {
                         .unwrap()
                         .model_handle,
                 });
+            } else if message.destination() == self.set_as_any_state {
+                sender.do_scene_command(SetMachineAnyStateCommand {
+                    node_handle: absm_node_handle,
+                    layer: layer_index,
+                    any_state: ui
+                        .node(self.placement_target)
+                        .query_component::<AbsmNode<State>>()
+                        .unwrap()
+                        .model_handle,
+                });
+            } else if message.destination() == self.set_as_exit_state {
+                sender.do_scene_command(SetMachineExitStateCommand {
+                    node_handle: absm_node_handle,
+                    layer: layer_index,
+                    exit: ui
+                        .node(self.placement_target)
+                        .query_component::<AbsmNode<State>>()
+                        .unwrap()
+                        .model_handle,
+                });
             } else if message.destination == self.enter_state {
                 ui.send_message(AbsmNodeMessage::enter(
                     self.placement_target,
                     MessageDirection::FromWidget,
                 ));
+            } else if message.destination() == self.align_left
+                || message.destination() == self.align_top
+                || message.destination() == self.distribute_horizontally
+            {
+                if let Selection::Absm(ref selection) = editor_scene.selection {
+                    let layer = &machine.layers()[layer_index];
+
+                    let states = selection
+                        .entities
+                        .iter()
+                        .filter_map(|e| match e {
+                            SelectedEntity::State(state) => Some(*state),
+                            _ => None,
+                        })
+                        .collect::<Vec<_>>();
+
+                    let positions = if message.destination() == self.align_left {
+                        align_states_left(layer, &states)
+                    } else if message.destination() == self.align_top {
+                        align_states_top(layer, &states)
+                    } else {
+                        distribute_states_horizontally(layer, &states)
+                    };
+
+                    if let Some(command) =
+                        make_move_states_command(absm_node_handle, layer_index, layer, positions)
+                    {
+                        sender.do_scene_command(command);
+                    }
+                }
             }
         } else if let Some(PopupMessage::Placement(Placement::Cursor(target))) = message.data() {
             if message.destination() == *self.menu {
@@ -245,19 +348,43 @@ impl NodeContextMenu {
 
 pub struct TransitionContextMenu {
     remove: Handle<UiNode>,
+    go_to_target: Handle<UiNode>,
+    add_reroute_point: Handle<UiNode>,
+    clear_reroute_points: Handle<UiNode>,
     pub menu: RcUiNodeHandle,
+    pub canvas: Handle<UiNode>,
     placement_target: Handle<UiNode>,
+    placement_position: Vector2<f32>,
 }
 
 impl TransitionContextMenu {
     pub fn new(ctx: &mut BuildContext) -> Self {
         let remove;
+        let go_to_target;
+        let add_reroute_point;
+        let clear_reroute_points;
         let menu = PopupBuilder::new(WidgetBuilder::new().with_visibility(false))
             .with_content(
-                StackPanelBuilder::new(WidgetBuilder::new().with_child({
-                    remove = create_menu_item("Remove Transition", vec![], ctx);
-                    remove
-                }))
+                StackPanelBuilder::new(
+                    WidgetBuilder::new()
+                        .with_child({
+                            remove = create_menu_item("Remove Transition", vec![], ctx);
+                            remove
+                        })
+                        .with_child({
+                            go_to_target = create_menu_item("Go To Target", vec![], ctx);
+                            go_to_target
+                        })
+                        .with_child({
+                            add_reroute_point = create_menu_item("Add Reroute Point", vec![], ctx);
+                            add_reroute_point
+                        })
+                        .with_child({
+                            clear_reroute_points =
+                                create_menu_item("Clear Reroute Points", vec![], ctx);
+                            clear_reroute_points
+                        }),
+                )
                 .build(ctx),
             )
             .build(ctx);
@@ -266,7 +393,12 @@ impl TransitionContextMenu {
         Self {
             menu,
             remove,
+            go_to_target,
+            add_reroute_point,
+            clear_reroute_points,
+            canvas: Default::default(),
             placement_target: Default::default(),
+            placement_position: Default::default(),
         }
     }
 
@@ -304,10 +436,57 @@ impl TransitionContextMenu {
 
                     sender.do_scene_command(CommandGroup::from(group));
                 }
+            } else if message.destination() == self.go_to_target {
+                if let Some(dest) = ui
+                    .node(self.placement_target)
+                    .query_component::<TransitionView>()
+                    .and_then(|transition_ref| ui.try_get_node(transition_ref.segment.dest))
+                {
+                    let position = dest.actual_local_position();
+                    let size = dest.actual_local_size();
+                    let dest = dest.handle();
+
+                    ui.send_message(AbsmCanvasMessage::selection_changed(
+                        self.canvas,
+                        MessageDirection::ToWidget,
+                        vec![dest],
+                    ));
+                    ui.send_message(AbsmCanvasMessage::frame(
+                        self.canvas,
+                        MessageDirection::ToWidget,
+                        Rect::new(position.x, position.y, size.x, size.y),
+                    ));
+                }
+            } else if message.destination() == self.add_reroute_point
+                || message.destination() == self.clear_reroute_points
+            {
+                if let Some(transition_ref) = ui
+                    .node(self.placement_target)
+                    .query_component::<TransitionView>()
+                {
+                    let value = if message.destination() == self.add_reroute_point {
+                        let point = ui
+                            .node(self.canvas)
+                            .screen_to_local(self.placement_position);
+                        transition_ref.reroute_points_with(point)
+                    } else {
+                        Vec::new()
+                    };
+
+                    if value != transition_ref.reroute_points() {
+                        sender.do_scene_command(SetTransitionReroutePointsCommand {
+                            node_handle: absm_node_handle,
+                            layer_index,
+                            handle: transition_ref.model_handle,
+                            value,
+                        });
+                    }
+                }
             }
         } else if let Some(PopupMessage::Placement(Placement::Cursor(target))) = message.data() {
             if message.destination() == *self.menu {
                 self.placement_target = *target;
+                self.placement_position = ui.cursor_position();
             }
         }
     }
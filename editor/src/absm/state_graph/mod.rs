@@ -2,29 +2,36 @@ use crate::message::MessageSender;
 use crate::{
     absm::{
         canvas::{AbsmCanvas, AbsmCanvasBuilder, AbsmCanvasMessage},
-        command::{AddTransitionCommand, MoveStateNodeCommand},
+        command::{
+            AddTransitionCommand, MoveStateNodeCommand, RenameStateCommand,
+            SetTransitionReroutePointsCommand,
+        },
         fetch_selection,
+        minimap::{AbsmMinimap, AbsmMinimapBuilder, AbsmMinimapMessage},
         node::{AbsmNode, AbsmNodeBuilder, AbsmNodeMessage},
         selection::{AbsmSelection, SelectedEntity},
         state_graph::context::{CanvasContextMenu, NodeContextMenu, TransitionContextMenu},
         transition::{TransitionBuilder, TransitionMessage, TransitionView},
-        NORMAL_BACKGROUND, NORMAL_ROOT_COLOR, SELECTED_BACKGROUND, SELECTED_ROOT_COLOR,
+        NORMAL_ANY_STATE_COLOR, NORMAL_BACKGROUND, NORMAL_EXIT_STATE_COLOR, NORMAL_ROOT_COLOR,
+        SELECTED_ANY_STATE_COLOR, SELECTED_BACKGROUND, SELECTED_EXIT_STATE_COLOR,
+        SELECTED_ROOT_COLOR,
     },
     scene::{
         commands::{ChangeSelectionCommand, CommandGroup, SceneCommand},
         EditorScene, Selection,
     },
     send_sync_message,
+    settings::absm::AbsmCanvasViewSettings,
 };
 use fyrox::{
     animation::machine::{MachineLayer, State, Transition},
-    core::pool::Handle,
+    core::{algebra::Vector2, color::Color, math::Rect, pool::Handle},
     gui::{
         border::BorderBuilder,
         message::{MessageDirection, UiMessage},
         widget::{WidgetBuilder, WidgetMessage},
         window::{WindowBuilder, WindowTitle},
-        BuildContext, Thickness, UiNode, UserInterface,
+        BuildContext, HorizontalAlignment, Thickness, UiNode, UserInterface, VerticalAlignment,
     },
     scene::{animation::absm::AnimationBlendingStateMachine, node::Node},
 };
@@ -32,13 +39,20 @@ use std::cmp::Ordering;
 
 mod context;
 
+/// Opacity of the states that do not match current search text.
+const SEARCH_MISMATCH_OPACITY: f32 = 0.3;
+
 pub struct StateGraphViewer {
     pub window: Handle<UiNode>,
     pub canvas: Handle<UiNode>,
+    minimap: Handle<UiNode>,
+    search_text: String,
     canvas_context_menu: CanvasContextMenu,
     node_context_menu: NodeContextMenu,
     transition_context_menu: TransitionContextMenu,
     last_selection: AbsmSelection,
+    live_active_state: Handle<State>,
+    live_active_transition: Handle<Transition>,
 }
 
 fn fetch_state_node_model_handle(handle: Handle<UiNode>, ui: &UserInterface) -> Handle<State> {
@@ -48,15 +62,40 @@ fn fetch_state_node_model_handle(handle: Handle<UiNode>, ui: &UserInterface) ->
         .model_handle
 }
 
+/// Returns normal and selected colors of a state view, entry, "any" and exit states have distinct colors.
+fn state_colors(layer: &MachineLayer, state: Handle<State>) -> (Color, Color) {
+    if state == layer.entry_state() {
+        (NORMAL_ROOT_COLOR, SELECTED_ROOT_COLOR)
+    } else if state == layer.any_state() {
+        (NORMAL_ANY_STATE_COLOR, SELECTED_ANY_STATE_COLOR)
+    } else if state == layer.exit_state() {
+        (NORMAL_EXIT_STATE_COLOR, SELECTED_EXIT_STATE_COLOR)
+    } else {
+        (NORMAL_BACKGROUND, SELECTED_BACKGROUND)
+    }
+}
+
 impl StateGraphViewer {
-    pub fn new(ctx: &mut BuildContext) -> Self {
+    pub fn new(ctx: &mut BuildContext, view: &AbsmCanvasViewSettings) -> Self {
         let mut node_context_menu = NodeContextMenu::new(ctx);
         let mut canvas_context_menu = CanvasContextMenu::new(ctx);
-        let transition_context_menu = TransitionContextMenu::new(ctx);
+        let mut transition_context_menu = TransitionContextMenu::new(ctx);
 
         let canvas = AbsmCanvasBuilder::new(
             WidgetBuilder::new().with_context_menu(canvas_context_menu.menu.clone()),
         )
+        .with_view(view.position, view.zoom)
+        .build(ctx);
+
+        let minimap = AbsmMinimapBuilder::new(
+            WidgetBuilder::new()
+                .with_width(150.0)
+                .with_height(100.0)
+                .with_margin(Thickness::uniform(4.0))
+                .with_horizontal_alignment(HorizontalAlignment::Right)
+                .with_vertical_alignment(VerticalAlignment::Bottom),
+        )
+        .with_canvas(canvas)
         .build(ctx);
 
         let window = WindowBuilder::new(WidgetBuilder::new())
@@ -67,7 +106,8 @@ impl StateGraphViewer {
                 BorderBuilder::new(
                     WidgetBuilder::new()
                         .with_margin(Thickness::uniform(1.0))
-                        .with_child(canvas),
+                        .with_child(canvas)
+                        .with_child(minimap),
                 )
                 .build(ctx),
             )
@@ -76,14 +116,19 @@ impl StateGraphViewer {
         canvas_context_menu.canvas = canvas;
         canvas_context_menu.node_context_menu = Some(node_context_menu.menu.clone());
         node_context_menu.canvas = canvas;
+        transition_context_menu.canvas = canvas;
 
         Self {
             window,
             canvas,
+            minimap,
+            search_text: Default::default(),
             node_context_menu,
             canvas_context_menu,
             transition_context_menu,
             last_selection: AbsmSelection::default(),
+            live_active_state: Default::default(),
+            live_active_transition: Default::default(),
         }
     }
 
@@ -93,14 +138,115 @@ impl StateGraphViewer {
         }
     }
 
-    pub fn activate_transition(&self, ui: &UserInterface, transition: Handle<Transition>) {
-        if let Some(view_handle) = ui.node(self.canvas).children().iter().cloned().find(|c| {
+    /// Sends current bounds of the state nodes and the visible area of the canvas to the minimap, does
+    /// nothing if they are the same as the ones shown by the minimap.
+    pub fn update_minimap(&self, ui: &UserInterface) {
+        let canvas = ui
+            .node(self.canvas)
+            .cast::<AbsmCanvas>()
+            .expect("Must be AbsmCanvas!");
+
+        let nodes = canvas
+            .children()
+            .iter()
+            .map(|c| ui.node(*c))
+            .filter(|n| n.has_component::<AbsmNode<State>>())
+            .map(|n| {
+                let position = n.actual_local_position();
+                let size = n.actual_local_size();
+                Rect::new(position.x, position.y, size.x, size.y)
+            })
+            .collect::<Vec<_>>();
+        let view = canvas.visible_area(ui);
+
+        let minimap = ui
+            .node(self.minimap)
+            .cast::<AbsmMinimap>()
+            .expect("Must be AbsmMinimap!");
+
+        if minimap.nodes() != nodes || minimap.view() != view {
+            ui.send_message(AbsmMinimapMessage::sync(
+                self.minimap,
+                MessageDirection::ToWidget,
+                nodes,
+                view,
+            ));
+        }
+    }
+
+    fn is_search_match(&self, name: &str) -> bool {
+        self.search_text.is_empty() || name.to_lowercase().contains(&self.search_text)
+    }
+
+    fn search_opacity(&self, name: &str) -> Option<f32> {
+        if self.is_search_match(name) {
+            None
+        } else {
+            Some(SEARCH_MISMATCH_OPACITY)
+        }
+    }
+
+    /// Sets new search text, states with names that do not contain the text are dimmed.
+    pub fn set_search_text(&mut self, ui: &UserInterface, text: &str) {
+        self.search_text = text.to_lowercase();
+
+        for &child in ui.node(self.canvas).children() {
+            if let Some(state_node) = ui.node(child).query_component::<AbsmNode<State>>() {
+                ui.send_message(WidgetMessage::opacity(
+                    child,
+                    MessageDirection::ToWidget,
+                    self.search_opacity(&state_node.name_value),
+                ));
+            }
+        }
+    }
+
+    /// Zooms and moves the view of the canvas to the first state that matches current search text.
+    pub fn focus_found_state(&self, ui: &UserInterface) {
+        if self.search_text.is_empty() {
+            return;
+        }
+
+        if let Some(state_node) = ui
+            .node(self.canvas)
+            .children()
+            .iter()
+            .map(|c| ui.node(*c))
+            .find(|n| {
+                n.query_component::<AbsmNode<State>>()
+                    .is_some_and(|state_node| self.is_search_match(&state_node.name_value))
+            })
+        {
+            let position = state_node.actual_local_position();
+            let size = state_node.actual_local_size();
+            ui.send_message(AbsmCanvasMessage::frame(
+                self.canvas,
+                MessageDirection::ToWidget,
+                Rect::new(position.x, position.y, size.x, size.y),
+            ));
+        }
+    }
+
+    pub fn cursor_position(&self, ui: &UserInterface) -> Vector2<f32> {
+        ui.node(self.canvas).screen_to_local(ui.cursor_position())
+    }
+
+    fn find_transition_view(
+        &self,
+        ui: &UserInterface,
+        transition: Handle<Transition>,
+    ) -> Option<Handle<UiNode>> {
+        ui.node(self.canvas).children().iter().cloned().find(|c| {
             ui.node(*c)
                 .query_component::<TransitionView>()
                 .map_or(false, |transition_view_ref| {
                     transition_view_ref.model_handle == transition
                 })
-        }) {
+        })
+    }
+
+    pub fn activate_transition(&self, ui: &UserInterface, transition: Handle<Transition>) {
+        if let Some(view_handle) = self.find_transition_view(ui, transition) {
             ui.send_message(TransitionMessage::activate(
                 view_handle,
                 MessageDirection::ToWidget,
@@ -108,6 +254,49 @@ impl StateGraphViewer {
         }
     }
 
+    fn set_transition_progress(
+        &self,
+        ui: &UserInterface,
+        transition: Handle<Transition>,
+        progress: Option<f32>,
+    ) {
+        if let Some(view_handle) = self.find_transition_view(ui, transition) {
+            ui.send_message(TransitionMessage::progress(
+                view_handle,
+                MessageDirection::ToWidget,
+                progress,
+            ));
+        }
+    }
+
+    /// Shows the live state of the given layer: highlights its active state and shows the progress of its
+    /// active transition. Should be called every frame while the machine is running.
+    pub fn sync_live_state(&mut self, ui: &UserInterface, layer: &MachineLayer) {
+        let active_state = layer.active_state();
+        if active_state.is_some() && active_state != self.live_active_state {
+            self.activate_state(ui, active_state);
+            self.live_active_state = active_state;
+        }
+
+        let active_transition = layer.active_transition();
+        if active_transition != self.live_active_transition {
+            self.set_transition_progress(ui, self.live_active_transition, None);
+            self.live_active_transition = active_transition;
+        }
+
+        if let Some(transition) = layer.transitions().try_borrow(active_transition) {
+            self.set_transition_progress(ui, active_transition, Some(transition.blend_factor()));
+        }
+    }
+
+    /// Removes all live state indicators shown by [`Self::sync_live_state`].
+    pub fn reset_live_state(&mut self, ui: &UserInterface) {
+        self.set_transition_progress(ui, self.live_active_transition, None);
+        self.activate_state(ui, Handle::NONE);
+        self.live_active_state = Handle::NONE;
+        self.live_active_transition = Handle::NONE;
+    }
+
     pub fn activate_state(&self, ui: &UserInterface, state: Handle<State>) {
         for (state_view_handle, state_view_ref) in ui
             .node(self.canvas)
@@ -215,6 +404,42 @@ impl StateGraphViewer {
             }
         }
 
+        if let Some(TransitionMessage::ReroutePoints(points)) = message.data() {
+            if message.direction() == MessageDirection::FromWidget {
+                let model_handle = ui
+                    .try_get_node(message.destination())
+                    .and_then(|n| n.query_component::<TransitionView>())
+                    .map(|transition_view_ref| transition_view_ref.model_handle);
+                if let Some(transition_handle) = model_handle.filter(|handle| {
+                    absm_node.machine().layers()[layer_index]
+                        .transitions()
+                        .try_borrow(*handle)
+                        .is_some_and(|transition| transition.reroute_points() != *points)
+                }) {
+                    sender.do_scene_command(SetTransitionReroutePointsCommand {
+                        node_handle: absm_node_handle,
+                        layer_index,
+                        handle: transition_handle,
+                        value: points.clone(),
+                    });
+                }
+            }
+        } else if let Some(AbsmNodeMessage::Name(name)) = message.data() {
+            if message.direction() == MessageDirection::FromWidget {
+                if let Some(state_node) = ui
+                    .try_get_node(message.destination())
+                    .and_then(|n| n.query_component::<AbsmNode<State>>())
+                {
+                    sender.do_scene_command(RenameStateCommand {
+                        node_handle: absm_node_handle,
+                        handle: state_node.model_handle,
+                        value: name.clone(),
+                        layer_index,
+                    });
+                }
+            }
+        }
+
         self.node_context_menu.handle_ui_message(
             message,
             ui,
@@ -229,6 +454,7 @@ impl StateGraphViewer {
             message,
             ui,
             absm_node_handle,
+            absm_node,
             layer_index,
         );
         self.transition_context_menu.handle_ui_message(
@@ -297,18 +523,11 @@ impl StateGraphViewer {
                                 .with_context_menu(self.node_context_menu.menu.clone())
                                 .with_desired_position(state.position),
                         )
-                        .with_normal_color(if state_handle == machine_layer.entry_state() {
-                            NORMAL_ROOT_COLOR
-                        } else {
-                            NORMAL_BACKGROUND
-                        })
-                        .with_selected_color(if state_handle == machine_layer.entry_state() {
-                            SELECTED_ROOT_COLOR
-                        } else {
-                            SELECTED_BACKGROUND
-                        })
+                        .with_normal_color(state_colors(machine_layer, state_handle).0)
+                        .with_selected_color(state_colors(machine_layer, state_handle).1)
                         .with_model_handle(state_handle)
                         .with_name(state.name.clone())
+                        .with_renamable(true)
                         .build(&mut ui.build_ctx());
 
                         states.push(state_view_handle);
@@ -366,6 +585,17 @@ impl StateGraphViewer {
             let state_model_handle = state_node.model_handle;
             let state_model_ref = &machine_layer.states()[state_node.model_handle];
 
+            if state_node.opacity() != self.search_opacity(&state_model_ref.name) {
+                send_sync_message(
+                    ui,
+                    WidgetMessage::opacity(
+                        *state,
+                        MessageDirection::ToWidget,
+                        self.search_opacity(&state_model_ref.name),
+                    ),
+                );
+            }
+
             if state_model_ref.name != state_node.name_value {
                 send_sync_message(
                     ui,
@@ -391,11 +621,7 @@ impl StateGraphViewer {
                 AbsmNodeMessage::normal_color(
                     *state,
                     MessageDirection::ToWidget,
-                    if state_model_handle == machine_layer.entry_state() {
-                        NORMAL_ROOT_COLOR
-                    } else {
-                        NORMAL_BACKGROUND
-                    },
+                    state_colors(machine_layer, state_model_handle).0,
                 ),
             );
             send_sync_message(
@@ -403,11 +629,7 @@ impl StateGraphViewer {
                 AbsmNodeMessage::selected_color(
                     *state,
                     MessageDirection::ToWidget,
-                    if state_model_handle == machine_layer.entry_state() {
-                        SELECTED_ROOT_COLOR
-                    } else {
-                        SELECTED_BACKGROUND
-                    },
+                    state_colors(machine_layer, state_model_handle).1,
                 ),
             );
         }
@@ -454,6 +676,7 @@ impl StateGraphViewer {
                         )
                         .with_source(find_state_view(transition.source(), &states, ui))
                         .with_dest(find_state_view(transition.dest(), &states, ui))
+                        .with_reroute_points(transition.reroute_points().to_vec())
                         .build(transition_handle, &mut ui.build_ctx());
 
                         send_sync_message(
@@ -513,6 +736,28 @@ impl StateGraphViewer {
             Ordering::Equal => {}
         }
 
+        for transition_view in transitions.iter() {
+            let transition_view_ref = ui
+                .node(*transition_view)
+                .query_component::<TransitionView>()
+                .unwrap();
+            if let Some(transition) = machine_layer
+                .transitions()
+                .try_borrow(transition_view_ref.model_handle)
+            {
+                if transition_view_ref.reroute_points() != transition.reroute_points() {
+                    send_sync_message(
+                        ui,
+                        TransitionMessage::reroute_points(
+                            *transition_view,
+                            MessageDirection::ToWidget,
+                            transition.reroute_points().to_vec(),
+                        ),
+                    );
+                }
+            }
+        }
+
         // Sync selection.
         let new_selection = current_selection
             .entities
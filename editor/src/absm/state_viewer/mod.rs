@@ -22,6 +22,7 @@ use crate::{
         EditorScene, Selection,
     },
     send_sync_message,
+    settings::absm::AbsmCanvasViewSettings,
 };
 use fyrox::{
     animation::{
@@ -48,7 +49,7 @@ mod context;
 
 pub struct StateViewer {
     pub window: Handle<UiNode>,
-    canvas: Handle<UiNode>,
+    pub canvas: Handle<UiNode>,
     state: Handle<State>,
     canvas_context_menu: CanvasContextMenu,
     node_context_menu: NodeContextMenu,
@@ -137,7 +138,7 @@ fn make_pose_node_name(
 }
 
 impl StateViewer {
-    pub fn new(ctx: &mut BuildContext) -> Self {
+    pub fn new(ctx: &mut BuildContext, view: &AbsmCanvasViewSettings) -> Self {
         let mut node_context_menu = NodeContextMenu::new(ctx);
         let mut canvas_context_menu = CanvasContextMenu::new(ctx);
         let connection_context_menu = ConnectionContextMenu::new(ctx);
@@ -145,6 +146,7 @@ impl StateViewer {
         let canvas = AbsmCanvasBuilder::new(
             WidgetBuilder::new().with_context_menu(canvas_context_menu.menu.clone()),
         )
+        .with_view(view.position, view.zoom)
         .build(ctx);
         let window = WindowBuilder::new(WidgetBuilder::new())
             .can_close(false)
@@ -0,0 +1,112 @@
+//! Prebuilt machines for common cases, they are generated programmatically with all the parameters,
+//! states and transitions already wired. Animations of the states are left unassigned, so a user have
+//! to pick them in the state viewer.
+
+use fyrox::{
+    animation::machine::{
+        transition::{LogicNode, NotNode},
+        Machine, MachineLayer, Parameter, PoseNode, State, Transition,
+    },
+    core::{algebra::Vector2, pool::Handle},
+};
+
+/// Horizontal distance between the states of a template.
+const STATE_SPACING: f32 = 250.0;
+/// Duration of every transition of a template (in seconds).
+const TRANSITION_TIME: f32 = 0.25;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum MachineTemplate {
+    /// `Idle`, `Walk` and `Run` states controlled by `Walk` and `Run` rules.
+    Locomotion,
+    /// `Off` and `On` states controlled by `Active` rule.
+    Toggle,
+}
+
+impl MachineTemplate {
+    pub const ALL: [MachineTemplate; 2] = [MachineTemplate::Locomotion, MachineTemplate::Toggle];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            MachineTemplate::Locomotion => "Locomotion (Idle/Walk/Run)",
+            MachineTemplate::Toggle => "Toggle (Off/On)",
+        }
+    }
+
+    pub fn make_machine(self) -> Machine {
+        let mut machine = Machine::new();
+
+        let mut layer = MachineLayer::new();
+        layer.set_name("Base Layer");
+
+        match self {
+            MachineTemplate::Locomotion => {
+                machine.set_parameter("Walk", Parameter::Rule(false));
+                machine.set_parameter("Run", Parameter::Rule(false));
+
+                let idle = add_state(&mut layer, "Idle", 0);
+                let walk = add_state(&mut layer, "Walk", 1);
+                let run = add_state(&mut layer, "Run", 2);
+
+                add_transitions(&mut layer, idle, walk, "Walk");
+                add_transitions(&mut layer, walk, run, "Run");
+
+                layer.set_entry_state(idle);
+            }
+            MachineTemplate::Toggle => {
+                machine.set_parameter("Active", Parameter::Rule(false));
+
+                let off = add_state(&mut layer, "Off", 0);
+                let on = add_state(&mut layer, "On", 1);
+
+                add_transitions(&mut layer, off, on, "Active");
+
+                layer.set_entry_state(off);
+            }
+        }
+
+        machine.add_layer(layer);
+
+        machine
+    }
+}
+
+/// Adds a state with an animation player node without an animation.
+fn add_state(layer: &mut MachineLayer, name: &str, column: usize) -> Handle<State> {
+    let node = layer.add_node(PoseNode::make_play_animation(Default::default()));
+
+    let mut state = State::new(name, node);
+    state.position = Vector2::new(column as f32 * STATE_SPACING, 0.0);
+    let state = layer.add_state(state);
+
+    layer.node_mut(node).parent_state = state;
+
+    state
+}
+
+/// Adds a pair of transitions between the states: forward one is active when the rule is `true` and
+/// backward one when it is `false`.
+fn add_transitions(layer: &mut MachineLayer, from: Handle<State>, to: Handle<State>, rule: &str) {
+    let from_name = layer.state(from).name.clone();
+    let to_name = layer.state(to).name.clone();
+
+    layer.add_transition(Transition::new(
+        &format!("{from_name}->{to_name}"),
+        from,
+        to,
+        TRANSITION_TIME,
+        rule,
+    ));
+
+    let mut backward = Transition::new(
+        &format!("{to_name}->{from_name}"),
+        to,
+        from,
+        TRANSITION_TIME,
+        rule,
+    );
+    backward.set_condition(LogicNode::Not(NotNode {
+        lhs: Box::new(LogicNode::Parameter(rule.into())),
+    }));
+    layer.add_transition(backward);
+}
@@ -1,12 +1,17 @@
 use crate::message::MessageSender;
 use crate::{
     absm::{
-        command::{AddLayerCommand, RemoveLayerCommand, SetLayerMaskCommand, SetLayerNameCommand},
+        command::{
+            AddLayerCommand, RemoveLayerCommand, SetLayerMaskCommand, SetLayerNameCommand,
+            SetMachineCommand,
+        },
         fetch_selection,
         selection::AbsmSelection,
+        template::MachineTemplate,
     },
     gui::make_dropdown_list_option,
     load_image,
+    menu::{create_menu_item, create_root_menu_item},
     scene::{
         commands::{ChangeSelectionCommand, CommandGroup, SceneCommand},
         selector::{HierarchyNode, NodeSelectorMessage, NodeSelectorWindowBuilder},
@@ -15,55 +20,151 @@ use crate::{
     send_sync_message,
 };
 use fyrox::{
-    animation::machine::{LayerMask, MachineLayer},
-    core::pool::Handle,
+    animation::machine::{LayerMask, Machine, MachineLayer},
+    asset::io::FsResourceIo,
+    core::{
+        futures::executor::block_on,
+        log::Log,
+        pool::Handle,
+        visitor::{Visit, Visitor},
+    },
     fxhash::FxHashSet,
     gui::{
-        button::{ButtonBuilder, ButtonMessage},
+        button::{ButtonBuilder, ButtonContent, ButtonMessage},
         check_box::{CheckBoxBuilder, CheckBoxMessage},
         dropdown_list::{DropdownListBuilder, DropdownListMessage},
+        file_browser::{FileBrowserMode, FileSelectorBuilder, FileSelectorMessage, Filter},
         image::ImageBuilder,
-        message::{MessageDirection, UiMessage},
+        menu::{MenuBuilder, MenuItemMessage},
+        message::{KeyCode, MessageDirection, UiMessage},
+        messagebox::{MessageBoxBuilder, MessageBoxButtons, MessageBoxMessage, MessageBoxResult},
+        numeric::{NumericUpDownBuilder, NumericUpDownMessage},
+        searchbar::{SearchBar, SearchBarBuilder, SearchBarMessage},
         stack_panel::StackPanelBuilder,
+        style::Style,
         text::{TextBuilder, TextMessage},
         text_box::{TextBox, TextBoxBuilder},
         utils::{make_cross, make_simple_tooltip},
         widget::{WidgetBuilder, WidgetMessage},
         window::{WindowBuilder, WindowMessage, WindowTitle},
         BuildContext, Orientation, Thickness, UiNode, UserInterface, VerticalAlignment,
-        BRUSH_BRIGHT,
     },
     scene::{
         animation::{absm::AnimationBlendingStateMachine, AnimationPlayer},
         graph::Graph,
     },
 };
+use std::path::{Path, PathBuf};
 
 pub struct Toolbar {
     pub panel: Handle<UiNode>,
     pub preview: Handle<UiNode>,
+    pub preview_speed: Handle<UiNode>,
+    pub rewind: Handle<UiNode>,
     pub layers: Handle<UiNode>,
     pub layer_name: Handle<UiNode>,
     pub add_layer: Handle<UiNode>,
     pub remove_layer: Handle<UiNode>,
     pub edit_mask: Handle<UiNode>,
     pub node_selector: Handle<UiNode>,
+    pub search_bar: Handle<UiNode>,
+    pub save_machine: Handle<UiNode>,
+    pub load_machine: Handle<UiNode>,
+    pub save_file_selector: Handle<UiNode>,
+    pub load_file_selector: Handle<UiNode>,
+    pub unsaved_changes_message_box: Handle<UiNode>,
+    pub templates: Vec<(Handle<UiNode>, MachineTemplate)>,
+    /// Whether the machine was changed since it was selected, saved to or loaded from a file.
+    pub has_unsaved_changes: bool,
+    // Serialized machine at the moment of the last save/load, used to detect unsaved changes.
+    saved_machine: Option<Vec<u8>>,
+    take_snapshot: bool,
+    load_after_save: bool,
 }
 
 pub enum ToolbarAction {
     None,
     EnterPreviewMode,
     LeavePreviewMode,
+    SetPreviewSpeed(f32),
+    RewindPreview,
+    SearchStates(String),
+    FocusFoundState,
+}
+
+fn make_machine_snapshot(machine: &Machine) -> Option<Vec<u8>> {
+    let mut visitor = Visitor::new();
+    machine.clone().visit("Machine", &mut visitor).ok()?;
+    visitor.save_binary_to_vec().ok()
+}
+
+fn open_file_selector(ui: &UserInterface, file_selector: Handle<UiNode>) {
+    ui.send_message(WindowMessage::open_modal(
+        file_selector,
+        MessageDirection::ToWidget,
+        true,
+    ));
+    ui.send_message(FileSelectorMessage::root(
+        file_selector,
+        MessageDirection::ToWidget,
+        Some(std::env::current_dir().unwrap()),
+    ));
+}
+
+/// Creates a command that replaces the machine of the selected ABSM node and selects the first layer of
+/// the new machine.
+fn make_set_machine_command(
+    selection: &AbsmSelection,
+    editor_scene: &EditorScene,
+    machine: Machine,
+) -> CommandGroup {
+    CommandGroup::from(vec![
+        SceneCommand::new(ChangeSelectionCommand::new(
+            Selection::Absm(AbsmSelection {
+                absm_node_handle: selection.absm_node_handle,
+                layer: if machine.layers().is_empty() {
+                    None
+                } else {
+                    Some(0)
+                },
+                entities: vec![],
+            }),
+            editor_scene.selection.clone(),
+        )),
+        SceneCommand::new(SetMachineCommand {
+            absm_node_handle: selection.absm_node_handle,
+            machine,
+        }),
+    ])
+}
+
+fn make_machine_file_filter() -> Filter {
+    Filter::new(|p: &Path| {
+        if let Some(ext) = p.extension() {
+            ext.to_string_lossy().as_ref() == "absm"
+        } else {
+            p.is_dir()
+        }
+    })
 }
 
 impl Toolbar {
     pub fn new(ctx: &mut BuildContext) -> Self {
+        let save_machine;
+        let load_machine;
         let preview;
+        let preview_speed;
+        let rewind;
         let layers;
         let layer_name;
         let add_layer;
         let remove_layer;
         let edit_mask;
+        let search_bar;
+        let templates = MachineTemplate::ALL
+            .iter()
+            .map(|template| (create_menu_item(template.name(), vec![], ctx), *template))
+            .collect::<Vec<_>>();
         let panel = StackPanelBuilder::new(
             WidgetBuilder::new()
                 .with_child({
@@ -80,6 +181,38 @@ impl Toolbar {
                     .build(ctx);
                     preview
                 })
+                .with_child({
+                    preview_speed = NumericUpDownBuilder::new(
+                        WidgetBuilder::new()
+                            .with_enabled(false)
+                            .with_margin(Thickness::uniform(1.0))
+                            .with_width(50.0)
+                            .with_tooltip(make_simple_tooltip(
+                                ctx,
+                                "Playback speed of the animations in the preview mode.",
+                            )),
+                    )
+                    .with_value(1.0f32)
+                    .with_min_value(0.0)
+                    .with_step(0.1)
+                    .build(ctx);
+                    preview_speed
+                })
+                .with_child({
+                    rewind = ButtonBuilder::new(
+                        WidgetBuilder::new()
+                            .with_enabled(false)
+                            .with_margin(Thickness::uniform(1.0))
+                            .with_tooltip(make_simple_tooltip(
+                                ctx,
+                                "Restart the preview from the entry states, current values of the \
+                                parameters are kept.",
+                            )),
+                    )
+                    .with_text("Rewind")
+                    .build(ctx);
+                    rewind
+                })
                 .with_child({
                     layer_name = TextBoxBuilder::new(
                         WidgetBuilder::new()
@@ -137,7 +270,7 @@ impl Toolbar {
                                 .with_width(18.0)
                                 .with_height(18.0)
                                 .with_margin(Thickness::uniform(1.0))
-                                .with_background(BRUSH_BRIGHT),
+                                .with_background_style(Style::BRUSH_BRIGHT),
                         )
                         .with_opt_texture(load_image(include_bytes!(
                             "../../resources/embed/filter.png"
@@ -146,20 +279,120 @@ impl Toolbar {
                     )
                     .build(ctx);
                     edit_mask
+                })
+                .with_child({
+                    save_machine = ButtonBuilder::new(
+                        WidgetBuilder::new()
+                            .with_margin(Thickness::uniform(1.0))
+                            .with_tooltip(make_simple_tooltip(
+                                ctx,
+                                "Save the machine to a file, so it could be used in other scenes.",
+                            )),
+                    )
+                    .with_text("Save...")
+                    .build(ctx);
+                    save_machine
+                })
+                .with_child({
+                    load_machine = ButtonBuilder::new(
+                        WidgetBuilder::new()
+                            .with_margin(Thickness::uniform(1.0))
+                            .with_tooltip(make_simple_tooltip(
+                                ctx,
+                                "Replace the machine with the one loaded from a file.",
+                            )),
+                    )
+                    .with_text("Load...")
+                    .build(ctx);
+                    load_machine
+                })
+                .with_child(
+                    MenuBuilder::new(
+                        WidgetBuilder::new()
+                            .with_margin(Thickness::uniform(1.0))
+                            .with_tooltip(make_simple_tooltip(
+                                ctx,
+                                "Replace the machine with a prebuilt one.",
+                            )),
+                    )
+                    .with_items(vec![create_root_menu_item(
+                        "New From Template",
+                        templates.iter().map(|(item, _)| *item).collect(),
+                        ctx,
+                    )])
+                    .build(ctx),
+                )
+                .with_child({
+                    search_bar = SearchBarBuilder::new(
+                        WidgetBuilder::new()
+                            .with_margin(Thickness::uniform(1.0))
+                            .with_width(150.0)
+                            .with_tooltip(make_simple_tooltip(
+                                ctx,
+                                "Search states by name, press Enter to focus on the first found \
+                                state.",
+                            )),
+                    )
+                    .build(ctx);
+                    search_bar
                 }),
         )
         .with_orientation(Orientation::Horizontal)
         .build(ctx);
 
+        let save_file_selector = FileSelectorBuilder::new(
+            WindowBuilder::new(WidgetBuilder::new().with_width(300.0).with_height(400.0))
+                .open(false)
+                .with_title(WindowTitle::text("Save Machine As")),
+        )
+        .with_mode(FileBrowserMode::Save {
+            default_file_name: PathBuf::from("unnamed.absm"),
+        })
+        .with_path("./")
+        .with_filter(make_machine_file_filter())
+        .build(ctx);
+
+        let load_file_selector = FileSelectorBuilder::new(
+            WindowBuilder::new(WidgetBuilder::new().with_width(300.0).with_height(400.0))
+                .open(false)
+                .with_title(WindowTitle::text("Load Machine")),
+        )
+        .with_path("./")
+        .with_filter(make_machine_file_filter())
+        .build(ctx);
+
+        let unsaved_changes_message_box = MessageBoxBuilder::new(
+            WindowBuilder::new(WidgetBuilder::new().with_width(300.0).with_height(100.0))
+                .can_close(false)
+                .can_minimize(false)
+                .open(false)
+                .with_title(WindowTitle::text("Unsaved changes")),
+        )
+        .with_buttons(MessageBoxButtons::YesNoCancel)
+        .build(ctx);
+
         Self {
             panel,
             preview,
+            preview_speed,
+            rewind,
             layers,
             layer_name,
             add_layer,
             remove_layer,
             edit_mask,
             node_selector: Handle::NONE,
+            search_bar,
+            save_machine,
+            load_machine,
+            save_file_selector,
+            load_file_selector,
+            unsaved_changes_message_box,
+            templates,
+            has_unsaved_changes: false,
+            saved_machine: None,
+            take_snapshot: true,
+            load_after_save: false,
         }
     }
 
@@ -207,8 +440,32 @@ impl Toolbar {
                     });
                 }
             }
+        } else if let Some(SearchBarMessage::Text(text)) = message.data() {
+            if message.destination() == self.search_bar
+                && message.direction() == MessageDirection::FromWidget
+            {
+                return ToolbarAction::SearchStates(text.clone());
+            }
+        } else if let Some(WidgetMessage::KeyDown(KeyCode::Enter | KeyCode::NumpadEnter)) =
+            message.data()
+        {
+            if ui
+                .node(self.search_bar)
+                .query_component::<SearchBar>()
+                .is_some_and(|search_bar| search_bar.text_box == message.destination())
+            {
+                return ToolbarAction::FocusFoundState;
+            }
+        } else if let Some(NumericUpDownMessage::Value(speed)) = message.data() {
+            if message.destination() == self.preview_speed
+                && message.direction() == MessageDirection::FromWidget
+            {
+                return ToolbarAction::SetPreviewSpeed(*speed);
+            }
         } else if let Some(ButtonMessage::Click) = message.data() {
-            if message.destination() == self.add_layer {
+            if message.destination() == self.rewind {
+                return ToolbarAction::RewindPreview;
+            } else if message.destination() == self.add_layer {
                 let mut layer = MachineLayer::new();
 
                 layer.set_name(
@@ -292,6 +549,23 @@ impl Toolbar {
                         }
                     }
                 }
+            } else if message.destination() == self.save_machine {
+                open_file_selector(ui, self.save_file_selector);
+            } else if message.destination() == self.load_machine {
+                if self.has_unsaved_changes {
+                    ui.send_message(MessageBoxMessage::open(
+                        self.unsaved_changes_message_box,
+                        MessageDirection::ToWidget,
+                        None,
+                        Some(
+                            "The machine has unsaved changes. Do you wish to save them to a file \
+                            before loading another machine?"
+                                .to_string(),
+                        ),
+                    ));
+                } else {
+                    open_file_selector(ui, self.load_file_selector);
+                }
             } else if message.destination() == self.remove_layer {
                 if let Some(absm_node) = graph
                     .try_get_of_type::<AnimationBlendingStateMachine>(selection.absm_node_handle)
@@ -321,6 +595,76 @@ impl Toolbar {
                     }
                 }
             }
+        } else if let Some(FileSelectorMessage::Commit(path)) = message.data() {
+            if message.destination() == self.save_file_selector {
+                if let Some(absm_node) = graph
+                    .try_get_of_type::<AnimationBlendingStateMachine>(selection.absm_node_handle)
+                {
+                    match absm_node.machine().clone().save(path) {
+                        Ok(_) => {
+                            Log::info(format!("Machine was saved to {}", path.display()));
+                            self.saved_machine = make_machine_snapshot(absm_node.machine());
+                            self.set_has_unsaved_changes(false, ui);
+                            if std::mem::take(&mut self.load_after_save) {
+                                open_file_selector(ui, self.load_file_selector);
+                            }
+                        }
+                        Err(e) => Log::err(format!(
+                            "Unable to save the machine to {}. Reason: {:?}",
+                            path.display(),
+                            e
+                        )),
+                    }
+                }
+            } else if message.destination() == self.load_file_selector {
+                match block_on(Machine::load_from_file(path, &FsResourceIo)) {
+                    Ok(machine) => {
+                        sender.do_scene_command(make_set_machine_command(
+                            &selection,
+                            editor_scene,
+                            machine,
+                        ));
+                        self.take_snapshot = true;
+                    }
+                    Err(e) => Log::err(format!(
+                        "Unable to load a machine from {}. Reason: {:?}",
+                        path.display(),
+                        e
+                    )),
+                }
+            }
+        } else if let Some(FileSelectorMessage::Cancel) = message.data() {
+            if message.destination() == self.save_file_selector {
+                self.load_after_save = false;
+            }
+        } else if let Some(MessageBoxMessage::Close(result)) = message.data() {
+            if message.destination() == self.unsaved_changes_message_box {
+                match result {
+                    MessageBoxResult::Yes => {
+                        self.load_after_save = true;
+                        open_file_selector(ui, self.save_file_selector);
+                    }
+                    MessageBoxResult::No => open_file_selector(ui, self.load_file_selector),
+                    _ => (),
+                }
+            }
+        } else if let Some(MenuItemMessage::Click) = message.data() {
+            if let Some((_, template)) = self
+                .templates
+                .iter()
+                .find(|(item, _)| *item == message.destination())
+            {
+                if graph
+                    .try_get_of_type::<AnimationBlendingStateMachine>(selection.absm_node_handle)
+                    .is_some()
+                {
+                    sender.do_scene_command(make_set_machine_command(
+                        &selection,
+                        editor_scene,
+                        template.make_machine(),
+                    ));
+                }
+            }
         } else if let Some(NodeSelectorMessage::Selection(mask_selection)) = message.data() {
             if message.destination() == self.node_selector
                 && message.direction() == MessageDirection::FromWidget
@@ -346,12 +690,41 @@ impl Toolbar {
         ToolbarAction::None
     }
 
+    fn set_has_unsaved_changes(&mut self, has_unsaved_changes: bool, ui: &UserInterface) {
+        if self.has_unsaved_changes != has_unsaved_changes {
+            self.has_unsaved_changes = has_unsaved_changes;
+            ui.send_message(ButtonMessage::content(
+                self.save_machine,
+                MessageDirection::ToWidget,
+                ButtonContent::text(if has_unsaved_changes {
+                    "Save...*"
+                } else {
+                    "Save..."
+                }),
+            ));
+        }
+    }
+
+    /// Forgets the state of the previous machine, must be called when another ABSM node is selected.
+    pub fn on_absm_changed(&mut self) {
+        self.take_snapshot = true;
+        self.load_after_save = false;
+    }
+
     pub fn sync_to_model(
         &mut self,
         absm_node: &AnimationBlendingStateMachine,
         ui: &mut UserInterface,
         selection: &AbsmSelection,
     ) {
+        let snapshot = make_machine_snapshot(absm_node.machine());
+        if std::mem::take(&mut self.take_snapshot) {
+            self.saved_machine = snapshot;
+            self.set_has_unsaved_changes(false, ui);
+        } else {
+            self.set_has_unsaved_changes(snapshot != self.saved_machine, ui);
+        }
+
         let layers = absm_node
             .machine()
             .layers()
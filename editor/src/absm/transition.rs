@@ -13,7 +13,7 @@ use fyrox::{
         brush::Brush,
         define_constructor, define_widget_deref,
         draw::{CommandTexture, Draw, DrawingContext},
-        message::{MessageDirection, UiMessage},
+        message::{MessageDirection, MouseButton, UiMessage},
         widget::{Widget, WidgetBuilder, WidgetMessage},
         BuildContext, Control, UiNode, UserInterface,
     },
@@ -31,14 +31,28 @@ const SELECTED_COLOR: Color = Color::opaque(120, 120, 120);
 const PICKED_BRUSH: Brush = Brush::Solid(PICKED_COLOR);
 const NORMAL_BRUSH: Brush = Brush::Solid(NORMAL_COLOR);
 const SELECTED_BRUSH: Brush = Brush::Solid(SELECTED_COLOR);
+const PROGRESS_BRUSH: Brush = Brush::Solid(Color::opaque(0, 200, 255));
+const HANDLE_BRUSH: Brush = Brush::Solid(Color::opaque(220, 220, 220));
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+/// Half-size of a reroute point handle (in local units of the canvas).
+const HANDLE_HALF_SIZE: f32 = 5.0;
+/// Amount of line segments per span between two control points of a routed transition.
+const SPLINE_SUBDIVISIONS: usize = 16;
+
+#[derive(Debug, Clone, PartialEq)]
 pub enum TransitionMessage {
     Activate,
+    /// Sets progress of the transition (in `[0; 1]` range) to show, `None` hides the progress bar.
+    Progress(Option<f32>),
+    /// Sets new reroute points of the transition (in local coordinates of the canvas). The message with
+    /// [`MessageDirection::FromWidget`] is emitted when a user has finished dragging a reroute point.
+    ReroutePoints(Vec<Vector2<f32>>),
 }
 
 impl TransitionMessage {
     define_constructor!(TransitionMessage:Activate => fn activate(), layout: false);
+    define_constructor!(TransitionMessage:Progress => fn progress(Option<f32>), layout: false);
+    define_constructor!(TransitionMessage:ReroutePoints => fn reroute_points(Vec<Vector2<f32>>), layout: false);
 }
 
 #[derive(Clone, Debug, Visit, Reflect)]
@@ -48,9 +62,59 @@ pub struct TransitionView {
     pub model_handle: Handle<Transition>,
     selectable: Selectable,
     activity_factor: f32,
+    progress: Option<f32>,
+    reroute_points: Vec<Vector2<f32>>,
+    dragged_point: Option<usize>,
 }
 
 impl TransitionView {
+    /// Returns points of the transition line: source position, reroute points and destination position.
+    fn control_points(&self) -> Vec<Vector2<f32>> {
+        let mut points = Vec::with_capacity(self.reroute_points.len() + 2);
+        points.push(self.segment.source_pos);
+        points.extend_from_slice(&self.reroute_points);
+        points.push(self.segment.dest_pos);
+        points
+    }
+
+    /// Returns a copy of current reroute points with a new point inserted between the two closest control
+    /// points, so the new point does not change the order of the existing ones.
+    pub fn reroute_points_with(&self, new_point: Vector2<f32>) -> Vec<Vector2<f32>> {
+        fn distance_to_segment(p: Vector2<f32>, a: Vector2<f32>, b: Vector2<f32>) -> f32 {
+            let ab = b - a;
+            let t = if ab.norm_squared() > 0.0 {
+                ((p - a).dot(&ab) / ab.norm_squared()).clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+            (a + ab.scale(t) - p).norm()
+        }
+
+        let index = self
+            .control_points()
+            .windows(2)
+            .map(|w| distance_to_segment(new_point, w[0], w[1]))
+            .enumerate()
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(i, _)| i)
+            .unwrap_or_default();
+
+        let mut points = self.reroute_points.clone();
+        points.insert(index.min(points.len()), new_point);
+        points
+    }
+
+    pub fn reroute_points(&self) -> &[Vector2<f32>] {
+        &self.reroute_points
+    }
+
+    fn pick_reroute_point(&self, local_position: Vector2<f32>) -> Option<usize> {
+        self.reroute_points.iter().position(|point| {
+            (point.x - local_position.x).abs() <= HANDLE_HALF_SIZE
+                && (point.y - local_position.y).abs() <= HANDLE_HALF_SIZE
+        })
+    }
+
     fn handle_selection_change(&self, ui: &UserInterface) {
         ui.send_message(WidgetMessage::foreground(
             self.handle(),
@@ -66,18 +130,65 @@ impl TransitionView {
 
 define_widget_deref!(TransitionView);
 
-pub fn draw_transition(
-    drawing_context: &mut DrawingContext,
-    clip_bounds: Rect<f32>,
-    brush: Brush,
-    source_pos: Vector2<f32>,
-    dest_pos: Vector2<f32>,
-) {
-    drawing_context.push_line(source_pos, dest_pos, 4.0);
+/// Builds a smooth line (Catmull-Rom spline) that passes through every given control point.
+pub fn make_spline(control_points: &[Vector2<f32>]) -> Vec<Vector2<f32>> {
+    if control_points.len() <= 2 {
+        return control_points.to_vec();
+    }
+
+    let last = control_points.len() - 1;
+    let mut points = Vec::with_capacity(last * SPLINE_SUBDIVISIONS + 1);
+    for i in 0..last {
+        let p0 = control_points[i.saturating_sub(1)];
+        let p1 = control_points[i];
+        let p2 = control_points[i + 1];
+        let p3 = control_points[(i + 2).min(last)];
+
+        for step in 0..SPLINE_SUBDIVISIONS {
+            let t = step as f32 / SPLINE_SUBDIVISIONS as f32;
+            let t2 = t * t;
+            let t3 = t2 * t;
+            points.push(
+                (p1.scale(2.0)
+                    + (p2 - p0).scale(t)
+                    + (p0.scale(2.0) - p1.scale(5.0) + p2.scale(4.0) - p3).scale(t2)
+                    + (p1.scale(3.0) - p0 - p2.scale(3.0) + p3).scale(t3))
+                .scale(0.5),
+            );
+        }
+    }
+    points.push(control_points[last]);
+
+    points
+}
+
+/// Returns a part of the given polyline from its beginning to the given fraction (`[0; 1]`) of its length.
+fn polyline_prefix(points: &[Vector2<f32>], fraction: f32) -> Vec<Vector2<f32>> {
+    let length = points.windows(2).map(|w| (w[1] - w[0]).norm()).sum::<f32>();
+    let mut remaining = length * fraction.clamp(0.0, 1.0);
 
-    let axis = (dest_pos - source_pos).normalize();
-    let center = (dest_pos + source_pos).scale(0.5);
-    let perp = Vector2::new(axis.y, -axis.x).normalize();
+    let mut prefix = points.iter().take(1).cloned().collect::<Vec<_>>();
+    for w in points.windows(2) {
+        let segment_length = (w[1] - w[0]).norm();
+        if remaining >= segment_length {
+            prefix.push(w[1]);
+            remaining -= segment_length;
+        } else {
+            if segment_length > 0.0 {
+                prefix.push(w[0] + (w[1] - w[0]).scale(remaining / segment_length));
+            }
+            break;
+        }
+    }
+    prefix
+}
+
+fn push_arrow(drawing_context: &mut DrawingContext, a: Vector2<f32>, b: Vector2<f32>) {
+    let axis = (b - a)
+        .try_normalize(f32::EPSILON)
+        .unwrap_or_else(Vector2::x);
+    let center = (a + b).scale(0.5);
+    let perp = Vector2::new(axis.y, -axis.x);
 
     let size = 18.0;
 
@@ -86,6 +197,45 @@ pub fn draw_transition(
         center + perp.scale(size * 0.5),
         center - perp.scale(size * 0.5),
     ]);
+}
+
+pub fn draw_transition(
+    drawing_context: &mut DrawingContext,
+    clip_bounds: Rect<f32>,
+    brush: Brush,
+    source_pos: Vector2<f32>,
+    dest_pos: Vector2<f32>,
+) {
+    draw_routed_transition(drawing_context, clip_bounds, brush, &[source_pos, dest_pos]);
+}
+
+/// Draws a transition that passes through every given control point, every span between control points
+/// has its own arrow.
+pub fn draw_routed_transition(
+    drawing_context: &mut DrawingContext,
+    clip_bounds: Rect<f32>,
+    brush: Brush,
+    control_points: &[Vector2<f32>],
+) {
+    let points = make_spline(control_points);
+    for w in points.windows(2) {
+        drawing_context.push_line(w[0], w[1], 4.0);
+    }
+
+    if control_points.len() <= 2 {
+        if let [a, b] = control_points {
+            push_arrow(drawing_context, *a, *b);
+        }
+    } else {
+        // Put an arrow in the middle of each span of the spline.
+        let half = SPLINE_SUBDIVISIONS / 2;
+        for span in 0..control_points.len() - 1 {
+            let index = span * SPLINE_SUBDIVISIONS + half;
+            if let (Some(a), Some(b)) = (points.get(index - 1), points.get(index + 1)) {
+                push_arrow(drawing_context, *a, *b);
+            }
+        }
+    }
 
     drawing_context.commit(clip_bounds, brush, CommandTexture::None, None);
 }
@@ -108,13 +258,42 @@ impl Control for TransitionView {
             NORMAL_COLOR
         };
 
-        draw_transition(
+        let control_points = self.control_points();
+
+        draw_routed_transition(
             drawing_context,
             self.clip_bounds(),
             Brush::Solid(color + Color::from(Hsv::new(180.0, 100.0, 50.0 * self.activity_factor))),
-            self.segment.source_pos,
-            self.segment.dest_pos,
+            &control_points,
         );
+
+        if let Some(progress) = self.progress {
+            let points = polyline_prefix(&make_spline(&control_points), progress);
+            for w in points.windows(2) {
+                drawing_context.push_line(w[0], w[1], 6.0);
+            }
+            drawing_context.commit(
+                self.clip_bounds(),
+                PROGRESS_BRUSH,
+                CommandTexture::None,
+                None,
+            );
+        }
+
+        if self.selectable.selected && !self.reroute_points.is_empty() {
+            for point in self.reroute_points.iter() {
+                drawing_context.push_rect_filled(
+                    &Rect::new(
+                        point.x - HANDLE_HALF_SIZE,
+                        point.y - HANDLE_HALF_SIZE,
+                        HANDLE_HALF_SIZE * 2.0,
+                        HANDLE_HALF_SIZE * 2.0,
+                    ),
+                    None,
+                );
+            }
+            drawing_context.commit(self.clip_bounds(), HANDLE_BRUSH, CommandTexture::None, None);
+        }
     }
 
     fn handle_routed_message(&mut self, ui: &mut UserInterface, message: &mut UiMessage) {
@@ -125,6 +304,38 @@ impl Control for TransitionView {
 
         if let Some(msg) = message.data::<WidgetMessage>() {
             match msg {
+                WidgetMessage::MouseDown { pos, button }
+                    if *button == MouseButton::Left
+                        && self.selectable.selected
+                        && message.destination() == self.handle() =>
+                {
+                    if let Some(index) = self.pick_reroute_point(self.screen_to_local(*pos)) {
+                        self.dragged_point = Some(index);
+                        ui.capture_mouse(self.handle());
+                        message.set_handled(true);
+                    }
+                }
+                WidgetMessage::MouseMove { pos, .. } => {
+                    if let Some(index) = self.dragged_point {
+                        let local_position = self.screen_to_local(*pos);
+                        if let Some(point) = self.reroute_points.get_mut(index) {
+                            *point = local_position;
+                        }
+                        message.set_handled(true);
+                    }
+                }
+                WidgetMessage::MouseUp { button, .. }
+                    if *button == MouseButton::Left && self.dragged_point.is_some() =>
+                {
+                    self.dragged_point = None;
+                    ui.release_mouse_capture();
+                    ui.send_message(TransitionMessage::reroute_points(
+                        self.handle(),
+                        MessageDirection::FromWidget,
+                        self.reroute_points.clone(),
+                    ));
+                    message.set_handled(true);
+                }
                 WidgetMessage::MouseEnter => {
                     ui.send_message(WidgetMessage::foreground(
                         self.handle(),
@@ -143,8 +354,25 @@ impl Control for TransitionView {
             {
                 self.handle_selection_change(ui);
             }
-        } else if let Some(TransitionMessage::Activate) = message.data() {
-            self.activity_factor = 1.0;
+        } else if let Some(msg) = message.data::<TransitionMessage>() {
+            if message.destination() == self.handle()
+                && message.direction() == MessageDirection::ToWidget
+            {
+                match msg {
+                    TransitionMessage::Activate => {
+                        self.activity_factor = 1.0;
+                    }
+                    TransitionMessage::Progress(progress) => {
+                        self.progress = progress.map(|p| p.clamp(0.0, 1.0));
+                    }
+                    TransitionMessage::ReroutePoints(points) => {
+                        // Do not interrupt dragging, the model will be synced when it is finished.
+                        if self.dragged_point.is_none() {
+                            self.reroute_points = points.clone();
+                        }
+                    }
+                }
+            }
         }
     }
 
@@ -158,6 +386,7 @@ pub struct TransitionBuilder {
     widget_builder: WidgetBuilder,
     source: Handle<UiNode>,
     dest: Handle<UiNode>,
+    reroute_points: Vec<Vector2<f32>>,
 }
 
 impl TransitionBuilder {
@@ -166,9 +395,15 @@ impl TransitionBuilder {
             widget_builder,
             source: Default::default(),
             dest: Default::default(),
+            reroute_points: Default::default(),
         }
     }
 
+    pub fn with_reroute_points(mut self, reroute_points: Vec<Vector2<f32>>) -> Self {
+        self.reroute_points = reroute_points;
+        self
+    }
+
     pub fn with_source(mut self, source: Handle<UiNode>) -> Self {
         self.source = source;
         self
@@ -195,6 +430,9 @@ impl TransitionBuilder {
             model_handle,
             selectable: Selectable::default(),
             activity_factor: 0.0,
+            progress: None,
+            reroute_points: self.reroute_points,
+            dragged_point: None,
         };
 
         ctx.add_node(UiNode::new(transition))
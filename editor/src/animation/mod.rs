@@ -25,9 +25,10 @@ use fyrox::{
         curve::{CurveEditorBuilder, CurveEditorMessage, HighlightZone},
         grid::{Column, GridBuilder, Row},
         message::{MessageDirection, UiMessage},
+        style::Style,
         widget::{WidgetBuilder, WidgetMessage},
         window::{WindowBuilder, WindowMessage, WindowTitle},
-        BuildContext, UiNode, UserInterface, BRUSH_DARK, BRUSH_PRIMARY,
+        BuildContext, UiNode, UserInterface, BRUSH_PRIMARY,
     },
     scene::{animation::AnimationPlayer, node::Node, Scene},
 };
@@ -111,7 +112,7 @@ impl AnimationEditor {
                                         .with_child({
                                             curve_editor = CurveEditorBuilder::new(
                                                 WidgetBuilder::new()
-                                                    .with_background(BRUSH_DARK)
+                                                    .with_background_style(Style::BRUSH_DARK)
                                                     .on_row(1),
                                             )
                                             .with_show_x_values(false)
@@ -216,12 +217,14 @@ impl AnimationEditor {
                     {
                         let ui = &engine.user_interface;
                         match msg {
-                            CurveEditorMessage::Sync(curve) => {
-                                sender.do_scene_command(ReplaceTrackCurveCommand {
-                                    animation_player: selection.animation_player,
-                                    animation: selection.animation,
-                                    curve: curve.clone(),
-                                });
+                            CurveEditorMessage::Sync(curves) => {
+                                if let Some(curve) = curves.first() {
+                                    sender.do_scene_command(ReplaceTrackCurveCommand {
+                                        animation_player: selection.animation_player,
+                                        animation: selection.animation,
+                                        curve: curve.clone(),
+                                    });
+                                }
                             }
                             CurveEditorMessage::ViewPosition(position) => {
                                 ui.send_message(RulerMessage::view_position(
@@ -631,7 +634,7 @@ impl AnimationEditor {
                             CurveEditorMessage::sync(
                                 self.curve_editor,
                                 MessageDirection::ToWidget,
-                                selected_curve.clone(),
+                                vec![selected_curve.clone()],
                             ),
                         );
                     }
@@ -671,7 +674,7 @@ impl AnimationEditor {
                 CurveEditorMessage::sync(
                     self.curve_editor,
                     MessageDirection::ToWidget,
-                    Default::default(),
+                    vec![Default::default()],
                 ),
             );
         }
@@ -17,9 +17,9 @@ use fyrox::{
         popup::PopupBuilder,
         popup::{Placement, PopupMessage},
         stack_panel::StackPanelBuilder,
+        style::Style,
         widget::{Widget, WidgetBuilder, WidgetMessage},
-        BuildContext, Control, UiNode, UserInterface, BRUSH_BRIGHT, BRUSH_DARKER, BRUSH_LIGHTER,
-        BRUSH_LIGHTEST,
+        BuildContext, Control, UiNode, UserInterface, BRUSH_BRIGHT, BRUSH_LIGHTEST,
     },
 };
 use std::fmt::{Debug, Formatter};
@@ -455,8 +455,8 @@ impl RulerBuilder {
                 .widget_builder
                 .with_preview_messages(true)
                 .with_context_menu(context_menu.menu.clone())
-                .with_background(BRUSH_DARKER)
-                .with_foreground(BRUSH_LIGHTER)
+                .with_background_style(Style::BRUSH_DARKER)
+                .with_foreground_style(Style::BRUSH_LIGHTER)
                 .build(),
             zoom: 1.0,
             view_position: 0.0,
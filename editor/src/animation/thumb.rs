@@ -12,8 +12,9 @@ use fyrox::{
         define_constructor, define_widget_deref,
         draw::{CommandTexture, Draw, DrawingContext},
         message::{MessageDirection, UiMessage},
+        style::Style,
         widget::{Widget, WidgetBuilder},
-        BuildContext, Control, UiNode, UserInterface, BRUSH_BRIGHT,
+        BuildContext, Control, UiNode, UserInterface,
     },
 };
 use std::{
@@ -127,7 +128,7 @@ impl ThumbBuilder {
             widget: self
                 .widget_builder
                 .with_hit_test_visibility(false)
-                .with_foreground(BRUSH_BRIGHT)
+                .with_foreground_style(Style::BRUSH_BRIGHT)
                 .build(),
             zoom: 1.0,
             view_position: 0.0,
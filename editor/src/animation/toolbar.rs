@@ -34,6 +34,7 @@ use fyrox::{
         numeric::{NumericUpDownBuilder, NumericUpDownMessage},
         popup::{Placement, PopupBuilder, PopupMessage},
         stack_panel::StackPanelBuilder,
+        style::Style,
         text::{TextBuilder, TextMessage},
         text_box::{TextBox, TextBoxBuilder},
         utils::{make_cross, make_simple_tooltip},
@@ -41,7 +42,7 @@ use fyrox::{
         widget::{WidgetBuilder, WidgetMessage},
         window::{WindowBuilder, WindowMessage, WindowTitle},
         BuildContext, HorizontalAlignment, Orientation, Thickness, UiNode, UserInterface,
-        VerticalAlignment, BRUSH_BRIGHT, BRUSH_LIGHT,
+        VerticalAlignment,
     },
     resource::model::{Model, ModelResourceExtension},
     scene::{animation::AnimationPlayer, node::Node, Scene},
@@ -392,7 +393,7 @@ impl Toolbar {
         let panel = BorderBuilder::new(
             WidgetBuilder::new()
                 .on_row(0)
-                .with_foreground(BRUSH_LIGHT)
+                .with_foreground_style(Style::BRUSH_LIGHT)
                 .with_child(
                     StackPanelBuilder::new(
                         WidgetBuilder::new()
@@ -443,7 +444,7 @@ impl Toolbar {
                                             .with_width(18.0)
                                             .with_height(18.0)
                                             .with_margin(Thickness::uniform(1.0))
-                                            .with_background(BRUSH_BRIGHT),
+                                            .with_background_style(Style::BRUSH_BRIGHT),
                                     )
                                     .with_opt_texture(load_image(include_bytes!(
                                         "../../resources/embed/import.png"
@@ -473,7 +474,7 @@ impl Toolbar {
                                             .with_width(18.0)
                                             .with_height(18.0)
                                             .with_margin(Thickness::uniform(1.0))
-                                            .with_background(BRUSH_BRIGHT),
+                                            .with_background_style(Style::BRUSH_BRIGHT),
                                     )
                                     .with_opt_texture(load_image(include_bytes!(
                                         "../../resources/embed/reimport.png"
@@ -502,7 +503,7 @@ impl Toolbar {
                                             .with_width(18.0)
                                             .with_height(18.0)
                                             .with_margin(Thickness::uniform(1.0))
-                                            .with_background(BRUSH_BRIGHT),
+                                            .with_background_style(Style::BRUSH_BRIGHT),
                                     )
                                     .with_opt_texture(load_image(include_bytes!(
                                         "../../resources/embed/rename.png"
@@ -557,7 +558,7 @@ impl Toolbar {
                                             .with_width(18.0)
                                             .with_height(18.0)
                                             .with_margin(Thickness::uniform(1.0))
-                                            .with_background(BRUSH_BRIGHT),
+                                            .with_background_style(Style::BRUSH_BRIGHT),
                                     )
                                     .with_opt_texture(load_image(include_bytes!(
                                         "../../resources/embed/copy.png"
@@ -613,7 +614,7 @@ impl Toolbar {
                                         .with_width(18.0)
                                         .with_height(18.0)
                                         .with_margin(Thickness::uniform(1.0))
-                                        .with_background(BRUSH_BRIGHT),
+                                        .with_background_style(Style::BRUSH_BRIGHT),
                                 )
                                 .with_opt_texture(load_image(include_bytes!(
                                     "../../resources/embed/speed.png"
@@ -642,7 +643,7 @@ impl Toolbar {
                                         .with_width(18.0)
                                         .with_height(18.0)
                                         .with_margin(Thickness::uniform(1.0))
-                                        .with_background(BRUSH_BRIGHT),
+                                        .with_background_style(Style::BRUSH_BRIGHT),
                                 )
                                 .with_opt_texture(load_image(include_bytes!(
                                     "../../resources/embed/time.png"
@@ -727,7 +728,7 @@ impl Toolbar {
                                 .with_content(
                                     VectorImageBuilder::new(
                                         WidgetBuilder::new()
-                                            .with_foreground(BRUSH_BRIGHT)
+                                            .with_foreground_style(Style::BRUSH_BRIGHT)
                                             .with_tooltip(make_simple_tooltip(ctx, "Play/Pause")),
                                     )
                                     .with_primitives(vec![
@@ -759,7 +760,8 @@ impl Toolbar {
                                 )
                                 .with_content(
                                     VectorImageBuilder::new(
-                                        WidgetBuilder::new().with_foreground(BRUSH_BRIGHT),
+                                        WidgetBuilder::new()
+                                            .with_foreground_style(Style::BRUSH_BRIGHT),
                                     )
                                     .with_primitives(vec![Primitive::RectangleFilled {
                                         rect: Rect::new(0.0, 0.0, 16.0, 16.0),
@@ -52,6 +52,7 @@ use fyrox::{
         popup::PopupBuilder,
         scroll_viewer::{ScrollViewerBuilder, ScrollViewerMessage},
         stack_panel::StackPanelBuilder,
+        style::Style,
         text::{Text, TextBuilder, TextMessage},
         text_box::{TextBoxBuilder, TextCommitMode},
         tree::{Tree, TreeBuilder, TreeMessage, TreeRootBuilder, TreeRootMessage},
@@ -59,7 +60,7 @@ use fyrox::{
         widget::{Widget, WidgetBuilder, WidgetMessage},
         window::{WindowBuilder, WindowMessage, WindowTitle},
         BuildContext, Control, NodeHandleMapping, Orientation, RcUiNodeHandle, Thickness, UiNode,
-        UserInterface, VerticalAlignment, BRUSH_BRIGHT, BRUSH_TEXT,
+        UserInterface, VerticalAlignment, BRUSH_TEXT,
     },
     scene::{animation::AnimationPlayer, graph::Graph, node::Node, Scene},
 };
@@ -501,7 +502,7 @@ impl Toolbar {
                     .with_content(
                         ImageBuilder::new(
                             WidgetBuilder::new()
-                                .with_background(BRUSH_BRIGHT)
+                                .with_background_style(Style::BRUSH_BRIGHT)
                                 .with_width(16.0)
                                 .with_height(16.0),
                         )
@@ -523,7 +524,7 @@ impl Toolbar {
                     .with_content(
                         ImageBuilder::new(
                             WidgetBuilder::new()
-                                .with_background(BRUSH_BRIGHT)
+                                .with_background_style(Style::BRUSH_BRIGHT)
                                 .with_width(16.0)
                                 .with_height(16.0),
                         )
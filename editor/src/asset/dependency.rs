@@ -6,7 +6,6 @@ use fyrox::{
     core::{log::Log, pool::Handle},
     gui::{
         button::{ButtonBuilder, ButtonMessage},
-        copypasta::ClipboardProvider,
         grid::{Column, GridBuilder, Row},
         message::{MessageDirection, UiMessage},
         scroll_viewer::ScrollViewerBuilder,
@@ -114,13 +113,50 @@ impl DependencyViewer {
     }
 
     pub fn open(&mut self, resource: &UntypedResource, ui: &mut UserInterface) {
-        let resource_graph = ResourceDependencyGraph::new(resource);
+        self.show(
+            ResourceDependencyGraph::new(resource),
+            "Dependency Viewer",
+            ui,
+        );
+    }
+
+    /// Shows a list of resources that use the given resource.
+    pub fn open_usages(
+        &mut self,
+        resource: &UntypedResource,
+        usages: Vec<UntypedResource>,
+        ui: &mut UserInterface,
+    ) {
+        let root = ResourceGraphNode {
+            resource: resource.clone(),
+            children: usages
+                .into_iter()
+                .map(|resource| ResourceGraphNode {
+                    resource,
+                    children: Default::default(),
+                })
+                .collect(),
+        };
+        self.show(ResourceDependencyGraph { root }, "Usages", ui);
+    }
+
+    fn show(
+        &mut self,
+        resource_graph: ResourceDependencyGraph,
+        title: &str,
+        ui: &mut UserInterface,
+    ) {
         let root = build_tree_recursively(&resource_graph.root, &mut ui.build_ctx());
         ui.send_message(TreeRootMessage::items(
             self.tree_root,
             MessageDirection::ToWidget,
             vec![root],
         ));
+        ui.send_message(WindowMessage::title(
+            self.window,
+            MessageDirection::ToWidget,
+            WindowTitle::text(title),
+        ));
         ui.send_message(WindowMessage::open(
             self.window,
             MessageDirection::ToWidget,
@@ -137,10 +173,11 @@ impl DependencyViewer {
                     MessageDirection::ToWidget,
                 ));
             } else if message.destination() == self.copy_to_clipboard {
-                if let Some(mut clipboard) = ui.clipboard_mut() {
-                    if let Some(resource_graph) = self.resource_graph.as_ref() {
-                        Log::verify(clipboard.set_contents(resource_graph.pretty_print()));
-                    }
+                if let Some(resource_graph) = self.resource_graph.as_ref() {
+                    Log::verify(
+                        ui.clipboard_provider_mut()
+                            .set_contents(resource_graph.pretty_print()),
+                    );
                 }
             }
         } else if let Some(WindowMessage::Close) = message.data() {
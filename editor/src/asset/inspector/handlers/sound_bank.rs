@@ -0,0 +1,68 @@
+use crate::asset::inspector::handlers::ImportOptionsHandler;
+use fyrox::{
+    asset::{io::FsResourceIo, manager::ResourceManager},
+    core::{futures::executor::block_on, log::Log, reflect::prelude::*},
+    gui::inspector::{PropertyAction, PropertyChanged},
+    resource::sound_bank::SoundBank,
+};
+use std::path::{Path, PathBuf};
+
+// Sound banks have no import options, the handler edits the bank itself instead.
+pub struct SoundBankHandler {
+    resource_path: PathBuf,
+    resource_manager: ResourceManager,
+    bank: SoundBank,
+}
+
+impl SoundBankHandler {
+    pub fn new(resource_path: &Path, resource_manager: ResourceManager) -> Self {
+        Self {
+            resource_path: resource_path.to_owned(),
+            bank: load_bank(resource_path, resource_manager.clone()),
+            resource_manager,
+        }
+    }
+}
+
+fn load_bank(path: &Path, resource_manager: ResourceManager) -> SoundBank {
+    match block_on(SoundBank::from_file(path, &FsResourceIo, resource_manager)) {
+        Ok(bank) => bank,
+        Err(err) => {
+            Log::err(format!(
+                "Unable to load sound bank {}. Reason: {}",
+                path.display(),
+                err
+            ));
+            Default::default()
+        }
+    }
+}
+
+impl ImportOptionsHandler for SoundBankHandler {
+    fn apply(&self, resource_manager: ResourceManager) {
+        Log::verify(self.bank.clone().save(&self.resource_path));
+
+        let bank = resource_manager.request::<SoundBank, _>(&self.resource_path);
+        resource_manager
+            .state()
+            .reload_resource(bank.into_untyped());
+    }
+
+    fn revert(&mut self) {
+        self.bank = load_bank(&self.resource_path, self.resource_manager.clone());
+    }
+
+    fn value(&self) -> &dyn Reflect {
+        &self.bank
+    }
+
+    fn handle_property_changed(&mut self, property_changed: &PropertyChanged) {
+        PropertyAction::from_field_kind(&property_changed.value).apply(
+            &property_changed.path(),
+            &mut self.bank,
+            &mut |result| {
+                Log::verify(result);
+            },
+        );
+    }
+}
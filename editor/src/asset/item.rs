@@ -11,10 +11,11 @@ use fyrox::{
         grid::{Column, GridBuilder, Row},
         image::ImageBuilder,
         message::{MessageDirection, UiMessage},
+        style::Style,
         text::TextBuilder,
         widget::{Widget, WidgetBuilder, WidgetMessage},
         BuildContext, Control, HorizontalAlignment, RcUiNodeHandle, Thickness, UiNode,
-        UserInterface, BRUSH_DARKER, BRUSH_DARKEST,
+        UserInterface,
     },
     resource::texture::Texture,
     utils::into_gui_texture,
@@ -42,6 +43,7 @@ pub enum AssetKind {
     Texture,
     Sound,
     Shader,
+    SoundBank,
 }
 
 impl Deref for AssetItem {
@@ -132,14 +134,14 @@ fn make_tooltip(ctx: &mut BuildContext, text: &str) -> RcUiNodeHandle {
     let handle = BorderBuilder::new(
         WidgetBuilder::new()
             .with_visibility(false)
-            .with_foreground(BRUSH_DARKEST)
+            .with_foreground_style(Style::BRUSH_DARKEST)
             .with_background(Brush::Solid(Color::opaque(230, 230, 230)))
             .with_max_size(Vector2::new(300.0, f32::INFINITY))
             .with_child(
                 TextBuilder::new(
                     WidgetBuilder::new()
                         .with_margin(Thickness::uniform(2.0))
-                        .with_foreground(BRUSH_DARKER),
+                        .with_foreground_style(Style::BRUSH_DARKER),
                 )
                 .with_wrap(WrapMode::Letter)
                 .with_text(text)
@@ -183,7 +185,7 @@ impl AssetItemBuilder {
                         kind = AssetKind::Model;
                         load_image(include_bytes!("../../resources/embed/model.png"))
                     }
-                    "ogg" | "wav" => {
+                    "ogg" | "wav" | "flac" => {
                         kind = AssetKind::Sound;
                         load_image(include_bytes!("../../resources/embed/sound.png"))
                     }
@@ -191,6 +193,10 @@ impl AssetItemBuilder {
                         kind = AssetKind::Shader;
                         load_image(include_bytes!("../../resources/embed/shader.png"))
                     }
+                    "soundbank" => {
+                        kind = AssetKind::SoundBank;
+                        load_image(include_bytes!("../../resources/embed/sound.png"))
+                    }
                     _ => None,
                 });
 
@@ -4,7 +4,7 @@ use crate::{
         inspector::{
             handlers::{
                 model::ModelImportOptionsHandler, sound::SoundBufferImportOptionsHandler,
-                texture::TextureImportOptionsHandler,
+                sound_bank::SoundBankHandler, texture::TextureImportOptionsHandler,
             },
             AssetInspector,
         },
@@ -32,7 +32,6 @@ use fyrox::{
     gui::{
         border::BorderBuilder,
         brush::Brush,
-        copypasta::ClipboardProvider,
         file_browser::{FileBrowserBuilder, FileBrowserMessage, Filter},
         grid::{Column, GridBuilder, Row},
         menu::{MenuItemBuilder, MenuItemContent, MenuItemMessage},
@@ -41,11 +40,12 @@ use fyrox::{
         scroll_viewer::{ScrollViewerBuilder, ScrollViewerMessage},
         searchbar::{SearchBarBuilder, SearchBarMessage},
         stack_panel::StackPanelBuilder,
+        style::Style,
         widget::{WidgetBuilder, WidgetMessage},
         window::{WindowBuilder, WindowTitle},
         wrap_panel::WrapPanelBuilder,
         BuildContext, HorizontalAlignment, Orientation, RcUiNodeHandle, Thickness, UiNode,
-        UserInterface, VerticalAlignment, BRUSH_DARK,
+        UserInterface, VerticalAlignment,
     },
     material::{Material, PropertyValue, SharedMaterial},
     resource::texture::Texture,
@@ -77,6 +77,9 @@ struct ContextMenu {
     delete: Handle<UiNode>,
     placement_target: Handle<UiNode>,
     dependencies: Handle<UiNode>,
+    find_usages: Handle<UiNode>,
+    reimport_with_dependents: Handle<UiNode>,
+    find_broken_references: Handle<UiNode>,
 }
 
 fn execute_command(command: &mut Command) {
@@ -102,9 +105,12 @@ fn open_in_explorer<P: AsRef<Path>>(path: P) {
 }
 
 fn put_path_to_clipboard(engine: &mut Engine, path: &OsStr) {
-    if let Some(mut clipboard) = engine.user_interface.clipboard_mut() {
-        Log::verify(clipboard.set_contents(path.to_string_lossy().to_string()));
-    }
+    Log::verify(
+        engine
+            .user_interface
+            .clipboard_provider_mut()
+            .set_contents(path.to_string_lossy().to_string()),
+    );
 }
 
 impl ContextMenu {
@@ -115,6 +121,9 @@ impl ContextMenu {
         let copy_path;
         let copy_file_name;
         let dependencies;
+        let find_usages;
+        let reimport_with_dependents;
+        let find_broken_references;
         let menu = PopupBuilder::new(WidgetBuilder::new())
             .with_content(
                 StackPanelBuilder::new(
@@ -154,6 +163,24 @@ impl ContextMenu {
                                 .with_content(MenuItemContent::text("Dependencies"))
                                 .build(ctx);
                             dependencies
+                        })
+                        .with_child({
+                            find_usages = MenuItemBuilder::new(WidgetBuilder::new())
+                                .with_content(MenuItemContent::text("Find Usages"))
+                                .build(ctx);
+                            find_usages
+                        })
+                        .with_child({
+                            reimport_with_dependents = MenuItemBuilder::new(WidgetBuilder::new())
+                                .with_content(MenuItemContent::text("Reimport With Dependents"))
+                                .build(ctx);
+                            reimport_with_dependents
+                        })
+                        .with_child({
+                            find_broken_references = MenuItemBuilder::new(WidgetBuilder::new())
+                                .with_content(MenuItemContent::text("Find Broken References"))
+                                .build(ctx);
+                            find_broken_references
                         }),
                 )
                 .build(ctx),
@@ -170,6 +197,9 @@ impl ContextMenu {
             placement_target: Default::default(),
             copy_file_name,
             dependencies,
+            find_usages,
+            reimport_with_dependents,
+            find_broken_references,
         }
     }
 
@@ -259,7 +289,7 @@ impl AssetBrowser {
                         .with_child(
                             BorderBuilder::new(
                                 WidgetBuilder::new()
-                                    .with_background(BRUSH_DARK)
+                                    .with_background_style(Style::BRUSH_DARK)
                                     .with_child({
                                         folder_browser = FileBrowserBuilder::new(
                                             WidgetBuilder::new().on_column(0),
@@ -282,6 +312,7 @@ impl AssetBrowser {
                                                 .with_height(22.0)
                                                 .with_margin(Thickness::uniform(1.0)),
                                         )
+                                        .with_debounce(0.25)
                                         .build(ctx);
                                         search_bar
                                     })
@@ -553,6 +584,14 @@ impl AssetBrowser {
                 AssetKind::Shader => {
                     Log::warn("Implement me!");
                 }
+                AssetKind::SoundBank => {
+                    let path = item.path.clone();
+                    self.inspector.inspect_resource_import_options(
+                        SoundBankHandler::new(&path, engine.resource_manager.clone()),
+                        &mut engine.user_interface,
+                        sender,
+                    )
+                }
             }
         } else if let Some(FileBrowserMessage::Path(path)) = message.data::<FileBrowserMessage>() {
             if message.destination() == self.folder_browser
@@ -611,6 +650,46 @@ impl AssetBrowser {
                             .open(&resource, &mut engine.user_interface);
                     }
                 }
+            } else if message.destination() == self.context_menu.find_usages {
+                if let Some(item) = engine
+                    .user_interface
+                    .try_get_node(self.context_menu.placement_target)
+                    .and_then(|n| n.cast::<AssetItem>())
+                {
+                    let resource = engine.resource_manager.request_untyped(&item.path);
+                    let usages = engine.resource_manager.state().find_usages(&resource);
+                    self.dependency_viewer.open_usages(
+                        &resource,
+                        usages,
+                        &mut engine.user_interface,
+                    );
+                }
+            } else if message.destination() == self.context_menu.reimport_with_dependents {
+                if let Some(item) = engine
+                    .user_interface
+                    .try_get_node(self.context_menu.placement_target)
+                    .and_then(|n| n.cast::<AssetItem>())
+                {
+                    let resource = engine.resource_manager.request_untyped(&item.path);
+                    block_on(
+                        engine
+                            .resource_manager
+                            .reload_resource_with_dependents(resource),
+                    );
+                }
+            } else if message.destination() == self.context_menu.find_broken_references {
+                let broken_references = engine.resource_manager.state().find_broken_references();
+                if broken_references.is_empty() {
+                    Log::info("No broken references found.");
+                } else {
+                    for (resource, dependency) in broken_references {
+                        Log::warn(format!(
+                            "Resource {} references {}, which failed to load!",
+                            resource.path().display(),
+                            dependency.path().display()
+                        ));
+                    }
+                }
             }
         }
     }
@@ -10,11 +10,13 @@ use fyrox::{
         grid::{Column, GridBuilder, Row},
         list_view::{ListViewBuilder, ListViewMessage},
         message::{MessageDirection, UiMessage},
+        numeric::{NumericUpDownBuilder, NumericUpDownMessage},
+        style::Style,
         text::{TextBuilder, TextMessage},
         utils::make_simple_tooltip,
         widget::{Widget, WidgetBuilder},
         BuildContext, Control, HorizontalAlignment, Thickness, UiNode, UserInterface,
-        VerticalAlignment, BRUSH_LIGHTER,
+        VerticalAlignment,
     },
     scene::sound::{AudioBus, AudioBusGraph},
 };
@@ -29,6 +31,7 @@ pub enum AudioBusViewMessage {
     PossibleParentBuses(Vec<(Handle<AudioBus>, String)>),
     EffectNames(Vec<String>),
     Name(String),
+    Gain(f32),
 }
 
 impl AudioBusViewMessage {
@@ -36,6 +39,7 @@ impl AudioBusViewMessage {
     define_constructor!(AudioBusViewMessage:PossibleParentBuses => fn possible_parent_buses(Vec<(Handle<AudioBus>, String)>), layout: false);
     define_constructor!(AudioBusViewMessage:EffectNames => fn effect_names(Vec<String>), layout: false);
     define_constructor!(AudioBusViewMessage:Name => fn name(String), layout: false);
+    define_constructor!(AudioBusViewMessage:Gain => fn gain(f32), layout: false);
 }
 
 #[derive(Clone, Visit, Reflect, Debug)]
@@ -46,6 +50,7 @@ pub struct AudioBusView {
     possible_parent_buses: Vec<Handle<AudioBus>>,
     effect_names_list: Handle<UiNode>,
     name: Handle<UiNode>,
+    gain: Handle<UiNode>,
 }
 
 define_widget_deref!(AudioBusView);
@@ -96,6 +101,15 @@ impl Control for AudioBusView {
                             new_name.clone(),
                         ));
                     }
+                    AudioBusViewMessage::Gain(gain) => {
+                        let mut msg = NumericUpDownMessage::value(
+                            self.gain,
+                            MessageDirection::ToWidget,
+                            *gain,
+                        );
+                        msg.flags = message.flags;
+                        ui.send_message(msg);
+                    }
                 }
             }
         }
@@ -110,6 +124,15 @@ impl Control for AudioBusView {
                     self.possible_parent_buses[*selection],
                 ));
             }
+        } else if message.destination == self.gain
+            && message.direction() == MessageDirection::FromWidget
+        {
+            if let Some(NumericUpDownMessage::Value(gain)) = message.data() {
+                let mut msg =
+                    AudioBusViewMessage::gain(self.handle, MessageDirection::FromWidget, *gain);
+                msg.flags = message.flags;
+                ui.send_message(msg);
+            }
         }
     }
 }
@@ -124,7 +147,7 @@ fn make_items(buses: &[(Handle<AudioBus>, String)], ctx: &mut BuildContext) -> V
 fn make_effect_names(names: &[String], ctx: &mut BuildContext) -> Vec<Handle<UiNode>> {
     if names.is_empty() {
         vec![
-            TextBuilder::new(WidgetBuilder::new().with_foreground(BRUSH_LIGHTER))
+            TextBuilder::new(WidgetBuilder::new().with_foreground_style(Style::BRUSH_LIGHTER))
                 .with_text("No Effects")
                 .with_horizontal_text_alignment(HorizontalAlignment::Center)
                 .build(ctx),
@@ -149,6 +172,7 @@ pub struct AudioBusViewBuilder {
     bus: Handle<AudioBus>,
     parent_bus: Handle<AudioBus>,
     possible_parent_buses: Vec<(Handle<AudioBus>, String)>,
+    gain: f32,
 }
 
 impl AudioBusViewBuilder {
@@ -160,6 +184,7 @@ impl AudioBusViewBuilder {
             bus: Default::default(),
             parent_bus: Default::default(),
             possible_parent_buses: Default::default(),
+            gain: 1.0,
         }
     }
 
@@ -183,6 +208,11 @@ impl AudioBusViewBuilder {
         self
     }
 
+    pub fn with_gain(mut self, gain: f32) -> Self {
+        self.gain = gain;
+        self
+    }
+
     pub fn with_possible_parent_buses(
         mut self,
         possible_parent_buses: Vec<(Handle<AudioBus>, String)>,
@@ -195,6 +225,7 @@ impl AudioBusViewBuilder {
         let effect_names_list;
         let name;
         let parent_bus_selector;
+        let gain;
         let grid = GridBuilder::new(
             WidgetBuilder::new()
                 .with_child(
@@ -231,11 +262,28 @@ impl AudioBusViewBuilder {
                     )
                     .build(ctx),
                 )
+                .with_child({
+                    gain = NumericUpDownBuilder::new(
+                        WidgetBuilder::new()
+                            .on_row(2)
+                            .on_column(0)
+                            .with_margin(Thickness::uniform(1.0))
+                            .with_tooltip(make_simple_tooltip(
+                                ctx,
+                                "Gain of the audio bus. It affects every child audio bus as well.",
+                            )),
+                    )
+                    .with_value(self.gain)
+                    .with_min_value(0.0)
+                    .with_step(0.05)
+                    .build(ctx);
+                    gain
+                })
                 .with_child({
                     parent_bus_selector = DropdownListBuilder::new(
                         WidgetBuilder::new()
                             .with_visibility(self.parent_bus.is_some())
-                            .on_row(2)
+                            .on_row(3)
                             .on_column(0)
                             .with_margin(Thickness::uniform(1.0))
                             .with_tooltip(make_simple_tooltip(
@@ -256,6 +304,7 @@ impl AudioBusViewBuilder {
         .add_row(Row::strict(25.0))
         .add_row(Row::stretch())
         .add_row(Row::strict(25.0))
+        .add_row(Row::strict(25.0))
         .add_column(Column::stretch())
         .build(ctx);
 
@@ -278,6 +327,7 @@ impl AudioBusViewBuilder {
                 .collect::<Vec<_>>(),
             effect_names_list,
             name,
+            gain,
         };
         ctx.add_node(UiNode::new(view))
     }
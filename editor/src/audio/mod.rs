@@ -5,14 +5,14 @@ use crate::{
     inspector::editors::resource::{ResourceFieldBuilder, ResourceFieldMessage},
     message::MessageSender,
     scene::commands::{
-        effect::{AddAudioBusCommand, LinkAudioBuses, RemoveAudioBusCommand},
+        effect::{AddAudioBusCommand, LinkAudioBuses, RemoveAudioBusCommand, SetPropertyCommand},
         sound_context::{SetDistanceModelCommand, SetRendererCommand},
         CommandGroup,
     },
     send_sync_message,
     utils::window_content,
     ChangeSelectionCommand, EditorScene, GridBuilder, MessageDirection, Mode, SceneCommand,
-    Selection, UserInterface,
+    Selection, UserInterface, MSG_SYNC_FLAG,
 };
 use fyrox::{
     core::{futures::executor::block_on, pool::Handle},
@@ -304,6 +304,21 @@ impl AudioPanel {
                     parent: *new_parent,
                 });
             }
+        } else if let Some(AudioBusViewMessage::Gain(gain)) = message.data() {
+            if message.direction() == MessageDirection::FromWidget && message.flags != MSG_SYNC_FLAG
+            {
+                let audio_bus_view_ref = engine
+                    .user_interface
+                    .node(message.destination())
+                    .query_component::<AudioBusView>()
+                    .unwrap();
+
+                sender.do_scene_command(SetPropertyCommand::new(
+                    audio_bus_view_ref.bus,
+                    "gain".to_string(),
+                    Box::new(*gain),
+                ));
+            }
         } else if let Some(DropdownListMessage::SelectionChanged(Some(index))) = message.data() {
             if message.direction() == MessageDirection::FromWidget {
                 if message.destination() == self.renderer {
@@ -381,6 +396,7 @@ impl AudioPanel {
                                 .with_margin(Thickness::uniform(1.0)),
                         )
                         .with_name(audio_bus.name())
+                        .with_gain(audio_bus.gain())
                         .with_effect_names(audio_bus_effect_names(audio_bus))
                         .with_parent_bus(audio_bus.parent())
                         .with_possible_parent_buses(fetch_possible_parent_buses(
@@ -482,6 +498,14 @@ impl AudioPanel {
                     audio_bus_ref.name().to_owned(),
                 ),
             );
+            send_sync_message(
+                ui,
+                AudioBusViewMessage::gain(
+                    *audio_bus_view,
+                    MessageDirection::ToWidget,
+                    audio_bus_ref.gain(),
+                ),
+            );
         }
 
         send_sync_message(
@@ -8,10 +8,11 @@ use fyrox::{
         message::{MessageDirection, UiMessage},
         scroll_viewer::ScrollViewerBuilder,
         stack_panel::StackPanelBuilder,
+        style::Style,
         text::{TextBuilder, TextMessage},
         widget::WidgetBuilder,
         window::{WindowBuilder, WindowMessage, WindowTitle},
-        BuildContext, Thickness, UiNode, UserInterface, BRUSH_DARKEST,
+        BuildContext, Thickness, UiNode, UserInterface,
     },
     gui::{HorizontalAlignment, Orientation},
 };
@@ -54,7 +55,7 @@ impl BuildWindow {
                                 WidgetBuilder::new()
                                     .on_row(1)
                                     .with_margin(Thickness::uniform(2.0))
-                                    .with_background(BRUSH_DARKEST)
+                                    .with_background_style(Style::BRUSH_DARKEST)
                                     .with_child(
                                         ScrollViewerBuilder::new(WidgetBuilder::new())
                                             .with_content({
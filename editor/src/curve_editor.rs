@@ -304,12 +304,22 @@ impl CurveEditorWindow {
                 CurveEditorMessage::sync(
                     self.curve_editor,
                     MessageDirection::ToWidget,
-                    curve_resource.data_ref().curve.clone(),
+                    vec![curve_resource.data_ref().curve.clone()],
                 ),
             );
         }
     }
 
+    fn undo(&mut self, ui: &UserInterface) {
+        self.command_stack.undo(CurveEditorContext {});
+        self.sync_to_model(ui);
+    }
+
+    fn redo(&mut self, ui: &UserInterface) {
+        self.command_stack.redo(CurveEditorContext {});
+        self.sync_to_model(ui);
+    }
+
     fn save(&self) {
         if let Some(curve_resource) = self.curve_resource.as_ref() {
             if let ResourceStateRefMut::Ok(state) = curve_resource.state().get_mut() {
@@ -333,6 +343,13 @@ impl CurveEditorWindow {
         self.sync_to_model(ui);
         self.sync_title(ui);
 
+        // Newly loaded curve could be anywhere, so make sure it is visible.
+        ui.send_message(CurveEditorMessage::zoom_to_fit(
+            self.curve_editor,
+            MessageDirection::ToWidget,
+            true,
+        ));
+
         self.modified = false;
 
         self.command_stack.clear(CurveEditorContext {});
@@ -374,7 +391,7 @@ impl CurveEditorWindow {
             CurveEditorMessage::sync(
                 self.curve_editor,
                 MessageDirection::ToWidget,
-                Default::default(),
+                vec![Default::default()],
             ),
         );
     }
@@ -431,12 +448,14 @@ impl CurveEditorWindow {
                     self.close(ui);
                 }
             }
-        } else if let Some(CurveEditorMessage::Sync(curve)) = message.data() {
+        } else if let Some(CurveEditorMessage::Sync(curves)) = message.data() {
             if message.destination() == self.curve_editor
                 && message.direction() == MessageDirection::FromWidget
                 && message.flags != MSG_SYNC_FLAG
             {
-                if let Some(curve_resource) = self.curve_resource.as_ref() {
+                if let (Some(curve_resource), Some(curve)) =
+                    (self.curve_resource.as_ref(), curves.first())
+                {
                     self.command_stack.do_command(
                         Box::new(ModifyCurveCommand {
                             curve_resource: curve_resource.clone(),
@@ -450,13 +469,9 @@ impl CurveEditorWindow {
             }
         } else if let Some(MenuItemMessage::Click) = message.data() {
             if message.destination() == self.menu.edit.undo {
-                self.command_stack.undo(CurveEditorContext {});
-
-                self.sync_to_model(ui);
+                self.undo(ui);
             } else if message.destination() == self.menu.edit.redo {
-                self.command_stack.redo(CurveEditorContext {});
-
-                self.sync_to_model(ui);
+                self.redo(ui);
             } else if message.destination() == self.menu.file.load {
                 ui.send_message(FileSelectorMessage::root(
                     self.load_file_selector,
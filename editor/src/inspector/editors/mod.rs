@@ -6,10 +6,12 @@ use crate::{
         },
         handle::NodeHandlePropertyEditorDefinition,
         material::MaterialPropertyEditorDefinition,
+        parameter::ParameterNamePropertyEditorDefinition,
         resource::ResourceFieldPropertyEditorDefinition,
         script::ScriptPropertyEditorDefinition,
         spritesheet::SpriteSheetFramesContainerEditorDefinition,
         surface::SurfaceDataPropertyEditorDefinition,
+        sync::StateSyncMarkersPropertyEditorDefinition,
         texture::TexturePropertyEditorDefinition,
     },
     message::MessageSender,
@@ -23,14 +25,22 @@ use fyrox::{
                 blendspace::{BlendSpace, BlendSpacePoint},
                 BasePoseNode,
             },
-            state::{StateAction, StateActionWrapper},
-            transition::{AndNode, LogicNode, NotNode, OrNode, XorNode},
+            parameter::PARAMETER_NAME_TAG,
+            state::{
+                StateAction, StateActionWrapper, StateEvent, StateEventTrigger,
+                StateRootMotionSettings,
+            },
+            transition::{
+                AndNode, CompareNode, ComparisonOperator, LogicNode, NotNode, OrNode, XorNode,
+            },
             BlendAnimations, BlendAnimationsByIndex, BlendPose, IndexedBlendInput, Machine,
             PlayAnimation, PoseNode, PoseWeight, State,
         },
         Animation, AnimationContainer,
     },
     core::{
+        color_gradient::ColorGradient,
+        curve::Curve,
         futures::executor::block_on,
         parking_lot::Mutex,
         pool::{ErasedHandle, Handle},
@@ -47,7 +57,8 @@ use fyrox::{
     renderer::framework::state::PolygonFillMode,
     resource::{
         curve::{CurveResource, CurveResourceState},
-        model::{MaterialSearchOptions, Model, ModelResource},
+        model::{MaterialSearchOptions, Model, ModelResource, ModelUpAxis},
+        sound_bank::SoundEvent,
         texture::{
             CompressionOptions, MipFilter, TextureMagnificationFilter, TextureMinificationFilter,
             TextureResource, TextureWrapMode,
@@ -78,21 +89,35 @@ use fyrox::{
         node::Node,
         particle_system::{
             emitter::{
-                base::BaseEmitter, cuboid::CuboidEmitter, cylinder::CylinderEmitter,
-                sphere::SphereEmitter, Emitter,
+                base::{BaseEmitter, EmissionBurst, LifetimeVectorCurve, ParticleTrail},
+                cone::ConeEmitter,
+                cuboid::CuboidEmitter,
+                cylinder::CylinderEmitter,
+                sphere::SphereEmitter,
+                torus::TorusEmitter,
+                Emitter,
             },
-            ParticleSystemRng,
+            flipbook::ParticleFlipbook,
+            gpu::ParticleSimulationMode,
+            lod::ParticleSystemLod,
+            noise::ParticleNoise,
+            ParticleSortingMode, ParticleSystemRng,
         },
         rigidbody::RigidBodyType,
         sound::{
             self,
+            delay::DelayEffect,
+            dynamics::{CompressorEffect, LimiterEffect},
+            equalizer::{EqualizerBand, EqualizerBandKind, ParametricEqualizerEffect},
             filter::{
                 AllPassFilterEffect, BandPassFilterEffect, HighPassFilterEffect,
                 HighShelfFilterEffect, LowPassFilterEffect, LowShelfFilterEffect,
             },
+            occlusion::SoundOcclusion,
+            pitch::PitchShiftEffect,
             reverb::Reverb,
-            Attenuate, AudioBus, Biquad, DistanceModel, Effect, SoundBuffer, SoundBufferResource,
-            Status,
+            Attenuate, AudioBus, Biquad, BusSnapshot, DistanceModel, Effect, SoundBuffer,
+            SoundBufferResource, Status,
         },
         terrain::{Chunk, Layer},
         transform::Transform,
@@ -103,10 +128,12 @@ use std::rc::Rc;
 pub mod animation;
 pub mod handle;
 pub mod material;
+pub mod parameter;
 pub mod resource;
 pub mod script;
 pub mod spritesheet;
 pub mod surface;
+pub mod sync;
 pub mod texture;
 
 pub fn make_status_enum_editor_definition() -> EnumPropertyEditorDefinition<Status> {
@@ -248,7 +275,7 @@ pub fn make_property_editors_container(sender: MessageSender) -> PropertyEditorD
     container.register_inheritable_inspectable::<BaseLight>();
 
     container.insert(EnumPropertyEditorDefinition::<Effect>::new());
-    container.insert(VecCollectionPropertyEditorDefinition::<Effect>::new());
+    container.register_inheritable_vec_collection::<Effect>();
 
     container.insert(InspectablePropertyEditorDefinition::<Attenuate>::new());
     container.insert(InspectablePropertyEditorDefinition::<LowPassFilterEffect>::new());
@@ -258,15 +285,40 @@ pub fn make_property_editors_container(sender: MessageSender) -> PropertyEditorD
     container.insert(InspectablePropertyEditorDefinition::<LowShelfFilterEffect>::new());
     container.insert(InspectablePropertyEditorDefinition::<HighShelfFilterEffect>::new());
     container.insert(InspectablePropertyEditorDefinition::<Reverb>::new());
+    container.insert(InspectablePropertyEditorDefinition::<
+        ParametricEqualizerEffect,
+    >::new());
+    container.insert(InspectablePropertyEditorDefinition::<EqualizerBand>::new());
+    container.insert(VecCollectionPropertyEditorDefinition::<EqualizerBand>::new());
+    container.insert(EnumPropertyEditorDefinition::<EqualizerBandKind>::new());
+    container.insert(InspectablePropertyEditorDefinition::<BusSnapshot>::new());
+    container.insert(VecCollectionPropertyEditorDefinition::<BusSnapshot>::new());
+    container.insert(InspectablePropertyEditorDefinition::<CompressorEffect>::new());
+    container.insert(InspectablePropertyEditorDefinition::<LimiterEffect>::new());
+    container.insert(InspectablePropertyEditorDefinition::<DelayEffect>::new());
+    container.insert(InspectablePropertyEditorDefinition::<PitchShiftEffect>::new());
 
     container.register_inheritable_enum::<Emitter, _>();
+    container.register_inheritable_enum::<ParticleSimulationMode, _>();
+    container.register_inheritable_enum::<ParticleSortingMode, _>();
 
     container.register_inheritable_inspectable::<Biquad>();
     container.register_inheritable_inspectable::<AudioBus>();
+    container.register_inheritable_inspectable::<SoundOcclusion>();
+    container.insert(InspectablePropertyEditorDefinition::<SoundEvent>::new());
+    container.insert(VecCollectionPropertyEditorDefinition::<SoundEvent>::new());
     container.register_inheritable_inspectable::<BaseEmitter>();
     container.register_inheritable_inspectable::<SphereEmitter>();
     container.register_inheritable_inspectable::<CylinderEmitter>();
     container.register_inheritable_inspectable::<CuboidEmitter>();
+    container.register_inheritable_inspectable::<ConeEmitter>();
+    container.register_inheritable_inspectable::<TorusEmitter>();
+    container.insert(InspectablePropertyEditorDefinition::<EmissionBurst>::new());
+    container.insert(VecCollectionPropertyEditorDefinition::<EmissionBurst>::new());
+    container.insert(InspectablePropertyEditorDefinition::<LifetimeVectorCurve>::new());
+    container.insert(EnumPropertyEditorDefinition::<LifetimeVectorCurve>::new_optional());
+    container.insert(InspectablePropertyEditorDefinition::<ParticleTrail>::new());
+    container.insert(EnumPropertyEditorDefinition::<ParticleTrail>::new_optional());
     container.register_inheritable_inspectable::<PerspectiveProjection>();
     container.register_inheritable_inspectable::<OrthographicProjection>();
     container.register_inheritable_inspectable::<Transform>();
@@ -281,8 +333,16 @@ pub fn make_property_editors_container(sender: MessageSender) -> PropertyEditorD
     container.register_inheritable_option::<ColorGradingLut>();
     container.register_inheritable_option::<Biquad>();
     container.register_inheritable_option::<SkyBox>();
+    container.register_inheritable_option::<ParticleNoise>();
+    container.register_inheritable_option::<ParticleFlipbook>();
+    container.register_inheritable_option::<ParticleSystemLod>();
+    container.register_inheritable_option::<Curve>();
+    container.register_inheritable_option::<ColorGradient>();
 
     container.register_inheritable_inspectable::<SkyBox>();
+    container.register_inheritable_inspectable::<ParticleNoise>();
+    container.register_inheritable_inspectable::<ParticleFlipbook>();
+    container.register_inheritable_inspectable::<ParticleSystemLod>();
 
     container.register_inheritable_enum::<dim2::collider::ColliderShape, _>();
     container.register_inheritable_enum::<CoefficientCombineRule, _>();
@@ -298,6 +358,7 @@ pub fn make_property_editors_container(sender: MessageSender) -> PropertyEditorD
     container.register_inheritable_enum::<Exposure, _>();
     container.register_inheritable_enum::<FrustumSplitOptions, _>();
     container.register_inheritable_enum::<MaterialSearchOptions, _>();
+    container.register_inheritable_enum::<ModelUpAxis, _>();
     container.register_inheritable_enum::<DistanceModel, _>();
     container.register_inheritable_enum::<sound::Renderer, _>();
     container.register_inheritable_enum::<RenderPath, _>();
@@ -339,6 +400,12 @@ pub fn make_property_editors_container(sender: MessageSender) -> PropertyEditorD
     container.insert(EnumPropertyEditorDefinition::<StateAction>::new());
     container.insert(InspectablePropertyEditorDefinition::<StateActionWrapper>::new());
     container.insert(VecCollectionPropertyEditorDefinition::<StateActionWrapper>::new());
+    container.insert(EnumPropertyEditorDefinition::<StateEventTrigger>::new());
+    container.insert(InspectablePropertyEditorDefinition::<StateEvent>::new());
+    container.insert(VecCollectionPropertyEditorDefinition::<StateEvent>::new());
+    container.insert(InspectablePropertyEditorDefinition::<StateRootMotionSettings>::new());
+    container.insert(EnumPropertyEditorDefinition::<StateRootMotionSettings>::new_optional());
+    container.insert(StateSyncMarkersPropertyEditorDefinition);
     container.insert(InspectablePropertyEditorDefinition::<BlendAnimationsByIndex>::new());
     container.insert(InspectablePropertyEditorDefinition::<BlendAnimations>::new());
     container.insert(InspectablePropertyEditorDefinition::<BlendSpace>::new());
@@ -349,6 +416,7 @@ pub fn make_property_editors_container(sender: MessageSender) -> PropertyEditorD
 
     container.insert(VecCollectionPropertyEditorDefinition::<Handle<Animation>>::new());
     container.insert(AnimationPropertyEditorDefinition);
+    container.insert_tagged(PARAMETER_NAME_TAG, ParameterNamePropertyEditorDefinition);
 
     container.insert(AnimationContainerPropertyEditorDefinition);
     container.insert(InheritablePropertyEditorDefinition::<AnimationContainer>::new());
@@ -361,6 +429,8 @@ pub fn make_property_editors_container(sender: MessageSender) -> PropertyEditorD
     container.insert(InspectablePropertyEditorDefinition::<OrNode>::new());
     container.insert(InspectablePropertyEditorDefinition::<XorNode>::new());
     container.insert(InspectablePropertyEditorDefinition::<NotNode>::new());
+    container.insert(InspectablePropertyEditorDefinition::<CompareNode>::new());
+    container.insert(EnumPropertyEditorDefinition::<ComparisonOperator>::new());
 
     container.insert(InspectablePropertyEditorDefinition::<ParticleSystemRng>::new());
     container.insert(EnumPropertyEditorDefinition::<PolygonFillMode>::new());
@@ -0,0 +1,103 @@
+//! Parameter selector for string fields of transitions and pose nodes of ABSM, that reference machine
+//! parameters by name. The selector is registered for the properties tagged with
+//! [`fyrox::animation::machine::parameter::PARAMETER_NAME_TAG`].
+
+use crate::{gui::make_dropdown_list_option, inspector::EditorEnvironment};
+use fyrox::gui::{
+    dropdown_list::{DropdownListBuilder, DropdownListMessage},
+    inspector::{
+        editors::{
+            PropertyEditorBuildContext, PropertyEditorDefinition, PropertyEditorInstance,
+            PropertyEditorMessageContext, PropertyEditorTranslationContext,
+        },
+        FieldKind, InspectorError, PropertyChanged,
+    },
+    message::{MessageDirection, UiMessage},
+    widget::WidgetBuilder,
+};
+use std::any::TypeId;
+
+#[derive(Debug)]
+pub struct ParameterNamePropertyEditorDefinition;
+
+impl PropertyEditorDefinition for ParameterNamePropertyEditorDefinition {
+    fn value_type_id(&self) -> TypeId {
+        TypeId::of::<String>()
+    }
+
+    fn create_instance(
+        &self,
+        ctx: PropertyEditorBuildContext,
+    ) -> Result<PropertyEditorInstance, InspectorError> {
+        let value = ctx.property_info.cast_value::<String>()?;
+        if let Some(environment) = EditorEnvironment::try_get_from(&ctx.environment) {
+            let mut items = environment
+                .available_parameters
+                .iter()
+                .map(|d| {
+                    make_dropdown_list_option(
+                        ctx.build_context,
+                        &format!("{} ({})", d.name, d.value.as_ref()),
+                    )
+                })
+                .collect::<Vec<_>>();
+            let mut selected = environment
+                .available_parameters
+                .iter()
+                .position(|d| &d.name == value);
+            // Keep a reference to a removed (or renamed) parameter visible, so it could be fixed.
+            if selected.is_none() && !value.is_empty() {
+                selected = Some(items.len());
+                items.push(make_dropdown_list_option(
+                    ctx.build_context,
+                    &format!("{value} (Missing)"),
+                ));
+            }
+
+            Ok(PropertyEditorInstance::Simple {
+                editor: DropdownListBuilder::new(WidgetBuilder::new())
+                    .with_items(items)
+                    .with_opt_selected(selected)
+                    .build(ctx.build_context),
+            })
+        } else {
+            Err(InspectorError::Custom("No environment!".to_string()))
+        }
+    }
+
+    fn create_message(
+        &self,
+        ctx: PropertyEditorMessageContext,
+    ) -> Result<Option<UiMessage>, InspectorError> {
+        let value = ctx.property_info.cast_value::<String>()?;
+        if let Some(environment) = EditorEnvironment::try_get_from(&ctx.environment) {
+            Ok(Some(DropdownListMessage::selection(
+                ctx.instance,
+                MessageDirection::ToWidget,
+                environment
+                    .available_parameters
+                    .iter()
+                    .position(|d| &d.name == value),
+            )))
+        } else {
+            Err(InspectorError::Custom("No environment!".to_string()))
+        }
+    }
+
+    fn translate_message(&self, ctx: PropertyEditorTranslationContext) -> Option<PropertyChanged> {
+        if ctx.message.direction() == MessageDirection::FromWidget {
+            if let Some(DropdownListMessage::SelectionChanged(Some(value))) = ctx.message.data() {
+                if let Some(environment) = EditorEnvironment::try_get_from(&ctx.environment) {
+                    if let Some(definition) = environment.available_parameters.get(*value) {
+                        return Some(PropertyChanged {
+                            name: ctx.name.to_string(),
+                            owner_type_id: ctx.owner_type_id,
+                            value: FieldKind::object(definition.name.clone()),
+                        });
+                    }
+                }
+            }
+        }
+        None
+    }
+}
@@ -0,0 +1,332 @@
+//! Timeline editor for sync markers of ABSM states. Markers are shown on a horizontal strip that
+//! represents normalized time of a state: left click on an empty space adds a marker, left click on a
+//! marker selects it (it could be dragged then), right click on a marker removes it. Name of the
+//! selected marker could be changed in the text box below the strip.
+
+use fyrox::{
+    animation::SyncMarker,
+    core::{
+        algebra::Vector2, color::Color, math::Rect, pool::Handle, reflect::prelude::*, uuid::Uuid,
+        visitor::prelude::*,
+    },
+    gui::{
+        brush::Brush,
+        define_constructor, define_widget_deref,
+        draw::{CommandTexture, Draw, DrawingContext},
+        inspector::{
+            editors::{
+                PropertyEditorBuildContext, PropertyEditorDefinition, PropertyEditorInstance,
+                PropertyEditorMessageContext, PropertyEditorTranslationContext,
+            },
+            FieldKind, InspectorError, PropertyChanged,
+        },
+        message::{MessageDirection, MouseButton, UiMessage},
+        text::TextMessage,
+        text_box::TextBoxBuilder,
+        utils::make_simple_tooltip,
+        widget::{Widget, WidgetBuilder, WidgetMessage},
+        BuildContext, Control, Thickness, UiNode, UserInterface, VerticalAlignment,
+    },
+};
+use std::{
+    any::{Any, TypeId},
+    ops::{Deref, DerefMut},
+};
+
+/// Height of the strip with the markers.
+const STRIP_HEIGHT: f32 = 20.0;
+/// Maximum horizontal distance (in pixels) between a cursor and a marker to pick the marker.
+const PICK_DISTANCE: f32 = 4.0;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SyncMarkersEditorMessage {
+    Markers(Vec<SyncMarker>),
+}
+
+impl SyncMarkersEditorMessage {
+    define_constructor!(SyncMarkersEditorMessage:Markers => fn markers(Vec<SyncMarker>), layout: false);
+}
+
+#[derive(Clone, Visit, Reflect, Debug)]
+pub struct SyncMarkersEditor {
+    widget: Widget,
+    markers: Vec<SyncMarker>,
+    selected: Option<usize>,
+    is_dragging: bool,
+    name: Handle<UiNode>,
+}
+
+define_widget_deref!(SyncMarkersEditor);
+
+impl SyncMarkersEditor {
+    fn strip_width(&self) -> f32 {
+        self.actual_local_size().x.max(f32::EPSILON)
+    }
+
+    fn local_to_time(&self, local_position: Vector2<f32>) -> f32 {
+        (local_position.x / self.strip_width()).clamp(0.0, 1.0)
+    }
+
+    fn pick_marker(&self, local_position: Vector2<f32>) -> Option<usize> {
+        let width = self.strip_width();
+        self.markers
+            .iter()
+            .enumerate()
+            .map(|(i, m)| (i, (m.time * width - local_position.x).abs()))
+            .filter(|(_, distance)| *distance <= PICK_DISTANCE)
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(i, _)| i)
+    }
+
+    fn select(&mut self, selected: Option<usize>, ui: &UserInterface) {
+        self.selected = selected;
+
+        let name = selected
+            .and_then(|i| self.markers.get(i))
+            .map(|m| m.name.clone());
+
+        ui.send_message(WidgetMessage::enabled(
+            self.name,
+            MessageDirection::ToWidget,
+            name.is_some(),
+        ));
+        ui.send_message(TextMessage::text(
+            self.name,
+            MessageDirection::ToWidget,
+            name.unwrap_or_default(),
+        ));
+    }
+
+    fn commit(&self, ui: &UserInterface) {
+        ui.send_message(SyncMarkersEditorMessage::markers(
+            self.handle(),
+            MessageDirection::FromWidget,
+            self.markers.clone(),
+        ));
+    }
+}
+
+impl Control for SyncMarkersEditor {
+    fn query_component(&self, type_id: TypeId) -> Option<&dyn Any> {
+        if type_id == TypeId::of::<Self>() {
+            Some(self)
+        } else {
+            None
+        }
+    }
+
+    fn draw(&self, ctx: &mut DrawingContext) {
+        let width = self.strip_width();
+
+        ctx.push_rect_filled(&Rect::new(0.0, 0.0, width, STRIP_HEIGHT), None);
+        ctx.commit(
+            self.clip_bounds(),
+            self.widget.background(),
+            CommandTexture::None,
+            None,
+        );
+
+        for (i, marker) in self.markers.iter().enumerate() {
+            let x = marker.time * width;
+
+            ctx.push_line(Vector2::new(x, 0.0), Vector2::new(x, STRIP_HEIGHT), 1.0);
+            ctx.push_triangle_filled([
+                Vector2::new(x - PICK_DISTANCE, 0.0),
+                Vector2::new(x + PICK_DISTANCE, 0.0),
+                Vector2::new(x, PICK_DISTANCE),
+            ]);
+
+            let brush = if self.selected == Some(i) {
+                Brush::Solid(Color::opaque(255, 200, 0))
+            } else {
+                self.widget.foreground()
+            };
+            ctx.commit(self.clip_bounds(), brush, CommandTexture::None, None);
+        }
+    }
+
+    fn handle_routed_message(&mut self, ui: &mut UserInterface, message: &mut UiMessage) {
+        self.widget.handle_routed_message(ui, message);
+
+        if let Some(msg) = message.data::<WidgetMessage>() {
+            match msg {
+                WidgetMessage::MouseDown { pos, button } => {
+                    let local_position = self.screen_to_local(*pos);
+                    if local_position.y > STRIP_HEIGHT {
+                        return;
+                    }
+
+                    match button {
+                        MouseButton::Left => {
+                            let selected = match self.pick_marker(local_position) {
+                                Some(index) => index,
+                                None => {
+                                    self.markers.push(SyncMarker::new(
+                                        Uuid::new_v4(),
+                                        &format!("Marker{}", self.markers.len()),
+                                        self.local_to_time(local_position),
+                                    ));
+                                    self.commit(ui);
+                                    self.markers.len() - 1
+                                }
+                            };
+                            self.select(Some(selected), ui);
+
+                            self.is_dragging = true;
+                            ui.capture_mouse(self.handle());
+                            message.set_handled(true);
+                        }
+                        MouseButton::Right => {
+                            if let Some(index) = self.pick_marker(local_position) {
+                                self.markers.remove(index);
+                                self.select(None, ui);
+                                self.commit(ui);
+                                message.set_handled(true);
+                            }
+                        }
+                        _ => (),
+                    }
+                }
+                WidgetMessage::MouseMove { pos, .. } if self.is_dragging => {
+                    let time = self.local_to_time(self.screen_to_local(*pos));
+                    if let Some(marker) = self.selected.and_then(|i| self.markers.get_mut(i)) {
+                        marker.time = time;
+                    }
+                }
+                WidgetMessage::MouseUp { button, .. }
+                    if *button == MouseButton::Left && self.is_dragging =>
+                {
+                    self.is_dragging = false;
+                    ui.release_mouse_capture();
+                    // Commit only once per drag, otherwise there will be a command per mouse move.
+                    self.commit(ui);
+                    message.set_handled(true);
+                }
+                _ => (),
+            }
+        } else if let Some(TextMessage::Text(text)) = message.data() {
+            if message.destination() == self.name
+                && message.direction() == MessageDirection::FromWidget
+            {
+                let name = text.trim();
+                if let Some(marker) = self.selected.and_then(|i| self.markers.get_mut(i)) {
+                    if !name.is_empty() && marker.name != name {
+                        marker.name = name.to_owned();
+                        self.commit(ui);
+                    }
+                }
+            }
+        } else if let Some(SyncMarkersEditorMessage::Markers(markers)) = message.data() {
+            if message.destination() == self.handle()
+                && message.direction() == MessageDirection::ToWidget
+                && &self.markers != markers
+            {
+                self.markers = markers.clone();
+                self.select(self.selected.filter(|i| *i < self.markers.len()), ui);
+            }
+        }
+    }
+}
+
+pub struct SyncMarkersEditorBuilder {
+    widget_builder: WidgetBuilder,
+    markers: Vec<SyncMarker>,
+}
+
+impl SyncMarkersEditorBuilder {
+    pub fn new(widget_builder: WidgetBuilder) -> Self {
+        Self {
+            widget_builder,
+            markers: Default::default(),
+        }
+    }
+
+    pub fn with_markers(mut self, markers: Vec<SyncMarker>) -> Self {
+        self.markers = markers;
+        self
+    }
+
+    pub fn build(self, ctx: &mut BuildContext) -> Handle<UiNode> {
+        let name = TextBoxBuilder::new(
+            WidgetBuilder::new()
+                .with_enabled(false)
+                .with_height(22.0)
+                .with_margin(Thickness {
+                    left: 0.0,
+                    top: STRIP_HEIGHT + 2.0,
+                    right: 0.0,
+                    bottom: 0.0,
+                }),
+        )
+        .with_vertical_text_alignment(VerticalAlignment::Center)
+        .build(ctx);
+
+        let tooltip = make_simple_tooltip(
+            ctx,
+            "Left click - add or select a marker, drag to move the selected marker.\n\
+            Right click - remove a marker.",
+        );
+
+        let editor = SyncMarkersEditor {
+            widget: self
+                .widget_builder
+                .with_tooltip(tooltip)
+                .with_background(Brush::Solid(Color::opaque(50, 50, 50)))
+                .with_foreground(Brush::Solid(Color::opaque(220, 220, 220)))
+                .with_child(name)
+                .build(),
+            markers: self.markers,
+            selected: None,
+            is_dragging: false,
+            name,
+        };
+
+        ctx.add_node(UiNode::new(editor))
+    }
+}
+
+#[derive(Debug)]
+pub struct StateSyncMarkersPropertyEditorDefinition;
+
+impl PropertyEditorDefinition for StateSyncMarkersPropertyEditorDefinition {
+    fn value_type_id(&self) -> TypeId {
+        TypeId::of::<Vec<SyncMarker>>()
+    }
+
+    fn create_instance(
+        &self,
+        ctx: PropertyEditorBuildContext,
+    ) -> Result<PropertyEditorInstance, InspectorError> {
+        let value = ctx.property_info.cast_value::<Vec<SyncMarker>>()?;
+        Ok(PropertyEditorInstance::Simple {
+            editor: SyncMarkersEditorBuilder::new(WidgetBuilder::new())
+                .with_markers(value.clone())
+                .build(ctx.build_context),
+        })
+    }
+
+    fn create_message(
+        &self,
+        ctx: PropertyEditorMessageContext,
+    ) -> Result<Option<UiMessage>, InspectorError> {
+        let value = ctx.property_info.cast_value::<Vec<SyncMarker>>()?;
+        Ok(Some(SyncMarkersEditorMessage::markers(
+            ctx.instance,
+            MessageDirection::ToWidget,
+            value.clone(),
+        )))
+    }
+
+    fn translate_message(&self, ctx: PropertyEditorTranslationContext) -> Option<PropertyChanged> {
+        if ctx.message.direction() == MessageDirection::FromWidget {
+            if let Some(SyncMarkersEditorMessage::Markers(value)) = ctx.message.data() {
+                return Some(PropertyChanged {
+                    name: ctx.name.to_string(),
+                    owner_type_id: ctx.owner_type_id,
+                    value: FieldKind::object(value.clone()),
+                });
+            }
+        }
+        None
+    }
+}
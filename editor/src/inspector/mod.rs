@@ -19,7 +19,7 @@ use crate::{
     Brush, CommandGroup, Engine, Message, Mode, WidgetMessage, WrapMode, MSG_SYNC_FLAG,
 };
 use fyrox::{
-    animation::Animation,
+    animation::{machine::parameter::ParameterDefinition, Animation},
     asset::manager::ResourceManager,
     core::{
         color::Color,
@@ -63,6 +63,9 @@ pub struct EditorEnvironment {
     /// List of animations definitions (name + handle). It is filled only if current selection
     /// is `AnimationBlendingStateMachine`. The list is filled using ABSM's animation player.
     pub available_animations: Vec<AnimationDefinition>,
+    /// List of parameters of ABSM. It is filled only if current selection is
+    /// `AnimationBlendingStateMachine`.
+    pub available_parameters: Vec<ParameterDefinition>,
     pub sender: MessageSender,
 }
 
@@ -347,6 +350,15 @@ impl Inspector {
         let environment = Rc::new(EditorEnvironment {
             resource_manager,
             serialization_context,
+            available_parameters: if let Selection::Absm(absm_selection) = selection {
+                graph
+                    .try_get(absm_selection.absm_node_handle)
+                    .and_then(|n| n.query_component_ref::<AnimationBlendingStateMachine>())
+                    .map(|absm| absm.machine().parameters().iter().cloned().collect())
+                    .unwrap_or_default()
+            } else {
+                Default::default()
+            },
             available_animations: if let Selection::Absm(absm_selection) = selection {
                 if let Some(animation_player) = graph
                     .try_get(absm_selection.absm_node_handle)
@@ -84,7 +84,7 @@ use fyrox::{asset::io::FsResourceIo, event_loop::EventLoopWindowTarget};
 use fyrox::{
     asset::manager::ResourceManager,
     core::{
-        algebra::{Matrix3, Vector2},
+        algebra::Vector2,
         color::Color,
         futures::executor::block_on,
         log::{Log, MessageKind},
@@ -127,7 +127,8 @@ use fyrox::{
         TextureResource, TextureResourceExtension,
     },
     scene::{
-        camera::Camera, graph::GraphUpdateSwitches, mesh::Mesh, node::Node, Scene, SceneLoader,
+        animation::absm::AnimationBlendingStateMachine, camera::Camera, graph::GraphUpdateSwitches,
+        mesh::Mesh, node::Node, Scene, SceneLoader,
     },
     utils::{into_gui_texture, translate_cursor_icon, translate_event},
     window::{Icon, WindowAttributes},
@@ -534,6 +535,22 @@ impl SceneContainer {
         self.current_scene
     }
 
+    /// Moves a scene to a new position, the current scene remains the same.
+    pub fn move_scene(&mut self, from: usize, to: usize) {
+        if from >= self.scenes.len() || to >= self.scenes.len() {
+            return;
+        }
+
+        let current_scene = self.current_scene_entry_ref().map(|e| e.editor_scene.scene);
+
+        let entry = self.scenes.remove(from);
+        self.scenes.insert(to, entry);
+
+        if let Some(current_scene) = current_scene {
+            self.set_current_scene(current_scene);
+        }
+    }
+
     pub fn set_current_scene(&mut self, scene: Handle<Scene>) -> bool {
         if let Some(index) = self
             .scenes
@@ -789,14 +806,12 @@ impl Editor {
             graphics_context.window.scale_factor()
         ));
 
+        // The scale factor of the window is tracked by the UI itself, the editor only adds its own scale.
+        engine.user_interface.set_scale(settings.general.ui_scale);
         let logical_size = graphics_context
             .window
             .inner_size()
-            .to_logical(graphics_context.window.scale_factor());
-        set_ui_scaling(
-            &engine.user_interface,
-            graphics_context.window.scale_factor() as f32,
-        );
+            .to_logical(engine.user_interface.effective_scale() as f64);
 
         let overlay_pass = OverlayRenderPass::new(graphics_context.renderer.pipeline_state());
         graphics_context
@@ -806,6 +821,10 @@ impl Editor {
         let (message_sender, message_receiver) = mpsc::channel();
         let message_sender = MessageSender(message_sender);
 
+        engine
+            .user_interface
+            .set_style(settings.general.style.make_style());
+
         engine.user_interface.default_font.set(
             Font::from_memory(
                 include_bytes!("../resources/embed/arial.ttf").as_slice(),
@@ -846,7 +865,7 @@ impl Editor {
         let log = LogPanel::new(ctx, log_message_receiver);
         let inspector = Inspector::new(ctx, message_sender.clone());
         let animation_editor = AnimationEditor::new(ctx);
-        let absm_editor = AbsmEditor::new(ctx, message_sender.clone());
+        let absm_editor = AbsmEditor::new(ctx, message_sender.clone(), &settings);
         let particle_system_control_panel = ParticleSystemPreviewControlPanel::new(ctx);
         let camera_control_panel = CameraPreviewControlPanel::new(ctx);
         let audio_preview_panel = AudioPreviewPanel::new(ctx);
@@ -1114,6 +1133,28 @@ impl Editor {
         editor
     }
 
+    /// Applies the user-defined scale from the editor settings to the UI (the scale factor of the main window
+    /// is tracked by the UI itself). The root grid is resized to occupy the whole window.
+    fn set_ui_scaling(&mut self) {
+        let window = &self.engine.graphics_context.as_initialized_ref().window;
+        let ui = &mut self.engine.user_interface;
+        ui.set_scale(self.settings.general.ui_scale);
+        let logical_size = window
+            .inner_size()
+            .to_logical::<f32>(ui.effective_scale() as f64);
+
+        ui.send_message(WidgetMessage::width(
+            self.root_grid,
+            MessageDirection::ToWidget,
+            logical_size.width,
+        ));
+        ui.send_message(WidgetMessage::height(
+            self.root_grid,
+            MessageDirection::ToWidget,
+            logical_size.height,
+        ));
+    }
+
     fn reload_settings(&mut self) {
         match Settings::load() {
             Ok(settings) => {
@@ -1135,6 +1176,12 @@ impl Editor {
             .file_menu
             .update_recent_files_list(&mut self.engine.user_interface, &self.settings);
 
+        self.engine
+            .user_interface
+            .set_style(self.settings.general.style.make_style());
+
+        self.set_ui_scaling();
+
         match self
             .engine
             .graphics_context
@@ -1256,17 +1303,29 @@ impl Editor {
                     }
                 } else if hot_key == key_bindings.copy_selection {
                     if let Some(editor_scene) = self.scenes.current_editor_scene_mut() {
-                        if let Selection::Graph(graph_selection) = &editor_scene.selection {
-                            editor_scene.clipboard.fill_from_selection(
-                                graph_selection,
-                                editor_scene.scene,
-                                engine,
-                            );
+                        match &editor_scene.selection {
+                            Selection::Graph(graph_selection) => {
+                                editor_scene.clipboard.fill_from_selection(
+                                    graph_selection,
+                                    editor_scene.scene,
+                                    engine,
+                                );
+                            }
+                            Selection::Absm(absm_selection) => {
+                                self.absm_editor.copy_selection(
+                                    absm_selection,
+                                    &engine.scenes[editor_scene.scene].graph,
+                                );
+                            }
+                            _ => (),
                         }
                     }
                 } else if hot_key == key_bindings.paste {
                     if let Some(editor_scene) = self.scenes.current_editor_scene_mut() {
-                        if !editor_scene.clipboard.is_empty() {
+                        if let Selection::Absm(absm_selection) = &editor_scene.selection {
+                            self.absm_editor
+                                .paste(absm_selection, &engine.user_interface, &sender);
+                        } else if !editor_scene.clipboard.is_empty() {
                             sender.do_scene_command(PasteCommand::new(
                                 editor_scene.scene_content_root,
                             ));
@@ -1293,6 +1352,26 @@ impl Editor {
                                         make_delete_selection_command(editor_scene, engine),
                                     ));
                                 }
+                            } else if let Selection::Absm(ref selection) = editor_scene.selection {
+                                if let Some(command) = engine.scenes[editor_scene.scene]
+                                    .graph
+                                    .try_get_of_type::<AnimationBlendingStateMachine>(
+                                        selection.absm_node_handle,
+                                    )
+                                    .zip(selection.layer)
+                                    .and_then(|(absm, layer_index)| {
+                                        absm.machine().layers().get(layer_index)
+                                    })
+                                    .and_then(|layer| {
+                                        absm::command::make_delete_selection_command(
+                                            selection,
+                                            layer,
+                                            &editor_scene.selection,
+                                        )
+                                    })
+                                {
+                                    sender.do_scene_command(command);
+                                }
                             }
                         }
                     }
@@ -1398,8 +1477,13 @@ impl Editor {
                 .handle_ui_message(message, editor_scene, engine);
             self.audio_preview_panel
                 .handle_ui_message(message, editor_scene, engine);
-            self.absm_editor
-                .handle_ui_message(message, engine, &self.message_sender, editor_scene);
+            self.absm_editor.handle_ui_message(
+                message,
+                engine,
+                &self.message_sender,
+                editor_scene,
+                &mut self.settings,
+            );
             self.audio_panel
                 .handle_ui_message(message, editor_scene, &self.message_sender, engine);
             self.node_removal_dialog.handle_ui_message(
@@ -2325,6 +2409,12 @@ impl Editor {
                     Message::LoadLayout => {
                         self.load_layout();
                     }
+                    Message::SetUiScale(scale) => {
+                        if self.settings.general.ui_scale != scale {
+                            self.settings.general.ui_scale = scale;
+                        }
+                        self.set_ui_scaling();
+                    }
                     Message::ProvideSceneHierarchy { view } => {
                         if let Some(editor_scene) = self.scenes.current_editor_scene_ref() {
                             let scene = &self.engine.scenes[editor_scene.scene];
@@ -2366,7 +2456,9 @@ impl Editor {
 
             editor_scene.update(&mut self.engine, dt, &mut self.settings);
 
-            self.absm_editor.update(editor_scene, &mut self.engine);
+            self.absm_editor.update(editor_scene, &mut self.engine, dt);
+            self.particle_system_control_panel
+                .update(dt, editor_scene, &mut self.engine);
 
             if let Some(mode) = editor_scene_entry.current_interaction_mode {
                 editor_scene_entry.interaction_modes[mode as usize].update(
@@ -2505,27 +2597,7 @@ impl Editor {
                                 );
                             }
 
-                            let logical_size = size.to_logical(
-                                self.engine
-                                    .graphics_context
-                                    .as_initialized_ref()
-                                    .window
-                                    .scale_factor(),
-                            );
-                            self.engine
-                                .user_interface
-                                .send_message(WidgetMessage::width(
-                                    self.root_grid,
-                                    MessageDirection::ToWidget,
-                                    logical_size.width,
-                                ));
-                            self.engine
-                                .user_interface
-                                .send_message(WidgetMessage::height(
-                                    self.root_grid,
-                                    MessageDirection::ToWidget,
-                                    logical_size.height,
-                                ));
+                            self.set_ui_scaling();
 
                             if size.width > 0 && size.height > 0 {
                                 self.settings.windows.window_size.x = size.width as f32;
@@ -2542,7 +2614,12 @@ impl Editor {
                             }
                         }
                         WindowEvent::ScaleFactorChanged { scale_factor, .. } => {
-                            set_ui_scaling(&self.engine.user_interface, *scale_factor as f32);
+                            // The UI is fed with the event below, but the root grid must be resized
+                            // with the new scale factor right away.
+                            self.engine
+                                .user_interface
+                                .set_scale_factor(*scale_factor as f32);
+                            self.set_ui_scaling();
                         }
                         WindowEvent::RedrawRequested => {
                             if self.is_active() {
@@ -2619,15 +2696,6 @@ impl Editor {
     }
 }
 
-fn set_ui_scaling(ui: &UserInterface, scale: f32) {
-    // High-DPI screen support
-    ui.send_message(WidgetMessage::render_transform(
-        ui.root(),
-        MessageDirection::ToWidget,
-        Matrix3::new_scaling(scale),
-    ));
-}
-
 fn update(editor: &mut Editor, window_target: &EventLoopWindowTarget<()>) {
     scope_profile!();
 
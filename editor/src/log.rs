@@ -11,7 +11,6 @@ use fyrox::{
     gui::{
         border::BorderBuilder,
         button::ButtonMessage,
-        copypasta::ClipboardProvider,
         dropdown_list::DropdownListMessage,
         formatted_text::WrapMode,
         grid::{Column, GridBuilder, Row},
@@ -71,9 +70,10 @@ impl ContextMenu {
                     .and_then(|n| n.query_component::<Text>())
                 {
                     let text = field.text();
-                    if let Some(mut clipboard) = engine.user_interface.clipboard_mut() {
-                        let _ = clipboard.set_contents(text);
-                    }
+                    let _ = engine
+                        .user_interface
+                        .clipboard_provider_mut()
+                        .set_contents(text);
                 }
             }
         }
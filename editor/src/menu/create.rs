@@ -37,7 +37,7 @@ use fyrox::{
             ParticleSystemBuilder,
         },
         pivot::PivotBuilder,
-        sound::{listener::ListenerBuilder, SoundBuilder},
+        sound::{listener::ListenerBuilder, reverb_zone::ReverbZoneBuilder, SoundBuilder},
         sprite::SpriteBuilder,
         terrain::{Layer, TerrainBuilder},
     },
@@ -95,6 +95,7 @@ pub struct CreateEntityMenu {
     create_sprite: Handle<UiNode>,
     create_particle_system: Handle<UiNode>,
     create_listener: Handle<UiNode>,
+    create_reverb_zone: Handle<UiNode>,
     create_sound_source: Handle<UiNode>,
     physics_menu: PhysicsMenu,
     physics2d_menu: Physics2dMenu,
@@ -121,6 +122,7 @@ impl CreateEntityMenu {
         let create_pivot;
         let create_sound_source;
         let create_listener;
+        let create_reverb_zone;
         let physics_menu = PhysicsMenu::new(ctx);
         let physics2d_menu = Physics2dMenu::new(ctx);
         let dim2_menu = Dim2Menu::new(ctx);
@@ -168,6 +170,10 @@ impl CreateEntityMenu {
                         create_listener = create_menu_item("Listener", vec![], ctx);
                         create_listener
                     },
+                    {
+                        create_reverb_zone = create_menu_item("Reverb Zone", vec![], ctx);
+                        create_reverb_zone
+                    },
                 ],
                 ctx,
             ),
@@ -237,6 +243,7 @@ impl CreateEntityMenu {
                 create_terrain,
                 create_sound_source,
                 create_listener,
+                create_reverb_zone,
                 create_navmesh,
                 create_decal,
                 physics_menu,
@@ -384,6 +391,11 @@ impl CreateEntityMenu {
                             ListenerBuilder::new(BaseBuilder::new().with_name("Listener"))
                                 .build_node(),
                         )
+                    } else if message.destination() == self.create_reverb_zone {
+                        Some(
+                            ReverbZoneBuilder::new(BaseBuilder::new().with_name("ReverbZone"))
+                                .build_node(),
+                        )
                     } else {
                         None
                     }
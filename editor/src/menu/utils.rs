@@ -1,13 +1,23 @@
-use crate::menu::{create_menu_item, create_root_menu_item, Panels};
+use crate::{
+    menu::{create_menu_item, create_root_menu_item, Panels},
+    utils::create_file_selector,
+};
 use fyrox::{
-    asset::core::pool::Handle,
+    asset::{
+        bundle::ResourceBundleBuilder,
+        core::{log::Log, make_relative_path, pool::Handle},
+    },
     gui::{
+        file_browser::{FileBrowserMode, FileSelectorBuilder, FileSelectorMessage, Filter},
         menu::MenuItemMessage,
         message::{MessageDirection, UiMessage},
-        window::WindowMessage,
+        widget::WidgetBuilder,
+        window::{WindowBuilder, WindowMessage, WindowTitle},
         BuildContext, UiNode, UserInterface,
     },
+    resource::sound_bank::SoundBank,
 };
+use std::path::{Path, PathBuf};
 
 pub struct UtilsMenu {
     pub menu: Handle<UiNode>,
@@ -16,6 +26,56 @@ pub struct UtilsMenu {
     absm_editor: Handle<UiNode>,
     animation_editor: Handle<UiNode>,
     ragdoll_wizard: Handle<UiNode>,
+    pack_assets: Handle<UiNode>,
+    assets_directory_selector: Handle<UiNode>,
+    new_sound_bank: Handle<UiNode>,
+    sound_bank_selector: Handle<UiNode>,
+}
+
+fn create_sound_bank(path: &Path) {
+    match SoundBank::default().save(path) {
+        Ok(_) => Log::info(format!(
+            "Sound bank {} was successfully created!",
+            path.display()
+        )),
+        Err(e) => Log::err(format!(
+            "Unable to create sound bank {}. Reason: {e:?}",
+            path.display()
+        )),
+    }
+}
+
+/// Packs every file of the given directory to a bundle next to it (`data` is packed to `data.bundle`).
+/// The directory must be inside the working directory, so the paths in the bundle match the paths that
+/// are used to load resources.
+fn pack_assets(path: &Path) {
+    let directory = match make_relative_path(path) {
+        Ok(directory) => directory,
+        Err(e) => {
+            Log::err(format!(
+                "Unable to pack assets from {}, the directory must be inside the working \
+                directory. Reason: {e:?}",
+                path.display()
+            ));
+            return;
+        }
+    };
+    let bundle = directory.with_extension("bundle");
+
+    match ResourceBundleBuilder::new()
+        .with_directory(&directory)
+        .and_then(|builder| builder.save(&bundle))
+    {
+        Ok(_) => Log::info(format!(
+            "Assets from {} were successfully packed to {}!",
+            directory.display(),
+            bundle.display()
+        )),
+        Err(e) => Log::err(format!(
+            "Unable to pack assets from {}. Reason: {e:?}",
+            directory.display()
+        )),
+    }
 }
 
 impl UtilsMenu {
@@ -25,6 +85,8 @@ impl UtilsMenu {
         let absm_editor;
         let animation_editor;
         let ragdoll_wizard;
+        let pack_assets;
+        let new_sound_bank;
         let menu = create_root_menu_item(
             "Utils",
             vec![
@@ -48,10 +110,34 @@ impl UtilsMenu {
                     ragdoll_wizard = create_menu_item("Ragdoll Wizard", vec![], ctx);
                     ragdoll_wizard
                 },
+                {
+                    pack_assets = create_menu_item("Pack Assets", vec![], ctx);
+                    pack_assets
+                },
+                {
+                    new_sound_bank = create_menu_item("New Sound Bank", vec![], ctx);
+                    new_sound_bank
+                },
             ],
             ctx,
         );
 
+        let assets_directory_selector = FileSelectorBuilder::new(
+            WindowBuilder::new(WidgetBuilder::new().with_width(300.0).with_height(400.0))
+                .open(false)
+                .with_title(WindowTitle::text("Select Assets Directory")),
+        )
+        .with_filter(Filter::new(|p: &Path| p.is_dir()))
+        .build(ctx);
+
+        let sound_bank_selector = create_file_selector(
+            ctx,
+            "soundbank",
+            FileBrowserMode::Save {
+                default_file_name: PathBuf::from("unnamed.soundbank"),
+            },
+        );
+
         Self {
             menu,
             open_path_fixer,
@@ -59,6 +145,10 @@ impl UtilsMenu {
             absm_editor,
             animation_editor,
             ragdoll_wizard,
+            pack_assets,
+            assets_directory_selector,
+            new_sound_bank,
+            sound_bank_selector,
         }
     }
 
@@ -78,6 +168,34 @@ impl UtilsMenu {
                 panels.animation_editor.open(ui);
             } else if message.destination() == self.ragdoll_wizard {
                 panels.ragdoll_wizard.open(ui);
+            } else if message.destination() == self.pack_assets {
+                ui.send_message(FileSelectorMessage::root(
+                    self.assets_directory_selector,
+                    MessageDirection::ToWidget,
+                    Some(std::env::current_dir().unwrap()),
+                ));
+                ui.send_message(WindowMessage::open_modal(
+                    self.assets_directory_selector,
+                    MessageDirection::ToWidget,
+                    true,
+                ));
+            } else if message.destination() == self.new_sound_bank {
+                ui.send_message(FileSelectorMessage::root(
+                    self.sound_bank_selector,
+                    MessageDirection::ToWidget,
+                    Some(std::env::current_dir().unwrap()),
+                ));
+                ui.send_message(WindowMessage::open_modal(
+                    self.sound_bank_selector,
+                    MessageDirection::ToWidget,
+                    true,
+                ));
+            }
+        } else if let Some(FileSelectorMessage::Commit(path)) = message.data() {
+            if message.destination() == self.sound_bank_selector {
+                create_sound_bank(path);
+            } else if message.destination() == self.assets_directory_selector {
+                pack_assets(path);
             }
         }
     }
@@ -76,6 +76,7 @@ pub enum Message {
     ShowDocumentation(String),
     SaveLayout,
     LoadLayout,
+    SetUiScale(f32),
 }
 
 #[derive(Clone, Debug)]
@@ -11,6 +11,7 @@ use fyrox::{
         grid::{Column, GridBuilder, Row},
         message::{MessageDirection, UiMessage},
         numeric::{NumericUpDownBuilder, NumericUpDownMessage},
+        scroll_bar::{ScrollBarBuilder, ScrollBarMessage},
         text::TextBuilder,
         widget::WidgetBuilder,
         window::{WindowBuilder, WindowMessage, WindowTitle},
@@ -25,11 +26,13 @@ pub struct ParticleSystemPreviewControlPanel {
     play: Handle<UiNode>,
     pause: Handle<UiNode>,
     stop: Handle<UiNode>,
-    rewind: Handle<UiNode>,
+    restart: Handle<UiNode>,
     time: Handle<UiNode>,
     set_time: Handle<UiNode>,
+    time_scale: Handle<UiNode>,
     particle_systems_state: Vec<(Handle<Node>, Node)>,
     desired_playback_time: f32,
+    desired_time_scale: f32,
 }
 
 impl ParticleSystemPreviewControlPanel {
@@ -38,7 +41,7 @@ impl ParticleSystemPreviewControlPanel {
         let play;
         let pause;
         let stop;
-        let rewind;
+        let restart;
 
         let grid = GridBuilder::new(
             WidgetBuilder::new()
@@ -96,15 +99,15 @@ impl ParticleSystemPreviewControlPanel {
                     stop
                 })
                 .with_child({
-                    rewind = ButtonBuilder::new(
+                    restart = ButtonBuilder::new(
                         WidgetBuilder::new()
                             .on_row(0)
                             .on_column(4)
                             .with_margin(Thickness::uniform(1.0)),
                     )
-                    .with_text("Rewind")
+                    .with_text("Restart")
                     .build(ctx);
-                    rewind
+                    restart
                 }),
         )
         .add_row(Row::stretch())
@@ -117,64 +120,104 @@ impl ParticleSystemPreviewControlPanel {
 
         let time;
         let set_time;
+        let time_scale;
         let window = WindowBuilder::new(
             WidgetBuilder::new()
                 .with_name("ParticleSystemPanel")
                 .with_width(300.0)
-                .with_height(70.0),
+                .with_height(92.0),
         )
         .open(false)
         .with_title(WindowTitle::text("Particle System"))
         .with_content(
             GridBuilder::new(
-                WidgetBuilder::new().with_child(grid).with_child(
-                    GridBuilder::new(
-                        WidgetBuilder::new()
-                            .on_row(1)
-                            .on_column(0)
-                            .with_child(
-                                TextBuilder::new(
-                                    WidgetBuilder::new()
-                                        .on_column(0)
-                                        .with_vertical_alignment(VerticalAlignment::Center)
-                                        .with_margin(Thickness::uniform(1.0)),
-                                )
-                                .with_text("Playback Time")
-                                .build(ctx),
-                            )
-                            .with_child({
-                                time = NumericUpDownBuilder::new(
-                                    WidgetBuilder::new()
-                                        .on_column(1)
-                                        .with_margin(Thickness::uniform(1.0)),
-                                )
-                                .with_min_value(0.0f32)
-                                .with_max_value(10.0 * 60.0) // 10 Minutes
-                                .with_value(0.0f32)
-                                .build(ctx);
-                                time
-                            })
-                            .with_child({
-                                set_time = ButtonBuilder::new(
-                                    WidgetBuilder::new()
-                                        .on_column(2)
-                                        .with_width(33.0)
-                                        .with_margin(Thickness::uniform(1.0)),
+                WidgetBuilder::new()
+                    .with_child(grid)
+                    .with_child(
+                        GridBuilder::new(
+                            WidgetBuilder::new()
+                                .on_row(1)
+                                .on_column(0)
+                                .with_child(
+                                    TextBuilder::new(
+                                        WidgetBuilder::new()
+                                            .on_column(0)
+                                            .with_vertical_alignment(VerticalAlignment::Center)
+                                            .with_margin(Thickness::uniform(1.0)),
+                                    )
+                                    .with_text("Playback Time")
+                                    .build(ctx),
                                 )
-                                .with_text("Set")
-                                .build(ctx);
-                                set_time
-                            }),
+                                .with_child({
+                                    time = NumericUpDownBuilder::new(
+                                        WidgetBuilder::new()
+                                            .on_column(1)
+                                            .with_margin(Thickness::uniform(1.0)),
+                                    )
+                                    .with_min_value(0.0f32)
+                                    .with_max_value(10.0 * 60.0) // 10 Minutes
+                                    .with_value(0.0f32)
+                                    .build(ctx);
+                                    time
+                                })
+                                .with_child({
+                                    set_time = ButtonBuilder::new(
+                                        WidgetBuilder::new()
+                                            .on_column(2)
+                                            .with_width(33.0)
+                                            .with_margin(Thickness::uniform(1.0)),
+                                    )
+                                    .with_text("Set")
+                                    .build(ctx);
+                                    set_time
+                                }),
+                        )
+                        .add_row(Row::stretch())
+                        .add_column(Column::auto())
+                        .add_column(Column::stretch())
+                        .add_column(Column::auto())
+                        .build(ctx),
                     )
-                    .add_row(Row::stretch())
-                    .add_column(Column::auto())
-                    .add_column(Column::stretch())
-                    .add_column(Column::auto())
-                    .build(ctx),
-                ),
+                    .with_child(
+                        GridBuilder::new(
+                            WidgetBuilder::new()
+                                .on_row(2)
+                                .on_column(0)
+                                .with_child(
+                                    TextBuilder::new(
+                                        WidgetBuilder::new()
+                                            .on_column(0)
+                                            .with_vertical_alignment(VerticalAlignment::Center)
+                                            .with_margin(Thickness::uniform(1.0)),
+                                    )
+                                    .with_text("Time Scale")
+                                    .build(ctx),
+                                )
+                                .with_child({
+                                    time_scale = ScrollBarBuilder::new(
+                                        WidgetBuilder::new()
+                                            .on_column(1)
+                                            .with_margin(Thickness::uniform(1.0)),
+                                    )
+                                    .with_min(0.0)
+                                    .with_max(4.0)
+                                    .with_step(0.1)
+                                    .with_value(1.0)
+                                    .show_value(true)
+                                    .with_value_precision(1)
+                                    .build(ctx);
+                                    time_scale
+                                }),
+                        )
+                        .add_row(Row::stretch())
+                        .add_column(Column::auto())
+                        .add_column(Column::stretch())
+                        .build(ctx),
+                    ),
             )
             .add_row(Row::stretch())
             .add_row(Row::stretch())
+            .add_row(Row::stretch())
             .add_column(Column::stretch())
             .build(ctx),
         )
@@ -185,12 +228,14 @@ impl ParticleSystemPreviewControlPanel {
             play,
             pause,
             stop,
-            rewind,
+            restart,
             time,
             preview,
             particle_systems_state: Default::default(),
             set_time,
+            time_scale,
             desired_playback_time: 0.0,
+            desired_time_scale: 1.0,
         }
     }
 
@@ -229,11 +274,25 @@ impl ParticleSystemPreviewControlPanel {
         }
     }
 
+    /// Simulates previewed particle systems. They are not updated by the engine in the preview mode, because
+    /// they must be simulated with custom time scale.
+    pub fn update(&mut self, dt: f32, editor_scene: &EditorScene, engine: &mut Engine) {
+        let scene = &mut engine.scenes[editor_scene.scene];
+
+        for (particle_system_handle, _) in self.particle_systems_state.iter() {
+            if let Some(particle_system) = scene
+                .graph
+                .try_get_mut_of_type::<ParticleSystem>(*particle_system_handle)
+            {
+                particle_system.update_simulation(dt * self.desired_time_scale);
+            }
+        }
+    }
+
     fn enter_preview_mode(&mut self, editor_scene: &mut EditorScene, engine: &mut Engine) {
         assert!(self.particle_systems_state.is_empty());
 
         let scene = &engine.scenes[editor_scene.scene];
-        let node_overrides = editor_scene.graph_switches.node_overrides.as_mut().unwrap();
 
         if let Selection::Graph(ref new_graph_selection) = editor_scene.selection {
             // Enable particle systems from new selection.
@@ -245,8 +304,6 @@ impl ParticleSystemPreviewControlPanel {
                 {
                     self.particle_systems_state
                         .push((node_handle, scene.graph[node_handle].clone_box()));
-
-                    assert!(node_overrides.insert(node_handle));
                 }
             }
         }
@@ -254,12 +311,9 @@ impl ParticleSystemPreviewControlPanel {
 
     pub fn leave_preview_mode(&mut self, editor_scene: &mut EditorScene, engine: &mut Engine) {
         let scene = &mut engine.scenes[editor_scene.scene];
-        let node_overrides = editor_scene.graph_switches.node_overrides.as_mut().unwrap();
 
         for (particle_system_handle, original) in self.particle_systems_state.drain(..) {
             scene.graph[particle_system_handle] = original;
-
-            assert!(node_overrides.remove(&particle_system_handle));
         }
 
         send_sync_message(
@@ -293,8 +347,9 @@ impl ParticleSystemPreviewControlPanel {
                         } else if message.destination() == self.stop {
                             particle_system.play(false);
                             particle_system.clear_particles();
-                        } else if message.destination() == self.rewind {
+                        } else if message.destination() == self.restart {
                             particle_system.clear_particles();
+                            particle_system.play(true);
                         } else if message.destination() == self.set_time {
                             particle_system.rewind(FIXED_TIMESTEP, self.desired_playback_time);
                         }
@@ -317,6 +372,12 @@ impl ParticleSystemPreviewControlPanel {
                 {
                     self.desired_playback_time = *desired_playback_time;
                 }
+            } else if let Some(ScrollBarMessage::Value(time_scale)) = message.data() {
+                if message.destination() == self.time_scale
+                    && message.direction() == MessageDirection::FromWidget
+                {
+                    self.desired_time_scale = *time_scale;
+                }
             }
         }
     }
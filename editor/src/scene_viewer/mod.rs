@@ -31,6 +31,7 @@ use fyrox::{
         image::{ImageBuilder, ImageMessage},
         message::{KeyCode, MessageDirection, MouseButton, UiMessage},
         stack_panel::StackPanelBuilder,
+        style::Style,
         tab_control::{
             Tab, TabControl, TabControlBuilder, TabControlMessage, TabDefinition, TabUserData,
         },
@@ -40,8 +41,7 @@ use fyrox::{
         widget::{WidgetBuilder, WidgetMessage},
         window::{WindowBuilder, WindowMessage, WindowTitle},
         BuildContext, HorizontalAlignment, Orientation, Thickness, UiNode, UserInterface,
-        VerticalAlignment, BRUSH_BRIGHT_BLUE, BRUSH_DARKER, BRUSH_DARKEST, BRUSH_LIGHT,
-        BRUSH_LIGHTER, BRUSH_LIGHTEST,
+        VerticalAlignment,
     },
     resource::{
         model::{Model, ModelResourceExtension},
@@ -109,13 +109,13 @@ fn make_interaction_mode_button(
     )
     .with_back(
         DecoratorBuilder::new(
-            BorderBuilder::new(WidgetBuilder::new().with_foreground(BRUSH_DARKER))
+            BorderBuilder::new(WidgetBuilder::new().with_foreground_style(Style::BRUSH_DARKER))
                 .with_stroke_thickness(Thickness::uniform(1.0)),
         )
-        .with_normal_brush(BRUSH_LIGHT)
-        .with_hover_brush(BRUSH_LIGHTER)
-        .with_pressed_brush(BRUSH_LIGHTEST)
-        .with_selected_brush(BRUSH_BRIGHT_BLUE)
+        .with_normal_brush_style(Style::BRUSH_LIGHT)
+        .with_hover_brush_style(Style::BRUSH_LIGHTER)
+        .with_pressed_brush_style(Style::BRUSH_LIGHTEST)
+        .with_selected_brush_style(Style::BRUSH_BRIGHT_BLUE)
         .with_selected(selected)
         .build(ctx),
     )
@@ -366,7 +366,7 @@ impl SceneViewer {
         let no_scene_reminder = TextBuilder::new(
             WidgetBuilder::new()
                 .with_hit_test_visibility(false)
-                .with_foreground(BRUSH_DARKEST),
+                .with_foreground_style(Style::BRUSH_DARKEST),
         )
         .with_text("No scene loaded. Create a new scene (File -> New Scene) or load existing (File -> Load Scene)")
         .with_vertical_text_alignment(VerticalAlignment::Center)
@@ -396,8 +396,9 @@ impl SceneViewer {
                         .on_row(0)
                         .with_child(top_ribbon)
                         .with_child({
-                            tab_control =
-                                TabControlBuilder::new(WidgetBuilder::new().on_row(1)).build(ctx);
+                            tab_control = TabControlBuilder::new(WidgetBuilder::new().on_row(1))
+                                .with_tab_reordering(true)
+                                .build(ctx);
                             tab_control
                         })
                         .with_child(
@@ -616,6 +617,10 @@ impl SceneViewer {
                                 .send(Message::SetCurrentScene(entry.editor_scene.scene));
                         }
                     }
+                    &TabControlMessage::MoveTab { from, to } => {
+                        // Keep the order of the scenes in sync with the tabs.
+                        scenes.move_scene(from, to);
+                    }
                     _ => (),
                 }
             }
@@ -0,0 +1,26 @@
+use fyrox::core::{algebra::Vector2, reflect::prelude::*};
+use serde::{Deserialize, Serialize};
+
+/// Position and zoom of the view of a canvas of the ABSM editor.
+#[derive(Deserialize, Serialize, PartialEq, Debug, Clone, Reflect)]
+pub struct AbsmCanvasViewSettings {
+    pub position: Vector2<f32>,
+    pub zoom: f32,
+}
+
+impl Default for AbsmCanvasViewSettings {
+    fn default() -> Self {
+        Self {
+            position: Default::default(),
+            zoom: 1.0,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, PartialEq, Debug, Clone, Default, Reflect)]
+pub struct AbsmSettings {
+    #[serde(default)]
+    pub state_graph_view: AbsmCanvasViewSettings,
+    #[serde(default)]
+    pub state_viewer_view: AbsmCanvasViewSettings,
+}
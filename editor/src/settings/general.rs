@@ -1,5 +1,36 @@
-use fyrox::core::reflect::prelude::*;
+use fyrox::{core::reflect::prelude::*, gui::style::Style};
 use serde::{Deserialize, Serialize};
+use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
+
+/// A set of built-in styles of the editor.
+#[derive(
+    Deserialize,
+    Serialize,
+    PartialEq,
+    Eq,
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    Reflect,
+    AsRefStr,
+    EnumString,
+    EnumVariantNames,
+)]
+pub enum EditorStyle {
+    #[default]
+    Dark,
+    Light,
+}
+
+impl EditorStyle {
+    pub fn make_style(self) -> Style {
+        match self {
+            EditorStyle::Dark => Style::dark(),
+            EditorStyle::Light => Style::light(),
+        }
+    }
+}
 
 #[derive(Deserialize, Serialize, PartialEq, Clone, Debug, Reflect)]
 pub struct GeneralSettings {
@@ -14,17 +45,36 @@ pub struct GeneralSettings {
     )]
     #[serde(default = "default_suspension_state")]
     pub suspend_unfocused_editor: bool,
+
+    #[reflect(description = "Visual style of the editor.")]
+    #[serde(default)]
+    pub style: EditorStyle,
+
+    #[reflect(
+        description = "Scale of the editor UI. It is multiplied with the scale factor (DPI) of the main window of the editor.",
+        min_value = 0.5,
+        max_value = 4.0,
+        step = 0.05
+    )]
+    #[serde(default = "default_ui_scale")]
+    pub ui_scale: f32,
 }
 
 fn default_suspension_state() -> bool {
     true
 }
 
+fn default_ui_scale() -> f32 {
+    1.0
+}
+
 impl Default for GeneralSettings {
     fn default() -> Self {
         Self {
             show_node_removal_dialog: true,
             suspend_unfocused_editor: default_suspension_state(),
+            style: Default::default(),
+            ui_scale: default_ui_scale(),
         }
     }
 }
@@ -2,13 +2,23 @@ use crate::{
     inspector::editors::make_property_editors_container,
     message::MessageSender,
     settings::{
-        camera::CameraSettings, debugging::DebuggingSettings, general::GeneralSettings,
-        graphics::GraphicsSettings, keys::KeyBindings, keys::TerrainKeyBindings,
-        model::ModelSettings, move_mode::MoveInteractionModeSettings, navmesh::NavmeshSettings,
-        recent::RecentFiles, rotate_mode::RotateInteractionModeSettings, scene::SceneSettings,
-        selection::SelectionSettings, windows::WindowsSettings,
+        absm::AbsmSettings,
+        camera::CameraSettings,
+        debugging::DebuggingSettings,
+        general::{EditorStyle, GeneralSettings},
+        graphics::GraphicsSettings,
+        keys::KeyBindings,
+        keys::TerrainKeyBindings,
+        model::ModelSettings,
+        move_mode::MoveInteractionModeSettings,
+        navmesh::NavmeshSettings,
+        recent::RecentFiles,
+        rotate_mode::RotateInteractionModeSettings,
+        scene::SceneSettings,
+        selection::SelectionSettings,
+        windows::WindowsSettings,
     },
-    Engine, MSG_SYNC_FLAG,
+    Engine, Message, MSG_SYNC_FLAG,
 };
 use fyrox::{
     core::{log::Log, pool::Handle, reflect::prelude::*, scope_profile},
@@ -42,6 +52,7 @@ use std::{
     rc::Rc,
 };
 
+pub mod absm;
 pub mod camera;
 pub mod debugging;
 pub mod general;
@@ -83,6 +94,9 @@ pub struct SettingsData {
     #[serde(default)]
     #[reflect(hidden)]
     pub windows: WindowsSettings,
+    #[serde(default)]
+    #[reflect(hidden)]
+    pub absm: AbsmSettings,
 }
 
 #[derive(Default)]
@@ -181,6 +195,7 @@ impl SettingsData {
         container.insert(InspectablePropertyEditorDefinition::<GraphicsSettings>::new());
         container.insert(InspectablePropertyEditorDefinition::<SelectionSettings>::new());
         container.insert(EnumPropertyEditorDefinition::<ShadowMapPrecision>::new());
+        container.insert(EnumPropertyEditorDefinition::<EditorStyle>::new());
         container.insert(InspectablePropertyEditorDefinition::<DebuggingSettings>::new());
         container.insert(InspectablePropertyEditorDefinition::<CsmSettings>::new());
         container.insert(InspectablePropertyEditorDefinition::<QualitySettings>::new());
@@ -316,6 +331,8 @@ impl SettingsWindow {
         scope_profile!();
 
         let mut need_save = false;
+        let prev_style = settings.general.style;
+        let prev_ui_scale = settings.general.ui_scale;
 
         if let Some(ButtonMessage::Click) = message.data::<ButtonMessage>() {
             if message.destination() == self.ok {
@@ -338,6 +355,16 @@ impl SettingsWindow {
         let graphics_context = engine.graphics_context.as_initialized_mut();
 
         if need_save {
+            if settings.general.style != prev_style {
+                engine
+                    .user_interface
+                    .set_style(settings.general.style.make_style());
+            }
+
+            if settings.general.ui_scale != prev_ui_scale {
+                sender.send(Message::SetUiScale(settings.general.ui_scale));
+            }
+
             if settings.graphics.quality != graphics_context.renderer.get_quality_settings() {
                 if let Err(e) = graphics_context
                     .renderer
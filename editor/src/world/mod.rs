@@ -31,19 +31,20 @@ use fyrox::{
         grid::{Column, GridBuilder, Row},
         message::{MessageDirection, UiMessage},
         scroll_viewer::{ScrollViewerBuilder, ScrollViewerMessage},
-        searchbar::{SearchBarBuilder, SearchBarMessage},
+        searchbar::{filter_hierarchy, SearchBarBuilder, SearchBarMessage},
         stack_panel::StackPanelBuilder,
+        style::Style,
         text::TextBuilder,
         tree::{
-            TreeBuilder, TreeExpansionStrategy, TreeMessage, TreeRoot, TreeRootBuilder,
-            TreeRootMessage,
+            TreeBuilder, TreeDropPosition, TreeExpansionStrategy, TreeMessage, TreeRoot,
+            TreeRootBuilder, TreeRootMessage,
         },
         ttf::{FontBuilder, SharedFont},
         widget::{WidgetBuilder, WidgetMessage},
         window::{WindowBuilder, WindowTitle},
         wrap_panel::WrapPanelBuilder,
         BuildContext, Orientation, RcUiNodeHandle, Thickness, UiNode, UserInterface,
-        VerticalAlignment, BRUSH_BRIGHT_BLUE, BRUSH_PRIMARY,
+        VerticalAlignment,
     },
     scene::{graph::Graph, node::Node, Scene},
 };
@@ -190,6 +191,7 @@ impl WorldViewer {
                 .on_row(1)
                 .with_margin(Thickness::uniform(1.0)),
         )
+        .with_debounce(0.25)
         .build(ctx);
         let size = 15.0;
         let window = WindowBuilder::new(WidgetBuilder::new().with_name("WorldOutliner"))
@@ -343,10 +345,10 @@ impl WorldViewer {
         let element = ButtonBuilder::new(WidgetBuilder::new().with_height(16.0))
             .with_back(
                 DecoratorBuilder::new(BorderBuilder::new(
-                    WidgetBuilder::new().with_foreground(BRUSH_PRIMARY),
+                    WidgetBuilder::new().with_foreground_style(Style::BRUSH_PRIMARY),
                 ))
-                .with_normal_brush(BRUSH_PRIMARY)
-                .with_hover_brush(BRUSH_BRIGHT_BLUE)
+                .with_normal_brush_style(Style::BRUSH_PRIMARY)
+                .with_hover_brush_style(Style::BRUSH_BRIGHT_BLUE)
                 .build(ctx),
             )
             .with_content(
@@ -554,31 +556,13 @@ impl WorldViewer {
     }
 
     fn apply_filter(&self, editor_scene: &EditorScene, ui: &UserInterface) {
-        fn apply_filter_recursive(node: Handle<UiNode>, filter: &str, ui: &UserInterface) -> bool {
-            let node_ref = ui.node(node);
-
-            let mut is_any_match = false;
-            for &child in node_ref.children() {
-                is_any_match |= apply_filter_recursive(child, filter, ui)
-            }
-
-            let name = node_ref.cast::<SceneItem>().map(|i| i.name());
-
-            if let Some(name) = name {
-                is_any_match |= name.to_lowercase().contains(filter)
-                    || fuzzy_compare(filter, name.to_lowercase().as_str()) >= 0.33;
-
-                ui.send_message(WidgetMessage::visibility(
-                    node,
-                    MessageDirection::ToWidget,
-                    is_any_match,
-                ));
-            }
-
-            is_any_match
-        }
-
-        apply_filter_recursive(self.tree_root, &self.filter.to_lowercase(), ui);
+        let filter = self.filter.to_lowercase();
+        filter_hierarchy(ui, self.tree_root, &|node| {
+            node.cast::<SceneItem>().map(|item| {
+                let name = item.name().to_lowercase();
+                name.contains(&filter) || fuzzy_compare(&filter, &name) >= 0.33
+            })
+        });
 
         if self.filter.is_empty() {
             if let Selection::Graph(ref selection) = editor_scene.selection {
@@ -623,8 +607,10 @@ impl WorldViewer {
             {
                 self.handle_selection(selection, editor_scene, engine);
             }
-        } else if let Some(&WidgetMessage::Drop(node)) = message.data::<WidgetMessage>() {
-            self.handle_drop(engine, editor_scene, message.destination(), node);
+        } else if let Some(&TreeMessage::Drop { what, position }) = message.data() {
+            if message.direction() == MessageDirection::FromWidget {
+                self.handle_drop(engine, editor_scene, message.destination(), what, position);
+            }
         } else if let Some(ButtonMessage::Click) = message.data::<ButtonMessage>() {
             if let Some(&view) = self.breadcrumbs.get(&message.destination()) {
                 if let Some(graph_node) = engine
@@ -760,12 +746,14 @@ impl WorldViewer {
 
     /// `target` - is a node at which `dropped` was dropped.
     /// `dropped` - is a node which was dropped at `target`.
+    /// `position` - defines whether `dropped` should become a child or a sibling of `target`.
     fn handle_drop(
         &self,
         engine: &Engine,
         editor_scene: &EditorScene,
         target: Handle<UiNode>,
         dropped: Handle<UiNode>,
+        position: TreeDropPosition,
     ) {
         let ui = &engine.user_interface;
 
@@ -779,14 +767,29 @@ impl WorldViewer {
             ) {
                 if let Selection::Graph(ref selection) = editor_scene.selection {
                     if selection.nodes.contains(&child.entity_handle) {
+                        let graph = &engine.scenes[editor_scene.scene].graph;
+
+                        // Dropping before or after an item makes the dropped nodes its siblings. The
+                        // root node cannot have siblings, so fall back to attaching to it.
+                        let new_parent = match position {
+                            TreeDropPosition::Inside => parent.entity_handle,
+                            TreeDropPosition::Before | TreeDropPosition::After => {
+                                let graph_parent = graph[parent.entity_handle].parent();
+                                if graph_parent.is_some() {
+                                    graph_parent
+                                } else {
+                                    parent.entity_handle
+                                }
+                            }
+                        };
+
                         let mut commands = Vec::new();
 
                         for &node_handle in selection.nodes.iter() {
                             // Make sure we won't create any loops - child must not have parent in its
                             // descendants.
                             let mut attach = true;
-                            let graph = &engine.scenes[editor_scene.scene].graph;
-                            let mut p = parent.entity_handle;
+                            let mut p = new_parent;
                             while p.is_some() {
                                 if p == node_handle {
                                     attach = false;
@@ -798,7 +801,7 @@ impl WorldViewer {
                             if attach {
                                 commands.push(SceneCommand::new(LinkNodesCommand::new(
                                     node_handle,
-                                    parent.entity_handle,
+                                    new_parent,
                                 )));
                             }
                         }
@@ -117,6 +117,8 @@ fn quote_field_prop(
 
     let description = field.description.clone().unwrap_or_default();
 
+    let tag = field.tag.clone().unwrap_or_default();
+
     quote! {
         FieldInfo {
             owner_type_id: std::any::TypeId::of::<Self>(),
@@ -132,6 +134,7 @@ fn quote_field_prop(
             step: #step,
             precision: #precision,
             description: #description,
+            tag: #tag,
             type_name: std::any::type_name::<#ty>()
         }
     }
@@ -210,6 +210,12 @@ pub struct FieldArgs {
     /// Description of the property.
     #[darling(default)]
     pub description: Option<String>,
+
+    /// `#[reflect(tag = "ParameterName")]`
+    ///
+    /// Tag of the property, that is used to pick a specialized property editor for it.
+    #[darling(default)]
+    pub tag: Option<String>,
 }
 
 impl FieldArgs {
@@ -1,5 +1,5 @@
 use crate::{
-    math::{cubicf, inf_sup_cubicf, lerpf, Rect},
+    math::{cubicf, inf_sup_cubicf, lerpf, weighted_cubicf, Rect},
     reflect::prelude::*,
     visitor::prelude::*,
 };
@@ -37,12 +37,104 @@ impl CurveKeyKind {
     }
 }
 
+/// Defines how the tangents of a cubic key are edited and whether their lengths affect the
+/// interpolation or not. It is ignored for non-cubic keys.
+#[derive(Visit, Reflect, Clone, Copy, Debug, PartialEq)]
+pub struct TangentMode {
+    /// Broken tangents are edited independently. Otherwise (unified tangents) changing one tangent
+    /// changes the other one as well, so the curve stays smooth at the key.
+    pub broken: bool,
+    /// Whether the weights of the tangents affect the interpolation or not.
+    pub weighted: bool,
+    /// Horizontal length of the left tangent handle as a fraction of the distance to the previous
+    /// key. Used only if the tangents are weighted. Default is `1/3`.
+    pub left_weight: f32,
+    /// Horizontal length of the right tangent handle as a fraction of the distance to the next key.
+    /// Used only if the tangents are weighted. Default is `1/3`.
+    pub right_weight: f32,
+}
+
+impl Default for TangentMode {
+    fn default() -> Self {
+        Self {
+            broken: false,
+            weighted: false,
+            left_weight: Self::DEFAULT_WEIGHT,
+            right_weight: Self::DEFAULT_WEIGHT,
+        }
+    }
+}
+
+impl TangentMode {
+    /// Weight of unweighted tangents, it makes the spline equal to the cubic Hermite spline.
+    pub const DEFAULT_WEIGHT: f32 = 1.0 / 3.0;
+
+    /// Returns the weight of the left tangent used in the interpolation.
+    #[inline]
+    pub fn effective_left_weight(&self) -> f32 {
+        if self.weighted {
+            self.left_weight.clamp(0.01, 1.0)
+        } else {
+            Self::DEFAULT_WEIGHT
+        }
+    }
+
+    /// Returns the weight of the right tangent used in the interpolation.
+    #[inline]
+    pub fn effective_right_weight(&self) -> f32 {
+        if self.weighted {
+            self.right_weight.clamp(0.01, 1.0)
+        } else {
+            Self::DEFAULT_WEIGHT
+        }
+    }
+}
+
+fn cubic_span(p0: f32, p1: f32, t: f32, m0: f32, m1: f32, w0: f32, w1: f32) -> f32 {
+    if w0 == TangentMode::DEFAULT_WEIGHT && w1 == TangentMode::DEFAULT_WEIGHT {
+        cubicf(p0, p1, t, m0, m1)
+    } else {
+        weighted_cubicf(p0, p1, t, m0, m1, w0, w1)
+    }
+}
+
+fn cubic_span_bounds(p0: f32, p1: f32, m0: f32, m1: f32, w0: f32, w1: f32) -> (f32, f32) {
+    if w0 == TangentMode::DEFAULT_WEIGHT && w1 == TangentMode::DEFAULT_WEIGHT {
+        inf_sup_cubicf(p0, p1, m0, m1)
+    } else {
+        // There's no simple analytical solution for weighted spans, so just sample them.
+        const SAMPLES: usize = 32;
+        (0..=SAMPLES)
+            .map(|i| weighted_cubicf(p0, p1, i as f32 / SAMPLES as f32, m0, m1, w0, w1))
+            .fold((f32::MAX, -f32::MAX), |(min, max), y| {
+                (min.min(y), max.max(y))
+            })
+    }
+}
+
+/// Defines how a curve is evaluated outside of the range of its keys.
+#[derive(Visit, Reflect, Default, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CurveExtrapolation {
+    /// The value of the nearest key is used.
+    #[default]
+    Constant,
+    /// The curve continues with the slope of the two nearest keys.
+    Linear,
+    /// The curve repeats itself.
+    Loop,
+    /// The curve repeats itself, every other repetition is mirrored.
+    PingPong,
+}
+
 #[derive(Visit, Clone, Default, Debug, PartialEq)]
 pub struct CurveKey {
     pub id: Uuid,
     location: f32,
     pub value: f32,
     pub kind: CurveKeyKind,
+    /// Editing mode and weights of the tangents, used only by cubic keys.
+    #[visit(optional)]
+    pub tangent_mode: TangentMode,
 }
 
 impl CurveKey {
@@ -53,8 +145,16 @@ impl CurveKey {
             location,
             value,
             kind,
+            tangent_mode: Default::default(),
         }
     }
+
+    /// Sets editing mode and weights of the tangents of the key and returns the key.
+    #[inline]
+    pub fn with_tangent_mode(mut self, tangent_mode: TangentMode) -> Self {
+        self.tangent_mode = tangent_mode;
+        self
+    }
 }
 
 impl CurveKey {
@@ -94,7 +194,15 @@ impl CurveKey {
                     ..
                 },
                 CurveKeyKind::Linear,
-            ) => cubicf(self.value, other.value, t, *left_tangent, 0.0),
+            ) => cubic_span(
+                self.value,
+                other.value,
+                t,
+                *left_tangent,
+                0.0,
+                self.tangent_mode.effective_right_weight(),
+                TangentMode::DEFAULT_WEIGHT,
+            ),
 
             // Cubic-to-cubic
             (
@@ -106,7 +214,15 @@ impl CurveKey {
                     left_tangent: right_tangent,
                     ..
                 },
-            ) => cubicf(self.value, other.value, t, *left_tangent, *right_tangent),
+            ) => cubic_span(
+                self.value,
+                other.value,
+                t,
+                *left_tangent,
+                *right_tangent,
+                self.tangent_mode.effective_right_weight(),
+                other.tangent_mode.effective_left_weight(),
+            ),
         }
     }
 }
@@ -121,6 +237,12 @@ pub struct Curve {
     name: String,
 
     keys: Vec<CurveKey>,
+
+    #[visit(optional)]
+    pre_extrapolation: CurveExtrapolation,
+
+    #[visit(optional)]
+    post_extrapolation: CurveExtrapolation,
 }
 
 impl Default for Curve {
@@ -129,6 +251,8 @@ impl Default for Curve {
             id: Uuid::new_v4(),
             name: Default::default(),
             keys: Default::default(),
+            pre_extrapolation: Default::default(),
+            post_extrapolation: Default::default(),
         }
     }
 }
@@ -152,6 +276,8 @@ impl From<Vec<CurveKey>> for Curve {
             id: Uuid::new_v4(),
             name: Default::default(),
             keys,
+            pre_extrapolation: Default::default(),
+            post_extrapolation: Default::default(),
         }
     }
 }
@@ -177,6 +303,28 @@ impl Curve {
         &self.name
     }
 
+    /// Sets how the curve is evaluated before its first key.
+    #[inline]
+    pub fn set_pre_extrapolation(&mut self, extrapolation: CurveExtrapolation) {
+        self.pre_extrapolation = extrapolation;
+    }
+
+    #[inline]
+    pub fn pre_extrapolation(&self) -> CurveExtrapolation {
+        self.pre_extrapolation
+    }
+
+    /// Sets how the curve is evaluated after its last key.
+    #[inline]
+    pub fn set_post_extrapolation(&mut self, extrapolation: CurveExtrapolation) {
+        self.post_extrapolation = extrapolation;
+    }
+
+    #[inline]
+    pub fn post_extrapolation(&self) -> CurveExtrapolation {
+        self.post_extrapolation
+    }
+
     #[inline]
     pub fn clear(&mut self) {
         self.keys.clear()
@@ -218,6 +366,54 @@ impl Curve {
 
     #[inline]
     pub fn value_at(&self, location: f32) -> f32 {
+        let (Some(first), Some(last)) = (self.keys.first(), self.keys.last()) else {
+            return 0.0;
+        };
+
+        let (extrapolation, slope_keys) = if location < first.location {
+            (self.pre_extrapolation, self.keys.get(0..2))
+        } else if location > last.location {
+            (
+                self.post_extrapolation,
+                self.keys.get(self.keys.len().saturating_sub(2)..),
+            )
+        } else {
+            return self.clamped_value_at(location);
+        };
+
+        let span = last.location - first.location;
+        match extrapolation {
+            CurveExtrapolation::Constant => self.clamped_value_at(location),
+            CurveExtrapolation::Linear => match slope_keys {
+                Some([left, right]) if right.location > left.location => {
+                    let slope = (right.value - left.value) / (right.location - left.location);
+                    let nearest = if location < first.location {
+                        first
+                    } else {
+                        last
+                    };
+                    nearest.value + (location - nearest.location) * slope
+                }
+                _ => self.clamped_value_at(location),
+            },
+            _ if span <= f32::EPSILON => first.value,
+            CurveExtrapolation::Loop => {
+                self.clamped_value_at(first.location + (location - first.location).rem_euclid(span))
+            }
+            CurveExtrapolation::PingPong => {
+                let offset = (location - first.location).rem_euclid(2.0 * span);
+                let offset = if offset > span {
+                    2.0 * span - offset
+                } else {
+                    offset
+                };
+                self.clamped_value_at(first.location + offset)
+            }
+        }
+    }
+
+    #[inline]
+    fn clamped_value_at(&self, location: f32) -> f32 {
         if let (Some(first), Some(last)) = (self.keys.first(), self.keys.last()) {
             if location <= first.location {
                 first.value
@@ -278,7 +474,14 @@ impl Curve {
                     },
                     CurveKeyKind::Linear,
                 ) => {
-                    let (y0, y1) = inf_sup_cubicf(left.value, right.value, *left_tangent, 0.0);
+                    let (y0, y1) = cubic_span_bounds(
+                        left.value,
+                        right.value,
+                        *left_tangent,
+                        0.0,
+                        left.tangent_mode.effective_right_weight(),
+                        TangentMode::DEFAULT_WEIGHT,
+                    );
                     push(left.location, y0);
                     push(right.location, y1);
                 }
@@ -294,8 +497,14 @@ impl Curve {
                         ..
                     },
                 ) => {
-                    let (y0, y1) =
-                        inf_sup_cubicf(left.value, right.value, *left_tangent, *right_tangent);
+                    let (y0, y1) = cubic_span_bounds(
+                        left.value,
+                        right.value,
+                        *left_tangent,
+                        *right_tangent,
+                        left.tangent_mode.effective_right_weight(),
+                        right.tangent_mode.effective_left_weight(),
+                    );
                     push(left.location, y0);
                     push(right.location, y1);
                 }
@@ -314,7 +523,34 @@ impl Curve {
 mod test {
     use uuid::Uuid;
 
-    use crate::curve::{Curve, CurveKey, CurveKeyKind};
+    use crate::curve::{Curve, CurveExtrapolation, CurveKey, CurveKeyKind, TangentMode};
+
+    #[test]
+    fn test_curve_extrapolation() {
+        let mut curve = Curve::from(vec![
+            CurveKey::new(0.0, 0.0, CurveKeyKind::Linear),
+            CurveKey::new(1.0, 2.0, CurveKeyKind::Linear),
+        ]);
+
+        assert_eq!(curve.value_at(-1.0), 0.0);
+        assert_eq!(curve.value_at(2.0), 2.0);
+
+        curve.set_pre_extrapolation(CurveExtrapolation::Linear);
+        curve.set_post_extrapolation(CurveExtrapolation::Linear);
+        assert_eq!(curve.value_at(-1.0), -2.0);
+        assert_eq!(curve.value_at(2.0), 4.0);
+
+        curve.set_pre_extrapolation(CurveExtrapolation::Loop);
+        curve.set_post_extrapolation(CurveExtrapolation::Loop);
+        assert_eq!(curve.value_at(-0.25), 1.5);
+        assert_eq!(curve.value_at(1.25), 0.5);
+
+        curve.set_pre_extrapolation(CurveExtrapolation::PingPong);
+        curve.set_post_extrapolation(CurveExtrapolation::PingPong);
+        assert_eq!(curve.value_at(-0.25), 0.5);
+        assert_eq!(curve.value_at(1.25), 1.5);
+        assert_eq!(curve.value_at(2.25), 0.5);
+    }
 
     #[test]
     fn test_curve_key_insertion_order() {
@@ -429,11 +665,32 @@ mod test {
             CurveKeyKind::new_cubic(0.0, 0.0),
             CurveKeyKind::Cubic {
                 left_tangent: 0.0,
-                right_tangent: 0.0
+                right_tangent: 0.0,
             }
         );
     }
 
+    #[test]
+    fn test_weighted_cubic_keys() {
+        let key = CurveKey::new(0.0, 0.0, CurveKeyKind::new_cubic(0.5, 0.5));
+        let next = CurveKey::new(1.0, 1.0, CurveKeyKind::new_cubic(-0.5, -0.5));
+
+        let mut weighted = key.clone();
+        weighted.tangent_mode.weighted = true;
+        // Default weights match the unweighted spline.
+        for t in [0.0, 0.3, 0.7, 1.0] {
+            assert!((key.interpolate(&next, t) - weighted.interpolate(&next, t)).abs() < 1.0e-4);
+        }
+
+        weighted.tangent_mode.right_weight = 0.9;
+        assert_ne!(
+            key.interpolate(&next, 0.5),
+            weighted.interpolate(&next, 0.5)
+        );
+        assert_eq!(weighted.interpolate(&next, 0.0), 0.0);
+        assert_eq!(weighted.interpolate(&next, 1.0), 1.0);
+    }
+
     #[test]
     fn test_curve_key() {
         assert_eq!(
@@ -443,6 +700,7 @@ mod test {
                 location: 0.0,
                 value: 0.0,
                 kind: CurveKeyKind::Constant,
+                tangent_mode: TangentMode::default(),
             },
         );
 
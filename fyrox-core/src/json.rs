@@ -0,0 +1,540 @@
+//! Minimal JSON reader and writer.
+//!
+//! It is used for data exchange formats (glTF documents, exported curve keys, etc.) and intentionally has no
+//! serde integration: values are accessed dynamically via [`JsonValue`] methods.
+
+use std::fmt::{Display, Formatter, Write};
+
+/// Maximum nesting depth of arrays and objects, it protects the parser from stack overflow on malicious input.
+const MAX_DEPTH: usize = 256;
+
+/// JSON value.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum JsonValue {
+    /// `null` literal.
+    #[default]
+    Null,
+    /// `true` or `false` literal.
+    Bool(bool),
+    /// Any number, JSON does not distinguish integers and floating-point numbers.
+    Number(f64),
+    /// String with all the escape sequences decoded.
+    String(String),
+    /// Ordered list of values.
+    Array(Vec<JsonValue>),
+    /// Members of an object in the order they appear in the source.
+    Object(Vec<(String, JsonValue)>),
+}
+
+/// An error that could occur while parsing a JSON document.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct JsonError {
+    /// Line of the error, starts from 1.
+    pub line: usize,
+    /// Column (in characters) of the error, starts from 1.
+    pub column: usize,
+    /// Description of the error.
+    pub reason: String,
+}
+
+impl Display for JsonError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} at line {}, column {}",
+            self.reason, self.line, self.column
+        )
+    }
+}
+
+impl std::error::Error for JsonError {}
+
+impl JsonValue {
+    /// Parses the given text as a single JSON value, the text may contain whitespace around the value only.
+    pub fn parse(text: &str) -> Result<Self, JsonError> {
+        let mut parser = Parser { text, position: 0 };
+        let value = parser.parse_value(0)?;
+        parser.skip_whitespace();
+        if parser.position < text.len() {
+            return Err(parser.error("Unexpected trailing characters"));
+        }
+        Ok(value)
+    }
+
+    /// Returns a member of an object with the given name. Returns `None` if the value is not an object or it
+    /// has no such member.
+    pub fn get(&self, name: &str) -> Option<&JsonValue> {
+        self.as_object()?
+            .iter()
+            .find_map(|(member, value)| (member == name).then_some(value))
+    }
+
+    /// Returns `true` if the value is `null`.
+    pub fn is_null(&self) -> bool {
+        matches!(self, Self::Null)
+    }
+
+    /// Returns the value of a boolean.
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Self::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Returns the value of a number.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Self::Number(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// Returns the value of a number converted to `f32`.
+    pub fn as_f32(&self) -> Option<f32> {
+        self.as_f64().map(|value| value as f32)
+    }
+
+    /// Returns the value of a number, if it is a non-negative integer that fits `usize`.
+    pub fn as_usize(&self) -> Option<usize> {
+        let value = self.as_f64()?;
+        (value >= 0.0 && value.fract() == 0.0 && value <= usize::MAX as f64)
+            .then_some(value as usize)
+    }
+
+    /// Returns the value of a string.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the items of an array.
+    pub fn as_array(&self) -> Option<&[JsonValue]> {
+        match self {
+            Self::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    /// Returns the members of an object.
+    pub fn as_object(&self) -> Option<&[(String, JsonValue)]> {
+        match self {
+            Self::Object(members) => Some(members),
+            _ => None,
+        }
+    }
+
+    /// Writes the value in a human-readable form: every item of arrays and objects is placed on its own
+    /// line and indented by two spaces. Use [`ToString`] to get a compact form.
+    pub fn to_string_pretty(&self) -> String {
+        let mut string = String::new();
+        self.write(&mut string, Some(0))
+            .expect("Writing to a string never fails");
+        string
+    }
+
+    fn write(&self, out: &mut impl Write, indent: Option<usize>) -> std::fmt::Result {
+        let new_line = |out: &mut dyn Write, level: usize| -> std::fmt::Result {
+            if indent.is_some() {
+                write!(out, "\n{:1$}", "", level * 2)
+            } else {
+                Ok(())
+            }
+        };
+        let level = indent.unwrap_or_default();
+        let nested = indent.map(|level| level + 1);
+
+        match self {
+            Self::Null => out.write_str("null"),
+            Self::Bool(value) => write!(out, "{value}"),
+            // JSON has no representation for infinities and NaN.
+            Self::Number(value) if !value.is_finite() => out.write_str("null"),
+            Self::Number(value) => write!(out, "{value}"),
+            Self::String(value) => write_string(out, value),
+            Self::Array(items) if items.is_empty() => out.write_str("[]"),
+            Self::Array(items) => {
+                out.write_char('[')?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        out.write_char(',')?;
+                    }
+                    new_line(out, level + 1)?;
+                    item.write(out, nested)?;
+                }
+                new_line(out, level)?;
+                out.write_char(']')
+            }
+            Self::Object(members) if members.is_empty() => out.write_str("{}"),
+            Self::Object(members) => {
+                out.write_char('{')?;
+                for (i, (name, value)) in members.iter().enumerate() {
+                    if i > 0 {
+                        out.write_char(',')?;
+                    }
+                    new_line(out, level + 1)?;
+                    write_string(out, name)?;
+                    out.write_str(if indent.is_some() { ": " } else { ":" })?;
+                    value.write(out, nested)?;
+                }
+                new_line(out, level)?;
+                out.write_char('}')
+            }
+        }
+    }
+}
+
+impl Display for JsonValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.write(f, None)
+    }
+}
+
+impl From<bool> for JsonValue {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+impl From<f64> for JsonValue {
+    fn from(value: f64) -> Self {
+        Self::Number(value)
+    }
+}
+
+impl From<f32> for JsonValue {
+    fn from(value: f32) -> Self {
+        // Go through the shortest decimal representation of the value, so `0.1f32` is written as `0.1` and not
+        // as `0.10000000149011612`. It still converts back to the same `f32`.
+        Self::Number(value.to_string().parse().unwrap_or(value as f64))
+    }
+}
+
+impl From<usize> for JsonValue {
+    fn from(value: usize) -> Self {
+        Self::Number(value as f64)
+    }
+}
+
+impl From<&str> for JsonValue {
+    fn from(value: &str) -> Self {
+        Self::String(value.to_owned())
+    }
+}
+
+impl From<String> for JsonValue {
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
+
+impl From<Vec<JsonValue>> for JsonValue {
+    fn from(items: Vec<JsonValue>) -> Self {
+        Self::Array(items)
+    }
+}
+
+fn write_string(out: &mut (impl Write + ?Sized), string: &str) -> std::fmt::Result {
+    out.write_char('"')?;
+    for c in string.chars() {
+        match c {
+            '"' => out.write_str("\\\"")?,
+            '\\' => out.write_str("\\\\")?,
+            '\n' => out.write_str("\\n")?,
+            '\r' => out.write_str("\\r")?,
+            '\t' => out.write_str("\\t")?,
+            c if (c as u32) < 0x20 => write!(out, "\\u{:04x}", c as u32)?,
+            c => out.write_char(c)?,
+        }
+    }
+    out.write_char('"')
+}
+
+struct Parser<'a> {
+    text: &'a str,
+    // Byte offset in the text.
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn error(&self, reason: &str) -> JsonError {
+        let consumed = &self.text[..self.position];
+        let line_start = consumed.rfind('\n').map_or(0, |i| i + 1);
+        JsonError {
+            line: consumed.matches('\n').count() + 1,
+            column: consumed[line_start..].chars().count() + 1,
+            reason: reason.to_owned(),
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.text.as_bytes().get(self.position).cloned()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(b' ' | b'\t' | b'\n' | b'\r') = self.peek() {
+            self.position += 1;
+        }
+    }
+
+    fn expect(&mut self, byte: u8) -> Result<(), JsonError> {
+        if self.peek() == Some(byte) {
+            self.position += 1;
+            Ok(())
+        } else {
+            Err(self.error(&format!("Expected `{}`", byte as char)))
+        }
+    }
+
+    fn parse_value(&mut self, depth: usize) -> Result<JsonValue, JsonError> {
+        if depth > MAX_DEPTH {
+            return Err(self.error("Nesting is too deep"));
+        }
+
+        self.skip_whitespace();
+        match self.peek() {
+            Some(b'n') => self.parse_literal("null", JsonValue::Null),
+            Some(b't') => self.parse_literal("true", JsonValue::Bool(true)),
+            Some(b'f') => self.parse_literal("false", JsonValue::Bool(false)),
+            Some(b'"') => self.parse_string().map(JsonValue::String),
+            Some(b'[') => self.parse_array(depth),
+            Some(b'{') => self.parse_object(depth),
+            Some(b'-' | b'0'..=b'9') => self.parse_number(),
+            Some(_) => Err(self.error("Unexpected character")),
+            None => Err(self.error("Unexpected end of input")),
+        }
+    }
+
+    fn parse_literal(&mut self, literal: &str, value: JsonValue) -> Result<JsonValue, JsonError> {
+        if self.text[self.position..].starts_with(literal) {
+            self.position += literal.len();
+            Ok(value)
+        } else {
+            Err(self.error("Unexpected character"))
+        }
+    }
+
+    fn parse_number(&mut self) -> Result<JsonValue, JsonError> {
+        let start = self.position;
+        while let Some(b'-' | b'+' | b'.' | b'e' | b'E' | b'0'..=b'9') = self.peek() {
+            self.position += 1;
+        }
+        let number = &self.text[start..self.position];
+        // Rust accepts a few forms that are invalid in JSON (`1.`, `.5`, `01`), reject them explicitly.
+        let digits = number.strip_prefix('-').unwrap_or(number).as_bytes();
+        let leading_zero = digits.len() > 1 && digits[0] == b'0' && digits[1].is_ascii_digit();
+        let dangling_point =
+            number.ends_with('.') || number.contains(".e") || number.contains(".E");
+        let invalid =
+            !digits.first().is_some_and(u8::is_ascii_digit) || leading_zero || dangling_point;
+        match number.parse() {
+            Ok(value) if !invalid => Ok(JsonValue::Number(value)),
+            _ => {
+                self.position = start;
+                Err(self.error("Invalid number"))
+            }
+        }
+    }
+
+    fn parse_hex4(&mut self) -> Result<u32, JsonError> {
+        let hex = self
+            .text
+            .get(self.position..self.position + 4)
+            .filter(|hex| hex.bytes().all(|byte| byte.is_ascii_hexdigit()))
+            .ok_or_else(|| self.error("Invalid unicode escape sequence"))?;
+        self.position += 4;
+        Ok(u32::from_str_radix(hex, 16).unwrap())
+    }
+
+    fn parse_string(&mut self) -> Result<String, JsonError> {
+        self.expect(b'"')?;
+        let mut string = String::new();
+        loop {
+            let rest = &self.text[self.position..];
+            // Copy everything up to the next special character at once.
+            let end = rest
+                .find(|c: char| c == '"' || c == '\\' || (c as u32) < 0x20)
+                .ok_or_else(|| self.error("Unterminated string"))?;
+            string.push_str(&rest[..end]);
+            self.position += end;
+
+            match self.peek() {
+                Some(b'"') => {
+                    self.position += 1;
+                    return Ok(string);
+                }
+                Some(b'\\') => {
+                    self.position += 1;
+                    let escaped = self.peek();
+                    self.position += 1;
+                    match escaped {
+                        Some(b'"') => string.push('"'),
+                        Some(b'\\') => string.push('\\'),
+                        Some(b'/') => string.push('/'),
+                        Some(b'b') => string.push('\u{8}'),
+                        Some(b'f') => string.push('\u{c}'),
+                        Some(b'n') => string.push('\n'),
+                        Some(b'r') => string.push('\r'),
+                        Some(b't') => string.push('\t'),
+                        Some(b'u') => {
+                            let mut code = self.parse_hex4()?;
+                            // Characters outside of the basic multilingual plane are encoded as surrogate pairs.
+                            if (0xD800..0xDC00).contains(&code)
+                                && self.text[self.position..].starts_with("\\u")
+                            {
+                                self.position += 2;
+                                let low = self.parse_hex4()?;
+                                if !(0xDC00..0xE000).contains(&low) {
+                                    return Err(self.error("Invalid surrogate pair"));
+                                }
+                                code = 0x10000 + ((code - 0xD800) << 10) + (low - 0xDC00);
+                            }
+                            string.push(
+                                char::from_u32(code)
+                                    .ok_or_else(|| self.error("Invalid unicode character"))?,
+                            );
+                        }
+                        _ => {
+                            self.position -= 1;
+                            return Err(self.error("Invalid escape sequence"));
+                        }
+                    }
+                }
+                _ => return Err(self.error("Control character in string")),
+            }
+        }
+    }
+
+    fn parse_array(&mut self, depth: usize) -> Result<JsonValue, JsonError> {
+        self.expect(b'[')?;
+        let mut items = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(b']') {
+            self.position += 1;
+            return Ok(JsonValue::Array(items));
+        }
+        loop {
+            items.push(self.parse_value(depth + 1)?);
+            self.skip_whitespace();
+            match self.peek() {
+                Some(b',') => self.position += 1,
+                Some(b']') => {
+                    self.position += 1;
+                    return Ok(JsonValue::Array(items));
+                }
+                _ => return Err(self.error("Expected `,` or `]`")),
+            }
+        }
+    }
+
+    fn parse_object(&mut self, depth: usize) -> Result<JsonValue, JsonError> {
+        self.expect(b'{')?;
+        let mut members = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(b'}') {
+            self.position += 1;
+            return Ok(JsonValue::Object(members));
+        }
+        loop {
+            self.skip_whitespace();
+            let name = self.parse_string()?;
+            self.skip_whitespace();
+            self.expect(b':')?;
+            members.push((name, self.parse_value(depth + 1)?));
+            self.skip_whitespace();
+            match self.peek() {
+                Some(b',') => self.position += 1,
+                Some(b'}') => {
+                    self.position += 1;
+                    return Ok(JsonValue::Object(members));
+                }
+                _ => return Err(self.error("Expected `,` or `}`")),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::json::JsonValue;
+
+    #[test]
+    fn test_parse() {
+        let value = JsonValue::parse(
+            r#" { "a": [1, -2.5e2, true, null], "b": { "c": "x\"\\\/\n\u00e9\ud83d\ude00" }, "d": [] } "#,
+        )
+        .unwrap();
+        assert_eq!(
+            value.get("a").unwrap().as_array().unwrap(),
+            [
+                JsonValue::Number(1.0),
+                JsonValue::Number(-250.0),
+                JsonValue::Bool(true),
+                JsonValue::Null
+            ]
+        );
+        assert_eq!(
+            value.get("b").unwrap().get("c").unwrap().as_str(),
+            Some("x\"\\/\né😀")
+        );
+        assert_eq!(value.get("d").unwrap().as_array().unwrap().len(), 0);
+        assert!(value.get("e").is_none());
+        assert_eq!(
+            value.get("a").unwrap().as_array().unwrap()[0].as_usize(),
+            Some(1)
+        );
+        assert_eq!(
+            value.get("a").unwrap().as_array().unwrap()[1].as_usize(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        for text in [
+            "",
+            "[1,]",
+            "{\"a\" 1}",
+            "01",
+            "1.",
+            ".5",
+            "\"abc",
+            "[1] 2",
+            "tru",
+            "\"\\x\"",
+            "\"\n\"",
+        ] {
+            assert!(JsonValue::parse(text).is_err(), "{text:?} must be rejected");
+        }
+
+        let error = JsonValue::parse("{\n  \"a\": ?\n}").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 8));
+
+        let deep = "[".repeat(1000) + &"]".repeat(1000);
+        assert!(JsonValue::parse(&deep).is_err());
+    }
+
+    #[test]
+    fn test_write() {
+        let value = JsonValue::Object(vec![
+            ("name".to_owned(), "a\"b\n".into()),
+            (
+                "items".to_owned(),
+                vec![0.1f32.into(), 2.0f64.into()].into(),
+            ),
+            ("empty".to_owned(), JsonValue::Array(vec![])),
+            ("nan".to_owned(), f64::NAN.into()),
+        ]);
+        assert_eq!(
+            value.to_string(),
+            r#"{"name":"a\"b\n","items":[0.1,2],"empty":[],"nan":null}"#
+        );
+        assert_eq!(
+            value.to_string_pretty(),
+            "{\n  \"name\": \"a\\\"b\\n\",\n  \"items\": [\n    0.1,\n    2\n  ],\n  \"empty\": [],\n  \"nan\": null\n}"
+        );
+        let parsed = JsonValue::parse(&value.to_string_pretty()).unwrap();
+        assert_eq!(parsed.get("items"), value.get("items"));
+    }
+}
@@ -31,6 +31,7 @@ pub mod color;
 pub mod color_gradient;
 pub mod curve;
 pub mod io;
+pub mod json;
 pub mod log;
 pub mod math;
 pub mod numeric_range;
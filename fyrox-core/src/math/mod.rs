@@ -492,6 +492,40 @@ pub fn cubicf(p0: f32, p1: f32, t: f32, m0: f32, m1: f32) -> f32 {
         + (t3 - t2) * m1 * scale
 }
 
+/// Weighted version of [`cubicf`]. The spline is treated as a 2D Bezier curve, where `w0` and `w1`
+/// define horizontal lengths of the tangent handles as a fraction of the span. With `w0 == w1 == 1/3`
+/// it gives exactly the same result as [`cubicf`].
+#[inline]
+pub fn weighted_cubicf(p0: f32, p1: f32, t: f32, m0: f32, m1: f32, w0: f32, w1: f32) -> f32 {
+    let scale = (p1 - p0).abs();
+    let c0 = (w0, p0 + m0 * scale * w0);
+    let c1 = (1.0 - w1, p1 - m1 * scale * w1);
+
+    let bezier = |a: f32, b: f32, c: f32, d: f32, s: f32| {
+        let k = 1.0 - s;
+        k * k * k * a + 3.0 * k * k * s * b + 3.0 * k * s * s * c + s * s * s * d
+    };
+
+    // Find the Bezier parameter for the given location using bisection, it is robust even if the
+    // handles overlap.
+    let (mut min, mut max) = (0.0f32, 1.0f32);
+    let mut s = t;
+    for _ in 0..24 {
+        let x = bezier(0.0, c0.0, c1.0, 1.0, s);
+        if (x - t).abs() <= 1.0e-6 {
+            break;
+        }
+        if x < t {
+            min = s;
+        } else {
+            max = s;
+        }
+        s = (min + max) * 0.5;
+    }
+
+    bezier(p0, c0.1, c1.1, p1, s)
+}
+
 #[inline]
 pub fn cubicf_derivative(p0: f32, p1: f32, t: f32, m0: f32, m1: f32) -> f32 {
     let t2 = t * t;
@@ -1115,12 +1149,13 @@ mod test {
     use num_traits::Zero;
 
     use super::{
-        barycentric_is_inside, barycentric_to_world, cubicf_derivative, get_barycentric_coords,
-        get_barycentric_coords_2d, get_closest_point, get_closest_point_triangle_set,
-        get_closest_point_triangles, get_farthest_point, get_signed_triangle_area, ieee_remainder,
-        inf_sup_cubicf, m4x4_approx_eq, quat_from_euler, round_to_step, spherical_to_cartesian,
-        triangle_area, wrap_angle, wrapf, Matrix3Ext, Matrix4Ext, PositionProvider, Rect,
-        RotationOrder, SmoothAngle, TriangleDefinition, TriangleEdge, Vector2Ext, Vector3Ext,
+        barycentric_is_inside, barycentric_to_world, cubicf, cubicf_derivative,
+        get_barycentric_coords, get_barycentric_coords_2d, get_closest_point,
+        get_closest_point_triangle_set, get_closest_point_triangles, get_farthest_point,
+        get_signed_triangle_area, ieee_remainder, inf_sup_cubicf, m4x4_approx_eq, quat_from_euler,
+        round_to_step, spherical_to_cartesian, triangle_area, weighted_cubicf, wrap_angle, wrapf,
+        Matrix3Ext, Matrix4Ext, PositionProvider, Rect, RotationOrder, SmoothAngle,
+        TriangleDefinition, TriangleEdge, Vector2Ext, Vector3Ext,
     };
     use crate::algebra::Vector2;
 
@@ -1400,6 +1435,17 @@ mod test {
         assert_eq!(wrapf(12.0, 5.0, 10.0), 7.0);
     }
 
+    #[test]
+    fn test_weighted_cubicf() {
+        for t in [0.0, 0.25, 0.5, 0.75, 1.0] {
+            let expected = cubicf(1.0, 3.0, t, 0.5, -1.0);
+            let weighted = weighted_cubicf(1.0, 3.0, t, 0.5, -1.0, 1.0 / 3.0, 1.0 / 3.0);
+            assert!((expected - weighted).abs() < 1.0e-4);
+        }
+        assert_eq!(weighted_cubicf(1.0, 3.0, 0.0, 0.5, -1.0, 0.9, 0.1), 1.0);
+        assert_eq!(weighted_cubicf(1.0, 3.0, 1.0, 0.5, -1.0, 0.9, 0.1), 3.0);
+    }
+
     #[test]
     fn test_cubicf_derivative() {
         assert_eq!(cubicf_derivative(1.0, 1.0, 1.0, 1.0, 1.0), 0.0);
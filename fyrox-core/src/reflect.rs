@@ -90,6 +90,10 @@ pub struct FieldInfo<'a, 'b> {
 
     /// Maximum amount of decimal places for a numeric property.
     pub precision: Option<usize>,
+
+    /// An arbitrary tag of the property, it is used to pick a specialized property editor for it (for example,
+    /// a list of available names for a string that references something by name). Empty if there's no tag.
+    pub tag: &'b str,
 }
 
 impl<'a, 'b> FieldInfo<'a, 'b> {
@@ -119,6 +123,7 @@ impl<'a, 'b> fmt::Debug for FieldInfo<'a, 'b> {
             .field("step", &self.step)
             .field("precision", &self.precision)
             .field("description", &self.description)
+            .field("tag", &self.tag)
             .finish()
     }
 }
@@ -138,6 +143,7 @@ impl<'a, 'b> PartialEq<Self> for FieldInfo<'a, 'b> {
             && self.step == other.step
             && self.precision == other.precision
             && self.description == other.description
+            && self.tag == other.tag
     }
 }
 
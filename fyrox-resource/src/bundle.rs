@@ -0,0 +1,536 @@
+//! Resource bundles (packed archives). See [`ResourceBundle`] docs for more info.
+
+use crate::io::{PathIter, ResourceIo, ResourceIoFuture};
+use flate2::{read::DeflateDecoder, write::DeflateEncoder, Compression};
+use fyrox_core::{io::FileLoadError, parking_lot::Mutex};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    future::ready,
+    io::{self, Cursor, Read, Seek, SeekFrom, Write},
+    path::{Component, Path, PathBuf},
+};
+
+const MAGIC: &[u8; 8] = b"FYRBNDL\0";
+const VERSION: u32 = 1;
+
+/// Converts a path to the form that is used in bundle index - relative path with `/` separators,
+/// without `.` components. Returns `None` if the path contains `..` components, since there is no way
+/// to resolve them inside a bundle.
+fn normalize_path(path: &Path) -> Option<String> {
+    let mut normalized = String::new();
+    for component in path.components() {
+        match component {
+            Component::Normal(name) => {
+                if !normalized.is_empty() {
+                    normalized.push('/');
+                }
+                normalized.push_str(&name.to_string_lossy());
+            }
+            Component::ParentDir => return None,
+            Component::Prefix(_) | Component::RootDir | Component::CurDir => (),
+        }
+    }
+    Some(normalized)
+}
+
+fn read_u32(reader: &mut dyn Read) -> io::Result<u32> {
+    let mut bytes = [0; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+fn read_u64(reader: &mut dyn Read) -> io::Result<u64> {
+    let mut bytes = [0; 8];
+    reader.read_exact(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_owned())
+}
+
+/// An entry of a bundle index.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BundleEntry {
+    /// Offset of the compressed data of the entry, relative to the beginning of the data section.
+    pub offset: u64,
+    /// Size of the compressed data of the entry.
+    pub compressed_size: u64,
+    /// Size of the decompressed data of the entry.
+    pub size: u64,
+}
+
+trait BundleSource: Read + Seek + Send {}
+
+impl<T> BundleSource for T where T: Read + Seek + Send {}
+
+/// Resource bundle is a single archive file that contains a set of compressed files along with an index
+/// of them. Bundles are used in shipped builds to replace loose-file layouts: a game have to ship a
+/// few large files instead of thousands of small ones, which is faster to load and harder to break.
+///
+/// Bundle implements [`ResourceIo`], so it can be mounted directly to the resource manager and every
+/// resource will be loaded from it transparently:
+///
+/// ```rust,no_run
+/// use fyrox_resource::{bundle::ResourceBundle, manager::ResourceManager};
+/// use std::sync::Arc;
+///
+/// let resource_manager = ResourceManager::new();
+/// let bundle = ResourceBundle::open("data.bundle").unwrap();
+/// resource_manager.state().set_resource_io(Arc::new(bundle));
+/// ```
+///
+/// Bundles could be created using [`ResourceBundleBuilder`].
+pub struct ResourceBundle {
+    // Sorted, so every entry of a directory could be found using a range query.
+    index: BTreeMap<String, BundleEntry>,
+    data_offset: u64,
+    source: Mutex<Box<dyn BundleSource>>,
+}
+
+impl ResourceBundle {
+    /// Opens a bundle file at the given path. Only the index is read, the contents of the files are
+    /// read on demand.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let file = std::fs::File::open(path)?;
+        Self::from_source(Box::new(io::BufReader::new(file)))
+    }
+
+    /// Creates a bundle from its raw bytes. It is useful on platforms that do not have direct file
+    /// system access (WebAssembly, for example).
+    pub fn from_bytes(bytes: Vec<u8>) -> io::Result<Self> {
+        Self::from_source(Box::new(Cursor::new(bytes)))
+    }
+
+    fn from_source(mut source: Box<dyn BundleSource>) -> io::Result<Self> {
+        // The header is untrusted, so every size in it is checked against the size of the file before
+        // allocating anything.
+        let total_size = source.seek(SeekFrom::End(0))?;
+        source.seek(SeekFrom::Start(0))?;
+
+        let mut magic = [0; 8];
+        source.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(invalid_data("Not a resource bundle!"));
+        }
+
+        let version = read_u32(&mut source)?;
+        if version != VERSION {
+            return Err(invalid_data(&format!(
+                "Unsupported resource bundle version {version}!"
+            )));
+        }
+
+        let count = read_u32(&mut source)?;
+        let mut index = BTreeMap::new();
+        for _ in 0..count {
+            let path_len = read_u32(&mut source)? as u64;
+            if source.stream_position()? + path_len > total_size {
+                return Err(invalid_data("Entry path is out of bounds!"));
+            }
+            let mut path = vec![0; path_len as usize];
+            source.read_exact(&mut path)?;
+            let path = String::from_utf8(path).map_err(|_| invalid_data("Invalid entry path!"))?;
+            let entry = BundleEntry {
+                offset: read_u64(&mut source)?,
+                compressed_size: read_u64(&mut source)?,
+                size: read_u64(&mut source)?,
+            };
+            index.insert(path, entry);
+        }
+
+        let data_offset = source.stream_position()?;
+
+        for entry in index.values() {
+            let end = entry
+                .offset
+                .checked_add(entry.compressed_size)
+                .and_then(|end| end.checked_add(data_offset));
+            if end.map_or(true, |end| end > total_size) {
+                return Err(invalid_data("Entry data is out of bounds!"));
+            }
+        }
+
+        Ok(Self {
+            index,
+            data_offset,
+            source: Mutex::new(source),
+        })
+    }
+
+    /// Returns an index entry for a given path (if any).
+    pub fn entry<P: AsRef<Path>>(&self, path: P) -> Option<&BundleEntry> {
+        self.index.get(&normalize_path(path.as_ref())?)
+    }
+
+    /// Returns `true` if the bundle contains a file at the given path.
+    pub fn contains<P: AsRef<Path>>(&self, path: P) -> bool {
+        self.entry(path).is_some()
+    }
+
+    /// Returns an iterator over paths of every file in the bundle.
+    pub fn paths(&self) -> impl Iterator<Item = PathBuf> + '_ {
+        self.index.keys().map(PathBuf::from)
+    }
+
+    /// Returns total amount of files in the bundle.
+    pub fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    /// Returns `true` if the bundle has no files.
+    pub fn is_empty(&self) -> bool {
+        self.index.is_empty()
+    }
+
+    /// Reads and decompresses a file at the given path.
+    pub fn read<P: AsRef<Path>>(&self, path: P) -> io::Result<Vec<u8>> {
+        let entry = self.entry(path.as_ref()).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("There's no {} file in the bundle!", path.as_ref().display()),
+            )
+        })?;
+
+        let mut compressed = vec![0; entry.compressed_size as usize];
+        {
+            let mut source = self.source.lock();
+            source.seek(SeekFrom::Start(self.data_offset + entry.offset))?;
+            source.read_exact(&mut compressed)?;
+        }
+
+        // Decompressed size is not trusted either, so the data is not preallocated.
+        let mut data = Vec::new();
+        DeflateDecoder::new(compressed.as_slice())
+            .take(entry.size)
+            .read_to_end(&mut data)?;
+        if data.len() as u64 != entry.size {
+            return Err(invalid_data("Entry size mismatch!"));
+        }
+        Ok(data)
+    }
+
+    /// Returns an iterator over every entry inside the directory at the given normalized path.
+    fn entries_in<'a>(&'a self, prefix: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+        let start = if prefix.is_empty() {
+            String::new()
+        } else {
+            format!("{prefix}/")
+        };
+        self.index
+            .range(start.clone()..)
+            .map(|(path, _)| path.as_str())
+            .take_while(move |path| path.starts_with(&start))
+            .map(move |path| {
+                if prefix.is_empty() {
+                    path
+                } else {
+                    &path[prefix.len() + 1..]
+                }
+            })
+    }
+
+    fn is_directory(&self, path: &Path) -> bool {
+        normalize_path(path)
+            .is_some_and(|prefix| prefix.is_empty() || self.entries_in(&prefix).next().is_some())
+    }
+
+    fn children(&self, path: &Path, recursive: bool) -> Vec<PathBuf> {
+        let Some(prefix) = normalize_path(path) else {
+            return Vec::new();
+        };
+
+        let mut children = BTreeSet::new();
+        for rest in self.entries_in(&prefix) {
+            let mut child = PathBuf::from(&prefix);
+            if recursive {
+                // Directories are listed too, so the result matches the one of file system walkers.
+                for component in rest.split('/') {
+                    child.push(component);
+                    children.insert(child.clone());
+                }
+            } else {
+                child.push(rest.split('/').next().unwrap_or_default());
+                children.insert(child);
+            }
+        }
+        children.into_iter().collect()
+    }
+}
+
+impl ResourceIo for ResourceBundle {
+    fn load_file<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<Vec<u8>, FileLoadError>> {
+        Box::pin(ready(self.read(path).map_err(FileLoadError::Io)))
+    }
+
+    fn read_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<PathIter, FileLoadError>> {
+        let iter: PathIter = Box::new(self.children(path, false).into_iter());
+        Box::pin(ready(Ok(iter)))
+    }
+
+    fn walk_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<PathIter, FileLoadError>> {
+        let iter: PathIter = Box::new(self.children(path, true).into_iter());
+        Box::pin(ready(Ok(iter)))
+    }
+
+    fn exists<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, bool> {
+        Box::pin(ready(self.contains(path) || self.is_directory(path)))
+    }
+
+    fn is_file<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, bool> {
+        Box::pin(ready(self.contains(path)))
+    }
+
+    fn is_dir<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, bool> {
+        Box::pin(ready(self.is_directory(path)))
+    }
+}
+
+/// Creates resource bundles. See [`ResourceBundle`] docs for more info.
+///
+/// ```rust,no_run
+/// use fyrox_resource::bundle::ResourceBundleBuilder;
+///
+/// ResourceBundleBuilder::new()
+///     .with_directory("data")
+///     .unwrap()
+///     .save("data.bundle")
+///     .unwrap();
+/// ```
+pub struct ResourceBundleBuilder {
+    files: BTreeMap<String, Vec<u8>>,
+    compression: u32,
+}
+
+impl Default for ResourceBundleBuilder {
+    fn default() -> Self {
+        Self {
+            files: Default::default(),
+            compression: Compression::default().level(),
+        }
+    }
+}
+
+impl ResourceBundleBuilder {
+    /// Creates a new empty bundle builder with default compression level.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets compression level in `[0; 9]` range, where `0` means no compression and `9` - the best
+    /// (and the slowest) compression.
+    pub fn with_compression_level(mut self, level: u32) -> Self {
+        self.compression = level.min(9);
+        self
+    }
+
+    /// Adds a file with the given path (inside the bundle) and content. If there's already a file with the
+    /// same path, it will be replaced. Paths with `..` components are rejected.
+    pub fn with_file<P: AsRef<Path>>(mut self, path: P, data: Vec<u8>) -> io::Result<Self> {
+        self.add_file(path, data)?;
+        Ok(self)
+    }
+
+    /// Adds a file with the given path (inside the bundle) and content. If there's already a file with the
+    /// same path, it will be replaced. Paths with `..` components are rejected.
+    pub fn add_file<P: AsRef<Path>>(&mut self, path: P, data: Vec<u8>) -> io::Result<()> {
+        let normalized = normalize_path(path.as_ref()).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "Path {} must not contain \"..\" components!",
+                    path.as_ref().display()
+                ),
+            )
+        })?;
+        self.files.insert(normalized, data);
+        Ok(())
+    }
+
+    /// Recursively adds every file of the given directory. Paths of the files in the bundle will be the
+    /// same as their paths on the file system (relative to the working directory if `path` is relative).
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn with_directory<P: AsRef<Path>>(mut self, path: P) -> io::Result<Self> {
+        for entry in walkdir::WalkDir::new(path.as_ref()) {
+            let entry = entry.map_err(io::Error::from)?;
+            if entry.file_type().is_file() {
+                let data = std::fs::read(entry.path())?;
+                self.add_file(entry.path(), data)?;
+            }
+        }
+        Ok(self)
+    }
+
+    /// Compresses every file and writes the bundle to the given writer.
+    pub fn write<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        let mut data = Vec::new();
+        let mut entries = Vec::with_capacity(self.files.len());
+        for (path, content) in self.files.iter() {
+            let mut encoder = DeflateEncoder::new(Vec::new(), Compression::new(self.compression));
+            encoder.write_all(content)?;
+            let compressed = encoder.finish()?;
+            entries.push((
+                path,
+                BundleEntry {
+                    offset: data.len() as u64,
+                    compressed_size: compressed.len() as u64,
+                    size: content.len() as u64,
+                },
+            ));
+            data.extend_from_slice(&compressed);
+        }
+
+        writer.write_all(MAGIC)?;
+        writer.write_all(&VERSION.to_le_bytes())?;
+        writer.write_all(&(entries.len() as u32).to_le_bytes())?;
+        for (path, entry) in entries {
+            writer.write_all(&(path.len() as u32).to_le_bytes())?;
+            writer.write_all(path.as_bytes())?;
+            writer.write_all(&entry.offset.to_le_bytes())?;
+            writer.write_all(&entry.compressed_size.to_le_bytes())?;
+            writer.write_all(&entry.size.to_le_bytes())?;
+        }
+        writer.write_all(&data)?;
+
+        Ok(())
+    }
+
+    /// Writes the bundle to a file at the given path.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let file = std::fs::File::create(path)?;
+        let mut writer = io::BufWriter::new(file);
+        self.write(&mut writer)?;
+        writer.flush()
+    }
+
+    /// Writes the bundle to a vector of bytes.
+    pub fn build(&self) -> io::Result<Vec<u8>> {
+        let mut bytes = Vec::new();
+        self.write(&mut bytes)?;
+        Ok(bytes)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use fyrox_core::futures::executor::block_on;
+
+    fn make_bundle() -> ResourceBundle {
+        let bytes = ResourceBundleBuilder::new()
+            .with_file("data/textures/foo.png", vec![1, 2, 3])
+            .and_then(|b| b.with_file("./data/models/bar.fbx", vec![4; 1024]))
+            .and_then(|b| b.with_file("readme.txt", b"Hello".to_vec()))
+            .unwrap()
+            .build()
+            .unwrap();
+        ResourceBundle::from_bytes(bytes).unwrap()
+    }
+
+    #[test]
+    fn test_bundle_read() {
+        let bundle = make_bundle();
+
+        assert_eq!(bundle.len(), 3);
+        assert_eq!(bundle.read("data/textures/foo.png").unwrap(), vec![1, 2, 3]);
+        assert_eq!(bundle.read("data/models/bar.fbx").unwrap(), vec![4; 1024]);
+        assert_eq!(bundle.read("./readme.txt").unwrap(), b"Hello".to_vec());
+        assert!(bundle.entry("data/models/bar.fbx").unwrap().compressed_size < 1024);
+        assert_eq!(
+            bundle.read("missing.txt").unwrap_err().kind(),
+            io::ErrorKind::NotFound
+        );
+        assert!(ResourceBundle::from_bytes(vec![1, 2, 3]).is_err());
+    }
+
+    #[test]
+    fn test_bundle_resource_io() {
+        let bundle = make_bundle();
+
+        assert!(block_on(bundle.exists(Path::new("data/textures"))));
+        assert!(block_on(bundle.is_dir(Path::new("data"))));
+        assert!(!block_on(bundle.is_dir(Path::new("data/textures/foo.png"))));
+        assert!(block_on(bundle.is_file(Path::new("data/textures/foo.png"))));
+        assert!(!block_on(bundle.exists(Path::new("dat"))));
+        assert_eq!(
+            block_on(bundle.load_file(Path::new("readme.txt"))).unwrap(),
+            b"Hello".to_vec()
+        );
+
+        let mut children = block_on(bundle.read_directory(Path::new("data")))
+            .unwrap()
+            .collect::<Vec<_>>();
+        children.sort();
+        assert_eq!(
+            children,
+            vec![PathBuf::from("data/models"), PathBuf::from("data/textures")]
+        );
+
+        let walked = block_on(bundle.walk_directory(Path::new("data")))
+            .unwrap()
+            .collect::<Vec<_>>();
+        assert_eq!(walked.len(), 4);
+        assert!(walked.contains(&PathBuf::from("data/models/bar.fbx")));
+    }
+
+    #[test]
+    fn test_bundle_rejects_parent_dir() {
+        assert!(ResourceBundleBuilder::new()
+            .with_file("data/../secret.txt", vec![1])
+            .is_err());
+        assert!(ResourceBundleBuilder::new()
+            .with_file("../secret.txt", vec![1])
+            .is_err());
+
+        let bundle = make_bundle();
+        assert!(!bundle.contains("data/../readme.txt"));
+        assert!(!block_on(bundle.is_dir(Path::new("data/.."))));
+    }
+
+    #[test]
+    fn test_bundle_corrupted_header() {
+        let bytes = ResourceBundleBuilder::new()
+            .with_file("readme.txt", b"Hello".to_vec())
+            .unwrap()
+            .build()
+            .unwrap();
+        // Magic, version and entry count.
+        let path_len_offset = MAGIC.len() + 4 + 4;
+        let entry_offset = path_len_offset + 4 + "readme.txt".len();
+
+        let mut huge_path = bytes.clone();
+        huge_path[path_len_offset..path_len_offset + 4].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert_eq!(
+            ResourceBundle::from_bytes(huge_path).err().unwrap().kind(),
+            io::ErrorKind::InvalidData
+        );
+
+        let mut huge_entry = bytes.clone();
+        // Compressed size of the entry.
+        huge_entry[entry_offset + 8..entry_offset + 16].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert_eq!(
+            ResourceBundle::from_bytes(huge_entry).err().unwrap().kind(),
+            io::ErrorKind::InvalidData
+        );
+
+        let mut huge_size = bytes;
+        // Decompressed size of the entry.
+        huge_size[entry_offset + 16..entry_offset + 24].copy_from_slice(&u64::MAX.to_le_bytes());
+        let bundle = ResourceBundle::from_bytes(huge_size).unwrap();
+        assert_eq!(
+            bundle.read("readme.txt").unwrap_err().kind(),
+            io::ErrorKind::InvalidData
+        );
+    }
+}
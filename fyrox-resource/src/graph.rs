@@ -3,6 +3,22 @@
 use crate::{collect_used_resources, state::ResourceState, untyped::UntypedResource};
 use fxhash::FxHashSet;
 
+/// Collects direct dependencies of a resource (resources that are referenced by the resource data).
+/// Resources that are not loaded (pending or failed to load) do not have any dependencies.
+#[allow(clippy::mutable_key_type)]
+pub fn collect_direct_dependencies(resource: &UntypedResource) -> FxHashSet<UntypedResource> {
+    let mut dependencies = FxHashSet::default();
+
+    let resource_state = resource.0.lock();
+    if let ResourceState::Ok(resource_data) = &*resource_state {
+        (**resource_data).as_reflect(&mut |entity| {
+            collect_used_resources(entity, &mut dependencies);
+        });
+    }
+
+    dependencies
+}
+
 /// A node of [`ResourceDependencyGraph`].
 pub struct ResourceGraphNode {
     /// A resource associated with the graph node.
@@ -15,23 +31,11 @@ impl ResourceGraphNode {
     /// Creates a new resource graph node for a given untyped resource. This method is recursive -
     /// it will initialize the entire sub-graph of dependencies automatically.
     pub fn new(resource: &UntypedResource) -> Self {
-        let mut children = Vec::new();
-
         // Look for dependent resources.
-        let mut dependent_resources = FxHashSet::default();
-
-        let resource_state = resource.0.lock();
-        if let ResourceState::Ok(resource_data) = &*resource_state {
-            (**resource_data).as_reflect(&mut |entity| {
-                collect_used_resources(entity, &mut dependent_resources);
-            });
-        }
-
-        children.extend(
-            dependent_resources
-                .into_iter()
-                .map(|r| ResourceGraphNode::new(&r)),
-        );
+        let children = collect_direct_dependencies(resource)
+            .into_iter()
+            .map(|r| ResourceGraphNode::new(&r))
+            .collect();
 
         Self {
             resource: resource.clone(),
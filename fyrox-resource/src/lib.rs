@@ -33,6 +33,7 @@ use std::{
 pub use fyrox_core as core;
 use fyrox_core::log::Log;
 
+pub mod bundle;
 pub mod constructor;
 pub mod entry;
 pub mod event;
@@ -44,6 +45,7 @@ pub mod options;
 pub mod state;
 mod task;
 pub mod untyped;
+pub mod vfs;
 
 /// Type UUID of texture resource. It is defined here to load old versions of resources.
 pub const TEXTURE_RESOURCE_UUID: Uuid = uuid!("02c23a44-55fa-411a-bc39-eb7a5eadf15c");
@@ -1,8 +1,11 @@
 //! Resource loader. It manages resource loading.
 
-use crate::{event::ResourceEventBroadcaster, io::ResourceIo, UntypedResource};
-use fyrox_core::uuid::Uuid;
-use std::{any::Any, future::Future, pin::Pin, sync::Arc};
+use crate::{
+    event::ResourceEventBroadcaster, io::ResourceIo, ResourceData, ResourceLoadError,
+    UntypedResource,
+};
+use fyrox_core::{log::Log, uuid::Uuid, TypeUuidProvider};
+use std::{any::Any, future::Future, path::PathBuf, pin::Pin, sync::Arc};
 
 #[cfg(target_arch = "wasm32")]
 #[doc(hidden)]
@@ -70,6 +73,109 @@ pub type BoxedLoaderFuture = Pin<Box<dyn Future<Output = ()>>>;
 #[cfg(not(target_arch = "wasm32"))]
 pub type BoxedLoaderFuture = Pin<Box<dyn Future<Output = ()> + Send>>;
 
+#[cfg(target_arch = "wasm32")]
+#[doc(hidden)]
+pub trait LoaderFuture<T>: Future<Output = T> + 'static {}
+
+#[cfg(target_arch = "wasm32")]
+impl<F, T> LoaderFuture<T> for F where F: Future<Output = T> + 'static {}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[doc(hidden)]
+pub trait LoaderFuture<T>: Future<Output = T> + Send + 'static {}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl<F, T> LoaderFuture<T> for F where F: Future<Output = T> + Send + 'static {}
+
+type DataLoaderFn<T> = Box<
+    dyn Fn(
+            PathBuf,
+            Arc<dyn ResourceIo>,
+        ) -> Pin<Box<dyn LoaderFuture<Result<T, Box<dyn ResourceLoadError>>>>>
+        + Send
+        + Sync,
+>;
+
+/// A resource loader that uses a function to load resource data of a custom type. It is the simplest
+/// way to add user-defined resource types (dialog trees, loot tables, level metadata, etc.) to the
+/// resource manager - the loader takes care of committing loaded data (or an error) to a resource and
+/// broadcasting respective events, so the data will be shared, cached and hot-reloaded the same way
+/// as built-in resources. See [`crate::manager::ResourceManager::add_resource_type`] for an example.
+pub struct FnResourceLoader<T> {
+    extensions: Vec<&'static str>,
+    func: DataLoaderFn<T>,
+}
+
+impl<T> FnResourceLoader<T>
+where
+    T: ResourceData + TypeUuidProvider,
+{
+    /// Creates a new loader for a given set of file extensions. `func` must load resource data from
+    /// a given path, using the resource io to access files.
+    pub fn new<F, Fut, E>(extensions: &[&'static str], func: F) -> Self
+    where
+        F: Fn(PathBuf, Arc<dyn ResourceIo>) -> Fut + Send + Sync + 'static,
+        Fut: LoaderFuture<Result<T, E>>,
+        E: ResourceLoadError,
+    {
+        Self {
+            extensions: extensions.to_vec(),
+            func: Box::new(move |path, io| {
+                let future = func(path, io);
+                Box::pin(async move {
+                    future
+                        .await
+                        .map_err(|e| Box::new(e) as Box<dyn ResourceLoadError>)
+                })
+            }),
+        }
+    }
+}
+
+impl<T> ResourceLoader for FnResourceLoader<T>
+where
+    T: ResourceData + TypeUuidProvider,
+{
+    fn extensions(&self) -> &[&str] {
+        &self.extensions
+    }
+
+    fn data_type_uuid(&self) -> Uuid {
+        <T as TypeUuidProvider>::type_uuid()
+    }
+
+    fn load(
+        &self,
+        resource: UntypedResource,
+        event_broadcaster: ResourceEventBroadcaster,
+        reload: bool,
+        io: Arc<dyn ResourceIo>,
+    ) -> BoxedLoaderFuture {
+        let path = resource.path();
+        let future = (self.func)(path.clone(), io);
+        Box::pin(async move {
+            match future.await {
+                Ok(mut data) => {
+                    Log::info(format!("Resource {:?} is loaded!", path));
+
+                    data.set_path(path);
+                    resource.commit_ok(data);
+
+                    event_broadcaster.broadcast_loaded_or_reloaded(resource, reload);
+                }
+                Err(error) => {
+                    Log::err(format!(
+                        "Unable to load resource from {:?}! Reason {:?}",
+                        path, error
+                    ));
+
+                    resource.commit_error(path, error);
+                }
+            }
+        })
+    }
+}
+
 /// Container for resource loaders.
 #[derive(Default)]
 pub struct ResourceLoadersContainer {
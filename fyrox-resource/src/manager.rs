@@ -6,10 +6,11 @@ use crate::{
     constructor::ResourceConstructorContainer,
     entry::{TimedEntry, DEFAULT_RESOURCE_LIFETIME},
     event::{ResourceEvent, ResourceEventBroadcaster},
-    loader::ResourceLoadersContainer,
+    graph::collect_direct_dependencies,
+    loader::{FnResourceLoader, LoaderFuture, ResourceLoadersContainer},
     state::ResourceState,
     task::TaskPool,
-    Resource, ResourceData, UntypedResource,
+    Resource, ResourceData, ResourceLoadError, UntypedResource,
 };
 use fxhash::FxHashMap;
 use fyrox_core::{
@@ -235,6 +236,103 @@ impl ResourceManager {
         }
     }
 
+    /// Registers a custom (user-defined) resource type, that will be loaded from files with the given
+    /// extensions using the given function. Such resources go through the same pipeline as built-in
+    /// ones: they're loaded asynchronously, shared, cached and hot-reloaded (if the watcher is set).
+    /// The method also registers a constructor for the type, so resources of this type could be
+    /// serialized as a part of other resources (scenes, for example).
+    ///
+    /// ```rust
+    /// use fyrox_core::{
+    ///     reflect::prelude::*, uuid::{uuid, Uuid}, visitor::prelude::*, TypeUuidProvider,
+    /// };
+    /// use fyrox_resource::{manager::ResourceManager, ResourceData};
+    /// use std::{
+    ///     any::Any,
+    ///     borrow::Cow,
+    ///     path::{Path, PathBuf},
+    /// };
+    ///
+    /// #[derive(Default, Debug, Visit, Reflect)]
+    /// struct LootTable {
+    ///     path: PathBuf,
+    ///     items: Vec<String>,
+    /// }
+    ///
+    /// impl TypeUuidProvider for LootTable {
+    ///     fn type_uuid() -> Uuid {
+    ///         uuid!("a4e4ce3b-8e2a-45c1-b1df-6bc3a3d6bd6a")
+    ///     }
+    /// }
+    ///
+    /// impl ResourceData for LootTable {
+    ///     fn path(&self) -> Cow<Path> {
+    ///         Cow::Borrowed(&self.path)
+    ///     }
+    ///
+    ///     fn set_path(&mut self, path: PathBuf) {
+    ///         self.path = path;
+    ///     }
+    ///
+    ///     fn as_any(&self) -> &dyn Any {
+    ///         self
+    ///     }
+    ///
+    ///     fn as_any_mut(&mut self) -> &mut dyn Any {
+    ///         self
+    ///     }
+    ///
+    ///     fn type_uuid(&self) -> Uuid {
+    ///         <Self as TypeUuidProvider>::type_uuid()
+    ///     }
+    ///
+    ///     fn is_procedural(&self) -> bool {
+    ///         false
+    ///     }
+    /// }
+    ///
+    /// fn register(resource_manager: &ResourceManager) {
+    ///     resource_manager.add_resource_type::<LootTable, _, _, _>(&["loot"], |path, io| async move {
+    ///         let bytes = io.load_file(&path).await?;
+    ///         Ok::<_, fyrox_core::io::FileLoadError>(LootTable {
+    ///             path,
+    ///             items: String::from_utf8_lossy(&bytes)
+    ///                 .lines()
+    ///                 .map(|l| l.to_owned())
+    ///                 .collect(),
+    ///         })
+    ///     });
+    /// }
+    /// ```
+    pub fn add_resource_type<T, F, Fut, E>(&self, extensions: &[&'static str], func: F)
+    where
+        T: ResourceData + TypeUuidProvider + Default,
+        F: Fn(PathBuf, Arc<dyn ResourceIo>) -> Fut + Send + Sync + 'static,
+        Fut: LoaderFuture<Result<T, E>>,
+        E: ResourceLoadError,
+    {
+        let state = &mut *self.state();
+        state.constructors_container.add_custom(
+            <T as TypeUuidProvider>::type_uuid(),
+            Box::new(|| Box::<T>::default()),
+        );
+        state.loaders.set(FnResourceLoader::new(extensions, func));
+    }
+
+    /// Reloads (reimports) a resource together with every resource that depends on it (directly or
+    /// indirectly). The resource is reloaded first, then its dependents are reloaded in the order of
+    /// their "distance" from the resource, so every dependent is reloaded after its dependencies.
+    /// See [`ResourceManagerState::find_dependents`] for more info.
+    pub async fn reload_resource_with_dependents(&self, resource: UntypedResource) {
+        let dependents = self.state().find_dependents(&resource);
+
+        for resource in std::iter::once(resource).chain(dependents) {
+            self.state().reload_resource(resource.clone());
+            // Errors are logged by loaders.
+            let _ = resource.await;
+        }
+    }
+
     /// Reloads all loaded resources. Normally it should never be called, because it is **very** heavy
     /// method! This method is asynchronous, it uses all available CPU power to reload resources as
     /// fast as possible.
@@ -503,6 +601,94 @@ impl ResourceManagerState {
         }
     }
 
+    /// Returns a list of loaded resources that directly reference the given resource. For example, if
+    /// the resource is a texture, the method will return all the models (and other resources) that
+    /// use the texture.
+    #[allow(clippy::mutable_key_type)]
+    pub fn find_usages(&self, resource: &UntypedResource) -> Vec<UntypedResource> {
+        self.resources
+            .iter()
+            .filter(|entry| {
+                entry.value != *resource
+                    && collect_direct_dependencies(&entry.value).contains(resource)
+            })
+            .map(|entry| entry.value.clone())
+            .collect()
+    }
+
+    /// Returns a list of all loaded resources that depend on the given resource, directly or indirectly
+    /// (scene → model → texture). Direct users go first, then users of the users, and so on. Every
+    /// resource is listed only once, even if it is reachable by multiple paths.
+    #[allow(clippy::mutable_key_type)]
+    pub fn find_dependents(&self, resource: &UntypedResource) -> Vec<UntypedResource> {
+        // Build reverse dependency map first, this is much faster than calling `find_usages`
+        // for every found dependent.
+        let mut users = FxHashMap::<UntypedResource, Vec<UntypedResource>>::default();
+        for entry in self.resources.iter() {
+            for dependency in collect_direct_dependencies(&entry.value) {
+                if dependency != entry.value {
+                    users
+                        .entry(dependency)
+                        .or_default()
+                        .push(entry.value.clone());
+                }
+            }
+        }
+
+        let mut dependents = Vec::<UntypedResource>::new();
+        let mut queue = std::collections::VecDeque::from([resource.clone()]);
+        while let Some(current) = queue.pop_front() {
+            if let Some(current_users) = users.get(&current) {
+                for user in current_users {
+                    if user != resource && !dependents.contains(user) {
+                        dependents.push(user.clone());
+                        queue.push_back(user.clone());
+                    }
+                }
+            }
+        }
+
+        // Move every dependent after all of its dependencies, so reloading the list in order
+        // will never use stale data. Resources involved in cycles keep their order.
+        let mut sorted = Vec::with_capacity(dependents.len());
+        let mut remaining = dependents;
+        while !remaining.is_empty() {
+            let count = remaining.len();
+            let mut i = 0;
+            while i < remaining.len() {
+                let dependencies = collect_direct_dependencies(&remaining[i]);
+                if remaining
+                    .iter()
+                    .all(|other| other == &remaining[i] || !dependencies.contains(other))
+                {
+                    sorted.push(remaining.remove(i));
+                } else {
+                    i += 1;
+                }
+            }
+            if remaining.len() == count {
+                sorted.append(&mut remaining);
+            }
+        }
+
+        sorted
+    }
+
+    /// Returns a list of broken references - pairs of a loaded resource and a resource it references,
+    /// but that failed to load (for example, it was deleted or moved).
+    #[allow(clippy::mutable_key_type)]
+    pub fn find_broken_references(&self) -> Vec<(UntypedResource, UntypedResource)> {
+        let mut broken = Vec::new();
+        for entry in self.resources.iter() {
+            for dependency in collect_direct_dependencies(&entry.value) {
+                if matches!(*dependency.0.lock(), ResourceState::LoadError { .. }) {
+                    broken.push((entry.value.clone(), dependency));
+                }
+            }
+        }
+        broken
+    }
+
     /// Reloads all resources in the container. Returns a list of resources that will be reloaded.
     /// You can use the list to wait until all resources are loading.
     pub fn reload_resources(&mut self) -> Vec<UntypedResource> {
@@ -597,6 +783,35 @@ mod test {
         }
     }
 
+    #[derive(Debug, Default, Reflect, Visit)]
+    struct Composite {
+        dependencies: Vec<UntypedResource>,
+    }
+
+    impl ResourceData for Composite {
+        fn path(&self) -> std::borrow::Cow<'_, std::path::Path> {
+            std::borrow::Cow::Borrowed(Path::new("test.composite"))
+        }
+
+        fn set_path(&mut self, _path: std::path::PathBuf) {}
+
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+
+        fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+            self
+        }
+
+        fn type_uuid(&self) -> Uuid {
+            uuid!("2ff2b4f1-46c5-4b83-a77c-4d7b8d1f7a3e")
+        }
+
+        fn is_procedural(&self) -> bool {
+            false
+        }
+    }
+
     impl ResourceLoader for Stub {
         fn extensions(&self) -> &[&str] {
             &["txt"]
@@ -772,6 +987,77 @@ mod test {
         assert!(!res.is_loading());
     }
 
+    #[test]
+    fn resource_manager_add_resource_type() {
+        let resource_manager = ResourceManager::new();
+        resource_manager.add_resource_type::<Stub, _, _, _>(&["stub"], |path, _io| async move {
+            if path.ends_with("bad.stub") {
+                Err("Bad stub!".to_string())
+            } else {
+                Ok(Stub {})
+            }
+        });
+
+        {
+            let state = resource_manager.state();
+            assert!(state.loaders.find::<FnResourceLoader<Stub>>().is_some());
+            assert!(state
+                .constructors_container
+                .try_create(&<Stub as TypeUuidProvider>::type_uuid())
+                .is_some());
+        }
+
+        let resource = resource_manager.request_untyped("foo.stub");
+        assert_eq!(
+            resource.type_uuid(),
+            <Stub as TypeUuidProvider>::type_uuid()
+        );
+        assert!(fyrox_core::futures::executor::block_on(resource).is_ok());
+
+        let resource = resource_manager.request_untyped("bad.stub");
+        assert!(fyrox_core::futures::executor::block_on(resource).is_err());
+    }
+
+    #[test]
+    fn resource_manager_state_dependents() {
+        let mut state = ResourceManagerState::new();
+
+        let texture = UntypedResource::new_ok(Stub {});
+        let model = UntypedResource::new_ok(Composite {
+            dependencies: vec![texture.clone()],
+        });
+        let scene = UntypedResource::new_ok(Composite {
+            dependencies: vec![model.clone(), texture.clone()],
+        });
+        state.push(scene.clone());
+        state.push(model.clone());
+        state.push(texture.clone());
+
+        assert_eq!(
+            state.find_usages(&texture),
+            vec![scene.clone(), model.clone()]
+        );
+        assert_eq!(state.find_usages(&scene), vec![]);
+        // Model must go before the scene, because the scene depends on it.
+        assert_eq!(state.find_dependents(&texture), vec![model.clone(), scene]);
+        assert_eq!(state.find_dependents(&model).len(), 1);
+        assert!(state.find_broken_references().is_empty());
+    }
+
+    #[test]
+    fn resource_manager_state_find_broken_references() {
+        let mut state = ResourceManagerState::new();
+
+        let missing =
+            UntypedResource::new_load_error(PathBuf::from("missing.txt"), None, Uuid::default());
+        let model = UntypedResource::new_ok(Composite {
+            dependencies: vec![missing.clone(), UntypedResource::new_ok(Stub {})],
+        });
+        state.push(model.clone());
+
+        assert_eq!(state.find_broken_references(), vec![(model, missing)]);
+    }
+
     #[test]
     fn resource_manager_state_reload_resource() {
         let mut state = ResourceManagerState::new();
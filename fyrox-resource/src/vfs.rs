@@ -0,0 +1,412 @@
+//! Virtual file system with mountable sources. See [`VirtualFileSystem`] docs for more info.
+
+use crate::io::{FileReader, FsResourceIo, PathIter, ResourceIo, ResourceIoFuture};
+use fxhash::FxHashSet;
+use fyrox_core::{io::FileLoadError, parking_lot::RwLock};
+use std::{
+    io,
+    path::{Component, Path, PathBuf},
+    sync::Arc,
+};
+
+/// Removes `.` components from a path, so `./data/foo.png` and `data/foo.png` are treated the same.
+fn normalize_path(path: &Path) -> PathBuf {
+    path.components()
+        .filter(|c| !matches!(c, Component::CurDir))
+        .collect()
+}
+
+/// A source of files mounted to a [`VirtualFileSystem`].
+#[derive(Clone)]
+pub struct Mount {
+    /// Unique name of the mount. It is used to unmount the source.
+    pub name: String,
+    /// Priority of the mount. Files from mounts with higher priority override files with the same
+    /// path from mounts with lower priority.
+    pub priority: i32,
+    /// Virtual path prefix at which the source is mounted. Only the paths starting with this prefix
+    /// are looked up in the source. Empty path means that the source is mounted at the root.
+    pub mount_point: PathBuf,
+    /// Path prefix inside the source that corresponds to the mount point. For example, it could be
+    /// a mod directory when mounting a [`FsResourceIo`].
+    pub source_root: PathBuf,
+    /// Actual source of the files.
+    pub io: Arc<dyn ResourceIo>,
+}
+
+impl Mount {
+    /// Creates a new mount with zero priority, mounted at the root.
+    pub fn new<S: AsRef<str>>(name: S, io: Arc<dyn ResourceIo>) -> Self {
+        Self {
+            name: name.as_ref().to_owned(),
+            priority: 0,
+            mount_point: Default::default(),
+            source_root: Default::default(),
+            io,
+        }
+    }
+
+    /// Creates a new mount of a file system directory. Every path of the virtual file system will be
+    /// looked up relative to the given directory.
+    pub fn directory<S: AsRef<str>, P: AsRef<Path>>(name: S, directory: P) -> Self {
+        Self::new(name, Arc::new(FsResourceIo)).with_source_root(directory)
+    }
+
+    /// Sets the priority of the mount.
+    pub fn with_priority(mut self, priority: i32) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Sets the virtual path prefix at which the source is mounted.
+    pub fn with_mount_point<P: AsRef<Path>>(mut self, mount_point: P) -> Self {
+        self.mount_point = normalize_path(mount_point.as_ref());
+        self
+    }
+
+    /// Sets the path prefix inside the source that corresponds to the mount point.
+    pub fn with_source_root<P: AsRef<Path>>(mut self, source_root: P) -> Self {
+        self.source_root = normalize_path(source_root.as_ref());
+        self
+    }
+
+    /// Converts a virtual path to the path in the source, returns `None` if the path is outside of
+    /// the mount point.
+    fn source_path(&self, path: &Path) -> Option<PathBuf> {
+        let relative = normalize_path(path)
+            .strip_prefix(&self.mount_point)
+            .ok()?
+            .to_path_buf();
+        let source_path = self.source_root.join(relative);
+        // Empty path is not a valid directory for file system, so use `.` instead.
+        Some(if source_path.as_os_str().is_empty() {
+            PathBuf::from(".")
+        } else {
+            source_path
+        })
+    }
+
+    /// Converts a path in the source back to the virtual path.
+    fn virtual_path(&self, path: &Path) -> PathBuf {
+        let path = normalize_path(path);
+        match path.strip_prefix(&self.source_root) {
+            Ok(relative) => self.mount_point.join(relative),
+            Err(_) => path,
+        }
+    }
+}
+
+/// Virtual file system combines multiple mounted sources (project directory, DLC bundles, user mods
+/// directories and so on) into a single tree of files. When the same path exists in multiple sources,
+/// the source with the highest priority wins, if priorities are equal - the most recently mounted one
+/// wins. This allows games to support patching and modding without any custom path handling - a mod
+/// just needs to contain a file with the same path as the original one.
+///
+/// Virtual file system implements [`ResourceIo`], so it can be set directly to the resource manager.
+/// Mounts could be added and removed at any time, even after the file system was passed to the
+/// resource manager:
+///
+/// ```rust,no_run
+/// use fyrox_resource::{
+///     bundle::ResourceBundle,
+///     manager::ResourceManager,
+///     vfs::{Mount, VirtualFileSystem},
+/// };
+/// use std::sync::Arc;
+///
+/// let vfs = Arc::new(VirtualFileSystem::new());
+/// vfs.mount(Mount::directory("project", "."));
+/// vfs.mount(Mount::new("dlc", Arc::new(ResourceBundle::open("dlc.bundle").unwrap())).with_priority(1));
+/// vfs.mount(Mount::directory("my_mod", "mods/my_mod").with_priority(2));
+///
+/// let resource_manager = ResourceManager::new();
+/// resource_manager.state().set_resource_io(vfs.clone());
+/// ```
+#[derive(Default)]
+pub struct VirtualFileSystem {
+    // Sorted by priority in descending order.
+    mounts: RwLock<Vec<Mount>>,
+}
+
+impl VirtualFileSystem {
+    /// Creates a new virtual file system without any mounts.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a new mount to the file system. If there's already a mount with the same name, it will be
+    /// replaced.
+    pub fn mount(&self, mount: Mount) {
+        let mut mounts = self.mounts.write();
+        mounts.retain(|m| m.name != mount.name);
+        let position = mounts
+            .iter()
+            .position(|m| m.priority <= mount.priority)
+            .unwrap_or(mounts.len());
+        mounts.insert(position, mount);
+    }
+
+    /// Adds a new mount to the file system. See [`Self::mount`] for more info.
+    pub fn with_mount(self, mount: Mount) -> Self {
+        self.mount(mount);
+        self
+    }
+
+    /// Removes a mount with the given name. Returns `true` if the mount existed.
+    pub fn unmount(&self, name: &str) -> bool {
+        let mut mounts = self.mounts.write();
+        let count = mounts.len();
+        mounts.retain(|m| m.name != name);
+        mounts.len() != count
+    }
+
+    /// Returns `true` if there's a mount with the given name.
+    pub fn is_mounted(&self, name: &str) -> bool {
+        self.mounts.read().iter().any(|m| m.name == name)
+    }
+
+    /// Returns a copy of the current mounts, sorted by priority in descending order.
+    pub fn mounts(&self) -> Vec<Mount> {
+        self.mounts.read().clone()
+    }
+
+    /// Returns every mount that covers the given path along with the path inside the respective source.
+    /// The lock is released before returning, so the result could be used across await points.
+    fn candidates(&self, path: &Path) -> Vec<(Mount, PathBuf)> {
+        self.mounts
+            .read()
+            .iter()
+            .filter_map(|m| m.source_path(path).map(|p| (m.clone(), p)))
+            .collect()
+    }
+
+    /// Returns the name of the mount from which a file at the given path will be loaded.
+    pub async fn resolve(&self, path: &Path) -> Option<String> {
+        for (mount, source_path) in self.candidates(path) {
+            if mount.io.is_file(&source_path).await {
+                return Some(mount.name);
+            }
+        }
+        None
+    }
+
+    async fn list(&self, path: &Path, recursive: bool) -> Result<PathIter, FileLoadError> {
+        let mut visited = FxHashSet::default();
+        let mut paths = Vec::new();
+
+        // Mount points that are located deeper in the tree should be listed as directories too.
+        let path = normalize_path(path);
+        let mut nested = Vec::new();
+        for mount in self.mounts.read().iter() {
+            if let Ok(rest) = mount.mount_point.strip_prefix(&path) {
+                let mut directory = path.clone();
+                for component in rest.components() {
+                    directory.push(component);
+                    if visited.insert(directory.clone()) {
+                        paths.push(directory.clone());
+                    }
+                    if !recursive {
+                        break;
+                    }
+                }
+                if recursive && !rest.as_os_str().is_empty() {
+                    nested.push(mount.clone());
+                }
+            }
+        }
+
+        let mut sources = self.candidates(&path);
+        sources.extend(nested.into_iter().map(|m| {
+            let source_path = m.source_path(&m.mount_point).unwrap_or_default();
+            (m, source_path)
+        }));
+
+        for (mount, source_path) in sources {
+            if !mount.io.is_dir(&source_path).await {
+                continue;
+            }
+            let iter = if recursive {
+                mount.io.walk_directory(&source_path).await?
+            } else {
+                mount.io.read_directory(&source_path).await?
+            };
+            for item in iter {
+                let item = mount.virtual_path(&item);
+                if !item.as_os_str().is_empty() && visited.insert(item.clone()) {
+                    paths.push(item);
+                }
+            }
+        }
+        let iter: PathIter = Box::new(paths.into_iter());
+        Ok(iter)
+    }
+}
+
+fn not_found(path: &Path) -> FileLoadError {
+    FileLoadError::Io(io::Error::new(
+        io::ErrorKind::NotFound,
+        format!(
+            "There's no {} file in any of the mounted sources!",
+            path.display()
+        ),
+    ))
+}
+
+impl ResourceIo for VirtualFileSystem {
+    fn load_file<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<Vec<u8>, FileLoadError>> {
+        Box::pin(async move {
+            for (mount, source_path) in self.candidates(path) {
+                if mount.io.is_file(&source_path).await {
+                    return mount.io.load_file(&source_path).await;
+                }
+            }
+            Err(not_found(path))
+        })
+    }
+
+    fn read_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<PathIter, FileLoadError>> {
+        Box::pin(self.list(path, false))
+    }
+
+    fn walk_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<PathIter, FileLoadError>> {
+        Box::pin(self.list(path, true))
+    }
+
+    fn file_reader<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> ResourceIoFuture<'a, Result<Box<dyn FileReader>, FileLoadError>> {
+        Box::pin(async move {
+            for (mount, source_path) in self.candidates(path) {
+                if mount.io.is_file(&source_path).await {
+                    return mount.io.file_reader(&source_path).await;
+                }
+            }
+            Err(not_found(path))
+        })
+    }
+
+    fn exists<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, bool> {
+        Box::pin(async move {
+            for (mount, source_path) in self.candidates(path) {
+                if mount.io.exists(&source_path).await {
+                    return true;
+                }
+            }
+            false
+        })
+    }
+
+    fn is_file<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, bool> {
+        Box::pin(async move {
+            for (mount, source_path) in self.candidates(path) {
+                if mount.io.is_file(&source_path).await {
+                    return true;
+                }
+            }
+            false
+        })
+    }
+
+    fn is_dir<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, bool> {
+        Box::pin(async move {
+            for (mount, source_path) in self.candidates(path) {
+                if mount.io.is_dir(&source_path).await {
+                    return true;
+                }
+            }
+            false
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::bundle::{ResourceBundle, ResourceBundleBuilder};
+    use fyrox_core::futures::executor::block_on;
+
+    fn bundle(files: &[(&str, &[u8])]) -> Arc<dyn ResourceIo> {
+        let mut builder = ResourceBundleBuilder::new();
+        for (path, data) in files {
+            builder.add_file(path, data.to_vec()).unwrap();
+        }
+        Arc::new(ResourceBundle::from_bytes(builder.build().unwrap()).unwrap())
+    }
+
+    fn load(vfs: &VirtualFileSystem, path: &str) -> Option<Vec<u8>> {
+        block_on(vfs.load_file(Path::new(path))).ok()
+    }
+
+    #[test]
+    fn test_vfs_priorities() {
+        let vfs = VirtualFileSystem::new()
+            .with_mount(Mount::new(
+                "base",
+                bundle(&[("data/a.txt", b"base"), ("data/b.txt", b"base")]),
+            ))
+            .with_mount(Mount::new("mod", bundle(&[("data/a.txt", b"mod")])).with_priority(1));
+
+        assert_eq!(load(&vfs, "data/a.txt").unwrap(), b"mod");
+        assert_eq!(load(&vfs, "./data/b.txt").unwrap(), b"base");
+        assert!(load(&vfs, "data/c.txt").is_none());
+        assert_eq!(
+            block_on(vfs.resolve(Path::new("data/a.txt"))).as_deref(),
+            Some("mod")
+        );
+
+        // Equal priority - the most recent mount wins.
+        vfs.mount(Mount::new("patch", bundle(&[("data/a.txt", b"patch")])).with_priority(1));
+        assert_eq!(load(&vfs, "data/a.txt").unwrap(), b"patch");
+
+        assert!(vfs.unmount("patch"));
+        assert!(vfs.unmount("mod"));
+        assert!(!vfs.unmount("mod"));
+        assert_eq!(load(&vfs, "data/a.txt").unwrap(), b"base");
+        assert!(vfs.is_mounted("base"));
+    }
+
+    #[test]
+    fn test_vfs_mount_point() {
+        let vfs = VirtualFileSystem::new()
+            .with_mount(Mount::new("base", bundle(&[("data/a.txt", b"base")])))
+            .with_mount(
+                Mount::new("dlc", bundle(&[("dlc/levels/x.txt", b"x")]))
+                    .with_mount_point("data/levels")
+                    .with_source_root("dlc/levels"),
+            );
+
+        assert_eq!(load(&vfs, "data/levels/x.txt").unwrap(), b"x");
+        assert!(load(&vfs, "dlc/levels/x.txt").is_none());
+        assert!(block_on(vfs.is_dir(Path::new("data/levels"))));
+        assert!(block_on(vfs.exists(Path::new("data/a.txt"))));
+
+        let mut children = block_on(vfs.read_directory(Path::new("data")))
+            .unwrap()
+            .collect::<Vec<_>>();
+        children.sort();
+        assert_eq!(
+            children,
+            vec![PathBuf::from("data/a.txt"), PathBuf::from("data/levels")]
+        );
+
+        let walked = block_on(vfs.walk_directory(Path::new("data")))
+            .unwrap()
+            .collect::<Vec<_>>();
+        assert!(walked.contains(&PathBuf::from("data/levels/x.txt")));
+
+        let walked = block_on(vfs.walk_directory(Path::new("data/levels")))
+            .unwrap()
+            .collect::<Vec<_>>();
+        assert_eq!(walked, vec![PathBuf::from("data/levels/x.txt")]);
+    }
+}
@@ -30,7 +30,7 @@ pub struct SoundBufferLoader {
 
 impl ResourceLoader for SoundBufferLoader {
     fn extensions(&self) -> &[&str] {
-        &["wav", "ogg"]
+        &["wav", "ogg", "flac"]
     }
 
     fn data_type_uuid(&self) -> Uuid {
@@ -47,18 +47,22 @@ impl ResourceLoader for SoundBufferLoader {
         let default_import_options = self.default_import_options.clone();
 
         Box::pin(async move {
-            let io = io.as_ref();
-
             let path = resource.path().to_path_buf();
 
-            let import_options = try_get_import_settings(&path, io)
+            let import_options = try_get_import_settings(&path, io.as_ref())
                 .await
                 .unwrap_or(default_import_options);
 
-            match DataSource::from_file(&path, io).await {
+            match DataSource::from_file(&path, io.as_ref()).await {
                 Ok(source) => {
                     let buffer = if import_options.stream {
-                        SoundBuffer::raw_streaming(source)
+                        SoundBuffer::raw_streaming(source).map(|mut buffer| {
+                            if let SoundBuffer::Streaming(ref mut streaming) = buffer {
+                                // Keep the IO to be able to reopen the file later on.
+                                streaming.set_resource_io(io.clone());
+                            }
+                            buffer
+                        })
                     } else {
                         SoundBuffer::raw_generic(source)
                     };
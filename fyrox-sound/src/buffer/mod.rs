@@ -46,7 +46,7 @@ pub enum DataSource {
         data: Box<dyn FileReader>,
     },
 
-    /// Data source is a memory block. Memory block must be in valid format (wav or vorbis/ogg). This variant can
+    /// Data source is a memory block. Memory block must be in valid format (wav, vorbis/ogg or flac). This variant can
     /// be used together with virtual file system.
     Memory(Cursor<Vec<u8>>),
 
@@ -157,6 +157,7 @@ pub enum SoundBufferResourceLoadError {
 
 /// Sound buffer is a data source for sound sources. See module documentation for more info.
 #[derive(Debug, Visit, Reflect)]
+#[allow(clippy::large_enum_variant)]
 pub enum SoundBuffer {
     /// General-purpose buffer, usually contains all the data and allows random
     /// access to samples. It is also used to make streaming buffer via composition.
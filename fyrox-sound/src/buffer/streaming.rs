@@ -33,8 +33,11 @@ use crate::{
     error::SoundError,
 };
 use fyrox_core::{reflect::prelude::*, visitor::prelude::*};
+use fyrox_resource::io::{FsResourceIo, ResourceIo, ResourceIoFuture};
 use std::{
+    fmt::{Debug, Formatter},
     ops::{Deref, DerefMut},
+    sync::Arc,
     time::Duration,
 };
 
@@ -50,6 +53,19 @@ pub struct StreamingBuffer {
     #[visit(skip)]
     #[reflect(hidden)]
     streaming_source: StreamingSource,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    resource_io: SourceIo,
+}
+
+/// Resource IO that was used to open the file of a streaming buffer, it is used to reopen the file.
+#[derive(Clone, Default)]
+struct SourceIo(Option<Arc<dyn ResourceIo>>);
+
+impl Debug for SourceIo {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "SourceIo")
+    }
 }
 
 #[derive(Debug)]
@@ -187,9 +203,42 @@ impl StreamingBuffer {
             },
             use_count: 0,
             streaming_source,
+            resource_io: Default::default(),
         })
     }
 
+    /// Sets resource IO that will be used to reopen the file of the buffer (see [`Self::reopen`]). It
+    /// should be the same IO that was used to open the file in the first place, otherwise the file
+    /// could be unreachable (if it is packed in a bundle, for example). Sound buffer loader sets it
+    /// automatically.
+    pub fn set_resource_io(&mut self, io: Arc<dyn ResourceIo>) {
+        self.resource_io = SourceIo(Some(io));
+    }
+
+    /// Creates a future, that opens the file of the buffer once again and creates a new streaming buffer,
+    /// that reads the file from the beginning. It allows to play the same file by multiple sound sources
+    /// at once. The file is opened using the resource IO of the buffer (see [`Self::set_resource_io`]),
+    /// or using the file system if there is no such IO. Returns `None` if the buffer is not backed by a
+    /// file (memory or raw streaming data sources), the future resolves to `None` if the file cannot be
+    /// opened or decoded.
+    pub fn reopen(&self) -> Option<ResourceIoFuture<'static, Option<Self>>> {
+        if self.generic.is_procedural || self.generic.external_source_path.as_os_str().is_empty() {
+            return None;
+        }
+        let path = self.generic.external_source_path.clone();
+        let io = self
+            .resource_io
+            .0
+            .clone()
+            .unwrap_or_else(|| Arc::new(FsResourceIo));
+        Some(Box::pin(async move {
+            let data = io.file_reader(&path).await.ok()?;
+            let mut buffer = Self::new(DataSource::File { path, data }).ok()?;
+            buffer.set_resource_io(io);
+            Some(buffer)
+        }))
+    }
+
     #[inline]
     pub(crate) fn read_next_block(&mut self) {
         self.streaming_source
@@ -1,7 +1,12 @@
 //! Everything related to audio buses and audio bus graphs. See docs of [`AudioBus`] and [`AudioBusGraph`]
 //! for more info and examples
 
-use crate::effects::{Effect, EffectRenderTrait};
+use crate::{
+    context::SAMPLE_RATE,
+    dsp::filters::OnePole,
+    effects::{Effect, EffectRenderTrait},
+    mixer::{BusModifier, BusSnapshot, Mixer},
+};
 use fyrox_core::{
     pool::{Handle, Pool, Ticket},
     reflect::prelude::*,
@@ -86,6 +91,12 @@ pub struct AudioBus {
     effects: Vec<Effect>,
     gain: f32,
 
+    #[visit(optional)]
+    pitch: f32,
+
+    #[visit(optional)]
+    snapshots: Vec<BusSnapshot>,
+
     #[reflect(hidden)]
     child_buses: Vec<Handle<AudioBus>>,
 
@@ -95,6 +106,14 @@ pub struct AudioBus {
     #[reflect(hidden)]
     #[visit(skip)]
     ping_pong_buffer: PingPongBuffer,
+
+    #[reflect(hidden)]
+    #[visit(skip)]
+    modifier: BusModifier,
+
+    #[reflect(hidden)]
+    #[visit(skip)]
+    low_pass: [OnePole; 2],
 }
 
 impl Default for AudioBus {
@@ -104,8 +123,12 @@ impl Default for AudioBus {
             child_buses: Default::default(),
             effects: Default::default(),
             gain: 1.0,
+            pitch: 1.0,
+            snapshots: Default::default(),
             ping_pong_buffer: Default::default(),
             parent_bus: Default::default(),
+            modifier: Default::default(),
+            low_pass: Default::default(),
         }
     }
 }
@@ -152,6 +175,43 @@ impl AudioBus {
         self.gain
     }
 
+    /// Sets new pitch of the audio bus. It is applied to every sound source bound to this bus or to
+    /// any of its descendant buses.
+    pub fn set_pitch(&mut self, pitch: f32) {
+        self.pitch = pitch;
+    }
+
+    /// Returns current pitch of the audio bus.
+    pub fn pitch(&self) -> f32 {
+        self.pitch
+    }
+
+    /// Adds settings of the bus for a mixer snapshot. If the bus already has the settings for a snapshot
+    /// with the same name, they will be replaced. See [`Mixer`] docs for more info.
+    pub fn add_snapshot(&mut self, snapshot: BusSnapshot) {
+        if let Some(existing) = self.snapshots.iter_mut().find(|s| s.name == snapshot.name) {
+            *existing = snapshot;
+        } else {
+            self.snapshots.push(snapshot);
+        }
+    }
+
+    /// Removes settings of the bus for a mixer snapshot with the given name.
+    pub fn remove_snapshot(&mut self, name: &str) -> Option<BusSnapshot> {
+        let index = self.snapshots.iter().position(|s| s.name == name)?;
+        Some(self.snapshots.remove(index))
+    }
+
+    /// Returns settings of the bus for every mixer snapshot it takes part in.
+    pub fn snapshots(&self) -> &[BusSnapshot] {
+        &self.snapshots
+    }
+
+    /// Returns current combined result of all active mixer snapshots for this bus.
+    pub fn modifier(&self) -> BusModifier {
+        self.modifier
+    }
+
     pub(crate) fn input_buffer(&mut self) -> &mut [(f32, f32)] {
         self.ping_pong_buffer.input_mut()
     }
@@ -171,6 +231,17 @@ impl AudioBus {
             effect.render(input, output);
             self.ping_pong_buffer.swap();
         }
+
+        if self.modifier.cutoff_frequency_hz < BusModifier::BYPASS_FREQUENCY {
+            let fc = self.modifier.cutoff_frequency_hz / SAMPLE_RATE as f32;
+            let [left_filter, right_filter] = &mut self.low_pass;
+            left_filter.set_fc(fc);
+            right_filter.set_fc(fc);
+            for (left, right) in self.ping_pong_buffer.input_mut() {
+                *left = left_filter.feed(*left);
+                *right = right_filter.feed(*right);
+            }
+        }
     }
 
     /// Adds new effect to the effects chain.
@@ -287,6 +358,10 @@ impl AudioBus {
 pub struct AudioBusGraph {
     buses: Pool<AudioBus>,
     root: Handle<AudioBus>,
+
+    #[reflect(hidden)]
+    #[visit(skip)]
+    mixer: Mixer,
 }
 
 impl AudioBusGraph {
@@ -299,7 +374,11 @@ impl AudioBusGraph {
         let root = AudioBus::new(Self::PRIMARY_BUS.to_string());
         let mut buses = Pool::new();
         let root = buses.spawn(root);
-        Self { buses, root }
+        Self {
+            buses,
+            root,
+            mixer: Default::default(),
+        }
     }
 
     /// Adds a new audio bus to the graph and attaches it to the given parent. `parent` handle must be
@@ -347,6 +426,44 @@ impl AudioBusGraph {
         self.buses[parent].child_buses.push(child);
     }
 
+    /// Tries to find an audio bus with the given name and returns its handle.
+    pub fn find_bus(&self, name: &str) -> Option<Handle<AudioBus>> {
+        self.buses
+            .pair_iter()
+            .find_map(|(handle, bus)| if bus.name == name { Some(handle) } else { None })
+    }
+
+    /// Returns a reference to the mixer, that could be used to check which snapshots are active.
+    pub fn mixer(&self) -> &Mixer {
+        &self.mixer
+    }
+
+    /// Returns a reference to the mixer, that could be used to activate or deactivate snapshots.
+    pub fn mixer_mut(&mut self) -> &mut Mixer {
+        &mut self.mixer
+    }
+
+    /// Advances transitions of the mixer snapshots and applies their result to every audio bus. This
+    /// method is called automatically by the engine every frame.
+    pub fn update_mixer(&mut self, dt: f32) {
+        self.mixer.update(dt);
+        for bus in self.buses.iter_mut() {
+            bus.modifier = self.mixer.calculate_modifier(&bus.snapshots);
+        }
+    }
+
+    /// Calculates the resulting pitch of the audio bus with the given name, that includes pitches of all
+    /// its ancestors and the active mixer snapshots.
+    pub fn bus_pitch(&self, name: &str) -> f32 {
+        let mut pitch = 1.0;
+        let mut handle = self.find_bus(name).unwrap_or_default();
+        while let Some(bus) = self.buses.try_borrow(handle) {
+            pitch *= bus.pitch * bus.modifier.pitch;
+            handle = bus.parent_bus;
+        }
+        pitch
+    }
+
     pub(crate) fn try_get_bus_input_buffer(&mut self, name: &str) -> Option<&mut [(f32, f32)]> {
         self.buses.iter_mut().find_map(|bus| {
             if bus.name == name {
@@ -476,7 +593,7 @@ impl AudioBusGraph {
                 let leaf_ref = ctx.try_get(leaf).expect("Malformed bus graph!");
 
                 let input_buffer = leaf_ref.ping_pong_buffer.input_ref();
-                let leaf_gain = leaf_ref.gain;
+                let leaf_gain = leaf_ref.gain * leaf_ref.modifier.gain;
                 let output_buffer = if leaf_ref.parent_bus.is_none() {
                     // Special case for the root bus - it writes directly to the output device buffer.
                     &mut *output_device_buffer
@@ -505,6 +622,7 @@ mod test {
     use crate::{
         bus::{AudioBus, AudioBusGraph},
         effects::{Attenuate, Effect},
+        mixer::BusSnapshot,
     };
 
     #[test]
@@ -586,4 +704,36 @@ mod test {
 
         assert_eq!(output_buffer[0], (0.75, 0.75));
     }
+
+    #[test]
+    fn test_mixer_snapshots() {
+        let mut output_buffer = [(0.0f32, 0.0f32)];
+
+        let mut graph = AudioBusGraph::new();
+
+        let mut music = AudioBus::new("Music".to_string());
+        music.set_pitch(0.5);
+        music.add_snapshot(BusSnapshot::new("Dialog").with_gain(0.25).with_pitch(2.0));
+        let music = graph.add_bus(music, graph.root);
+        graph.primary_bus_mut().set_pitch(0.5);
+
+        assert_eq!(graph.bus_pitch("Music"), 0.25);
+
+        graph.mixer_mut().activate_snapshot("Dialog", 0.0);
+        graph.update_mixer(0.0);
+
+        assert_eq!(graph.bus_pitch("Music"), 0.5);
+        assert_eq!(graph.bus_pitch(AudioBusGraph::PRIMARY_BUS), 0.5);
+
+        graph.begin_render(output_buffer.len());
+
+        for (left, right) in graph.buses[music].input_buffer() {
+            *left = 1.0;
+            *right = 1.0;
+        }
+
+        graph.end_render(&mut output_buffer);
+
+        assert_eq!(output_buffer[0], (0.25, 0.25));
+    }
 }
@@ -78,7 +78,7 @@ pub enum DistanceModel {
     ///         `rolloff_factor` - coefficient that defines how fast volume will decay
     ExponentDistance = 3,
 
-	 BetterLinearDistance = 4,
+    BetterLinearDistance = 4,
     //CustomDistance(fn(f32) -> f32) = 4,
     //CustomDistance(Box<dyn Fn(f32) -> f32>) = 4,
     //CustomDistance(Box<fn(f32) -> f32>) = 4,
@@ -275,6 +275,8 @@ impl State {
                 .iter_mut()
                 .filter(|s| s.status() == Status::Playing)
             {
+                source.bus_pitch = self.bus_graph.bus_pitch(&source.bus) as f64;
+
                 if let Some(bus_input_buffer) = self.bus_graph.try_get_bus_input_buffer(&source.bus)
                 {
                     source.render(output_device_buffer.len());
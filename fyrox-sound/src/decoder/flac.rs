@@ -0,0 +1,274 @@
+use crate::{buffer::DataSource, error::SoundError};
+use claxon::{
+    frame::FrameReader,
+    input::BufferedReader,
+    metadata::{SeekPoint, StreamInfo},
+    Block, FlacReader,
+};
+use std::{
+    fmt::{Debug, Formatter},
+    io::{self, Read, Seek, SeekFrom},
+    time::Duration,
+    vec,
+};
+
+/// Type of the SEEKTABLE metadata block.
+const SEEK_TABLE_BLOCK_TYPE: u8 = 3;
+/// Size of a single seek point in the SEEKTABLE metadata block.
+const SEEK_POINT_SIZE: u64 = 18;
+
+pub struct FlacDecoder {
+    // Option here is because the reader must be re-created on every seek - claxon does not support seeking.
+    frames: Option<FrameReader<BufferedReader<DataSource>>>,
+    samples: vec::IntoIter<f32>,
+    // Position of the first frame of the stream in the data source.
+    first_frame_position: u64,
+    // Seek points from the seek table of the stream (if any), offsets are relative to the first frame.
+    seek_points: Vec<SeekPoint>,
+    // Block buffer that is reused between blocks to avoid allocations.
+    buffer: Vec<i32>,
+    scale: f32,
+    pub channel_count: usize,
+    pub sample_rate: usize,
+    pub channel_duration_in_samples: usize,
+}
+
+impl Debug for FlacDecoder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "FlacDecoder")
+    }
+}
+
+impl Iterator for FlacDecoder {
+    type Item = f32;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(sample) = self.samples.next() {
+            Some(sample)
+        } else {
+            self.read_block();
+            self.samples.next()
+        }
+    }
+}
+
+fn read_stream_info(source: &mut DataSource) -> Option<StreamInfo> {
+    let pos = source.stream_position().unwrap();
+
+    let info = FlacReader::new(source.by_ref())
+        .ok()
+        .map(|reader| reader.streaminfo());
+
+    source.seek(SeekFrom::Start(pos)).unwrap();
+
+    info
+}
+
+/// Skips the metadata blocks of a stream and collects the seek points of its seek table (if any). The source must
+/// be at the beginning of the stream, it is left at the first frame of the stream.
+fn read_metadata(source: &mut DataSource) -> io::Result<Vec<SeekPoint>> {
+    // `fLaC` marker.
+    source.seek(SeekFrom::Current(4))?;
+
+    let mut seek_points = Vec::new();
+    loop {
+        let mut header = [0; 4];
+        source.read_exact(&mut header)?;
+        let length = u32::from_be_bytes([0, header[1], header[2], header[3]]) as u64;
+
+        if header[0] & 0x7F == SEEK_TABLE_BLOCK_TYPE {
+            for _ in 0..length / SEEK_POINT_SIZE {
+                let mut point = [0; SEEK_POINT_SIZE as usize];
+                source.read_exact(&mut point)?;
+                let sample = u64::from_be_bytes(point[0..8].try_into().unwrap());
+                // Skip placeholders.
+                if sample != u64::MAX {
+                    seek_points.push(SeekPoint {
+                        sample,
+                        offset: u64::from_be_bytes(point[8..16].try_into().unwrap()),
+                        samples: u16::from_be_bytes(point[16..18].try_into().unwrap()),
+                    });
+                }
+            }
+            source.seek(SeekFrom::Current((length % SEEK_POINT_SIZE) as i64))?;
+        } else {
+            source.seek(SeekFrom::Current(length as i64))?;
+        }
+
+        // Last metadata block flag.
+        if header[0] & 0x80 != 0 {
+            return Ok(seek_points);
+        }
+    }
+}
+
+impl FlacDecoder {
+    pub fn new(mut source: DataSource) -> Result<Self, DataSource> {
+        let info = match read_stream_info(&mut source) {
+            Some(info) => info,
+            None => return Err(source),
+        };
+
+        let start_position = source.stream_position().unwrap();
+        let seek_points = match read_metadata(&mut source) {
+            Ok(seek_points) => seek_points,
+            Err(_) => {
+                source.seek(SeekFrom::Start(start_position)).unwrap();
+                return Err(source);
+            }
+        };
+        let first_frame_position = source.stream_position().unwrap();
+
+        let mut decoder = Self {
+            frames: Some(FrameReader::new(BufferedReader::new(source))),
+            samples: Vec::new().into_iter(),
+            first_frame_position,
+            seek_points,
+            buffer: Default::default(),
+            scale: 1.0 / (1u64 << (info.bits_per_sample.clamp(1, 32) - 1)) as f32,
+            channel_count: info.channels as usize,
+            sample_rate: info.sample_rate as usize,
+            channel_duration_in_samples: info.samples.unwrap_or_default() as usize,
+        };
+
+        decoder.read_block();
+
+        Ok(decoder)
+    }
+
+    fn next_block(&mut self) -> Option<Block> {
+        let frames = self.frames.as_mut()?;
+        let buffer = std::mem::take(&mut self.buffer);
+        frames.read_next_or_eof(buffer).ok().flatten()
+    }
+
+    fn read_block(&mut self) {
+        if let Some(block) = self.next_block() {
+            self.set_block(block, 0);
+        }
+    }
+
+    fn set_block(&mut self, block: Block, first_sample: u32) {
+        // Interleave samples, the rest of the library works with interleaved samples.
+        let mut samples = Vec::with_capacity(block.len() as usize);
+        for i in first_sample..block.duration() {
+            for channel in 0..block.channels() {
+                samples.push(block.sample(channel, i) as f32 * self.scale);
+            }
+        }
+        self.samples = samples.into_iter();
+        self.buffer = block.into_buffer();
+    }
+
+    fn seek_frame(&mut self, position: u64) -> Result<(), SoundError> {
+        let mut source = self
+            .frames
+            .take()
+            .ok_or(SoundError::UnsupportedFormat)?
+            .into_inner()
+            .into_inner();
+        source.seek(SeekFrom::Start(position))?;
+        self.frames = Some(FrameReader::new(BufferedReader::new(source)));
+        self.samples = Vec::new().into_iter();
+        Ok(())
+    }
+
+    pub fn rewind(&mut self) -> Result<(), SoundError> {
+        self.seek_frame(self.first_frame_position)?;
+        self.read_block();
+        Ok(())
+    }
+
+    pub fn time_seek(&mut self, location: Duration) {
+        let sample_index = (location.as_secs_f64() * self.sample_rate as f64) as u64;
+
+        // claxon does not support seeking, so jump to the closest seek point before the desired position (or
+        // to the first frame if the stream has no seek table) and skip whole blocks from there.
+        let offset = self
+            .seek_points
+            .iter()
+            .rev()
+            .find(|point| point.sample <= sample_index)
+            .map_or(0, |point| point.offset);
+        if self.seek_frame(self.first_frame_position + offset).is_err() {
+            return;
+        }
+
+        while let Some(block) = self.next_block() {
+            if sample_index < block.time() + block.duration() as u64 {
+                let first_sample = sample_index.saturating_sub(block.time()) as u32;
+                self.set_block(block, first_sample);
+                return;
+            }
+            self.buffer = block.into_buffer();
+        }
+    }
+
+    pub fn channel_duration_in_samples(&self) -> usize {
+        self.channel_duration_in_samples
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{buffer::DataSource, decoder::flac::FlacDecoder};
+    use std::time::Duration;
+
+    fn load() -> FlacDecoder {
+        let data = std::fs::read("examples/data/sine_440hz.flac").unwrap();
+        FlacDecoder::new(DataSource::from_memory(data)).unwrap()
+    }
+
+    fn expected_sample(index: usize) -> f32 {
+        (index as f32 * 440.0 * std::f32::consts::TAU / 8000.0).sin() * 0.5
+    }
+
+    #[test]
+    fn test_flac_decode() {
+        let decoder = load();
+        assert_eq!(decoder.channel_count, 1);
+        assert_eq!(decoder.sample_rate, 8000);
+        assert_eq!(decoder.channel_duration_in_samples(), 4096);
+        assert!(!decoder.seek_points.is_empty());
+
+        let samples = decoder.collect::<Vec<_>>();
+        assert_eq!(samples.len(), 4096);
+        for (i, sample) in samples.into_iter().enumerate() {
+            assert!((sample - expected_sample(i)).abs() < 0.001);
+        }
+
+        assert!(FlacDecoder::new(DataSource::from_memory(vec![0; 64])).is_err());
+    }
+
+    #[test]
+    fn test_flac_rewind() {
+        let mut decoder = load();
+        let first = decoder.by_ref().take(100).collect::<Vec<_>>();
+        assert_eq!(decoder.by_ref().count(), 4096 - 100);
+
+        decoder.rewind().unwrap();
+        assert_eq!(decoder.by_ref().take(100).collect::<Vec<_>>(), first);
+        assert_eq!(decoder.count(), 4096 - 100);
+    }
+
+    #[test]
+    fn test_flac_time_seek() {
+        let mut decoder = load();
+        let samples = decoder.by_ref().collect::<Vec<_>>();
+
+        // Exactly at a seek point, between the seek points and back to the start.
+        for index in [2048, 3000, 100, 0] {
+            decoder.time_seek(Duration::from_secs_f64(index as f64 / 8000.0));
+            assert_eq!(decoder.by_ref().collect::<Vec<_>>(), samples[index..]);
+        }
+
+        // Without a seek table the blocks are skipped from the first frame.
+        decoder.seek_points.clear();
+        decoder.time_seek(Duration::from_secs_f64(3000.0 / 8000.0));
+        assert_eq!(decoder.by_ref().collect::<Vec<_>>(), samples[3000..]);
+
+        decoder.time_seek(Duration::from_secs(10));
+        assert_eq!(decoder.next(), None);
+    }
+}
@@ -1,10 +1,11 @@
 use crate::{
     buffer::DataSource,
-    decoder::{vorbis::OggDecoder, wav::WavDecoder},
+    decoder::{flac::FlacDecoder, vorbis::OggDecoder, wav::WavDecoder},
     error::SoundError,
 };
 use std::time::Duration;
 
+mod flac;
 mod vorbis;
 mod wav;
 
@@ -12,6 +13,7 @@ mod wav;
 pub(crate) enum Decoder {
     Wav(WavDecoder),
     Ogg(OggDecoder),
+    Flac(FlacDecoder),
 }
 
 impl Iterator for Decoder {
@@ -21,6 +23,7 @@ impl Iterator for Decoder {
         match self {
             Decoder::Wav(wav) => wav.next(),
             Decoder::Ogg(ogg) => ogg.next(),
+            Decoder::Flac(flac) => flac.next(),
         }
     }
 }
@@ -37,6 +40,11 @@ impl Decoder {
             Ok(ogg_decoder) => return Ok(Decoder::Ogg(ogg_decoder)),
             Err(source) => source,
         };
+        // Try Flac
+        let source = match FlacDecoder::new(source) {
+            Ok(flac_decoder) => return Ok(Decoder::Flac(flac_decoder)),
+            Err(source) => source,
+        };
         Err(source)
     }
 
@@ -44,6 +52,7 @@ impl Decoder {
         match self {
             Decoder::Wav(wav) => wav.rewind(),
             Decoder::Ogg(ogg) => ogg.rewind(),
+            Decoder::Flac(flac) => flac.rewind(),
         }
     }
 
@@ -51,6 +60,7 @@ impl Decoder {
         match self {
             Decoder::Wav(wav) => wav.time_seek(location),
             Decoder::Ogg(ogg) => ogg.time_seek(location),
+            Decoder::Flac(flac) => flac.time_seek(location),
         }
     }
 
@@ -58,6 +68,7 @@ impl Decoder {
         match self {
             Decoder::Wav(wav) => wav.channel_count(),
             Decoder::Ogg(ogg) => ogg.channel_count,
+            Decoder::Flac(flac) => flac.channel_count,
         }
     }
 
@@ -65,6 +76,7 @@ impl Decoder {
         match self {
             Decoder::Wav(wav) => wav.sample_rate(),
             Decoder::Ogg(ogg) => ogg.sample_rate,
+            Decoder::Flac(flac) => flac.sample_rate,
         }
     }
 
@@ -76,6 +88,7 @@ impl Decoder {
         match self {
             Decoder::Wav(wav) => wav.channel_duration_in_samples(),
             Decoder::Ogg(ogg) => ogg.channel_duration_in_samples(),
+            Decoder::Flac(flac) => flac.channel_duration_in_samples(),
         }
     }
 }
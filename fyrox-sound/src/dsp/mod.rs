@@ -56,6 +56,12 @@ impl DelayLine {
         self.last
     }
 
+    /// Returns the sample that will be returned by the next call of [`Self::feed`], without processing
+    /// anything. It is useful to create feedback loops.
+    pub fn peek(&self) -> f32 {
+        self.samples.0[self.pos as usize]
+    }
+
     /// Returns last processed sample.
     pub fn last(&self) -> f32 {
         self.last
@@ -72,6 +78,19 @@ impl Default for DelayLine {
     }
 }
 
+/// Reads a stereo sample from a circular buffer at the given (fractional) distance in samples behind
+/// the write position, using linear interpolation between neighbouring samples. `delay` must be in
+/// `0.0..buffer.len() - 1` range, zero delay corresponds to the sample at the write position.
+pub fn read_interpolated(buffer: &[(f32, f32)], write_pos: usize, delay: f32) -> (f32, f32) {
+    let len = buffer.len();
+    let position = (write_pos + len) as f32 - delay;
+    let index = position.floor();
+    let t = position - index;
+    let a = buffer[index as usize % len];
+    let b = buffer[(index as usize + 1) % len];
+    (a.0 + (b.0 - a.0) * t, a.1 + (b.1 - a.1) * t)
+}
+
 /// Calculates single coefficient of Hamming window.
 /// <https://en.wikipedia.org/wiki/Window_function#Hamming_window>
 pub fn hamming_window(i: usize, sample_count: usize) -> f32 {
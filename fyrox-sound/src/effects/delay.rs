@@ -0,0 +1,201 @@
+//! Delay (echo) effect. See [`DelayEffect`] docs for more info.
+
+use crate::{context::SAMPLE_RATE, dsp::read_interpolated, effects::EffectRenderTrait};
+use fyrox_core::{reflect::prelude::*, visitor::prelude::*};
+
+/// Maximum delay time in seconds, the buffer of the effect is allocated for this time once.
+const MAX_DELAY_TIME: f32 = 10.0;
+
+/// How fast the read tap moves to the new position when delay time changes (per sample). Moving tap
+/// smoothly prevents clicks, the echo slides in pitch instead, just like on a tape delay.
+const TAP_SMOOTHNESS: f32 = 0.0005;
+
+/// Delay effect repeats the input signal after some period of time, each repetition is quieter than
+/// the previous one. It could be used to simulate echo in mountains or in large empty spaces.
+#[derive(Clone, Reflect, Visit, Debug, PartialEq)]
+pub struct DelayEffect {
+    #[reflect(
+        description = "Time in seconds between repetitions.",
+        setter = "set_delay_time",
+        min_value = 0.001,
+        max_value = 10.0
+    )]
+    delay_time: f32,
+
+    #[reflect(
+        description = "How much of the delayed signal is fed back to the delay, defines the amount of repetitions.",
+        setter = "set_feedback",
+        min_value = 0.0,
+        max_value = 0.99
+    )]
+    feedback: f32,
+
+    #[reflect(
+        description = "How much of the input signal passes to the output without processing.",
+        setter = "set_dry",
+        min_value = 0.0,
+        max_value = 1.0
+    )]
+    dry: f32,
+
+    #[reflect(
+        description = "Gain of the delayed signal.",
+        setter = "set_wet",
+        min_value = 0.0,
+        max_value = 1.0
+    )]
+    wet: f32,
+
+    #[reflect(hidden)]
+    #[visit(skip)]
+    buffer: Vec<(f32, f32)>,
+    #[reflect(hidden)]
+    #[visit(skip)]
+    write_pos: usize,
+    /// Current distance between the read tap and the write position in samples.
+    #[reflect(hidden)]
+    #[visit(skip)]
+    tap: f32,
+}
+
+impl Default for DelayEffect {
+    fn default() -> Self {
+        Self {
+            delay_time: 0.3,
+            feedback: 0.4,
+            dry: 1.0,
+            wet: 0.5,
+            buffer: Default::default(),
+            write_pos: 0,
+            tap: 0.0,
+        }
+    }
+}
+
+impl DelayEffect {
+    /// Sets time in seconds between repetitions. Maximum delay time is 10 seconds. The delay time
+    /// could be changed while the effect is playing, already delayed signal is kept.
+    pub fn set_delay_time(&mut self, delay_time: f32) {
+        self.delay_time = delay_time.clamp(0.001, MAX_DELAY_TIME);
+    }
+
+    /// Returns time in seconds between repetitions.
+    pub fn delay_time(&self) -> f32 {
+        self.delay_time
+    }
+
+    /// Sets how much of the delayed signal is fed back to the delay. The higher the value, the more
+    /// repetitions will be heard.
+    pub fn set_feedback(&mut self, feedback: f32) {
+        self.feedback = feedback.clamp(0.0, 0.99);
+    }
+
+    /// Returns feedback coefficient.
+    pub fn feedback(&self) -> f32 {
+        self.feedback
+    }
+
+    /// Sets how much of the input signal passes to the output without processing.
+    pub fn set_dry(&mut self, dry: f32) {
+        self.dry = dry.clamp(0.0, 1.0);
+    }
+
+    /// Returns dry part.
+    pub fn dry(&self) -> f32 {
+        self.dry
+    }
+
+    /// Sets gain of the delayed signal.
+    pub fn set_wet(&mut self, wet: f32) {
+        self.wet = wet.clamp(0.0, 1.0);
+    }
+
+    /// Returns gain of the delayed signal.
+    pub fn wet(&self) -> f32 {
+        self.wet
+    }
+
+    pub(crate) fn sync_parameters(&mut self, other: &Self) {
+        self.delay_time = other.delay_time;
+        self.feedback = other.feedback;
+        self.dry = other.dry;
+        self.wet = other.wet;
+    }
+}
+
+impl EffectRenderTrait for DelayEffect {
+    fn render(&mut self, input: &[(f32, f32)], output: &mut [(f32, f32)]) {
+        // The buffer is allocated lazily, this way it could be skipped on serialization.
+        if self.buffer.is_empty() {
+            self.buffer = vec![(0.0, 0.0); (MAX_DELAY_TIME * SAMPLE_RATE as f32) as usize + 2];
+        }
+
+        let target_tap = (self.delay_time * SAMPLE_RATE as f32).max(1.0);
+        if self.tap == 0.0 {
+            self.tap = target_tap;
+        }
+
+        for ((input_left, input_right), (output_left, output_right)) in input.iter().zip(output) {
+            self.tap += (target_tap - self.tap) * TAP_SMOOTHNESS;
+            let (delayed_left, delayed_right) =
+                read_interpolated(&self.buffer, self.write_pos, self.tap);
+            self.buffer[self.write_pos] = (
+                *input_left + delayed_left * self.feedback,
+                *input_right + delayed_right * self.feedback,
+            );
+            self.write_pos = (self.write_pos + 1) % self.buffer.len();
+            *output_left = *input_left * self.dry + delayed_left * self.wet;
+            *output_right = *input_right * self.dry + delayed_right * self.wet;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        context::SAMPLE_RATE,
+        effects::{delay::DelayEffect, EffectRenderTrait},
+    };
+
+    fn render_impulse(effect: &mut DelayEffect, len: usize) -> Vec<(f32, f32)> {
+        let mut input = vec![(0.0, 0.0); len];
+        input[0] = (1.0, 1.0);
+        let mut output = vec![(0.0, 0.0); len];
+        effect.render(&input, &mut output);
+        output
+    }
+
+    #[test]
+    fn test_delay_repeats_impulse() {
+        let mut effect = DelayEffect::default();
+        effect.set_delay_time(0.01);
+        effect.set_feedback(0.5);
+        effect.set_dry(0.0);
+        effect.set_wet(1.0);
+
+        let delay = (0.01 * SAMPLE_RATE as f32) as usize;
+        let output = render_impulse(&mut effect, 3 * delay);
+
+        assert_eq!(output[0], (0.0, 0.0));
+        assert!((output[delay].0 - 1.0).abs() < 1.0e-5);
+        assert!((output[2 * delay].0 - 0.5).abs() < 1.0e-5);
+    }
+
+    #[test]
+    fn test_delay_time_change_keeps_buffer() {
+        let mut effect = DelayEffect::default();
+        effect.set_delay_time(0.01);
+        let output = render_impulse(&mut effect, 100);
+        assert_eq!(output[0], (1.0, 1.0));
+        let buffer_len = effect.buffer.len();
+
+        effect.set_delay_time(0.02);
+        let mut output = vec![(0.0, 0.0); 1000];
+        effect.render(&vec![(0.0, 0.0); 1000], &mut output);
+
+        // No reallocation and the tap moves smoothly towards the new delay time.
+        assert_eq!(effect.buffer.len(), buffer_len);
+        assert!(effect.tap > 0.01 * SAMPLE_RATE as f32);
+        assert!(effect.tap < 0.02 * SAMPLE_RATE as f32);
+    }
+}
@@ -0,0 +1,286 @@
+//! Dynamic range processing effects. See [`CompressorEffect`] and [`LimiterEffect`] docs for more info.
+
+use crate::{context::SAMPLE_RATE, effects::EffectRenderTrait};
+use fyrox_core::{reflect::prelude::*, visitor::prelude::*};
+
+fn db_to_linear(db: f32) -> f32 {
+    10.0f32.powf(db / 20.0)
+}
+
+fn linear_to_db(linear: f32) -> f32 {
+    20.0 * linear.max(1.0e-6).log10()
+}
+
+// Calculates a coefficient of one-pole smoothing filter that reaches ~63% of a target value in
+// the given amount of time.
+fn smoothing_coefficient(time: f32) -> f32 {
+    if time <= 0.0 {
+        0.0
+    } else {
+        (-1.0 / (time * SAMPLE_RATE as f32)).exp()
+    }
+}
+
+/// Compressor reduces the volume of loud sounds, thus narrowing the dynamic range of a signal. Every
+/// part of the signal with the level above the threshold is attenuated in accordance with the ratio.
+/// It could be used to make loud explosions less overwhelming or to make dialogs more consistent in
+/// loudness.
+#[derive(Clone, Reflect, Visit, Debug, PartialEq)]
+pub struct CompressorEffect {
+    #[reflect(
+        description = "Level in decibels above which the signal is compressed.",
+        setter = "set_threshold_db",
+        max_value = 0.0
+    )]
+    threshold_db: f32,
+
+    #[reflect(
+        description = "Compression ratio, for example 4.0 means that 4 dB of input above the threshold produce 1 dB of output.",
+        setter = "set_ratio",
+        min_value = 1.0
+    )]
+    ratio: f32,
+
+    #[reflect(
+        description = "Time in seconds that is needed for the compressor to react to rising level.",
+        setter = "set_attack_time",
+        min_value = 0.0
+    )]
+    attack_time: f32,
+
+    #[reflect(
+        description = "Time in seconds that is needed for the compressor to restore the gain after the level falls.",
+        setter = "set_release_time",
+        min_value = 0.0
+    )]
+    release_time: f32,
+
+    #[reflect(
+        description = "Gain in decibels applied after compression to compensate volume loss.",
+        setter = "set_makeup_gain_db"
+    )]
+    makeup_gain_db: f32,
+
+    #[reflect(hidden)]
+    #[visit(skip)]
+    envelope: f32,
+}
+
+impl Default for CompressorEffect {
+    fn default() -> Self {
+        Self {
+            threshold_db: -18.0,
+            ratio: 4.0,
+            attack_time: 0.01,
+            release_time: 0.1,
+            makeup_gain_db: 0.0,
+            envelope: 0.0,
+        }
+    }
+}
+
+impl CompressorEffect {
+    /// Sets level in decibels above which the signal is compressed.
+    pub fn set_threshold_db(&mut self, threshold_db: f32) {
+        self.threshold_db = threshold_db.min(0.0);
+    }
+
+    /// Returns level in decibels above which the signal is compressed.
+    pub fn threshold_db(&self) -> f32 {
+        self.threshold_db
+    }
+
+    /// Sets compression ratio. For example, 4.0 means that 4 dB of input above the threshold produce
+    /// only 1 dB of output.
+    pub fn set_ratio(&mut self, ratio: f32) {
+        self.ratio = ratio.max(1.0);
+    }
+
+    /// Returns compression ratio.
+    pub fn ratio(&self) -> f32 {
+        self.ratio
+    }
+
+    /// Sets time in seconds that is needed for the compressor to react to rising level.
+    pub fn set_attack_time(&mut self, attack_time: f32) {
+        self.attack_time = attack_time.max(0.0);
+    }
+
+    /// Returns attack time in seconds.
+    pub fn attack_time(&self) -> f32 {
+        self.attack_time
+    }
+
+    /// Sets time in seconds that is needed for the compressor to restore the gain after the level falls.
+    pub fn set_release_time(&mut self, release_time: f32) {
+        self.release_time = release_time.max(0.0);
+    }
+
+    /// Returns release time in seconds.
+    pub fn release_time(&self) -> f32 {
+        self.release_time
+    }
+
+    /// Sets gain in decibels applied after compression to compensate volume loss.
+    pub fn set_makeup_gain_db(&mut self, makeup_gain_db: f32) {
+        self.makeup_gain_db = makeup_gain_db;
+    }
+
+    /// Returns makeup gain in decibels.
+    pub fn makeup_gain_db(&self) -> f32 {
+        self.makeup_gain_db
+    }
+
+    pub(crate) fn sync_parameters(&mut self, other: &Self) {
+        self.threshold_db = other.threshold_db;
+        self.ratio = other.ratio;
+        self.attack_time = other.attack_time;
+        self.release_time = other.release_time;
+        self.makeup_gain_db = other.makeup_gain_db;
+    }
+}
+
+impl EffectRenderTrait for CompressorEffect {
+    fn render(&mut self, input: &[(f32, f32)], output: &mut [(f32, f32)]) {
+        let attack = smoothing_coefficient(self.attack_time);
+        let release = smoothing_coefficient(self.release_time);
+        let makeup_gain = db_to_linear(self.makeup_gain_db);
+        let slope = 1.0 - 1.0 / self.ratio;
+
+        for ((input_left, input_right), (output_left, output_right)) in input.iter().zip(output) {
+            // Channels are linked to keep stereo image stable.
+            let level = input_left.abs().max(input_right.abs());
+            let k = if level > self.envelope {
+                attack
+            } else {
+                release
+            };
+            self.envelope = level + k * (self.envelope - level);
+
+            let overshoot_db = linear_to_db(self.envelope) - self.threshold_db;
+            let gain = if overshoot_db > 0.0 {
+                db_to_linear(-overshoot_db * slope)
+            } else {
+                1.0
+            } * makeup_gain;
+
+            *output_left = *input_left * gain;
+            *output_right = *input_right * gain;
+        }
+    }
+}
+
+/// Limiter prevents the signal from exceeding the given level; it reacts instantly to peaks and
+/// smoothly restores the gain afterwards. It is usually placed at the end of an effect chain of the
+/// primary audio bus to prevent clipping when many loud sounds are playing at once.
+#[derive(Clone, Reflect, Visit, Debug, PartialEq)]
+pub struct LimiterEffect {
+    #[reflect(
+        description = "Maximum level of the output signal in decibels.",
+        setter = "set_threshold_db",
+        max_value = 0.0
+    )]
+    threshold_db: f32,
+
+    #[reflect(
+        description = "Time in seconds that is needed for the limiter to restore the gain after a peak.",
+        setter = "set_release_time",
+        min_value = 0.0
+    )]
+    release_time: f32,
+
+    #[reflect(hidden)]
+    #[visit(skip)]
+    envelope: f32,
+}
+
+impl Default for LimiterEffect {
+    fn default() -> Self {
+        Self {
+            threshold_db: -1.0,
+            release_time: 0.05,
+            envelope: 0.0,
+        }
+    }
+}
+
+impl LimiterEffect {
+    /// Sets maximum level of the output signal in decibels.
+    pub fn set_threshold_db(&mut self, threshold_db: f32) {
+        self.threshold_db = threshold_db.min(0.0);
+    }
+
+    /// Returns maximum level of the output signal in decibels.
+    pub fn threshold_db(&self) -> f32 {
+        self.threshold_db
+    }
+
+    /// Sets time in seconds that is needed for the limiter to restore the gain after a peak.
+    pub fn set_release_time(&mut self, release_time: f32) {
+        self.release_time = release_time.max(0.0);
+    }
+
+    /// Returns release time in seconds.
+    pub fn release_time(&self) -> f32 {
+        self.release_time
+    }
+
+    pub(crate) fn sync_parameters(&mut self, other: &Self) {
+        self.threshold_db = other.threshold_db;
+        self.release_time = other.release_time;
+    }
+}
+
+impl EffectRenderTrait for LimiterEffect {
+    fn render(&mut self, input: &[(f32, f32)], output: &mut [(f32, f32)]) {
+        let release = smoothing_coefficient(self.release_time);
+        let threshold = db_to_linear(self.threshold_db);
+
+        for ((input_left, input_right), (output_left, output_right)) in input.iter().zip(output) {
+            let level = input_left.abs().max(input_right.abs());
+            self.envelope = if level > self.envelope {
+                level
+            } else {
+                level + release * (self.envelope - level)
+            };
+
+            let gain = if self.envelope > threshold {
+                threshold / self.envelope
+            } else {
+                1.0
+            };
+
+            *output_left = *input_left * gain;
+            *output_right = *input_right * gain;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::effects::{
+        dynamics::{CompressorEffect, LimiterEffect},
+        EffectRenderTrait,
+    };
+
+    #[test]
+    fn test_limiter() {
+        let mut limiter = LimiterEffect::default();
+        limiter.set_threshold_db(-6.0);
+        let input = vec![(1.0, -1.0); 64];
+        let mut output = vec![(0.0, 0.0); 64];
+        limiter.render(&input, &mut output);
+        for (left, right) in output {
+            assert!(left <= 0.502 && right >= -0.502);
+        }
+    }
+
+    #[test]
+    fn test_compressor_passes_quiet_signal() {
+        let mut compressor = CompressorEffect::default();
+        let input = vec![(0.01, 0.01); 64];
+        let mut output = vec![(0.0, 0.0); 64];
+        compressor.render(&input, &mut output);
+        assert_eq!(input, output);
+    }
+}
@@ -0,0 +1,253 @@
+//! Parametric equalizer effect. See [`ParametricEqualizerEffect`] docs for more info.
+
+use crate::{
+    context::SAMPLE_RATE,
+    dsp::filters::{Biquad, BiquadKind},
+    effects::EffectRenderTrait,
+};
+use fyrox_core::{reflect::prelude::*, visitor::prelude::*};
+use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
+
+/// Defines a shape of the frequency response of an equalizer band.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    Visit,
+    Reflect,
+    AsRefStr,
+    EnumString,
+    EnumVariantNames,
+)]
+pub enum EqualizerBandKind {
+    /// Boosts or cuts frequencies around the center frequency of the band.
+    #[default]
+    Peak,
+    /// Boosts or cuts frequencies below the center frequency of the band.
+    LowShelf,
+    /// Boosts or cuts frequencies above the center frequency of the band.
+    HighShelf,
+}
+
+impl EqualizerBandKind {
+    fn biquad_kind(self) -> BiquadKind {
+        match self {
+            EqualizerBandKind::Peak => BiquadKind::Peak,
+            EqualizerBandKind::LowShelf => BiquadKind::LowShelf,
+            EqualizerBandKind::HighShelf => BiquadKind::HighShelf,
+        }
+    }
+}
+
+/// A single band of the parametric equalizer.
+#[derive(Clone, Reflect, Debug, PartialEq)]
+pub struct EqualizerBand {
+    #[reflect(
+        description = "Shape of the frequency response of the band.",
+        setter = "set_kind"
+    )]
+    kind: EqualizerBandKind,
+
+    #[reflect(
+        description = "Center frequency of the band in Hertz.",
+        setter = "set_frequency_hz",
+        min_value = 20.0,
+        max_value = 20000.0
+    )]
+    frequency_hz: f32,
+
+    #[reflect(
+        description = "Boost (positive values) or cut (negative values) in decibels.",
+        setter = "set_gain_db",
+        min_value = -48.0,
+        max_value = 48.0
+    )]
+    gain_db: f32,
+
+    #[reflect(
+        description = "Band width at the center frequency, the higher the value the narrower the band.",
+        setter = "set_quality",
+        min_value = 0.01
+    )]
+    quality: f32,
+
+    #[reflect(hidden)]
+    left: Biquad,
+    #[reflect(hidden)]
+    right: Biquad,
+}
+
+impl Visit for EqualizerBand {
+    fn visit(&mut self, name: &str, visitor: &mut Visitor) -> VisitResult {
+        let mut region = visitor.enter_region(name)?;
+
+        self.kind.visit("Kind", &mut region)?;
+        self.frequency_hz.visit("FrequencyHz", &mut region)?;
+        self.gain_db.visit("GainDb", &mut region)?;
+        self.quality.visit("Quality", &mut region)?;
+
+        // State of the filters is not serialized, the filters are re-tuned from the parameters.
+        if region.is_reading() {
+            self.update();
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for EqualizerBand {
+    fn default() -> Self {
+        Self::new(EqualizerBandKind::Peak, 1000.0, 0.0, 0.707)
+    }
+}
+
+impl EqualizerBand {
+    /// Creates new equalizer band.
+    pub fn new(kind: EqualizerBandKind, frequency_hz: f32, gain_db: f32, quality: f32) -> Self {
+        let mut band = Self {
+            kind,
+            frequency_hz,
+            gain_db,
+            quality: quality.max(0.01),
+            left: Default::default(),
+            right: Default::default(),
+        };
+        band.update();
+        band
+    }
+
+    /// Sets new shape of the frequency response of the band.
+    pub fn set_kind(&mut self, kind: EqualizerBandKind) {
+        self.kind = kind;
+        self.update();
+    }
+
+    /// Returns current shape of the frequency response of the band.
+    pub fn kind(&self) -> EqualizerBandKind {
+        self.kind
+    }
+
+    /// Sets center frequency of the band in Hertz.
+    pub fn set_frequency_hz(&mut self, frequency_hz: f32) {
+        self.frequency_hz = frequency_hz;
+        self.update();
+    }
+
+    /// Returns center frequency of the band in Hertz.
+    pub fn frequency_hz(&self) -> f32 {
+        self.frequency_hz
+    }
+
+    /// Sets boost (positive values) or cut (negative values) of the band in decibels.
+    pub fn set_gain_db(&mut self, gain_db: f32) {
+        self.gain_db = gain_db;
+        self.update();
+    }
+
+    /// Returns boost or cut of the band in decibels.
+    pub fn gain_db(&self) -> f32 {
+        self.gain_db
+    }
+
+    /// Sets quality of the band. The higher the value, the narrower the band.
+    pub fn set_quality(&mut self, quality: f32) {
+        self.quality = quality.max(0.01);
+        self.update();
+    }
+
+    /// Returns quality of the band.
+    pub fn quality(&self) -> f32 {
+        self.quality
+    }
+
+    fn sync_parameters(&mut self, other: &Self) {
+        if self.kind != other.kind
+            || self.frequency_hz != other.frequency_hz
+            || self.gain_db != other.gain_db
+            || self.quality != other.quality
+        {
+            self.kind = other.kind;
+            self.frequency_hz = other.frequency_hz;
+            self.gain_db = other.gain_db;
+            self.quality = other.quality;
+            self.update();
+        }
+    }
+
+    fn update(&mut self) {
+        let fc = self.frequency_hz.clamp(1.0, SAMPLE_RATE as f32 * 0.49) / SAMPLE_RATE as f32;
+        // Amplitude for peaking and shelving filters, see Audio EQ Cookbook.
+        let gain = 10.0f32.powf(self.gain_db / 40.0);
+        self.left
+            .tune(self.kind.biquad_kind(), fc, gain, self.quality);
+        self.right
+            .tune(self.kind.biquad_kind(), fc, gain, self.quality);
+    }
+}
+
+/// Parametric equalizer is a set of bands, each of which boosts or cuts some range of frequencies.
+/// It could be used to shape the tone of a sound, for example to make voices clearer or to make
+/// music sound "thinner" when played from a radio.
+#[derive(Clone, Reflect, Visit, Debug, PartialEq)]
+pub struct ParametricEqualizerEffect {
+    #[reflect(description = "A set of bands of the equalizer.")]
+    bands: Vec<EqualizerBand>,
+}
+
+impl Default for ParametricEqualizerEffect {
+    fn default() -> Self {
+        Self {
+            bands: vec![
+                EqualizerBand::new(EqualizerBandKind::LowShelf, 200.0, 0.0, 0.707),
+                EqualizerBand::new(EqualizerBandKind::Peak, 1000.0, 0.0, 0.707),
+                EqualizerBand::new(EqualizerBandKind::HighShelf, 5000.0, 0.0, 0.707),
+            ],
+        }
+    }
+}
+
+impl ParametricEqualizerEffect {
+    /// Creates new equalizer with the given set of bands.
+    pub fn new(bands: Vec<EqualizerBand>) -> Self {
+        Self { bands }
+    }
+
+    /// Returns a reference to the bands of the equalizer.
+    pub fn bands(&self) -> &[EqualizerBand] {
+        &self.bands
+    }
+
+    /// Returns a reference to the bands of the equalizer. It could be used to modify parameters of
+    /// the bands at runtime.
+    pub fn bands_mut(&mut self) -> &mut Vec<EqualizerBand> {
+        &mut self.bands
+    }
+
+    pub(crate) fn sync_parameters(&mut self, other: &Self) {
+        self.bands.truncate(other.bands.len());
+        for (i, other_band) in other.bands.iter().enumerate() {
+            match self.bands.get_mut(i) {
+                Some(band) => band.sync_parameters(other_band),
+                None => self.bands.push(other_band.clone()),
+            }
+        }
+    }
+}
+
+impl EffectRenderTrait for ParametricEqualizerEffect {
+    fn render(&mut self, input: &[(f32, f32)], output: &mut [(f32, f32)]) {
+        for ((input_left, input_right), (output_left, output_right)) in input.iter().zip(output) {
+            let mut left = *input_left;
+            let mut right = *input_right;
+            for band in self.bands.iter_mut() {
+                left = band.left.feed(left);
+                right = band.right.feed(right);
+            }
+            *output_left = left;
+            *output_right = right;
+        }
+    }
+}
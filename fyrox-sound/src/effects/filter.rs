@@ -98,6 +98,18 @@ macro_rules! define_filter_effect {
                 self.quality
             }
 
+            pub(crate) fn sync_parameters(&mut self, other: &Self) {
+                if self.cutoff_frequency_hz != other.cutoff_frequency_hz
+                    || self.gain != other.gain
+                    || self.quality != other.quality
+                {
+                    self.cutoff_frequency_hz = other.cutoff_frequency_hz;
+                    self.gain = other.gain;
+                    self.quality = other.quality;
+                    self.update();
+                }
+            }
+
             fn update(&mut self) {
                 self.left.tune(
                     $kind,
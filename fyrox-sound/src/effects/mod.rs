@@ -1,16 +1,24 @@
-//! Contins everything related to audio effects that can be applied to an audio bus.
+//! Contins everything related to audio effects that can be applied to an audio bus or a sound source.
 
-use crate::{
-    effects::filter::{
+use crate::effects::{
+    delay::DelayEffect,
+    dynamics::{CompressorEffect, LimiterEffect},
+    equalizer::ParametricEqualizerEffect,
+    filter::{
         AllPassFilterEffect, BandPassFilterEffect, HighPassFilterEffect, HighShelfFilterEffect,
         LowPassFilterEffect, LowShelfFilterEffect,
     },
-    effects::reverb::Reverb,
+    pitch::PitchShiftEffect,
+    reverb::Reverb,
 };
 use fyrox_core::{reflect::prelude::*, visitor::prelude::*};
 use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
 
+pub mod delay;
+pub mod dynamics;
+pub mod equalizer;
 pub mod filter;
+pub mod pitch;
 pub mod reverb;
 
 /// Attenuation effect.
@@ -48,6 +56,10 @@ impl EffectRenderTrait for Attenuate {
 /// Effects is a digital signal processing (DSP) unit that transforms input signal in a specific way.
 /// For example, [`LowPassFilterEffect`] could be used to muffle audio sources; to create "underwater"
 /// effect.
+///
+/// Effects could be chained, the chain could be attached to an audio bus (see [`crate::bus::AudioBus::add_effect`])
+/// or to a single sound source (see [`crate::source::SoundSource::set_effects`]). Parameters of every effect
+/// could be changed at runtime, which allows you to automate them.
 #[derive(Debug, Clone, PartialEq, Visit, Reflect, AsRefStr, EnumString, EnumVariantNames)]
 pub enum Effect {
     /// See [`Attenuate`] docs for more info.
@@ -66,6 +78,16 @@ pub enum Effect {
     LowShelfFilter(LowShelfFilterEffect),
     /// See [`HighShelfFilterEffect`] docs for more info.
     HighShelfFilter(HighShelfFilterEffect),
+    /// See [`ParametricEqualizerEffect`] docs for more info.
+    ParametricEqualizer(ParametricEqualizerEffect),
+    /// See [`CompressorEffect`] docs for more info.
+    Compressor(CompressorEffect),
+    /// See [`LimiterEffect`] docs for more info.
+    Limiter(LimiterEffect),
+    /// See [`DelayEffect`] docs for more info.
+    Delay(DelayEffect),
+    /// See [`PitchShiftEffect`] docs for more info.
+    PitchShift(PitchShiftEffect),
 }
 
 impl Default for Effect {
@@ -89,6 +111,11 @@ macro_rules! static_dispatch {
             Effect::AllPassFilter(v) => v.$func($($args),*),
             Effect::LowShelfFilter(v) => v.$func($($args),*),
             Effect::HighShelfFilter(v) => v.$func($($args),*),
+            Effect::ParametricEqualizer(v) => v.$func($($args),*),
+            Effect::Compressor(v) => v.$func($($args),*),
+            Effect::Limiter(v) => v.$func($($args),*),
+            Effect::Delay(v) => v.$func($($args),*),
+            Effect::PitchShift(v) => v.$func($($args),*),
         }
     };
 }
@@ -98,3 +125,32 @@ impl EffectRenderTrait for Effect {
         static_dispatch!(self, render, input, output)
     }
 }
+
+impl Effect {
+    /// Copies parameters of the other effect of the same kind, keeping internal state of the effect
+    /// (filter history, delay lines, etc.) intact. This way parameters could be changed smoothly, without
+    /// clicks. Returns `false` if the effects have different kinds.
+    pub(crate) fn sync_parameters(&mut self, other: &Effect) -> bool {
+        match (self, other) {
+            (Effect::Attenuate(v), Effect::Attenuate(other)) => v.clone_from(other),
+            (Effect::Reverb(v), Effect::Reverb(other)) => v.sync_parameters(other),
+            (Effect::LowPassFilter(v), Effect::LowPassFilter(other)) => v.sync_parameters(other),
+            (Effect::HighPassFilter(v), Effect::HighPassFilter(other)) => v.sync_parameters(other),
+            (Effect::BandPassFilter(v), Effect::BandPassFilter(other)) => v.sync_parameters(other),
+            (Effect::AllPassFilter(v), Effect::AllPassFilter(other)) => v.sync_parameters(other),
+            (Effect::LowShelfFilter(v), Effect::LowShelfFilter(other)) => v.sync_parameters(other),
+            (Effect::HighShelfFilter(v), Effect::HighShelfFilter(other)) => {
+                v.sync_parameters(other)
+            }
+            (Effect::ParametricEqualizer(v), Effect::ParametricEqualizer(other)) => {
+                v.sync_parameters(other)
+            }
+            (Effect::Compressor(v), Effect::Compressor(other)) => v.sync_parameters(other),
+            (Effect::Limiter(v), Effect::Limiter(other)) => v.sync_parameters(other),
+            (Effect::Delay(v), Effect::Delay(other)) => v.sync_parameters(other),
+            (Effect::PitchShift(v), Effect::PitchShift(other)) => v.sync_parameters(other),
+            _ => return false,
+        }
+        true
+    }
+}
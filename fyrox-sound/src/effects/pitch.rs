@@ -0,0 +1,114 @@
+//! Pitch shifting effect. See [`PitchShiftEffect`] docs for more info.
+
+use crate::{context::SAMPLE_RATE, dsp::read_interpolated, effects::EffectRenderTrait};
+use fyrox_core::{reflect::prelude::*, visitor::prelude::*};
+
+/// Maximum size of the processing window in seconds, the buffer of the effect is allocated for this
+/// size once, so the window size could be changed at runtime without losing the signal.
+const MAX_WINDOW_SIZE: f32 = 0.2;
+
+/// Pitch shift effect changes the pitch of a signal without changing its duration (unlike
+/// [`crate::source::SoundSource::set_pitch`], which changes playback speed). It could be used to make
+/// voices of monsters deeper or to create "slow motion" effect on a bus.
+///
+/// # Implementation
+///
+/// The effect uses two read taps of a short delay line moving with a speed that differs from the
+/// speed of writing, the taps are crossfaded to hide discontinuities. It is cheap, but could produce
+/// some artifacts on large pitch changes.
+#[derive(Clone, Reflect, Visit, Debug, PartialEq)]
+pub struct PitchShiftEffect {
+    #[reflect(
+        description = "Pitch multiplier, 2.0 - one octave up, 0.5 - one octave down.",
+        setter = "set_pitch",
+        min_value = 0.25,
+        max_value = 4.0
+    )]
+    pitch: f32,
+
+    #[reflect(
+        description = "Size of the processing window in seconds. Larger windows are smoother, but add more echo.",
+        setter = "set_window_size",
+        min_value = 0.01,
+        max_value = 0.2
+    )]
+    window_size: f32,
+
+    #[reflect(hidden)]
+    #[visit(skip)]
+    buffer: Vec<(f32, f32)>,
+    #[reflect(hidden)]
+    #[visit(skip)]
+    write_pos: usize,
+    #[reflect(hidden)]
+    #[visit(skip)]
+    phase: f32,
+}
+
+impl Default for PitchShiftEffect {
+    fn default() -> Self {
+        Self {
+            pitch: 1.0,
+            window_size: 0.05,
+            buffer: Default::default(),
+            write_pos: 0,
+            phase: 0.0,
+        }
+    }
+}
+
+impl PitchShiftEffect {
+    /// Sets pitch multiplier. For example 2.0 - one octave up, 0.5 - one octave down.
+    pub fn set_pitch(&mut self, pitch: f32) {
+        self.pitch = pitch.clamp(0.25, 4.0);
+    }
+
+    /// Returns pitch multiplier.
+    pub fn pitch(&self) -> f32 {
+        self.pitch
+    }
+
+    /// Sets size of the processing window in seconds.
+    pub fn set_window_size(&mut self, window_size: f32) {
+        self.window_size = window_size.clamp(0.01, MAX_WINDOW_SIZE);
+    }
+
+    /// Returns size of the processing window in seconds.
+    pub fn window_size(&self) -> f32 {
+        self.window_size
+    }
+
+    pub(crate) fn sync_parameters(&mut self, other: &Self) {
+        self.pitch = other.pitch;
+        self.window_size = other.window_size;
+    }
+}
+
+impl EffectRenderTrait for PitchShiftEffect {
+    fn render(&mut self, input: &[(f32, f32)], output: &mut [(f32, f32)]) {
+        if self.buffer.is_empty() {
+            self.buffer = vec![(0.0, 0.0); (MAX_WINDOW_SIZE * SAMPLE_RATE as f32) as usize + 2];
+        }
+
+        let window = (self.window_size * SAMPLE_RATE as f32).max(4.0);
+        let phase_step = (1.0 - self.pitch) / window;
+
+        for (input, (output_left, output_right)) in input.iter().zip(output) {
+            self.buffer[self.write_pos] = *input;
+
+            let phase_a = self.phase;
+            let phase_b = (self.phase + 0.5).fract();
+            // Triangular windows, sum of them is always 1.0.
+            let weight_a = 1.0 - (2.0 * phase_a - 1.0).abs();
+            let weight_b = 1.0 - weight_a;
+            let a = read_interpolated(&self.buffer, self.write_pos, phase_a * window);
+            let b = read_interpolated(&self.buffer, self.write_pos, phase_b * window);
+
+            *output_left = a.0 * weight_a + b.0 * weight_b;
+            *output_right = a.1 * weight_a + b.1 * weight_b;
+
+            self.write_pos = (self.write_pos + 1) % self.buffer.len();
+            self.phase = (self.phase + phase_step).rem_euclid(1.0);
+        }
+    }
+}
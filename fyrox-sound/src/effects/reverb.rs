@@ -139,6 +139,8 @@ pub struct Reverb {
     decay_time: f32,
     #[reflect(setter = "set_fc", min_value = 0.0, max_value = 1.0)]
     fc: f32,
+    #[reflect(setter = "set_gain", min_value = 0.0)]
+    gain: f32,
     #[reflect(hidden)]
     left: ChannelReverb,
     #[reflect(hidden)]
@@ -153,6 +155,10 @@ impl Visit for Reverb {
         self.wet.visit("Wet", &mut region)?;
         self.decay_time.visit("DecayTime", &mut region)?;
         self.fc.visit("Fc", &mut region)?;
+        // Optional, for backward compatibility.
+        if self.gain.visit("Gain", &mut region).is_err() {
+            self.gain = 1.0;
+        }
 
         if region.is_reading() {
             self.left = ChannelReverb::new(0, self.fc, Reverb::FEEDBACK, self.decay_time);
@@ -180,7 +186,7 @@ impl Reverb {
     /// too much which will cause signal overflow.
     ///
     /// 2.0 here because left and right signals will be mixed together.
-    const GAIN: f32 = 1.0 / (2.0 * Self::TOTAL_FILTERS_COUNT);
+    const INPUT_GAIN: f32 = 1.0 / (2.0 * Self::TOTAL_FILTERS_COUNT);
 
     /// Creates new instance of reverb effect with cutoff frequency of ~11.2 kHz and
     /// 5 seconds decay time.
@@ -194,6 +200,7 @@ impl Reverb {
             wet: 1.0,
             decay_time: 2.0,
             fc,
+            gain: 1.0,
             left: ChannelReverb::new(0, fc, Reverb::FEEDBACK, decay_time),
             right: ChannelReverb::new(23, fc, Reverb::FEEDBACK, decay_time),
         }
@@ -265,6 +272,29 @@ impl Reverb {
     pub fn fc(&self) -> f32 {
         self.fc
     }
+
+    /// Sets gain of the reverberated signal. It does not affect the dry part of the signal, so it
+    /// could be used to smoothly fade reverberation in and out. Default value is 1.0.
+    pub fn set_gain(&mut self, gain: f32) {
+        self.gain = gain.max(0.0);
+    }
+
+    /// Returns gain of the reverberated signal.
+    pub fn gain(&self) -> f32 {
+        self.gain
+    }
+
+    pub(crate) fn sync_parameters(&mut self, other: &Self) {
+        self.dry = other.dry;
+        self.wet = other.wet;
+        self.gain = other.gain;
+        if self.decay_time != other.decay_time {
+            self.set_decay_time(other.decay_time);
+        }
+        if self.fc != other.fc {
+            self.set_fc(other.fc);
+        }
+    }
 }
 
 impl EffectRenderTrait for Reverb {
@@ -274,10 +304,10 @@ impl EffectRenderTrait for Reverb {
 
         for ((out_left, out_right), &(left, right)) in mix_buf.iter_mut().zip(input.iter()) {
             let mid = (left + right) * 0.5;
-            let input = mid * Self::GAIN;
+            let input = mid * Self::INPUT_GAIN;
 
-            let processed_left = self.left.feed(input);
-            let processed_right = self.right.feed(input);
+            let processed_left = self.left.feed(input) * self.gain;
+            let processed_right = self.right.feed(input) * self.gain;
 
             *out_left = processed_left * wet + processed_right * dry + self.dry * left;
             *out_right = processed_right * wet + processed_left * dry + self.dry * right;
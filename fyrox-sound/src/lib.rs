@@ -72,6 +72,8 @@ pub mod effects;
 pub mod engine;
 pub mod error;
 pub mod listener;
+pub mod mixer;
+pub mod music;
 pub mod renderer;
 pub mod source;
 
@@ -0,0 +1,276 @@
+//! Mixer snapshots allow to change settings of multiple audio buses at once and to blend between the
+//! changes smoothly. See [`Mixer`] docs for more info.
+
+use crate::context::SAMPLE_RATE;
+use fyrox_core::{math::lerpf, reflect::prelude::*, visitor::prelude::*};
+
+/// Settings of an audio bus in a mixer snapshot with the given name. Every value is a modifier, that is
+/// applied on top of the settings of the bus when the snapshot is active. See [`Mixer`] docs for more
+/// info.
+#[derive(Debug, Clone, PartialEq, Visit, Reflect)]
+pub struct BusSnapshot {
+    /// Name of the snapshot.
+    #[reflect(description = "Name of the snapshot.")]
+    pub name: String,
+
+    /// Gain multiplier of the bus. Default is `1.0`.
+    #[reflect(
+        description = "Gain multiplier of the bus.",
+        min_value = 0.0,
+        step = 0.05
+    )]
+    pub gain: f32,
+
+    /// Pitch multiplier of the bus. Default is `1.0`.
+    #[reflect(
+        description = "Pitch multiplier of the bus.",
+        min_value = 0.0,
+        step = 0.05
+    )]
+    pub pitch: f32,
+
+    /// Cutoff frequency (in Hertz) of a low-pass filter applied to the bus. Default is `20000.0`, which
+    /// disables the filter.
+    #[reflect(
+        description = "Cutoff frequency (in Hertz) of a low-pass filter applied to the bus.",
+        min_value = 20.0,
+        max_value = 20000.0
+    )]
+    pub cutoff_frequency_hz: f32,
+}
+
+impl Default for BusSnapshot {
+    fn default() -> Self {
+        Self {
+            name: Default::default(),
+            gain: 1.0,
+            pitch: 1.0,
+            cutoff_frequency_hz: Self::MAX_CUTOFF_FREQUENCY,
+        }
+    }
+}
+
+impl BusSnapshot {
+    /// Cutoff frequency (in Hertz) at which the low-pass filter of the snapshot is disabled.
+    pub const MAX_CUTOFF_FREQUENCY: f32 = 20000.0;
+
+    /// Creates new bus snapshot with the given name, that does not modify the bus.
+    pub fn new<S: AsRef<str>>(name: S) -> Self {
+        Self {
+            name: name.as_ref().to_owned(),
+            ..Default::default()
+        }
+    }
+
+    /// Sets desired gain multiplier.
+    pub fn with_gain(mut self, gain: f32) -> Self {
+        self.gain = gain;
+        self
+    }
+
+    /// Sets desired pitch multiplier.
+    pub fn with_pitch(mut self, pitch: f32) -> Self {
+        self.pitch = pitch;
+        self
+    }
+
+    /// Sets desired cutoff frequency (in Hertz) of the low-pass filter.
+    pub fn with_cutoff_frequency_hz(mut self, cutoff_frequency_hz: f32) -> Self {
+        self.cutoff_frequency_hz = cutoff_frequency_hz;
+        self
+    }
+}
+
+/// A combined result of all active snapshots for a single audio bus.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BusModifier {
+    /// Gain multiplier.
+    pub gain: f32,
+    /// Pitch multiplier.
+    pub pitch: f32,
+    /// Cutoff frequency (in Hertz) of the low-pass filter.
+    pub cutoff_frequency_hz: f32,
+}
+
+impl Default for BusModifier {
+    fn default() -> Self {
+        Self {
+            gain: 1.0,
+            pitch: 1.0,
+            cutoff_frequency_hz: BusModifier::BYPASS_FREQUENCY,
+        }
+    }
+}
+
+impl BusModifier {
+    /// Nyquist frequency, the low-pass filter is disabled at this or higher cutoff frequency.
+    pub const BYPASS_FREQUENCY: f32 = SAMPLE_RATE as f32 * 0.5;
+}
+
+#[derive(Debug, Clone)]
+struct SnapshotState {
+    name: String,
+    weight: f32,
+    target_weight: f32,
+    // Weight change per second.
+    fade_speed: f32,
+}
+
+/// Mixer blends mixer snapshots. A snapshot is a named set of modifiers of audio buses, each audio bus
+/// stores its own settings for every snapshot it takes part in (see [`crate::bus::AudioBus::snapshots`]).
+/// Snapshots could be activated and deactivated at runtime with smooth transitions, multiple snapshots
+/// could be active at the same time: gains and pitches are multiplied and the lowest cutoff frequency
+/// is used.
+///
+/// Typical use cases are music ducking while a character speaks (a snapshot that halves the gain of
+/// a music bus) or muffling everything while a pause menu is open (a snapshot with low cutoff frequency
+/// of the primary bus).
+///
+/// # Example
+///
+/// ```rust
+/// use fyrox_sound::{
+///     bus::{AudioBus, AudioBusGraph},
+///     mixer::BusSnapshot,
+/// };
+///
+/// let mut graph = AudioBusGraph::new();
+/// let primary = graph.primary_bus_handle();
+///
+/// let mut music = AudioBus::new("Music".to_string());
+/// music.add_snapshot(BusSnapshot::new("Dialog").with_gain(0.3));
+/// graph.add_bus(music, primary);
+///
+/// // Duck the music in half a second.
+/// graph.mixer_mut().activate_snapshot("Dialog", 0.5);
+///
+/// // Must be called every frame, it is done automatically by the engine.
+/// graph.update_mixer(1.0 / 60.0);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Mixer {
+    snapshots: Vec<SnapshotState>,
+}
+
+impl Mixer {
+    /// Starts a smooth transition of the weight of the snapshot with the given name to the given value
+    /// (in `[0.0; 1.0]` range). Zero fade time means instant change.
+    pub fn set_snapshot_weight(&mut self, name: &str, weight: f32, fade_time: f32) {
+        let weight = weight.clamp(0.0, 1.0);
+        let fade_speed = if fade_time > 0.0 {
+            1.0 / fade_time
+        } else {
+            f32::INFINITY
+        };
+
+        if let Some(state) = self.snapshots.iter_mut().find(|s| s.name == name) {
+            state.target_weight = weight;
+            state.fade_speed = fade_speed;
+        } else if weight > 0.0 {
+            self.snapshots.push(SnapshotState {
+                name: name.to_owned(),
+                weight: 0.0,
+                target_weight: weight,
+                fade_speed,
+            });
+        }
+    }
+
+    /// Smoothly activates the snapshot with the given name.
+    pub fn activate_snapshot(&mut self, name: &str, fade_time: f32) {
+        self.set_snapshot_weight(name, 1.0, fade_time)
+    }
+
+    /// Smoothly deactivates the snapshot with the given name.
+    pub fn deactivate_snapshot(&mut self, name: &str, fade_time: f32) {
+        self.set_snapshot_weight(name, 0.0, fade_time)
+    }
+
+    /// Instantly deactivates all the snapshots.
+    pub fn reset(&mut self) {
+        self.snapshots.clear();
+    }
+
+    /// Returns current weight of the snapshot with the given name.
+    pub fn snapshot_weight(&self, name: &str) -> f32 {
+        self.snapshots
+            .iter()
+            .find(|s| s.name == name)
+            .map_or(0.0, |s| s.weight)
+    }
+
+    /// Returns an iterator over names and current weights of every snapshot that is active or in
+    /// transition.
+    pub fn active_snapshots(&self) -> impl Iterator<Item = (&str, f32)> {
+        self.snapshots.iter().map(|s| (s.name.as_str(), s.weight))
+    }
+
+    pub(crate) fn update(&mut self, dt: f32) {
+        for state in self.snapshots.iter_mut() {
+            if state.fade_speed.is_infinite() {
+                state.weight = state.target_weight;
+            } else {
+                let max_step = state.fade_speed * dt;
+                state.weight += (state.target_weight - state.weight).clamp(-max_step, max_step);
+            }
+        }
+
+        self.snapshots
+            .retain(|s| s.weight > 0.0 || s.target_weight > 0.0);
+    }
+
+    pub(crate) fn calculate_modifier(&self, bus_snapshots: &[BusSnapshot]) -> BusModifier {
+        let mut modifier = BusModifier::default();
+        for state in self.snapshots.iter() {
+            if let Some(snapshot) = bus_snapshots.iter().find(|s| s.name == state.name) {
+                modifier.gain *= lerpf(1.0, snapshot.gain, state.weight);
+                modifier.pitch *= lerpf(1.0, snapshot.pitch, state.weight);
+                if snapshot.cutoff_frequency_hz >= BusSnapshot::MAX_CUTOFF_FREQUENCY {
+                    continue;
+                }
+                // Interpolate in logarithmic scale, it is much closer to human perception.
+                let open = BusModifier::BYPASS_FREQUENCY;
+                let cutoff = open
+                    * (snapshot.cutoff_frequency_hz.clamp(20.0, open) / open).powf(state.weight);
+                modifier.cutoff_frequency_hz = modifier.cutoff_frequency_hz.min(cutoff);
+            }
+        }
+        modifier
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::mixer::{BusModifier, BusSnapshot, Mixer};
+
+    #[test]
+    fn test_snapshot_blending() {
+        let snapshots = [
+            BusSnapshot::new("Dialog").with_gain(0.5),
+            BusSnapshot::new("Pause")
+                .with_pitch(0.5)
+                .with_cutoff_frequency_hz(1000.0),
+        ];
+
+        let mut mixer = Mixer::default();
+        assert_eq!(mixer.calculate_modifier(&snapshots), BusModifier::default());
+
+        mixer.activate_snapshot("Dialog", 1.0);
+        mixer.update(0.5);
+        assert_eq!(mixer.snapshot_weight("Dialog"), 0.5);
+        assert_eq!(mixer.calculate_modifier(&snapshots).gain, 0.75);
+
+        mixer.activate_snapshot("Pause", 0.0);
+        mixer.update(0.5);
+        let modifier = mixer.calculate_modifier(&snapshots);
+        assert_eq!(modifier.gain, 0.5);
+        assert_eq!(modifier.pitch, 0.5);
+        assert!((modifier.cutoff_frequency_hz - 1000.0).abs() < 0.1);
+
+        mixer.deactivate_snapshot("Dialog", 0.5);
+        mixer.deactivate_snapshot("Pause", 0.0);
+        mixer.update(1.0);
+        assert_eq!(mixer.active_snapshots().count(), 0);
+        assert_eq!(mixer.calculate_modifier(&snapshots), BusModifier::default());
+    }
+}
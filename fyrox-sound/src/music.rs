@@ -0,0 +1,741 @@
+//! Music player. See [`MusicPlayer`] docs for more info.
+
+use crate::{
+    buffer::{streaming::StreamingBuffer, SoundBuffer, SoundBufferResource},
+    bus::AudioBusGraph,
+    context::State,
+    source::{LoopRegion, SoundSource, SoundSourceBuilder, Status},
+};
+use fyrox_core::{
+    futures::{future::ready, FutureExt},
+    log::Log,
+    pool::Handle,
+};
+use fyrox_resource::io::ResourceIoFuture;
+use std::{
+    collections::VecDeque,
+    f32::consts::FRAC_PI_2,
+    fmt::{Debug, Formatter},
+};
+
+/// A music track that could be played by [`MusicPlayer`].
+#[derive(Debug, Clone)]
+pub struct MusicTrack {
+    /// A buffer with the music. It is strongly advised to use streaming buffers for music, because
+    /// music tracks are usually long and decoding them into memory all at once takes lots of memory.
+    pub buffer: SoundBufferResource,
+    /// Gain of the track. Default is `1.0`.
+    pub gain: f32,
+    /// Whether the track should be repeated until some other track is played. Looping tracks never
+    /// end on their own, so the player will not switch to the next track of the queue automatically.
+    /// Default is `false`.
+    pub looping: bool,
+    /// An optional part of the track that will be repeated while the track is looping. It allows
+    /// to have an intro that is played only once. See [`LoopRegion`] docs for more info.
+    pub loop_region: Option<LoopRegion>,
+}
+
+impl MusicTrack {
+    /// Creates new non-looping music track with the given buffer.
+    pub fn new(buffer: SoundBufferResource) -> Self {
+        Self {
+            buffer,
+            gain: 1.0,
+            looping: false,
+            loop_region: None,
+        }
+    }
+
+    /// Sets the desired gain of the track.
+    pub fn with_gain(mut self, gain: f32) -> Self {
+        self.gain = gain;
+        self
+    }
+
+    /// Sets whether the track should be repeated or not.
+    pub fn with_looping(mut self, looping: bool) -> Self {
+        self.looping = looping;
+        self
+    }
+
+    /// Makes the track looping and sets the part of the track that will be repeated.
+    pub fn with_loop_region(mut self, loop_region: LoopRegion) -> Self {
+        self.looping = true;
+        self.loop_region = Some(loop_region);
+        self
+    }
+}
+
+#[derive(Debug)]
+struct Transition {
+    track: Option<MusicTrack>,
+    time: f32,
+}
+
+#[derive(Debug)]
+struct Voice {
+    source: Handle<SoundSource>,
+    track: MusicTrack,
+    // Fade factor of the voice in `[0; 1]` range.
+    fade: f32,
+    // Speed of the fade (fade units per second).
+    fade_speed: f32,
+}
+
+impl Voice {
+    fn fade_speed(time: f32) -> f32 {
+        if time > 0.0 {
+            1.0 / time
+        } else {
+            f32::INFINITY
+        }
+    }
+
+    fn advance(&mut self, target: f32, dt: f32) {
+        if self.fade_speed.is_infinite() {
+            self.fade = target;
+        } else {
+            let max_step = self.fade_speed * dt;
+            self.fade += (target - self.fade).clamp(-max_step, max_step);
+        }
+    }
+
+    fn sync_source(&self, source: &mut SoundSource, gain: f32, paused: bool) {
+        // Equal-power curve keeps the perceived loudness constant during crossfades.
+        source.set_gain(self.track.gain * gain * (self.fade * FRAC_PI_2).sin());
+        match source.status() {
+            Status::Playing if paused => {
+                source.pause();
+            }
+            Status::Paused if !paused => {
+                source.play();
+            }
+            _ => (),
+        }
+    }
+}
+
+// A track that waits until its streaming buffer is reopened. The file could be opened asynchronously
+// (on WebAssembly, for example), so the track starts only when the buffer is ready.
+struct PendingStart {
+    track: MusicTrack,
+    crossfade_time: f32,
+    reopen: ResourceIoFuture<'static, Option<StreamingBuffer>>,
+}
+
+impl Debug for PendingStart {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PendingStart")
+            .field("track", &self.track)
+            .field("crossfade_time", &self.crossfade_time)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Music player plays music tracks one after another and makes smooth transitions (crossfades)
+/// between them. It owns a queue of tracks (a playlist), which is played automatically: when the
+/// current track is about to end, the player starts the next track of the queue and crossfades both
+/// tracks using [`Self::crossfade_time`].
+///
+/// The player does not play anything on its own, instead it creates and controls sound sources of
+/// a sound context. This means that [`Self::update`] must be called every frame. Scenes already have a
+/// player, that is updated automatically, it could be accessed from scripts and game code using
+/// `scene.graph.sound_context.music_player_mut()`.
+///
+/// # Looping
+///
+/// Looping tracks are repeated gaplessly until some other track is played (via [`Self::play`] or
+/// [`Self::next`]). Every track could have its own loop region, that allows to have an intro of the
+/// track played once. See [`MusicTrack::loop_region`] for more info.
+///
+/// # Example
+///
+/// ```no_run
+/// use fyrox_sound::{
+///     buffer::SoundBufferResource,
+///     context::SoundContext,
+///     music::{MusicPlayer, MusicTrack},
+/// };
+///
+/// fn play_music(
+///     player: &mut MusicPlayer,
+///     context: &SoundContext,
+///     exploration: SoundBufferResource,
+///     combat: SoundBufferResource,
+/// ) {
+///     player.play(MusicTrack::new(exploration), 0.0);
+///     player.enqueue(MusicTrack::new(combat).with_looping(true));
+///
+///     // Must be called every frame.
+///     player.update(&mut context.state(), 1.0 / 60.0);
+/// }
+/// ```
+#[derive(Debug)]
+pub struct MusicPlayer {
+    queue: VecDeque<MusicTrack>,
+    current: Option<Voice>,
+    fading: Vec<Voice>,
+    transition: Option<Transition>,
+    pending: Option<PendingStart>,
+    crossfade_time: f32,
+    gain: f32,
+    bus: String,
+    paused: bool,
+    stopped: bool,
+}
+
+impl Default for MusicPlayer {
+    fn default() -> Self {
+        Self {
+            queue: Default::default(),
+            current: None,
+            fading: Default::default(),
+            transition: None,
+            pending: None,
+            crossfade_time: 2.0,
+            gain: 1.0,
+            bus: AudioBusGraph::PRIMARY_BUS.to_string(),
+            paused: false,
+            stopped: false,
+        }
+    }
+}
+
+impl MusicPlayer {
+    /// Creates new music player.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts playing the given track, the current track (if any) will be crossfaded with the new one
+    /// during the given amount of time (in seconds). Zero time means instant switch. The queue stays
+    /// untouched.
+    pub fn play(&mut self, track: MusicTrack, crossfade_time: f32) {
+        self.stopped = false;
+        self.transition = Some(Transition {
+            track: Some(track),
+            time: crossfade_time,
+        });
+    }
+
+    /// Stops playing the current track, it will fade out during the given amount of time (in seconds).
+    /// The queue stays untouched, use [`Self::next`] to resume playback.
+    pub fn stop(&mut self, fade_out_time: f32) {
+        self.stopped = true;
+        self.transition = Some(Transition {
+            track: None,
+            time: fade_out_time,
+        });
+    }
+
+    /// Switches to the next track of the queue using the given crossfade time (in seconds). If the queue
+    /// is empty, the current track fades out.
+    pub fn next(&mut self, crossfade_time: f32) {
+        self.stopped = false;
+        self.transition = Some(Transition {
+            track: self.queue.pop_front(),
+            time: crossfade_time,
+        });
+    }
+
+    /// Adds the given track to the end of the queue. If nothing is playing (and the player was not
+    /// stopped explicitly), the track will be played on the next update.
+    pub fn enqueue(&mut self, track: MusicTrack) {
+        self.queue.push_back(track);
+    }
+
+    /// Returns a reference to the queue of tracks.
+    pub fn queue(&self) -> &VecDeque<MusicTrack> {
+        &self.queue
+    }
+
+    /// Returns a reference to the queue of tracks. It could be used to reorder or remove queued tracks.
+    pub fn queue_mut(&mut self) -> &mut VecDeque<MusicTrack> {
+        &mut self.queue
+    }
+
+    /// Removes every track from the queue. The current track will continue playing.
+    pub fn clear_queue(&mut self) {
+        self.queue.clear();
+    }
+
+    /// Returns currently playing track (if any). Tracks that are fading out are not considered playing.
+    pub fn current_track(&self) -> Option<&MusicTrack> {
+        self.current.as_ref().map(|voice| &voice.track)
+    }
+
+    /// Returns `true` if the player has a track that is playing or fading out.
+    pub fn is_playing(&self) -> bool {
+        self.current.is_some() || !self.fading.is_empty()
+    }
+
+    /// Sets crossfade time (in seconds) that is used for automatic transitions between the tracks of
+    /// the queue. Default is `2.0`.
+    pub fn set_crossfade_time(&mut self, crossfade_time: f32) {
+        self.crossfade_time = crossfade_time.max(0.0);
+    }
+
+    /// Returns crossfade time (in seconds) that is used for automatic transitions between the tracks.
+    pub fn crossfade_time(&self) -> f32 {
+        self.crossfade_time
+    }
+
+    /// Sets the overall gain of the player. It is applied to every track in addition to its own gain.
+    pub fn set_gain(&mut self, gain: f32) {
+        self.gain = gain.max(0.0);
+    }
+
+    /// Returns the overall gain of the player.
+    pub fn gain(&self) -> f32 {
+        self.gain
+    }
+
+    /// Sets the name of an audio bus to which the music will be sent. It is a good practice to have a
+    /// separate bus for music, this way it could be processed separately from other sounds.
+    pub fn set_bus<S: AsRef<str>>(&mut self, bus: S) {
+        self.bus = bus.as_ref().to_string();
+    }
+
+    /// Returns the name of an audio bus to which the music is sent.
+    pub fn bus(&self) -> &str {
+        &self.bus
+    }
+
+    /// Pauses or resumes the playback.
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+    }
+
+    /// Returns `true` if the playback is paused.
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    // Streaming buffer could be used by a single sound source only, but the same track could still be
+    // playing (fading out, for example). In this case the buffer is reopened, so both voices could
+    // play at once. If it is impossible, voices that use the buffer are stopped to release it.
+    fn start(&mut self, state: &mut State, track: MusicTrack, crossfade_time: f32) {
+        let reopen = match *track.buffer.data_ref() {
+            SoundBuffer::Streaming(ref streaming) if streaming.use_count > 0 => {
+                Some(streaming.reopen().unwrap_or_else(|| Box::pin(ready(None))))
+            }
+            _ => None,
+        };
+
+        if let Some(reopen) = reopen {
+            self.pending = Some(PendingStart {
+                track,
+                crossfade_time,
+                reopen,
+            });
+            self.poll_pending(state);
+        } else {
+            self.pending = None;
+            let buffer = track.buffer.clone();
+            self.start_with_buffer(state, track, buffer, crossfade_time);
+        }
+    }
+
+    fn poll_pending(&mut self, state: &mut State) {
+        let Some(reopened) = self
+            .pending
+            .as_mut()
+            .and_then(|pending| pending.reopen.as_mut().now_or_never())
+        else {
+            return;
+        };
+        let Some(PendingStart {
+            track,
+            crossfade_time,
+            ..
+        }) = self.pending.take()
+        else {
+            unreachable!()
+        };
+
+        let buffer = match reopened {
+            Some(reopened) => SoundBufferResource::new_ok(SoundBuffer::Streaming(reopened)),
+            None => {
+                self.release_buffer(state, &track.buffer);
+                track.buffer.clone()
+            }
+        };
+        self.start_with_buffer(state, track, buffer, crossfade_time);
+    }
+
+    fn release_buffer(&mut self, state: &mut State, buffer: &SoundBufferResource) {
+        let uses_buffer = |voice: &Voice, state: &State| {
+            state
+                .sources()
+                .try_borrow(voice.source)
+                .and_then(|source| source.buffer())
+                .is_some_and(|source_buffer| &source_buffer == buffer)
+        };
+        if let Some(current) = self.current.as_ref() {
+            if uses_buffer(current, state) {
+                state.remove_source(current.source);
+            }
+        }
+        self.fading.retain(|voice| {
+            if uses_buffer(voice, state) {
+                state.remove_source(voice.source);
+                false
+            } else {
+                true
+            }
+        });
+    }
+
+    fn start_with_buffer(
+        &mut self,
+        state: &mut State,
+        track: MusicTrack,
+        buffer: SoundBufferResource,
+        crossfade_time: f32,
+    ) {
+        if let Some(mut current) = self.current.take() {
+            current.fade_speed = Voice::fade_speed(crossfade_time);
+            self.fading.push(current);
+        }
+
+        let mut builder = SoundSourceBuilder::new()
+            .with_buffer(buffer)
+            .with_looping(track.looping)
+            .with_spatial_blend_factor(0.0)
+            .with_bus(&self.bus)
+            .with_gain(0.0)
+            .with_status(Status::Playing);
+        if let Some(loop_region) = track.loop_region {
+            builder = builder.with_loop_region(loop_region);
+        }
+
+        match builder.build() {
+            Ok(source) => {
+                self.current = Some(Voice {
+                    source: state.add_source(source),
+                    track,
+                    fade: if crossfade_time > 0.0 { 0.0 } else { 1.0 },
+                    fade_speed: Voice::fade_speed(crossfade_time),
+                });
+            }
+            Err(err) => Log::err(format!("Unable to play music track. Reason: {:?}", err)),
+        }
+    }
+
+    // Checks whether the current track is about to end and the next track of the queue should be
+    // started.
+    fn should_switch_to_next(&self, state: &State) -> bool {
+        if self.stopped || self.pending.is_some() || self.queue.is_empty() {
+            return false;
+        }
+
+        let Some(current) = self.current.as_ref() else {
+            return true;
+        };
+
+        if !state.is_valid_handle(current.source) {
+            return true;
+        }
+
+        let source = state.source(current.source);
+        if source.status() == Status::Stopped {
+            return true;
+        }
+
+        if current.track.looping {
+            return false;
+        }
+
+        let duration = current.track.buffer.data_ref().duration();
+        let remaining = duration.saturating_sub(source.playback_time());
+        remaining.as_secs_f32() <= self.crossfade_time
+    }
+
+    /// Updates the player: starts new tracks, advances crossfades and removes finished tracks. Must be
+    /// called every frame.
+    pub fn update(&mut self, state: &mut State, dt: f32) {
+        self.poll_pending(state);
+
+        if let Some(transition) = self.transition.take() {
+            if let Some(track) = transition.track {
+                self.start(state, track, transition.time);
+            } else {
+                self.pending = None;
+                if let Some(mut current) = self.current.take() {
+                    current.fade_speed = Voice::fade_speed(transition.time);
+                    self.fading.push(current);
+                }
+            }
+        }
+
+        if !self.paused && self.should_switch_to_next(state) {
+            if let Some(track) = self.queue.pop_front() {
+                self.start(state, track, self.crossfade_time);
+            }
+        }
+
+        let dt = if self.paused { 0.0 } else { dt };
+
+        if let Some(current) = self.current.as_mut() {
+            current.advance(1.0, dt);
+            match state.try_get_source_mut(current.source) {
+                Some(source) if source.status() != Status::Stopped => {
+                    current.sync_source(source, self.gain, self.paused);
+                }
+                _ => {
+                    // The track has ended.
+                    if state.is_valid_handle(current.source) {
+                        state.remove_source(current.source);
+                    }
+                    self.current = None;
+                }
+            }
+        }
+
+        self.fading.retain_mut(|voice| {
+            voice.advance(0.0, dt);
+            match state.try_get_source_mut(voice.source) {
+                Some(source) if voice.fade > 0.0 && source.status() != Status::Stopped => {
+                    voice.sync_source(source, self.gain, self.paused);
+                    true
+                }
+                Some(_) => {
+                    state.remove_source(voice.source);
+                    false
+                }
+                None => false,
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        buffer::{
+            streaming::StreamingBuffer, DataSource, RawStreamingDataSource, SoundBuffer,
+            SoundBufferResource, SoundBufferResourceExtension,
+        },
+        context::{SoundContext, SAMPLE_RATE},
+        music::{MusicPlayer, MusicTrack},
+        source::Status,
+    };
+    use fyrox_core::io::FileLoadError;
+    use fyrox_resource::io::{ResourceIo, ResourceIoFuture};
+    use std::{
+        fs::File,
+        future::Future,
+        path::{Path, PathBuf},
+        pin::Pin,
+        sync::Arc,
+        task::{Context, Poll},
+    };
+
+    fn make_track() -> MusicTrack {
+        let buffer = SoundBufferResource::new_generic(DataSource::Raw {
+            sample_rate: SAMPLE_RATE as usize,
+            channel_count: 1,
+            samples: vec![0.0; SAMPLE_RATE as usize],
+        })
+        .unwrap();
+        MusicTrack::new(buffer).with_looping(true)
+    }
+
+    #[test]
+    fn test_crossfade() {
+        let context = SoundContext::new();
+        let mut state = context.state();
+        let mut player = MusicPlayer::new();
+
+        player.play(make_track(), 0.0);
+        player.update(&mut state, 0.1);
+        assert_eq!(state.sources().alive_count(), 1);
+        let first = state.sources().pair_iter().next().unwrap().0;
+        assert!((state.source(first).gain() - 1.0).abs() < f32::EPSILON);
+
+        player.play(make_track(), 1.0);
+        player.update(&mut state, 0.5);
+        assert_eq!(state.sources().alive_count(), 2);
+        assert!(state.source(first).gain() < 1.0);
+
+        player.update(&mut state, 0.5);
+        assert_eq!(state.sources().alive_count(), 1);
+        assert!(!state.is_valid_handle(first));
+        assert!(player.current_track().is_some());
+
+        player.enqueue(make_track());
+        player.stop(0.0);
+        player.update(&mut state, 0.1);
+        assert_eq!(state.sources().alive_count(), 0);
+        assert!(!player.is_playing());
+        assert_eq!(player.queue().len(), 1);
+    }
+
+    #[test]
+    fn test_queue() {
+        let context = SoundContext::new();
+        let mut state = context.state();
+        let mut player = MusicPlayer::new();
+
+        player.enqueue(make_track());
+        player.enqueue(make_track());
+        player.update(&mut state, 0.1);
+        assert_eq!(player.queue().len(), 1);
+        assert!(player.current_track().is_some());
+
+        player.next(0.0);
+        player.update(&mut state, 0.1);
+        assert!(player.queue().is_empty());
+        assert_eq!(state.sources().alive_count(), 1);
+    }
+
+    #[derive(Debug)]
+    struct Silence;
+
+    impl Iterator for Silence {
+        type Item = f32;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            Some(0.0)
+        }
+    }
+
+    impl RawStreamingDataSource for Silence {
+        fn sample_rate(&self) -> usize {
+            SAMPLE_RATE as usize
+        }
+
+        fn channel_count(&self) -> usize {
+            1
+        }
+    }
+
+    // Serves a single file from memory, every load takes one extra poll to imitate asynchronous IO.
+    struct AsyncMemoryIo {
+        path: PathBuf,
+        data: Vec<u8>,
+    }
+
+    struct YieldOnce(bool);
+
+    impl Future for YieldOnce {
+        type Output = ();
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            if self.0 {
+                Poll::Ready(())
+            } else {
+                self.0 = true;
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+        }
+    }
+
+    impl ResourceIo for AsyncMemoryIo {
+        fn load_file<'a>(
+            &'a self,
+            path: &'a Path,
+        ) -> ResourceIoFuture<'a, Result<Vec<u8>, FileLoadError>> {
+            Box::pin(async move {
+                YieldOnce(false).await;
+                if path == self.path {
+                    Ok(self.data.clone())
+                } else {
+                    Err(FileLoadError::Custom("No such file".to_string()))
+                }
+            })
+        }
+
+        fn exists<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, bool> {
+            Box::pin(async move { path == self.path })
+        }
+
+        fn is_file<'a>(&'a self, path: &'a Path) -> ResourceIoFuture<'a, bool> {
+            self.exists(path)
+        }
+
+        fn is_dir<'a>(&'a self, _path: &'a Path) -> ResourceIoFuture<'a, bool> {
+            Box::pin(async move { false })
+        }
+    }
+
+    #[test]
+    fn test_crossfade_same_streaming_track_async_io() {
+        let context = SoundContext::new();
+        let mut state = context.state();
+        let mut player = MusicPlayer::new();
+
+        // The file does not exist on the file system, so it could be reopened only by the same IO.
+        let path = PathBuf::from("bundle/music.wav");
+        let io = Arc::new(AsyncMemoryIo {
+            path: path.clone(),
+            data: std::fs::read("examples/data/sine_440hz.wav").unwrap(),
+        });
+        let source =
+            fyrox_core::futures::executor::block_on(DataSource::from_file(&path, io.as_ref()))
+                .unwrap();
+        let mut buffer = StreamingBuffer::new(source).unwrap();
+        buffer.set_resource_io(io);
+        let buffer = SoundBufferResource::new_ok(SoundBuffer::Streaming(buffer));
+
+        let track = MusicTrack::new(buffer).with_looping(true);
+        player.play(track.clone(), 0.0);
+        player.update(&mut state, 0.1);
+        player.play(track, 1.0);
+
+        // The old voice keeps playing while the file is being reopened.
+        player.update(&mut state, 0.1);
+        assert_eq!(state.sources().alive_count(), 1);
+
+        player.update(&mut state, 0.5);
+        assert_eq!(state.sources().alive_count(), 2);
+        for source in state.sources().iter() {
+            assert_eq!(source.status(), Status::Playing);
+            assert!(source.gain() > 0.0);
+        }
+    }
+
+    #[test]
+    fn test_crossfade_same_streaming_track() {
+        let context = SoundContext::new();
+        let mut state = context.state();
+        let mut player = MusicPlayer::new();
+
+        // File-based streaming buffer is reopened, so the track is crossfaded with itself.
+        let path = PathBuf::from("examples/data/sine_440hz.wav");
+        let buffer = SoundBufferResource::new_streaming(DataSource::File {
+            data: Box::new(File::open(&path).unwrap()),
+            path,
+        })
+        .unwrap();
+        let track = MusicTrack::new(buffer).with_looping(true);
+        player.play(track.clone(), 0.0);
+        player.update(&mut state, 0.1);
+        player.play(track, 1.0);
+        player.update(&mut state, 0.5);
+        assert_eq!(state.sources().alive_count(), 2);
+        for source in state.sources().iter() {
+            assert_eq!(source.status(), Status::Playing);
+            assert!(source.gain() > 0.0);
+        }
+
+        // Buffers that cannot be reopened are released by the old voice instead.
+        let mut player = MusicPlayer::new();
+        state.sources_mut().clear();
+        let buffer =
+            SoundBufferResource::new_streaming(DataSource::RawStreaming(Box::new(Silence)))
+                .unwrap();
+        let track = MusicTrack::new(buffer).with_looping(true);
+        player.play(track.clone(), 0.0);
+        player.update(&mut state, 0.1);
+        player.play(track, 1.0);
+        player.update(&mut state, 0.5);
+        assert_eq!(state.sources().alive_count(), 1);
+        assert!(player.current_track().is_some());
+        assert_eq!(
+            state.sources().iter().next().unwrap().status(),
+            Status::Playing
+        );
+    }
+}
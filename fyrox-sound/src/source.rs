@@ -32,11 +32,14 @@ use crate::{
     buffer::{streaming::StreamingBuffer, SoundBuffer, SoundBufferResource},
     bus::AudioBusGraph,
     context::DistanceModel,
+    dsp::filters::OnePole,
+    effects::{Effect, EffectRenderTrait},
     error::SoundError,
     listener::Listener,
 };
 use fyrox_core::{
     algebra::Vector3,
+    curve::Curve,
     reflect::prelude::*,
     visitor::{Visit, VisitResult, Visitor},
 };
@@ -59,6 +62,31 @@ pub enum Status {
     Paused = 2,
 }
 
+/// A part of a sound buffer that is repeated over and over again while the source is looping. It
+/// allows to have an intro part of a sound (for example an intro of a music track) that is played only
+/// once, while the rest of the sound is looped. Transition from the end of the region to its beginning
+/// is gapless.
+///
+/// # Streaming buffers
+///
+/// Jumps in streaming buffers are done by the decoder, which could be less precise than a jump within
+/// a generic buffer (for example, Ogg/Vorbis decoder could seek only to the closest page). Use generic
+/// buffers if you need sample-accurate loops.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Reflect, Visit)]
+pub struct LoopRegion {
+    /// Beginning of the region.
+    pub start: Duration,
+    /// End of the region. It will be clamped to the duration of the buffer.
+    pub end: Duration,
+}
+
+impl LoopRegion {
+    /// Creates new loop region.
+    pub fn new(start: Duration, end: Duration) -> Self {
+        Self { start, end }
+    }
+}
+
 /// See module info.
 #[derive(Debug, Clone, Reflect, Visit)]
 pub struct SoundSource {
@@ -93,6 +121,11 @@ pub struct SoundSource {
     // However such auto-resampling has poor quality, but it is fast.
     #[reflect(read_only)]
     resampling_multiplier: f64,
+    // Pitch multiplier of the audio bus to which the source is attached (including its parents),
+    // it is set by the context right before rendering.
+    #[reflect(hidden)]
+    #[visit(skip)]
+    pub(crate) bus_pitch: f64,
     status: Status,
     #[visit(optional)]
     pub(crate) bus: String,
@@ -136,6 +169,57 @@ pub struct SoundSource {
     #[reflect(hidden)]
     #[visit(skip)]
     pub(crate) prev_distance_gain: Option<f32>,
+    #[visit(optional)]
+    effects: Vec<Effect>,
+    #[reflect(hidden)]
+    #[visit(skip)]
+    effects_buffer: Vec<(f32, f32)>,
+    #[reflect(hidden)]
+    #[visit(skip)]
+    occlusion: Occlusion,
+    #[visit(optional)]
+    loop_region: Option<LoopRegion>,
+    #[visit(optional)]
+    distance_curve: Option<Curve>,
+}
+
+// Occlusion is a runtime-only state, it is driven by the code that knows about the geometry of the
+// environment (for example scene sound nodes).
+#[derive(Debug, Clone)]
+struct Occlusion {
+    gain: f32,
+    current_gain: f32,
+    fc: f32,
+    // Pole of the filters that corresponds to `fc`, zero pole means no filtering.
+    pole: f32,
+    current_pole: f32,
+    filters: [OnePole; 2],
+}
+
+impl Default for Occlusion {
+    fn default() -> Self {
+        Self {
+            gain: 1.0,
+            current_gain: 1.0,
+            fc: Occlusion::BYPASS_FC,
+            pole: 0.0,
+            current_pole: 0.0,
+            filters: Default::default(),
+        }
+    }
+}
+
+impl Occlusion {
+    // Nyquist frequency, any value above or equal to it disables filtering.
+    const BYPASS_FC: f32 = 0.5;
+
+    fn pole(fc: f32) -> f32 {
+        if fc >= Occlusion::BYPASS_FC {
+            0.0
+        } else {
+            (-2.0 * std::f32::consts::PI * fc).exp()
+        }
+    }
 }
 
 impl Default for SoundSource {
@@ -151,6 +235,7 @@ impl Default for SoundSource {
             spatial_blend: 1.0,
             looping: false,
             resampling_multiplier: 1.0,
+            bus_pitch: 1.0,
             status: Status::Stopped,
             bus: "Master".to_string(),
             play_once: false,
@@ -166,6 +251,11 @@ impl Default for SoundSource {
             prev_right_samples: Default::default(),
             prev_sampling_vector: Vector3::new(0.0, 0.0, 1.0),
             prev_distance_gain: None,
+            effects: Default::default(),
+            effects_buffer: Default::default(),
+            occlusion: Default::default(),
+            loop_region: None,
+            distance_curve: None,
         }
     }
 }
@@ -257,6 +347,18 @@ impl SoundSource {
         self.play_once = play_once;
     }
 
+    /// Sets a part of the buffer that will be repeated while the source is looping. `None` means that
+    /// the entire buffer will be repeated. Has no effect on non-looping sources. See [`LoopRegion`] docs
+    /// for more info.
+    pub fn set_loop_region(&mut self, loop_region: Option<LoopRegion>) {
+        self.loop_region = loop_region;
+    }
+
+    /// Returns current loop region of the source.
+    pub fn loop_region(&self) -> Option<LoopRegion> {
+        self.loop_region
+    }
+
     /// Returns true if this source is marked for single play, false - otherwise.
     pub fn is_play_once(&self) -> bool {
         self.play_once
@@ -396,6 +498,19 @@ impl SoundSource {
         self.max_distance
     }
 
+    /// Sets a custom distance attenuation curve, which maps the distance between the source and the
+    /// listener (in meters) to gain. When set, the curve overrides the distance model of the context,
+    /// radius, max distance and rolloff factor of the source are ignored.
+    pub fn set_distance_curve(&mut self, curve: Option<Curve>) -> &mut Self {
+        self.distance_curve = curve;
+        self
+    }
+
+    /// Returns current custom distance attenuation curve (if any).
+    pub fn distance_curve(&self) -> Option<&Curve> {
+        self.distance_curve.as_ref()
+    }
+
     /// Sets new name of the target audio bus. The name must be valid, otherwise the sound won't play!
     /// Default is [`AudioBusGraph::PRIMARY_BUS`].
     pub fn set_bus<S: AsRef<str>>(&mut self, bus: S) {
@@ -407,6 +522,63 @@ impl SoundSource {
         &self.bus
     }
 
+    /// Sets new chain of effects that will be applied to the samples of the source before they're
+    /// mixed into the audio bus. Effects at the same positions of the old and the new chain of the same
+    /// kind are not replaced; only their parameters are updated, thus it is safe to call this method
+    /// every frame to automate parameters.
+    pub fn set_effects(&mut self, effects: Vec<Effect>) {
+        self.effects.truncate(effects.len());
+        for (i, effect) in effects.into_iter().enumerate() {
+            match self.effects.get_mut(i) {
+                Some(existing) => {
+                    if !existing.sync_parameters(&effect) {
+                        *existing = effect;
+                    }
+                }
+                None => self.effects.push(effect),
+            }
+        }
+    }
+
+    /// Sets occlusion parameters of the source, where `gain` is an additional attenuation of the source
+    /// in `[0..1]` range and `fc` is a normalized cutoff frequency (frequency in Hertz divided by the sample
+    /// rate) of a low-pass filter that muffles the source. Gain
+    /// and cutoff frequency changes are interpolated across a rendered block, so the method could be
+    /// called every frame without clicks. Values of `1.0` and `0.5` respectively mean that the source is not occluded.
+    ///
+    /// The engine does not know anything about geometry of the environment, so this method must be
+    /// called by a user (or by a higher-level code that performs ray casting against the geometry).
+    pub fn set_occlusion(&mut self, gain: f32, fc: f32) {
+        self.occlusion.gain = gain.clamp(0.0, 1.0);
+        let fc = fc.clamp(0.0, Occlusion::BYPASS_FC);
+        if fc != self.occlusion.fc {
+            self.occlusion.fc = fc;
+            self.occlusion.pole = Occlusion::pole(fc);
+        }
+    }
+
+    /// Returns current occlusion gain of the source. See [`Self::set_occlusion`] for more info.
+    pub fn occlusion_gain(&self) -> f32 {
+        self.occlusion.gain
+    }
+
+    /// Returns current normalized cutoff frequency of occlusion filter of the source. See
+    /// [`Self::set_occlusion`] for more info.
+    pub fn occlusion_fc(&self) -> f32 {
+        self.occlusion.fc
+    }
+
+    /// Returns a reference to the chain of effects of the source.
+    pub fn effects(&self) -> &[Effect] {
+        &self.effects
+    }
+
+    /// Returns a reference to the chain of effects of the source. It could be used to add or remove
+    /// effects or to change their parameters at runtime.
+    pub fn effects_mut(&mut self) -> &mut Vec<Effect> {
+        &mut self.effects
+    }
+
     // Distance models were taken from OpenAL Specification because it looks like they're
     // standard in industry and there is no need to reinvent it.
     // https://www.openal.org/documentation/openal-1.1-specification.pdf
@@ -415,6 +587,11 @@ impl SoundSource {
         listener: &Listener,
         distance_model: DistanceModel,
     ) -> f32 {
+        if let Some(curve) = self.distance_curve.as_ref() {
+            let distance = self.position.metric_distance(&listener.position());
+            return curve.value_at(distance).max(0.0);
+        }
+
         let distance = self
             .position
             .metric_distance(&listener.position())
@@ -491,6 +668,48 @@ impl SoundSource {
         }
     }
 
+    // Returns loop region of the source in samples, if the source is looping and the region is valid.
+    fn loop_region_in_samples(&self, buffer: &SoundBuffer) -> Option<(f64, f64)> {
+        if !self.looping {
+            return None;
+        }
+        let region = self.loop_region?;
+        let sample_rate = buffer.sample_rate() as f64;
+        let duration = buffer.channel_duration_in_samples() as f64;
+        // Round the positions to whole samples, otherwise the precision of `Duration` could shift the
+        // region by a sample.
+        let start = (region.start.as_secs_f64() * sample_rate)
+            .round()
+            .min(duration);
+        let end = (region.end.as_secs_f64() * sample_rate)
+            .round()
+            .min(duration);
+        if start < end {
+            Some((start, end))
+        } else {
+            None
+        }
+    }
+
+    // Moves playback position to the given position (in samples). Unlike `set_playback_time`, it works
+    // with already locked buffer and it keeps fractional part of the position, this is important for
+    // gapless loops.
+    fn seek_locked(&mut self, buffer: &mut SoundBuffer, position: f64) {
+        self.playback_pos = position;
+        match buffer {
+            SoundBuffer::Streaming(streaming) => {
+                streaming.time_seek(Duration::from_secs_f64(
+                    position.floor() / streaming.sample_rate() as f64,
+                ));
+                streaming.read_next_block();
+                self.buf_read_pos = position.fract();
+            }
+            SoundBuffer::Generic(_) => {
+                self.buf_read_pos = position;
+            }
+        }
+    }
+
     pub(crate) fn render(&mut self, amount: usize) {
         if self.frame_samples.capacity() < amount {
             self.frame_samples = Vec::with_capacity(amount);
@@ -508,15 +727,72 @@ impl SoundSource {
         }
         // Fill the remaining part of frame_samples.
         self.frame_samples.resize(amount, (0.0, 0.0));
+
+        self.apply_effects();
+        self.apply_occlusion();
+    }
+
+    fn apply_occlusion(&mut self) {
+        let occlusion = &mut self.occlusion;
+        let filter = occlusion.pole > 0.0 || occlusion.current_pole > 0.0;
+        if !filter && occlusion.gain == 1.0 && occlusion.current_gain == 1.0 {
+            return;
+        }
+
+        let len = self.frame_samples.len().max(1) as f32;
+        let gain_step = (occlusion.gain - occlusion.current_gain) / len;
+        let pole_step = (occlusion.pole - occlusion.current_pole) / len;
+        let [left_filter, right_filter] = &mut occlusion.filters;
+        for (left, right) in self.frame_samples.iter_mut() {
+            occlusion.current_gain += gain_step;
+            if filter {
+                occlusion.current_pole += pole_step;
+                left_filter.set_pole(occlusion.current_pole);
+                right_filter.set_pole(occlusion.current_pole);
+                *left = left_filter.feed(*left);
+                *right = right_filter.feed(*right);
+            }
+            *left *= occlusion.current_gain;
+            *right *= occlusion.current_gain;
+        }
+        occlusion.current_gain = occlusion.gain;
+        occlusion.current_pole = occlusion.pole;
+    }
+
+    fn apply_effects(&mut self) {
+        for effect in self.effects.iter_mut() {
+            self.effects_buffer.clear();
+            self.effects_buffer
+                .extend_from_slice(self.frame_samples.as_slice());
+            effect.render(&self.effects_buffer, &mut self.frame_samples);
+        }
     }
 
     fn render_playing(&mut self, buffer: &mut SoundBuffer, amount: usize) {
         let mut count = 0;
         loop {
-            count += self.render_until_block_end(buffer, amount - count);
+            let loop_region = self.loop_region_in_samples(buffer);
+            let mut requested = amount - count;
+            if let Some((start, end)) = loop_region {
+                if self.playback_pos >= end {
+                    self.seek_locked(buffer, start + (self.playback_pos - end));
+                }
+                // Do not render past the end of the loop region, the rest will be rendered from its
+                // beginning.
+                let step = self.playback_step();
+                let remaining = ((end - self.playback_pos) / step).ceil().max(1.0) as usize;
+                requested = requested.min(remaining);
+            }
+
+            let rendered = self.render_until_block_end(buffer, requested);
+            count += rendered;
             if count == amount {
                 break;
             }
+            if loop_region.is_some() && rendered == requested {
+                // The end of the loop region is reached, not the end of the block.
+                continue;
+            }
 
             let channel_count = buffer.channel_count();
             let len = buffer.samples().len();
@@ -538,16 +814,23 @@ impl SoundSource {
                     self.status = Status::Stopped;
                     return;
                 }
+                if let Some((start, _)) = loop_region {
+                    self.seek_locked(buffer, start);
+                }
             } else {
                 self.buf_read_pos -= len as f64 / channel_count as f64;
             }
         }
     }
 
+    fn playback_step(&self) -> f64 {
+        self.pitch * self.bus_pitch * self.resampling_multiplier
+    }
+
     // Renders until the end of the block or until amount samples is written and returns
     // the number of written samples.
     fn render_until_block_end(&mut self, buffer: &mut SoundBuffer, mut amount: usize) -> usize {
-        let step = self.pitch * self.resampling_multiplier;
+        let step = self.playback_step();
         if step == 1.0 {
             if self.buf_read_pos < 0.0 {
                 // This can theoretically happen if we change pitch on the fly.
@@ -719,6 +1002,9 @@ pub struct SoundSourceBuilder {
     rolloff_factor: f32,
     spatial_blend: f32,
     bus: String,
+    effects: Vec<Effect>,
+    loop_region: Option<LoopRegion>,
+    distance_curve: Option<Curve>,
 }
 
 impl Default for SoundSourceBuilder {
@@ -746,6 +1032,9 @@ impl SoundSourceBuilder {
             rolloff_factor: 1.0,
             spatial_blend: 1.0,
             bus: AudioBusGraph::PRIMARY_BUS.to_string(),
+            effects: Default::default(),
+            loop_region: None,
+            distance_curve: None,
         }
     }
 
@@ -845,6 +1134,24 @@ impl SoundSourceBuilder {
         self
     }
 
+    /// Sets desired chain of effects of the sound source. See [`SoundSource::set_effects`] for more info.
+    pub fn with_effects(mut self, effects: Vec<Effect>) -> Self {
+        self.effects = effects;
+        self
+    }
+
+    /// Sets desired loop region of the sound source. See [`SoundSource::set_loop_region`] for more info.
+    pub fn with_loop_region(mut self, loop_region: LoopRegion) -> Self {
+        self.loop_region = Some(loop_region);
+        self
+    }
+
+    /// Sets desired distance attenuation curve. See [`SoundSource::set_distance_curve`] for more info.
+    pub fn with_distance_curve(mut self, curve: Option<Curve>) -> Self {
+        self.distance_curve = curve;
+        self
+    }
+
     /// Creates new instance of generic sound source. May fail if buffer is invalid.
     pub fn build(self) -> Result<SoundSource, SoundError> {
         let mut source = SoundSource {
@@ -865,6 +1172,11 @@ impl SoundSourceBuilder {
             prev_left_samples: Default::default(),
             prev_right_samples: Default::default(),
             bus: self.bus,
+            effects: self.effects,
+            effects_buffer: Default::default(),
+            occlusion: Default::default(),
+            loop_region: self.loop_region,
+            distance_curve: self.distance_curve,
             ..Default::default()
         };
 
@@ -874,3 +1186,74 @@ impl SoundSourceBuilder {
         Ok(source)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        buffer::{DataSource, SoundBufferResource, SoundBufferResourceExtension},
+        context::DistanceModel,
+        context::SAMPLE_RATE,
+        listener::Listener,
+        source::{LoopRegion, SoundSourceBuilder, Status},
+    };
+    use fyrox_core::{
+        algebra::Vector3,
+        curve::{Curve, CurveKey, CurveKeyKind},
+    };
+    use std::time::Duration;
+
+    fn samples_to_duration(samples: usize) -> Duration {
+        Duration::from_secs_f64(samples as f64 / SAMPLE_RATE as f64)
+    }
+
+    #[test]
+    fn test_loop_region() {
+        let buffer = SoundBufferResource::new_generic(DataSource::Raw {
+            sample_rate: SAMPLE_RATE as usize,
+            channel_count: 1,
+            samples: (0..10).map(|i| i as f32).collect(),
+        })
+        .unwrap();
+
+        let mut source = SoundSourceBuilder::new()
+            .with_buffer(buffer)
+            .with_looping(true)
+            .with_loop_region(LoopRegion::new(
+                samples_to_duration(2),
+                samples_to_duration(6),
+            ))
+            .with_status(Status::Playing)
+            .build()
+            .unwrap();
+
+        source.render(12);
+        let rendered = source
+            .frame_samples()
+            .iter()
+            .map(|(left, _)| *left)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            rendered,
+            [0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 2.0, 3.0, 4.0, 5.0, 2.0, 3.0]
+        );
+        assert_eq!(source.status(), Status::Playing);
+    }
+
+    #[test]
+    fn test_distance_curve() {
+        let source = SoundSourceBuilder::new()
+            .with_position(Vector3::new(5.0, 0.0, 0.0))
+            .with_distance_curve(Some(Curve::from(vec![
+                CurveKey::new(0.0, 1.0, CurveKeyKind::Linear),
+                CurveKey::new(10.0, 0.0, CurveKeyKind::Linear),
+            ])))
+            .build()
+            .unwrap();
+
+        let listener = Listener::new();
+        for distance_model in [DistanceModel::None, DistanceModel::InverseDistance] {
+            let gain = source.calculate_distance_gain(&listener, distance_model);
+            assert!((gain - 0.5).abs() < 1.0e-5);
+        }
+    }
+}
@@ -0,0 +1,250 @@
+//! Accordion is a vertical group of [`crate::expander::Expander`] widgets, that could optionally keep only one
+//! of them expanded at a time. See [`Accordion`] docs for more info and usage examples.
+
+#![warn(missing_docs)]
+
+use crate::{
+    core::{pool::Handle, reflect::prelude::*, visitor::prelude::*},
+    define_constructor,
+    expander::ExpanderMessage,
+    message::{MessageDirection, UiMessage},
+    stack_panel::StackPanelBuilder,
+    widget::{Widget, WidgetBuilder, WidgetMessage},
+    BuildContext, Control, NodeHandleMapping, UiNode, UserInterface,
+};
+use std::{
+    any::{Any, TypeId},
+    ops::{Deref, DerefMut},
+};
+
+/// A set of messages, that could be used to alternate the state of an [`Accordion`] widget.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AccordionMessage {
+    /// A message, that is used to specify a new set of expanders of an accordion. Previous expanders will be
+    /// removed.
+    Items(Vec<Handle<UiNode>>),
+    /// A message, that is used to define whether the accordion should keep only one expander expanded at a
+    /// time or not.
+    SingleExpanded(bool),
+}
+
+impl AccordionMessage {
+    define_constructor!(
+        /// Creates [`AccordionMessage::Items`] message.
+        AccordionMessage:Items => fn items(Vec<Handle<UiNode>>), layout: false
+    );
+    define_constructor!(
+        /// Creates [`AccordionMessage::SingleExpanded`] message.
+        AccordionMessage:SingleExpanded => fn single_expanded(bool), layout: false
+    );
+}
+
+/// Accordion is a vertical group of [`crate::expander::Expander`] widgets. When `single_expanded` mode is
+/// enabled, expanding one expander collapses all the others.
+///
+/// ## Examples
+///
+/// ```rust
+/// # use fyrox_ui::{
+/// #     accordion::AccordionBuilder, core::pool::Handle, expander::ExpanderBuilder,
+/// #     text::TextBuilder, widget::WidgetBuilder, BuildContext, UiNode,
+/// # };
+/// #
+/// fn make_section(name: &str, expanded: bool, ctx: &mut BuildContext) -> Handle<UiNode> {
+///     ExpanderBuilder::new(WidgetBuilder::new())
+///         .with_header(TextBuilder::new(WidgetBuilder::new()).with_text(name).build(ctx))
+///         .with_content(
+///             TextBuilder::new(WidgetBuilder::new())
+///                 .with_text("Content")
+///                 .build(ctx),
+///         )
+///         .with_expanded(expanded)
+///         .build(ctx)
+/// }
+///
+/// fn create_accordion(ctx: &mut BuildContext) -> Handle<UiNode> {
+///     let items = vec![
+///         make_section("Transform", true, ctx),
+///         make_section("Physics", false, ctx),
+///         make_section("Scripts", false, ctx),
+///     ];
+///
+///     AccordionBuilder::new(WidgetBuilder::new())
+///         .with_items(items)
+///         .with_single_expanded(true)
+///         .build(ctx)
+/// }
+/// ```
+///
+/// Note, that the accordion does not change the initial state of its expanders, so make sure that only one
+/// of them is expanded when `single_expanded` mode is used.
+#[derive(Clone, Visit, Reflect, Debug)]
+pub struct Accordion {
+    /// Base widget of the accordion.
+    pub widget: Widget,
+    /// Current layout panel of the accordion, that is used to arrange expanders.
+    pub panel: Handle<UiNode>,
+    /// Current expanders of the accordion.
+    pub items: Vec<Handle<UiNode>>,
+    /// A flag, that defines whether the accordion should keep only one expander expanded at a time.
+    pub single_expanded: bool,
+}
+
+crate::define_widget_deref!(Accordion);
+
+impl Control for Accordion {
+    fn query_component(&self, type_id: TypeId) -> Option<&dyn Any> {
+        if type_id == TypeId::of::<Self>() {
+            Some(self)
+        } else {
+            None
+        }
+    }
+
+    fn resolve(&mut self, node_map: &NodeHandleMapping) {
+        node_map.resolve(&mut self.panel);
+        node_map.resolve_slice(&mut self.items);
+    }
+
+    fn handle_routed_message(&mut self, ui: &mut UserInterface, message: &mut UiMessage) {
+        self.widget.handle_routed_message(ui, message);
+
+        if let Some(msg) = message.data::<AccordionMessage>() {
+            if message.destination() == self.handle()
+                && message.direction() == MessageDirection::ToWidget
+            {
+                match msg {
+                    AccordionMessage::Items(items) => {
+                        for &item in self.items.iter() {
+                            ui.send_message(WidgetMessage::remove(
+                                item,
+                                MessageDirection::ToWidget,
+                            ));
+                        }
+                        for &item in items {
+                            ui.send_message(WidgetMessage::link(
+                                item,
+                                MessageDirection::ToWidget,
+                                self.panel,
+                            ));
+                        }
+                        self.items = items.clone();
+                    }
+                    &AccordionMessage::SingleExpanded(single_expanded) => {
+                        self.single_expanded = single_expanded;
+                    }
+                }
+            }
+        } else if let Some(ExpanderMessage::Expand(true)) = message.data() {
+            if self.single_expanded
+                && message.direction() == MessageDirection::FromWidget
+                && self.items.contains(&message.destination())
+            {
+                for &item in self.items.iter() {
+                    if item != message.destination() {
+                        ui.send_message(ExpanderMessage::expand(
+                            item,
+                            MessageDirection::ToWidget,
+                            false,
+                        ));
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Accordion builder creates [`Accordion`] widget instances and adds them to the user interface.
+pub struct AccordionBuilder {
+    widget_builder: WidgetBuilder,
+    items: Vec<Handle<UiNode>>,
+    single_expanded: bool,
+}
+
+impl AccordionBuilder {
+    /// Creates new accordion builder.
+    pub fn new(widget_builder: WidgetBuilder) -> Self {
+        Self {
+            widget_builder,
+            items: Default::default(),
+            single_expanded: false,
+        }
+    }
+
+    /// Sets the desired expanders of the accordion.
+    pub fn with_items(mut self, items: Vec<Handle<UiNode>>) -> Self {
+        self.items = items;
+        self
+    }
+
+    /// Sets whether the accordion should keep only one expander expanded at a time or not.
+    pub fn with_single_expanded(mut self, single_expanded: bool) -> Self {
+        self.single_expanded = single_expanded;
+        self
+    }
+
+    /// Finishes widget building and adds it to the user interface, returning a handle to the new instance.
+    pub fn build(self, ctx: &mut BuildContext) -> Handle<UiNode> {
+        let panel =
+            StackPanelBuilder::new(WidgetBuilder::new().with_children(self.items.iter().cloned()))
+                .build(ctx);
+
+        let accordion = Accordion {
+            widget: self.widget_builder.with_child(panel).build(),
+            panel,
+            items: self.items,
+            single_expanded: self.single_expanded,
+        };
+
+        ctx.add_node(UiNode::new(accordion))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        accordion::AccordionBuilder,
+        core::algebra::Vector2,
+        expander::{Expander, ExpanderBuilder, ExpanderMessage},
+        message::MessageDirection,
+        widget::WidgetBuilder,
+        UiNode, UserInterface,
+    };
+    use fyrox_core::pool::Handle;
+
+    fn is_expanded(ui: &UserInterface, expander: Handle<UiNode>) -> bool {
+        ui.node(expander)
+            .query_component::<Expander>()
+            .unwrap()
+            .is_expanded
+    }
+
+    #[test]
+    fn accordion_single_expanded() {
+        let mut ui = UserInterface::new(Vector2::new(100.0, 100.0));
+
+        let ctx = &mut ui.build_ctx();
+        let items = (0..3)
+            .map(|i| {
+                ExpanderBuilder::new(WidgetBuilder::new())
+                    .with_expanded(i == 0)
+                    .build(ctx)
+            })
+            .collect::<Vec<_>>();
+        AccordionBuilder::new(WidgetBuilder::new())
+            .with_items(items.clone())
+            .with_single_expanded(true)
+            .build(ctx);
+
+        ui.send_message(ExpanderMessage::expand(
+            items[2],
+            MessageDirection::ToWidget,
+            true,
+        ));
+        while ui.poll_message().is_some() {}
+
+        assert!(!is_expanded(&ui, items[0]));
+        assert!(!is_expanded(&ui, items[1]));
+        assert!(is_expanded(&ui, items[2]));
+    }
+}
@@ -0,0 +1,573 @@
+//! Auto-complete text box is a text box with a drop-down list of suggestions. See [`AutoCompleteTextBox`] docs
+//! for more info and usage examples.
+
+#![warn(missing_docs)]
+
+use crate::{
+    core::{algebra::Vector2, pool::Handle, reflect::prelude::*, visitor::prelude::*},
+    define_constructor,
+    list_view::{ListViewBuilder, ListViewMessage},
+    message::{KeyCode, MessageDirection, UiMessage},
+    popup::{Placement, Popup, PopupBuilder, PopupMessage},
+    text::{TextBuilder, TextMessage},
+    text_box::{Position, TextBox, TextBoxBuilder, TextBoxMessage, TextCommitMode},
+    widget::{Widget, WidgetBuilder, WidgetMessage},
+    BuildContext, Control, NodeHandleMapping, Thickness, UiNode, UserInterface, VerticalAlignment,
+};
+use std::{
+    any::{Any, TypeId},
+    cell::Cell,
+    fmt::Debug,
+    ops::{Deref, DerefMut},
+    rc::Rc,
+    sync::mpsc::Sender,
+};
+
+/// A source of suggestions for [`AutoCompleteTextBox`] widget.
+pub trait SuggestionProvider: Debug {
+    /// Returns a list of suggestions for the given text.
+    fn suggestions(&self, text: &str) -> Vec<String>;
+}
+
+/// A simple suggestion provider, that suggests items of a fixed list that contain the input text (case-insensitive).
+/// Items that start with the input text go first.
+#[derive(Debug, Clone, Default)]
+pub struct StaticSuggestions {
+    /// Items, that could be suggested.
+    pub items: Vec<String>,
+}
+
+impl StaticSuggestions {
+    /// Creates a new suggestion provider with the given items.
+    pub fn new<S: AsRef<str>>(items: impl IntoIterator<Item = S>) -> Self {
+        Self {
+            items: items.into_iter().map(|i| i.as_ref().to_owned()).collect(),
+        }
+    }
+}
+
+impl SuggestionProvider for StaticSuggestions {
+    fn suggestions(&self, text: &str) -> Vec<String> {
+        let text = text.to_lowercase();
+        let mut suggestions = self
+            .items
+            .iter()
+            .filter_map(|item| {
+                let lowercase = item.to_lowercase();
+                lowercase
+                    .find(&text)
+                    .filter(|_| lowercase != text)
+                    .map(|position| (position != 0, item))
+            })
+            .collect::<Vec<_>>();
+        // Stable sort keeps the order of the items inside of both groups.
+        suggestions.sort_by_key(|(is_not_prefix, _)| *is_not_prefix);
+        suggestions
+            .into_iter()
+            .map(|(_, item)| item.clone())
+            .collect()
+    }
+}
+
+/// A set of messages, that could be used to alternate the state of an [`AutoCompleteTextBox`] widget or to listen
+/// for its changes.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AutoCompleteMessage {
+    /// A message, that could be used to either set new text (with [`MessageDirection::ToWidget`]) or to get the
+    /// new text (with [`MessageDirection::FromWidget`]) when it is changed by a user.
+    Text(String),
+    /// A message, that is used to accept a suggestion with the given index.
+    AcceptSuggestion(usize),
+    /// A message, that is emitted (with [`MessageDirection::FromWidget`]) when a user has accepted a suggestion.
+    SuggestionAccepted(String),
+}
+
+impl AutoCompleteMessage {
+    define_constructor!(
+        /// Creates [`AutoCompleteMessage::Text`] message.
+        AutoCompleteMessage:Text => fn text(String), layout: false
+    );
+    define_constructor!(
+        /// Creates [`AutoCompleteMessage::AcceptSuggestion`] message.
+        AutoCompleteMessage:AcceptSuggestion => fn accept_suggestion(usize), layout: false
+    );
+    define_constructor!(
+        /// Creates [`AutoCompleteMessage::SuggestionAccepted`] message.
+        AutoCompleteMessage:SuggestionAccepted => fn suggestion_accepted(String), layout: false
+    );
+}
+
+/// Auto-complete text box is a text box, that shows a drop-down list of suggestions while a user types something in it.
+/// Suggestions are provided by a [`SuggestionProvider`].
+///
+/// ## Built-in controls
+///
+/// `Up/Down Arrow` - highlight previous/next suggestion.
+/// `Tab/Enter` - accept highlighted (or the first one, if nothing is highlighted) suggestion.
+/// `Escape` - close the list of suggestions.
+///
+/// A suggestion could also be accepted by clicking on it.
+///
+/// ## Examples
+///
+/// ```rust
+/// # use fyrox_ui::{
+/// #     autocomplete::{AutoCompleteMessage, AutoCompleteTextBoxBuilder, StaticSuggestions},
+/// #     core::pool::Handle,
+/// #     message::{MessageDirection, UiMessage},
+/// #     widget::WidgetBuilder,
+/// #     BuildContext, UiNode,
+/// # };
+/// # use std::rc::Rc;
+/// fn create_parameter_name_field(ctx: &mut BuildContext) -> Handle<UiNode> {
+///     AutoCompleteTextBoxBuilder::new(WidgetBuilder::new().with_height(22.0))
+///         .with_provider(Rc::new(StaticSuggestions::new(["Speed", "IsJumping", "IsRunning"])))
+///         .build(ctx)
+/// }
+///
+/// fn on_ui_message(field: Handle<UiNode>, message: &UiMessage) {
+///     if let Some(AutoCompleteMessage::Text(text)) = message.data() {
+///         if message.destination() == field && message.direction() == MessageDirection::FromWidget {
+///             println!("New parameter name: {}", text);
+///         }
+///     }
+/// }
+/// ```
+#[derive(Clone, Visit, Reflect, Debug)]
+pub struct AutoCompleteTextBox {
+    /// Base widget of the text box.
+    pub widget: Widget,
+    /// A handle of the inner text box, that is used for text input.
+    pub text_box: Handle<UiNode>,
+    /// A handle of the popup, that shows the suggestions.
+    pub popup: Handle<UiNode>,
+    /// A handle of the list view with the suggestions.
+    pub list_view: Handle<UiNode>,
+    /// Current suggestions.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub suggestions: Vec<String>,
+    /// Current suggestion provider.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub provider: Option<Rc<dyn SuggestionProvider>>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    items: Vec<Handle<UiNode>>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    selection: Option<usize>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    accepting: bool,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    keyboard_selections: Cell<usize>,
+}
+
+crate::define_widget_deref!(AutoCompleteTextBox);
+
+impl AutoCompleteTextBox {
+    fn is_open(&self, ui: &UserInterface) -> bool {
+        ui.node(self.popup)
+            .query_component::<Popup>()
+            .is_some_and(|popup| popup.is_open)
+    }
+
+    fn close(&self, ui: &UserInterface) {
+        ui.send_message(PopupMessage::close(self.popup, MessageDirection::ToWidget));
+    }
+
+    fn select(&mut self, selection: usize, ui: &UserInterface) {
+        if self.selection == Some(selection) {
+            return;
+        }
+        self.selection = Some(selection);
+        // List view will report the change back, it must not be treated as a click.
+        self.keyboard_selections
+            .set(self.keyboard_selections.get() + 1);
+        ui.send_message(ListViewMessage::selection(
+            self.list_view,
+            MessageDirection::ToWidget,
+            self.selection,
+        ));
+        if let Some(&item) = self.items.get(selection) {
+            ui.send_message(ListViewMessage::bring_item_into_view(
+                self.list_view,
+                MessageDirection::ToWidget,
+                item,
+            ));
+        }
+    }
+
+    fn refresh_suggestions(&mut self, text: &str, ui: &mut UserInterface) {
+        self.suggestions = match self.provider.as_ref() {
+            Some(provider) if !text.is_empty() => provider.suggestions(text),
+            _ => Default::default(),
+        };
+
+        if self.suggestions.is_empty() {
+            self.close(ui);
+            return;
+        }
+
+        // Reset selection first, otherwise the list view may fix its selection when the items are
+        // changed, and it will be treated as a click.
+        self.selection = None;
+        ui.send_message(ListViewMessage::selection(
+            self.list_view,
+            MessageDirection::ToWidget,
+            None,
+        ));
+
+        let ctx = &mut ui.build_ctx();
+        self.items = self
+            .suggestions
+            .iter()
+            .map(|suggestion| {
+                TextBuilder::new(WidgetBuilder::new().with_margin(Thickness::uniform(2.0)))
+                    .with_text(suggestion)
+                    .build(ctx)
+            })
+            .collect();
+        ui.send_message(ListViewMessage::items(
+            self.list_view,
+            MessageDirection::ToWidget,
+            self.items.clone(),
+        ));
+
+        if !self.is_open(ui) {
+            ui.send_message(WidgetMessage::width(
+                self.popup,
+                MessageDirection::ToWidget,
+                self.actual_local_size().x,
+            ));
+            ui.send_message(PopupMessage::placement(
+                self.popup,
+                MessageDirection::ToWidget,
+                Placement::LeftBottom(self.handle),
+            ));
+            ui.send_message(PopupMessage::open(self.popup, MessageDirection::ToWidget));
+        }
+    }
+
+    fn accept(&mut self, index: usize, ui: &UserInterface) {
+        let Some(suggestion) = self.suggestions.get(index).cloned() else {
+            return;
+        };
+
+        self.close(ui);
+
+        let current_text = ui
+            .node(self.text_box)
+            .query_component::<TextBox>()
+            .map(|text_box| text_box.text())
+            .unwrap_or_default();
+        if current_text != suggestion {
+            // The text box will report the change back, it must not open suggestions again.
+            self.accepting = true;
+            ui.send_message(TextMessage::text(
+                self.text_box,
+                MessageDirection::ToWidget,
+                suggestion.clone(),
+            ));
+        }
+        ui.send_message(TextBoxMessage::caret_position(
+            self.text_box,
+            MessageDirection::ToWidget,
+            Position {
+                line: 0,
+                offset: suggestion.chars().count(),
+            },
+        ));
+        ui.send_message(AutoCompleteMessage::suggestion_accepted(
+            self.handle,
+            MessageDirection::FromWidget,
+            suggestion,
+        ));
+    }
+}
+
+impl Control for AutoCompleteTextBox {
+    fn query_component(&self, type_id: TypeId) -> Option<&dyn Any> {
+        if type_id == TypeId::of::<Self>() {
+            Some(self)
+        } else {
+            None
+        }
+    }
+
+    fn on_remove(&self, sender: &Sender<UiMessage>) {
+        // Popup won't be deleted with the text box, because it is not the child of the text box.
+        // So we have to remove it manually.
+        sender
+            .send(WidgetMessage::remove(
+                self.popup,
+                MessageDirection::ToWidget,
+            ))
+            .unwrap();
+    }
+
+    fn resolve(&mut self, node_map: &NodeHandleMapping) {
+        node_map.resolve(&mut self.text_box);
+        node_map.resolve(&mut self.popup);
+        node_map.resolve(&mut self.list_view);
+    }
+
+    fn handle_routed_message(&mut self, ui: &mut UserInterface, message: &mut UiMessage) {
+        self.widget.handle_routed_message(ui, message);
+
+        if let Some(msg) = message.data::<AutoCompleteMessage>() {
+            if message.destination() == self.handle
+                && message.direction() == MessageDirection::ToWidget
+            {
+                match msg {
+                    AutoCompleteMessage::Text(text) => {
+                        ui.send_message(TextMessage::text(
+                            self.text_box,
+                            MessageDirection::ToWidget,
+                            text.clone(),
+                        ));
+                    }
+                    &AutoCompleteMessage::AcceptSuggestion(index) => {
+                        self.accept(index, ui);
+                    }
+                    AutoCompleteMessage::SuggestionAccepted(_) => {
+                        // Emitted by the widget itself.
+                    }
+                }
+            }
+        } else if let Some(TextMessage::Text(text)) = message.data() {
+            if message.destination() == self.text_box
+                && message.direction() == MessageDirection::FromWidget
+            {
+                if self.accepting {
+                    self.accepting = false;
+                } else if ui.keyboard_focus_node == self.text_box {
+                    // Show suggestions only when the text is typed by a user.
+                    self.refresh_suggestions(text, ui);
+                }
+                ui.send_message(AutoCompleteMessage::text(
+                    self.handle,
+                    MessageDirection::FromWidget,
+                    text.clone(),
+                ));
+            }
+        } else if let Some(&WidgetMessage::KeyDown(key)) = message.data() {
+            // Text box marks all keys as handled, so the check is done by the destination only.
+            if message.destination() == self.text_box
+                && self.is_open(ui)
+                && !self.suggestions.is_empty()
+            {
+                let count = self.suggestions.len();
+                match key {
+                    KeyCode::ArrowDown => {
+                        let selection = self.selection.map_or(0, |s| (s + 1) % count);
+                        self.select(selection, ui);
+                        message.set_handled(true);
+                    }
+                    KeyCode::ArrowUp => {
+                        let selection = self
+                            .selection
+                            .map_or(count - 1, |s| (s + count - 1) % count);
+                        self.select(selection, ui);
+                        message.set_handled(true);
+                    }
+                    KeyCode::Tab | KeyCode::Enter | KeyCode::NumpadEnter => {
+                        self.accept(self.selection.unwrap_or(0), ui);
+                        message.set_handled(true);
+                    }
+                    KeyCode::Escape => {
+                        self.close(ui);
+                        message.set_handled(true);
+                    }
+                    _ => (),
+                }
+            }
+        } else if let Some(WidgetMessage::Unfocus) = message.data() {
+            if message.destination() == self.text_box && self.is_open(ui) {
+                self.close(ui);
+            }
+        }
+    }
+
+    fn preview_message(&self, ui: &UserInterface, message: &mut UiMessage) {
+        if let Some(&ListViewMessage::SelectionChanged(Some(index))) = message.data() {
+            if message.destination() == self.list_view
+                && message.direction() == MessageDirection::FromWidget
+            {
+                let keyboard_selections = self.keyboard_selections.get();
+                if keyboard_selections > 0 {
+                    self.keyboard_selections.set(keyboard_selections - 1);
+                    return;
+                }
+                // Any other selection change means that a user has clicked on a suggestion.
+                ui.send_message(AutoCompleteMessage::accept_suggestion(
+                    self.handle,
+                    MessageDirection::ToWidget,
+                    index,
+                ));
+            }
+        }
+    }
+}
+
+/// Auto-complete text box builder creates [`AutoCompleteTextBox`] widget instances and adds them to the user
+/// interface.
+pub struct AutoCompleteTextBoxBuilder {
+    widget_builder: WidgetBuilder,
+    text: String,
+    provider: Option<Rc<dyn SuggestionProvider>>,
+}
+
+impl AutoCompleteTextBoxBuilder {
+    /// Creates new builder instance.
+    pub fn new(widget_builder: WidgetBuilder) -> Self {
+        Self {
+            widget_builder,
+            text: Default::default(),
+            provider: None,
+        }
+    }
+
+    /// Sets the desired initial text.
+    pub fn with_text<S: AsRef<str>>(mut self, text: S) -> Self {
+        self.text = text.as_ref().to_owned();
+        self
+    }
+
+    /// Sets the desired suggestion provider.
+    pub fn with_provider(mut self, provider: Rc<dyn SuggestionProvider>) -> Self {
+        self.provider = Some(provider);
+        self
+    }
+
+    /// Finishes widget building and adds it to the user interface, returning a handle to the new instance.
+    pub fn build(self, ctx: &mut BuildContext) -> Handle<UiNode> {
+        let text_box = TextBoxBuilder::new(WidgetBuilder::new())
+            .with_text_commit_mode(TextCommitMode::Immediate)
+            .with_vertical_text_alignment(VerticalAlignment::Center)
+            .with_text(self.text)
+            .build(ctx);
+
+        let list_view = ListViewBuilder::new(
+            WidgetBuilder::new().with_max_size(Vector2::new(f32::INFINITY, 200.0)),
+        )
+        .build(ctx);
+
+        let popup = PopupBuilder::new(WidgetBuilder::new())
+            .with_content(list_view)
+            .build(ctx);
+
+        let auto_complete = AutoCompleteTextBox {
+            widget: self
+                .widget_builder
+                .with_preview_messages(true)
+                .with_child(text_box)
+                .build(),
+            text_box,
+            popup,
+            list_view,
+            suggestions: Default::default(),
+            provider: self.provider,
+            items: Default::default(),
+            selection: None,
+            accepting: false,
+            keyboard_selections: Default::default(),
+        };
+
+        ctx.add_node(UiNode::new(auto_complete))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        autocomplete::{
+            AutoCompleteMessage, AutoCompleteTextBox, AutoCompleteTextBoxBuilder,
+            StaticSuggestions, SuggestionProvider,
+        },
+        core::algebra::Vector2,
+        message::{KeyCode, MessageDirection},
+        popup::Popup,
+        text_box::TextBox,
+        widget::{WidgetBuilder, WidgetMessage},
+        UiNode, UserInterface,
+    };
+    use fyrox_core::pool::Handle;
+    use std::rc::Rc;
+
+    #[test]
+    fn static_suggestions() {
+        let provider = StaticSuggestions::new(["IsRunning", "Speed", "RunSpeed", "Run"]);
+        assert_eq!(provider.suggestions("run"), ["RunSpeed", "IsRunning"]);
+    }
+
+    fn key_down(ui: &mut UserInterface, text_box: Handle<UiNode>, key: KeyCode) {
+        ui.send_message(WidgetMessage::key_down(
+            text_box,
+            MessageDirection::FromWidget,
+            key,
+        ));
+    }
+
+    #[test]
+    fn accept_suggestion() {
+        let mut ui = UserInterface::new(Vector2::new(100.0, 100.0));
+
+        let auto_complete = AutoCompleteTextBoxBuilder::new(WidgetBuilder::new())
+            .with_provider(Rc::new(StaticSuggestions::new(["Speed", "Spin", "Jump"])))
+            .build(&mut ui.build_ctx());
+        let (text_box, popup) = {
+            let widget = ui
+                .node(auto_complete)
+                .query_component::<AutoCompleteTextBox>()
+                .unwrap();
+            (widget.text_box, widget.popup)
+        };
+        let is_open =
+            |ui: &UserInterface| ui.node(popup).query_component::<Popup>().unwrap().is_open;
+
+        ui.send_message(WidgetMessage::focus(text_box, MessageDirection::ToWidget));
+        for symbol in ['s', 'p'] {
+            ui.send_message(WidgetMessage::text(
+                text_box,
+                MessageDirection::ToWidget,
+                symbol.to_string(),
+            ));
+        }
+        while ui.poll_message().is_some() {}
+        assert!(is_open(&ui));
+        assert_eq!(
+            ui.node(auto_complete)
+                .query_component::<AutoCompleteTextBox>()
+                .unwrap()
+                .suggestions,
+            ["Speed", "Spin"]
+        );
+
+        key_down(&mut ui, text_box, KeyCode::ArrowDown);
+        key_down(&mut ui, text_box, KeyCode::ArrowDown);
+        let mut accepted = None;
+        while let Some(message) = ui.poll_message() {
+            if let Some(AutoCompleteMessage::SuggestionAccepted(suggestion)) = message.data() {
+                accepted = Some(suggestion.clone());
+            }
+        }
+        // Keyboard navigation does not accept anything.
+        assert_eq!(accepted, None);
+
+        key_down(&mut ui, text_box, KeyCode::Tab);
+        while let Some(message) = ui.poll_message() {
+            if let Some(AutoCompleteMessage::SuggestionAccepted(suggestion)) = message.data() {
+                accepted = Some(suggestion.clone());
+            }
+        }
+        assert_eq!(accepted.as_deref(), Some("Spin"));
+        assert!(!is_open(&ui));
+
+        let text_box_ref = ui.node(text_box).query_component::<TextBox>().unwrap();
+        assert_eq!(text_box_ref.text(), "Spin");
+        assert_eq!(text_box_ref.caret_position, text_box_ref.end_position());
+    }
+}
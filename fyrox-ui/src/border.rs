@@ -6,13 +6,14 @@
 use crate::{
     core::{
         algebra::Vector2, math::Rect, pool::Handle, reflect::prelude::*, scope_profile,
-        visitor::prelude::*,
+        sstorage::ImmutableString, visitor::prelude::*,
     },
     define_constructor,
     draw::{CommandTexture, Draw, DrawingContext},
     message::UiMessage,
+    style::Style,
     widget::{Widget, WidgetBuilder},
-    BuildContext, Control, MessageDirection, Thickness, UiNode, UserInterface, BRUSH_PRIMARY,
+    BuildContext, Control, MessageDirection, Thickness, UiNode, UserInterface,
 };
 use std::{
     any::{Any, TypeId},
@@ -87,6 +88,10 @@ pub struct Border {
     pub widget: Widget,
     /// Stroke thickness for each side of the border.
     pub stroke_thickness: Thickness,
+    /// Name of a style property, that is used as the stroke thickness of the border. See [`Style`] docs for
+    /// more info.
+    #[visit(optional)]
+    pub stroke_thickness_style: Option<ImmutableString>,
 }
 
 crate::define_widget_deref!(Border);
@@ -114,6 +119,18 @@ impl Control for Border {
         }
     }
 
+    fn apply_style(&mut self, style: &Style) {
+        self.widget.apply_style(style);
+
+        if let Some(thickness) = self
+            .stroke_thickness_style
+            .as_ref()
+            .and_then(|name| style.thickness(name))
+        {
+            self.stroke_thickness = thickness;
+        }
+    }
+
     fn measure_override(&self, ui: &UserInterface, available_size: Vector2<f32>) -> Vector2<f32> {
         scope_profile!();
 
@@ -184,6 +201,8 @@ impl Control for Border {
             && message.direction() == MessageDirection::ToWidget
         {
             if let Some(BorderMessage::StrokeThickness(thickness)) = message.data() {
+                // Explicitly set thickness is no longer bound to the style.
+                self.stroke_thickness_style = None;
                 if *thickness != self.stroke_thickness {
                     self.stroke_thickness = *thickness;
                     ui.send_message(message.reverse());
@@ -200,6 +219,8 @@ pub struct BorderBuilder {
     pub widget_builder: WidgetBuilder,
     /// Stroke thickness for each side of the border. Default is 1px wide border for each side.
     pub stroke_thickness: Thickness,
+    /// Name of a style property, that is used as the stroke thickness of the border.
+    pub stroke_thickness_style: Option<ImmutableString>,
 }
 
 impl BorderBuilder {
@@ -208,6 +229,7 @@ impl BorderBuilder {
         Self {
             widget_builder,
             stroke_thickness: Thickness::uniform(1.0),
+            stroke_thickness_style: None,
         }
     }
 
@@ -217,14 +239,24 @@ impl BorderBuilder {
         self
     }
 
+    /// Binds the stroke thickness of the border to a style property with the given name. The thickness will
+    /// be taken from the current style of the user interface, and it will be updated when the style changes.
+    pub fn with_stroke_thickness_style(mut self, name: &str) -> Self {
+        self.stroke_thickness_style = Some(ImmutableString::new(name));
+        self
+    }
+
     /// Creates a [`Border`] widget, but does not add it to the user interface. Also see [`Self::build`] docs.
     pub fn build_border(mut self) -> Border {
-        if self.widget_builder.foreground.is_none() {
-            self.widget_builder.foreground = Some(BRUSH_PRIMARY);
+        if !self.widget_builder.has_foreground() {
+            self.widget_builder = self
+                .widget_builder
+                .with_foreground_style(Style::BRUSH_PRIMARY);
         }
         Border {
             widget: self.widget_builder.build(),
             stroke_thickness: self.stroke_thickness,
+            stroke_thickness_style: self.stroke_thickness_style,
         }
     }
 
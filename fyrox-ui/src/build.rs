@@ -2,8 +2,8 @@
 //! more info.
 
 use crate::{
-    core::pool::Handle, message::UiMessage, ttf::SharedFont, RestrictionEntry, UiNode,
-    UserInterface,
+    core::pool::Handle, message::UiMessage, style::Style, ttf::SharedFont, RestrictionEntry,
+    UiNode, UserInterface,
 };
 use std::{
     ops::{Index, IndexMut},
@@ -90,9 +90,19 @@ impl<'a> From<&'a mut UserInterface> for BuildContext<'a> {
 }
 
 impl<'a> BuildContext<'a> {
-    /// Returns default font instance used by the UI.
+    /// Returns default font instance used by the UI. The font is taken from the current style (see
+    /// [`Style::FONT_DEFAULT`]), if it is specified there.
     pub fn default_font(&self) -> SharedFont {
-        self.ui.default_font.clone()
+        self.ui
+            .style
+            .font(Style::FONT_DEFAULT)
+            .unwrap_or_else(|| self.ui.default_font.clone())
+    }
+
+    /// Returns current style of the user interface. It could be used to take style properties for widgets,
+    /// that do not support style bindings.
+    pub fn style(&self) -> &Style {
+        &self.ui.style
     }
 
     /// Returns current message sender of the UI, that is used for message passing mechanism. You can
@@ -9,11 +9,12 @@ use crate::{
     decorator::DecoratorBuilder,
     define_constructor,
     message::{MessageDirection, UiMessage},
+    style::Style,
     text::TextBuilder,
     ttf::SharedFont,
     widget::{Widget, WidgetBuilder, WidgetMessage},
     BuildContext, Control, HorizontalAlignment, NodeHandleMapping, Thickness, UiNode,
-    UserInterface, VerticalAlignment, BRUSH_DARKER, BRUSH_LIGHT, BRUSH_LIGHTER, BRUSH_LIGHTEST,
+    UserInterface, VerticalAlignment,
 };
 use fyrox_core::uuid::{uuid, Uuid};
 use std::{
@@ -181,6 +182,8 @@ pub enum ButtonContent {
         text: String,
         /// Optional font of the button. If [`None`], the default font will be used.
         font: Option<SharedFont>,
+        /// If `true`, the text is a localization key. See [`crate::localization::Localization`] for more info.
+        localized: bool,
     },
     /// Arbitrary widget handle. It could be any widget handle, for example a handle of [`crate::image::Image`]
     /// widget.
@@ -193,6 +196,7 @@ impl ButtonContent {
         Self::Text {
             text: s.as_ref().to_owned(),
             font: None,
+            localized: false,
         }
     }
 
@@ -201,6 +205,16 @@ impl ButtonContent {
         Self::Text {
             text: s.as_ref().to_owned(),
             font: Some(font),
+            localized: false,
+        }
+    }
+
+    /// Creates [`ButtonContent::Text`] with default font, that takes its text from the given localization key.
+    pub fn localized_text<S: AsRef<str>>(key: S) -> Self {
+        Self::Text {
+            text: key.as_ref().to_owned(),
+            font: None,
+            localized: true,
         }
     }
 
@@ -211,12 +225,21 @@ impl ButtonContent {
 
     fn build(&self, ctx: &mut BuildContext) -> Handle<UiNode> {
         match self {
-            Self::Text { text, font } => TextBuilder::new(WidgetBuilder::new())
-                .with_text(text)
-                .with_horizontal_text_alignment(HorizontalAlignment::Center)
-                .with_vertical_text_alignment(VerticalAlignment::Center)
-                .with_font(font.clone().unwrap_or_else(|| ctx.default_font()))
-                .build(ctx),
+            Self::Text {
+                text,
+                font,
+                localized,
+            } => {
+                let builder = TextBuilder::new(WidgetBuilder::new())
+                    .with_horizontal_text_alignment(HorizontalAlignment::Center)
+                    .with_vertical_text_alignment(VerticalAlignment::Center)
+                    .with_font(font.clone().unwrap_or_else(|| ctx.default_font()));
+                if *localized {
+                    builder.with_localization_key(text).build(ctx)
+                } else {
+                    builder.with_text(text).build(ctx)
+                }
+            }
             Self::Node(node) => *node,
         }
     }
@@ -251,6 +274,13 @@ impl ButtonBuilder {
         self
     }
 
+    /// Sets the content of the button to be [`ButtonContent::Text`], that takes its text from the given localization
+    /// key.
+    pub fn with_localized_text(mut self, key: &str) -> Self {
+        self.content = Some(ButtonContent::localized_text(key));
+        self
+    }
+
     /// Sets the content of the button to be [`ButtonContent::Node`] (arbitrary widget handle).
     pub fn with_content(mut self, node: Handle<UiNode>) -> Self {
         self.content = Some(ButtonContent::Node(node));
@@ -273,14 +303,14 @@ impl ButtonBuilder {
             DecoratorBuilder::new(
                 BorderBuilder::new(
                     WidgetBuilder::new()
-                        .with_foreground(BRUSH_DARKER)
+                        .with_foreground_style(Style::BRUSH_DARKER)
                         .with_child(content),
                 )
                 .with_stroke_thickness(Thickness::uniform(1.0)),
             )
-            .with_normal_brush(BRUSH_LIGHT)
-            .with_hover_brush(BRUSH_LIGHTER)
-            .with_pressed_brush(BRUSH_LIGHTEST)
+            .with_normal_brush_style(Style::BRUSH_LIGHT)
+            .with_hover_brush_style(Style::BRUSH_LIGHTER)
+            .with_pressed_brush_style(Style::BRUSH_LIGHTEST)
             .build(ctx)
         });
 
@@ -0,0 +1,496 @@
+//! Chart widget is used to plot one or more series of values, for example frame times or memory usage.
+//! See [`Chart`] docs for more info and usage examples.
+
+#![warn(missing_docs)]
+
+use crate::{
+    brush::Brush,
+    core::{algebra::Vector2, math::Rect, pool::Handle, reflect::prelude::*, visitor::prelude::*},
+    define_constructor,
+    draw::{CommandTexture, Draw, DrawingContext},
+    formatted_text::{FormattedText, FormattedTextBuilder},
+    message::{MessageDirection, UiMessage},
+    style::Style,
+    widget::{Widget, WidgetBuilder},
+    BuildContext, Control, UiNode, UserInterface, BRUSH_DARKER, BRUSH_TEXT,
+};
+use std::{
+    any::{Any, TypeId},
+    cell::RefCell,
+    ops::{Deref, DerefMut, Range},
+};
+
+/// Width of the area at the left side of a chart, that is used to show labels of the vertical axis.
+const LABELS_WIDTH: f32 = 48.0;
+/// Amount of horizontal grid lines of a chart (not counting the borders).
+const GRID_LINES: usize = 3;
+
+/// Defines how a series of a [`Chart`] is drawn.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default, Visit, Reflect)]
+pub enum ChartSeriesKind {
+    /// Points of the series are connected by lines.
+    #[default]
+    Line,
+    /// The area between the series and the zero level is filled.
+    Area,
+    /// Every point of the series is drawn as a bar, that starts at the zero level.
+    Bar,
+}
+
+/// A named set of points of a [`Chart`].
+#[derive(Clone, Debug, PartialEq, Default, Visit, Reflect)]
+pub struct ChartSeries {
+    /// Name of the series, it is shown in the legend of the chart.
+    pub name: String,
+    /// Defines how the series is drawn.
+    pub kind: ChartSeriesKind,
+    /// A brush, that is used to draw the series.
+    pub brush: Brush,
+    /// Points of the series, sorted by their horizontal coordinate.
+    pub points: Vec<Vector2<f32>>,
+}
+
+impl ChartSeries {
+    /// Creates a new empty series with the given name, kind and brush.
+    pub fn new<S: AsRef<str>>(name: S, kind: ChartSeriesKind, brush: Brush) -> Self {
+        Self {
+            name: name.as_ref().to_owned(),
+            kind,
+            brush,
+            points: Default::default(),
+        }
+    }
+
+    /// Sets the initial points of the series.
+    pub fn with_points(mut self, points: Vec<Vector2<f32>>) -> Self {
+        self.points = points;
+        self
+    }
+}
+
+/// A set of messages, that could be used to alternate the state of a [`Chart`] widget.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChartMessage {
+    /// A message, that is used to specify a new set of series of a chart.
+    Series(Vec<ChartSeries>),
+    /// A message, that is used to add a new point to the end of a series with the given index. The point
+    /// must have the horizontal coordinate greater or equal than the last point of the series.
+    PushPoint {
+        /// Index of the series.
+        series: usize,
+        /// A new point of the series.
+        point: Vector2<f32>,
+    },
+    /// A message, that is used to set the width of the rolling window (along the horizontal axis) of a chart.
+    /// `None` means that every point will be shown.
+    TimeWindow(Option<f32>),
+    /// A message, that is used to remove every point of every series of a chart.
+    Clear,
+}
+
+impl ChartMessage {
+    define_constructor!(
+        /// Creates [`ChartMessage::Series`] message.
+        ChartMessage:Series => fn series(Vec<ChartSeries>), layout: false
+    );
+    define_constructor!(
+        /// Creates [`ChartMessage::PushPoint`] message.
+        ChartMessage:PushPoint => fn push_point(series: usize, point: Vector2<f32>), layout: false
+    );
+    define_constructor!(
+        /// Creates [`ChartMessage::TimeWindow`] message.
+        ChartMessage:TimeWindow => fn time_window(Option<f32>), layout: false
+    );
+    define_constructor!(
+        /// Creates [`ChartMessage::Clear`] message.
+        ChartMessage:Clear => fn clear(), layout: false
+    );
+}
+
+/// Chart is a lightweight widget that plots one or more series of points as lines, filled areas or bars.
+/// Both axes are scaled automatically to fit the visible points. Optional rolling window allows you to show
+/// only the most recent points, which is useful for runtime statistics (frame time, memory usage, etc.).
+///
+/// ## Examples
+///
+/// The following example creates a chart that shows frame times for the last 5 seconds:
+///
+/// ```rust
+/// # use fyrox_ui::{
+/// #     brush::Brush,
+/// #     chart::{ChartBuilder, ChartMessage, ChartSeries, ChartSeriesKind},
+/// #     core::{algebra::Vector2, color::Color, pool::Handle},
+/// #     message::MessageDirection,
+/// #     widget::WidgetBuilder,
+/// #     BuildContext, UiNode, UserInterface,
+/// # };
+/// fn create_chart(ctx: &mut BuildContext) -> Handle<UiNode> {
+///     ChartBuilder::new(WidgetBuilder::new().with_height(100.0))
+///         .with_series(vec![ChartSeries::new(
+///             "Frame Time (ms)",
+///             ChartSeriesKind::Area,
+///             Brush::Solid(Color::opaque(0, 162, 232)),
+///         )])
+///         .with_time_window(Some(5.0))
+///         .build(ctx)
+/// }
+///
+/// fn on_frame(chart: Handle<UiNode>, time: f32, frame_time: f32, ui: &UserInterface) {
+///     ui.send_message(ChartMessage::push_point(
+///         chart,
+///         MessageDirection::ToWidget,
+///         0,
+///         Vector2::new(time, frame_time * 1000.0),
+///     ));
+/// }
+/// ```
+#[derive(Clone, Visit, Reflect, Debug)]
+pub struct Chart {
+    /// Base widget of the chart.
+    pub widget: Widget,
+    /// Current series of the chart.
+    pub series: Vec<ChartSeries>,
+    /// Width of the rolling window (along the horizontal axis) of the chart. `None` means that every point
+    /// is shown.
+    pub time_window: Option<f32>,
+    /// A brush, that is used to draw the grid of the chart.
+    pub grid_brush: Brush,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    text: RefCell<FormattedText>,
+}
+
+crate::define_widget_deref!(Chart);
+
+impl Chart {
+    /// Returns a range of horizontal coordinates of the visible points.
+    pub fn x_range(&self) -> Range<f32> {
+        let (min, max) = self
+            .series
+            .iter()
+            .flat_map(|s| s.points.iter())
+            .fold((f32::MAX, f32::MIN), |(min, max), p| {
+                (min.min(p.x), max.max(p.x))
+            });
+        if min > max {
+            return 0.0..1.0;
+        }
+        let min = match self.time_window {
+            Some(window) => max - window,
+            None => min,
+        };
+        if max - min <= f32::EPSILON {
+            min..min + 1.0
+        } else {
+            min..max
+        }
+    }
+
+    /// Returns a range of vertical coordinates of the visible points. The range always includes zero
+    /// level if there is at least one area or bar series.
+    pub fn y_range(&self) -> Range<f32> {
+        let x_range = self.x_range();
+        let includes_zero = self.series.iter().any(|s| s.kind != ChartSeriesKind::Line);
+        let (mut min, mut max) = self
+            .series
+            .iter()
+            .flat_map(|s| s.points.iter())
+            .filter(|p| p.x >= x_range.start)
+            .fold((f32::MAX, f32::MIN), |(min, max), p| {
+                (min.min(p.y), max.max(p.y))
+            });
+        if min > max {
+            return 0.0..1.0;
+        }
+        if includes_zero {
+            min = min.min(0.0);
+            max = max.max(0.0);
+        }
+        if max - min <= f32::EPSILON {
+            min..min + 1.0
+        } else {
+            min..max
+        }
+    }
+
+    fn remove_old_points(&mut self) {
+        if let Some(window) = self.time_window {
+            let newest = self
+                .series
+                .iter()
+                .filter_map(|s| s.points.last())
+                .fold(f32::MIN, |newest, p| newest.max(p.x));
+            for series in self.series.iter_mut() {
+                // Keep one point outside of the window, so lines and areas reach the left border.
+                let first_visible = series.points.partition_point(|p| p.x < newest - window);
+                series.points.drain(..first_visible.saturating_sub(1));
+            }
+        }
+    }
+}
+
+impl Control for Chart {
+    fn query_component(&self, type_id: TypeId) -> Option<&dyn Any> {
+        if type_id == TypeId::of::<Self>() {
+            Some(self)
+        } else {
+            None
+        }
+    }
+
+    fn draw(&self, drawing_context: &mut DrawingContext) {
+        let bounds = self.widget.bounding_rect();
+        drawing_context.push_rect_filled(&bounds, None);
+        drawing_context.commit(
+            self.clip_bounds(),
+            self.widget.background(),
+            CommandTexture::None,
+            None,
+        );
+
+        let plot = Rect::new(
+            bounds.x() + LABELS_WIDTH,
+            bounds.y(),
+            (bounds.w() - LABELS_WIDTH).max(0.0),
+            bounds.h(),
+        );
+        let x_range = self.x_range();
+        let y_range = self.y_range();
+        let to_local = |p: Vector2<f32>| {
+            Vector2::new(
+                plot.x() + (p.x - x_range.start) / (x_range.end - x_range.start) * plot.w(),
+                plot.y() + plot.h()
+                    - (p.y - y_range.start) / (y_range.end - y_range.start) * plot.h(),
+            )
+        };
+
+        // Grid.
+        for i in 0..=GRID_LINES + 1 {
+            let y = plot.y() + plot.h() * i as f32 / (GRID_LINES + 1) as f32;
+            drawing_context.push_line(
+                Vector2::new(plot.x(), y),
+                Vector2::new(plot.x() + plot.w(), y),
+                1.0,
+            );
+        }
+        drawing_context.push_rect(&plot, 1.0);
+        drawing_context.commit(
+            self.clip_bounds(),
+            self.grid_brush.clone(),
+            CommandTexture::None,
+            None,
+        );
+
+        // Series are clipped by the plot area, because one point before the rolling window is kept.
+        let screen_bounds = self.screen_bounds();
+        let series_clip_bounds = Rect::new(
+            screen_bounds.x() + LABELS_WIDTH,
+            screen_bounds.y(),
+            (screen_bounds.w() - LABELS_WIDTH).max(0.0),
+            screen_bounds.h(),
+        )
+        .clip_by(self.clip_bounds());
+        let zero = y_range.start.max(0.0).min(y_range.end);
+        for series in self.series.iter() {
+            match series.kind {
+                ChartSeriesKind::Line => {
+                    for pair in series.points.windows(2) {
+                        drawing_context.push_line(to_local(pair[0]), to_local(pair[1]), 1.0);
+                    }
+                }
+                ChartSeriesKind::Area => {
+                    for pair in series.points.windows(2) {
+                        let (a, b) = (to_local(pair[0]), to_local(pair[1]));
+                        let a_base = to_local(Vector2::new(pair[0].x, zero));
+                        let b_base = to_local(Vector2::new(pair[1].x, zero));
+                        drawing_context.push_triangle_filled([a, b, b_base]);
+                        drawing_context.push_triangle_filled([a, b_base, a_base]);
+                    }
+                }
+                ChartSeriesKind::Bar => {
+                    let visible = series
+                        .points
+                        .iter()
+                        .filter(|p| p.x >= x_range.start)
+                        .count()
+                        .max(1);
+                    let width = (plot.w() / visible as f32 * 0.8).max(1.0);
+                    for &point in series.points.iter().filter(|p| p.x >= x_range.start) {
+                        let top = to_local(point);
+                        let base = to_local(Vector2::new(point.x, zero));
+                        let rect = Rect::new(
+                            top.x - width * 0.5,
+                            top.y.min(base.y),
+                            width,
+                            (top.y - base.y).abs(),
+                        );
+                        drawing_context.push_rect_filled(&rect, None);
+                    }
+                }
+            }
+            drawing_context.commit(
+                series_clip_bounds,
+                series.brush.clone(),
+                CommandTexture::None,
+                None,
+            );
+        }
+
+        // Labels of the vertical axis and the legend.
+        let mut text = self.text.borrow_mut();
+        for (value, y) in [
+            (y_range.end, plot.y()),
+            (y_range.start, plot.y() + plot.h() - 14.0),
+        ] {
+            text.set_brush(BRUSH_TEXT).set_text(format!("{:.2}", value));
+            text.build();
+            drawing_context.draw_text(self.clip_bounds(), Vector2::new(bounds.x() + 2.0, y), &text);
+        }
+        for (i, series) in self.series.iter().enumerate() {
+            text.set_brush(series.brush.clone());
+            match series.points.last() {
+                Some(last) => text.set_text(format!("{}: {:.2}", series.name, last.y)),
+                None => text.set_text(&series.name),
+            };
+            text.build();
+            drawing_context.draw_text(
+                self.clip_bounds(),
+                Vector2::new(plot.x() + 4.0, plot.y() + 2.0 + i as f32 * 14.0),
+                &text,
+            );
+        }
+    }
+
+    fn handle_routed_message(&mut self, ui: &mut UserInterface, message: &mut UiMessage) {
+        self.widget.handle_routed_message(ui, message);
+
+        if let Some(msg) = message.data::<ChartMessage>() {
+            if message.destination() == self.handle()
+                && message.direction() == MessageDirection::ToWidget
+            {
+                match msg {
+                    ChartMessage::Series(series) => {
+                        self.series = series.clone();
+                        self.remove_old_points();
+                    }
+                    &ChartMessage::PushPoint { series, point } => {
+                        if let Some(series) = self.series.get_mut(series) {
+                            series.points.push(point);
+                            self.remove_old_points();
+                        }
+                    }
+                    &ChartMessage::TimeWindow(time_window) => {
+                        self.time_window = time_window;
+                        self.remove_old_points();
+                    }
+                    ChartMessage::Clear => {
+                        for series in self.series.iter_mut() {
+                            series.points.clear();
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Chart builder creates [`Chart`] widget instances and adds them to the user interface.
+pub struct ChartBuilder {
+    widget_builder: WidgetBuilder,
+    series: Vec<ChartSeries>,
+    time_window: Option<f32>,
+    grid_brush: Option<Brush>,
+}
+
+impl ChartBuilder {
+    /// Creates new chart builder.
+    pub fn new(widget_builder: WidgetBuilder) -> Self {
+        Self {
+            widget_builder,
+            series: Default::default(),
+            time_window: None,
+            grid_brush: None,
+        }
+    }
+
+    /// Sets the desired series of the chart.
+    pub fn with_series(mut self, series: Vec<ChartSeries>) -> Self {
+        self.series = series;
+        self
+    }
+
+    /// Sets the desired width of the rolling window (along the horizontal axis) of the chart.
+    pub fn with_time_window(mut self, time_window: Option<f32>) -> Self {
+        self.time_window = time_window;
+        self
+    }
+
+    /// Sets the desired brush of the grid of the chart.
+    pub fn with_grid_brush(mut self, brush: Brush) -> Self {
+        self.grid_brush = Some(brush);
+        self
+    }
+
+    /// Finishes widget building and adds it to the user interface, returning a handle to the new instance.
+    pub fn build(mut self, ctx: &mut BuildContext) -> Handle<UiNode> {
+        if !self.widget_builder.has_background() {
+            self.widget_builder = self
+                .widget_builder
+                .with_background_style(Style::BRUSH_DARKEST);
+        }
+
+        let mut chart = Chart {
+            widget: self.widget_builder.build(),
+            series: self.series,
+            time_window: self.time_window,
+            grid_brush: self.grid_brush.unwrap_or(BRUSH_DARKER),
+            text: RefCell::new(FormattedTextBuilder::new(ctx.default_font()).build()),
+        };
+        chart.remove_old_points();
+
+        ctx.add_node(UiNode::new(chart))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        brush::Brush,
+        chart::{Chart, ChartBuilder, ChartMessage, ChartSeries, ChartSeriesKind},
+        core::algebra::Vector2,
+        message::MessageDirection,
+        widget::WidgetBuilder,
+        UserInterface,
+    };
+
+    #[test]
+    fn chart_rolling_window() {
+        let mut ui = UserInterface::new(Vector2::new(100.0, 100.0));
+
+        let chart = ChartBuilder::new(WidgetBuilder::new())
+            .with_series(vec![ChartSeries::new(
+                "Test",
+                ChartSeriesKind::Bar,
+                Brush::default(),
+            )])
+            .with_time_window(Some(2.0))
+            .build(&mut ui.build_ctx());
+
+        for (x, y) in [(0.0, 5.0), (1.0, 3.0), (2.0, 2.0), (3.0, 4.0), (4.0, 1.0)] {
+            ui.send_message(ChartMessage::push_point(
+                chart,
+                MessageDirection::ToWidget,
+                0,
+                Vector2::new(x, y),
+            ));
+        }
+        while ui.poll_message().is_some() {}
+
+        let chart_ref = ui.node(chart).query_component::<Chart>().unwrap();
+        // One point before the window is kept.
+        assert_eq!(chart_ref.series[0].points.len(), 4);
+        assert_eq!(chart_ref.x_range(), 2.0..4.0);
+        // Bars always include zero level.
+        assert_eq!(chart_ref.y_range(), 0.0..4.0);
+    }
+}
@@ -12,11 +12,11 @@ use crate::{
     define_constructor,
     grid::{Column, GridBuilder, Row},
     message::{MessageDirection, UiMessage},
+    style::Style,
     vector_image::{Primitive, VectorImageBuilder},
     widget::{Widget, WidgetBuilder, WidgetMessage},
     BuildContext, Control, HorizontalAlignment, MouseButton, NodeHandleMapping, Thickness, UiNode,
-    UserInterface, VerticalAlignment, BRUSH_BRIGHT, BRUSH_BRIGHT_BLUE, BRUSH_DARKEST, BRUSH_LIGHT,
-    BRUSH_TEXT,
+    UserInterface, VerticalAlignment,
 };
 use std::{
     any::{Any, TypeId},
@@ -318,14 +318,14 @@ impl CheckBoxBuilder {
         let check_mark = self.check_mark.unwrap_or_else(|| {
             BorderBuilder::new(
                 WidgetBuilder::new()
-                    .with_background(BRUSH_BRIGHT_BLUE)
+                    .with_background_style(Style::BRUSH_BRIGHT_BLUE)
                     .with_child(
                         VectorImageBuilder::new(
                             WidgetBuilder::new()
                                 .with_margin(Thickness::uniform(3.0))
                                 .with_vertical_alignment(VerticalAlignment::Center)
                                 .with_horizontal_alignment(HorizontalAlignment::Center)
-                                .with_foreground(BRUSH_TEXT),
+                                .with_foreground_style(Style::BRUSH_TEXT),
                         )
                         .with_primitives({
                             let size = 8.0;
@@ -369,7 +369,7 @@ impl CheckBoxBuilder {
             BorderBuilder::new(
                 WidgetBuilder::new()
                     .with_margin(Thickness::uniform(4.0))
-                    .with_background(BRUSH_BRIGHT)
+                    .with_background_style(Style::BRUSH_BRIGHT)
                     .with_foreground(Brush::Solid(Color::TRANSPARENT)),
             )
             .build(ctx)
@@ -384,8 +384,8 @@ impl CheckBoxBuilder {
             BorderBuilder::new(
                 WidgetBuilder::new()
                     .with_vertical_alignment(VerticalAlignment::Center)
-                    .with_background(BRUSH_DARKEST)
-                    .with_foreground(BRUSH_LIGHT),
+                    .with_background_style(Style::BRUSH_DARKEST)
+                    .with_foreground_style(Style::BRUSH_LIGHT),
             )
             .with_stroke_thickness(Thickness::uniform(1.0))
             .build(ctx)
@@ -0,0 +1,92 @@
+//! Clipboard abstraction of the user interface. See [`crate::UserInterface::set_clipboard`] docs for more info.
+
+#![warn(missing_docs)]
+
+use copypasta::ClipboardProvider;
+use std::error::Error;
+
+/// In-memory clipboard, that is used when the system clipboard is unavailable (for example, when there's no
+/// display server). The contents of this clipboard is visible only to the user interface that owns it.
+#[derive(Default, Debug, Clone)]
+pub struct LocalClipboard {
+    contents: String,
+}
+
+impl ClipboardProvider for LocalClipboard {
+    fn get_contents(&mut self) -> Result<String, Box<dyn Error + Send + Sync + 'static>> {
+        Ok(self.contents.clone())
+    }
+
+    fn set_contents(
+        &mut self,
+        contents: String,
+    ) -> Result<(), Box<dyn Error + Send + Sync + 'static>> {
+        self.contents = contents;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        clipboard::LocalClipboard,
+        core::{algebra::Vector2, pool::Handle},
+        message::{KeyCode, KeyboardModifiers, MessageDirection, OsEvent},
+        text_box::{TextBox, TextBoxBuilder},
+        widget::{WidgetBuilder, WidgetMessage},
+        UiNode, UserInterface,
+    };
+
+    fn text(ui: &UserInterface, text_box: Handle<UiNode>) -> String {
+        ui.node(text_box)
+            .query_component::<TextBox>()
+            .unwrap()
+            .text()
+    }
+
+    fn shortcut(ui: &mut UserInterface, text_box: Handle<UiNode>, key: KeyCode) {
+        ui.send_message(WidgetMessage::key_down(
+            text_box,
+            MessageDirection::FromWidget,
+            key,
+        ));
+        while ui.poll_message().is_some() {}
+    }
+
+    #[test]
+    fn cut_and_paste() {
+        let mut ui = UserInterface::new(Vector2::new(100.0, 100.0));
+        ui.set_clipboard(Box::<LocalClipboard>::default());
+
+        let ctx = &mut ui.build_ctx();
+        let source = TextBoxBuilder::new(WidgetBuilder::new())
+            .with_text("Foobar")
+            .build(ctx);
+        let destination = TextBoxBuilder::new(WidgetBuilder::new()).build(ctx);
+        // Make sure the text was laid out.
+        ui.update(Vector2::new(100.0, 100.0), 0.0);
+
+        ui.process_os_event(&OsEvent::KeyboardModifiers(KeyboardModifiers {
+            control: true,
+            ..Default::default()
+        }));
+
+        ui.send_message(WidgetMessage::focus(source, MessageDirection::ToWidget));
+        while ui.poll_message().is_some() {}
+        shortcut(&mut ui, source, KeyCode::KeyA);
+        shortcut(&mut ui, source, KeyCode::KeyX);
+        assert_eq!(
+            ui.clipboard_provider_mut().get_contents().unwrap(),
+            "Foobar"
+        );
+        assert_eq!(text(&ui, source), "");
+
+        ui.send_message(WidgetMessage::focus(
+            destination,
+            MessageDirection::ToWidget,
+        ));
+        while ui.poll_message().is_some() {}
+        shortcut(&mut ui, destination, KeyCode::KeyV);
+        assert_eq!(text(&ui, destination), "Foobar");
+    }
+}
@@ -14,10 +14,13 @@ use crate::{
     grid::{Column, GridBuilder, Row},
     menu::{MenuItemBuilder, MenuItemContent, MenuItemMessage},
     message::{CursorIcon, MessageDirection, MouseButton, UiMessage},
+    numeric::{NumericUpDownBuilder, NumericUpDownMessage},
     popup::{Placement, PopupBuilder, PopupMessage},
     stack_panel::StackPanelBuilder,
+    text::TextBuilder,
     widget::{Widget, WidgetBuilder, WidgetMessage},
-    BuildContext, Control, RcUiNodeHandle, UiNode, UserInterface,
+    BuildContext, Control, Orientation, RcUiNodeHandle, Thickness, UiNode, UserInterface,
+    VerticalAlignment,
 };
 use std::{
     any::{Any, TypeId},
@@ -156,6 +159,8 @@ pub struct ColorGradientEditor {
     widget: Widget,
     gradient_field: Handle<UiNode>,
     selector_field: Handle<UiNode>,
+    #[visit(optional)]
+    location_field: Handle<UiNode>,
     points_canvas: Handle<UiNode>,
     #[visit(skip)] // TODO
     #[reflect(hidden)] // TODO
@@ -260,23 +265,26 @@ impl Control for ColorGradientEditor {
                 && message.direction() == MessageDirection::FromWidget
                 && message.flags != SYNC_FLAG
             {
-                let mut gradient = ColorGradient::new();
-
-                for (handle, pt) in ui
-                    .node(self.points_canvas)
-                    .children()
-                    .iter()
-                    .map(|c| (*c, ui.node(*c).query_component::<ColorPoint>().unwrap()))
-                {
-                    gradient.add_point(GradientPoint::new(
-                        pt.location,
-                        if handle == self.context_menu_target.get() {
-                            *color
-                        } else {
-                            pt.color()
-                        },
-                    ));
-                }
+                let gradient = self.fetch_modified_gradient(ui, |_, point_color| {
+                    *point_color = *color;
+                });
+
+                ui.send_message(ColorGradientEditorMessage::value(
+                    self.handle,
+                    MessageDirection::FromWidget,
+                    gradient,
+                ));
+            }
+        } else if let Some(&NumericUpDownMessage::Value(location)) =
+            message.data::<NumericUpDownMessage<f32>>()
+        {
+            if message.destination() == self.location_field
+                && message.direction() == MessageDirection::FromWidget
+                && message.flags != SYNC_FLAG
+            {
+                let gradient = self.fetch_modified_gradient(ui, |point_location, _| {
+                    *point_location = location.clamp(0.0, 1.0);
+                });
 
                 ui.send_message(ColorGradientEditorMessage::value(
                     self.handle,
@@ -304,7 +312,17 @@ impl Control for ColorGradientEditor {
 
                         msg.flags = SYNC_FLAG;
 
-                        ui.send_message(msg)
+                        ui.send_message(msg);
+
+                        let mut msg = NumericUpDownMessage::value(
+                            self.location_field,
+                            MessageDirection::ToWidget,
+                            point.location,
+                        );
+
+                        msg.flags = SYNC_FLAG;
+
+                        ui.send_message(msg);
                     }
                 }
             }
@@ -328,6 +346,34 @@ impl ColorGradientEditor {
 
         gradient
     }
+
+    /// Collects the gradient from the points, modifying the point under the context menu.
+    fn fetch_modified_gradient(
+        &self,
+        ui: &UserInterface,
+        modify: impl FnOnce(&mut f32, &mut Color),
+    ) -> ColorGradient {
+        let mut gradient = ColorGradient::new();
+        let mut modify = Some(modify);
+
+        for (handle, pt) in ui
+            .node(self.points_canvas)
+            .children()
+            .iter()
+            .map(|c| (*c, ui.node(*c).query_component::<ColorPoint>().unwrap()))
+        {
+            let mut location = pt.location;
+            let mut color = pt.color();
+            if handle == self.context_menu_target.get() {
+                if let Some(modify) = modify.take() {
+                    modify(&mut location, &mut color);
+                }
+            }
+            gradient.add_point(GradientPoint::new(location, color));
+        }
+
+        gradient
+    }
 }
 
 pub struct ColorGradientEditorBuilder {
@@ -386,6 +432,7 @@ impl ColorGradientEditorBuilder {
         let context_menu = RcUiNodeHandle::new(context_menu, ctx.sender());
 
         let selector_field;
+        let location_field;
         let remove_point;
         let point_context_menu = PopupBuilder::new(WidgetBuilder::new().with_width(200.0))
             .with_content(
@@ -402,7 +449,35 @@ impl ColorGradientEditorBuilder {
                                 ColorFieldBuilder::new(WidgetBuilder::new().with_height(18.0))
                                     .build(ctx);
                             selector_field
-                        }),
+                        })
+                        .with_child(
+                            StackPanelBuilder::new(
+                                WidgetBuilder::new()
+                                    .with_margin(Thickness::uniform(1.0))
+                                    .with_child(
+                                        TextBuilder::new(
+                                            WidgetBuilder::new()
+                                                .with_margin(Thickness::right(4.0))
+                                                .with_vertical_alignment(VerticalAlignment::Center),
+                                        )
+                                        .with_text("Location")
+                                        .build(ctx),
+                                    )
+                                    .with_child({
+                                        location_field = NumericUpDownBuilder::<f32>::new(
+                                            WidgetBuilder::new().with_width(100.0),
+                                        )
+                                        .with_min_value(0.0)
+                                        .with_max_value(1.0)
+                                        .with_step(0.01)
+                                        .with_precision(3)
+                                        .build(ctx);
+                                        location_field
+                                    }),
+                            )
+                            .with_orientation(Orientation::Horizontal)
+                            .build(ctx),
+                        ),
                 )
                 .build(ctx),
             )
@@ -451,6 +526,7 @@ impl ColorGradientEditorBuilder {
             points_canvas,
             gradient_field,
             selector_field,
+            location_field,
             context_menu,
             point_context_menu,
             add_point,
@@ -659,3 +735,116 @@ impl ColorPointsCanvasBuilder {
         }))
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        color::gradient::{
+            ColorGradientEditor, ColorGradientEditorBuilder, ColorGradientEditorMessage, SYNC_FLAG,
+        },
+        core::{
+            algebra::Vector2,
+            color::Color,
+            color_gradient::{ColorGradient, GradientPoint},
+            pool::Handle,
+        },
+        message::{MessageDirection, UiMessage},
+        numeric::{NumericUpDown, NumericUpDownMessage},
+        popup::{Placement, PopupMessage},
+        widget::WidgetBuilder,
+        UiNode, UserInterface,
+    };
+
+    fn editor(ui: &UserInterface, handle: Handle<UiNode>) -> &ColorGradientEditor {
+        ui.node(handle)
+            .query_component::<ColorGradientEditor>()
+            .unwrap()
+    }
+
+    fn send(
+        ui: &mut UserInterface,
+        handle: Handle<UiNode>,
+        message: UiMessage,
+    ) -> Option<ColorGradient> {
+        ui.send_message(message);
+        let mut gradient = None;
+        while let Some(message) = ui.poll_message() {
+            if message.destination() == handle
+                && message.direction() == MessageDirection::FromWidget
+            {
+                if let Some(ColorGradientEditorMessage::Value(value)) = message.data() {
+                    gradient = Some(value.clone());
+                }
+            }
+        }
+        gradient
+    }
+
+    fn locations(gradient: &ColorGradient) -> Vec<(f32, Color)> {
+        gradient
+            .points()
+            .iter()
+            .map(|p| (p.location(), p.color()))
+            .collect()
+    }
+
+    #[test]
+    fn point_location() {
+        let mut ui = UserInterface::new(Vector2::new(100.0, 100.0));
+        let mut gradient = ColorGradient::new();
+        gradient.add_point(GradientPoint::new(0.0, Color::RED));
+        gradient.add_point(GradientPoint::new(0.5, Color::GREEN));
+        gradient.add_point(GradientPoint::new(1.0, Color::BLUE));
+        let handle = ColorGradientEditorBuilder::new(WidgetBuilder::new())
+            .with_color_gradient(gradient)
+            .build(&mut ui.build_ctx());
+        while ui.poll_message().is_some() {}
+
+        // Opening the context menu of a point syncs the location field with the point.
+        let point = ui.node(editor(&ui, handle).points_canvas).children()[1];
+        let point_context_menu = *editor(&ui, handle).point_context_menu;
+        send(
+            &mut ui,
+            handle,
+            PopupMessage::placement(
+                point_context_menu,
+                MessageDirection::ToWidget,
+                Placement::Cursor(point),
+            ),
+        );
+        let location_field = editor(&ui, handle).location_field;
+        assert_eq!(
+            ui.node(location_field)
+                .query_component::<NumericUpDown<f32>>()
+                .unwrap()
+                .value,
+            0.5
+        );
+
+        // Changing the location moves the point, the points are kept sorted.
+        let gradient = send(
+            &mut ui,
+            handle,
+            NumericUpDownMessage::value(location_field, MessageDirection::FromWidget, 0.25f32),
+        );
+        assert_eq!(
+            locations(&gradient.unwrap()),
+            [(0.0, Color::RED), (0.25, Color::GREEN), (1.0, Color::BLUE)]
+        );
+        let gradient = send(
+            &mut ui,
+            handle,
+            NumericUpDownMessage::value(location_field, MessageDirection::FromWidget, -1.0f32),
+        );
+        assert_eq!(
+            locations(&gradient.unwrap()),
+            [(0.0, Color::RED), (0.0, Color::GREEN), (1.0, Color::BLUE)]
+        );
+
+        // Syncing the field does not modify the gradient.
+        let mut message =
+            NumericUpDownMessage::value(location_field, MessageDirection::FromWidget, 0.75f32);
+        message.flags = SYNC_FLAG;
+        assert_eq!(send(&mut ui, handle, message), None);
+    }
+}
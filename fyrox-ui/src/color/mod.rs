@@ -15,8 +15,11 @@ use crate::{
     message::{MessageDirection, MouseButton, UiMessage},
     numeric::{NumericUpDownBuilder, NumericUpDownMessage},
     popup::{Placement, PopupBuilder, PopupMessage},
-    text::TextBuilder,
+    style::Style,
+    text::{TextBuilder, TextMessage},
+    text_box::{TextBoxBuilder, TextCommitMode},
     widget::{Widget, WidgetBuilder, WidgetMessage},
+    wrap_panel::WrapPanelBuilder,
     BuildContext, Control, NodeHandleMapping, Orientation, Thickness, UiNode, UserInterface,
     VerticalAlignment,
 };
@@ -85,11 +88,53 @@ pub enum ColorPickerMessage {
     ///
     /// Direction: **To Widget**.
     Hsv(Hsv),
+
+    /// Sets recently used colors, most recent first. Only first [`MAX_RECENT_COLORS`] colors are used.
+    ///
+    /// Direction: **To/From Widget**.
+    RecentColors(Vec<Color>),
 }
 
 impl ColorPickerMessage {
     define_constructor!(ColorPickerMessage:Color => fn color(Color), layout: false);
     define_constructor!(ColorPickerMessage:Hsv => fn hsv(Hsv), layout: false);
+    define_constructor!(ColorPickerMessage:RecentColors => fn recent_colors(Vec<Color>), layout: false);
+}
+
+/// Maximum amount of recently used colors, that is shown by [`ColorPicker`].
+pub const MAX_RECENT_COLORS: usize = 8;
+
+/// Formats a color as a hex string in `RRGGBBAA` format.
+pub fn color_to_hex(color: Color) -> String {
+    format!(
+        "{:02X}{:02X}{:02X}{:02X}",
+        color.r, color.g, color.b, color.a
+    )
+}
+
+/// Parses a color from a hex string in `RRGGBB` or `RRGGBBAA` format, leading `#` is optional.
+pub fn color_from_hex(hex: &str) -> Option<Color> {
+    let hex = hex.trim().trim_start_matches('#');
+    // `from_str_radix` accepts a sign, so the digits must be checked beforehand.
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let component = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    match hex.len() {
+        6 => Some(Color::from_rgba(
+            component(0)?,
+            component(2)?,
+            component(4)?,
+            255,
+        )),
+        8 => Some(Color::from_rgba(
+            component(0)?,
+            component(2)?,
+            component(4)?,
+            component(6)?,
+        )),
+        _ => None,
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -747,6 +792,12 @@ pub struct ColorPicker {
     pub saturation: Handle<UiNode>,
     pub brightness: Handle<UiNode>,
     pub color_mark: Handle<UiNode>,
+    #[visit(optional)]
+    pub hex: Handle<UiNode>,
+    #[visit(optional)]
+    pub swatches: Vec<Handle<UiNode>>,
+    #[visit(optional)]
+    pub recent_colors: Vec<Color>,
     pub color: Color,
     pub hsv: Hsv,
 }
@@ -802,12 +853,33 @@ impl ColorPicker {
             color.a as f32,
         )));
 
+        ui.send_message(mark_handled(TextMessage::text(
+            self.hex,
+            MessageDirection::ToWidget,
+            color_to_hex(color),
+        )));
+
         ui.send_message(mark_handled(WidgetMessage::background(
             self.color_mark,
             MessageDirection::ToWidget,
             Brush::Solid(color),
         )));
     }
+
+    fn sync_swatches(&self, ui: &UserInterface) {
+        for (i, swatch) in self.swatches.iter().enumerate() {
+            ui.send_message(WidgetMessage::background(
+                *swatch,
+                MessageDirection::ToWidget,
+                Brush::Solid(
+                    self.recent_colors
+                        .get(i)
+                        .cloned()
+                        .unwrap_or(Color::TRANSPARENT),
+                ),
+            ));
+        }
+    }
 }
 
 impl Control for ColorPicker {
@@ -831,12 +903,61 @@ impl Control for ColorPicker {
         node_map.resolve(&mut self.saturation);
         node_map.resolve(&mut self.brightness);
         node_map.resolve(&mut self.color_mark);
+        node_map.resolve(&mut self.hex);
+        node_map.resolve_slice(&mut self.swatches);
     }
 
     fn handle_routed_message(&mut self, ui: &mut UserInterface, message: &mut UiMessage) {
         self.widget.handle_routed_message(ui, message);
 
-        if let Some(&HueBarMessage::Hue(hue)) = message.data::<HueBarMessage>() {
+        if let Some(msg) = message.data::<WidgetMessage>() {
+            match msg {
+                WidgetMessage::MouseDown { button, .. } if *button == MouseButton::Left => {
+                    if let Some(color) = self
+                        .swatches
+                        .iter()
+                        .position(|s| *s == message.destination())
+                        .and_then(|i| self.recent_colors.get(i))
+                    {
+                        ui.send_message(ColorPickerMessage::color(
+                            self.handle,
+                            MessageDirection::ToWidget,
+                            *color,
+                        ));
+                    }
+                }
+                // Any finished interaction with the picker makes current color "recently used".
+                WidgetMessage::MouseUp { button, .. }
+                    if *button == MouseButton::Left
+                        && self.recent_colors.first() != Some(&self.color) =>
+                {
+                    let mut recent_colors = self.recent_colors.clone();
+                    recent_colors.retain(|c| *c != self.color);
+                    recent_colors.insert(0, self.color);
+                    ui.send_message(ColorPickerMessage::recent_colors(
+                        self.handle,
+                        MessageDirection::ToWidget,
+                        recent_colors,
+                    ));
+                }
+                _ => (),
+            }
+        } else if let Some(TextMessage::Text(text)) = message.data::<TextMessage>() {
+            if message.destination() == self.hex
+                && message.direction() == MessageDirection::FromWidget
+            {
+                if let Some(color) = color_from_hex(text) {
+                    ui.send_message(ColorPickerMessage::color(
+                        self.handle,
+                        MessageDirection::ToWidget,
+                        color,
+                    ));
+                } else {
+                    // Restore the text of the field.
+                    self.sync_fields(ui, self.color, self.hsv);
+                }
+            }
+        } else if let Some(&HueBarMessage::Hue(hue)) = message.data::<HueBarMessage>() {
             if message.destination() == self.hue_bar
                 && message.direction() == MessageDirection::FromWidget
             {
@@ -943,6 +1064,18 @@ impl Control for ColorPicker {
                 && message.direction() == MessageDirection::ToWidget
             {
                 match *msg {
+                    ColorPickerMessage::RecentColors(ref recent_colors) => {
+                        let recent_colors = recent_colors
+                            .iter()
+                            .take(MAX_RECENT_COLORS)
+                            .cloned()
+                            .collect::<Vec<_>>();
+                        if self.recent_colors != recent_colors {
+                            self.recent_colors = recent_colors;
+                            self.sync_swatches(ui);
+                            ui.send_message(message.reverse());
+                        }
+                    }
                     ColorPickerMessage::Color(color) => {
                         if self.color != color {
                             self.color = color;
@@ -1033,8 +1166,24 @@ impl ColorPickerBuilder {
         let brightness;
         let color_mark;
         let alpha;
+        let hex;
         let hsv = Hsv::from(self.color);
 
+        let swatches = (0..MAX_RECENT_COLORS)
+            .map(|_| {
+                BorderBuilder::new(
+                    WidgetBuilder::new()
+                        .with_width(16.0)
+                        .with_height(16.0)
+                        .with_margin(Thickness::uniform(1.0))
+                        .with_foreground_style(Style::BRUSH_LIGHT)
+                        .with_background(Brush::Solid(Color::TRANSPARENT)),
+                )
+                .with_stroke_thickness(Thickness::uniform(1.0))
+                .build(ctx)
+            })
+            .collect::<Vec<_>>();
+
         let numerics_grid = GridBuilder::new(
             WidgetBuilder::new()
                 .on_row(1)
@@ -1072,6 +1221,20 @@ impl ColorPickerBuilder {
                 .with_child({
                     alpha = make_input_field(ctx, self.color.a as f32, 255.0, 3, 1);
                     alpha
+                })
+                .with_child(make_text_mark(ctx, "#", 3, 2))
+                .with_child({
+                    hex = TextBoxBuilder::new(
+                        WidgetBuilder::new()
+                            .with_margin(Thickness::uniform(1.0))
+                            .on_row(3)
+                            .on_column(3),
+                    )
+                    .with_text(color_to_hex(self.color))
+                    .with_vertical_text_alignment(VerticalAlignment::Center)
+                    .with_text_commit_mode(TextCommitMode::LostFocusPlusEnter)
+                    .build(ctx);
+                    hex
                 }),
         )
         .add_column(Column::strict(10.0))
@@ -1131,10 +1294,20 @@ impl ColorPickerBuilder {
                                         .build(ctx);
                                         color_mark
                                     })
-                                    .with_child(numerics_grid),
+                                    .with_child(numerics_grid)
+                                    .with_child(
+                                        WrapPanelBuilder::new(
+                                            WidgetBuilder::new()
+                                                .on_row(2)
+                                                .with_children(swatches.iter().cloned()),
+                                        )
+                                        .with_orientation(Orientation::Horizontal)
+                                        .build(ctx),
+                                    ),
                             )
                             .add_row(Row::strict(25.0))
                             .add_row(Row::stretch())
+                            .add_row(Row::auto())
                             .add_column(Column::stretch())
                             .build(ctx),
                         ),
@@ -1142,7 +1315,7 @@ impl ColorPickerBuilder {
                 .add_column(Column::stretch())
                 .add_column(Column::strict(20.0))
                 .add_column(Column::strict(20.0))
-                .add_column(Column::strict(100.0))
+                .add_column(Column::strict(150.0))
                 .add_row(Row::auto())
                 .build(ctx),
             )
@@ -1163,6 +1336,9 @@ impl ColorPickerBuilder {
             hsv,
             alpha_bar,
             alpha,
+            hex,
+            swatches,
+            recent_colors: Default::default(),
         };
         ctx.add_node(UiNode::new(picker))
     }
@@ -1310,3 +1486,42 @@ impl ColorFieldBuilder {
         ctx.add_node(UiNode::new(field))
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        color::{color_from_hex, color_to_hex},
+        core::color::Color,
+    };
+
+    #[test]
+    fn test_color_from_hex() {
+        assert_eq!(
+            color_from_hex("FF8000"),
+            Some(Color::from_rgba(255, 128, 0, 255))
+        );
+        assert_eq!(
+            color_from_hex(" #ff800080 "),
+            Some(Color::from_rgba(255, 128, 0, 128))
+        );
+        for invalid in [
+            "",
+            "#",
+            "FFF",
+            "FF80001",
+            "FF8000800",
+            "GG8000",
+            "+1+2+3",
+            "ÿÿÿ",
+        ] {
+            assert_eq!(color_from_hex(invalid), None, "{invalid}");
+        }
+    }
+
+    #[test]
+    fn test_color_to_hex() {
+        let color = Color::from_rgba(1, 171, 255, 16);
+        assert_eq!(color_to_hex(color), "01ABFF10");
+        assert_eq!(color_from_hex(&color_to_hex(color)), Some(color));
+    }
+}
@@ -3,7 +3,9 @@ use crate::{
         algebra::Vector2, pool::Handle, reflect::Reflect, scope_profile, uuid::Uuid, visitor::Visit,
     },
     draw::DrawingContext,
+    localization::Localization,
     message::{OsEvent, UiMessage},
+    style::Style,
     widget::Widget,
     NodeHandleMapping, UiNode, UserInterface,
 };
@@ -104,6 +106,19 @@ pub trait Control: BaseControl + Deref<Target = Widget> + DerefMut + Reflect + V
     /// is guaranteed to be called once, and only if the widget is deleted via [`crate::widget::WidgetMessage::remove`].
     fn on_remove(&self, #[allow(unused_variables)] sender: &Sender<UiMessage>) {}
 
+    /// This method will be called when the widget is added to the user interface and every time when the style
+    /// of the user interface changes. It is used to update style-bound properties of the widget. Default
+    /// implementation updates the properties of the base widget, widgets with their own style-bound properties
+    /// must call [`Widget::apply_style`] (or the method of their base control) manually.
+    fn apply_style(&mut self, style: &Style) {
+        Widget::apply_style(self, style)
+    }
+
+    /// This method will be called when the widget is added to the user interface and every time when the
+    /// language or the translation provider of the user interface changes. It is used to update localized
+    /// properties of the widget (for example, the text of [`crate::text::Text`] widget).
+    fn apply_localization(&mut self, #[allow(unused_variables)] localization: &Localization) {}
+
     /// This method is used to override measurement step of the layout system. It should return desired size of
     /// the widget (how many space it wants to occupy).
     ///
@@ -0,0 +1,617 @@
+//! Import and export of curve keys in JSON and CSV formats, so curves could be shared between
+//! projects or generated by external tools and spreadsheets.
+
+use crate::{
+    core::{
+        algebra::Vector2,
+        curve::{CurveKeyKind, TangentMode},
+        json::{JsonError, JsonValue},
+        uuid::Uuid,
+    },
+    curve::key::{CurveKeyView, KeyContainer},
+};
+use std::{
+    fmt::{Display, Formatter},
+    path::Path,
+};
+
+/// Columns of a CSV file in the order they're written. Files with a header could have any subset
+/// of the columns in any order, but `location` and `value` are mandatory.
+pub const CSV_COLUMNS: [&str; 10] = [
+    "curve",
+    "location",
+    "value",
+    "kind",
+    "left_tangent",
+    "right_tangent",
+    "broken",
+    "weighted",
+    "left_weight",
+    "right_weight",
+];
+
+/// File format of exported curve keys.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CurveKeysFormat {
+    /// An object with `curves` array, each curve has `name` and `keys` array.
+    Json,
+    /// A table with one key per row, see [`CSV_COLUMNS`] for the list of the columns.
+    Csv,
+}
+
+impl CurveKeysFormat {
+    /// Picks a format by the extension of the given path (`json` or `csv`, case-insensitive).
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_string_lossy().to_lowercase();
+        match extension.as_str() {
+            "json" => Some(Self::Json),
+            "csv" => Some(Self::Csv),
+            _ => None,
+        }
+    }
+}
+
+/// An error that could occur while importing or exporting curve keys.
+#[derive(Debug)]
+pub enum CurveKeysError {
+    /// A file has an extension other than `json` or `csv`.
+    UnsupportedFormat,
+    /// An error reading or writing a file.
+    Io(std::io::Error),
+    /// Malformed JSON or JSON with unexpected structure.
+    Json(String),
+    /// Malformed CSV, `line` starts from 1.
+    Csv { line: usize, reason: String },
+}
+
+impl Display for CurveKeysError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnsupportedFormat => {
+                write!(
+                    f,
+                    "Unsupported file format, only json and csv are supported."
+                )
+            }
+            Self::Io(err) => write!(f, "File error: {err}"),
+            Self::Json(err) => write!(f, "Invalid JSON: {err}"),
+            Self::Csv { line, reason } => write!(f, "Invalid CSV at line {line}: {reason}"),
+        }
+    }
+}
+
+impl From<std::io::Error> for CurveKeysError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<JsonError> for CurveKeysError {
+    fn from(err: JsonError) -> Self {
+        Self::Json(err.to_string())
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+enum KeyKindName {
+    Constant,
+    #[default]
+    Linear,
+    Cubic,
+}
+
+impl KeyKindName {
+    fn parse(string: &str) -> Option<Self> {
+        match string.to_lowercase().as_str() {
+            "constant" => Some(Self::Constant),
+            "linear" => Some(Self::Linear),
+            "cubic" => Some(Self::Cubic),
+            _ => None,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Constant => "constant",
+            Self::Linear => "linear",
+            Self::Cubic => "cubic",
+        }
+    }
+}
+
+fn default_weight() -> f32 {
+    TangentMode::DEFAULT_WEIGHT
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct KeyRecord {
+    location: f32,
+    value: f32,
+    kind: KeyKindName,
+    left_tangent: f32,
+    right_tangent: f32,
+    broken: bool,
+    weighted: bool,
+    left_weight: f32,
+    right_weight: f32,
+}
+
+impl Default for KeyRecord {
+    fn default() -> Self {
+        Self {
+            location: 0.0,
+            value: 0.0,
+            kind: Default::default(),
+            left_tangent: 0.0,
+            right_tangent: 0.0,
+            broken: false,
+            weighted: false,
+            left_weight: default_weight(),
+            right_weight: default_weight(),
+        }
+    }
+}
+
+impl From<&CurveKeyView> for KeyRecord {
+    fn from(key: &CurveKeyView) -> Self {
+        let mut record = Self {
+            location: key.position.x,
+            value: key.position.y,
+            ..Default::default()
+        };
+        match key.kind {
+            CurveKeyKind::Constant => record.kind = KeyKindName::Constant,
+            CurveKeyKind::Linear => record.kind = KeyKindName::Linear,
+            CurveKeyKind::Cubic {
+                left_tangent,
+                right_tangent,
+            } => {
+                record.kind = KeyKindName::Cubic;
+                record.left_tangent = left_tangent;
+                record.right_tangent = right_tangent;
+                record.broken = key.tangent_mode.broken;
+                record.weighted = key.tangent_mode.weighted;
+                record.left_weight = key.tangent_mode.left_weight;
+                record.right_weight = key.tangent_mode.right_weight;
+            }
+        }
+        record
+    }
+}
+
+impl KeyRecord {
+    fn to_json(&self) -> JsonValue {
+        JsonValue::Object(vec![
+            ("location".to_string(), self.location.into()),
+            ("value".to_string(), self.value.into()),
+            ("kind".to_string(), self.kind.as_str().into()),
+            ("left_tangent".to_string(), self.left_tangent.into()),
+            ("right_tangent".to_string(), self.right_tangent.into()),
+            ("broken".to_string(), self.broken.into()),
+            ("weighted".to_string(), self.weighted.into()),
+            ("left_weight".to_string(), self.left_weight.into()),
+            ("right_weight".to_string(), self.right_weight.into()),
+        ])
+    }
+
+    // Only `location` and `value` are mandatory, unknown members are ignored.
+    fn from_json(json: &JsonValue) -> Result<Self, CurveKeysError> {
+        if json.as_object().is_none() {
+            return Err(CurveKeysError::Json("a key must be an object".to_string()));
+        }
+        let invalid = |name: &str| CurveKeysError::Json(format!("invalid key {name}"));
+        let number = |name: &str, default: Option<f32>| match json.get(name) {
+            Some(value) => value.as_f32().ok_or_else(|| invalid(name)),
+            None => default.ok_or_else(|| CurveKeysError::Json(format!("missing key {name}"))),
+        };
+        let boolean = |name: &str| match json.get(name) {
+            Some(value) => value.as_bool().ok_or_else(|| invalid(name)),
+            None => Ok(false),
+        };
+        Ok(Self {
+            location: number("location", None)?,
+            value: number("value", None)?,
+            kind: match json.get("kind") {
+                Some(kind) => kind
+                    .as_str()
+                    .and_then(KeyKindName::parse)
+                    .ok_or_else(|| invalid("kind"))?,
+                None => Default::default(),
+            },
+            left_tangent: number("left_tangent", Some(0.0))?,
+            right_tangent: number("right_tangent", Some(0.0))?,
+            broken: boolean("broken")?,
+            weighted: boolean("weighted")?,
+            left_weight: number("left_weight", Some(default_weight()))?,
+            right_weight: number("right_weight", Some(default_weight()))?,
+        })
+    }
+
+    fn into_key(self) -> CurveKeyView {
+        let kind = match self.kind {
+            KeyKindName::Constant => CurveKeyKind::Constant,
+            KeyKindName::Linear => CurveKeyKind::Linear,
+            KeyKindName::Cubic => CurveKeyKind::Cubic {
+                left_tangent: self.left_tangent,
+                right_tangent: self.right_tangent,
+            },
+        };
+        CurveKeyView {
+            position: Vector2::new(self.location, self.value),
+            kind,
+            id: Uuid::new_v4(),
+            tangent_mode: TangentMode {
+                broken: self.broken,
+                weighted: self.weighted,
+                left_weight: self.left_weight,
+                right_weight: self.right_weight,
+            },
+        }
+    }
+}
+
+#[derive(Default)]
+struct CurveRecord {
+    name: String,
+    keys: Vec<KeyRecord>,
+}
+
+#[derive(Default)]
+struct CurvesRecord {
+    curves: Vec<CurveRecord>,
+}
+
+impl CurvesRecord {
+    fn to_json(&self) -> JsonValue {
+        let curves = self
+            .curves
+            .iter()
+            .map(|curve| {
+                JsonValue::Object(vec![
+                    ("name".to_string(), curve.name.as_str().into()),
+                    (
+                        "keys".to_string(),
+                        JsonValue::Array(curve.keys.iter().map(KeyRecord::to_json).collect()),
+                    ),
+                ])
+            })
+            .collect();
+        JsonValue::Object(vec![("curves".to_string(), JsonValue::Array(curves))])
+    }
+
+    fn from_json(json: &JsonValue) -> Result<Self, CurveKeysError> {
+        let curves = json
+            .get("curves")
+            .and_then(JsonValue::as_array)
+            .ok_or_else(|| CurveKeysError::Json("missing curves array".to_string()))?;
+        let curves = curves
+            .iter()
+            .map(|curve| {
+                if curve.as_object().is_none() {
+                    return Err(CurveKeysError::Json(
+                        "a curve must be an object".to_string(),
+                    ));
+                }
+                let name = match curve.get("name") {
+                    Some(name) => name
+                        .as_str()
+                        .ok_or_else(|| CurveKeysError::Json("invalid curve name".to_string()))?
+                        .to_string(),
+                    None => Default::default(),
+                };
+                let keys = match curve.get("keys") {
+                    Some(keys) => keys
+                        .as_array()
+                        .ok_or_else(|| CurveKeysError::Json("invalid curve keys".to_string()))?
+                        .iter()
+                        .map(KeyRecord::from_json)
+                        .collect::<Result<_, _>>()?,
+                    None => Default::default(),
+                };
+                Ok(CurveRecord { name, keys })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { curves })
+    }
+}
+
+/// Serializes the keys of the given curves to a string in the given format.
+pub fn keys_to_string(curves: &[KeyContainer], format: CurveKeysFormat) -> String {
+    match format {
+        CurveKeysFormat::Json => {
+            let record = CurvesRecord {
+                curves: curves
+                    .iter()
+                    .map(|curve| CurveRecord {
+                        name: curve.name().to_string(),
+                        keys: curve.keys().iter().map(KeyRecord::from).collect(),
+                    })
+                    .collect(),
+            };
+            record.to_json().to_string_pretty()
+        }
+        CurveKeysFormat::Csv => {
+            let mut string = CSV_COLUMNS.join(",");
+            for (index, curve) in curves.iter().enumerate() {
+                for key in curve.keys() {
+                    let r = KeyRecord::from(key);
+                    string += &format!(
+                        "\n{},{},{},{},{},{},{},{},{},{}",
+                        index,
+                        r.location,
+                        r.value,
+                        r.kind.as_str(),
+                        r.left_tangent,
+                        r.right_tangent,
+                        r.broken,
+                        r.weighted,
+                        r.left_weight,
+                        r.right_weight
+                    );
+                }
+            }
+            string
+        }
+    }
+}
+
+fn parse_csv(string: &str) -> Result<Vec<Vec<CurveKeyView>>, CurveKeysError> {
+    let mut lines = string
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .peekable();
+
+    // The header is optional, if it is missing the columns are expected in the default order.
+    let mut columns = CSV_COLUMNS.to_vec();
+    if let Some((n, header)) = lines.peek().cloned() {
+        let first = header.split(',').next().unwrap_or_default().trim();
+        if first.parse::<f32>().is_err() {
+            columns = header
+                .split(',')
+                .map(|name| {
+                    let name = name.trim().to_lowercase();
+                    CSV_COLUMNS
+                        .iter()
+                        .find(|column| **column == name)
+                        .copied()
+                        .ok_or_else(|| CurveKeysError::Csv {
+                            line: n + 1,
+                            reason: format!("unknown column {name}"),
+                        })
+                })
+                .collect::<Result<_, _>>()?;
+            for mandatory in ["location", "value"] {
+                if !columns.contains(&mandatory) {
+                    return Err(CurveKeysError::Csv {
+                        line: n + 1,
+                        reason: format!("missing {mandatory} column"),
+                    });
+                }
+            }
+            lines.next();
+        }
+    }
+
+    let mut curves: Vec<Vec<CurveKeyView>> = Vec::new();
+    for (n, line) in lines {
+        let error = |reason: String| CurveKeysError::Csv {
+            line: n + 1,
+            reason,
+        };
+        let mut curve = 0;
+        let mut record = KeyRecord::default();
+        for (column, field) in columns.iter().zip(line.split(',')) {
+            let field = field.trim();
+            let invalid = || error(format!("invalid {column} {field:?}"));
+            match *column {
+                "curve" => curve = field.parse().map_err(|_| invalid())?,
+                "location" => record.location = field.parse().map_err(|_| invalid())?,
+                "value" => record.value = field.parse().map_err(|_| invalid())?,
+                "kind" => record.kind = KeyKindName::parse(field).ok_or_else(invalid)?,
+                "left_tangent" => record.left_tangent = field.parse().map_err(|_| invalid())?,
+                "right_tangent" => record.right_tangent = field.parse().map_err(|_| invalid())?,
+                "broken" => record.broken = field.parse().map_err(|_| invalid())?,
+                "weighted" => record.weighted = field.parse().map_err(|_| invalid())?,
+                "left_weight" => record.left_weight = field.parse().map_err(|_| invalid())?,
+                "right_weight" => record.right_weight = field.parse().map_err(|_| invalid())?,
+                _ => unreachable!(),
+            }
+        }
+        if line.split(',').count() < columns.len() {
+            return Err(error(format!(
+                "expected {} fields, got {}",
+                columns.len(),
+                line.split(',').count()
+            )));
+        }
+        if curves.len() <= curve {
+            curves.resize_with(curve + 1, Default::default);
+        }
+        curves[curve].push(record.into_key());
+    }
+    Ok(curves)
+}
+
+/// Deserializes the keys from a string in the given format. Returns a list of keys per curve,
+/// every key gets a new unique id.
+pub fn keys_from_string(
+    string: &str,
+    format: CurveKeysFormat,
+) -> Result<Vec<Vec<CurveKeyView>>, CurveKeysError> {
+    match format {
+        CurveKeysFormat::Json => {
+            let record = CurvesRecord::from_json(&JsonValue::parse(string)?)?;
+            Ok(record
+                .curves
+                .into_iter()
+                .map(|curve| curve.keys.into_iter().map(KeyRecord::into_key).collect())
+                .collect())
+        }
+        CurveKeysFormat::Csv => parse_csv(string),
+    }
+}
+
+/// Serializes the given keys into a string in JSON format (as keys of a single curve), that could
+/// be put into the clipboard.
+pub fn keys_to_clipboard_string<'a>(keys: impl Iterator<Item = &'a CurveKeyView>) -> String {
+    let record = CurvesRecord {
+        curves: vec![CurveRecord {
+            name: Default::default(),
+            keys: keys.map(KeyRecord::from).collect(),
+        }],
+    };
+    record.to_json().to_string_pretty()
+}
+
+/// Deserializes the keys from the clipboard. The keys could be in any of the supported formats (see
+/// [`CurveKeysFormat`]), so keys copied from a spreadsheet could be pasted too. Keys of all curves
+/// are merged together, every key gets a new unique id. Returns `None` if the string has invalid
+/// format.
+pub fn keys_from_clipboard_string(string: &str) -> Option<Vec<CurveKeyView>> {
+    [CurveKeysFormat::Json, CurveKeysFormat::Csv]
+        .into_iter()
+        .find_map(|format| keys_from_string(string, format).ok())
+        .map(|curves| curves.into_iter().flatten().collect())
+}
+
+/// Writes the keys of the given curves to a file, the format is picked by the extension of the file.
+pub fn export_keys(path: &Path, curves: &[KeyContainer]) -> Result<(), CurveKeysError> {
+    let format = CurveKeysFormat::from_path(path).ok_or(CurveKeysError::UnsupportedFormat)?;
+    std::fs::write(path, keys_to_string(curves, format))?;
+    Ok(())
+}
+
+/// Reads the keys from a file, the format is picked by the extension of the file. See
+/// [`keys_from_string`] for more info.
+pub fn import_keys(path: &Path) -> Result<Vec<Vec<CurveKeyView>>, CurveKeysError> {
+    let format = CurveKeysFormat::from_path(path).ok_or(CurveKeysError::UnsupportedFormat)?;
+    keys_from_string(&std::fs::read_to_string(path)?, format)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::{
+            algebra::Vector2,
+            curve::{Curve, CurveKey, CurveKeyKind, TangentMode},
+        },
+        curve::{
+            io::{
+                keys_from_clipboard_string, keys_from_string, keys_to_clipboard_string,
+                keys_to_string, CurveKeysFormat,
+            },
+            key::KeyContainer,
+        },
+    };
+
+    fn curves() -> Vec<KeyContainer> {
+        vec![
+            KeyContainer::from(&Curve::from(vec![
+                CurveKey::new(0.0, 1.0, CurveKeyKind::Constant),
+                CurveKey::new(
+                    1.0,
+                    2.0,
+                    CurveKeyKind::Cubic {
+                        left_tangent: 0.5,
+                        right_tangent: -0.25,
+                    },
+                )
+                .with_tangent_mode(TangentMode {
+                    broken: true,
+                    weighted: true,
+                    left_weight: 0.5,
+                    right_weight: 0.2,
+                }),
+            ])),
+            KeyContainer::from(&Curve::from(vec![CurveKey::new(
+                2.0,
+                -3.0,
+                CurveKeyKind::Linear,
+            )])),
+        ]
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let curves = curves();
+        for format in [CurveKeysFormat::Json, CurveKeysFormat::Csv] {
+            let string = keys_to_string(&curves, format);
+            let keys = keys_from_string(&string, format).unwrap();
+            assert_eq!(keys.len(), 2);
+            for (imported, curve) in keys.iter().zip(curves.iter()) {
+                assert_eq!(imported.len(), curve.keys().len());
+                for (a, b) in imported.iter().zip(curve.keys()) {
+                    assert_eq!(a.position, b.position);
+                    assert_eq!(a.kind, b.kind);
+                    assert_ne!(a.id, b.id);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_json_defaults() {
+        let keys = keys_from_string(
+            r#"{ "curves": [ { "keys": [ { "location": 1, "value": 2, "extra": [] } ] } ] }"#,
+            CurveKeysFormat::Json,
+        )
+        .unwrap();
+        assert_eq!(keys[0][0].position, Vector2::new(1.0, 2.0));
+        assert_eq!(keys[0][0].kind, CurveKeyKind::Linear);
+        assert_eq!(keys[0][0].tangent_mode, TangentMode::default());
+
+        for string in [
+            r#"{ "curves": [ { "keys": [ { "location": 1 } ] } ] }"#,
+            r#"{ "curves": [ { "keys": [ { "location": 1, "value": "2" } ] } ] }"#,
+            r#"{ "curves": [ { "keys": [ { "location": 1, "value": 2, "kind": "bezier" } ] } ] }"#,
+            r#"{ "keys": [] }"#,
+            r#"{ "curves": [ "#,
+        ] {
+            assert!(keys_from_string(string, CurveKeysFormat::Json).is_err());
+        }
+    }
+
+    #[test]
+    fn test_csv_partial_columns() {
+        let keys =
+            keys_from_string("Value, Location\n1, 0\n\n3, 2.5\n", CurveKeysFormat::Csv).unwrap();
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0][0].position, Vector2::new(0.0, 1.0));
+        assert_eq!(keys[0][1].position, Vector2::new(2.5, 3.0));
+        assert_eq!(keys[0][1].kind, CurveKeyKind::Linear);
+
+        let keys = keys_from_string("1,0.5,2", CurveKeysFormat::Csv);
+        assert!(keys.is_err());
+        assert!(keys_from_string("time,value\n0,1", CurveKeysFormat::Csv).is_err());
+        assert!(keys_from_string("location\n0", CurveKeysFormat::Csv).is_err());
+    }
+
+    #[test]
+    fn test_clipboard_round_trip() {
+        let curves = curves();
+        let keys = curves[0].keys();
+        let string = keys_to_clipboard_string(keys.iter());
+        // The clipboard uses the same format as exported files.
+        assert_eq!(
+            keys_from_string(&string, CurveKeysFormat::Json)
+                .unwrap()
+                .len(),
+            1
+        );
+        let pasted = keys_from_clipboard_string(&string).unwrap();
+        assert_eq!(pasted.len(), 2);
+        for (key, pasted) in keys.iter().zip(pasted.iter()) {
+            assert_eq!(key.position, pasted.position);
+            assert_eq!(key.kind, pasted.kind);
+            assert_ne!(key.id, pasted.id);
+        }
+
+        // Keys of all curves of CSV table are merged.
+        let pasted =
+            keys_from_clipboard_string(&keys_to_string(&curves, CurveKeysFormat::Csv)).unwrap();
+        assert_eq!(pasted.len(), 3);
+
+        assert!(keys_from_clipboard_string("not keys").is_none());
+    }
+}
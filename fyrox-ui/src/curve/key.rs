@@ -1,17 +1,20 @@
 use crate::core::{
     algebra::Vector2,
-    curve::{Curve, CurveKey, CurveKeyKind},
+    curve::{Curve, CurveExtrapolation, CurveKey, CurveKeyKind, TangentMode},
+    math::cubicf_derivative,
     reflect::prelude::*,
     uuid::Uuid,
     visitor::prelude::*,
 };
 use std::cmp::Ordering;
 
-#[derive(Clone, Debug, Visit, Reflect, Default)]
+#[derive(Clone, Debug, Visit, Reflect, Default, PartialEq)]
 pub struct CurveKeyView {
     pub position: Vector2<f32>,
     pub kind: CurveKeyKind,
     pub id: Uuid,
+    #[visit(optional)]
+    pub tangent_mode: TangentMode,
 }
 
 impl From<&CurveKey> for CurveKeyView {
@@ -20,14 +23,21 @@ impl From<&CurveKey> for CurveKeyView {
             position: Vector2::new(key.location(), key.value),
             kind: key.kind.clone(),
             id: key.id,
+            tangent_mode: key.tangent_mode,
         }
     }
 }
 
-#[derive(Clone, Visit, Reflect, Debug)]
+#[derive(Clone, Visit, Reflect, Debug, Default, PartialEq)]
 pub struct KeyContainer {
     id: Uuid,
+    #[visit(optional)]
+    name: String,
     keys: Vec<CurveKeyView>,
+    #[visit(optional)]
+    pre_extrapolation: CurveExtrapolation,
+    #[visit(optional)]
+    post_extrapolation: CurveExtrapolation,
 }
 
 impl From<&Curve> for KeyContainer {
@@ -39,11 +49,34 @@ impl From<&Curve> for KeyContainer {
                 .map(CurveKeyView::from)
                 .collect::<Vec<_>>(),
             id: curve.id(),
+            name: curve.name().to_string(),
+            pre_extrapolation: curve.pre_extrapolation(),
+            post_extrapolation: curve.post_extrapolation(),
         }
     }
 }
 
 impl KeyContainer {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn pre_extrapolation(&self) -> CurveExtrapolation {
+        self.pre_extrapolation
+    }
+
+    pub fn set_pre_extrapolation(&mut self, extrapolation: CurveExtrapolation) {
+        self.pre_extrapolation = extrapolation;
+    }
+
+    pub fn post_extrapolation(&self) -> CurveExtrapolation {
+        self.post_extrapolation
+    }
+
+    pub fn set_post_extrapolation(&mut self, extrapolation: CurveExtrapolation) {
+        self.post_extrapolation = extrapolation;
+    }
+
     pub fn add(&mut self, key: CurveKeyView) {
         self.keys.push(key)
     }
@@ -56,6 +89,10 @@ impl KeyContainer {
         }
     }
 
+    pub fn clear(&mut self) {
+        self.keys.clear();
+    }
+
     pub fn key_ref(&self, id: Uuid) -> Option<&CurveKeyView> {
         self.keys.iter().find(|k| k.id == id)
     }
@@ -92,18 +129,238 @@ impl KeyContainer {
         })
     }
 
+    /// Calculates Catmull-Rom style tangents (left and right) of a key with the given index, so the
+    /// curve passes smoothly through the key in the direction from its previous neighbour to its
+    /// next neighbour. The tangents are expressed in the units that are used by cubic keys, so they
+    /// could be assigned directly. Returns `None` if there's no key with the given index.
+    pub fn auto_smooth_tangents(&self, index: usize) -> Option<(f32, f32)> {
+        let key = self.keys.get(index)?.position;
+        let prev = index
+            .checked_sub(1)
+            .and_then(|i| self.keys.get(i))
+            .map(|k| k.position);
+        let next = self.keys.get(index + 1).map(|k| k.position);
+
+        let slope = |a: Vector2<f32>, b: Vector2<f32>| {
+            let dx = b.x - a.x;
+            if dx.abs() > f32::EPSILON {
+                (b.y - a.y) / dx
+            } else {
+                0.0
+            }
+        };
+        let slope = match (prev, next) {
+            (Some(prev), Some(next)) => slope(prev, next),
+            (Some(prev), None) => slope(prev, key),
+            (None, Some(next)) => slope(key, next),
+            (None, None) => 0.0,
+        };
+
+        // Tangents of cubic keys are scaled by the value difference and the duration of a span
+        // (see `cubicf`), so convert the slope to that space.
+        let tangent = |neighbour: Option<Vector2<f32>>| {
+            neighbour.map_or(0.0, |neighbour| {
+                let dy = (neighbour.y - key.y).abs();
+                if dy > f32::EPSILON {
+                    slope * (neighbour.x - key.x).abs() / dy
+                } else {
+                    0.0
+                }
+            })
+        };
+
+        Some((tangent(prev), tangent(next)))
+    }
+
+    /// Inserts a new key at the given location, so the shape of the curve stays the same: the new
+    /// key gets the value of the curve at the location, and tangents of the new key and its
+    /// neighbours are adjusted accordingly (the shape is preserved exactly for unweighted tangents).
+    /// Keys outside of the curve are linear. Returns the id of the new key or the id of an existing
+    /// key at the location.
+    pub fn insert_key(&mut self, location: f32) -> Uuid {
+        if let Some(existing) = self
+            .keys
+            .iter()
+            .find(|k| (k.position.x - location).abs() <= f32::EPSILON)
+        {
+            return existing.id;
+        }
+
+        self.sort_keys();
+        let value = self.curve().value_at(location);
+        let next = self
+            .keys
+            .iter()
+            .position(|k| k.position.x > location)
+            .unwrap_or(self.keys.len());
+
+        let (kind, tangent_mode) = match next.checked_sub(1).filter(|_| next < self.keys.len()) {
+            Some(prev) => self.split_span(prev, location, value),
+            None => (CurveKeyKind::Linear, Default::default()),
+        };
+
+        let id = Uuid::new_v4();
+        self.keys.insert(
+            next,
+            CurveKeyView {
+                position: Vector2::new(location, value),
+                kind,
+                id,
+                tangent_mode,
+            },
+        );
+        id
+    }
+
+    // Adjusts tangents of the keys of the span that starts at the given key, so a new key could be
+    // inserted at the given location without changing the shape. Returns the kind and the tangent
+    // mode of the new key.
+    fn split_span(
+        &mut self,
+        left: usize,
+        location: f32,
+        value: f32,
+    ) -> (CurveKeyKind, TangentMode) {
+        let (p0, x0) = (self.keys[left].position.y, self.keys[left].position.x);
+        let (p1, x1) = (
+            self.keys[left + 1].position.y,
+            self.keys[left + 1].position.x,
+        );
+        let t = (location - x0) / (x1 - x0);
+
+        let m0 = match self.keys[left].kind {
+            CurveKeyKind::Constant => return (CurveKeyKind::Constant, Default::default()),
+            CurveKeyKind::Linear => return (CurveKeyKind::Linear, Default::default()),
+            CurveKeyKind::Cubic { right_tangent, .. } => right_tangent,
+        };
+        let m1 = match self.keys[left + 1].kind {
+            CurveKeyKind::Cubic { left_tangent, .. } => left_tangent,
+            _ => 0.0,
+        };
+
+        // Tangents of cubic keys are scaled by the value difference of a span (see `cubicf`), so
+        // the derivatives must be converted to the new spans.
+        let to_tangent = |derivative: f32, delta: f32| {
+            if delta.abs() > f32::EPSILON {
+                derivative / delta.abs()
+            } else {
+                0.0
+            }
+        };
+        let scale = (p1 - p0).abs();
+        let derivative = cubicf_derivative(p0, p1, t, m0, m1);
+
+        if let CurveKeyKind::Cubic { right_tangent, .. } = &mut self.keys[left].kind {
+            *right_tangent = to_tangent(m0 * scale * t, value - p0);
+        }
+        if let CurveKeyKind::Cubic { left_tangent, .. } = &mut self.keys[left + 1].kind {
+            *left_tangent = to_tangent(m1 * scale * (1.0 - t), p1 - value);
+        }
+
+        (
+            CurveKeyKind::Cubic {
+                left_tangent: to_tangent(derivative * t, value - p0),
+                right_tangent: to_tangent(derivative * (1.0 - t), p1 - value),
+            },
+            TangentMode {
+                // Tangents of the new key are different in general, since they depend on the spans.
+                broken: true,
+                ..Default::default()
+            },
+        )
+    }
+
     pub fn curve(&self) -> Curve {
         let mut curve = Curve::from(
             self.keys
                 .iter()
                 .map(|k| {
-                    let mut key = CurveKey::new(k.position.x, k.position.y, k.kind.clone());
+                    let mut key = CurveKey::new(k.position.x, k.position.y, k.kind.clone())
+                        .with_tangent_mode(k.tangent_mode);
                     key.id = k.id;
                     key
                 })
                 .collect::<Vec<_>>(),
         );
         curve.set_id(self.id);
+        curve.set_name(&self.name);
+        curve.set_pre_extrapolation(self.pre_extrapolation);
+        curve.set_post_extrapolation(self.post_extrapolation);
         curve
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::curve::{Curve, CurveExtrapolation, CurveKey, CurveKeyKind},
+        curve::key::KeyContainer,
+    };
+
+    #[test]
+    fn test_key_container_keeps_curve_name() {
+        let mut curve = Curve::from(vec![CurveKey::new(0.0, 1.0, CurveKeyKind::Linear)]);
+        curve.set_name("Alpha");
+        curve.set_post_extrapolation(CurveExtrapolation::PingPong);
+
+        let container = KeyContainer::from(&curve);
+        assert_eq!(container.name(), "Alpha");
+
+        let restored = container.curve();
+        assert_eq!(restored.name(), "Alpha");
+        assert_eq!(restored.id(), curve.id());
+        assert_eq!(restored.keys().len(), 1);
+        assert_eq!(restored.pre_extrapolation(), CurveExtrapolation::Constant);
+        assert_eq!(restored.post_extrapolation(), CurveExtrapolation::PingPong);
+    }
+
+    #[test]
+    fn test_auto_smooth_tangents() {
+        let mut container = KeyContainer::from(&Curve::from(vec![
+            CurveKey::new(0.0, 0.0, CurveKeyKind::new_cubic(0.0, 0.0)),
+            CurveKey::new(1.0, 1.0, CurveKeyKind::new_cubic(0.0, 0.0)),
+            CurveKey::new(2.0, 2.0, CurveKeyKind::new_cubic(0.0, 0.0)),
+        ]));
+        assert_eq!(container.auto_smooth_tangents(3), None);
+
+        for index in 0..3 {
+            let (left_tangent, right_tangent) = container.auto_smooth_tangents(index).unwrap();
+            container.key_index_mut(index).unwrap().kind = CurveKeyKind::Cubic {
+                left_tangent,
+                right_tangent,
+            };
+        }
+
+        // Keys on a line must give a straight line.
+        let curve = container.curve();
+        for location in [0.25, 0.5, 1.3, 1.75] {
+            assert!((curve.value_at(location) - location).abs() < 1.0e-5);
+        }
+    }
+
+    #[test]
+    fn test_insert_key_keeps_shape() {
+        let curve = Curve::from(vec![
+            CurveKey::new(0.0, 0.0, CurveKeyKind::new_cubic(0.3, -0.2)),
+            CurveKey::new(2.0, 3.0, CurveKeyKind::new_cubic(-0.5, 0.7)),
+            CurveKey::new(3.0, 1.0, CurveKeyKind::Linear),
+            CurveKey::new(4.0, 2.0, CurveKeyKind::Linear),
+        ]);
+        let mut container = KeyContainer::from(&curve);
+
+        let id = container.insert_key(0.7);
+        assert_eq!(container.keys().len(), 5);
+        assert_eq!(container.keys()[1].id, id);
+        assert_eq!(container.insert_key(0.7), id);
+        container.insert_key(2.4);
+        container.insert_key(3.5);
+        container.insert_key(5.0);
+        assert_eq!(container.keys().len(), 8);
+
+        let new_curve = container.curve();
+        for i in 0..=50 {
+            let location = i as f32 * 0.1;
+            assert!((curve.value_at(location) - new_curve.value_at(location)).abs() < 1.0e-4);
+        }
+    }
+}
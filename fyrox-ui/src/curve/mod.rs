@@ -3,24 +3,32 @@ use crate::{
     core::{
         algebra::{Matrix3, Point2, SimdPartialOrd, Vector2, Vector3},
         color::Color,
-        curve::{Curve, CurveKeyKind},
-        math::{cubicf, lerpf, wrap_angle, Rect},
+        curve::{Curve, CurveExtrapolation, CurveKeyKind, TangentMode},
+        log::Log,
+        math::{cubicf, lerpf, weighted_cubicf, wrap_angle, Rect},
         pool::Handle,
         uuid::Uuid,
     },
     core::{reflect::prelude::*, visitor::prelude::*},
-    curve::key::{CurveKeyView, KeyContainer},
+    curve::{
+        io::{keys_from_clipboard_string, keys_to_clipboard_string},
+        key::{CurveKeyView, KeyContainer},
+    },
     define_constructor,
     draw::{CommandTexture, Draw, DrawingContext},
+    file_browser::{FileBrowserMode, FileSelectorBuilder, FileSelectorMessage, Filter},
     formatted_text::{FormattedText, FormattedTextBuilder},
     grid::{Column, GridBuilder, Row},
+    input::InputBindings,
     menu::{MenuItemBuilder, MenuItemContent, MenuItemMessage},
     message::{ButtonState, KeyCode, MessageDirection, MouseButton, UiMessage},
     numeric::{NumericUpDownBuilder, NumericUpDownMessage},
-    popup::PopupBuilder,
+    popup::{Placement, PopupBuilder, PopupMessage},
     stack_panel::StackPanelBuilder,
+    style::Style,
     text::TextBuilder,
     widget::{Widget, WidgetBuilder, WidgetMessage},
+    window::{WindowBuilder, WindowMessage, WindowTitle},
     BuildContext, Control, RcUiNodeHandle, Thickness, UiNode, UserInterface, VerticalAlignment,
 };
 use fxhash::FxHashSet;
@@ -28,22 +36,102 @@ use std::sync::mpsc::Sender;
 use std::{
     any::{Any, TypeId},
     cell::{Cell, RefCell},
-    ops::{Deref, DerefMut},
+    ops::{Deref, DerefMut, Range},
+    path::{Path, PathBuf},
 };
 
+pub mod io;
 pub mod key;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum CurveEditorMessage {
-    Sync(Curve),
+    /// Sets curves of the editor (when sent to the widget) or informs about changes in the curves
+    /// made in the editor (when sent from the widget).
+    Sync(Vec<Curve>),
     ViewPosition(Vector2<f32>),
     Zoom(Vector2<f32>),
+    /// Adjusts view position and zoom so the selected keys (or all the keys of visible curves if
+    /// there's no selection) fill the editor. The same could be done by pressing `F`.
     ZoomToFit {
         /// Should the zoom to fit be performed on some of the next update cycle (up to 10 frames delay), or immediately when
         /// processing the message.
         after_layout: bool,
     },
     HighlightZones(Vec<HighlightZone>),
+    /// Reverts the last change of the curve made in the editor (key moves, tangent edits, additions,
+    /// removals, etc.). The editor sends [`CurveEditorMessage::Sync`] with the restored curve.
+    Undo,
+    /// Re-applies the last change reverted by [`CurveEditorMessage::Undo`].
+    Redo,
+    /// Copies selected keys to the clipboard.
+    CopySelection,
+    /// Copies selected keys to the clipboard and removes them from the curve.
+    CutSelection,
+    /// Pastes keys from the clipboard, so the first pasted key will be at the given position (in
+    /// screen coordinates). Keys copied from another curve editor could be pasted too.
+    Paste(Vector2<f32>),
+    /// Sets a brush of a curve with the given index.
+    CurveBrush {
+        index: usize,
+        brush: Brush,
+    },
+    /// Shows or hides a curve with the given index. Hidden curves cannot be edited.
+    CurveVisibility {
+        index: usize,
+        visible: bool,
+    },
+    /// Locks or unlocks a curve with the given index. Locked curves are visible, but cannot be edited.
+    CurveLock {
+        index: usize,
+        locked: bool,
+    },
+    /// Sets snapping steps (in values space) of the X and Y axes that are used when dragging keys.
+    /// Zero step disables snapping along the respective axis. Snapping could be temporarily disabled
+    /// by holding Ctrl while dragging.
+    SnapStep(Vector2<f32>),
+    /// Shows a vertical playback cursor at the given location (in values space) along with the
+    /// values of the visible curves at that location. Could be used to scrub a timeline and see
+    /// where on the curve playback currently is.
+    SetCursorPosition(f32),
+    /// Hides the playback cursor.
+    HideCursor,
+    /// Enables or disables read-only mode. Curves cannot be edited in this mode in any way, but the
+    /// view still could be panned and zoomed. It is useful to show curves as a non-editable preview.
+    ReadOnly(bool),
+    /// Selects keys with the given ids, keys of hidden or locked curves are ignored. The editor sends
+    /// this message with [`MessageDirection::FromWidget`] every time its selection changes, so external
+    /// tools could synchronize their own selection with the editor.
+    SelectKeys(Vec<Uuid>),
+    /// Highlights a range of locations (in values space), for example a range of animation frames
+    /// that is selected in a timeline. An empty range (`start >= end`) removes the highlight.
+    HighlightRange {
+        start: f32,
+        end: f32,
+    },
+    /// Writes the keys of all curves to a file. The format is picked by the extension of the file,
+    /// see [`io::CurveKeysFormat`] for supported formats.
+    ExportKeys(PathBuf),
+    /// Reads the keys from a file (see [`io::CurveKeysFormat`] for supported formats) and adds them
+    /// to the curves with the same indices. If `replace` is `true`, the existing keys of these
+    /// curves are removed first. Keys of hidden or locked curves are left untouched.
+    ImportKeys {
+        path: PathBuf,
+        replace: bool,
+    },
+    /// Sets input bindings of the editor, for example to pan the view without a middle mouse button.
+    /// See [`InputBindings`] docs for more info.
+    InputBindings(InputBindings),
+    /// Stores current view state (zoom and view position) as a bookmark with the given name, a
+    /// bookmark with the same name is replaced. Bookmarks could be recalled from the context menu
+    /// of the editor or by [`CurveEditorMessage::RecallViewBookmark`].
+    StoreViewBookmark(String),
+    /// Restores the view state stored in a bookmark with the given name.
+    RecallViewBookmark(String),
+    /// Removes a bookmark with the given name.
+    RemoveViewBookmark(String),
+    /// Sets view bookmarks of the editor (when sent to the widget) or informs about changes in the
+    /// bookmarks (when sent from the widget), so they could be saved somewhere.
+    ViewBookmarks(Vec<ViewBookmark>),
 
     // Internal messages. Use only when you know what you're doing.
     // These are internal because you must use Sync message to request changes
@@ -51,23 +139,77 @@ pub enum CurveEditorMessage {
     ChangeSelectedKeysKind(CurveKeyKind),
     ChangeSelectedKeysValue(f32),
     ChangeSelectedKeysLocation(f32),
+    ChangeSelectedKeysLeftTangent(f32),
+    ChangeSelectedKeysRightTangent(f32),
+    // Makes tangents of the selected cubic keys broken (`true`) or unified (`false`).
+    ChangeSelectedKeysBrokenTangents(bool),
+    // Makes tangents of the selected cubic keys weighted or unweighted.
+    ChangeSelectedKeysWeightedTangents(bool),
+    // Makes the selected keys cubic with horizontal tangents.
+    FlattenSelectedKeysTangents,
+    // Makes the selected keys cubic with tangents calculated from the neighbouring keys.
+    AutoSmoothSelectedKeysTangents,
+    // Changes extrapolation of the curves of the selected keys (or all editable curves if there's
+    // no selection) before the first key (`pre == true`) or after the last key.
+    ChangeExtrapolation {
+        pre: bool,
+        extrapolation: CurveExtrapolation,
+    },
+    // Opens a file selector to export or import the keys.
+    OpenFileDialog(FileDialogAction),
     RemoveSelection,
     // Position in screen coordinates.
     AddKey(Vector2<f32>),
 }
 
 impl CurveEditorMessage {
-    define_constructor!(CurveEditorMessage:Sync => fn sync(Curve), layout: false);
+    define_constructor!(CurveEditorMessage:Sync => fn sync(Vec<Curve>), layout: false);
     define_constructor!(CurveEditorMessage:ViewPosition => fn view_position(Vector2<f32>), layout: false);
     define_constructor!(CurveEditorMessage:Zoom => fn zoom(Vector2<f32>), layout: false);
     define_constructor!(CurveEditorMessage:ZoomToFit => fn zoom_to_fit(after_layout: bool), layout: true);
     define_constructor!(CurveEditorMessage:HighlightZones => fn hightlight_zones(Vec<HighlightZone>), layout: false);
+    define_constructor!(CurveEditorMessage:Undo => fn undo(), layout: false);
+    define_constructor!(CurveEditorMessage:Redo => fn redo(), layout: false);
+    define_constructor!(CurveEditorMessage:CopySelection => fn copy_selection(), layout: false);
+    define_constructor!(CurveEditorMessage:CutSelection => fn cut_selection(), layout: false);
+    define_constructor!(CurveEditorMessage:Paste => fn paste(Vector2<f32>), layout: false);
+    define_constructor!(CurveEditorMessage:CurveBrush => fn curve_brush(index: usize, brush: Brush), layout: false);
+    define_constructor!(CurveEditorMessage:CurveVisibility => fn curve_visibility(index: usize, visible: bool), layout: false);
+    define_constructor!(CurveEditorMessage:CurveLock => fn curve_lock(index: usize, locked: bool), layout: false);
+    define_constructor!(CurveEditorMessage:SnapStep => fn snap_step(Vector2<f32>), layout: false);
+    define_constructor!(CurveEditorMessage:SetCursorPosition => fn set_cursor_position(f32), layout: false);
+    define_constructor!(CurveEditorMessage:HideCursor => fn hide_cursor(), layout: false);
+    define_constructor!(CurveEditorMessage:ReadOnly => fn read_only(bool), layout: false);
+    define_constructor!(CurveEditorMessage:SelectKeys => fn select_keys(Vec<Uuid>), layout: false);
+    define_constructor!(CurveEditorMessage:HighlightRange => fn highlight_range(start: f32, end: f32), layout: false);
+    define_constructor!(CurveEditorMessage:ExportKeys => fn export_keys(PathBuf), layout: false);
+    define_constructor!(CurveEditorMessage:ImportKeys => fn import_keys(path: PathBuf, replace: bool), layout: false);
+    define_constructor!(CurveEditorMessage:InputBindings => fn input_bindings(InputBindings), layout: false);
+    define_constructor!(CurveEditorMessage:StoreViewBookmark => fn store_view_bookmark(String), layout: false);
+    define_constructor!(CurveEditorMessage:RecallViewBookmark => fn recall_view_bookmark(String), layout: false);
+    define_constructor!(CurveEditorMessage:RemoveViewBookmark => fn remove_view_bookmark(String), layout: false);
+    define_constructor!(CurveEditorMessage:ViewBookmarks => fn view_bookmarks(Vec<ViewBookmark>), layout: false);
     // Internal. Use only when you know what you're doing.
     define_constructor!(CurveEditorMessage:RemoveSelection => fn remove_selection(), layout: false);
     define_constructor!(CurveEditorMessage:ChangeSelectedKeysKind => fn change_selected_keys_kind(CurveKeyKind), layout: false);
     define_constructor!(CurveEditorMessage:ChangeSelectedKeysValue => fn change_selected_keys_value(f32), layout: false);
     define_constructor!(CurveEditorMessage:ChangeSelectedKeysLocation => fn change_selected_keys_location(f32), layout: false);
+    define_constructor!(CurveEditorMessage:ChangeSelectedKeysLeftTangent => fn change_selected_keys_left_tangent(f32), layout: false);
+    define_constructor!(CurveEditorMessage:ChangeSelectedKeysRightTangent => fn change_selected_keys_right_tangent(f32), layout: false);
+    define_constructor!(CurveEditorMessage:ChangeSelectedKeysBrokenTangents => fn change_selected_keys_broken_tangents(bool), layout: false);
+    define_constructor!(CurveEditorMessage:ChangeSelectedKeysWeightedTangents => fn change_selected_keys_weighted_tangents(bool), layout: false);
+    define_constructor!(CurveEditorMessage:FlattenSelectedKeysTangents => fn flatten_selected_keys_tangents(), layout: false);
+    define_constructor!(CurveEditorMessage:AutoSmoothSelectedKeysTangents => fn auto_smooth_selected_keys_tangents(), layout: false);
+    define_constructor!(CurveEditorMessage:ChangeExtrapolation => fn change_extrapolation(pre: bool, extrapolation: CurveExtrapolation), layout: false);
     define_constructor!(CurveEditorMessage:AddKey => fn add_key(Vector2<f32>), layout: false);
+    define_constructor!(CurveEditorMessage:OpenFileDialog => fn open_file_dialog(FileDialogAction), layout: false);
+}
+
+/// An action that is performed with a file picked in the file selector of the [`CurveEditor`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FileDialogAction {
+    Export,
+    Import { replace: bool },
 }
 
 /// Highlight zone in values space.
@@ -77,10 +219,142 @@ pub struct HighlightZone {
     pub brush: Brush,
 }
 
+/// A named view state of the [`CurveEditor`], see [`CurveEditorMessage::StoreViewBookmark`].
+#[derive(Clone, Debug, PartialEq, Visit, Reflect, Default)]
+pub struct ViewBookmark {
+    pub name: String,
+    pub zoom: Vector2<f32>,
+    pub view_position: Vector2<f32>,
+}
+
+/// Display settings of a curve in the [`CurveEditor`].
+#[derive(Clone, Debug, PartialEq, Visit, Reflect)]
+pub struct CurveStyle {
+    /// A brush that is used to draw the curve. `None` means that the editor will pick a brush
+    /// automatically.
+    pub brush: Option<Brush>,
+    /// Hidden curves are not drawn and cannot be edited.
+    pub visible: bool,
+    /// Locked curves are drawn, but cannot be edited.
+    pub locked: bool,
+}
+
+impl Default for CurveStyle {
+    fn default() -> Self {
+        Self {
+            brush: None,
+            visible: true,
+            locked: false,
+        }
+    }
+}
+
+fn make_view_bookmark_items(
+    bookmarks: &[ViewBookmark],
+    ctx: &mut BuildContext,
+) -> Vec<Handle<UiNode>> {
+    bookmarks
+        .iter()
+        .map(|bookmark| {
+            MenuItemBuilder::new(WidgetBuilder::new())
+                .with_content(MenuItemContent::text(&bookmark.name))
+                .build(ctx)
+        })
+        .collect()
+}
+
+fn snap_value(value: f32, step: f32) -> f32 {
+    if step > 0.0 {
+        (value / step).round() * step
+    } else {
+        value
+    }
+}
+
+fn snap_position(position: Vector2<f32>, step: Vector2<f32>) -> Vector2<f32> {
+    Vector2::new(
+        snap_value(position.x, step.x),
+        snap_value(position.y, step.y),
+    )
+}
+
+// Returns a function that maps initial positions of the keys in the transform box with the given
+// initial bounds (in values space) to the new positions, when the handle is dragged by the given
+// offset (in values space too).
+fn transform_box_mapping(
+    handle: TransformBoxHandle,
+    initial_bounds: Rect<f32>,
+    offset: Vector2<f32>,
+) -> impl Fn(Vector2<f32>) -> Vector2<f32> {
+    // Returns a pivot and a scale along an axis. The selection cannot be flipped.
+    fn axis(side: i8, min: f32, size: f32, offset: f32) -> (f32, f32) {
+        let scale = |new_size: f32| {
+            if size > f32::EPSILON {
+                (new_size / size).max(0.0)
+            } else {
+                1.0
+            }
+        };
+        match side.signum() {
+            1 => (min, scale(size + offset)),
+            -1 => (min + size, scale(size - offset)),
+            _ => (min, 1.0),
+        }
+    }
+
+    let (translation, pivot, scale) = match handle {
+        TransformBoxHandle::Body => (offset, Vector2::default(), Vector2::new(1.0, 1.0)),
+        TransformBoxHandle::Scale { x, y } => {
+            let (pivot_x, scale_x) = axis(x, initial_bounds.x(), initial_bounds.w(), offset.x);
+            let (pivot_y, scale_y) = axis(y, initial_bounds.y(), initial_bounds.h(), offset.y);
+            (
+                Vector2::default(),
+                Vector2::new(pivot_x, pivot_y),
+                Vector2::new(scale_x, scale_y),
+            )
+        }
+    };
+
+    move |position| pivot + (position - pivot).component_mul(&scale) + translation
+}
+
+fn clamp_position(
+    position: Vector2<f32>,
+    x_bounds: Option<&Range<f32>>,
+    y_bounds: Option<&Range<f32>>,
+) -> Vector2<f32> {
+    Vector2::new(
+        clamp_to_range(position.x, x_bounds),
+        clamp_to_range(position.y, y_bounds),
+    )
+}
+
+// Unlike `f32::clamp` it does not panic if the range is inverted.
+fn clamp_to_range(value: f32, range: Option<&Range<f32>>) -> f32 {
+    range.map_or(value, |range| value.max(range.start).min(range.end))
+}
+
+// Brushes of the curves that do not have a brush specified explicitly.
+const CURVE_PALETTE: [Color; 6] = [
+    Color::opaque(220, 80, 80),
+    Color::opaque(80, 200, 80),
+    Color::opaque(90, 130, 230),
+    Color::opaque(220, 200, 70),
+    Color::opaque(80, 200, 210),
+    Color::opaque(200, 90, 210),
+];
+
+/// Curve editor allows to edit one or more curves at once (for example, RGB or XYZ channels). Every
+/// curve has its own [`CurveStyle`] that defines its brush and whether it is visible or locked. When
+/// the editor has more than one curve, it shows a legend with visibility (`V`) and lock (`L`)
+/// toggles of every curve.
 #[derive(Clone, Visit, Reflect, Debug)]
 pub struct CurveEditor {
     widget: Widget,
-    key_container: KeyContainer,
+    #[visit(optional)]
+    curves: Vec<KeyContainer>,
+    #[visit(optional)]
+    styles: Vec<CurveStyle>,
     zoom: Vector2<f32>,
     view_position: Vector2<f32>,
     // Transforms a point from local to view coordinates.
@@ -111,9 +385,14 @@ pub struct CurveEditor {
     selection: Option<Selection>,
     handle_radius: f32,
     context_menu: ContextMenu,
+    #[visit(optional)]
+    key_properties_popup: KeyPropertiesPopup,
     #[visit(skip)]
     #[reflect(hidden)]
     text: RefCell<FormattedText>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    legend_text: RefCell<FormattedText>,
     view_bounds: Option<Rect<f32>>,
     show_x_values: bool,
     show_y_values: bool,
@@ -121,9 +400,71 @@ pub struct CurveEditor {
     min_zoom: Vector2<f32>,
     max_zoom: Vector2<f32>,
     highlight_zones: Vec<HighlightZone>,
+    #[visit(optional)]
+    snap_step: Vector2<f32>,
+    #[visit(optional)]
+    x_bounds: Option<Range<f32>>,
+    #[visit(optional)]
+    y_bounds: Option<Range<f32>>,
+    #[visit(optional)]
+    read_only: bool,
+    #[visit(optional)]
+    highlight_range: Option<Range<f32>>,
+    #[visit(optional)]
+    input_bindings: InputBindings,
+    #[visit(optional)]
+    view_bookmarks: Vec<ViewBookmark>,
+    // A curve under the mouse cursor, a key could be inserted into it by double click.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    hovered_curve: Option<usize>,
+    // Currently opened file selector, if any, and what to do with the selected file.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    file_dialog: Option<(Handle<UiNode>, FileDialogAction)>,
+    // Location of the playback cursor in values space.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    cursor_position: Option<f32>,
     #[visit(skip)]
     #[reflect(hidden)]
     zoom_to_fit_timer: Option<usize>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    history: History,
+    // Keys before the first arrow key press, used to commit keyboard nudging as a single change.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    nudge_initial_keys: Option<Vec<KeyContainer>>,
+    // In screen coordinates.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    last_mouse_pos: Vector2<f32>,
+}
+
+// Internal undo/redo stack of the editor. It stores full copies of keys, which is fine since curves
+// usually have just a few dozens of keys.
+#[derive(Clone, Debug, Default)]
+struct History {
+    undo: Vec<Vec<KeyContainer>>,
+    redo: Vec<Vec<KeyContainer>>,
+}
+
+impl History {
+    const MAX_LEN: usize = 128;
+
+    fn push(&mut self, keys: Vec<KeyContainer>) {
+        if self.undo.len() >= Self::MAX_LEN {
+            self.undo.remove(0);
+        }
+        self.undo.push(keys);
+        self.redo.clear();
+    }
+
+    fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
 }
 
 crate::define_widget_deref!(CurveEditor);
@@ -135,19 +476,130 @@ struct ContextMenu {
     widget: RcUiNodeHandle,
     add_key: Handle<UiNode>,
     remove: Handle<UiNode>,
+    copy: Handle<UiNode>,
+    cut: Handle<UiNode>,
+    paste: Handle<UiNode>,
     key: Handle<UiNode>,
     make_constant: Handle<UiNode>,
     make_linear: Handle<UiNode>,
     make_cubic: Handle<UiNode>,
+    #[visit(optional)]
+    broken_tangents: Handle<UiNode>,
+    #[visit(optional)]
+    weighted_tangents: Handle<UiNode>,
+    #[visit(optional)]
+    flatten_tangents: Handle<UiNode>,
+    #[visit(optional)]
+    auto_smooth_tangents: Handle<UiNode>,
     zoom_to_fit: Handle<UiNode>,
     key_properties: Handle<UiNode>,
     key_value: Handle<UiNode>,
     key_location: Handle<UiNode>,
+    #[visit(optional)]
+    properties: Handle<UiNode>,
+    #[visit(optional)]
+    export_keys: Handle<UiNode>,
+    #[visit(optional)]
+    import_keys: Handle<UiNode>,
+    #[visit(optional)]
+    import_keys_replace: Handle<UiNode>,
+    #[visit(optional)]
+    view_bookmarks: Handle<UiNode>,
+    // Menu items of view bookmarks, in the same order as the bookmarks.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    view_bookmark_items: Vec<Handle<UiNode>>,
+    // Menu items of pre (`true`) and post extrapolation modes.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    extrapolation_items: Vec<(Handle<UiNode>, bool, CurveExtrapolation)>,
+}
+
+// A popup with exact numeric values of the selected key.
+#[derive(Clone, Visit, Reflect, Debug, Default)]
+struct KeyPropertiesPopup {
+    #[visit(skip)]
+    #[reflect(hidden)]
+    popup: Option<RcUiNodeHandle>,
+    location: Handle<UiNode>,
+    value: Handle<UiNode>,
+    left_tangent: Handle<UiNode>,
+    right_tangent: Handle<UiNode>,
+}
+
+impl KeyPropertiesPopup {
+    fn new(ctx: &mut BuildContext) -> Self {
+        fn make_row(
+            ctx: &mut BuildContext,
+            row: usize,
+            name: &str,
+            children: &mut Vec<Handle<UiNode>>,
+        ) -> Handle<UiNode> {
+            children.push(
+                TextBuilder::new(
+                    WidgetBuilder::new()
+                        .with_vertical_alignment(VerticalAlignment::Center)
+                        .with_margin(Thickness::uniform(1.0))
+                        .on_row(row)
+                        .on_column(0),
+                )
+                .with_text(name)
+                .build(ctx),
+            );
+            let field = NumericUpDownBuilder::<f32>::new(
+                WidgetBuilder::new()
+                    .with_margin(Thickness::uniform(1.0))
+                    .on_row(row)
+                    .on_column(1),
+            )
+            .build(ctx);
+            children.push(field);
+            field
+        }
+
+        let mut children = Vec::new();
+        let location = make_row(ctx, 0, "Location", &mut children);
+        let value = make_row(ctx, 1, "Value", &mut children);
+        let left_tangent = make_row(ctx, 2, "Left Tangent", &mut children);
+        let right_tangent = make_row(ctx, 3, "Right Tangent", &mut children);
+
+        let popup = PopupBuilder::new(WidgetBuilder::new().with_width(220.0))
+            .with_content(
+                GridBuilder::new(WidgetBuilder::new().with_children(children))
+                    .add_column(Column::auto())
+                    .add_column(Column::stretch())
+                    .add_row(Row::strict(22.0))
+                    .add_row(Row::strict(22.0))
+                    .add_row(Row::strict(22.0))
+                    .add_row(Row::strict(22.0))
+                    .build(ctx),
+            )
+            .build(ctx);
+
+        Self {
+            popup: Some(RcUiNodeHandle::new(popup, ctx.sender())),
+            location,
+            value,
+            left_tangent,
+            right_tangent,
+        }
+    }
+
+    fn handle(&self) -> Handle<UiNode> {
+        self.popup.as_ref().map(|p| **p).unwrap_or_default()
+    }
+}
+
+// Identifies a key of a particular curve of the editor.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+struct SelectedKey {
+    curve: usize,
+    id: Uuid,
 }
 
 #[derive(Clone, Debug)]
 struct DragEntry {
-    key: Uuid,
+    key: SelectedKey,
     initial_position: Vector2<f32>,
 }
 
@@ -157,41 +609,81 @@ enum OperationContext {
         // In local coordinates.
         initial_mouse_pos: Vector2<f32>,
         entries: Vec<DragEntry>,
+        initial_keys: Vec<KeyContainer>,
     },
     MoveView {
         initial_mouse_pos: Vector2<f32>,
         initial_view_pos: Vector2<f32>,
+        button: MouseButton,
     },
     DragTangent {
+        curve: usize,
         key: usize,
         left: bool,
+        initial_keys: Vec<KeyContainer>,
     },
     BoxSelection {
-        // In local coordinates.
+        // In screen coordinates.
         initial_mouse_pos: Vector2<f32>,
         min: Cell<Vector2<f32>>,
         max: Cell<Vector2<f32>>,
+        mode: BoxSelectionMode,
+    },
+    TransformKeys {
+        handle: TransformBoxHandle,
+        // In local coordinates.
+        initial_mouse_pos: Vector2<f32>,
+        initial_bounds: Rect<f32>,
+        entries: Vec<DragEntry>,
+        initial_keys: Vec<KeyContainer>,
     },
 }
 
+// A part of the transform box that is drawn around multiple selected keys.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum TransformBoxHandle {
+    // Moves the selection as a unit.
+    Body,
+    // Scales the selection relative to the opposite side of the box. `x` and `y` are -1, 0 or 1 and
+    // define the side of the box in values space (`y == 1` is the top side), zero means that the
+    // selection is not scaled along the respective axis.
+    Scale { x: i8, y: i8 },
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum BoxSelectionMode {
+    // Replaces current selection.
+    Replace,
+    // Adds keys to current selection (Shift).
+    Add,
+    // Removes keys from current selection (Ctrl).
+    Subtract,
+}
+
 #[derive(Clone, Debug)]
 enum Selection {
-    Keys { keys: FxHashSet<Uuid> },
+    Keys { keys: FxHashSet<SelectedKey> },
     // It is ok to use index directly in case of tangents since
     // we won't change position of keys so index will be valid.
-    LeftTangent { key: usize },
-    RightTangent { key: usize },
+    LeftTangent { curve: usize, key: usize },
+    RightTangent { curve: usize, key: usize },
 }
 
 #[derive(Copy, Clone)]
 enum PickResult {
-    Key(usize),
-    LeftTangent(usize),
-    RightTangent(usize),
+    Key { curve: usize, key: usize },
+    LeftTangent { curve: usize, key: usize },
+    RightTangent { curve: usize, key: usize },
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum LegendButton {
+    Visibility,
+    Lock,
 }
 
 impl Selection {
-    fn single_key(key: Uuid) -> Self {
+    fn single_key(key: SelectedKey) -> Self {
         let mut keys = FxHashSet::default();
         keys.insert(key);
         Self::Keys { keys }
@@ -212,10 +704,15 @@ impl Control for CurveEditor {
         self.update_matrices();
         self.draw_background(ctx);
         self.draw_highlight_zones(ctx);
+        self.draw_highlight_range(ctx);
+        self.draw_out_of_bounds_regions(ctx);
         self.draw_grid(ctx);
-        self.draw_curve(ctx);
+        self.draw_curves(ctx);
         self.draw_keys(ctx);
+        self.draw_transform_box(ctx);
+        self.draw_cursor(ctx);
         self.draw_operation(ctx);
+        self.draw_legend(ctx);
         ctx.transform_stack.pop();
     }
 
@@ -228,18 +725,121 @@ impl Control for CurveEditor {
                     WidgetMessage::KeyUp(KeyCode::Delete) => {
                         self.remove_selection(ui);
                     }
+                    WidgetMessage::KeyUp(
+                        KeyCode::ArrowLeft
+                        | KeyCode::ArrowRight
+                        | KeyCode::ArrowUp
+                        | KeyCode::ArrowDown,
+                    ) => {
+                        self.commit_nudge(ui);
+                    }
+                    WidgetMessage::KeyDown(key) => {
+                        let modifiers = ui.keyboard_modifiers();
+                        if self.input_bindings.is_zoom_to_fit(*key, modifiers) {
+                            self.zoom_to_fit(&ui.sender);
+                            message.set_handled(true);
+                        } else if modifiers.control {
+                            let handled = match key {
+                                KeyCode::KeyZ if modifiers.shift => {
+                                    self.redo(ui);
+                                    true
+                                }
+                                KeyCode::KeyZ => {
+                                    self.undo(ui);
+                                    true
+                                }
+                                KeyCode::KeyY => {
+                                    self.redo(ui);
+                                    true
+                                }
+                                KeyCode::KeyC => {
+                                    self.copy_selection(ui);
+                                    true
+                                }
+                                KeyCode::KeyX => {
+                                    self.cut_selection(ui);
+                                    true
+                                }
+                                KeyCode::KeyV => {
+                                    self.paste(self.last_mouse_pos, ui);
+                                    true
+                                }
+                                _ => false,
+                            };
+                            // Hosts must not handle the shortcuts (for example, paste scene nodes or undo
+                            // scene commands in the editor) when the curve editor is focused.
+                            if handled {
+                                message.set_handled(true);
+                            }
+                        } else {
+                            let direction = match key {
+                                KeyCode::ArrowLeft => Some(Vector2::new(-1.0, 0.0)),
+                                KeyCode::ArrowRight => Some(Vector2::new(1.0, 0.0)),
+                                KeyCode::ArrowUp => Some(Vector2::new(0.0, 1.0)),
+                                KeyCode::ArrowDown => Some(Vector2::new(0.0, -1.0)),
+                                _ => None,
+                            };
+                            if let Some(direction) = direction {
+                                // Shift - larger step, Alt - finer step.
+                                let multiplier = if modifiers.shift {
+                                    10.0
+                                } else if modifiers.alt {
+                                    0.1
+                                } else {
+                                    1.0
+                                };
+                                self.nudge_selection(direction.scale(multiplier));
+                                message.set_handled(true);
+                            }
+                        }
+                    }
+                    WidgetMessage::DoubleClick {
+                        button: MouseButton::Left,
+                    } => match self.pick(self.last_mouse_pos) {
+                        Some(PickResult::Key { .. }) => self.open_key_properties(ui),
+                        None => {
+                            if let Some(curve) = self.pick_curve(self.last_mouse_pos) {
+                                self.insert_key(curve, self.last_mouse_pos, ui);
+                            }
+                        }
+                        _ => (),
+                    },
                     WidgetMessage::MouseMove { pos, state } => {
+                        self.last_mouse_pos = *pos;
                         let local_mouse_pos = self.point_to_local_space(*pos);
+                        self.hovered_curve = if self.operation_context.is_none() {
+                            self.pick_curve(*pos)
+                        } else {
+                            None
+                        };
                         if let Some(operation_context) = self.operation_context.as_ref() {
                             match operation_context {
                                 OperationContext::DragKeys {
                                     entries,
                                     initial_mouse_pos,
+                                    ..
                                 } => {
                                     let local_delta = local_mouse_pos - initial_mouse_pos;
+                                    // Ctrl temporarily disables snapping.
+                                    let snap_step = if ui.keyboard_modifiers().control {
+                                        Vector2::default()
+                                    } else {
+                                        self.snap_step
+                                    };
                                     for entry in entries {
-                                        if let Some(key) = self.key_container.key_mut(entry.key) {
-                                            key.position = entry.initial_position + local_delta;
+                                        if let Some(key) = self
+                                            .curves
+                                            .get_mut(entry.key.curve)
+                                            .and_then(|c| c.key_mut(entry.key.id))
+                                        {
+                                            key.position = clamp_position(
+                                                snap_position(
+                                                    entry.initial_position + local_delta,
+                                                    snap_step,
+                                                ),
+                                                self.x_bounds.as_ref(),
+                                                self.y_bounds.as_ref(),
+                                            );
                                         }
                                     }
                                     self.sort_keys();
@@ -247,8 +847,17 @@ impl Control for CurveEditor {
                                 OperationContext::MoveView {
                                     initial_mouse_pos,
                                     initial_view_pos,
+                                    button,
                                 } => {
                                     let d = pos - initial_mouse_pos;
+                                    // Context menu is opened on right mouse button press, it must
+                                    // not stay open when the button is used for panning.
+                                    if *button == MouseButton::Right && d.norm() > 2.0 {
+                                        ui.send_message(PopupMessage::close(
+                                            *self.context_menu.widget,
+                                            MessageDirection::ToWidget,
+                                        ));
+                                    }
                                     let delta = Vector2::new(d.x / self.zoom.x, d.y / self.zoom.y);
                                     ui.send_message(CurveEditorMessage::view_position(
                                         self.handle,
@@ -256,8 +865,14 @@ impl Control for CurveEditor {
                                         initial_view_pos + delta,
                                     ));
                                 }
-                                OperationContext::DragTangent { key, left } => {
-                                    if let Some(key) = self.key_container.key_index_mut(*key) {
+                                OperationContext::DragTangent {
+                                    curve, key, left, ..
+                                } => {
+                                    if let Some(key) = self
+                                        .curves
+                                        .get_mut(*curve)
+                                        .and_then(|c| c.key_index_mut(*key))
+                                    {
                                         let key_pos = key.position;
 
                                         let screen_key_pos = self
@@ -266,6 +881,7 @@ impl Control for CurveEditor {
                                             .transform_point(&Point2::from(key_pos))
                                             .coords;
 
+                                        let mode = &mut key.tangent_mode;
                                         if let CurveKeyKind::Cubic {
                                             left_tangent,
                                             right_tangent,
@@ -277,14 +893,31 @@ impl Control for CurveEditor {
                                             } else {
                                                 local_delta.x = local_delta.x.max(f32::EPSILON);
                                             }
+                                            // Y axis is flipped in screen space.
                                             let tangent =
-                                                (local_delta.y / local_delta.x).clamp(-10e6, 10e6);
+                                                (-local_delta.y / local_delta.x).clamp(-10e6, 10e6);
 
-                                            if *left {
+                                            // Unified tangents are always collinear, so the other
+                                            // handle mirrors the dragged one.
+                                            if *left || !mode.broken {
                                                 *left_tangent = tangent;
-                                            } else {
+                                            }
+                                            if !*left || !mode.broken {
                                                 *right_tangent = tangent;
                                             }
+
+                                            // Length of a weighted handle defines its weight.
+                                            if mode.weighted {
+                                                let weight = (local_delta.norm()
+                                                    / self.handle_radius
+                                                    * TangentMode::DEFAULT_WEIGHT)
+                                                    .clamp(0.01, 1.0);
+                                                if *left {
+                                                    mode.left_weight = weight;
+                                                } else {
+                                                    mode.right_weight = weight;
+                                                }
+                                            }
                                         } else {
                                             unreachable!(
                                                 "attempt to edit tangents of non-cubic curve key!"
@@ -298,8 +931,44 @@ impl Control for CurveEditor {
                                     max,
                                     ..
                                 } => {
-                                    min.set(local_mouse_pos.inf(initial_mouse_pos));
-                                    max.set(local_mouse_pos.sup(initial_mouse_pos));
+                                    min.set(pos.inf(initial_mouse_pos));
+                                    max.set(pos.sup(initial_mouse_pos));
+                                }
+                                OperationContext::TransformKeys {
+                                    handle,
+                                    initial_mouse_pos,
+                                    initial_bounds,
+                                    entries,
+                                    ..
+                                } => {
+                                    let transform = transform_box_mapping(
+                                        *handle,
+                                        *initial_bounds,
+                                        local_mouse_pos - initial_mouse_pos,
+                                    );
+                                    // Ctrl temporarily disables snapping.
+                                    let snap_step = if ui.keyboard_modifiers().control {
+                                        Vector2::default()
+                                    } else {
+                                        self.snap_step
+                                    };
+                                    for entry in entries {
+                                        if let Some(key) = self
+                                            .curves
+                                            .get_mut(entry.key.curve)
+                                            .and_then(|c| c.key_mut(entry.key.id))
+                                        {
+                                            key.position = clamp_position(
+                                                snap_position(
+                                                    transform(entry.initial_position),
+                                                    snap_step,
+                                                ),
+                                                self.x_bounds.as_ref(),
+                                                self.y_bounds.as_ref(),
+                                            );
+                                        }
+                                    }
+                                    self.sort_keys();
                                 }
                             }
                         } else if state.left == ButtonState::Pressed {
@@ -312,36 +981,34 @@ impl Control for CurveEditor {
                                                 .map(|k| DragEntry {
                                                     key: *k,
                                                     initial_position: self
-                                                        .key_container
                                                         .key_ref(*k)
                                                         .map(|k| k.position)
                                                         .unwrap_or_default(),
                                                 })
                                                 .collect::<Vec<_>>(),
                                             initial_mouse_pos: local_mouse_pos,
+                                            initial_keys: self.curves.clone(),
                                         });
                                     }
-                                    Selection::LeftTangent { key } => {
+                                    Selection::LeftTangent { curve, key } => {
                                         self.operation_context =
                                             Some(OperationContext::DragTangent {
+                                                curve: *curve,
                                                 key: *key,
                                                 left: true,
+                                                initial_keys: self.curves.clone(),
                                             })
                                     }
-                                    Selection::RightTangent { key } => {
+                                    Selection::RightTangent { curve, key } => {
                                         self.operation_context =
                                             Some(OperationContext::DragTangent {
+                                                curve: *curve,
                                                 key: *key,
                                                 left: false,
+                                                initial_keys: self.curves.clone(),
                                             })
                                     }
                                 }
-                            } else {
-                                self.operation_context = Some(OperationContext::BoxSelection {
-                                    initial_mouse_pos: local_mouse_pos,
-                                    min: Default::default(),
-                                    max: Default::default(),
-                                })
                             }
 
                             if self.operation_context.is_some() {
@@ -356,60 +1023,60 @@ impl Control for CurveEditor {
 
                             // Send modified curve back to user.
                             match context {
-                                OperationContext::DragKeys { .. }
-                                | OperationContext::DragTangent { .. } => {
+                                OperationContext::DragKeys { initial_keys, .. }
+                                | OperationContext::DragTangent { initial_keys, .. }
+                                | OperationContext::TransformKeys { initial_keys, .. } => {
                                     // Ensure that the order of keys is correct.
                                     self.sort_keys();
 
-                                    self.send_curve(ui);
-                                }
-                                OperationContext::BoxSelection { min, max, .. } => {
-                                    let min = min.get();
-                                    let max = max.get();
-
-                                    let rect =
-                                        Rect::new(min.x, min.y, max.x - min.x, max.y - min.y);
-
-                                    let mut selection = FxHashSet::default();
-                                    for key in self.key_container.keys() {
-                                        if rect.contains(key.position) {
-                                            selection.insert(key.id);
-                                        }
-                                    }
-
-                                    if !selection.is_empty() {
-                                        self.set_selection(
-                                            Some(Selection::Keys { keys: selection }),
-                                            ui,
-                                        );
+                                    // Clicking a key without moving it must not produce a change.
+                                    if initial_keys != self.curves {
+                                        self.history.push(initial_keys);
+                                        self.send_curves(ui);
                                     }
                                 }
+                                OperationContext::BoxSelection { min, max, mode, .. } => {
+                                    self.apply_box_selection(min.get(), max.get(), mode, ui);
+                                }
                                 _ => {}
                             }
                         }
                     }
-                    WidgetMessage::MouseDown { pos, button } => match button {
-                        MouseButton::Left => {
+                    WidgetMessage::MouseDown { pos, button } => {
+                        if self.input_bindings.is_pan(*button, ui.keyboard_modifiers()) {
+                            ui.capture_mouse(self.handle);
+                            self.operation_context = Some(OperationContext::MoveView {
+                                initial_mouse_pos: *pos,
+                                initial_view_pos: self.view_position,
+                                button: *button,
+                            });
+                        } else if *button == MouseButton::Left {
+                            if let Some((index, button)) = self.pick_legend(*pos) {
+                                self.toggle_legend_button(index, button, ui);
+                                return;
+                            }
+
                             let pick_result = self.pick(*pos);
 
                             if let Some(picked) = pick_result {
                                 match picked {
-                                    PickResult::Key(picked_key) => {
-                                        if let Some(picked_key_id) = self
-                                            .key_container
-                                            .key_index_ref(picked_key)
-                                            .map(|key| key.id)
+                                    PickResult::Key { curve, key } => {
+                                        if let Some(picked_key) = self
+                                            .curves
+                                            .get(curve)
+                                            .and_then(|c| c.key_index_ref(key))
+                                            .map(|key| SelectedKey { curve, id: key.id })
                                         {
                                             if let Some(selection) = self.selection.as_mut() {
                                                 match selection {
                                                     Selection::Keys { keys } => {
                                                         if ui.keyboard_modifiers().control {
-                                                            keys.insert(picked_key_id);
+                                                            keys.insert(picked_key);
                                                         }
-                                                        if !keys.contains(&picked_key_id) {
+                                                        if !keys.contains(&picked_key) {
                                                             self.set_selection(
                                                                 Some(Selection::single_key(
-                                                                    picked_key_id,
+                                                                    picked_key,
                                                                 )),
                                                                 ui,
                                                             );
@@ -418,46 +1085,64 @@ impl Control for CurveEditor {
                                                     Selection::LeftTangent { .. }
                                                     | Selection::RightTangent { .. } => self
                                                         .set_selection(
-                                                            Some(Selection::single_key(
-                                                                picked_key_id,
-                                                            )),
+                                                            Some(Selection::single_key(picked_key)),
                                                             ui,
                                                         ),
                                                 }
                                             } else {
                                                 self.set_selection(
-                                                    Some(Selection::single_key(picked_key_id)),
+                                                    Some(Selection::single_key(picked_key)),
                                                     ui,
                                                 );
                                             }
                                         }
                                     }
-                                    PickResult::LeftTangent(picked_key) => {
+                                    PickResult::LeftTangent { curve, key } => {
                                         self.set_selection(
-                                            Some(Selection::LeftTangent { key: picked_key }),
+                                            Some(Selection::LeftTangent { curve, key }),
                                             ui,
                                         );
                                     }
-                                    PickResult::RightTangent(picked_key) => {
+                                    PickResult::RightTangent { curve, key } => {
                                         self.set_selection(
-                                            Some(Selection::RightTangent { key: picked_key }),
+                                            Some(Selection::RightTangent { curve, key }),
                                             ui,
                                         );
                                     }
                                 }
+                            } else if let Some(handle) = self.pick_transform_box(*pos) {
+                                if let Some(initial_bounds) = self.selection_bounds() {
+                                    ui.capture_mouse(self.handle);
+                                    self.operation_context =
+                                        Some(OperationContext::TransformKeys {
+                                            handle,
+                                            initial_mouse_pos: self.point_to_local_space(*pos),
+                                            initial_bounds,
+                                            entries: self.selected_drag_entries(),
+                                            initial_keys: self.curves.clone(),
+                                        });
+                                }
                             } else {
-                                self.set_selection(None, ui);
+                                let modifiers = ui.keyboard_modifiers();
+                                let mode = if modifiers.shift {
+                                    BoxSelectionMode::Add
+                                } else if modifiers.control {
+                                    BoxSelectionMode::Subtract
+                                } else {
+                                    self.set_selection(None, ui);
+                                    BoxSelectionMode::Replace
+                                };
+
+                                ui.capture_mouse(self.handle);
+                                self.operation_context = Some(OperationContext::BoxSelection {
+                                    initial_mouse_pos: *pos,
+                                    min: Cell::new(*pos),
+                                    max: Cell::new(*pos),
+                                    mode,
+                                });
                             }
                         }
-                        MouseButton::Middle => {
-                            ui.capture_mouse(self.handle);
-                            self.operation_context = Some(OperationContext::MoveView {
-                                initial_mouse_pos: *pos,
-                                initial_view_pos: self.view_position,
-                            });
-                        }
-                        _ => (),
-                    },
+                    }
                     WidgetMessage::MouseWheel { amount, .. } => {
                         let k = if *amount < 0.0 { 0.9 } else { 1.1 };
 
@@ -484,8 +1169,57 @@ impl Control for CurveEditor {
                     && message.direction() == MessageDirection::ToWidget
                 {
                     match msg {
-                        CurveEditorMessage::Sync(curve) => {
-                            self.key_container = KeyContainer::from(curve);
+                        CurveEditorMessage::Sync(curves) => {
+                            let curves = curves.iter().map(KeyContainer::from).collect::<Vec<_>>();
+                            // The host could echo the curves that were sent by the editor, the history is
+                            // still valid in this case.
+                            if curves != self.curves {
+                                if curves.len() != self.curves.len() {
+                                    self.set_selection(None, ui);
+                                }
+                                self.history.clear();
+                                self.curves = curves;
+                                self.styles.resize(self.curves.len(), Default::default());
+                            }
+                        }
+                        CurveEditorMessage::CurveBrush { index, brush } => {
+                            if let Some(style) = self.styles.get_mut(*index) {
+                                style.brush = Some(brush.clone());
+                                ui.send_message(message.reverse());
+                            }
+                        }
+                        CurveEditorMessage::CurveVisibility { index, visible } => {
+                            if let Some(style) = self.styles.get_mut(*index) {
+                                if style.visible != *visible {
+                                    style.visible = *visible;
+                                    self.set_selection(None, ui);
+                                    ui.send_message(message.reverse());
+                                }
+                            }
+                        }
+                        CurveEditorMessage::CurveLock { index, locked } => {
+                            if let Some(style) = self.styles.get_mut(*index) {
+                                if style.locked != *locked {
+                                    style.locked = *locked;
+                                    self.set_selection(None, ui);
+                                    ui.send_message(message.reverse());
+                                }
+                            }
+                        }
+                        CurveEditorMessage::Undo => {
+                            self.undo(ui);
+                        }
+                        CurveEditorMessage::Redo => {
+                            self.redo(ui);
+                        }
+                        CurveEditorMessage::CopySelection => {
+                            self.copy_selection(ui);
+                        }
+                        CurveEditorMessage::CutSelection => {
+                            self.cut_selection(ui);
+                        }
+                        CurveEditorMessage::Paste(screen_pos) => {
+                            self.paste(*screen_pos, ui);
                         }
                         CurveEditorMessage::ViewPosition(view_position) => {
                             self.set_view_position(*view_position);
@@ -502,15 +1236,20 @@ impl Control for CurveEditor {
                             self.change_selected_keys_kind(kind.clone(), ui);
                         }
                         CurveEditorMessage::AddKey(screen_pos) => {
-                            let local_pos = self.point_to_local_space(*screen_pos);
-                            self.key_container.add(CurveKeyView {
-                                position: local_pos,
-                                kind: CurveKeyKind::Linear,
-                                id: Uuid::new_v4(),
-                            });
-                            self.set_selection(None, ui);
-                            self.sort_keys();
-                            self.send_curve(ui);
+                            let local_pos =
+                                self.clamp_to_bounds(self.point_to_local_space(*screen_pos));
+                            if let Some(curve) = self.closest_editable_curve(local_pos) {
+                                self.history.push(self.curves.clone());
+                                self.curves[curve].add(CurveKeyView {
+                                    position: local_pos,
+                                    kind: CurveKeyKind::Linear,
+                                    id: Uuid::new_v4(),
+                                    tangent_mode: Default::default(),
+                                });
+                                self.set_selection(None, ui);
+                                self.sort_keys();
+                                self.send_curves(ui);
+                            }
                         }
                         CurveEditorMessage::ZoomToFit { after_layout } => {
                             if *after_layout {
@@ -527,9 +1266,143 @@ impl Control for CurveEditor {
                         CurveEditorMessage::ChangeSelectedKeysLocation(location) => {
                             self.change_selected_keys_location(*location, ui);
                         }
+                        CurveEditorMessage::ChangeSelectedKeysLeftTangent(tangent) => {
+                            self.change_selected_keys_tangent(*tangent, true, ui);
+                        }
+                        CurveEditorMessage::ChangeSelectedKeysRightTangent(tangent) => {
+                            self.change_selected_keys_tangent(*tangent, false, ui);
+                        }
+                        CurveEditorMessage::ChangeSelectedKeysBrokenTangents(broken) => {
+                            self.change_selected_keys_tangent_mode(ui, |mode| {
+                                mode.broken = *broken
+                            });
+                        }
+                        CurveEditorMessage::ChangeSelectedKeysWeightedTangents(weighted) => {
+                            self.change_selected_keys_tangent_mode(ui, |mode| {
+                                mode.weighted = *weighted
+                            });
+                        }
+                        CurveEditorMessage::FlattenSelectedKeysTangents => {
+                            self.set_selected_keys_tangents(false, ui);
+                        }
+                        CurveEditorMessage::AutoSmoothSelectedKeysTangents => {
+                            self.set_selected_keys_tangents(true, ui);
+                        }
+                        CurveEditorMessage::ChangeExtrapolation { pre, extrapolation } => {
+                            self.change_extrapolation(*pre, *extrapolation, ui);
+                        }
                         CurveEditorMessage::HighlightZones(zones) => {
                             self.highlight_zones = zones.clone();
                         }
+                        CurveEditorMessage::SnapStep(snap_step) => {
+                            self.snap_step = *snap_step;
+                        }
+                        CurveEditorMessage::SetCursorPosition(position) => {
+                            self.cursor_position = Some(*position);
+                        }
+                        CurveEditorMessage::HideCursor => {
+                            self.cursor_position = None;
+                        }
+                        CurveEditorMessage::ExportKeys(path) => {
+                            if let Err(err) = io::export_keys(path, &self.curves) {
+                                Log::err(format!(
+                                    "Unable to export curve keys to {}. Reason: {err}",
+                                    path.display()
+                                ));
+                            }
+                        }
+                        CurveEditorMessage::ImportKeys { path, replace } => {
+                            self.import_keys(path, *replace, ui);
+                        }
+                        CurveEditorMessage::OpenFileDialog(action) => {
+                            self.open_file_dialog(*action, ui);
+                        }
+                        CurveEditorMessage::InputBindings(input_bindings) => {
+                            self.input_bindings = input_bindings.clone();
+                        }
+                        CurveEditorMessage::StoreViewBookmark(name) => {
+                            let bookmark = ViewBookmark {
+                                name: name.clone(),
+                                zoom: self.zoom,
+                                view_position: self.view_position,
+                            };
+                            let mut bookmarks = self.view_bookmarks.clone();
+                            match bookmarks.iter_mut().find(|b| &b.name == name) {
+                                Some(existing) => *existing = bookmark,
+                                None => bookmarks.push(bookmark),
+                            }
+                            self.set_view_bookmarks(bookmarks, ui);
+                            ui.send_message(CurveEditorMessage::view_bookmarks(
+                                self.handle,
+                                MessageDirection::FromWidget,
+                                self.view_bookmarks.clone(),
+                            ));
+                        }
+                        CurveEditorMessage::RecallViewBookmark(name) => {
+                            if let Some(bookmark) =
+                                self.view_bookmarks.iter().find(|b| &b.name == name)
+                            {
+                                ui.send_message(CurveEditorMessage::zoom(
+                                    self.handle,
+                                    MessageDirection::ToWidget,
+                                    bookmark.zoom,
+                                ));
+                                ui.send_message(CurveEditorMessage::view_position(
+                                    self.handle,
+                                    MessageDirection::ToWidget,
+                                    bookmark.view_position,
+                                ));
+                            }
+                        }
+                        CurveEditorMessage::RemoveViewBookmark(name) => {
+                            if self.view_bookmarks.iter().any(|b| &b.name == name) {
+                                let mut bookmarks = self.view_bookmarks.clone();
+                                bookmarks.retain(|b| &b.name != name);
+                                self.set_view_bookmarks(bookmarks, ui);
+                                ui.send_message(CurveEditorMessage::view_bookmarks(
+                                    self.handle,
+                                    MessageDirection::FromWidget,
+                                    self.view_bookmarks.clone(),
+                                ));
+                            }
+                        }
+                        CurveEditorMessage::ViewBookmarks(bookmarks) => {
+                            if &self.view_bookmarks != bookmarks {
+                                self.set_view_bookmarks(bookmarks.clone(), ui);
+                            }
+                        }
+                        CurveEditorMessage::SelectKeys(ids) => {
+                            let keys = self
+                                .curves
+                                .iter()
+                                .enumerate()
+                                .filter(|(curve, _)| self.is_curve_editable(*curve))
+                                .flat_map(|(curve, container)| {
+                                    container
+                                        .keys()
+                                        .iter()
+                                        .filter(|key| ids.contains(&key.id))
+                                        .map(move |key| SelectedKey { curve, id: key.id })
+                                })
+                                .collect::<FxHashSet<_>>();
+                            if keys.is_empty() {
+                                self.set_selection(None, ui);
+                            } else {
+                                self.set_selection(Some(Selection::Keys { keys }), ui);
+                            }
+                        }
+                        CurveEditorMessage::HighlightRange { start, end } => {
+                            self.highlight_range = (start < end).then_some(*start..*end);
+                        }
+                        CurveEditorMessage::ReadOnly(read_only) => {
+                            if self.read_only != *read_only {
+                                self.read_only = *read_only;
+                                self.operation_context = None;
+                                self.hovered_curve = None;
+                                self.set_selection(None, ui);
+                                self.sync_context_menu_read_only(ui);
+                            }
+                        }
                     }
                 }
             }
@@ -559,10 +1432,33 @@ impl Control for CurveEditor {
                 ui.send_message(CurveEditorMessage::change_selected_keys_kind(
                     self.handle,
                     MessageDirection::ToWidget,
-                    CurveKeyKind::Cubic {
-                        left_tangent: 0.0,
-                        right_tangent: 0.0,
-                    },
+                    CurveKeyKind::new_cubic(0.0, 0.0),
+                ));
+            } else if message.destination() == self.context_menu.flatten_tangents {
+                ui.send_message(CurveEditorMessage::flatten_selected_keys_tangents(
+                    self.handle,
+                    MessageDirection::ToWidget,
+                ));
+            } else if message.destination() == self.context_menu.auto_smooth_tangents {
+                ui.send_message(CurveEditorMessage::auto_smooth_selected_keys_tangents(
+                    self.handle,
+                    MessageDirection::ToWidget,
+                ));
+            } else if message.destination() == self.context_menu.broken_tangents {
+                let broken = self.first_selected_tangent_mode().is_some_and(|m| m.broken);
+                ui.send_message(CurveEditorMessage::change_selected_keys_broken_tangents(
+                    self.handle,
+                    MessageDirection::ToWidget,
+                    !broken,
+                ));
+            } else if message.destination() == self.context_menu.weighted_tangents {
+                let weighted = self
+                    .first_selected_tangent_mode()
+                    .is_some_and(|m| m.weighted);
+                ui.send_message(CurveEditorMessage::change_selected_keys_weighted_tangents(
+                    self.handle,
+                    MessageDirection::ToWidget,
+                    !weighted,
                 ));
             } else if message.destination() == self.context_menu.add_key {
                 let screen_pos = ui.node(*self.context_menu.widget).screen_position();
@@ -571,27 +1467,133 @@ impl Control for CurveEditor {
                     MessageDirection::ToWidget,
                     screen_pos,
                 ));
+            } else if message.destination() == self.context_menu.copy {
+                ui.send_message(CurveEditorMessage::copy_selection(
+                    self.handle,
+                    MessageDirection::ToWidget,
+                ));
+            } else if message.destination() == self.context_menu.cut {
+                ui.send_message(CurveEditorMessage::cut_selection(
+                    self.handle,
+                    MessageDirection::ToWidget,
+                ));
+            } else if message.destination() == self.context_menu.paste {
+                let screen_pos = ui.node(*self.context_menu.widget).screen_position();
+                ui.send_message(CurveEditorMessage::paste(
+                    self.handle,
+                    MessageDirection::ToWidget,
+                    screen_pos,
+                ));
             } else if message.destination() == self.context_menu.zoom_to_fit {
                 ui.send_message(CurveEditorMessage::zoom_to_fit(
                     self.handle,
                     MessageDirection::ToWidget,
                     false,
                 ));
-            }
+            } else if message.destination() == self.context_menu.properties {
+                self.open_key_properties(ui);
+            } else if let Some(bookmark) = self
+                .context_menu
+                .view_bookmark_items
+                .iter()
+                .position(|item| *item == message.destination())
+                .and_then(|index| self.view_bookmarks.get(index))
+            {
+                ui.send_message(CurveEditorMessage::recall_view_bookmark(
+                    self.handle,
+                    MessageDirection::ToWidget,
+                    bookmark.name.clone(),
+                ));
+            } else if let Some(action) = [
+                (self.context_menu.export_keys, FileDialogAction::Export),
+                (
+                    self.context_menu.import_keys,
+                    FileDialogAction::Import { replace: false },
+                ),
+                (
+                    self.context_menu.import_keys_replace,
+                    FileDialogAction::Import { replace: true },
+                ),
+            ]
+            .into_iter()
+            .find_map(|(item, action)| (item == message.destination()).then_some(action))
+            {
+                ui.send_message(CurveEditorMessage::open_file_dialog(
+                    self.handle,
+                    MessageDirection::ToWidget,
+                    action,
+                ));
+            } else if let Some((_, pre, extrapolation)) = self
+                .context_menu
+                .extrapolation_items
+                .iter()
+                .find(|(item, _, _)| *item == message.destination())
+            {
+                ui.send_message(CurveEditorMessage::change_extrapolation(
+                    self.handle,
+                    MessageDirection::ToWidget,
+                    *pre,
+                    *extrapolation,
+                ));
+            }
+        } else if let Some(msg) = message.data::<FileSelectorMessage>() {
+            if let Some((selector, action)) = self.file_dialog {
+                if message.destination() == selector {
+                    match msg {
+                        FileSelectorMessage::Commit(path) => {
+                            ui.send_message(match action {
+                                FileDialogAction::Export => CurveEditorMessage::export_keys(
+                                    self.handle,
+                                    MessageDirection::ToWidget,
+                                    path.clone(),
+                                ),
+                                FileDialogAction::Import { replace } => {
+                                    CurveEditorMessage::import_keys(
+                                        self.handle,
+                                        MessageDirection::ToWidget,
+                                        path.clone(),
+                                        replace,
+                                    )
+                                }
+                            });
+                        }
+                        FileSelectorMessage::Cancel => (),
+                        _ => return,
+                    }
+                    ui.send_message(WidgetMessage::remove(selector, MessageDirection::ToWidget));
+                }
+            }
         } else if let Some(NumericUpDownMessage::<f32>::Value(value)) = message.data() {
             if message.direction() == MessageDirection::FromWidget && !message.handled() {
-                if message.destination() == self.context_menu.key_value {
+                let popup = &self.key_properties_popup;
+                if message.destination() == self.context_menu.key_value
+                    || message.destination() == popup.value
+                {
                     ui.send_message(CurveEditorMessage::change_selected_keys_value(
                         self.handle,
                         MessageDirection::ToWidget,
                         *value,
                     ));
-                } else if message.destination() == self.context_menu.key_location {
+                } else if message.destination() == self.context_menu.key_location
+                    || message.destination() == popup.location
+                {
                     ui.send_message(CurveEditorMessage::change_selected_keys_location(
                         self.handle,
                         MessageDirection::ToWidget,
                         *value,
                     ));
+                } else if message.destination() == popup.left_tangent {
+                    ui.send_message(CurveEditorMessage::change_selected_keys_left_tangent(
+                        self.handle,
+                        MessageDirection::ToWidget,
+                        *value,
+                    ));
+                } else if message.destination() == popup.right_tangent {
+                    ui.send_message(CurveEditorMessage::change_selected_keys_right_tangent(
+                        self.handle,
+                        MessageDirection::ToWidget,
+                        *value,
+                    ));
                 }
             }
         }
@@ -608,27 +1610,71 @@ impl Control for CurveEditor {
     }
 }
 
+// Positions are in screen space, while the tangents are in values space, so the spline matches the
+// evaluated curve. `weights` are weights of the left and the right tangents, see `TangentMode` for
+// more info.
 fn draw_cubic(
     left_pos: Vector2<f32>,
     left_tangent: f32,
     right_pos: Vector2<f32>,
     right_tangent: f32,
-    steps: usize,
+    weights: (f32, f32),
+    thickness: f32,
     ctx: &mut DrawingContext,
 ) {
+    let steps = ((right_pos.x - left_pos.x).abs() / 2.0) as usize;
+    // Y axis is flipped in screen space.
+    let (left_tangent, right_tangent) = (-left_tangent, -right_tangent);
+    let (w0, w1) = weights;
+    let weighted = w0 != TangentMode::DEFAULT_WEIGHT || w1 != TangentMode::DEFAULT_WEIGHT;
     let mut prev = left_pos;
     for i in 0..steps {
         let t = i as f32 / (steps - 1) as f32;
         let middle_x = lerpf(left_pos.x, right_pos.x, t);
-        let middle_y = cubicf(left_pos.y, right_pos.y, t, left_tangent, right_tangent);
+        let middle_y = if weighted {
+            weighted_cubicf(
+                left_pos.y,
+                right_pos.y,
+                t,
+                left_tangent,
+                right_tangent,
+                w0,
+                w1,
+            )
+        } else {
+            cubicf(left_pos.y, right_pos.y, t, left_tangent, right_tangent)
+        };
         let pt = Vector2::new(middle_x, middle_y);
-        ctx.push_line(prev, pt, 1.0);
+        ctx.push_line(prev, pt, thickness);
         prev = pt;
     }
 }
 
-fn round_to_step(x: f32, step: f32) -> f32 {
-    x - x % step
+// Returns a step (in values space) between major grid lines, which is the smallest power of ten that
+// is at least `min_spacing` pixels long at the given zoom, and visibility (in `[0; 1]` range) of minor
+// lines that subdivide major lines into 10 parts. Minor lines fade in when the zoom increases, and
+// they become major lines when they are `min_spacing` pixels apart.
+fn grid_step(zoom: f32, min_spacing: f32) -> (f32, f32) {
+    let zoom = zoom.max(f32::EPSILON);
+    let min_spacing = min_spacing.max(1.0);
+    let major = 10.0f32.powf((min_spacing / zoom).log10().ceil());
+    let minor_spacing = major * zoom / 10.0;
+    let visibility = ((minor_spacing - min_spacing / 10.0) / (min_spacing * 0.9)).clamp(0.0, 1.0);
+    (major, visibility)
+}
+
+// Returns indices and values of grid lines with the given step in the given range.
+fn grid_lines(min: f32, max: f32, step: f32) -> impl Iterator<Item = (i64, f32)> {
+    // Protects from drawing millions of lines in case of invalid step.
+    const MAX_LINES: i64 = 4096;
+    let first = (min / step).floor() as i64;
+    let last = ((max / step).ceil() as i64).min(first + MAX_LINES);
+    (first..=last).map(move |i| (i, i as f32 * step))
+}
+
+// Amount of decimal places that is enough to show values of grid lines with the given step.
+fn grid_step_decimals(step: f32) -> usize {
+    (-step.log10().round()).max(0.0) as usize
 }
 
 impl CurveEditor {
@@ -647,15 +1693,180 @@ impl CurveEditor {
             let clamped_view_space = -clamped_local_space_position;
             clamped_view_space
         });
+
+        // Keep the center of the view inside the value/time bounds.
+        if self.x_bounds.is_some() || self.y_bounds.is_some() {
+            let half_size = self.actual_local_size().scale(0.5);
+            let center = Vector2::new(
+                half_size.x - self.view_position.x,
+                half_size.y + self.view_position.y,
+            );
+            let center = clamp_position(center, self.x_bounds.as_ref(), self.y_bounds.as_ref());
+            self.view_position = Vector2::new(half_size.x - center.x, center.y - half_size.y);
+        }
     }
 
-    fn zoom_to_fit(&mut self, sender: &Sender<UiMessage>) {
-        let bounds = if self.key_container.keys().is_empty() {
-            Rect::new(-1.0, -1.0, 2.0, 2.0)
-        } else {
-            self.key_container.curve().bounds()
+    fn clamp_to_bounds(&self, position: Vector2<f32>) -> Vector2<f32> {
+        clamp_position(position, self.x_bounds.as_ref(), self.y_bounds.as_ref())
+    }
+
+    // Returns bounds of the selected keys or `None` if no keys are selected.
+    fn selection_bounds(&self) -> Option<Rect<f32>> {
+        let Some(Selection::Keys { keys }) = self.selection.as_ref() else {
+            return None;
+        };
+        let mut bounds: Option<Rect<f32>> = None;
+        for position in keys
+            .iter()
+            .filter_map(|key| self.key_ref(*key))
+            .map(|key| key.position)
+        {
+            match bounds.as_mut() {
+                Some(bounds) => bounds.push(position),
+                None => bounds = Some(Rect::new(position.x, position.y, 0.0, 0.0)),
+            }
+        }
+        bounds
+    }
+
+    fn selected_drag_entries(&self) -> Vec<DragEntry> {
+        let Some(Selection::Keys { keys }) = self.selection.as_ref() else {
+            return Default::default();
+        };
+        keys.iter()
+            .filter_map(|key| {
+                self.key_ref(*key).map(|k| DragEntry {
+                    key: *key,
+                    initial_position: k.position,
+                })
+            })
+            .collect()
+    }
+
+    // Returns screen-space bounds of the transform box, it is shown only when multiple keys are
+    // selected.
+    fn transform_box_screen_bounds(&self) -> Option<Rect<f32>> {
+        match self.selection.as_ref() {
+            Some(Selection::Keys { keys }) if keys.len() > 1 => (),
+            _ => return None,
+        }
+        let bounds = self.selection_bounds()?;
+        let a = self.point_to_screen_space(bounds.left_top_corner());
+        let b = self.point_to_screen_space(bounds.right_bottom_corner());
+        // Keep some space around the keys, so the box is usable even if the keys are aligned.
+        Some(
+            Rect::new(
+                a.x.min(b.x),
+                a.y.min(b.y),
+                (b.x - a.x).abs(),
+                (b.y - a.y).abs(),
+            )
+            .inflate(self.key_size, self.key_size),
+        )
+    }
+
+    // Returns screen-space positions of the scaling handles of the transform box with the given
+    // screen-space bounds.
+    fn transform_box_handles(
+        bounds: Rect<f32>,
+    ) -> impl Iterator<Item = (TransformBoxHandle, Vector2<f32>)> {
+        [-1i8, 0, 1]
+            .into_iter()
+            .flat_map(|x| [-1i8, 0, 1].into_iter().map(move |y| (x, y)))
+            .filter(|(x, y)| *x != 0 || *y != 0)
+            .map(move |(x, y)| {
+                let position = Vector2::new(
+                    bounds.x() + (x + 1) as f32 * 0.5 * bounds.w(),
+                    // Y axis is flipped in screen space.
+                    bounds.y() + (1 - y) as f32 * 0.5 * bounds.h(),
+                );
+                (TransformBoxHandle::Scale { x, y }, position)
+            })
+    }
+
+    /// Returns the closest editable curve, that passes near the given point. `pos` must be in screen
+    /// space.
+    fn pick_curve(&self, pos: Vector2<f32>) -> Option<usize> {
+        let location = self.point_to_local_space(pos).x;
+        self.visible_curves()
+            .filter(|(i, container)| self.is_curve_editable(*i) && !container.keys().is_empty())
+            .map(|(i, container)| {
+                let value = container.curve().value_at(location);
+                let screen_y = self.point_to_screen_space(Vector2::new(location, value)).y;
+                (i, (screen_y - pos.y).abs())
+            })
+            .filter(|(_, distance)| *distance <= self.key_size)
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(i, _)| i)
+    }
+
+    // Inserts a key into the given curve at the location of the given screen-space point, so the
+    // shape of the curve stays the same.
+    fn insert_key(&mut self, curve: usize, screen_pos: Vector2<f32>, ui: &mut UserInterface) {
+        let location = clamp_to_range(
+            self.point_to_local_space(screen_pos).x,
+            self.x_bounds.as_ref(),
+        );
+        let initial_keys = self.curves.clone();
+        let Some(container) = self.curves.get_mut(curve) else {
+            return;
         };
+        let id = container.insert_key(location);
+        if initial_keys != self.curves {
+            self.history.push(initial_keys);
+            self.send_curves(ui);
+        }
+        self.set_selection(Some(Selection::single_key(SelectedKey { curve, id })), ui);
+    }
+
+    /// `pos` must be in screen space.
+    fn pick_transform_box(&self, pos: Vector2<f32>) -> Option<TransformBoxHandle> {
+        let bounds = self.transform_box_screen_bounds()?;
+        Self::transform_box_handles(bounds)
+            .find(|(_, handle_pos)| {
+                let d = pos - *handle_pos;
+                d.x.abs() <= self.key_size * 0.5 && d.y.abs() <= self.key_size * 0.5
+            })
+            .map(|(handle, _)| handle)
+            .or_else(|| bounds.contains(pos).then_some(TransformBoxHandle::Body))
+    }
+
+    fn zoom_to_fit(&mut self, sender: &Sender<UiMessage>) {
+        let mut bounds = self.selection_bounds();
+        if bounds.is_none() {
+            for (_, container) in self.visible_curves() {
+                if !container.keys().is_empty() {
+                    let curve_bounds = container.curve().bounds();
+                    match bounds.as_mut() {
+                        Some(bounds) => bounds.extend_to_contain(curve_bounds),
+                        None => bounds = Some(curve_bounds),
+                    }
+                }
+            }
+        }
+        let bounds = bounds.unwrap_or_else(|| Rect::new(-1.0, -1.0, 2.0, 2.0));
         let center = bounds.center();
+        // Single key or a flat curve has zero size along some axis, so keep some space around it.
+        // Also leave a small margin, so the keys at the edges are not clipped.
+        let size = Vector2::new(
+            if bounds.w() > f32::EPSILON {
+                bounds.w()
+            } else {
+                1.0
+            },
+            if bounds.h() > f32::EPSILON {
+                bounds.h()
+            } else {
+                1.0
+            },
+        )
+        .scale(1.1);
+        let bounds = Rect::new(
+            center.x - size.x * 0.5,
+            center.y - size.y * 0.5,
+            size.x,
+            size.y,
+        );
 
         sender
             .send(CurveEditorMessage::zoom(
@@ -736,12 +1947,111 @@ impl CurveEditor {
     }
 
     fn sort_keys(&mut self) {
-        self.key_container.sort_keys();
+        for curve in self.curves.iter_mut() {
+            curve.sort_keys();
+        }
+    }
+
+    fn is_curve_visible(&self, curve: usize) -> bool {
+        self.styles.get(curve).map_or(true, |style| style.visible)
+    }
+
+    fn is_curve_editable(&self, curve: usize) -> bool {
+        !self.read_only
+            && self
+                .styles
+                .get(curve)
+                .map_or(true, |style| style.visible && !style.locked)
+    }
+
+    // Keys cannot be selected in read-only mode, so only the items that do not need selection
+    // must be disabled.
+    fn sync_context_menu_read_only(&self, ui: &UserInterface) {
+        for item in [
+            self.context_menu.add_key,
+            self.context_menu.paste,
+            self.context_menu.import_keys,
+            self.context_menu.import_keys_replace,
+        ]
+        .into_iter()
+        .chain(
+            self.context_menu
+                .extrapolation_items
+                .iter()
+                .map(|(item, ..)| *item),
+        ) {
+            ui.send_message(WidgetMessage::enabled(
+                item,
+                MessageDirection::ToWidget,
+                !self.read_only,
+            ));
+        }
+    }
+
+    fn visible_curves(&self) -> impl Iterator<Item = (usize, &KeyContainer)> {
+        self.curves
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| self.is_curve_visible(*i))
+    }
+
+    fn curve_brush(&self, curve: usize) -> Brush {
+        match self.styles.get(curve).and_then(|style| style.brush.clone()) {
+            Some(brush) => brush,
+            None if self.curves.len() <= 1 => self.foreground(),
+            None => Brush::Solid(CURVE_PALETTE[curve % CURVE_PALETTE.len()]),
+        }
+    }
+
+    fn key_ref(&self, key: SelectedKey) -> Option<&CurveKeyView> {
+        self.curves.get(key.curve)?.key_ref(key.id)
+    }
+
+    // Returns an index of an editable curve which value at the given location is the closest to the
+    // given value.
+    fn closest_editable_curve(&self, local_pos: Vector2<f32>) -> Option<usize> {
+        self.curves
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| self.is_curve_editable(*i))
+            .map(|(i, container)| {
+                let value = container.curve().value_at(local_pos.x);
+                (i, (value - local_pos.y).abs())
+            })
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(i, _)| i)
+    }
+
+    // Returns ids of the selected keys, or an id of a key whose tangent is selected.
+    fn selected_key_ids(&self) -> FxHashSet<Uuid> {
+        match self.selection.as_ref() {
+            Some(Selection::Keys { keys }) => keys.iter().map(|key| key.id).collect(),
+            Some(
+                Selection::LeftTangent { curve, key } | Selection::RightTangent { curve, key },
+            ) => self
+                .curves
+                .get(*curve)
+                .and_then(|c| c.key_index_ref(*key))
+                .map(|key| key.id)
+                .into_iter()
+                .collect(),
+            None => Default::default(),
+        }
     }
 
     fn set_selection(&mut self, selection: Option<Selection>, ui: &UserInterface) {
+        let old_ids = self.selected_key_ids();
         self.selection = selection;
 
+        let new_ids = self.selected_key_ids();
+        if old_ids != new_ids {
+            ui.send_message(CurveEditorMessage::select_keys(
+                self.handle,
+                MessageDirection::FromWidget,
+                new_ids.into_iter().collect(),
+            ));
+        }
+
         ui.send_message(WidgetMessage::enabled(
             self.context_menu.remove,
             MessageDirection::ToWidget,
@@ -754,15 +2064,28 @@ impl CurveEditor {
             self.selection.is_some(),
         ));
 
-        ui.send_message(WidgetMessage::enabled(
+        for item in [self.context_menu.copy, self.context_menu.cut] {
+            ui.send_message(WidgetMessage::enabled(
+                item,
+                MessageDirection::ToWidget,
+                matches!(self.selection, Some(Selection::Keys { .. })),
+            ));
+        }
+
+        for item in [
             self.context_menu.key_properties,
-            MessageDirection::ToWidget,
-            self.selection.is_some(),
-        ));
+            self.context_menu.properties,
+        ] {
+            ui.send_message(WidgetMessage::enabled(
+                item,
+                MessageDirection::ToWidget,
+                self.selection.is_some(),
+            ));
+        }
 
         if let Some(Selection::Keys { keys }) = self.selection.as_ref() {
             if let Some(first) = keys.iter().next() {
-                if let Some(key) = self.key_container.key_ref(*first) {
+                if let Some(key) = self.key_ref(*first) {
                     ui.send_message(
                         NumericUpDownMessage::value(
                             self.context_menu.key_location,
@@ -785,36 +2108,307 @@ impl CurveEditor {
         }
     }
 
+    fn apply_box_selection(
+        &mut self,
+        min: Vector2<f32>,
+        max: Vector2<f32>,
+        mode: BoxSelectionMode,
+        ui: &UserInterface,
+    ) {
+        let rect = Rect::new(min.x, min.y, max.x - min.x, max.y - min.y);
+
+        let mut keys = match (mode, self.selection.as_ref()) {
+            (
+                BoxSelectionMode::Add | BoxSelectionMode::Subtract,
+                Some(Selection::Keys { keys }),
+            ) => keys.clone(),
+            _ => FxHashSet::default(),
+        };
+
+        for (curve, container) in self.curves.iter().enumerate() {
+            if !self.is_curve_editable(curve) {
+                continue;
+            }
+
+            for key in container.keys() {
+                if rect.contains(self.point_to_screen_space(key.position)) {
+                    let key = SelectedKey { curve, id: key.id };
+                    if mode == BoxSelectionMode::Subtract {
+                        keys.remove(&key);
+                    } else {
+                        keys.insert(key);
+                    }
+                }
+            }
+        }
+
+        if keys.is_empty() {
+            self.set_selection(None, ui);
+        } else {
+            self.set_selection(Some(Selection::Keys { keys }), ui);
+        }
+    }
+
+    fn undo(&mut self, ui: &UserInterface) {
+        if self.read_only {
+            return;
+        }
+        if let Some(keys) = self.history.undo.pop() {
+            let current = std::mem::replace(&mut self.curves, keys);
+            self.history.redo.push(current);
+            self.set_selection(None, ui);
+            self.send_curves(ui);
+        }
+    }
+
+    fn redo(&mut self, ui: &UserInterface) {
+        if self.read_only {
+            return;
+        }
+        if let Some(keys) = self.history.redo.pop() {
+            let current = std::mem::replace(&mut self.curves, keys);
+            self.history.undo.push(current);
+            self.set_selection(None, ui);
+            self.send_curves(ui);
+        }
+    }
+
+    fn copy_selection(&self, ui: &UserInterface) {
+        if let Some(Selection::Keys { keys }) = self.selection.as_ref() {
+            let string = keys_to_clipboard_string(self.curves.iter().enumerate().flat_map(
+                |(curve, container)| {
+                    container
+                        .keys()
+                        .iter()
+                        .filter(move |k| keys.contains(&SelectedKey { curve, id: k.id }))
+                },
+            ));
+
+            let _ = ui.clipboard_provider_mut().set_contents(string);
+        }
+    }
+
+    fn cut_selection(&mut self, ui: &mut UserInterface) {
+        self.copy_selection(ui);
+        self.remove_selection(ui);
+    }
+
+    fn paste(&mut self, screen_pos: Vector2<f32>, ui: &UserInterface) {
+        let Ok(string) = ui.clipboard_provider_mut().get_contents() else {
+            return;
+        };
+
+        let Some(mut keys) = keys_from_clipboard_string(&string) else {
+            return;
+        };
+        let Some(first) = keys
+            .iter()
+            .map(|k| k.position)
+            .min_by(|a, b| a.x.total_cmp(&b.x))
+        else {
+            return;
+        };
+
+        let local_pos = self.point_to_local_space(screen_pos);
+        // Keys are pasted to the curve with selected keys or to the closest curve.
+        let target = match self.selection.as_ref() {
+            Some(Selection::Keys { keys }) => keys.iter().next().map(|key| key.curve),
+            _ => None,
+        }
+        .filter(|curve| self.is_curve_editable(*curve))
+        .or_else(|| self.closest_editable_curve(local_pos));
+        let Some(target) = target else {
+            return;
+        };
+
+        self.history.push(self.curves.clone());
+
+        let offset = local_pos - first;
+        let mut selection = FxHashSet::default();
+        for mut key in keys.drain(..) {
+            key.position = self.clamp_to_bounds(key.position + offset);
+            selection.insert(SelectedKey {
+                curve: target,
+                id: key.id,
+            });
+            self.curves[target].add(key);
+        }
+
+        self.sort_keys();
+        self.set_selection(Some(Selection::Keys { keys: selection }), ui);
+        self.send_curves(ui);
+    }
+
+    fn set_view_bookmarks(&mut self, bookmarks: Vec<ViewBookmark>, ui: &mut UserInterface) {
+        self.view_bookmarks = bookmarks;
+
+        let items = make_view_bookmark_items(&self.view_bookmarks, &mut ui.build_ctx());
+        ui.send_message(MenuItemMessage::items(
+            self.context_menu.view_bookmarks,
+            MessageDirection::ToWidget,
+            items.clone(),
+        ));
+        ui.send_message(WidgetMessage::enabled(
+            self.context_menu.view_bookmarks,
+            MessageDirection::ToWidget,
+            !items.is_empty(),
+        ));
+        self.context_menu.view_bookmark_items = items;
+    }
+
+    fn open_file_dialog(&mut self, action: FileDialogAction, ui: &mut UserInterface) {
+        let (title, mode) = match action {
+            FileDialogAction::Export => (
+                "Export Keys",
+                FileBrowserMode::Save {
+                    default_file_name: PathBuf::from("curve.json"),
+                },
+            ),
+            FileDialogAction::Import { .. } => ("Import Keys", FileBrowserMode::Open),
+        };
+
+        let selector = FileSelectorBuilder::new(
+            WindowBuilder::new(WidgetBuilder::new().with_width(300.0).with_height(450.0))
+                .open(false)
+                .with_title(WindowTitle::text(title)),
+        )
+        .with_mode(mode)
+        .with_filter(Filter::new(|path: &Path| {
+            path.is_dir() || io::CurveKeysFormat::from_path(path).is_some()
+        }))
+        .build(&mut ui.build_ctx());
+
+        ui.send_message(WindowMessage::open_modal(
+            selector,
+            MessageDirection::ToWidget,
+            true,
+        ));
+
+        self.file_dialog = Some((selector, action));
+    }
+
+    fn import_keys(&mut self, path: &Path, replace: bool, ui: &UserInterface) {
+        let imported = match io::import_keys(path) {
+            Ok(imported) => imported,
+            Err(err) => {
+                Log::err(format!(
+                    "Unable to import curve keys from {}. Reason: {err}",
+                    path.display()
+                ));
+                return;
+            }
+        };
+
+        let initial_keys = self.curves.clone();
+        let mut selection = FxHashSet::default();
+        let mut modified = false;
+        for (curve, keys) in imported.into_iter().enumerate() {
+            if curve >= self.curves.len() || !self.is_curve_editable(curve) {
+                continue;
+            }
+
+            if replace {
+                self.curves[curve].clear();
+            }
+            for mut key in keys {
+                key.position = self.clamp_to_bounds(key.position);
+                selection.insert(SelectedKey { curve, id: key.id });
+                self.curves[curve].add(key);
+            }
+            modified = true;
+        }
+
+        if modified {
+            self.history.push(initial_keys);
+            self.sort_keys();
+            if selection.is_empty() {
+                self.set_selection(None, ui);
+            } else {
+                self.set_selection(Some(Selection::Keys { keys: selection }), ui);
+            }
+            self.send_curves(ui);
+        }
+    }
+
     fn remove_selection(&mut self, ui: &mut UserInterface) {
         if let Some(Selection::Keys { keys }) = self.selection.as_ref() {
-            for &id in keys {
-                self.key_container.remove(id);
+            self.history.push(self.curves.clone());
+            for key in keys {
+                if let Some(curve) = self.curves.get_mut(key.curve) {
+                    curve.remove(key.id);
+                }
             }
 
             self.set_selection(None, ui);
 
             // Send modified curve back to user.
-            self.send_curve(ui);
+            self.send_curves(ui);
+        }
+    }
+
+    fn change_extrapolation(
+        &mut self,
+        pre: bool,
+        extrapolation: CurveExtrapolation,
+        ui: &mut UserInterface,
+    ) {
+        let curves = match self.selection.as_ref() {
+            Some(Selection::Keys { keys }) => {
+                keys.iter().map(|key| key.curve).collect::<FxHashSet<_>>()
+            }
+            _ => (0..self.curves.len())
+                .filter(|i| self.is_curve_editable(*i))
+                .collect::<FxHashSet<_>>(),
+        };
+
+        let initial_keys = self.curves.clone();
+        let mut modified = false;
+        for curve in curves {
+            if let Some(container) = self.curves.get_mut(curve) {
+                if pre && container.pre_extrapolation() != extrapolation {
+                    container.set_pre_extrapolation(extrapolation);
+                    modified = true;
+                } else if !pre && container.post_extrapolation() != extrapolation {
+                    container.set_post_extrapolation(extrapolation);
+                    modified = true;
+                }
+            }
+        }
+
+        if modified {
+            self.history.push(initial_keys);
+            self.send_curves(ui);
         }
     }
 
     fn change_selected_keys_kind(&mut self, kind: CurveKeyKind, ui: &mut UserInterface) {
         if let Some(Selection::Keys { keys }) = self.selection.as_ref() {
+            self.history.push(self.curves.clone());
             for key in keys {
-                if let Some(key) = self.key_container.key_mut(*key) {
+                if let Some(key) = self
+                    .curves
+                    .get_mut(key.curve)
+                    .and_then(|c| c.key_mut(key.id))
+                {
                     key.kind = kind.clone();
                 }
             }
 
-            self.send_curve(ui);
+            self.send_curves(ui);
         }
     }
 
     fn change_selected_keys_value(&mut self, value: f32, ui: &mut UserInterface) {
         if let Some(Selection::Keys { keys }) = self.selection.as_ref() {
+            let initial_keys = self.curves.clone();
             let mut modified = false;
             for key in keys {
-                if let Some(key) = self.key_container.key_mut(*key) {
+                if let Some(key) = self
+                    .curves
+                    .get_mut(key.curve)
+                    .and_then(|c| c.key_mut(key.id))
+                {
+                    let value = clamp_to_range(value, self.y_bounds.as_ref());
                     let key_value = &mut key.position.y;
                     if (*key_value).ne(&value) {
                         *key_value = value;
@@ -824,16 +2418,263 @@ impl CurveEditor {
             }
 
             if modified {
-                self.send_curve(ui);
+                self.history.push(initial_keys);
+                self.send_curves(ui);
+            }
+        }
+    }
+
+    // Makes the selected keys cubic and either flattens their tangents or calculates them from the
+    // neighbouring keys (`smooth == true`).
+    fn set_selected_keys_tangents(&mut self, smooth: bool, ui: &mut UserInterface) {
+        let Some(Selection::Keys { keys }) = self.selection.as_ref() else {
+            return;
+        };
+
+        let initial_keys = self.curves.clone();
+        let mut modified = false;
+        for key in keys {
+            let Some(container) = self.curves.get_mut(key.curve) else {
+                continue;
+            };
+            let Some(index) = container.keys().iter().position(|k| k.id == key.id) else {
+                continue;
+            };
+
+            let (left_tangent, right_tangent) = if smooth {
+                // Tangents are calculated from the initial state, so the result does not depend on
+                // the order of the keys in the selection.
+                initial_keys[key.curve]
+                    .auto_smooth_tangents(index)
+                    .unwrap_or_default()
+            } else {
+                (0.0, 0.0)
+            };
+            let kind = CurveKeyKind::Cubic {
+                left_tangent,
+                right_tangent,
+            };
+
+            if let Some(key) = container.key_index_mut(index) {
+                if key.kind != kind {
+                    key.kind = kind;
+                    modified = true;
+                }
+            }
+        }
+
+        if modified {
+            self.history.push(initial_keys);
+            self.send_curves(ui);
+        }
+    }
+
+    fn first_selected_tangent_mode(&self) -> Option<TangentMode> {
+        let Some(Selection::Keys { keys }) = self.selection.as_ref() else {
+            return None;
+        };
+        keys.iter()
+            .filter_map(|key| self.key_ref(*key))
+            .find_map(|key| match key.kind {
+                CurveKeyKind::Cubic { .. } => Some(key.tangent_mode),
+                _ => None,
+            })
+    }
+
+    fn change_selected_keys_tangent_mode<F>(&mut self, ui: &mut UserInterface, mut func: F)
+    where
+        F: FnMut(&mut TangentMode),
+    {
+        if let Some(Selection::Keys { keys }) = self.selection.as_ref() {
+            let initial_keys = self.curves.clone();
+            let mut modified = false;
+            for key in keys {
+                if let Some(CurveKeyView {
+                    kind:
+                        CurveKeyKind::Cubic {
+                            left_tangent,
+                            right_tangent,
+                        },
+                    tangent_mode: mode,
+                    ..
+                }) = self
+                    .curves
+                    .get_mut(key.curve)
+                    .and_then(|c| c.key_mut(key.id))
+                {
+                    let old_mode = *mode;
+                    func(mode);
+                    if old_mode != *mode {
+                        // Unified tangents must be collinear.
+                        if old_mode.broken && !mode.broken {
+                            let tangent = (*left_tangent + *right_tangent) * 0.5;
+                            *left_tangent = tangent;
+                            *right_tangent = tangent;
+                        }
+                        modified = true;
+                    }
+                }
+            }
+
+            if modified {
+                self.history.push(initial_keys);
+                self.send_curves(ui);
             }
         }
     }
 
+    fn change_selected_keys_tangent(&mut self, tangent: f32, left: bool, ui: &mut UserInterface) {
+        if let Some(Selection::Keys { keys }) = self.selection.as_ref() {
+            let initial_keys = self.curves.clone();
+            let mut modified = false;
+            for key in keys {
+                if let Some(CurveKeyView {
+                    kind:
+                        CurveKeyKind::Cubic {
+                            left_tangent,
+                            right_tangent,
+                        },
+                    tangent_mode: mode,
+                    ..
+                }) = self
+                    .curves
+                    .get_mut(key.curve)
+                    .and_then(|c| c.key_mut(key.id))
+                {
+                    let (key_tangent, other_tangent) = if left {
+                        (left_tangent, right_tangent)
+                    } else {
+                        (right_tangent, left_tangent)
+                    };
+                    if (*key_tangent).ne(&tangent) {
+                        *key_tangent = tangent;
+                        if !mode.broken {
+                            *other_tangent = tangent;
+                        }
+                        modified = true;
+                    }
+                }
+            }
+
+            if modified {
+                self.history.push(initial_keys);
+                self.send_curves(ui);
+            }
+        }
+    }
+
+    // Moves selected keys by the given amount of grid steps (or snapping steps, if snapping is
+    // enabled). The change is committed when an arrow key is released, see `commit_nudge`.
+    fn nudge_selection(&mut self, steps: Vector2<f32>) {
+        let Some(Selection::Keys { keys }) = self.selection.as_ref() else {
+            return;
+        };
+
+        let grid_step = Vector2::new(
+            grid_step(self.zoom.x, self.grid_size.x).0,
+            grid_step(self.zoom.y, self.grid_size.y).0,
+        );
+        let step = Vector2::new(
+            if self.snap_step.x > 0.0 {
+                self.snap_step.x
+            } else {
+                grid_step.x
+            },
+            if self.snap_step.y > 0.0 {
+                self.snap_step.y
+            } else {
+                grid_step.y
+            },
+        );
+
+        if self.nudge_initial_keys.is_none() {
+            self.nudge_initial_keys = Some(self.curves.clone());
+        }
+
+        for key in keys {
+            if let Some(key) = self
+                .curves
+                .get_mut(key.curve)
+                .and_then(|c| c.key_mut(key.id))
+            {
+                key.position = clamp_position(
+                    key.position + steps.component_mul(&step),
+                    self.x_bounds.as_ref(),
+                    self.y_bounds.as_ref(),
+                );
+            }
+        }
+        self.sort_keys();
+    }
+
+    fn commit_nudge(&mut self, ui: &mut UserInterface) {
+        if let Some(initial_keys) = self.nudge_initial_keys.take() {
+            if initial_keys != self.curves {
+                self.history.push(initial_keys);
+                self.send_curves(ui);
+            }
+        }
+    }
+
+    // Opens a popup with exact values of the first selected key at the cursor position.
+    fn open_key_properties(&self, ui: &UserInterface) {
+        let Some(Selection::Keys { keys }) = self.selection.as_ref() else {
+            return;
+        };
+        let Some(key) = keys.iter().next().and_then(|key| self.key_ref(*key)) else {
+            return;
+        };
+
+        let popup = &self.key_properties_popup;
+        let (tangents, left_tangent, right_tangent) = match key.kind {
+            CurveKeyKind::Cubic {
+                left_tangent,
+                right_tangent,
+                ..
+            } => (true, left_tangent, right_tangent),
+            _ => (false, 0.0, 0.0),
+        };
+        for (field, value) in [
+            (popup.location, key.position.x),
+            (popup.value, key.position.y),
+            (popup.left_tangent, left_tangent),
+            (popup.right_tangent, right_tangent),
+        ] {
+            ui.send_message(
+                NumericUpDownMessage::value(field, MessageDirection::ToWidget, value)
+                    .with_handled(true),
+            );
+        }
+        for field in [popup.left_tangent, popup.right_tangent] {
+            ui.send_message(WidgetMessage::enabled(
+                field,
+                MessageDirection::ToWidget,
+                tangents,
+            ));
+        }
+
+        ui.send_message(PopupMessage::placement(
+            popup.handle(),
+            MessageDirection::ToWidget,
+            Placement::Cursor(self.handle),
+        ));
+        ui.send_message(PopupMessage::open(
+            popup.handle(),
+            MessageDirection::ToWidget,
+        ));
+    }
+
     fn change_selected_keys_location(&mut self, location: f32, ui: &mut UserInterface) {
         if let Some(Selection::Keys { keys }) = self.selection.as_ref() {
+            let initial_keys = self.curves.clone();
             let mut modified = false;
             for key in keys {
-                if let Some(key) = self.key_container.key_mut(*key) {
+                if let Some(key) = self
+                    .curves
+                    .get_mut(key.curve)
+                    .and_then(|c| c.key_mut(key.id))
+                {
+                    let location = clamp_to_range(location, self.x_bounds.as_ref());
                     let key_location = &mut key.position.x;
                     if (*key_location).ne(&location) {
                         *key_location = location;
@@ -843,7 +2684,8 @@ impl CurveEditor {
             }
 
             if modified {
-                self.send_curve(ui);
+                self.history.push(initial_keys);
+                self.send_curves(ui);
             }
         }
     }
@@ -852,54 +2694,132 @@ impl CurveEditor {
     fn pick(&self, pos: Vector2<f32>) -> Option<PickResult> {
         // Linear search is fine here, having a curve with thousands of
         // points is insane anyway.
-        for (i, key) in self.key_container.keys().iter().enumerate() {
-            let screen_pos = self.point_to_screen_space(key.position);
-            let bounds = Rect::new(
-                screen_pos.x - self.key_size * 0.5,
-                screen_pos.y - self.key_size * 0.5,
-                self.key_size,
-                self.key_size,
-            );
-            if bounds.contains(pos) {
-                return Some(PickResult::Key(i));
+        for (curve, container) in self.curves.iter().enumerate() {
+            if !self.is_curve_editable(curve) {
+                continue;
             }
 
-            // Check tangents.
-            if let CurveKeyKind::Cubic {
-                left_tangent,
-                right_tangent,
-            } = key.kind
-            {
-                let left_handle_pos = self.tangent_screen_position(
-                    wrap_angle(left_tangent.atan()) + std::f32::consts::PI,
-                    key.position,
+            for (i, key) in container.keys().iter().enumerate() {
+                let screen_pos = self.point_to_screen_space(key.position);
+                let bounds = Rect::new(
+                    screen_pos.x - self.key_size * 0.5,
+                    screen_pos.y - self.key_size * 0.5,
+                    self.key_size,
+                    self.key_size,
                 );
+                if bounds.contains(pos) {
+                    return Some(PickResult::Key { curve, key: i });
+                }
 
-                if (left_handle_pos - pos).norm() <= self.key_size * 0.5 {
-                    return Some(PickResult::LeftTangent(i));
+                // Check tangents.
+                if let CurveKeyKind::Cubic {
+                    left_tangent,
+                    right_tangent,
+                } = key.kind
+                {
+                    let mode = key.tangent_mode;
+                    let left_handle_pos = self.tangent_screen_position(
+                        wrap_angle((-left_tangent).atan()) + std::f32::consts::PI,
+                        key.position,
+                        mode.effective_left_weight(),
+                    );
+
+                    if (left_handle_pos - pos).norm() <= self.key_size * 0.5 {
+                        return Some(PickResult::LeftTangent { curve, key: i });
+                    }
+
+                    let right_handle_pos = self.tangent_screen_position(
+                        wrap_angle((-right_tangent).atan()),
+                        key.position,
+                        mode.effective_right_weight(),
+                    );
+
+                    if (right_handle_pos - pos).norm() <= self.key_size * 0.5 {
+                        return Some(PickResult::RightTangent { curve, key: i });
+                    }
                 }
+            }
+        }
+        None
+    }
+
+    const LEGEND_ROW_HEIGHT: f32 = 18.0;
+    const LEGEND_BUTTON_SIZE: f32 = 14.0;
+
+    // The legend is shown only when there's more than one curve.
+    fn has_legend(&self) -> bool {
+        self.curves.len() > 1
+    }
 
-                let right_handle_pos =
-                    self.tangent_screen_position(wrap_angle(right_tangent.atan()), key.position);
+    /// Returns screen-space bounds of a button in the legend row of the given curve.
+    fn legend_button_bounds(&self, curve: usize, button: LegendButton) -> Rect<f32> {
+        let origin = self.screen_position()
+            + Vector2::new(4.0, 4.0 + curve as f32 * Self::LEGEND_ROW_HEIGHT);
+        let offset = match button {
+            LegendButton::Visibility => 0.0,
+            LegendButton::Lock => Self::LEGEND_BUTTON_SIZE + 4.0,
+        };
+        Rect::new(
+            origin.x + offset,
+            origin.y,
+            Self::LEGEND_BUTTON_SIZE,
+            Self::LEGEND_BUTTON_SIZE,
+        )
+    }
+
+    /// `pos` must be in screen space.
+    fn pick_legend(&self, pos: Vector2<f32>) -> Option<(usize, LegendButton)> {
+        if !self.has_legend() {
+            return None;
+        }
 
-                if (right_handle_pos - pos).norm() <= self.key_size * 0.5 {
-                    return Some(PickResult::RightTangent(i));
+        for curve in 0..self.curves.len() {
+            for button in [LegendButton::Visibility, LegendButton::Lock] {
+                if self.legend_button_bounds(curve, button).contains(pos) {
+                    return Some((curve, button));
                 }
             }
         }
         None
     }
 
-    fn tangent_screen_position(&self, angle: f32, key_position: Vector2<f32>) -> Vector2<f32> {
+    fn toggle_legend_button(&self, curve: usize, button: LegendButton, ui: &UserInterface) {
+        if let Some(style) = self.styles.get(curve) {
+            ui.send_message(match button {
+                LegendButton::Visibility => CurveEditorMessage::curve_visibility(
+                    self.handle,
+                    MessageDirection::ToWidget,
+                    curve,
+                    !style.visible,
+                ),
+                LegendButton::Lock => CurveEditorMessage::curve_lock(
+                    self.handle,
+                    MessageDirection::ToWidget,
+                    curve,
+                    !style.locked,
+                ),
+            });
+        }
+    }
+
+    // Handles of weighted tangents are scaled by their weights, default weight gives `handle_radius`.
+    // `angle` is in screen space, where Y axis is flipped, so angles of tangents must be negated.
+    fn tangent_screen_position(
+        &self,
+        angle: f32,
+        key_position: Vector2<f32>,
+        weight: f32,
+    ) -> Vector2<f32> {
         self.point_to_screen_space(key_position)
-            + Vector2::new(angle.cos(), angle.sin()).scale(self.handle_radius)
+            + Vector2::new(angle.cos(), angle.sin())
+                .scale(self.handle_radius * weight / TangentMode::DEFAULT_WEIGHT)
     }
 
-    fn send_curve(&self, ui: &UserInterface) {
+    fn send_curves(&self, ui: &UserInterface) {
         ui.send_message(CurveEditorMessage::sync(
             self.handle,
             MessageDirection::FromWidget,
-            self.key_container.curve(),
+            self.curves.iter().map(|c| c.curve()).collect(),
         ));
     }
 
@@ -915,6 +2835,87 @@ impl CurveEditor {
         );
     }
 
+    // Shades the regions outside of the value/time bounds.
+    fn draw_out_of_bounds_regions(&self, ctx: &mut DrawingContext) {
+        if self.x_bounds.is_none() && self.y_bounds.is_none() {
+            return;
+        }
+
+        let screen_bounds = self.screen_bounds();
+        let left = screen_bounds.x();
+        let top = screen_bounds.y();
+        let right = left + screen_bounds.w();
+        let bottom = top + screen_bounds.h();
+
+        let mut push_rect = |x0: f32, y0: f32, x1: f32, y1: f32| {
+            let (x0, x1) = (x0.max(left), x1.min(right));
+            let (y0, y1) = (y0.max(top), y1.min(bottom));
+            if x1 > x0 && y1 > y0 {
+                ctx.push_rect_filled(&Rect::new(x0, y0, x1 - x0, y1 - y0), None);
+            }
+        };
+
+        if let Some(x_bounds) = self.x_bounds.as_ref() {
+            let start = self
+                .point_to_screen_space(Vector2::new(x_bounds.start, 0.0))
+                .x;
+            let end = self
+                .point_to_screen_space(Vector2::new(x_bounds.end, 0.0))
+                .x;
+            push_rect(left, top, start, bottom);
+            push_rect(end, top, right, bottom);
+        }
+
+        if let Some(y_bounds) = self.y_bounds.as_ref() {
+            // Y axis is flipped in screen space.
+            let start = self
+                .point_to_screen_space(Vector2::new(0.0, y_bounds.start))
+                .y;
+            let end = self
+                .point_to_screen_space(Vector2::new(0.0, y_bounds.end))
+                .y;
+            push_rect(left, start, right, bottom);
+            push_rect(left, top, right, end);
+        }
+
+        ctx.commit(
+            self.clip_bounds(),
+            Brush::Solid(Color::from_rgba(0, 0, 0, 70)),
+            CommandTexture::None,
+            None,
+        );
+    }
+
+    fn draw_highlight_range(&self, ctx: &mut DrawingContext) {
+        let Some(range) = self.highlight_range.as_ref() else {
+            return;
+        };
+
+        let screen_bounds = self.screen_bounds();
+        let start = self.point_to_screen_space(Vector2::new(range.start, 0.0)).x;
+        let end = self.point_to_screen_space(Vector2::new(range.end, 0.0)).x;
+        let top = screen_bounds.y();
+        let bottom = top + screen_bounds.h();
+
+        ctx.push_rect_filled(&Rect::new(start, top, end - start, screen_bounds.h()), None);
+        ctx.commit(
+            self.clip_bounds(),
+            Brush::Solid(Color::from_rgba(80, 140, 220, 40)),
+            CommandTexture::None,
+            None,
+        );
+
+        for x in [start, end] {
+            ctx.push_line(Vector2::new(x, top), Vector2::new(x, bottom), 1.0);
+        }
+        ctx.commit(
+            self.clip_bounds(),
+            Brush::Solid(Color::from_rgba(80, 140, 220, 160)),
+            CommandTexture::None,
+            None,
+        );
+    }
+
     fn draw_highlight_zones(&self, ctx: &mut DrawingContext) {
         for zone in self.highlight_zones.iter() {
             let left_top_corner = self.point_to_screen_space(zone.rect.left_top_corner());
@@ -939,105 +2940,172 @@ impl CurveEditor {
 
     fn draw_grid(&self, ctx: &mut DrawingContext) {
         let screen_bounds = self.screen_bounds();
+        let a = self.point_to_local_space(screen_bounds.left_top_corner());
+        let b = self.point_to_local_space(screen_bounds.right_bottom_corner());
+        let (min, max) = (a.inf(&b), a.sup(&b));
 
-        let step_size_x = self.grid_size.x / self.zoom.x;
-        let step_size_y = self.grid_size.y / self.zoom.y;
-
-        let mut local_left_bottom = self.point_to_local_space(screen_bounds.left_top_corner());
-        let local_left_bottom_n = local_left_bottom;
-        local_left_bottom.x = round_to_step(local_left_bottom.x, step_size_x);
-        local_left_bottom.y = round_to_step(local_left_bottom.y, step_size_y);
-
-        let mut local_right_top = self.point_to_local_space(screen_bounds.right_bottom_corner());
-        local_right_top.x = round_to_step(local_right_top.x, step_size_x);
-        local_right_top.y = round_to_step(local_right_top.y, step_size_y);
-
-        let w = (local_right_top.x - local_left_bottom.x).abs();
-        let h = (local_right_top.y - local_left_bottom.y).abs();
+        let (major_x, minor_x_visibility) = grid_step(self.zoom.x, self.grid_size.x);
+        let (major_y, minor_y_visibility) = grid_step(self.zoom.y, self.grid_size.y);
 
-        let nw = ((w / step_size_x).ceil()) as usize;
-        let nh = ((h / step_size_y).ceil()) as usize;
+        let vertical_line = |x: f32| {
+            (
+                self.point_to_screen_space(Vector2::new(x, min.y)),
+                self.point_to_screen_space(Vector2::new(x, max.y)),
+            )
+        };
+        let horizontal_line = |y: f32| {
+            (
+                self.point_to_screen_space(Vector2::new(min.x, y)),
+                self.point_to_screen_space(Vector2::new(max.x, y)),
+            )
+        };
 
-        for ny in 0..=nh {
-            let k = ny as f32 / (nh) as f32;
-            let y = local_left_bottom.y - k * h;
-            ctx.push_line(
-                self.point_to_screen_space(Vector2::new(local_left_bottom.x - step_size_x, y)),
-                self.point_to_screen_space(Vector2::new(local_right_top.x + step_size_x, y)),
-                1.0,
+        // Minor lines subdivide major lines into 10 parts and fade out when they get too dense.
+        for (visibility, major, lower, upper, vertical) in [
+            (minor_x_visibility, major_x, min.x, max.x, true),
+            (minor_y_visibility, major_y, min.y, max.y, false),
+        ] {
+            let Brush::Solid(color) = self.grid_brush else {
+                break;
+            };
+            if visibility <= 0.0 {
+                continue;
+            }
+            for (i, value) in grid_lines(lower, upper, major / 10.0) {
+                if i % 10 != 0 {
+                    let (begin, end) = if vertical {
+                        vertical_line(value)
+                    } else {
+                        horizontal_line(value)
+                    };
+                    ctx.push_line(begin, end, 1.0);
+                }
+            }
+            ctx.commit(
+                self.clip_bounds(),
+                Brush::Solid(color.with_new_alpha((color.a as f32 * visibility) as u8)),
+                CommandTexture::None,
+                None,
             );
         }
 
-        for nx in 0..=nw {
-            let k = nx as f32 / (nw) as f32;
-            let x = local_left_bottom.x + k * w;
-            ctx.push_line(
-                self.point_to_screen_space(Vector2::new(x, local_left_bottom.y + step_size_y)),
-                self.point_to_screen_space(Vector2::new(x, local_right_top.y - step_size_y)),
-                1.0,
-            );
+        for (_, x) in grid_lines(min.x, max.x, major_x) {
+            let (begin, end) = vertical_line(x);
+            ctx.push_line(begin, end, 1.0);
         }
+        for (_, y) in grid_lines(min.y, max.y, major_y) {
+            let (begin, end) = horizontal_line(y);
+            ctx.push_line(begin, end, 1.0);
+        }
+        ctx.commit(
+            self.clip_bounds(),
+            self.grid_brush.clone(),
+            CommandTexture::None,
+            None,
+        );
 
         // Draw main axes.
-        let vb = self.point_to_screen_space(Vector2::new(0.0, -10e6));
-        let ve = self.point_to_screen_space(Vector2::new(0.0, 10e6));
-        ctx.push_line(vb, ve, 2.0);
-
-        let hb = self.point_to_screen_space(Vector2::new(-10e6, 0.0));
-        let he = self.point_to_screen_space(Vector2::new(10e6, 0.0));
-        ctx.push_line(hb, he, 2.0);
-
+        let (begin, end) = vertical_line(0.0);
+        ctx.push_line(begin, end, 2.0);
+        let (begin, end) = horizontal_line(0.0);
+        ctx.push_line(begin, end, 2.0);
         ctx.commit(
             self.clip_bounds(),
-            self.grid_brush.clone(),
+            Brush::Solid(Color::from_rgba(150, 150, 150, 120)),
             CommandTexture::None,
             None,
         );
 
-        // Draw values.
+        // Draw values of major lines.
         let mut text = self.text.borrow_mut();
 
         if self.show_y_values {
-            for ny in 0..=nh {
-                let k = ny as f32 / (nh) as f32;
-                let y = local_left_bottom.y - k * h;
-                text.set_text(format!("{:.1}", y)).build();
+            let decimals = grid_step_decimals(major_y);
+            for (_, y) in grid_lines(min.y, max.y, major_y) {
+                text.set_text(format!("{:.*}", decimals, y)).build();
                 ctx.draw_text(
                     self.clip_bounds(),
-                    self.point_to_screen_space(Vector2::new(local_left_bottom_n.x, y)),
+                    self.point_to_screen_space(Vector2::new(min.x, y)),
                     &text,
                 );
             }
         }
 
         if self.show_x_values {
-            for nx in 0..=nw {
-                let k = nx as f32 / (nw) as f32;
-                let x = local_left_bottom.x + k * w;
-                text.set_text(format!("{:.1}", x)).build();
+            let decimals = grid_step_decimals(major_x);
+            let bottom = screen_bounds.y() + screen_bounds.h();
+            for (_, x) in grid_lines(min.x, max.x, major_x) {
+                let size = text.set_text(format!("{:.*}", decimals, x)).build();
                 ctx.draw_text(
                     self.clip_bounds(),
-                    self.point_to_screen_space(Vector2::new(x, local_left_bottom_n.y)),
+                    Vector2::new(
+                        self.point_to_screen_space(Vector2::new(x, 0.0)).x,
+                        bottom - size.y,
+                    ),
                     &text,
                 );
             }
         }
     }
 
-    fn draw_curve(&self, ctx: &mut DrawingContext) {
-        let screen_bounds = self.screen_bounds();
-        let draw_keys = self.key_container.keys();
+    fn draw_curves(&self, ctx: &mut DrawingContext) {
+        for (curve, container) in self.visible_curves() {
+            let thickness = if self.hovered_curve == Some(curve) {
+                2.0
+            } else {
+                1.0
+            };
+            self.draw_curve(ctx, container, self.curve_brush(curve), thickness);
+        }
+    }
+
+    // Draws the extrapolated part of the curve between the given screen-space X coordinates as a
+    // dashed line.
+    fn draw_extrapolation(&self, ctx: &mut DrawingContext, curve: &Curve, from: f32, to: f32) {
+        const STEP: f32 = 2.0;
+        // Amount of steps in a dash and in a gap between dashes.
+        const DASH_STEPS: usize = 3;
+
+        let screen_y = self.screen_bounds().y();
+        let screen_point = |x: f32| {
+            let location = self.point_to_local_space(Vector2::new(x, screen_y)).x;
+            self.point_to_screen_space(Vector2::new(location, curve.value_at(location)))
+        };
 
-        if let Some(first) = draw_keys.first() {
-            let screen_pos = self.point_to_screen_space(first.position);
-            ctx.push_line(Vector2::new(0.0, screen_pos.y), screen_pos, 1.0);
+        let steps = ((to - from) / STEP).ceil().max(0.0) as usize;
+        let mut prev = screen_point(from);
+        for i in 0..steps {
+            let next = screen_point((from + (i + 1) as f32 * STEP).min(to));
+            if (i / DASH_STEPS) % 2 == 0 {
+                ctx.push_line(prev, next, 1.0);
+            }
+            prev = next;
         }
-        if let Some(last) = draw_keys.last() {
-            let screen_pos = self.point_to_screen_space(last.position);
-            ctx.push_line(
-                screen_pos,
-                Vector2::new(screen_bounds.x() + screen_bounds.w(), screen_pos.y),
-                1.0,
+    }
+
+    fn draw_curve(
+        &self,
+        ctx: &mut DrawingContext,
+        container: &KeyContainer,
+        brush: Brush,
+        thickness: f32,
+    ) {
+        let screen_bounds = self.screen_bounds();
+        let draw_keys = container.keys();
+
+        if let (Some(first), Some(last)) = (draw_keys.first(), draw_keys.last()) {
+            let curve = container.curve();
+            self.draw_extrapolation(
+                ctx,
+                &curve,
+                screen_bounds.x(),
+                self.point_to_screen_space(first.position).x,
+            );
+            self.draw_extrapolation(
+                ctx,
+                &curve,
+                self.point_to_screen_space(last.position).x,
+                screen_bounds.x() + screen_bounds.w(),
             );
         }
 
@@ -1048,22 +3116,20 @@ impl CurveEditor {
             let left_pos = self.point_to_screen_space(left.position);
             let right_pos = self.point_to_screen_space(right.position);
 
-            let steps = ((right_pos.x - left_pos.x).abs() / 2.0) as usize;
-
             match (&left.kind, &right.kind) {
                 // Constant-to-any is depicted as two straight lines.
                 (CurveKeyKind::Constant, CurveKeyKind::Constant)
                 | (CurveKeyKind::Constant, CurveKeyKind::Linear)
                 | (CurveKeyKind::Constant, CurveKeyKind::Cubic { .. }) => {
-                    ctx.push_line(left_pos, Vector2::new(right_pos.x, left_pos.y), 1.0);
-                    ctx.push_line(Vector2::new(right_pos.x, left_pos.y), right_pos, 1.0);
+                    ctx.push_line(left_pos, Vector2::new(right_pos.x, left_pos.y), thickness);
+                    ctx.push_line(Vector2::new(right_pos.x, left_pos.y), right_pos, thickness);
                 }
 
                 // Linear-to-any is depicted as a straight line.
                 (CurveKeyKind::Linear, CurveKeyKind::Constant)
                 | (CurveKeyKind::Linear, CurveKeyKind::Linear)
                 | (CurveKeyKind::Linear, CurveKeyKind::Cubic { .. }) => {
-                    ctx.push_line(left_pos, right_pos, 1.0)
+                    ctx.push_line(left_pos, right_pos, thickness)
                 }
 
                 // Cubic-to-constant and cubic-to-linear is depicted as Hermite spline with right tangent == 0.0.
@@ -1080,7 +3146,18 @@ impl CurveEditor {
                         ..
                     },
                     CurveKeyKind::Linear,
-                ) => draw_cubic(left_pos, *left_tangent, right_pos, 0.0, steps, ctx),
+                ) => draw_cubic(
+                    left_pos,
+                    *left_tangent,
+                    right_pos,
+                    0.0,
+                    (
+                        left.tangent_mode.effective_right_weight(),
+                        TangentMode::DEFAULT_WEIGHT,
+                    ),
+                    thickness,
+                    ctx,
+                ),
 
                 // Cubic-to-cubic is depicted as Hermite spline.
                 (
@@ -1097,22 +3174,30 @@ impl CurveEditor {
                     *left_tangent,
                     right_pos,
                     *right_tangent,
-                    steps,
+                    (
+                        left.tangent_mode.effective_right_weight(),
+                        right.tangent_mode.effective_left_weight(),
+                    ),
+                    thickness,
                     ctx,
                 ),
             }
         }
-        ctx.commit(
-            self.clip_bounds(),
-            self.foreground(),
-            CommandTexture::None,
-            None,
-        );
+        ctx.commit(self.clip_bounds(), brush, CommandTexture::None, None);
     }
 
     fn draw_keys(&self, ctx: &mut DrawingContext) {
-        let keys_to_draw = self.key_container.keys();
+        for (curve, container) in self.visible_curves() {
+            self.draw_curve_keys(ctx, curve, container.keys());
+        }
+    }
 
+    fn draw_curve_keys(
+        &self,
+        ctx: &mut DrawingContext,
+        curve: usize,
+        keys_to_draw: &[CurveKeyView],
+    ) {
         for (i, key) in keys_to_draw.iter().enumerate() {
             let origin = self.point_to_screen_space(key.position);
             let size = Vector2::new(self.key_size, self.key_size);
@@ -1132,10 +3217,17 @@ impl CurveEditor {
             if let Some(selection) = self.selection.as_ref() {
                 match selection {
                     Selection::Keys { keys } => {
-                        selected = keys.contains(&key.id);
+                        selected = keys.contains(&SelectedKey { curve, id: key.id });
+                    }
+                    Selection::LeftTangent {
+                        curve: selected_curve,
+                        key,
                     }
-                    Selection::LeftTangent { key } | Selection::RightTangent { key } => {
-                        selected = i == *key;
+                    | Selection::RightTangent {
+                        curve: selected_curve,
+                        key,
+                    } => {
+                        selected = curve == *selected_curve && i == *key;
                     }
                 }
             }
@@ -1160,10 +3252,12 @@ impl CurveEditor {
                     right_tangent,
                 } = key.kind
                 {
+                    let mode = key.tangent_mode;
                     if show_left {
                         let left_handle_pos = self.tangent_screen_position(
-                            wrap_angle(left_tangent.atan()) + std::f32::consts::PI,
+                            wrap_angle((-left_tangent).atan()) + std::f32::consts::PI,
                             key.position,
+                            mode.effective_left_weight(),
                         );
                         ctx.push_line(origin, left_handle_pos, 1.0);
                         ctx.push_circle(
@@ -1176,8 +3270,9 @@ impl CurveEditor {
 
                     if show_right {
                         let right_handle_pos = self.tangent_screen_position(
-                            wrap_angle(right_tangent.atan()),
+                            wrap_angle((-right_tangent).atan()),
                             key.position,
+                            mode.effective_right_weight(),
                         );
                         ctx.push_line(origin, right_handle_pos, 1.0);
                         ctx.push_circle(
@@ -1203,12 +3298,88 @@ impl CurveEditor {
         }
     }
 
+    fn draw_cursor(&self, ctx: &mut DrawingContext) {
+        let Some(location) = self.cursor_position else {
+            return;
+        };
+
+        let screen_bounds = self.screen_bounds();
+        let x = self.point_to_screen_space(Vector2::new(location, 0.0)).x;
+        ctx.push_line(
+            Vector2::new(x, screen_bounds.y()),
+            Vector2::new(x, screen_bounds.y() + screen_bounds.h()),
+            1.0,
+        );
+        ctx.commit(
+            self.clip_bounds(),
+            Brush::Solid(Color::opaque(230, 160, 40)),
+            CommandTexture::None,
+            None,
+        );
+
+        // Mark evaluated values of the curves at the cursor.
+        let mut text = self.legend_text.borrow_mut();
+        for (curve, container) in self.visible_curves() {
+            if container.keys().is_empty() {
+                continue;
+            }
+
+            let value = container.curve().value_at(location);
+            let position = self.point_to_screen_space(Vector2::new(location, value));
+            ctx.push_circle(position, self.key_size * 0.5, 10, Default::default());
+            ctx.commit(
+                self.clip_bounds(),
+                self.curve_brush(curve),
+                CommandTexture::None,
+                None,
+            );
+
+            text.set_text(format!("{:.3}", value)).build();
+            ctx.draw_text(
+                self.clip_bounds(),
+                position + Vector2::new(self.key_size, -self.key_size * 2.0),
+                &text,
+            );
+        }
+    }
+
+    fn draw_transform_box(&self, ctx: &mut DrawingContext) {
+        if matches!(
+            self.operation_context,
+            Some(OperationContext::BoxSelection { .. })
+        ) {
+            return;
+        }
+        let Some(bounds) = self.transform_box_screen_bounds() else {
+            return;
+        };
+
+        ctx.push_rect(&bounds, 1.0);
+        for (_, position) in Self::transform_box_handles(bounds) {
+            ctx.push_rect_filled(
+                &Rect::new(
+                    position.x - self.key_size * 0.5,
+                    position.y - self.key_size * 0.5,
+                    self.key_size,
+                    self.key_size,
+                ),
+                None,
+            );
+        }
+        ctx.commit(
+            self.clip_bounds(),
+            Brush::Solid(Color::opaque(200, 170, 80)),
+            CommandTexture::None,
+            None,
+        );
+    }
+
     fn draw_operation(&self, ctx: &mut DrawingContext) {
         if let Some(OperationContext::BoxSelection { min, max, .. }) =
             self.operation_context.as_ref()
         {
-            let min = self.point_to_screen_space(min.get());
-            let max = self.point_to_screen_space(max.get());
+            let min = min.get();
+            let max = max.get();
             let rect = Rect::new(min.x, min.y, max.x - min.x, max.y - min.y);
 
             ctx.push_rect(&rect, 1.0);
@@ -1220,11 +3391,74 @@ impl CurveEditor {
             );
         }
     }
+
+    fn draw_legend(&self, ctx: &mut DrawingContext) {
+        if !self.has_legend() {
+            return;
+        }
+
+        let mut text = self.legend_text.borrow_mut();
+        for (curve, container) in self.curves.iter().enumerate() {
+            let style = self.styles.get(curve).cloned().unwrap_or_default();
+
+            for (button, enabled, label) in [
+                (LegendButton::Visibility, style.visible, "V"),
+                (LegendButton::Lock, style.locked, "L"),
+            ] {
+                let bounds = self.legend_button_bounds(curve, button);
+                if enabled {
+                    ctx.push_rect_filled(&bounds, None);
+                    ctx.commit(
+                        self.clip_bounds(),
+                        Brush::Solid(Color::opaque(70, 70, 70)),
+                        CommandTexture::None,
+                        None,
+                    );
+                }
+                ctx.push_rect(&bounds, 1.0);
+                ctx.commit(
+                    self.clip_bounds(),
+                    Brush::Solid(Color::opaque(120, 120, 120)),
+                    CommandTexture::None,
+                    None,
+                );
+                text.set_text(label).build();
+                ctx.draw_text(
+                    self.clip_bounds(),
+                    bounds.position + Vector2::new(3.0, 0.0),
+                    &text,
+                );
+            }
+
+            let lock_bounds = self.legend_button_bounds(curve, LegendButton::Lock);
+            let x = lock_bounds.x() + lock_bounds.w() + 4.0;
+            let y = lock_bounds.y() + lock_bounds.h() * 0.5;
+            ctx.push_line(Vector2::new(x, y), Vector2::new(x + 16.0, y), 2.0);
+            ctx.commit(
+                self.clip_bounds(),
+                self.curve_brush(curve),
+                CommandTexture::None,
+                None,
+            );
+
+            if container.name().is_empty() {
+                text.set_text(format!("Curve {}", curve)).build();
+            } else {
+                text.set_text(container.name()).build();
+            }
+            ctx.draw_text(
+                self.clip_bounds(),
+                Vector2::new(x + 20.0, lock_bounds.y()),
+                &text,
+            );
+        }
+    }
 }
 
 pub struct CurveEditorBuilder {
     widget_builder: WidgetBuilder,
-    curve: Curve,
+    curves: Vec<Curve>,
+    curve_styles: Vec<CurveStyle>,
     view_position: Vector2<f32>,
     zoom: f32,
     view_bounds: Option<Rect<f32>>,
@@ -1234,13 +3468,20 @@ pub struct CurveEditorBuilder {
     min_zoom: Vector2<f32>,
     max_zoom: Vector2<f32>,
     highlight_zones: Vec<HighlightZone>,
+    snap_step: Vector2<f32>,
+    x_bounds: Option<Range<f32>>,
+    y_bounds: Option<Range<f32>>,
+    read_only: bool,
+    input_bindings: InputBindings,
+    view_bookmarks: Vec<ViewBookmark>,
 }
 
 impl CurveEditorBuilder {
     pub fn new(widget_builder: WidgetBuilder) -> Self {
         Self {
             widget_builder,
-            curve: Default::default(),
+            curves: vec![Default::default()],
+            curve_styles: Default::default(),
             view_position: Default::default(),
             zoom: 1.0,
             view_bounds: None,
@@ -1250,11 +3491,30 @@ impl CurveEditorBuilder {
             min_zoom: Vector2::new(0.001, 0.001),
             max_zoom: Vector2::new(1000.0, 1000.0),
             highlight_zones: Default::default(),
+            snap_step: Default::default(),
+            x_bounds: None,
+            y_bounds: None,
+            read_only: false,
+            input_bindings: Default::default(),
+            view_bookmarks: Default::default(),
         }
     }
 
+    /// Sets a single curve to edit.
     pub fn with_curve(mut self, curve: Curve) -> Self {
-        self.curve = curve;
+        self.curves = vec![curve];
+        self
+    }
+
+    /// Sets a set of curves to edit.
+    pub fn with_curves(mut self, curves: Vec<Curve>) -> Self {
+        self.curves = curves;
+        self
+    }
+
+    /// Sets display settings of the curves, `i`-th style is used for `i`-th curve.
+    pub fn with_curve_styles(mut self, styles: Vec<CurveStyle>) -> Self {
+        self.curve_styles = styles;
         self
     }
 
@@ -1284,6 +3544,8 @@ impl CurveEditorBuilder {
         self
     }
 
+    /// Sets minimal distance (in pixels) between major grid lines along each axis. Grid steps are
+    /// powers of ten that adapt to the zoom level, default is `50.0` pixels.
     pub fn with_grid_size(mut self, size: Vector2<f32>) -> Self {
         self.grid_size = size;
         self
@@ -1304,19 +3566,106 @@ impl CurveEditorBuilder {
         self
     }
 
+    /// Sets snapping steps (in values space) of the X and Y axes that are used when dragging keys.
+    /// Zero step (default) disables snapping along the respective axis.
+    pub fn with_snap_step(mut self, snap_step: Vector2<f32>) -> Self {
+        self.snap_step = snap_step;
+        self
+    }
+
+    /// Sets allowed range of locations (X axis) of the keys. Keys cannot be dragged outside of the
+    /// range, the view cannot be panned too far from it and the region outside of it is shaded.
+    pub fn with_x_bounds(mut self, bounds: Range<f32>) -> Self {
+        self.x_bounds = Some(bounds);
+        self
+    }
+
+    /// Sets allowed range of values (Y axis) of the keys, for example `0.0..1.0` for curves that
+    /// must stay normalized. See [`Self::with_x_bounds`] for more info.
+    pub fn with_y_bounds(mut self, bounds: Range<f32>) -> Self {
+        self.y_bounds = Some(bounds);
+        self
+    }
+
+    /// Enables or disables read-only mode, see [`CurveEditorMessage::ReadOnly`] for more info.
+    pub fn with_read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
+    /// Sets input bindings of the editor, see [`InputBindings`] for more info.
+    pub fn with_input_bindings(mut self, input_bindings: InputBindings) -> Self {
+        self.input_bindings = input_bindings;
+        self
+    }
+
+    /// Sets view bookmarks of the editor, see [`CurveEditorMessage::StoreViewBookmark`] for more info.
+    pub fn with_view_bookmarks(mut self, view_bookmarks: Vec<ViewBookmark>) -> Self {
+        self.view_bookmarks = view_bookmarks;
+        self
+    }
+
     pub fn build(mut self, ctx: &mut BuildContext) -> Handle<UiNode> {
-        let keys = KeyContainer::from(&self.curve);
+        let curves = self
+            .curves
+            .iter()
+            .map(KeyContainer::from)
+            .collect::<Vec<_>>();
+        let mut styles = self.curve_styles;
+        styles.resize(curves.len(), Default::default());
 
         let add_key;
         let remove;
+        let copy;
+        let cut;
+        let paste;
         let make_constant;
         let make_linear;
         let make_cubic;
+        let broken_tangents;
+        let weighted_tangents;
+        let flatten_tangents;
+        let auto_smooth_tangents;
         let key;
         let zoom_to_fit;
         let key_properties;
         let key_value;
         let key_location;
+        let properties;
+        let export_keys;
+        let import_keys;
+        let import_keys_replace;
+        let view_bookmarks;
+        let view_bookmark_items = make_view_bookmark_items(&self.view_bookmarks, ctx);
+        let read_only = self.read_only;
+        let mut extrapolation_items = Vec::new();
+        let mut make_extrapolation_menu = |ctx: &mut BuildContext, pre: bool| {
+            let items = [
+                ("Constant", CurveExtrapolation::Constant),
+                ("Linear", CurveExtrapolation::Linear),
+                ("Loop", CurveExtrapolation::Loop),
+                ("Ping-Pong", CurveExtrapolation::PingPong),
+            ]
+            .into_iter()
+            .map(|(name, extrapolation)| {
+                let item = MenuItemBuilder::new(WidgetBuilder::new().with_enabled(!read_only))
+                    .with_content(MenuItemContent::text(name))
+                    .build(ctx);
+                extrapolation_items.push((item, pre, extrapolation));
+                item
+            })
+            .collect::<Vec<_>>();
+            MenuItemBuilder::new(WidgetBuilder::new())
+                .with_content(MenuItemContent::text(if pre {
+                    "Pre Infinity..."
+                } else {
+                    "Post Infinity..."
+                }))
+                .with_items(items)
+                .build(ctx)
+        };
+        let pre_infinity = make_extrapolation_menu(ctx, true);
+        let post_infinity = make_extrapolation_menu(ctx, false);
         let context_menu = PopupBuilder::new(WidgetBuilder::new())
             .with_content(
                 StackPanelBuilder::new(
@@ -1376,9 +3725,11 @@ impl CurveEditorBuilder {
                             key_properties
                         })
                         .with_child({
-                            add_key = MenuItemBuilder::new(WidgetBuilder::new())
-                                .with_content(MenuItemContent::text("Add Key"))
-                                .build(ctx);
+                            add_key = MenuItemBuilder::new(
+                                WidgetBuilder::new().with_enabled(!self.read_only),
+                            )
+                            .with_content(MenuItemContent::text("Add Key"))
+                            .build(ctx);
                             add_key
                         })
                         .with_child({
@@ -1387,6 +3738,26 @@ impl CurveEditorBuilder {
                                 .build(ctx);
                             remove
                         })
+                        .with_child({
+                            copy = MenuItemBuilder::new(WidgetBuilder::new().with_enabled(false))
+                                .with_content(MenuItemContent::text_with_shortcut("Copy", "Ctrl+C"))
+                                .build(ctx);
+                            copy
+                        })
+                        .with_child({
+                            cut = MenuItemBuilder::new(WidgetBuilder::new().with_enabled(false))
+                                .with_content(MenuItemContent::text_with_shortcut("Cut", "Ctrl+X"))
+                                .build(ctx);
+                            cut
+                        })
+                        .with_child({
+                            paste = MenuItemBuilder::new(
+                                WidgetBuilder::new().with_enabled(!self.read_only),
+                            )
+                            .with_content(MenuItemContent::text_with_shortcut("Paste", "Ctrl+V"))
+                            .build(ctx);
+                            paste
+                        })
                         .with_child({
                             key = MenuItemBuilder::new(WidgetBuilder::new().with_enabled(false))
                                 .with_content(MenuItemContent::text("Key..."))
@@ -1409,24 +3780,107 @@ impl CurveEditorBuilder {
                                             .build(ctx);
                                         make_cubic
                                     },
+                                    {
+                                        flatten_tangents =
+                                            MenuItemBuilder::new(WidgetBuilder::new())
+                                                .with_content(MenuItemContent::text(
+                                                    "Flatten Tangents",
+                                                ))
+                                                .build(ctx);
+                                        flatten_tangents
+                                    },
+                                    {
+                                        auto_smooth_tangents =
+                                            MenuItemBuilder::new(WidgetBuilder::new())
+                                                .with_content(MenuItemContent::text("Auto-Smooth"))
+                                                .build(ctx);
+                                        auto_smooth_tangents
+                                    },
+                                    {
+                                        broken_tangents =
+                                            MenuItemBuilder::new(WidgetBuilder::new())
+                                                .with_content(MenuItemContent::text(
+                                                    "Broken Tangents",
+                                                ))
+                                                .build(ctx);
+                                        broken_tangents
+                                    },
+                                    {
+                                        weighted_tangents =
+                                            MenuItemBuilder::new(WidgetBuilder::new())
+                                                .with_content(MenuItemContent::text(
+                                                    "Weighted Tangents",
+                                                ))
+                                                .build(ctx);
+                                        weighted_tangents
+                                    },
                                 ])
                                 .build(ctx);
                             key
                         })
+                        .with_child(pre_infinity)
+                        .with_child(post_infinity)
                         .with_child({
                             zoom_to_fit = MenuItemBuilder::new(WidgetBuilder::new())
                                 .with_content(MenuItemContent::text("Zoom To Fit"))
                                 .build(ctx);
                             zoom_to_fit
-                        }),
+                        })
+                        .with_child({
+                            view_bookmarks = MenuItemBuilder::new(
+                                WidgetBuilder::new().with_enabled(!view_bookmark_items.is_empty()),
+                            )
+                            .with_content(MenuItemContent::text("View Bookmarks"))
+                            .with_items(view_bookmark_items.clone())
+                            .build(ctx);
+                            view_bookmarks
+                        })
+                        .with_child({
+                            properties =
+                                MenuItemBuilder::new(WidgetBuilder::new().with_enabled(false))
+                                    .with_content(MenuItemContent::text("Properties..."))
+                                    .build(ctx);
+                            properties
+                        })
+                        .with_child(
+                            MenuItemBuilder::new(WidgetBuilder::new())
+                                .with_content(MenuItemContent::text("Import/Export"))
+                                .with_items(vec![
+                                    {
+                                        export_keys = MenuItemBuilder::new(WidgetBuilder::new())
+                                            .with_content(MenuItemContent::text("Export..."))
+                                            .build(ctx);
+                                        export_keys
+                                    },
+                                    {
+                                        import_keys = MenuItemBuilder::new(
+                                            WidgetBuilder::new().with_enabled(!read_only),
+                                        )
+                                        .with_content(MenuItemContent::text("Import (Merge)..."))
+                                        .build(ctx);
+                                        import_keys
+                                    },
+                                    {
+                                        import_keys_replace = MenuItemBuilder::new(
+                                            WidgetBuilder::new().with_enabled(!read_only),
+                                        )
+                                        .with_content(MenuItemContent::text("Import (Replace)..."))
+                                        .build(ctx);
+                                        import_keys_replace
+                                    },
+                                ])
+                                .build(ctx),
+                        ),
                 )
                 .build(ctx),
             )
             .build(ctx);
         let context_menu = RcUiNodeHandle::new(context_menu, ctx.sender());
 
-        if self.widget_builder.foreground.is_none() {
-            self.widget_builder.foreground = Some(Brush::Solid(Color::opaque(130, 130, 130)))
+        if !self.widget_builder.has_foreground() {
+            self.widget_builder = self
+                .widget_builder
+                .with_foreground_style(Style::BRUSH_BRIGHT);
         }
 
         let editor = CurveEditor {
@@ -1435,7 +3889,8 @@ impl CurveEditorBuilder {
                 .with_context_menu(context_menu.clone())
                 .with_preview_messages(true)
                 .build(),
-            key_container: keys,
+            curves,
+            styles,
             zoom: Vector2::new(1.0, 1.0),
             view_position: Default::default(),
             view_matrix: Default::default(),
@@ -1453,19 +3908,39 @@ impl CurveEditorBuilder {
                     .with_brush(Brush::Solid(Color::opaque(100, 100, 100)))
                     .build(),
             ),
+            legend_text: RefCell::new(
+                FormattedTextBuilder::new(ctx.default_font())
+                    .with_brush(Brush::Solid(Color::opaque(200, 200, 200)))
+                    .build(),
+            ),
             context_menu: ContextMenu {
                 widget: context_menu,
                 add_key,
                 remove,
+                copy,
+                cut,
+                paste,
                 make_constant,
                 make_linear,
                 make_cubic,
+                broken_tangents,
+                weighted_tangents,
+                flatten_tangents,
+                auto_smooth_tangents,
                 key,
                 zoom_to_fit,
                 key_properties,
                 key_value,
                 key_location,
+                properties,
+                export_keys,
+                import_keys,
+                import_keys_replace,
+                view_bookmarks,
+                view_bookmark_items,
+                extrapolation_items,
             },
+            key_properties_popup: KeyPropertiesPopup::new(ctx),
             view_bounds: self.view_bounds,
             show_x_values: self.show_x_values,
             show_y_values: self.show_y_values,
@@ -1473,9 +3948,125 @@ impl CurveEditorBuilder {
             min_zoom: self.min_zoom,
             max_zoom: self.max_zoom,
             highlight_zones: self.highlight_zones,
+            snap_step: self.snap_step,
+            x_bounds: self.x_bounds,
+            y_bounds: self.y_bounds,
+            cursor_position: None,
+            hovered_curve: None,
+            file_dialog: None,
+            read_only: self.read_only,
+            highlight_range: None,
+            input_bindings: self.input_bindings,
+            view_bookmarks: self.view_bookmarks,
             zoom_to_fit_timer: None,
+            history: Default::default(),
+            nudge_initial_keys: None,
+            last_mouse_pos: Default::default(),
         };
 
         ctx.add_node(UiNode::new(editor))
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        clipboard::LocalClipboard,
+        core::{
+            algebra::Vector2,
+            curve::{Curve, CurveKey, CurveKeyKind},
+        },
+        curve::{CurveEditorBuilder, CurveEditorMessage},
+        message::{KeyCode, KeyboardModifiers, MessageDirection, OsEvent},
+        widget::{WidgetBuilder, WidgetMessage},
+        UserInterface,
+    };
+
+    #[test]
+    fn test_shortcuts_are_handled() {
+        let mut ui = UserInterface::new(Vector2::new(100.0, 100.0));
+        ui.set_clipboard(Box::<LocalClipboard>::default());
+        let editor = CurveEditorBuilder::new(WidgetBuilder::new()).build(&mut ui.build_ctx());
+        ui.send_message(CurveEditorMessage::sync(
+            editor,
+            MessageDirection::ToWidget,
+            vec![Curve::from(vec![CurveKey::new(
+                0.0,
+                1.0,
+                CurveKeyKind::Linear,
+            )])],
+        ));
+        while ui.poll_message().is_some() {}
+
+        let key_down = |ui: &mut UserInterface, key: KeyCode| {
+            ui.send_message(WidgetMessage::key_down(
+                editor,
+                MessageDirection::FromWidget,
+                key,
+            ));
+            let message = ui.poll_message().unwrap();
+            while ui.poll_message().is_some() {}
+            message.handled()
+        };
+
+        // Zoom to fit.
+        assert!(key_down(&mut ui, KeyCode::KeyF));
+        ui.process_os_event(&OsEvent::KeyboardModifiers(KeyboardModifiers {
+            control: true,
+            ..Default::default()
+        }));
+        for key in [
+            KeyCode::KeyZ,
+            KeyCode::KeyY,
+            KeyCode::KeyC,
+            KeyCode::KeyX,
+            KeyCode::KeyV,
+        ] {
+            assert!(key_down(&mut ui, key));
+        }
+    }
+
+    #[test]
+    fn test_undo_redo() {
+        let mut ui = UserInterface::new(Vector2::new(100.0, 100.0));
+        let editor = CurveEditorBuilder::new(WidgetBuilder::new()).build(&mut ui.build_ctx());
+        let key = CurveKey::new(0.0, 1.0, CurveKeyKind::Linear);
+        let id = key.id;
+        ui.send_message(CurveEditorMessage::sync(
+            editor,
+            MessageDirection::ToWidget,
+            vec![Curve::from(vec![key])],
+        ));
+        ui.send_message(CurveEditorMessage::select_keys(
+            editor,
+            MessageDirection::ToWidget,
+            vec![id],
+        ));
+        while ui.poll_message().is_some() {}
+
+        // Returns the value of the key from the last curve reported by the editor.
+        let send = |ui: &mut UserInterface, message| {
+            ui.send_message(message);
+            let mut value = None;
+            while let Some(message) = ui.poll_message() {
+                if let Some(CurveEditorMessage::Sync(curves)) = message.data() {
+                    if message.direction() == MessageDirection::FromWidget {
+                        value = Some(curves[0].keys()[0].value);
+                    }
+                }
+            }
+            value
+        };
+
+        let change_value =
+            CurveEditorMessage::change_selected_keys_value(editor, MessageDirection::ToWidget, 2.0);
+        let undo = CurveEditorMessage::undo(editor, MessageDirection::ToWidget);
+        let redo = CurveEditorMessage::redo(editor, MessageDirection::ToWidget);
+        assert_eq!(send(&mut ui, change_value), Some(2.0));
+        assert_eq!(send(&mut ui, undo.clone()), Some(1.0));
+        // Nothing left to undo.
+        assert_eq!(send(&mut ui, undo), None);
+        assert_eq!(send(&mut ui, redo.clone()), Some(2.0));
+        assert_eq!(send(&mut ui, redo), None);
+    }
+}
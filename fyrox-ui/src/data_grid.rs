@@ -0,0 +1,680 @@
+//! Data grid is a table with columns and rows of arbitrary widgets. See [`DataGrid`] docs for more info and
+//! usage examples.
+
+#![warn(missing_docs)]
+
+use crate::{
+    brush::Brush,
+    core::{algebra::Vector2, math::Rect, pool::Handle},
+    core::{reflect::prelude::*, visitor::prelude::*},
+    define_constructor,
+    draw::{CommandTexture, Draw, DrawingContext},
+    message::{MessageDirection, MouseButton, UiMessage},
+    style::Style,
+    text::TextBuilder,
+    widget::{Widget, WidgetBuilder, WidgetMessage},
+    BuildContext, Control, NodeHandleMapping, Thickness, UiNode, UserInterface, VerticalAlignment,
+    BRUSH_BRIGHT_BLUE, BRUSH_LIGHT, BRUSH_LIGHTEST, BRUSH_PRIMARY,
+};
+use std::{
+    any::{Any, TypeId},
+    cmp::Ordering,
+    ops::{Deref, DerefMut},
+};
+
+/// Maximum horizontal distance (in pixels) between a cursor and a column border to start resizing the column.
+const RESIZE_GRIP_WIDTH: f32 = 4.0;
+
+/// A set of messages for [`DataGrid`] widget.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DataGridMessage {
+    /// Replaces the rows of the data grid. Cells of the old rows, that are not used by the new rows, are removed.
+    Rows(Vec<DataGridRow>),
+    /// Used to change the selected row (with [`MessageDirection::ToWidget`]) or to fetch if the selected row has
+    /// changed (with [`MessageDirection::FromWidget`]).
+    SelectedRow(Option<usize>),
+    /// Used to sort the rows (with [`MessageDirection::ToWidget`]) or to fetch if the rows were sorted, for example
+    /// by clicking on a column header (with [`MessageDirection::FromWidget`]).
+    Sort {
+        /// Index of the column which sort keys will be used to sort the rows.
+        column: usize,
+        /// Sort order.
+        ascending: bool,
+    },
+    /// Used to change the width of a column (with [`MessageDirection::ToWidget`]) or to fetch if the column was
+    /// resized by a user (with [`MessageDirection::FromWidget`]).
+    ColumnWidth {
+        /// Index of the column.
+        column: usize,
+        /// New width of the column.
+        width: f32,
+    },
+}
+
+impl DataGridMessage {
+    define_constructor!(
+        /// Creates [`DataGridMessage::Rows`] message.
+        DataGridMessage:Rows => fn rows(Vec<DataGridRow>), layout: false
+    );
+    define_constructor!(
+        /// Creates [`DataGridMessage::SelectedRow`] message.
+        DataGridMessage:SelectedRow => fn selected_row(Option<usize>), layout: false
+    );
+    define_constructor!(
+        /// Creates [`DataGridMessage::Sort`] message.
+        DataGridMessage:Sort => fn sort(column: usize, ascending: bool), layout: false
+    );
+    define_constructor!(
+        /// Creates [`DataGridMessage::ColumnWidth`] message.
+        DataGridMessage:ColumnWidth => fn column_width(column: usize, width: f32), layout: false
+    );
+}
+
+/// Definition of a column of [`DataGrid`] widget.
+#[derive(Clone, Debug, PartialEq, Visit, Reflect)]
+pub struct DataGridColumn {
+    /// Text of the column header.
+    pub header: String,
+    /// Current width of the column.
+    pub width: f32,
+    /// Minimal width of the column, the column cannot be resized to be narrower than this value.
+    pub min_width: f32,
+    /// A flag, that defines whether the rows could be sorted by this column by clicking on its header or not.
+    pub sortable: bool,
+}
+
+impl Default for DataGridColumn {
+    fn default() -> Self {
+        Self {
+            header: Default::default(),
+            width: 100.0,
+            min_width: 20.0,
+            sortable: true,
+        }
+    }
+}
+
+impl DataGridColumn {
+    /// Creates a new sortable column with the given header and width.
+    pub fn new<S: AsRef<str>>(header: S, width: f32) -> Self {
+        Self {
+            header: header.as_ref().to_owned(),
+            width,
+            ..Default::default()
+        }
+    }
+
+    /// Sets whether the rows could be sorted by this column or not.
+    pub fn with_sortable(mut self, sortable: bool) -> Self {
+        self.sortable = sortable;
+        self
+    }
+
+    /// Sets minimal width of the column.
+    pub fn with_min_width(mut self, min_width: f32) -> Self {
+        self.min_width = min_width;
+        self
+    }
+}
+
+/// A row of [`DataGrid`] widget. Every cell of the row is an arbitrary widget (a cell template), which is placed in
+/// the respective column.
+#[derive(Clone, Debug, Default, PartialEq, Visit, Reflect)]
+pub struct DataGridRow {
+    /// Cells of the row, one per column.
+    pub cells: Vec<Handle<UiNode>>,
+    /// Sort keys of the cells, one per column. Keys, that could be parsed as numbers, are compared as numbers,
+    /// all other keys are compared as strings.
+    pub sort_keys: Vec<String>,
+}
+
+impl DataGridRow {
+    /// Creates a new row from the given pairs of a cell and its sort key.
+    pub fn new<S: AsRef<str>>(cells: impl IntoIterator<Item = (Handle<UiNode>, S)>) -> Self {
+        let (cells, sort_keys) = cells
+            .into_iter()
+            .map(|(cell, key)| (cell, key.as_ref().to_owned()))
+            .unzip();
+        Self { cells, sort_keys }
+    }
+}
+
+fn compare_keys(a: &str, b: &str) -> Ordering {
+    match (a.parse::<f64>(), b.parse::<f64>()) {
+        (Ok(a), Ok(b)) => a.total_cmp(&b),
+        _ => a.cmp(b),
+    }
+}
+
+/// Data grid is a table with columns and rows of arbitrary widgets. It supports sorting by clicking on column headers,
+/// resizing of columns by dragging the borders of their headers and selection of rows. It is useful for list-heavy
+/// panels, that have to show multiple properties of every item.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// # use fyrox_ui::{
+/// #     core::pool::Handle,
+/// #     data_grid::{DataGridBuilder, DataGridColumn, DataGridRow},
+/// #     text::TextBuilder,
+/// #     widget::WidgetBuilder,
+/// #     BuildContext, UiNode,
+/// # };
+/// fn create_data_grid(ctx: &mut BuildContext) -> Handle<UiNode> {
+///     let mut rows = Vec::new();
+///     for (name, time) in [("Footstep", 0.25), ("Jump", 1.5)] {
+///         let name_cell = TextBuilder::new(WidgetBuilder::new()).with_text(name).build(ctx);
+///         let time_cell = TextBuilder::new(WidgetBuilder::new())
+///             .with_text(time.to_string())
+///             .build(ctx);
+///         rows.push(DataGridRow::new([
+///             (name_cell, name.to_string()),
+///             (time_cell, time.to_string()),
+///         ]));
+///     }
+///
+///     DataGridBuilder::new(WidgetBuilder::new())
+///         .with_columns(vec![
+///             DataGridColumn::new("Name", 150.0),
+///             DataGridColumn::new("Time", 80.0),
+///         ])
+///         .with_rows(rows)
+///         .build(ctx)
+/// }
+/// ```
+///
+/// Use [`DataGridMessage`] to change the content of the data grid and to listen to its changes. Data grid does not
+/// scroll its content, put it in a [`crate::scroll_viewer::ScrollViewer`] if needed.
+#[derive(Clone, Visit, Reflect, Debug)]
+pub struct DataGrid {
+    /// Base widget of the data grid.
+    pub widget: Widget,
+    /// Columns of the data grid.
+    pub columns: Vec<DataGridColumn>,
+    /// Rows of the data grid in the current order.
+    pub rows: Vec<DataGridRow>,
+    /// Header widgets of the columns.
+    pub headers: Vec<Handle<UiNode>>,
+    /// Height of the header row.
+    pub header_height: f32,
+    /// Height of every row.
+    pub row_height: f32,
+    /// Index of the selected row.
+    pub selected_row: Option<usize>,
+    /// Index of the column, that was used to sort the rows.
+    pub sort_column: Option<usize>,
+    /// Sort order of the rows.
+    pub sort_ascending: bool,
+    /// A brush, that is used to highlight the selected row.
+    pub selection_brush: Brush,
+    /// Index of a column, that is being resized, initial position of the cursor and initial width of the column.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub resize: Option<(usize, f32, f32)>,
+}
+
+crate::define_widget_deref!(DataGrid);
+
+impl DataGrid {
+    fn column_offsets(&self) -> impl Iterator<Item = (f32, f32)> + '_ {
+        self.columns.iter().scan(0.0, |x, column| {
+            let offset = *x;
+            *x += column.width;
+            Some((offset, column.width))
+        })
+    }
+
+    fn total_width(&self) -> f32 {
+        self.columns.iter().map(|c| c.width).sum()
+    }
+
+    fn row_bounds(&self, row: usize) -> Rect<f32> {
+        Rect::new(
+            0.0,
+            self.header_height + row as f32 * self.row_height,
+            self.total_width(),
+            self.row_height,
+        )
+    }
+
+    fn pick_resize_grip(&self, local_position: Vector2<f32>) -> Option<usize> {
+        self.column_offsets()
+            .position(|(x, width)| (x + width - local_position.x).abs() <= RESIZE_GRIP_WIDTH)
+    }
+
+    fn pick_column(&self, local_position: Vector2<f32>) -> Option<usize> {
+        self.column_offsets()
+            .position(|(x, width)| local_position.x >= x && local_position.x < x + width)
+    }
+
+    fn pick_row(&self, local_position: Vector2<f32>) -> Option<usize> {
+        let y = local_position.y - self.header_height;
+        if y < 0.0 || local_position.x > self.total_width() {
+            return None;
+        }
+        let row = (y / self.row_height) as usize;
+        if row < self.rows.len() {
+            Some(row)
+        } else {
+            None
+        }
+    }
+
+    fn sort_rows(&mut self, column: usize, ascending: bool) {
+        let selected = self.selected_row.and_then(|i| self.rows.get(i)).cloned();
+
+        // Stable sort keeps the order of the rows with equal keys.
+        self.rows.sort_by(|a, b| {
+            let ordering = compare_keys(
+                a.sort_keys.get(column).map_or("", |k| k.as_str()),
+                b.sort_keys.get(column).map_or("", |k| k.as_str()),
+            );
+            if ascending {
+                ordering
+            } else {
+                ordering.reverse()
+            }
+        });
+
+        // Keep the same row selected.
+        if let Some(selected) = selected {
+            self.selected_row = self.rows.iter().position(|r| *r == selected);
+        }
+    }
+}
+
+impl Control for DataGrid {
+    fn query_component(&self, type_id: TypeId) -> Option<&dyn Any> {
+        if type_id == TypeId::of::<Self>() {
+            Some(self)
+        } else {
+            None
+        }
+    }
+
+    fn resolve(&mut self, node_map: &NodeHandleMapping) {
+        node_map.resolve_slice(&mut self.headers);
+        for row in self.rows.iter_mut() {
+            node_map.resolve_slice(&mut row.cells);
+        }
+    }
+
+    fn measure_override(&self, ui: &UserInterface, _available_size: Vector2<f32>) -> Vector2<f32> {
+        for (header, column) in self.headers.iter().zip(self.columns.iter()) {
+            ui.measure_node(*header, Vector2::new(column.width, self.header_height));
+        }
+
+        for row in self.rows.iter() {
+            for (cell, column) in row.cells.iter().zip(self.columns.iter()) {
+                ui.measure_node(*cell, Vector2::new(column.width, self.row_height));
+            }
+        }
+
+        Vector2::new(
+            self.total_width(),
+            self.header_height + self.rows.len() as f32 * self.row_height,
+        )
+    }
+
+    fn arrange_override(&self, ui: &UserInterface, final_size: Vector2<f32>) -> Vector2<f32> {
+        for (header, (x, width)) in self.headers.iter().zip(self.column_offsets()) {
+            ui.arrange_node(*header, &Rect::new(x, 0.0, width, self.header_height));
+        }
+
+        for (row_index, row) in self.rows.iter().enumerate() {
+            let y = self.header_height + row_index as f32 * self.row_height;
+            for (cell, (x, width)) in row.cells.iter().zip(self.column_offsets()) {
+                ui.arrange_node(*cell, &Rect::new(x, y, width, self.row_height));
+            }
+        }
+
+        final_size
+    }
+
+    fn draw(&self, ctx: &mut DrawingContext) {
+        let bounds = self.widget.bounding_rect();
+        ctx.push_rect_filled(&bounds, None);
+        ctx.commit(
+            self.clip_bounds(),
+            self.widget.background(),
+            CommandTexture::None,
+            None,
+        );
+
+        ctx.push_rect_filled(
+            &Rect::new(0.0, 0.0, self.total_width(), self.header_height),
+            None,
+        );
+        ctx.commit(
+            self.clip_bounds(),
+            BRUSH_PRIMARY,
+            CommandTexture::None,
+            None,
+        );
+
+        if let Some(selected_row) = self.selected_row {
+            ctx.push_rect_filled(&self.row_bounds(selected_row), None);
+            ctx.commit(
+                self.clip_bounds(),
+                self.selection_brush.clone(),
+                CommandTexture::None,
+                None,
+            );
+        }
+
+        let height = self.header_height + self.rows.len() as f32 * self.row_height;
+        for (x, width) in self.column_offsets() {
+            let border = x + width;
+            ctx.push_line(Vector2::new(border, 0.0), Vector2::new(border, height), 1.0);
+        }
+        ctx.push_line(
+            Vector2::new(0.0, self.header_height),
+            Vector2::new(self.total_width(), self.header_height),
+            1.0,
+        );
+        ctx.commit(self.clip_bounds(), BRUSH_LIGHT, CommandTexture::None, None);
+
+        // Sort order indicator.
+        if let Some(((x, width), ascending)) = self
+            .sort_column
+            .and_then(|column| self.column_offsets().nth(column))
+            .map(|offsets| (offsets, self.sort_ascending))
+        {
+            let center = Vector2::new(x + width - 8.0, self.header_height * 0.5);
+            let size = 3.0;
+            let direction = if ascending { -1.0 } else { 1.0 };
+            ctx.push_triangle_filled([
+                Vector2::new(center.x - size, center.y - direction * size),
+                Vector2::new(center.x + size, center.y - direction * size),
+                Vector2::new(center.x, center.y + direction * size),
+            ]);
+            ctx.commit(
+                self.clip_bounds(),
+                BRUSH_LIGHTEST,
+                CommandTexture::None,
+                None,
+            );
+        }
+    }
+
+    fn handle_routed_message(&mut self, ui: &mut UserInterface, message: &mut UiMessage) {
+        self.widget.handle_routed_message(ui, message);
+
+        if let Some(msg) = message.data::<WidgetMessage>() {
+            match msg {
+                WidgetMessage::MouseDown { pos, button } if *button == MouseButton::Left => {
+                    let local_position = self.screen_to_local(*pos);
+                    if local_position.y <= self.header_height {
+                        if let Some(column) = self.pick_resize_grip(local_position) {
+                            self.resize =
+                                Some((column, local_position.x, self.columns[column].width));
+                            ui.capture_mouse(self.handle());
+                        } else if let Some(column) = self.pick_column(local_position) {
+                            if self.columns[column].sortable {
+                                let ascending =
+                                    self.sort_column != Some(column) || !self.sort_ascending;
+                                ui.send_message(DataGridMessage::sort(
+                                    self.handle(),
+                                    MessageDirection::ToWidget,
+                                    column,
+                                    ascending,
+                                ));
+                            }
+                        }
+                    } else if let Some(row) = self.pick_row(local_position) {
+                        ui.send_message(DataGridMessage::selected_row(
+                            self.handle(),
+                            MessageDirection::ToWidget,
+                            Some(row),
+                        ));
+                    }
+                }
+                WidgetMessage::MouseMove { pos, .. } => {
+                    if let Some((column, start_x, start_width)) = self.resize {
+                        let local_position = self.screen_to_local(*pos);
+                        if let Some(column) = self.columns.get_mut(column) {
+                            column.width =
+                                (start_width + local_position.x - start_x).max(column.min_width);
+                            self.invalidate_layout();
+                        }
+                    }
+                }
+                WidgetMessage::MouseUp { button, .. } if *button == MouseButton::Left => {
+                    if let Some((column, _, _)) = self.resize.take() {
+                        ui.release_mouse_capture();
+                        ui.send_message(DataGridMessage::column_width(
+                            self.handle(),
+                            MessageDirection::FromWidget,
+                            column,
+                            self.columns[column].width,
+                        ));
+                    }
+                }
+                _ => (),
+            }
+        } else if let Some(msg) = message.data::<DataGridMessage>() {
+            if message.destination() == self.handle()
+                && message.direction() == MessageDirection::ToWidget
+            {
+                match msg {
+                    DataGridMessage::Rows(rows) => {
+                        for row in self.rows.iter() {
+                            for cell in row.cells.iter() {
+                                if rows.iter().all(|r| !r.cells.contains(cell)) {
+                                    ui.send_message(WidgetMessage::remove(
+                                        *cell,
+                                        MessageDirection::ToWidget,
+                                    ));
+                                }
+                            }
+                        }
+                        for row in rows.iter() {
+                            for cell in row.cells.iter() {
+                                ui.send_message(WidgetMessage::link(
+                                    *cell,
+                                    MessageDirection::ToWidget,
+                                    self.handle(),
+                                ));
+                            }
+                        }
+
+                        self.rows = rows.clone();
+                        self.selected_row = None;
+                        if let Some(column) = self.sort_column {
+                            self.sort_rows(column, self.sort_ascending);
+                        }
+                        self.invalidate_layout();
+                    }
+                    &DataGridMessage::SelectedRow(selected_row) => {
+                        let selected_row = selected_row.filter(|i| *i < self.rows.len());
+                        if self.selected_row != selected_row {
+                            self.selected_row = selected_row;
+                            ui.send_message(DataGridMessage::selected_row(
+                                self.handle(),
+                                MessageDirection::FromWidget,
+                                selected_row,
+                            ));
+                        }
+                    }
+                    &DataGridMessage::Sort { column, ascending } => {
+                        if column < self.columns.len() {
+                            let selected_row = self.selected_row;
+                            self.sort_column = Some(column);
+                            self.sort_ascending = ascending;
+                            self.sort_rows(column, ascending);
+                            self.invalidate_layout();
+                            ui.send_message(message.reverse());
+                            if self.selected_row != selected_row {
+                                ui.send_message(DataGridMessage::selected_row(
+                                    self.handle(),
+                                    MessageDirection::FromWidget,
+                                    self.selected_row,
+                                ));
+                            }
+                        }
+                    }
+                    &DataGridMessage::ColumnWidth { column, width } => {
+                        if let Some(column_ref) = self.columns.get_mut(column) {
+                            let width = width.max(column_ref.min_width);
+                            if column_ref.width != width {
+                                column_ref.width = width;
+                                self.invalidate_layout();
+                                ui.send_message(DataGridMessage::column_width(
+                                    self.handle(),
+                                    MessageDirection::FromWidget,
+                                    column,
+                                    width,
+                                ));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Data grid builder creates [`DataGrid`] widget instances and adds them to the user interface.
+pub struct DataGridBuilder {
+    widget_builder: WidgetBuilder,
+    columns: Vec<DataGridColumn>,
+    rows: Vec<DataGridRow>,
+    header_height: f32,
+    row_height: f32,
+    selection_brush: Brush,
+}
+
+impl DataGridBuilder {
+    /// Creates a new data grid builder.
+    pub fn new(widget_builder: WidgetBuilder) -> Self {
+        Self {
+            widget_builder,
+            columns: Default::default(),
+            rows: Default::default(),
+            header_height: 22.0,
+            row_height: 22.0,
+            selection_brush: BRUSH_BRIGHT_BLUE,
+        }
+    }
+
+    /// Sets the columns of the data grid.
+    pub fn with_columns(mut self, columns: Vec<DataGridColumn>) -> Self {
+        self.columns = columns;
+        self
+    }
+
+    /// Sets the initial rows of the data grid.
+    pub fn with_rows(mut self, rows: Vec<DataGridRow>) -> Self {
+        self.rows = rows;
+        self
+    }
+
+    /// Sets the height of the header row.
+    pub fn with_header_height(mut self, height: f32) -> Self {
+        self.header_height = height;
+        self
+    }
+
+    /// Sets the height of every row.
+    pub fn with_row_height(mut self, height: f32) -> Self {
+        self.row_height = height;
+        self
+    }
+
+    /// Sets the brush, that is used to highlight the selected row.
+    pub fn with_selection_brush(mut self, brush: Brush) -> Self {
+        self.selection_brush = brush;
+        self
+    }
+
+    /// Finishes data grid building, adds it to the user interface and returns its handle.
+    pub fn build(self, ctx: &mut BuildContext) -> Handle<UiNode> {
+        let headers = self
+            .columns
+            .iter()
+            .map(|column| {
+                TextBuilder::new(WidgetBuilder::new().with_margin(Thickness::left(4.0)))
+                    .with_text(&column.header)
+                    .with_vertical_text_alignment(VerticalAlignment::Center)
+                    .build(ctx)
+            })
+            .collect::<Vec<_>>();
+
+        let data_grid = DataGrid {
+            widget: self
+                .widget_builder
+                .with_background_style(Style::BRUSH_DARKER)
+                .with_children(headers.iter().cloned())
+                .with_children(self.rows.iter().flat_map(|r| r.cells.iter().cloned()))
+                .build(),
+            columns: self.columns,
+            rows: self.rows,
+            headers,
+            header_height: self.header_height,
+            row_height: self.row_height,
+            selected_row: None,
+            sort_column: None,
+            sort_ascending: true,
+            selection_brush: self.selection_brush,
+            resize: None,
+        };
+
+        ctx.add_node(UiNode::new(data_grid))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        data_grid::{DataGrid, DataGridBuilder, DataGridColumn, DataGridMessage, DataGridRow},
+        message::MessageDirection,
+        text::TextBuilder,
+        widget::WidgetBuilder,
+        UserInterface,
+    };
+    use fyrox_core::algebra::Vector2;
+
+    #[test]
+    fn data_grid_sort() {
+        let mut ui = UserInterface::new(Vector2::new(100.0, 100.0));
+
+        let ctx = &mut ui.build_ctx();
+        let rows = ["10", "9", "abc"]
+            .into_iter()
+            .map(|key| {
+                let cell = TextBuilder::new(WidgetBuilder::new())
+                    .with_text(key)
+                    .build(ctx);
+                DataGridRow::new([(cell, key)])
+            })
+            .collect::<Vec<_>>();
+        let data_grid = DataGridBuilder::new(WidgetBuilder::new())
+            .with_columns(vec![DataGridColumn::new("Key", 50.0)])
+            .with_rows(rows)
+            .build(ctx);
+
+        ui.send_message(DataGridMessage::selected_row(
+            data_grid,
+            MessageDirection::ToWidget,
+            Some(0),
+        ));
+        ui.send_message(DataGridMessage::sort(
+            data_grid,
+            MessageDirection::ToWidget,
+            0,
+            true,
+        ));
+        while ui.poll_message().is_some() {}
+
+        let data_grid = ui.node(data_grid).query_component::<DataGrid>().unwrap();
+        let keys = data_grid
+            .rows
+            .iter()
+            .map(|r| r.sort_keys[0].as_str())
+            .collect::<Vec<_>>();
+        // Numbers are compared as numbers, not as strings.
+        assert_eq!(keys, ["9", "10", "abc"]);
+        // Selection follows the selected row.
+        assert_eq!(data_grid.selected_row, Some(1));
+    }
+}
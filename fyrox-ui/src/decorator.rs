@@ -6,14 +6,15 @@
 use crate::{
     border::{Border, BorderBuilder},
     brush::Brush,
-    core::{algebra::Vector2, pool::Handle},
+    core::{algebra::Vector2, pool::Handle, sstorage::ImmutableString},
     core::{reflect::prelude::*, visitor::prelude::*},
     define_constructor,
     draw::DrawingContext,
     message::{MessageDirection, UiMessage},
+    style::Style,
     widget::{Widget, WidgetMessage},
-    BuildContext, Control, NodeHandleMapping, UiNode, UserInterface, BRUSH_BRIGHT, BRUSH_DARKER,
-    BRUSH_LIGHT, BRUSH_LIGHTER, BRUSH_LIGHTEST,
+    BuildContext, Control, NodeHandleMapping, UiNode, UserInterface, BRUSH_BRIGHT, BRUSH_LIGHT,
+    BRUSH_LIGHTER, BRUSH_LIGHTEST,
 };
 use std::{
     any::{Any, TypeId},
@@ -100,6 +101,18 @@ pub struct Decorator {
     pub pressed_brush: Brush,
     /// Current brush used for `Selected` state.
     pub selected_brush: Brush,
+    /// Name of a style property, that is used as the brush for `Normal` state.
+    #[visit(optional)]
+    pub normal_brush_style: Option<ImmutableString>,
+    /// Name of a style property, that is used as the brush for `Hovered` state.
+    #[visit(optional)]
+    pub hover_brush_style: Option<ImmutableString>,
+    /// Name of a style property, that is used as the brush for `Pressed` state.
+    #[visit(optional)]
+    pub pressed_brush_style: Option<ImmutableString>,
+    /// Name of a style property, that is used as the brush for `Selected` state.
+    #[visit(optional)]
+    pub selected_brush_style: Option<ImmutableString>,
     /// Whether the decorator is in `Selected` state or not.
     pub is_selected: bool,
     /// Whether the decorator should react to mouse clicks and switch its state to `Pressed` or not.
@@ -135,6 +148,28 @@ impl Control for Decorator {
         self.border.resolve(node_map)
     }
 
+    fn apply_style(&mut self, style: &Style) {
+        self.border.apply_style(style);
+
+        for (brush, name) in [
+            (&mut self.normal_brush, &self.normal_brush_style),
+            (&mut self.hover_brush, &self.hover_brush_style),
+            (&mut self.pressed_brush, &self.pressed_brush_style),
+            (&mut self.selected_brush, &self.selected_brush_style),
+        ] {
+            if let Some(new_brush) = name.as_ref().and_then(|name| style.brush(name)) {
+                *brush = new_brush;
+            }
+        }
+
+        let background = if self.is_selected {
+            self.selected_brush.clone()
+        } else {
+            self.normal_brush.clone()
+        };
+        self.border.set_background(background);
+    }
+
     fn measure_override(&self, ui: &UserInterface, available_size: Vector2<f32>) -> Vector2<f32> {
         self.border.measure_override(ui, available_size)
     }
@@ -176,6 +211,7 @@ impl Control for Decorator {
                 }
                 DecoratorMessage::HoverBrush(brush) => {
                     self.hover_brush = brush.clone();
+                    self.hover_brush_style = None;
                     if self.is_mouse_directly_over {
                         ui.send_message(WidgetMessage::background(
                             self.handle(),
@@ -186,6 +222,7 @@ impl Control for Decorator {
                 }
                 DecoratorMessage::NormalBrush(brush) => {
                     self.normal_brush = brush.clone();
+                    self.normal_brush_style = None;
                     if !self.is_selected && !self.is_mouse_directly_over {
                         ui.send_message(WidgetMessage::background(
                             self.handle(),
@@ -196,9 +233,11 @@ impl Control for Decorator {
                 }
                 DecoratorMessage::PressedBrush(brush) => {
                     self.pressed_brush = brush.clone();
+                    self.pressed_brush_style = None;
                 }
                 DecoratorMessage::SelectedBrush(brush) => {
                     self.selected_brush = brush.clone();
+                    self.selected_brush_style = None;
                     if self.is_selected {
                         ui.send_message(WidgetMessage::background(
                             self.handle(),
@@ -271,12 +310,18 @@ pub struct DecoratorBuilder {
     hover_brush: Brush,
     pressed_brush: Brush,
     selected_brush: Brush,
+    normal_brush_style: Option<ImmutableString>,
+    hover_brush_style: Option<ImmutableString>,
+    pressed_brush_style: Option<ImmutableString>,
+    selected_brush_style: Option<ImmutableString>,
     pressable: bool,
     selected: bool,
 }
 
 impl DecoratorBuilder {
-    /// Creates a new decorator builder.
+    /// Creates a new decorator builder. Brushes of the decorator are bound to [`Style::BRUSH_LIGHT`],
+    /// [`Style::BRUSH_LIGHTER`], [`Style::BRUSH_LIGHTEST`] and [`Style::BRUSH_BRIGHT`] style properties
+    /// by default.
     pub fn new(border_builder: BorderBuilder) -> Self {
         Self {
             border_builder,
@@ -284,6 +329,10 @@ impl DecoratorBuilder {
             hover_brush: BRUSH_LIGHTER,
             pressed_brush: BRUSH_LIGHTEST,
             selected_brush: BRUSH_BRIGHT,
+            normal_brush_style: Some(ImmutableString::new(Style::BRUSH_LIGHT)),
+            hover_brush_style: Some(ImmutableString::new(Style::BRUSH_LIGHTER)),
+            pressed_brush_style: Some(ImmutableString::new(Style::BRUSH_LIGHTEST)),
+            selected_brush_style: Some(ImmutableString::new(Style::BRUSH_BRIGHT)),
             pressable: true,
             selected: false,
         }
@@ -292,24 +341,52 @@ impl DecoratorBuilder {
     /// Sets a desired brush for `Normal` state.
     pub fn with_normal_brush(mut self, brush: Brush) -> Self {
         self.normal_brush = brush;
+        self.normal_brush_style = None;
         self
     }
 
     /// Sets a desired brush for `Hovered` state.
     pub fn with_hover_brush(mut self, brush: Brush) -> Self {
         self.hover_brush = brush;
+        self.hover_brush_style = None;
         self
     }
 
     /// Sets a desired brush for `Pressed` state.
     pub fn with_pressed_brush(mut self, brush: Brush) -> Self {
         self.pressed_brush = brush;
+        self.pressed_brush_style = None;
         self
     }
 
     /// Sets a desired brush for `Selected` state.
     pub fn with_selected_brush(mut self, brush: Brush) -> Self {
         self.selected_brush = brush;
+        self.selected_brush_style = None;
+        self
+    }
+
+    /// Binds the brush for `Normal` state to a style property with the given name.
+    pub fn with_normal_brush_style(mut self, name: &str) -> Self {
+        self.normal_brush_style = Some(ImmutableString::new(name));
+        self
+    }
+
+    /// Binds the brush for `Hovered` state to a style property with the given name.
+    pub fn with_hover_brush_style(mut self, name: &str) -> Self {
+        self.hover_brush_style = Some(ImmutableString::new(name));
+        self
+    }
+
+    /// Binds the brush for `Pressed` state to a style property with the given name.
+    pub fn with_pressed_brush_style(mut self, name: &str) -> Self {
+        self.pressed_brush_style = Some(ImmutableString::new(name));
+        self
+    }
+
+    /// Binds the brush for `Selected` state to a style property with the given name.
+    pub fn with_selected_brush_style(mut self, name: &str) -> Self {
+        self.selected_brush_style = Some(ImmutableString::new(name));
         self
     }
 
@@ -330,12 +407,17 @@ impl DecoratorBuilder {
         let normal_brush = self.normal_brush;
         let selected_brush = self.selected_brush;
 
-        if self.border_builder.widget_builder.foreground.is_none() {
-            self.border_builder.widget_builder.foreground = Some(BRUSH_DARKER);
+        if !self.border_builder.widget_builder.has_foreground() {
+            self.border_builder.widget_builder = self
+                .border_builder
+                .widget_builder
+                .with_foreground_style(Style::BRUSH_DARKER);
         }
 
         let mut border = self.border_builder.build_border();
 
+        // Background is defined by the state of the decorator.
+        border.background_style = None;
         if self.selected {
             border.set_background(selected_brush.clone());
         } else {
@@ -348,6 +430,10 @@ impl DecoratorBuilder {
             hover_brush: self.hover_brush,
             pressed_brush: self.pressed_brush,
             selected_brush,
+            normal_brush_style: self.normal_brush_style,
+            hover_brush_style: self.hover_brush_style,
+            pressed_brush_style: self.pressed_brush_style,
+            selected_brush_style: self.selected_brush_style,
             is_selected: self.selected,
             is_pressable: self.pressable,
         });
@@ -10,10 +10,10 @@ use crate::{
     list_view::{ListViewBuilder, ListViewMessage},
     message::{MessageDirection, UiMessage},
     popup::{Placement, PopupBuilder, PopupMessage},
+    style::Style,
     utils::{make_arrow, ArrowDirection},
     widget::{Widget, WidgetBuilder, WidgetMessage},
-    BuildContext, Control, NodeHandleMapping, Thickness, UiNode, UserInterface, BRUSH_DARKER,
-    BRUSH_LIGHT,
+    BuildContext, Control, NodeHandleMapping, Thickness, UiNode, UserInterface,
 };
 use std::{
     any::{Any, TypeId},
@@ -321,8 +321,8 @@ impl DropdownListBuilder {
                 .with_child(
                     BorderBuilder::new(
                         WidgetBuilder::new()
-                            .with_background(BRUSH_DARKER)
-                            .with_foreground(BRUSH_LIGHT)
+                            .with_background_style(Style::BRUSH_DARKER)
+                            .with_foreground_style(Style::BRUSH_LIGHT)
                             .with_child(main_grid),
                     )
                     .build(ctx),
@@ -5,20 +5,29 @@
 
 use crate::{
     check_box::{CheckBoxBuilder, CheckBoxMessage},
-    core::pool::Handle,
+    core::{
+        algebra::{Matrix3, Vector2},
+        pool::Handle,
+    },
     core::{reflect::prelude::*, visitor::prelude::*},
     define_constructor,
     grid::{Column, GridBuilder, Row},
     message::{MessageDirection, UiMessage},
     utils::{make_arrow, ArrowDirection},
     widget::{Widget, WidgetBuilder, WidgetMessage},
-    BuildContext, Control, UiNode, UserInterface, VerticalAlignment,
+    BuildContext, Control, NodeHandleMapping, UiNode, UserInterface, VerticalAlignment,
 };
 use std::{
     any::{Any, TypeId},
+    cell::Cell,
+    f32::consts::FRAC_PI_2,
     ops::{Deref, DerefMut},
+    sync::mpsc::Sender,
 };
 
+/// Angular speed (in radians per second) of the arrow of an expander.
+const ARROW_SPEED: f32 = FRAC_PI_2 / 0.15;
+
 /// A set messages that can be used to either alternate the state of an [`Expander`] widget, or to listen for
 /// state changes.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -134,6 +143,16 @@ impl ExpanderMessage {
 ///
 /// To switch expander state at runtime, send [`ExpanderMessage::Expand`] to your Expander widget instance with
 /// [`MessageDirection::ToWidget`].
+///
+/// ## Animation
+///
+/// The default arrow of the expander is smoothly rotated when the expander changes its state. Custom check boxes
+/// (see [`ExpanderBuilder::with_checkbox`]) are not animated.
+///
+/// ## Accordion
+///
+/// Use [`crate::accordion::Accordion`] widget to group multiple expanders and optionally keep only one of them
+/// expanded at a time.
 #[derive(Clone, Visit, Reflect, Debug)]
 pub struct Expander {
     /// Base widget of the expander.
@@ -144,6 +163,19 @@ pub struct Expander {
     pub expander: Handle<UiNode>,
     /// A flag, that indicates whether the expander is expanded or collapsed.
     pub is_expanded: bool,
+    /// Arrows of the expander, that are rotated when the expander changes its state. It is empty, if the
+    /// expander uses a custom check box.
+    #[visit(optional)]
+    pub arrows: Vec<Handle<UiNode>>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    arrow_angle: f32,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    arrow_center: Cell<Vector2<f32>>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    arrow_transform_dirty: Cell<bool>,
 }
 
 crate::define_widget_deref!(Expander);
@@ -157,6 +189,57 @@ impl Control for Expander {
         }
     }
 
+    fn resolve(&mut self, node_map: &NodeHandleMapping) {
+        node_map.resolve(&mut self.content);
+        node_map.resolve(&mut self.expander);
+        node_map.resolve_slice(&mut self.arrows);
+    }
+
+    fn arrange_override(&self, ui: &UserInterface, final_size: Vector2<f32>) -> Vector2<f32> {
+        let size = self.widget.arrange_override(ui, final_size);
+
+        if let Some(arrow) = self.arrows.first().and_then(|&a| ui.try_get_node(a)) {
+            // Arrows are rotated around their centers, so the transform must be refreshed when
+            // the layout changes.
+            let center = arrow.actual_local_position() + arrow.actual_local_size().scale(0.5);
+            if self.arrow_center.get() != center {
+                self.arrow_center.set(center);
+                self.arrow_transform_dirty.set(true);
+            }
+        }
+
+        size
+    }
+
+    fn update(&mut self, dt: f32, sender: &Sender<UiMessage>) {
+        let target_angle = if self.is_expanded { FRAC_PI_2 } else { 0.0 };
+        if self.arrow_angle != target_angle {
+            let step = ARROW_SPEED * dt;
+            self.arrow_angle = if self.arrow_angle < target_angle {
+                (self.arrow_angle + step).min(target_angle)
+            } else {
+                (self.arrow_angle - step).max(target_angle)
+            };
+            self.arrow_transform_dirty.set(true);
+        }
+
+        if self.arrow_transform_dirty.replace(false) {
+            let center = self.arrow_center.get();
+            let transform = Matrix3::new_translation(&center)
+                * Matrix3::new_rotation(self.arrow_angle)
+                * Matrix3::new_translation(&-center);
+            for &arrow in self.arrows.iter() {
+                sender
+                    .send(WidgetMessage::render_transform(
+                        arrow,
+                        MessageDirection::ToWidget,
+                        transform,
+                    ))
+                    .unwrap();
+            }
+        }
+    }
+
     fn handle_routed_message(&mut self, ui: &mut UserInterface, message: &mut UiMessage) {
         if let Some(&ExpanderMessage::Expand(expand)) = message.data::<ExpanderMessage>() {
             if message.destination() == self.handle()
@@ -176,6 +259,7 @@ impl Control for Expander {
                     expand,
                 ));
                 self.is_expanded = expand;
+                ui.send_message(message.reverse());
             }
         } else if let Some(CheckBoxMessage::Check(value)) = message.data::<CheckBoxMessage>() {
             if message.destination() == self.expander
@@ -248,14 +332,19 @@ impl ExpanderBuilder {
 
     /// Finishes widget building and adds it to the user interface, returning a handle to the new instance.
     pub fn build(self, ctx: &mut BuildContext<'_>) -> Handle<UiNode> {
+        let mut arrows = Vec::new();
         let expander = if self.check_box.is_some() {
             self.check_box
         } else {
+            // Both marks are the same arrow, it is rotated by the expander when its state changes.
+            let check_mark = make_arrow(ctx, ArrowDirection::Right, 8.0);
+            let uncheck_mark = make_arrow(ctx, ArrowDirection::Right, 8.0);
+            arrows.extend([check_mark, uncheck_mark]);
             CheckBoxBuilder::new(
                 WidgetBuilder::new().with_vertical_alignment(VerticalAlignment::Center),
             )
-            .with_check_mark(make_arrow(ctx, ArrowDirection::Bottom, 8.0))
-            .with_uncheck_mark(make_arrow(ctx, ArrowDirection::Right, 8.0))
+            .with_check_mark(check_mark)
+            .with_uncheck_mark(uncheck_mark)
             .checked(Some(self.is_expanded))
             .build(ctx)
         };
@@ -301,6 +390,10 @@ impl ExpanderBuilder {
             content: self.content,
             expander,
             is_expanded: self.is_expanded,
+            arrows,
+            arrow_angle: if self.is_expanded { FRAC_PI_2 } else { 0.0 },
+            arrow_center: Default::default(),
+            arrow_transform_dirty: Cell::new(true),
         });
         ctx.add_node(e)
     }
@@ -136,6 +136,13 @@ impl FormattedText {
         self
     }
 
+    /// Sets the render scale of the font of the text (see [`Font::set_render_scale`]). The font is shared, so
+    /// every other text with the same font will use the new render scale after it is built again.
+    pub fn set_font_render_scale(&mut self, scale: f32) -> &mut Self {
+        self.font.0.lock().set_render_scale(scale);
+        self
+    }
+
     pub fn get_lines(&self) -> &[TextLine] {
         &self.lines
     }
@@ -456,6 +463,11 @@ impl FormattedText {
             self.constraint.x
         };
 
+        // Glyphs are rasterized at the render scale of the font, so their bitmaps must be shrunk back to
+        // logical units and snapped to the pixels of the scaled bitmaps.
+        let render_scale = font.render_scale();
+        let snap = |value: f32| (value * render_scale).floor() / render_scale;
+
         let mut cursor = Vector2::new(cursor_x_start, cursor_y_start);
         for line in self.lines.iter_mut() {
             cursor.x = line.x_offset;
@@ -463,14 +475,15 @@ impl FormattedText {
             for &character in text.iter().take(line.end).skip(line.begin) {
                 match font.glyphs().get(character.glyph_index as usize) {
                     Some(glyph) => {
+                        let width = glyph.bitmap_width as f32 / render_scale;
+                        let height = glyph.bitmap_height as f32 / render_scale;
+
                         // Insert glyph
                         let rect = Rect::new(
-                            cursor.x + glyph.left.floor(),
-                            cursor.y + font.ascender().floor()
-                                - glyph.top.floor()
-                                - glyph.bitmap_height as f32,
-                            glyph.bitmap_width as f32,
-                            glyph.bitmap_height as f32,
+                            cursor.x + snap(glyph.left),
+                            cursor.y + snap(font.ascender()) - snap(glyph.top) - height,
+                            width,
+                            height,
                         );
                         let text_glyph = TextGlyph {
                             bounds: rect,
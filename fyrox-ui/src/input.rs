@@ -0,0 +1,121 @@
+//! Configurable input bindings for canvas-like widgets (such as [`crate::curve::CurveEditor`]), so the
+//! actions could be remapped, for example to be usable on laptops without a middle mouse button. See
+//! [`InputBindings`] docs for more info.
+
+#![warn(missing_docs)]
+
+use crate::{
+    core::{reflect::prelude::*, visitor::prelude::*},
+    key::HotKey,
+    message::{KeyCode, KeyboardModifiers, MouseButton},
+};
+
+/// Mouse binding is a combination of a mouse button with a set of keyboard modifiers (such as Ctrl, Shift,
+/// Alt keys), that must be held when the button is pressed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Reflect, Visit)]
+pub struct MouseBinding {
+    /// Mouse button of the binding.
+    pub button: MouseButton,
+    /// Keyboard modifiers that must be held. Any other modifiers could be held as well.
+    pub modifiers: KeyboardModifiers,
+}
+
+impl Default for MouseBinding {
+    fn default() -> Self {
+        Self::new(MouseButton::Left)
+    }
+}
+
+impl MouseBinding {
+    /// Creates a new mouse binding that consists of a single button, without any modifiers.
+    pub fn new(button: MouseButton) -> Self {
+        Self {
+            button,
+            modifiers: Default::default(),
+        }
+    }
+
+    /// Creates a new mouse binding, that consists of combination `Alt + Button`.
+    pub fn alt(button: MouseButton) -> Self {
+        Self {
+            button,
+            modifiers: KeyboardModifiers {
+                alt: true,
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Checks if the given button with the given held modifiers triggers the binding.
+    pub fn matches(&self, button: MouseButton, modifiers: KeyboardModifiers) -> bool {
+        let required = self.modifiers;
+        self.button == button
+            && (!required.alt || modifiers.alt)
+            && (!required.shift || modifiers.shift)
+            && (!required.control || modifiers.control)
+            && (!required.system || modifiers.system)
+    }
+}
+
+/// A set of input bindings of common actions of canvas-like widgets, that show some content which could
+/// be panned and zoomed.
+///
+/// ## Examples
+///
+/// Pan the view by dragging with the right mouse button, in addition to the default bindings:
+///
+/// ```rust
+/// # use fyrox_ui::{
+/// #     input::{InputBindings, MouseBinding},
+/// #     message::MouseButton,
+/// # };
+/// #
+/// fn make_bindings() -> InputBindings {
+///     let mut bindings = InputBindings::default();
+///     bindings.pan.push(MouseBinding::new(MouseButton::Right));
+///     bindings
+/// }
+/// ```
+///
+/// Keep in mind, that a context menu of a widget is opened on right mouse button press. Widgets that
+/// support right mouse button panning close the menu as soon as the panning starts.
+#[derive(Clone, Debug, PartialEq, Reflect, Visit)]
+pub struct InputBindings {
+    /// Mouse bindings, that pan the view while the mouse is dragged. Default is the middle mouse button and
+    /// `Alt + Left` mouse button.
+    pub pan: Vec<MouseBinding>,
+    /// A hot key, that fits the content into the view. Default is `F`.
+    pub zoom_to_fit: HotKey,
+}
+
+impl Default for InputBindings {
+    fn default() -> Self {
+        Self {
+            pan: vec![
+                MouseBinding::new(MouseButton::Middle),
+                MouseBinding::alt(MouseButton::Left),
+            ],
+            zoom_to_fit: HotKey::from_key_code(KeyCode::KeyF),
+        }
+    }
+}
+
+impl InputBindings {
+    /// Checks if the given button with the given held modifiers starts panning.
+    pub fn is_pan(&self, button: MouseButton, modifiers: KeyboardModifiers) -> bool {
+        self.pan
+            .iter()
+            .any(|binding| binding.matches(button, modifiers))
+    }
+
+    /// Checks if the given key with the given held modifiers fits the content into the view.
+    pub fn is_zoom_to_fit(&self, key: KeyCode, modifiers: KeyboardModifiers) -> bool {
+        match self.zoom_to_fit {
+            HotKey::NotSet => false,
+            HotKey::Some {
+                code,
+                modifiers: required,
+            } => code == key && required == modifiers,
+        }
+    }
+}
@@ -33,6 +33,7 @@ impl PropertyEditorDefinition for CurvePropertyEditorDefinition {
                 .with_margin(Thickness::uniform(1.0)),
         )
         .with_curve(value.clone())
+        .with_read_only(ctx.property_info.read_only)
         .build(ctx.build_context);
         ctx.build_context
             .sender()
@@ -53,13 +54,14 @@ impl PropertyEditorDefinition for CurvePropertyEditorDefinition {
         Ok(Some(CurveEditorMessage::sync(
             ctx.instance,
             MessageDirection::ToWidget,
-            value.clone(),
+            vec![value.clone()],
         )))
     }
 
     fn translate_message(&self, ctx: PropertyEditorTranslationContext) -> Option<PropertyChanged> {
         if ctx.message.direction() == MessageDirection::FromWidget {
-            if let Some(CurveEditorMessage::Sync(value)) = ctx.message.data() {
+            if let Some(CurveEditorMessage::Sync(curves)) = ctx.message.data() {
+                let value = curves.first()?;
                 return Some(PropertyChanged {
                     name: ctx.name.to_string(),
                     owner_type_id: ctx.owner_type_id,
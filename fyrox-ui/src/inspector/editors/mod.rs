@@ -151,6 +151,7 @@ pub trait PropertyEditorDefinition: Debug {
 #[derive(Clone, Default)]
 pub struct PropertyEditorDefinitionContainer {
     definitions: RefCell<FxHashMap<TypeId, Rc<dyn PropertyEditorDefinition>>>,
+    tagged_definitions: RefCell<FxHashMap<(TypeId, String), Rc<dyn PropertyEditorDefinition>>>,
 }
 
 macro_rules! reg_array_property_editor {
@@ -283,6 +284,37 @@ impl PropertyEditorDefinitionContainer {
             .insert(definition.value_type_id(), Rc::new(definition))
     }
 
+    /// Registers a property editor, that will be used for the properties of its type with the given tag
+    /// (`#[reflect(tag = "...")]`) instead of the editor registered for the type.
+    pub fn insert_tagged<T>(
+        &self,
+        tag: &str,
+        definition: T,
+    ) -> Option<Rc<dyn PropertyEditorDefinition>>
+    where
+        T: PropertyEditorDefinition + 'static,
+    {
+        self.tagged_definitions.borrow_mut().insert(
+            (definition.value_type_id(), tag.to_string()),
+            Rc::new(definition),
+        )
+    }
+
+    /// Returns a property editor for the given property, editors for tagged properties take precedence.
+    pub fn definition_for(&self, info: &FieldInfo) -> Option<Rc<dyn PropertyEditorDefinition>> {
+        let type_id = info.value.type_id();
+        if !info.tag.is_empty() {
+            if let Some(definition) = self
+                .tagged_definitions
+                .borrow()
+                .get(&(type_id, info.tag.to_string()))
+            {
+                return Some(definition.clone());
+            }
+        }
+        self.definitions.borrow().get(&type_id).cloned()
+    }
+
     pub fn register_inheritable_vec_collection<T>(&self)
     where
         T: CollectionItem + FieldValue,
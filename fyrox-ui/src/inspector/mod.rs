@@ -28,7 +28,6 @@ use crate::{
     widget::{Widget, WidgetBuilder, WidgetMessage},
     BuildContext, Control, RcUiNodeHandle, Thickness, UiNode, UserInterface, VerticalAlignment,
 };
-use copypasta::ClipboardProvider;
 use std::sync::Arc;
 use std::{
     any::{Any, TypeId},
@@ -684,10 +683,7 @@ impl InspectorContext {
                     format!("{}\n\n{}", info.display_name, info.description)
                 };
 
-                if let Some(definition) = definition_container
-                    .definitions()
-                    .get(&info.value.type_id())
-                {
+                if let Some(definition) = definition_container.definition_for(&info) {
                     let editor = match definition.create_instance(PropertyEditorBuildContext {
                         build_context: ctx,
                         property_info: &info,
@@ -815,11 +811,7 @@ impl InspectorContext {
                     continue;
                 }
 
-                if let Some(constructor) = self
-                    .property_definitions
-                    .definitions()
-                    .get(&info.value.type_id())
-                {
+                if let Some(constructor) = self.property_definitions.definition_for(&info) {
                     if let Some(property_editor) = self.find_property_editor(info.name) {
                         let ctx = PropertyEditorMessageContext {
                             sync_flag: self.sync_flag,
@@ -939,8 +931,7 @@ impl Control for Inspector {
                         for entry in self.context.entries.iter() {
                             if entry.property_container == parent_handle {
                                 let _ = ui
-                                    .clipboard_mut()
-                                    .unwrap()
+                                    .clipboard_provider_mut()
                                     .set_contents(entry.property_debug_output.clone());
                                 break;
                             }
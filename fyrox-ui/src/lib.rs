@@ -90,6 +90,7 @@
 //! is used to create "scrollable" area in conjunction with the Scroll Viewer.
 //! * [`crate::expander::Expander`]: The Expander handles hiding and showing multiple panels of widgets in an according style UI element.
 //! Multiple panels can be shown or hidden at any time based on user input.
+//! * [`crate::accordion::Accordion`]: The Accordion groups multiple Expanders and can keep only one of them expanded at a time.
 //! * [`crate::tab_control::TabControl`]: The Tab Control handles hiding several panels of widgets, only showing the one that the user
 //! has selected.
 //! * [`crate::dock::DockingManager`]: The Docking manager allows you to dock windows and hold them in-place.
@@ -105,6 +106,7 @@
 //! * [`crate::rect::RectEditor`]: The Rect allows you to specify numeric values for X, Y, Width, and Height of a rectangle.
 //! * [`crate::progress_bar::ProgressBar`]: The Progress Bar shows a bar whose fill state can be adjusted to indicate visually how full
 //! something is, for example how close to 100% is a loading process.
+//! * [`crate::chart::Chart`]: The Chart plots series of values as lines, areas or bars, for example frame times.
 //! * [`crate::decorator::Decorator`]: The Decorator is used to style any widget. It has support for different styles depending on various
 //! events like mouse hover or click.
 //! * [`crate::border::Border`]: The Border widget is used in conjunction with the Decorator widget to provide configurable boarders to
@@ -119,6 +121,7 @@
 //! * [`crate::check_box::CheckBox`]: The Check Box is a toggle-able control that can contain other UI elements, for example a Text
 //! or Image Widget.
 //! * [`crate::text_box::TextBox`]: The Text Box is a control that allows the editing of text.
+//! * [`crate::autocomplete::AutoCompleteTextBox`]: The Auto-complete Text Box is a Text Box with a drop-down list of suggestions.
 //! * [`crate::scroll_bar::ScrollBar`]: The Scroll Bar provides a scroll bar like control that can be used on it's own as a data input or with
 //! certain other widgets to provide content scrolling capabilities.
 //! * [`crate::numeric::NumericUpDown`]: The Numeric Field provides the ability to adjust a number via increment and decrement buttons or direct
@@ -180,17 +183,22 @@ pub use copypasta;
 pub use fyrox_core as core;
 use message::TouchPhase;
 
+pub mod accordion;
 mod alignment;
+pub mod autocomplete;
 pub mod bit;
 pub mod border;
 pub mod brush;
 mod build;
 pub mod button;
 pub mod canvas;
+pub mod chart;
 pub mod check_box;
+pub mod clipboard;
 pub mod color;
 mod control;
 pub mod curve;
+pub mod data_grid;
 pub mod decorator;
 pub mod dock;
 pub mod draw;
@@ -200,35 +208,45 @@ pub mod file_browser;
 pub mod formatted_text;
 pub mod grid;
 pub mod image;
+pub mod input;
 pub mod inspector;
 pub mod key;
 pub mod list_view;
+pub mod localization;
 pub mod menu;
 pub mod message;
 pub mod messagebox;
 pub mod nine_patch;
 mod node;
+pub mod notification;
 pub mod numeric;
 pub mod path;
 pub mod popup;
 pub mod progress_bar;
 pub mod range;
+pub mod range_slider;
 pub mod rect;
+pub mod rich_text;
 pub mod scroll_bar;
 pub mod scroll_panel;
 pub mod scroll_viewer;
+pub mod scrubber;
 pub mod searchbar;
 pub mod stack_panel;
+pub mod style;
 pub mod tab_control;
 pub mod text;
 pub mod text_box;
 mod thickness;
+pub mod toolbar;
+pub mod transition;
 pub mod tree;
 pub mod ttf;
 pub mod utils;
 pub mod uuid;
 pub mod vec;
 pub mod vector_image;
+pub mod virtual_list_view;
 pub mod widget;
 pub mod window;
 pub mod wrap_panel;
@@ -236,6 +254,7 @@ pub mod wrap_panel;
 use crate::{
     brush::Brush,
     canvas::Canvas,
+    clipboard::LocalClipboard,
     container::WidgetContainer,
     core::{
         algebra::{Matrix3, Vector2},
@@ -247,15 +266,22 @@ use crate::{
         visitor::prelude::*,
     },
     draw::{CommandTexture, Draw, DrawingContext},
+    localization::{Localization, LocalizationMessage, TranslationProvider},
     message::{
-        ButtonState, CursorIcon, KeyboardModifiers, MessageDirection, MouseButton, OsEvent,
-        UiMessage,
+        ButtonState, CursorIcon, ImeEvent, KeyboardModifiers, MessageDirection, MouseButton,
+        OsEvent, UiMessage,
     },
+    messagebox::{MessageBoxBuilder, MessageBoxButtons, MessageBoxMessage},
+    notification::{NotificationBuilder, NotificationSeverity},
     popup::{Placement, PopupMessage},
+    stack_panel::StackPanelBuilder,
+    style::Style,
+    transition::{ActiveTransition, TransitionMessage},
     ttf::{Font, FontBuilder, SharedFont},
     widget::{Widget, WidgetBuilder, WidgetMessage},
+    window::{WindowBuilder, WindowTitle},
 };
-use copypasta::ClipboardContext;
+use copypasta::{ClipboardContext, ClipboardProvider};
 use fxhash::{FxHashMap, FxHashSet};
 use serde::{Deserialize, Serialize};
 use std::{
@@ -460,6 +486,8 @@ pub struct DragContext {
     pub drag_node: Handle<UiNode>,
     pub click_pos: Vector2<f32>,
     pub drag_preview: Handle<UiNode>,
+    /// A widget with drop allowed, that is currently under the cursor during dragging.
+    pub drop_target: Handle<UiNode>,
 }
 
 impl Default for DragContext {
@@ -469,6 +497,7 @@ impl Default for DragContext {
             drag_node: Default::default(),
             click_pos: Vector2::new(0.0, 0.0),
             drag_preview: Default::default(),
+            drop_target: Default::default(),
         }
     }
 }
@@ -542,13 +571,30 @@ pub enum LayoutEvent {
     VisibilityChanged(Handle<UiNode>),
 }
 
+/// A set of messages, that is used to change the scale of the user interface at runtime.
+#[derive(Debug, Clone, PartialEq)]
+pub enum UiScaleMessage {
+    /// Sets new user-defined scale of the user interface (see [`UserInterface::set_scale`]). This message is
+    /// handled by the user interface itself, send it to the root widget ([`UserInterface::root`]).
+    ///
+    /// Direction: **To UI**.
+    Scale(f32),
+}
+
+impl UiScaleMessage {
+    define_constructor!(
+        /// Creates [`UiScaleMessage::Scale`] message.
+        UiScaleMessage:Scale => fn scale(f32), layout: false
+    );
+}
+
 #[derive(Clone, Debug, Visit, Reflect, Default)]
 struct DoubleClickEntry {
     timer: f32,
     click_count: u32,
 }
 
-struct Clipboard(Option<RefCell<ClipboardContext>>);
+struct Clipboard(RefCell<Box<dyn ClipboardProvider + Send>>);
 
 impl Debug for Clipboard {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
@@ -606,8 +652,25 @@ pub struct UserInterface {
     pub default_font: SharedFont,
     #[visit(skip)]
     #[reflect(hidden)]
+    style: Style,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    scale: f32,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    scale_factor: f32,
+    #[visit(skip)]
+    #[reflect(hidden)]
     double_click_entries: FxHashMap<MouseButton, DoubleClickEntry>,
     pub double_click_time_slice: f32,
+    #[visit(optional)]
+    notification_panel: Handle<UiNode>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    transitions: Vec<ActiveTransition>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    localization: Localization,
 }
 
 fn is_on_screen(node: &UiNode, nodes: &Pool<UiNode, WidgetContainer>) -> bool {
@@ -721,13 +784,22 @@ impl UserInterface {
             cursor_icon: Default::default(),
             active_tooltip: Default::default(),
             preview_set: Default::default(),
-            clipboard: Clipboard(ClipboardContext::new().ok().map(RefCell::new)),
+            clipboard: Clipboard(RefCell::new(match ClipboardContext::new() {
+                Ok(clipboard) => Box::new(clipboard),
+                Err(_) => Box::<LocalClipboard>::default(),
+            })),
             layout_events_receiver,
             layout_events_sender,
             need_update_global_transform: Default::default(),
             default_font,
+            style: Style::dark(),
+            scale: 1.0,
+            scale_factor: 1.0,
             double_click_entries: Default::default(),
             double_click_time_slice: 0.5, // 500 ms is standard in most operating systems.
+            notification_panel: Handle::NONE,
+            transitions: Default::default(),
+            localization: Default::default(),
         };
         ui.root_canvas = ui.add_node(UiNode::new(Canvas {
             widget: WidgetBuilder::new().build(),
@@ -744,6 +816,53 @@ impl UserInterface {
         self.into()
     }
 
+    /// Returns current style of the user interface. See [`Style`] docs for more info.
+    pub fn style(&self) -> &Style {
+        &self.style
+    }
+
+    /// Sets a new style of the user interface and updates style-bound properties of every widget. If the style
+    /// does not specify [`Style::FONT_DEFAULT`], the default font of the user interface is used instead.
+    pub fn set_style(&mut self, mut style: Style) {
+        if style.font(Style::FONT_DEFAULT).is_none() {
+            style.set_font(Style::FONT_DEFAULT, self.default_font.clone());
+        }
+        for font in style.fonts() {
+            font.0.lock().set_render_scale(self.effective_scale());
+        }
+        self.style = style;
+        for node in self.nodes.iter_mut() {
+            node.apply_style(&self.style);
+            node.invalidate_layout();
+        }
+    }
+
+    /// Returns current localization context of the user interface. See [`Localization`] docs for more info.
+    pub fn localization(&self) -> &Localization {
+        &self.localization
+    }
+
+    /// Sets a new translation provider of the user interface and updates every localized widget.
+    pub fn set_translation_provider(&mut self, provider: Box<dyn TranslationProvider>) {
+        self.localization.set_provider(provider);
+        self.apply_localization();
+    }
+
+    /// Sets a new language of the user interface and updates every localized widget. The same could be done
+    /// by sending [`LocalizationMessage::Language`] message to the root widget.
+    pub fn set_language(&mut self, language: &str) {
+        if self.localization.language() != language {
+            self.localization.set_language(language.to_string());
+            self.apply_localization();
+        }
+    }
+
+    fn apply_localization(&mut self) {
+        for node in self.nodes.iter_mut() {
+            node.apply_localization(&self.localization);
+        }
+    }
+
     #[inline]
     pub fn capture_mouse(&mut self, node: Handle<UiNode>) -> bool {
         if self.captured_node.is_none() {
@@ -845,6 +964,68 @@ impl UserInterface {
         self.screen_size = screen_size;
     }
 
+    /// Returns current user-defined scale of the user interface. See [`Self::set_scale`] for more info.
+    pub fn scale(&self) -> f32 {
+        self.scale
+    }
+
+    /// Sets new user-defined scale of the user interface. The final scale of the user interface is a product
+    /// of this scale and the [scale factor](Self::scale_factor) of the window, see [`Self::effective_scale`].
+    /// The same could be done by sending [`UiScaleMessage::Scale`] message to the root widget. Values less or
+    /// equal to zero are ignored.
+    pub fn set_scale(&mut self, scale: f32) {
+        if scale > 0.0 && self.scale != scale {
+            self.scale = scale;
+            self.apply_scale();
+        }
+    }
+
+    /// Returns current scale factor (DPI) of the window, that hosts the user interface.
+    pub fn scale_factor(&self) -> f32 {
+        self.scale_factor
+    }
+
+    /// Sets new scale factor (DPI) of the window, that hosts the user interface. Usually, there's no need to
+    /// call this method manually, because the scale factor is updated by [`OsEvent::ScaleFactorChanged`]
+    /// event. Values less or equal to zero are ignored.
+    pub fn set_scale_factor(&mut self, scale_factor: f32) {
+        if scale_factor > 0.0 && self.scale_factor != scale_factor {
+            self.scale_factor = scale_factor;
+            self.apply_scale();
+        }
+    }
+
+    /// Returns the final scale of the user interface, that is a product of the user-defined
+    /// [scale](Self::scale) and the [scale factor](Self::scale_factor) of the window. The scale is applied to
+    /// the root canvas as a render transform, so every widget becomes bigger or smaller, while the layout is
+    /// performed in logical units (screen size divided by the scale). Fonts are rasterized at their height
+    /// multiplied by the scale, so the text stays crisp.
+    pub fn effective_scale(&self) -> f32 {
+        self.scale * self.scale_factor
+    }
+
+    fn apply_scale(&mut self) {
+        let scale = self.effective_scale();
+
+        self.nodes[self.root_canvas].render_transform = Matrix3::new_scaling(scale);
+
+        self.default_font.0.lock().set_render_scale(scale);
+        for font in self.style.fonts() {
+            font.0.lock().set_render_scale(scale);
+        }
+
+        // Texts must be formatted again to use new glyphs of the fonts.
+        for node in self.nodes.iter_mut() {
+            node.invalidate_layout();
+        }
+    }
+
+    /// Returns size of the screen in logical units, that are used for layout. It is the screen size divided by
+    /// the current [effective scale](Self::effective_scale).
+    pub fn logical_screen_size(&self) -> Vector2<f32> {
+        self.screen_size / self.effective_scale()
+    }
+
     fn handle_layout_events(&mut self) {
         fn invalidate_recursive_up(
             nodes: &Pool<UiNode, WidgetContainer>,
@@ -888,12 +1069,16 @@ impl UserInterface {
             entry.timer -= dt;
         }
 
+        self.update_transitions(dt);
+
         self.handle_layout_events();
 
-        self.measure_node(self.root_canvas, screen_size);
+        let logical_screen_size = self.logical_screen_size();
+        self.measure_node(self.root_canvas, logical_screen_size);
+        self.update_notification_panel_position();
         let arrangement_changed = self.arrange_node(
             self.root_canvas,
-            &Rect::new(0.0, 0.0, screen_size.x, screen_size.y),
+            &Rect::new(0.0, 0.0, logical_screen_size.x, logical_screen_size.y),
         );
 
         if self.need_update_global_transform {
@@ -931,6 +1116,107 @@ impl UserInterface {
         }
     }
 
+    /// Advances every running transition and notifies widgets about finished ones.
+    fn update_transitions(&mut self, dt: f32) {
+        if dt <= 0.0 {
+            return;
+        }
+
+        let nodes = &mut self.nodes;
+        let sender = &self.sender;
+        self.transitions.retain_mut(|transition| {
+            let Some(node) = nodes.try_borrow_mut(transition.node) else {
+                return false;
+            };
+
+            if transition.update(dt, node) {
+                let _ = sender.send(TransitionMessage::finished(
+                    transition.node,
+                    MessageDirection::FromWidget,
+                    transition.transition,
+                ));
+                false
+            } else {
+                true
+            }
+        });
+    }
+
+    /// Keeps the notification area in the bottom-right corner of the screen.
+    fn update_notification_panel_position(&mut self) {
+        let screen_size = self.logical_screen_size();
+        if let Some(panel) = self.nodes.try_borrow_mut(self.notification_panel) {
+            let position = screen_size - panel.desired_size();
+            if panel.desired_local_position() != position {
+                panel.set_desired_local_position(position);
+                self.nodes[self.root_canvas].invalidate_arrange();
+            }
+        }
+    }
+
+    /// Shows a notification (toast) with the given severity and text in the bottom-right corner of the screen. The
+    /// notification fades out after `duration` seconds. Returns a handle of the notification, that could be used
+    /// to close it earlier with [`notification::NotificationMessage::Close`].
+    pub fn show_notification<S: AsRef<str>>(
+        &mut self,
+        severity: NotificationSeverity,
+        text: S,
+        duration: f32,
+    ) -> Handle<UiNode> {
+        self.push_notification(
+            NotificationBuilder::new(WidgetBuilder::new())
+                .with_severity(severity)
+                .with_text(text)
+                .with_duration(duration),
+        )
+    }
+
+    /// Builds a notification and shows it in the bottom-right corner of the screen. Unlike
+    /// [`Self::show_notification`], this method allows you to add action buttons to the notification.
+    pub fn push_notification(&mut self, builder: NotificationBuilder) -> Handle<UiNode> {
+        if !self.nodes.is_valid_handle(self.notification_panel) {
+            self.notification_panel =
+                StackPanelBuilder::new(WidgetBuilder::new().with_margin(Thickness::uniform(4.0)))
+                    .build(&mut self.build_ctx());
+        }
+        let notification = builder.build(&mut self.build_ctx());
+        self.link_nodes_internal(notification, self.notification_panel, false);
+        // Notifications must be on top of everything else.
+        self.send_message(WidgetMessage::topmost(
+            self.notification_panel,
+            MessageDirection::ToWidget,
+        ));
+        notification
+    }
+
+    /// Shows a modal message box with the given title, text and buttons. The rest of the UI does not receive any input
+    /// until the message box is closed, then the message box is removed and keyboard focus returns to the widget that
+    /// had it before. The result can be fetched from [`MessageBoxMessage::Close`] message of the message box.
+    pub fn show_message_box<S: AsRef<str>>(
+        &mut self,
+        title: S,
+        text: S,
+        buttons: MessageBoxButtons,
+    ) -> Handle<UiNode> {
+        let message_box = MessageBoxBuilder::new(
+            WindowBuilder::new(WidgetBuilder::new().with_width(300.0).with_height(120.0))
+                .with_title(WindowTitle::text(title.as_ref()))
+                .can_minimize(false)
+                .open(false),
+        )
+        .with_text(text.as_ref())
+        .with_buttons(buttons)
+        .with_remove_on_close(true)
+        .build(&mut self.build_ctx());
+        self.send_message(MessageBoxMessage::open(
+            message_box,
+            MessageDirection::ToWidget,
+            None,
+            None,
+        ));
+        message_box
+    }
+
     pub fn cursor(&self) -> CursorIcon {
         self.cursor_icon
     }
@@ -994,12 +1280,59 @@ impl UserInterface {
         &self.drawing_context
     }
 
-    pub fn clipboard(&self) -> Option<Ref<ClipboardContext>> {
-        self.clipboard.0.as_ref().map(|v| v.borrow())
+    /// Returns a reference to the current clipboard of the user interface. The clipboard is always available, so
+    /// the method always returns `Some`, it is kept for backward compatibility - use
+    /// [`Self::clipboard_provider`] instead.
+    pub fn clipboard(&self) -> Option<Ref<'_, dyn ClipboardProvider + Send>> {
+        Some(self.clipboard_provider())
+    }
+
+    /// Returns a reference to the current clipboard of the user interface. The clipboard is always available, so
+    /// the method always returns `Some`, it is kept for backward compatibility - use
+    /// [`Self::clipboard_provider_mut`] instead.
+    pub fn clipboard_mut(&self) -> Option<RefMut<'_, dyn ClipboardProvider + Send>> {
+        Some(self.clipboard_provider_mut())
     }
 
-    pub fn clipboard_mut(&self) -> Option<RefMut<ClipboardContext>> {
-        self.clipboard.0.as_ref().map(|v| v.borrow_mut())
+    /// Returns a reference to the current clipboard of the user interface.
+    pub fn clipboard_provider(&self) -> Ref<'_, dyn ClipboardProvider + Send> {
+        Ref::map(self.clipboard.0.borrow(), |clipboard| &**clipboard)
+    }
+
+    /// Returns a reference to the current clipboard of the user interface. Text widgets use it to copy, cut and
+    /// paste text.
+    pub fn clipboard_provider_mut(&self) -> RefMut<'_, dyn ClipboardProvider + Send> {
+        RefMut::map(self.clipboard.0.borrow_mut(), |clipboard| &mut **clipboard)
+    }
+
+    /// Sets new clipboard of the user interface. By default, the user interface uses the system clipboard and
+    /// falls back to in-memory [`LocalClipboard`] if the system clipboard is unavailable. Use this method if you
+    /// want to plug your own clipboard, for example when your game uses a custom windowing library.
+    ///
+    /// ```rust
+    /// # use fyrox_ui::{copypasta::ClipboardProvider, UserInterface};
+    /// # use std::error::Error;
+    /// #
+    /// struct MyClipboard;
+    ///
+    /// impl ClipboardProvider for MyClipboard {
+    ///     fn get_contents(&mut self) -> Result<String, Box<dyn Error + Send + Sync + 'static>> {
+    ///         // Fetch the text from your window here.
+    ///         Ok(String::new())
+    ///     }
+    ///
+    ///     fn set_contents(&mut self, _contents: String) -> Result<(), Box<dyn Error + Send + Sync + 'static>> {
+    ///         // Put the text to your window here.
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// fn set_clipboard(ui: &mut UserInterface) {
+    ///     ui.set_clipboard(Box::new(MyClipboard));
+    /// }
+    /// ```
+    pub fn set_clipboard(&mut self, clipboard: Box<dyn ClipboardProvider + Send>) {
+        self.clipboard = Clipboard(RefCell::new(clipboard));
     }
 
     pub fn arrange_node(&self, handle: Handle<UiNode>, final_rect: &Rect<f32>) -> bool {
@@ -1614,12 +1947,12 @@ impl UserInterface {
                         WidgetMessage::Center => {
                             if self.nodes.is_valid_handle(message.destination()) {
                                 let node = self.node(message.destination());
-                                let size = node.actual_initial_size();
+                                let size = node.actual_local_size();
                                 let parent = node.parent();
                                 let parent_size = if parent.is_some() {
-                                    self.node(parent).actual_initial_size()
+                                    self.node(parent).actual_local_size()
                                 } else {
-                                    self.screen_size
+                                    self.logical_screen_size()
                                 };
 
                                 self.send_message(WidgetMessage::desired_position(
@@ -1633,12 +1966,12 @@ impl UserInterface {
                             if self.nodes.is_valid_handle(message.destination()) {
                                 let node = self.node(message.destination());
                                 let mut position = node.actual_local_position();
-                                let size = node.actual_initial_size();
+                                let size = node.actual_local_size();
                                 let parent = node.parent();
                                 let parent_size = if parent.is_some() {
-                                    self.node(parent).actual_initial_size()
+                                    self.node(parent).actual_local_size()
                                 } else {
-                                    self.screen_size
+                                    self.logical_screen_size()
                                 };
 
                                 if position.x < 0.0 {
@@ -1697,6 +2030,33 @@ impl UserInterface {
                         }
                         _ => {}
                     }
+                } else if let Some(msg) = message.data::<TransitionMessage>() {
+                    if message.direction() == MessageDirection::ToWidget {
+                        let node = message.destination();
+                        match msg {
+                            TransitionMessage::Start(transition) => {
+                                self.transitions
+                                    .retain(|t| !t.is_same_property(node, transition));
+                                self.transitions.push(ActiveTransition::new(
+                                    node,
+                                    &self.nodes[node],
+                                    *transition,
+                                ));
+                            }
+                            TransitionMessage::Stop => {
+                                self.transitions.retain(|t| t.node != node);
+                            }
+                            TransitionMessage::Finished(_) => {}
+                        }
+                    }
+                } else if let Some(LocalizationMessage::Language(language)) = message.data() {
+                    if message.direction() == MessageDirection::ToWidget {
+                        self.set_language(language);
+                    }
+                } else if let Some(UiScaleMessage::Scale(scale)) = message.data() {
+                    if message.direction() == MessageDirection::ToWidget {
+                        self.set_scale(*scale);
+                    }
                 }
 
                 Some(message)
@@ -1708,6 +2068,42 @@ impl UserInterface {
         }
     }
 
+    /// Tries to find a widget with drop allowed in the hierarchy starting from the given node.
+    fn find_drop_target(&self, mut handle: Handle<UiNode>) -> Handle<UiNode> {
+        while let Some(node) = self.nodes.try_borrow(handle) {
+            if node.is_drop_allowed() {
+                return handle;
+            }
+            handle = node.parent();
+        }
+        Handle::NONE
+    }
+
+    /// Changes current drop target and notifies both previous and new targets about the change.
+    fn set_drop_target(&mut self, drop_target: Handle<UiNode>) {
+        if self.drag_context.drop_target == drop_target {
+            return;
+        }
+
+        if self.nodes.is_valid_handle(self.drag_context.drop_target) {
+            self.send_message(WidgetMessage::drag_leave(
+                self.drag_context.drop_target,
+                MessageDirection::FromWidget,
+                self.drag_context.drag_node,
+            ));
+        }
+
+        if drop_target.is_some() {
+            self.send_message(WidgetMessage::drag_enter(
+                drop_target,
+                MessageDirection::FromWidget,
+                self.drag_context.drag_node,
+            ));
+        }
+
+        self.drag_context.drop_target = drop_target;
+    }
+
     pub fn screen_to_root_canvas_space(&self, position: Vector2<f32>) -> Vector2<f32> {
         self.node(self.root()).screen_to_local(position)
     }
@@ -1942,23 +2338,15 @@ impl UserInterface {
                                 self.drag_context.is_dragging = false;
                                 self.cursor_icon = CursorIcon::Default;
 
-                                // Try to find node with drop allowed in hierarchy starting from picked node.
-                                self.stack.clear();
-                                self.stack.push(self.picked_node);
-                                while let Some(handle) = self.stack.pop() {
-                                    let node = &self.nodes[handle];
-                                    if node.is_drop_allowed() {
-                                        self.send_message(WidgetMessage::drop(
-                                            handle,
-                                            MessageDirection::FromWidget,
-                                            self.drag_context.drag_node,
-                                        ));
-                                        self.stack.clear();
-                                        break;
-                                    } else if node.parent().is_some() {
-                                        self.stack.push(node.parent());
-                                    }
+                                let drop_target = self.find_drop_target(self.picked_node);
+                                if drop_target.is_some() {
+                                    self.send_message(WidgetMessage::drop(
+                                        drop_target,
+                                        MessageDirection::FromWidget,
+                                        self.drag_context.drag_node,
+                                    ));
                                 }
+                                self.set_drop_target(Handle::NONE);
                             }
                             self.drag_context.drag_node = Handle::NONE;
                             if self.nodes.is_valid_handle(self.drag_context.drag_preview) {
@@ -2004,14 +2392,16 @@ impl UserInterface {
                     self.cursor_icon = CursorIcon::Crosshair;
                 }
 
-                if self.drag_context.is_dragging
-                    && self.nodes.is_valid_handle(self.drag_context.drag_preview)
-                {
-                    self.send_message(WidgetMessage::desired_position(
-                        self.drag_context.drag_preview,
-                        MessageDirection::ToWidget,
-                        *position,
-                    ));
+                if self.drag_context.is_dragging {
+                    if self.nodes.is_valid_handle(self.drag_context.drag_preview) {
+                        self.send_message(WidgetMessage::desired_position(
+                            self.drag_context.drag_preview,
+                            MessageDirection::ToWidget,
+                            self.screen_to_root_canvas_space(*position),
+                        ));
+                    }
+
+                    self.set_drop_target(self.find_drop_target(self.picked_node));
                 }
 
                 // Fire mouse leave for previously picked node
@@ -2099,10 +2489,32 @@ impl UserInterface {
                     event_processed = true;
                 }
             }
+            OsEvent::Ime(ime) => {
+                if self.keyboard_focus_node.is_some() {
+                    if let ImeEvent::Commit(text) = ime {
+                        self.send_message(WidgetMessage::text(
+                            self.keyboard_focus_node,
+                            MessageDirection::FromWidget,
+                            text.clone(),
+                        ));
+                    } else {
+                        self.send_message(WidgetMessage::ime(
+                            self.keyboard_focus_node,
+                            MessageDirection::FromWidget,
+                            ime.clone(),
+                        ));
+                    }
+
+                    event_processed = true;
+                }
+            }
             &OsEvent::KeyboardModifiers(modifiers) => {
                 // TODO: Is message needed for focused node?
                 self.keyboard_modifiers = modifiers;
             }
+            &OsEvent::ScaleFactorChanged(scale_factor) => {
+                self.set_scale_factor(scale_factor);
+            }
             OsEvent::Touch {
                 phase,
                 location,
@@ -2229,23 +2641,15 @@ impl UserInterface {
                         if self.drag_context.is_dragging {
                             self.drag_context.is_dragging = false;
 
-                            // Try to find node with drop allowed in hierarchy starting from picked node.
-                            self.stack.clear();
-                            self.stack.push(self.picked_node);
-                            while let Some(handle) = self.stack.pop() {
-                                let node = &self.nodes[handle];
-                                if node.is_drop_allowed() {
-                                    self.send_message(WidgetMessage::drop(
-                                        handle,
-                                        MessageDirection::FromWidget,
-                                        self.drag_context.drag_node,
-                                    ));
-                                    self.stack.clear();
-                                    break;
-                                } else if node.parent().is_some() {
-                                    self.stack.push(node.parent());
-                                }
+                            let drop_target = self.find_drop_target(self.picked_node);
+                            if drop_target.is_some() {
+                                self.send_message(WidgetMessage::drop(
+                                    drop_target,
+                                    MessageDirection::FromWidget,
+                                    self.drag_context.drag_node,
+                                ));
                             }
+                            self.set_drop_target(Handle::NONE);
                         }
                         self.drag_context.drag_node = Handle::NONE;
                         if self.nodes.is_valid_handle(self.drag_context.drag_preview) {
@@ -2269,6 +2673,7 @@ impl UserInterface {
                             self.drag_context.is_dragging = false;
                             self.cursor_icon = CursorIcon::Default;
                             self.stack.clear();
+                            self.set_drop_target(Handle::NONE);
                         }
                         self.drag_context.drag_node = Handle::NONE;
                         if self.nodes.is_valid_handle(self.drag_context.drag_preview) {
@@ -2320,6 +2725,8 @@ impl UserInterface {
             self.link_nodes_internal(child, node_handle, false)
         }
         let node = self.nodes[node_handle].deref_mut();
+        node.apply_style(&self.style);
+        node.apply_localization(&self.localization);
         node.layout_events_sender = Some(self.layout_events_sender.clone());
         if node.preview_messages {
             self.preview_set.insert(node_handle);
@@ -2718,11 +3125,13 @@ mod test {
         border::BorderBuilder,
         core::algebra::{Rotation2, UnitComplex, Vector2},
         message::MessageDirection,
+        text::TextBuilder,
         text_box::TextBoxBuilder,
         transform_size,
         widget::{WidgetBuilder, WidgetMessage},
-        OsEvent, UserInterface,
+        MouseButton, OsEvent, UiScaleMessage, UserInterface,
     };
+    use std::rc::Rc;
 
     #[test]
     fn test_transform_size() {
@@ -2753,6 +3162,150 @@ mod test {
         assert_eq!(actual_position, expected_position);
     }
 
+    #[test]
+    fn scale() {
+        let screen_size = Vector2::new(1000.0, 1000.0);
+        let widget_size = Vector2::new(100.0, 100.0);
+        let mut ui = UserInterface::new(screen_size);
+        let widget = BorderBuilder::new(
+            WidgetBuilder::new()
+                .with_width(widget_size.x)
+                .with_height(widget_size.y),
+        )
+        .build(&mut ui.build_ctx());
+        ui.set_scale(2.0);
+        ui.update(screen_size, 0.0);
+        assert_eq!(ui.logical_screen_size(), Vector2::new(500.0, 500.0));
+        assert_eq!(
+            ui.node(ui.root()).actual_local_size(),
+            ui.logical_screen_size()
+        );
+        // Layout is done in logical units, but the widget is twice as big on the screen.
+        assert_eq!(ui.node(widget).actual_local_size(), widget_size);
+        assert_eq!(ui.node(widget).actual_global_size(), widget_size.scale(2.0));
+
+        // Centering must be done in logical units too.
+        ui.send_message(WidgetMessage::center(widget, MessageDirection::ToWidget));
+        while ui.poll_message().is_some() {}
+        ui.update(screen_size, 0.0);
+        assert_eq!(
+            ui.node(widget).screen_bounds().center(),
+            screen_size.scale(0.5)
+        );
+    }
+
+    #[test]
+    fn scale_factor() {
+        let screen_size = Vector2::new(1000.0, 1000.0);
+        let mut ui = UserInterface::new(screen_size);
+        let text = TextBuilder::new(WidgetBuilder::new())
+            .with_text("Text")
+            .build(&mut ui.build_ctx());
+        ui.update(screen_size, 0.0);
+        let text_size = ui.node(text).actual_local_size();
+        let atlas_size = ui.default_font.0.lock().atlas_size();
+
+        // The scale factor of the window is applied on top of the user-defined scale.
+        ui.process_os_event(&OsEvent::ScaleFactorChanged(2.0));
+        ui.send_message(UiScaleMessage::scale(
+            ui.root(),
+            MessageDirection::ToWidget,
+            1.5,
+        ));
+        while ui.poll_message().is_some() {}
+        ui.update(screen_size, 0.0);
+        assert_eq!(ui.scale(), 1.5);
+        assert_eq!(ui.scale_factor(), 2.0);
+        assert_eq!(ui.effective_scale(), 3.0);
+
+        // Fonts are rasterized at the effective scale, but the layout of the text stays the same.
+        let font = ui.default_font.0.lock();
+        assert_eq!(font.render_scale(), 3.0);
+        assert!(font.atlas_size() > atlas_size);
+        drop(font);
+        assert_eq!(ui.node(text).actual_local_size(), text_size);
+        assert_eq!(
+            ui.node(text).actual_global_size(),
+            text_size.scale(ui.effective_scale())
+        );
+    }
+
+    #[test]
+    fn drag_and_drop() {
+        let screen_size = Vector2::new(1000.0, 1000.0);
+        let mut ui = UserInterface::new(screen_size);
+        let ctx = &mut ui.build_ctx();
+        let source = BorderBuilder::new(
+            WidgetBuilder::new()
+                .with_width(100.0)
+                .with_height(100.0)
+                .with_allow_drag(true)
+                .with_drag_payload(Rc::new("Payload".to_string())),
+        )
+        .build(ctx);
+        let target = BorderBuilder::new(
+            WidgetBuilder::new()
+                .with_desired_position(Vector2::new(200.0, 0.0))
+                .with_width(100.0)
+                .with_height(100.0)
+                .with_allow_drop(true),
+        )
+        .build(ctx);
+        ui.update(screen_size, 0.0);
+        ui.draw();
+
+        let mut events = Vec::new();
+        let mut process = |ui: &mut UserInterface, event: OsEvent| {
+            ui.process_os_event(&event);
+            while let Some(message) = ui.poll_message() {
+                match message.data() {
+                    Some(WidgetMessage::DragEnter(dragged)) => {
+                        events.push(("enter", message.destination(), *dragged))
+                    }
+                    Some(WidgetMessage::DragLeave(dragged)) => {
+                        events.push(("leave", message.destination(), *dragged))
+                    }
+                    Some(WidgetMessage::Drop(dragged)) => {
+                        events.push(("drop", message.destination(), *dragged))
+                    }
+                    _ => (),
+                }
+            }
+        };
+
+        let move_cursor = |x, y| OsEvent::CursorMoved {
+            position: Vector2::new(x, y),
+        };
+        let mouse_input = |state| OsEvent::MouseInput {
+            button: MouseButton::Left,
+            state,
+        };
+        process(&mut ui, move_cursor(50.0, 50.0));
+        process(&mut ui, mouse_input(ButtonState::Pressed));
+        process(&mut ui, move_cursor(60.0, 50.0));
+        assert!(ui.drag_context().is_dragging);
+        process(&mut ui, move_cursor(250.0, 50.0));
+        process(&mut ui, move_cursor(500.0, 50.0));
+        process(&mut ui, move_cursor(250.0, 50.0));
+        process(&mut ui, mouse_input(ButtonState::Released));
+
+        assert_eq!(
+            events,
+            [
+                ("enter", target, source),
+                ("leave", target, source),
+                ("enter", target, source),
+                ("drop", target, source),
+                ("leave", target, source),
+            ]
+        );
+        assert_eq!(
+            ui.node(source).drag_payload_ref::<String>().unwrap(),
+            "Payload"
+        );
+        assert!(!ui.drag_context().is_dragging);
+    }
+
     #[test]
     fn test_keyboard_focus() {
         let screen_size = Vector2::new(1000.0, 1000.0);
@@ -14,9 +14,9 @@ use crate::{
     message::{MessageDirection, UiMessage},
     scroll_viewer::{ScrollViewer, ScrollViewerBuilder, ScrollViewerMessage},
     stack_panel::StackPanelBuilder,
+    style::Style,
     widget::{Widget, WidgetBuilder, WidgetMessage},
-    BuildContext, Control, NodeHandleMapping, Thickness, UiNode, UserInterface, BRUSH_DARK,
-    BRUSH_LIGHT,
+    BuildContext, Control, NodeHandleMapping, Thickness, UiNode, UserInterface,
 };
 use std::{
     any::{Any, TypeId},
@@ -496,8 +496,8 @@ impl ListViewBuilder {
 
         let back = BorderBuilder::new(
             WidgetBuilder::new()
-                .with_background(BRUSH_DARK)
-                .with_foreground(BRUSH_LIGHT),
+                .with_background_style(Style::BRUSH_DARK)
+                .with_foreground_style(Style::BRUSH_LIGHT),
         )
         .with_stroke_thickness(Thickness::uniform(1.0))
         .build(ctx);
@@ -0,0 +1,229 @@
+//! Localization layer of the user interface. Text widgets could be bound to localization keys, which are resolved
+//! into text of current language using a [`TranslationProvider`]. See [`Localization`] docs for more info.
+
+#![warn(missing_docs)]
+
+use crate::{
+    core::pool::Handle,
+    define_constructor,
+    message::{MessageDirection, UiMessage},
+    UiNode,
+};
+use fxhash::FxHashMap;
+use std::fmt::Debug;
+
+/// Translation provider resolves localization keys into text of a specific language. Implement this trait if
+/// you want to fetch your translations from some custom source (a database, a special file format, etc.).
+pub trait TranslationProvider: Debug {
+    /// Returns translated text for the given key in the given language, or [`None`] if there's no such translation.
+    fn translate(&self, language: &str, key: &str) -> Option<String>;
+}
+
+/// Simple in-memory translation provider, that stores strings of every language in a hash map.
+#[derive(Default, Debug, Clone)]
+pub struct StringTable {
+    languages: FxHashMap<String, FxHashMap<String, String>>,
+}
+
+impl StringTable {
+    /// Creates new empty string table.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a translation of the given key to the given language. Previous translation (if any) is replaced.
+    pub fn add_string<L, K, T>(&mut self, language: L, key: K, text: T)
+    where
+        L: Into<String>,
+        K: Into<String>,
+        T: Into<String>,
+    {
+        self.languages
+            .entry(language.into())
+            .or_default()
+            .insert(key.into(), text.into());
+    }
+
+    /// Adds a translation of the given key to the given language and returns the table back. Useful for chaining.
+    pub fn with_string<L, K, T>(mut self, language: L, key: K, text: T) -> Self
+    where
+        L: Into<String>,
+        K: Into<String>,
+        T: Into<String>,
+    {
+        self.add_string(language, key, text);
+        self
+    }
+}
+
+impl TranslationProvider for StringTable {
+    fn translate(&self, language: &str, key: &str) -> Option<String> {
+        self.languages
+            .get(language)
+            .and_then(|strings| strings.get(key))
+            .cloned()
+    }
+}
+
+/// Localization context of the user interface - current language and a translation provider. Every text widget
+/// with a localization key (see [`crate::text::TextBuilder::with_localization_key`]) takes its text from the
+/// context, and it is updated automatically when the language or the provider changes.
+///
+/// ## Examples
+///
+/// ```rust
+/// # use fyrox_ui::{
+/// #     button::ButtonBuilder,
+/// #     localization::{LocalizationMessage, StringTable},
+/// #     message::MessageDirection,
+/// #     widget::WidgetBuilder,
+/// #     UserInterface,
+/// # };
+/// #
+/// fn create_ui(ui: &mut UserInterface) {
+///     ui.set_translation_provider(Box::new(
+///         StringTable::new()
+///             .with_string("en", "button.quit", "Quit")
+///             .with_string("de", "button.quit", "Beenden"),
+///     ));
+///
+///     ButtonBuilder::new(WidgetBuilder::new())
+///         .with_localized_text("button.quit")
+///         .build(&mut ui.build_ctx());
+/// }
+///
+/// fn switch_to_german(ui: &UserInterface) {
+///     ui.send_message(LocalizationMessage::language(
+///         ui.root(),
+///         MessageDirection::ToWidget,
+///         "de".to_string(),
+///     ));
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Localization {
+    language: String,
+    provider: Option<Box<dyn TranslationProvider>>,
+}
+
+impl Default for Localization {
+    fn default() -> Self {
+        Self {
+            language: "en".to_string(),
+            provider: None,
+        }
+    }
+}
+
+impl Localization {
+    /// Returns current language.
+    pub fn language(&self) -> &str {
+        &self.language
+    }
+
+    /// Translates the given key to current language. If there's no translation, the key itself is returned, so
+    /// missing translations are easy to spot.
+    pub fn translate(&self, key: &str) -> String {
+        self.provider
+            .as_ref()
+            .and_then(|provider| provider.translate(&self.language, key))
+            .unwrap_or_else(|| key.to_string())
+    }
+
+    pub(crate) fn set_language(&mut self, language: String) {
+        self.language = language;
+    }
+
+    pub(crate) fn set_provider(&mut self, provider: Box<dyn TranslationProvider>) {
+        self.provider = Some(provider);
+    }
+}
+
+/// A set of messages, that is used to change localization of the user interface at runtime.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LocalizationMessage {
+    /// Sets new language of the user interface and updates every localized text. This message is handled by the
+    /// user interface itself, send it to the root widget ([`crate::UserInterface::root`]).
+    ///
+    /// Direction: **To UI**.
+    Language(String),
+}
+
+impl LocalizationMessage {
+    define_constructor!(
+        /// Creates [`LocalizationMessage::Language`] message.
+        LocalizationMessage:Language => fn language(String), layout: false
+    );
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        button::ButtonBuilder,
+        core::algebra::Vector2,
+        localization::{LocalizationMessage, StringTable},
+        menu::{MenuItemBuilder, MenuItemContent},
+        message::MessageDirection,
+        text::{Text, TextBuilder, TextMessage},
+        widget::WidgetBuilder,
+        UserInterface,
+    };
+
+    fn texts(ui: &UserInterface) -> Vec<String> {
+        ui.nodes()
+            .iter()
+            .filter_map(|n| n.query_component::<Text>())
+            .map(|t| t.text())
+            .filter(|t| !t.is_empty())
+            .collect()
+    }
+
+    #[test]
+    fn switch_language() {
+        let mut ui = UserInterface::new(Vector2::new(100.0, 100.0));
+        ui.set_translation_provider(Box::new(
+            StringTable::new()
+                .with_string("en", "greeting", "Hello")
+                .with_string("en", "quit", "Quit")
+                .with_string("en", "file", "File")
+                .with_string("de", "greeting", "Hallo")
+                .with_string("de", "quit", "Beenden")
+                .with_string("de", "file", "Datei"),
+        ));
+
+        let ctx = &mut ui.build_ctx();
+        let text = TextBuilder::new(WidgetBuilder::new())
+            .with_localization_key("greeting")
+            .build(ctx);
+        ButtonBuilder::new(WidgetBuilder::new())
+            .with_localized_text("quit")
+            .build(ctx);
+        MenuItemBuilder::new(WidgetBuilder::new())
+            .with_content(MenuItemContent::localized_text("file"))
+            .build(ctx);
+        assert_eq!(texts(&ui), ["Hello", "Quit", "File"]);
+
+        ui.send_message(LocalizationMessage::language(
+            ui.root(),
+            MessageDirection::ToWidget,
+            "de".to_string(),
+        ));
+        while ui.poll_message().is_some() {}
+        assert_eq!(ui.localization().language(), "de");
+        assert_eq!(texts(&ui), ["Hallo", "Beenden", "Datei"]);
+
+        // Explicitly set text is no longer bound to the key.
+        ui.send_message(TextMessage::text(
+            text,
+            MessageDirection::ToWidget,
+            "Custom".to_string(),
+        ));
+        while ui.poll_message().is_some() {}
+        ui.set_language("en");
+        assert_eq!(texts(&ui), ["Custom", "Quit", "File"]);
+
+        // Missing translations are shown as keys.
+        ui.set_language("fr");
+        assert_eq!(texts(&ui), ["Custom", "quit", "file"]);
+    }
+}
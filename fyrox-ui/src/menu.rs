@@ -14,13 +14,13 @@ use crate::{
     message::{ButtonState, MessageDirection, OsEvent, UiMessage},
     popup::{Placement, Popup, PopupBuilder, PopupMessage},
     stack_panel::StackPanelBuilder,
+    style::Style,
     text::TextBuilder,
     utils::{make_arrow_primitives, ArrowDirection},
     vector_image::VectorImageBuilder,
     widget::{Widget, WidgetBuilder, WidgetMessage},
     BuildContext, Control, HorizontalAlignment, NodeHandleMapping, Orientation, RestrictionEntry,
-    Thickness, UiNode, UserInterface, VerticalAlignment, BRUSH_BRIGHT, BRUSH_BRIGHT_BLUE,
-    BRUSH_PRIMARY,
+    Thickness, UiNode, UserInterface, VerticalAlignment,
 };
 use std::{
     any::{Any, TypeId},
@@ -602,7 +602,7 @@ impl MenuBuilder {
 
         let back = BorderBuilder::new(
             WidgetBuilder::new()
-                .with_background(BRUSH_PRIMARY)
+                .with_background_style(Style::BRUSH_PRIMARY)
                 .with_child(
                     StackPanelBuilder::new(
                         WidgetBuilder::new().with_children(self.items.iter().cloned()),
@@ -646,6 +646,8 @@ pub enum MenuItemContent<'a, 'b> {
         icon: Handle<UiNode>,
         /// Create an arrow or not.
         arrow: bool,
+        /// If `true`, the text is a localization key. See [`crate::localization::Localization`] for more info.
+        localized: bool,
     },
     /// Allows to put any node into menu item. It allows to customize menu item how needed - i.e. put image in it, or other user
     /// control.
@@ -660,6 +662,7 @@ impl<'a, 'b> MenuItemContent<'a, 'b> {
             shortcut,
             icon: Default::default(),
             arrow: true,
+            localized: false,
         }
     }
 
@@ -670,6 +673,19 @@ impl<'a, 'b> MenuItemContent<'a, 'b> {
             shortcut: "",
             icon: Default::default(),
             arrow: true,
+            localized: false,
+        }
+    }
+
+    /// Creates a menu item content with a text, that is taken from the given localization key, and an arrow (with no
+    /// icon or shortcut).
+    pub fn localized_text(key: &'a str) -> Self {
+        MenuItemContent::Text {
+            text: key,
+            shortcut: "",
+            icon: Default::default(),
+            arrow: true,
+            localized: true,
         }
     }
 
@@ -680,6 +696,7 @@ impl<'a, 'b> MenuItemContent<'a, 'b> {
             shortcut: "",
             icon: Default::default(),
             arrow: false,
+            localized: false,
         }
     }
 }
@@ -739,17 +756,24 @@ impl<'a, 'b> MenuItemBuilder<'a, 'b> {
                 shortcut,
                 icon,
                 arrow,
+                localized,
             }) => GridBuilder::new(
                 WidgetBuilder::new()
                     .with_child(icon)
                     .with_child(
-                        TextBuilder::new(
-                            WidgetBuilder::new()
-                                .with_vertical_alignment(VerticalAlignment::Center)
-                                .with_margin(Thickness::left(2.0))
-                                .on_column(1),
-                        )
-                        .with_text(text)
+                        {
+                            let builder = TextBuilder::new(
+                                WidgetBuilder::new()
+                                    .with_vertical_alignment(VerticalAlignment::Center)
+                                    .with_margin(Thickness::left(2.0))
+                                    .on_column(1),
+                            );
+                            if localized {
+                                builder.with_localization_key(text)
+                            } else {
+                                builder.with_text(text)
+                            }
+                        }
                         .build(ctx),
                     )
                     .with_child(
@@ -768,7 +792,7 @@ impl<'a, 'b> MenuItemBuilder<'a, 'b> {
                             WidgetBuilder::new()
                                 .with_visibility(!self.items.is_empty())
                                 .on_column(3)
-                                .with_foreground(BRUSH_BRIGHT)
+                                .with_foreground_style(Style::BRUSH_BRIGHT)
                                 .with_horizontal_alignment(HorizontalAlignment::Center)
                                 .with_vertical_alignment(VerticalAlignment::Center),
                         )
@@ -792,8 +816,8 @@ impl<'a, 'b> MenuItemBuilder<'a, 'b> {
                 BorderBuilder::new(WidgetBuilder::new())
                     .with_stroke_thickness(Thickness::uniform(0.0)),
             )
-            .with_hover_brush(BRUSH_BRIGHT_BLUE)
-            .with_normal_brush(BRUSH_PRIMARY)
+            .with_hover_brush_style(Style::BRUSH_BRIGHT_BLUE)
+            .with_normal_brush_style(Style::BRUSH_PRIMARY)
             .with_pressed_brush(Brush::Solid(Color::TRANSPARENT))
             .with_pressable(false)
             .build(ctx)
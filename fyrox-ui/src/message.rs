@@ -523,6 +523,34 @@ pub enum OsEvent {
         /// Unique touch event identifier to distinguish between fingers, for example
         id: u64,
     },
+    /// Input method editor (IME) event, that is used to enter text of languages which cannot be typed with a single
+    /// key press (Chinese, Japanese, Korean, etc.).
+    Ime(ImeEvent),
+    /// Scale factor (DPI) of the window has changed, see [`crate::UserInterface::set_scale_factor`].
+    ScaleFactorChanged(f32),
+}
+
+/// An event of an input method editor (IME). While the user composes a text, the IME sends a series of
+/// [`ImeEvent::Preedit`] events with the text being composed, which ends with [`ImeEvent::Commit`] that
+/// contains the final text.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ImeEvent {
+    /// The IME was enabled, composition events may be sent after this event.
+    Enabled,
+    /// A new composing text should be shown at the caret position. Empty text means that the composition
+    /// was cancelled or finished.
+    Preedit {
+        /// Text that is being composed.
+        text: String,
+        /// Byte range of the cursor within the composing text, [`None`] means that the cursor should be hidden.
+        cursor: Option<(usize, usize)>,
+    },
+    /// The composition is finished, the text should be inserted at the caret position. This event is converted
+    /// to [`crate::widget::WidgetMessage::Text`] by the user interface, so every widget that accepts text
+    /// input supports IME automatically.
+    Commit(String),
+    /// The IME was disabled, any composing text should be discarded.
+    Disabled,
 }
 
 /// A set of possible keyboard modifiers.
@@ -15,7 +15,7 @@ use crate::{
     message::{MessageDirection, OsEvent, UiMessage},
     stack_panel::StackPanelBuilder,
     text::{TextBuilder, TextMessage},
-    widget::{Widget, WidgetBuilder},
+    widget::{Widget, WidgetBuilder, WidgetMessage},
     window::{Window, WindowBuilder, WindowMessage, WindowTitle},
     BuildContext, Control, HorizontalAlignment, NodeHandleMapping, Orientation, RestrictionEntry,
     Thickness, UiNode, UserInterface,
@@ -55,7 +55,8 @@ impl MessageBoxMessage {
 /// A set of possible reasons why a message box was closed.
 #[derive(Copy, Clone, PartialOrd, PartialEq, Ord, Eq, Hash, Debug)]
 pub enum MessageBoxResult {
-    /// `Ok` button was pressed. It can be emitted only if your message box was created with [`MessageBoxButtons::Ok`].
+    /// `Ok` button was pressed. It can be emitted only if your message box was created with [`MessageBoxButtons::Ok`] or
+    /// [`MessageBoxButtons::OkCancel`].
     Ok,
     /// `No` button was pressed. It can be emitted only if your message box was created with [`MessageBoxButtons::YesNo`] or
     /// [`MessageBoxButtons::YesNoCancel`].
@@ -63,7 +64,8 @@ pub enum MessageBoxResult {
     /// `Yes` button was pressed. It can be emitted only if your message box was created with [`MessageBoxButtons::YesNo`] or
     /// [`MessageBoxButtons::YesNoCancel`].
     Yes,
-    /// `Cancel` button was pressed. It can be emitted only if your message box was created with [`MessageBoxButtons::YesNoCancel`].
+    /// `Cancel` button was pressed. It can be emitted only if your message box was created with [`MessageBoxButtons::OkCancel`]
+    /// or [`MessageBoxButtons::YesNoCancel`].
     Cancel,
 }
 
@@ -73,6 +75,8 @@ pub enum MessageBoxButtons {
     /// Only `Ok` button. It is typically used to show a message with results of some finished action.
     #[default]
     Ok,
+    /// `Ok` and `Cancel` buttons. It is typically used to ask a user to confirm an action, that could be cancelled.
+    OkCancel,
     /// `Yes` and `No` buttons. It is typically used to show a message to ask a user if they are want to continue or not.
     YesNo,
     /// `Yes`, `No`, `Cancel` buttons. It is typically used to show a message to ask a user if they are want to confirm action,
@@ -166,6 +170,9 @@ pub struct MessageBox {
     pub cancel: Handle<UiNode>,
     /// A handle of text widget.
     pub text: Handle<UiNode>,
+    /// A flag, that defines whether the message box should be removed from the UI when closed or not.
+    #[visit(optional)]
+    pub remove_on_close: bool,
 }
 
 impl Deref for MessageBox {
@@ -225,7 +232,7 @@ impl Control for MessageBox {
         if let Some(ButtonMessage::Click) = message.data::<ButtonMessage>() {
             if message.destination() == self.ok_yes {
                 let result = match self.buttons {
-                    MessageBoxButtons::Ok => MessageBoxResult::Ok,
+                    MessageBoxButtons::Ok | MessageBoxButtons::OkCancel => MessageBoxResult::Ok,
                     MessageBoxButtons::YesNo => MessageBoxResult::Yes,
                     MessageBoxButtons::YesNoCancel => MessageBoxResult::Yes,
                 };
@@ -278,6 +285,12 @@ impl Control for MessageBox {
                         self.handle(),
                         MessageDirection::ToWidget,
                     ));
+                    if self.remove_on_close {
+                        ui.send_message(WidgetMessage::remove(
+                            self.handle(),
+                            MessageDirection::ToWidget,
+                        ));
+                    }
                 }
             }
         }
@@ -302,6 +315,7 @@ pub struct MessageBoxBuilder<'b> {
     window_builder: WindowBuilder,
     buttons: MessageBoxButtons,
     text: &'b str,
+    remove_on_close: bool,
 }
 
 impl<'b> MessageBoxBuilder<'b> {
@@ -311,6 +325,7 @@ impl<'b> MessageBoxBuilder<'b> {
             window_builder,
             buttons: MessageBoxButtons::Ok,
             text: "",
+            remove_on_close: false,
         }
     }
 
@@ -326,6 +341,13 @@ impl<'b> MessageBoxBuilder<'b> {
         self
     }
 
+    /// Sets whether the message box should be removed from the user interface when closed or not. It is useful for
+    /// one-shot message boxes.
+    pub fn with_remove_on_close(mut self, remove_on_close: bool) -> Self {
+        self.remove_on_close = remove_on_close;
+        self
+    }
+
     /// Finished message box building and adds it to the user interface.
     pub fn build(mut self, ctx: &mut BuildContext) -> Handle<UiNode> {
         let ok_yes;
@@ -404,6 +426,49 @@ impl<'b> MessageBoxBuilder<'b> {
             .add_row(Row::strict(25.0))
             .add_column(Column::stretch())
             .build(ctx),
+            MessageBoxButtons::OkCancel => GridBuilder::new(
+                WidgetBuilder::new()
+                    .with_child({
+                        text = TextBuilder::new(WidgetBuilder::new())
+                            .with_text(self.text)
+                            .with_wrap(WrapMode::Word)
+                            .build(ctx);
+                        text
+                    })
+                    .with_child(
+                        StackPanelBuilder::new(
+                            WidgetBuilder::new()
+                                .with_horizontal_alignment(HorizontalAlignment::Right)
+                                .on_row(1)
+                                .with_child({
+                                    ok_yes = ButtonBuilder::new(
+                                        WidgetBuilder::new()
+                                            .with_width(80.0)
+                                            .with_margin(Thickness::uniform(1.0)),
+                                    )
+                                    .with_text("OK")
+                                    .build(ctx);
+                                    ok_yes
+                                })
+                                .with_child({
+                                    cancel = ButtonBuilder::new(
+                                        WidgetBuilder::new()
+                                            .with_width(80.0)
+                                            .with_margin(Thickness::uniform(1.0)),
+                                    )
+                                    .with_text("Cancel")
+                                    .build(ctx);
+                                    cancel
+                                }),
+                        )
+                        .with_orientation(Orientation::Horizontal)
+                        .build(ctx),
+                    ),
+            )
+            .add_row(Row::stretch())
+            .add_row(Row::strict(25.0))
+            .add_column(Column::stretch())
+            .build(ctx),
             MessageBoxButtons::YesNoCancel => GridBuilder::new(
                 WidgetBuilder::new()
                     .with_child({
@@ -474,6 +539,7 @@ impl<'b> MessageBoxBuilder<'b> {
             no,
             cancel,
             text,
+            remove_on_close: self.remove_on_close,
         };
 
         let handle = ctx.add_node(UiNode::new(message_box));
@@ -486,3 +552,42 @@ impl<'b> MessageBoxBuilder<'b> {
         handle
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        button::ButtonBuilder,
+        core::algebra::Vector2,
+        message::MessageDirection,
+        messagebox::{MessageBoxButtons, MessageBoxMessage, MessageBoxResult},
+        widget::{WidgetBuilder, WidgetMessage},
+        UserInterface,
+    };
+
+    #[test]
+    fn message_box_focus() {
+        let screen_size = Vector2::new(800.0, 600.0);
+        let mut ui = UserInterface::new(screen_size);
+
+        let button = ButtonBuilder::new(WidgetBuilder::new()).build(&mut ui.build_ctx());
+        ui.send_message(WidgetMessage::focus(button, MessageDirection::ToWidget));
+        while ui.poll_message().is_some() {}
+        assert_eq!(ui.keyboard_focus_node, button);
+
+        let message_box = ui.show_message_box("Title", "Text", MessageBoxButtons::OkCancel);
+        while ui.poll_message().is_some() {}
+        // Modal message box takes the focus and restricts picking.
+        assert_eq!(ui.keyboard_focus_node, message_box);
+        assert_eq!(ui.top_picking_restriction().unwrap().handle, message_box);
+
+        ui.send_message(MessageBoxMessage::close(
+            message_box,
+            MessageDirection::ToWidget,
+            MessageBoxResult::Cancel,
+        ));
+        while ui.poll_message().is_some() {}
+        assert_eq!(ui.keyboard_focus_node, button);
+        assert!(ui.top_picking_restriction().is_none());
+        assert!(ui.try_get_node(message_box).is_none());
+    }
+}
@@ -0,0 +1,406 @@
+//! Notification (toast) is a small non-modal message, that is shown in a corner of the screen for some time. See
+//! [`Notification`] docs for more info and usage examples.
+
+#![warn(missing_docs)]
+
+use crate::{
+    border::BorderBuilder,
+    brush::Brush,
+    button::{ButtonBuilder, ButtonMessage},
+    core::{algebra::Vector2, color::Color, pool::Handle},
+    core::{reflect::prelude::*, visitor::prelude::*},
+    define_constructor,
+    formatted_text::WrapMode,
+    grid::{Column, GridBuilder, Row},
+    message::{MessageDirection, UiMessage},
+    stack_panel::StackPanelBuilder,
+    style::Style,
+    text::TextBuilder,
+    widget::{Widget, WidgetBuilder, WidgetMessage},
+    BuildContext, Control, HorizontalAlignment, NodeHandleMapping, Orientation, Thickness, UiNode,
+    UserInterface, VerticalAlignment, BRUSH_BRIGHT_BLUE,
+};
+use std::{
+    any::{Any, TypeId},
+    ops::{Deref, DerefMut},
+    sync::mpsc::Sender,
+};
+
+/// Duration (in seconds) of fade-in and fade-out animations of a notification.
+const FADE_TIME: f32 = 0.25;
+
+/// Severity of a [`Notification`]. It defines the color of the notification.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash, Visit, Reflect)]
+pub enum NotificationSeverity {
+    /// Informational message, for example "Asset imported".
+    #[default]
+    Info,
+    /// Warning message, something went wrong, but it is not critical.
+    Warning,
+    /// Error message, for example "Save failed".
+    Error,
+}
+
+impl NotificationSeverity {
+    /// Returns a brush, that is used to highlight notifications of the severity.
+    pub fn brush(self) -> Brush {
+        match self {
+            NotificationSeverity::Info => BRUSH_BRIGHT_BLUE,
+            NotificationSeverity::Warning => Brush::Solid(Color::opaque(210, 150, 40)),
+            NotificationSeverity::Error => Brush::Solid(Color::opaque(200, 50, 50)),
+        }
+    }
+}
+
+/// A set of messages, that can be used to modify/fetch the state of a [`Notification`] widget instance.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NotificationMessage {
+    /// Used to close a notification (with [`MessageDirection::ToWidget`] direction). The notification fades out and
+    /// then it is removed. Also emitted by the widget (with [`MessageDirection::FromWidget`]) when it starts closing.
+    Close,
+    /// Emitted by the widget (with [`MessageDirection::FromWidget`]) when an action button was clicked. It contains
+    /// an index of the action in the order of [`NotificationBuilder::with_action`] calls.
+    Action(usize),
+}
+
+impl NotificationMessage {
+    define_constructor!(
+        /// Creates [`NotificationMessage::Close`] message.
+        NotificationMessage:Close => fn close(), layout: false
+    );
+    define_constructor!(
+        /// Creates [`NotificationMessage::Action`] message.
+        NotificationMessage:Action => fn action(usize), layout: false
+    );
+}
+
+/// Notification (toast) is a small non-modal message, that is used to inform a user about background events
+/// (asset imported, save failed, etc.). Notifications stack in the bottom-right corner of the screen, fade in when
+/// shown and fade out when their time is over. A notification can also carry action buttons - a click on an action
+/// button emits [`NotificationMessage::Action`] and closes the notification.
+///
+/// ## Example
+///
+/// The easiest way to show a notification is to use [`UserInterface::show_notification`]:
+///
+/// ```rust
+/// # use fyrox_ui::{notification::NotificationSeverity, UserInterface};
+/// fn notify(ui: &mut UserInterface) {
+///     ui.show_notification(NotificationSeverity::Info, "Asset imported", 3.0);
+/// }
+/// ```
+///
+/// Use [`NotificationBuilder`] with [`UserInterface::push_notification`] to show a notification with actions:
+///
+/// ```rust
+/// # use fyrox_ui::{
+/// #     core::pool::Handle,
+/// #     notification::{NotificationBuilder, NotificationSeverity},
+/// #     widget::WidgetBuilder,
+/// #     UiNode, UserInterface,
+/// # };
+/// fn notify_failure(ui: &mut UserInterface) -> Handle<UiNode> {
+///     ui.push_notification(
+///         NotificationBuilder::new(WidgetBuilder::new())
+///             .with_severity(NotificationSeverity::Error)
+///             .with_text("Failed to save the scene")
+///             .with_duration(f32::INFINITY)
+///             .with_action("Retry"),
+///     )
+/// }
+/// ```
+#[derive(Clone, Debug, Visit, Reflect)]
+pub struct Notification {
+    /// Base widget of the notification.
+    pub widget: Widget,
+    /// Severity of the notification.
+    pub severity: NotificationSeverity,
+    /// Amount of time (in seconds) the notification will be shown before fading out.
+    pub duration: f32,
+    /// Amount of time (in seconds) the notification is shown.
+    pub elapsed: f32,
+    /// Remaining time of fade-out animation, `None` if the notification is not closing.
+    pub fade_out: Option<f32>,
+    /// A button, that closes the notification.
+    pub close: Handle<UiNode>,
+    /// Action buttons of the notification.
+    pub actions: Vec<Handle<UiNode>>,
+}
+
+crate::define_widget_deref!(Notification);
+
+impl Notification {
+    fn begin_close(&mut self, ui: &UserInterface) {
+        if self.fade_out.is_none() {
+            self.fade_out = Some(FADE_TIME);
+            ui.send_message(NotificationMessage::close(
+                self.handle,
+                MessageDirection::FromWidget,
+            ));
+        }
+    }
+}
+
+impl Control for Notification {
+    fn query_component(&self, type_id: TypeId) -> Option<&dyn Any> {
+        if type_id == TypeId::of::<Self>() {
+            Some(self)
+        } else {
+            None
+        }
+    }
+
+    fn resolve(&mut self, node_map: &NodeHandleMapping) {
+        node_map.resolve(&mut self.close);
+        node_map.resolve_slice(&mut self.actions);
+    }
+
+    fn update(&mut self, dt: f32, sender: &Sender<UiMessage>) {
+        self.elapsed += dt;
+
+        if let Some(fade_out) = self.fade_out {
+            let fade_out = fade_out - dt;
+            self.fade_out = Some(fade_out);
+            self.set_opacity(Some((fade_out / FADE_TIME).max(0.0)));
+            // Remove the notification only once, when the animation is over.
+            if fade_out <= 0.0 && fade_out + dt > 0.0 {
+                sender
+                    .send(WidgetMessage::remove(
+                        self.handle,
+                        MessageDirection::ToWidget,
+                    ))
+                    .unwrap();
+            }
+        } else {
+            let opacity = (self.elapsed / FADE_TIME).min(1.0);
+            self.set_opacity(Some(opacity));
+            if self.elapsed >= self.duration {
+                self.fade_out = Some(FADE_TIME);
+                sender
+                    .send(NotificationMessage::close(
+                        self.handle,
+                        MessageDirection::FromWidget,
+                    ))
+                    .unwrap();
+            }
+        }
+    }
+
+    fn handle_routed_message(&mut self, ui: &mut UserInterface, message: &mut UiMessage) {
+        self.widget.handle_routed_message(ui, message);
+
+        if let Some(ButtonMessage::Click) = message.data() {
+            if message.destination() == self.close {
+                self.begin_close(ui);
+            } else if let Some(index) = self
+                .actions
+                .iter()
+                .position(|action| *action == message.destination())
+            {
+                ui.send_message(NotificationMessage::action(
+                    self.handle,
+                    MessageDirection::FromWidget,
+                    index,
+                ));
+                self.begin_close(ui);
+            }
+        } else if let Some(NotificationMessage::Close) = message.data() {
+            if message.destination() == self.handle
+                && message.direction() == MessageDirection::ToWidget
+            {
+                self.begin_close(ui);
+            }
+        }
+    }
+}
+
+/// Notification builder creates [`Notification`] instances and adds them to the user interface. Use
+/// [`UserInterface::push_notification`] to show the notification in the notification area of the screen.
+pub struct NotificationBuilder {
+    widget_builder: WidgetBuilder,
+    severity: NotificationSeverity,
+    text: String,
+    duration: f32,
+    actions: Vec<String>,
+}
+
+impl NotificationBuilder {
+    /// Creates new builder instance.
+    pub fn new(widget_builder: WidgetBuilder) -> Self {
+        Self {
+            widget_builder,
+            severity: Default::default(),
+            text: Default::default(),
+            duration: 3.0,
+            actions: Default::default(),
+        }
+    }
+
+    /// Sets the desired severity of the notification.
+    pub fn with_severity(mut self, severity: NotificationSeverity) -> Self {
+        self.severity = severity;
+        self
+    }
+
+    /// Sets the desired text of the notification.
+    pub fn with_text<S: AsRef<str>>(mut self, text: S) -> Self {
+        self.text = text.as_ref().to_owned();
+        self
+    }
+
+    /// Sets the desired amount of time (in seconds) the notification will be shown. Use [`f32::INFINITY`] to keep the
+    /// notification until it is closed by a user.
+    pub fn with_duration(mut self, duration: f32) -> Self {
+        self.duration = duration;
+        self
+    }
+
+    /// Adds a new action button with the given text.
+    pub fn with_action<S: AsRef<str>>(mut self, text: S) -> Self {
+        self.actions.push(text.as_ref().to_owned());
+        self
+    }
+
+    /// Finishes notification building and adds it to the user interface.
+    pub fn build(self, ctx: &mut BuildContext) -> Handle<UiNode> {
+        let text = TextBuilder::new(
+            WidgetBuilder::new()
+                .on_column(0)
+                .with_margin(Thickness::uniform(4.0)),
+        )
+        .with_wrap(WrapMode::Word)
+        .with_text(self.text)
+        .build(ctx);
+
+        let close = ButtonBuilder::new(
+            WidgetBuilder::new()
+                .on_column(1)
+                .with_width(18.0)
+                .with_height(18.0)
+                .with_margin(Thickness::uniform(2.0))
+                .with_vertical_alignment(VerticalAlignment::Top),
+        )
+        .with_text("X")
+        .build(ctx);
+
+        let actions = self
+            .actions
+            .iter()
+            .map(|action| {
+                ButtonBuilder::new(
+                    WidgetBuilder::new()
+                        .with_min_size(Vector2::new(60.0, 20.0))
+                        .with_margin(Thickness::uniform(2.0)),
+                )
+                .with_text(action)
+                .build(ctx)
+            })
+            .collect::<Vec<_>>();
+
+        let actions_panel = StackPanelBuilder::new(
+            WidgetBuilder::new()
+                .on_row(1)
+                .on_column(0)
+                .with_horizontal_alignment(HorizontalAlignment::Right)
+                .with_children(actions.iter().cloned()),
+        )
+        .with_orientation(Orientation::Horizontal)
+        .build(ctx);
+
+        let grid = GridBuilder::new(
+            WidgetBuilder::new()
+                .with_child(text)
+                .with_child(close)
+                .with_child(actions_panel),
+        )
+        .add_row(Row::auto())
+        .add_row(Row::auto())
+        .add_column(Column::stretch())
+        .add_column(Column::auto())
+        .build(ctx);
+
+        let border = BorderBuilder::new(
+            WidgetBuilder::new()
+                .with_background_style(Style::BRUSH_DARKER)
+                .with_foreground(self.severity.brush())
+                .with_child(grid),
+        )
+        .with_stroke_thickness(Thickness {
+            left: 4.0,
+            top: 1.0,
+            right: 1.0,
+            bottom: 1.0,
+        })
+        .build(ctx);
+
+        let notification = Notification {
+            widget: self
+                .widget_builder
+                .with_width(300.0)
+                .with_margin(Thickness::uniform(2.0))
+                .with_opacity(Some(0.0))
+                .with_child(border)
+                .build(),
+            severity: self.severity,
+            duration: self.duration,
+            elapsed: 0.0,
+            fade_out: None,
+            close,
+            actions,
+        };
+
+        ctx.add_node(UiNode::new(notification))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::algebra::Vector2,
+        message::MessageDirection,
+        notification::{NotificationMessage, NotificationSeverity},
+        UserInterface,
+    };
+
+    #[test]
+    fn notification_lifetime() {
+        let screen_size = Vector2::new(800.0, 600.0);
+        let mut ui = UserInterface::new(screen_size);
+
+        let first = ui.show_notification(NotificationSeverity::Info, "First", 1.0);
+        let second = ui.show_notification(NotificationSeverity::Error, "Second", f32::INFINITY);
+
+        let mut closed = Vec::new();
+        for _ in 0..20 {
+            ui.update(screen_size, 0.1);
+            while let Some(message) = ui.poll_message() {
+                if let Some(NotificationMessage::Close) = message.data() {
+                    if message.direction() == MessageDirection::FromWidget {
+                        closed.push(message.destination());
+                    }
+                }
+            }
+        }
+
+        // The first notification has faded out and removed, the second one must stay until it is closed.
+        assert_eq!(closed, vec![first]);
+        assert!(ui.try_get_node(first).is_none());
+        assert!(ui.try_get_node(second).is_some());
+
+        // Notifications stack in the bottom-right corner of the screen.
+        let bounds = ui.node(second).screen_bounds();
+        assert!(bounds.right_bottom_corner().x <= screen_size.x);
+        assert!(bounds.right_bottom_corner().y <= screen_size.y);
+        assert!(bounds.right_bottom_corner().x > screen_size.x - 20.0);
+
+        ui.send_message(NotificationMessage::close(
+            second,
+            MessageDirection::ToWidget,
+        ));
+        for _ in 0..5 {
+            ui.update(screen_size, 0.1);
+            while ui.poll_message().is_some() {}
+        }
+        assert!(ui.try_get_node(second).is_none());
+    }
+}
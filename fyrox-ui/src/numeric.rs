@@ -17,12 +17,13 @@ use crate::{
     define_constructor,
     grid::{Column, GridBuilder, Row},
     message::{KeyCode, MessageDirection, MouseButton, UiMessage},
+    style::Style,
     text::TextMessage,
     text_box::{TextBox, TextBoxBuilder},
     utils::{make_arrow, ArrowDirection},
     widget::{Widget, WidgetBuilder, WidgetMessage},
     BuildContext, Control, HorizontalAlignment, NodeHandleMapping, Thickness, UiNode,
-    UserInterface, VerticalAlignment, BRUSH_DARK, BRUSH_LIGHT,
+    UserInterface, VerticalAlignment,
 };
 use std::{
     any::{Any, TypeId},
@@ -75,6 +76,16 @@ impl<T> NumericType for T where
 {
 }
 
+/// Returns `true` if the numeric type is an integer type.
+pub(crate) fn is_integer<T: NumericType>() -> bool {
+    <T as NumCast>::from(0.5f64).map_or(true, |half| half == T::zero())
+}
+
+/// Converts a value of the numeric type to `f64`.
+pub(crate) fn to_f64<T: NumericType>(value: T) -> f64 {
+    <f64 as NumCast>::from(value).unwrap_or_default()
+}
+
 /// A set of messages that can be used to modify [`NumericUpDown`] widget state (with [`MessageDirection::ToWidget`], or to
 /// fetch changes from it (with [`MessageDirection::FromWidget`]).
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -659,8 +670,8 @@ impl<T: NumericType> NumericUpDownBuilder<T> {
         let field;
         let back = BorderBuilder::new(
             WidgetBuilder::new()
-                .with_background(BRUSH_DARK)
-                .with_foreground(BRUSH_LIGHT),
+                .with_background_style(Style::BRUSH_DARK)
+                .with_foreground_style(Style::BRUSH_LIGHT),
         )
         .with_stroke_thickness(Thickness::uniform(1.0))
         .build(ctx);
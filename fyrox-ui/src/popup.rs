@@ -9,9 +9,9 @@ use crate::{
     core::{reflect::prelude::*, visitor::prelude::*},
     define_constructor,
     message::{ButtonState, MessageDirection, OsEvent, UiMessage},
+    style::Style,
     widget::{Widget, WidgetBuilder, WidgetMessage},
     BuildContext, Control, NodeHandleMapping, RestrictionEntry, Thickness, UiNode, UserInterface,
-    BRUSH_DARKEST, BRUSH_PRIMARY,
 };
 use std::{
     any::{Any, TypeId},
@@ -522,8 +522,8 @@ impl PopupBuilder {
     pub fn build_popup(self, ctx: &mut BuildContext) -> Popup {
         let body = BorderBuilder::new(
             WidgetBuilder::new()
-                .with_background(BRUSH_PRIMARY)
-                .with_foreground(BRUSH_DARKEST)
+                .with_background_style(Style::BRUSH_PRIMARY)
+                .with_foreground_style(Style::BRUSH_DARKEST)
                 .with_child(self.content),
         )
         .with_stroke_thickness(Thickness::uniform(1.0))
@@ -0,0 +1,423 @@
+//! Range slider is a widget with two thumbs on a single track, that is used to edit min/max pairs. See
+//! [`RangeSlider`] docs for more info and usage examples.
+
+#![warn(missing_docs)]
+
+use crate::{
+    core::{
+        algebra::Vector2,
+        math::Rect,
+        num_traits::{clamp, NumCast},
+        pool::Handle,
+    },
+    core::{reflect::prelude::*, visitor::prelude::*},
+    define_constructor,
+    draw::{CommandTexture, Draw, DrawingContext},
+    message::{MessageDirection, MouseButton, UiMessage},
+    numeric::{is_integer, to_f64, NumericType},
+    style::Style,
+    widget::{Widget, WidgetBuilder, WidgetMessage},
+    BuildContext, Control, UiNode, UserInterface, BRUSH_LIGHTEST,
+};
+use std::{
+    any::{Any, TypeId},
+    ops::{Deref, DerefMut, Range},
+};
+
+/// Width of a thumb in pixels.
+const THUMB_WIDTH: f32 = 8.0;
+
+/// A set of messages, that can be used to modify/fetch the state of a [`RangeSlider`] widget instance.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RangeSliderMessage<T: NumericType> {
+    /// Used to set new value of the widget (with [`MessageDirection::ToWidget`] direction). Also emitted by the widget
+    /// when the new value is set (with [`MessageDirection::FromWidget`]). The value is always validated - its start
+    /// is less or equal to its end, and both are within the bounds of the widget.
+    Value(Range<T>),
+    /// Used to set new bounds of the track (with [`MessageDirection::ToWidget`] direction). Also emitted by the widget
+    /// when the new bounds are set (with [`MessageDirection::FromWidget`]).
+    Bounds(Range<T>),
+}
+
+impl<T: NumericType> RangeSliderMessage<T> {
+    define_constructor!(
+        /// Creates [`RangeSliderMessage::Value`] message.
+        RangeSliderMessage:Value => fn value(Range<T>), layout: false
+    );
+    define_constructor!(
+        /// Creates [`RangeSliderMessage::Bounds`] message.
+        RangeSliderMessage:Bounds => fn bounds(Range<T>), layout: false
+    );
+}
+
+/// A thumb of [`RangeSlider`] widget.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum RangeSliderThumb {
+    /// A thumb, that defines the start of the range.
+    Start,
+    /// A thumb, that defines the end of the range.
+    End,
+}
+
+/// Range slider is a widget with two thumbs on a single track, that is used to edit min/max pairs (for example,
+/// lifetime range of particles). The thumbs cannot pass each other, so the start of the value is always less or
+/// equal to its end.
+///
+/// ## Example
+///
+/// ```rust
+/// # use fyrox_ui::{
+/// #     core::pool::Handle, range_slider::RangeSliderBuilder, widget::WidgetBuilder, BuildContext,
+/// #     UiNode,
+/// # };
+/// fn create_range_slider(ctx: &mut BuildContext) -> Handle<UiNode> {
+///     RangeSliderBuilder::new(WidgetBuilder::new().with_height(16.0))
+///         .with_bounds(0.0f32..10.0)
+///         .with_value(1.0..2.5)
+///         .build(ctx)
+/// }
+/// ```
+///
+/// Use [`RangeSliderMessage::Value`] with [`MessageDirection::FromWidget`] direction to "catch" the moment when the
+/// value has changed. Keep in mind, that the type of the range in the message must match the type of the widget.
+#[derive(Debug, Clone, Reflect, Visit)]
+pub struct RangeSlider<T: NumericType> {
+    /// Base widget of the range slider.
+    pub widget: Widget,
+    /// Current value of the range slider.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub value: Range<T>,
+    /// Bounds of the track.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub bounds: Range<T>,
+    /// A thumb, that is being dragged.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub dragged_thumb: Option<RangeSliderThumb>,
+}
+
+impl<T: NumericType> Deref for RangeSlider<T> {
+    type Target = Widget;
+
+    fn deref(&self) -> &Self::Target {
+        &self.widget
+    }
+}
+
+impl<T: NumericType> DerefMut for RangeSlider<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.widget
+    }
+}
+
+/// Makes sure that the start of the range is less or equal to its end and that both are within the bounds.
+fn validate_range<T: NumericType>(range: Range<T>, bounds: &Range<T>) -> Range<T> {
+    let (start, end) = if range.start <= range.end {
+        (range.start, range.end)
+    } else {
+        (range.end, range.start)
+    };
+    clamp(start, bounds.start, bounds.end)..clamp(end, bounds.start, bounds.end)
+}
+
+impl<T: NumericType> RangeSlider<T> {
+    fn track_width(&self) -> f32 {
+        (self.actual_local_size().x - THUMB_WIDTH).max(0.0)
+    }
+
+    fn value_to_x(&self, value: T) -> f32 {
+        let bounds_len = to_f64(self.bounds.end) - to_f64(self.bounds.start);
+        let fraction = if bounds_len > 0.0 {
+            ((to_f64(value) - to_f64(self.bounds.start)) / bounds_len).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        fraction as f32 * self.track_width() + THUMB_WIDTH * 0.5
+    }
+
+    fn x_to_value(&self, x: f32) -> T {
+        let track_width = self.track_width();
+        let fraction = if track_width > 0.0 {
+            ((x - THUMB_WIDTH * 0.5) / track_width).clamp(0.0, 1.0) as f64
+        } else {
+            0.0
+        };
+        let start = to_f64(self.bounds.start);
+        let mut value = start + (to_f64(self.bounds.end) - start) * fraction;
+        if is_integer::<T>() {
+            value = value.round();
+        }
+        <T as NumCast>::from(value).unwrap_or(self.bounds.start)
+    }
+
+    fn thumb_bounds(&self, value: T) -> Rect<f32> {
+        Rect::new(
+            self.value_to_x(value) - THUMB_WIDTH * 0.5,
+            0.0,
+            THUMB_WIDTH,
+            self.actual_local_size().y,
+        )
+    }
+
+    fn move_thumb(&self, ui: &UserInterface, thumb: RangeSliderThumb, x: f32) {
+        let value = self.x_to_value(x);
+        // Thumbs cannot pass each other.
+        let new_value = match thumb {
+            RangeSliderThumb::Start => {
+                (if value > self.value.end {
+                    self.value.end
+                } else {
+                    value
+                })..self.value.end
+            }
+            RangeSliderThumb::End => {
+                self.value.start..(if value < self.value.start {
+                    self.value.start
+                } else {
+                    value
+                })
+            }
+        };
+        ui.send_message(RangeSliderMessage::value(
+            self.handle,
+            MessageDirection::ToWidget,
+            new_value,
+        ));
+    }
+}
+
+impl<T: NumericType> Control for RangeSlider<T> {
+    fn query_component(&self, type_id: TypeId) -> Option<&dyn Any> {
+        if type_id == TypeId::of::<Self>() {
+            Some(self)
+        } else {
+            None
+        }
+    }
+
+    fn draw(&self, drawing_context: &mut DrawingContext) {
+        let size = self.actual_local_size();
+        let track_height = (size.y * 0.25).max(2.0);
+        let track_y = (size.y - track_height) * 0.5;
+
+        drawing_context.push_rect_filled(&Rect::new(0.0, track_y, size.x, track_height), None);
+        drawing_context.commit(
+            self.clip_bounds(),
+            self.widget.background(),
+            CommandTexture::None,
+            None,
+        );
+
+        let start_x = self.value_to_x(self.value.start);
+        let end_x = self.value_to_x(self.value.end);
+        drawing_context.push_rect_filled(
+            &Rect::new(start_x, track_y, end_x - start_x, track_height),
+            None,
+        );
+        drawing_context.commit(
+            self.clip_bounds(),
+            self.widget.foreground(),
+            CommandTexture::None,
+            None,
+        );
+
+        for value in [self.value.start, self.value.end] {
+            drawing_context.push_rect_filled(&self.thumb_bounds(value), None);
+        }
+        drawing_context.commit(
+            self.clip_bounds(),
+            BRUSH_LIGHTEST,
+            CommandTexture::None,
+            None,
+        );
+    }
+
+    fn handle_routed_message(&mut self, ui: &mut UserInterface, message: &mut UiMessage) {
+        self.widget.handle_routed_message(ui, message);
+
+        if let Some(msg) = message.data::<WidgetMessage>() {
+            match msg {
+                WidgetMessage::MouseDown { pos, button } if *button == MouseButton::Left => {
+                    let x = self.screen_to_local(*pos).x;
+                    let start_x = self.value_to_x(self.value.start);
+                    let end_x = self.value_to_x(self.value.end);
+                    // Pick the closest thumb, if the thumbs overlap - pick the one, that could be moved towards the
+                    // cursor.
+                    let thumb = if (start_x - end_x).abs() < f32::EPSILON {
+                        if x < start_x {
+                            RangeSliderThumb::Start
+                        } else {
+                            RangeSliderThumb::End
+                        }
+                    } else if (x - start_x).abs() <= (x - end_x).abs() {
+                        RangeSliderThumb::Start
+                    } else {
+                        RangeSliderThumb::End
+                    };
+                    self.dragged_thumb = Some(thumb);
+                    self.move_thumb(ui, thumb, x);
+                    ui.capture_mouse(self.handle);
+                    message.set_handled(true);
+                }
+                WidgetMessage::MouseMove { pos, .. } => {
+                    if let Some(thumb) = self.dragged_thumb {
+                        self.move_thumb(ui, thumb, self.screen_to_local(*pos).x);
+                    }
+                }
+                WidgetMessage::MouseUp { button, .. }
+                    if *button == MouseButton::Left && self.dragged_thumb.is_some() =>
+                {
+                    self.dragged_thumb = None;
+                    ui.release_mouse_capture();
+                    message.set_handled(true);
+                }
+                _ => (),
+            }
+        } else if let Some(msg) = message.data::<RangeSliderMessage<T>>() {
+            if message.destination() == self.handle
+                && message.direction() == MessageDirection::ToWidget
+            {
+                match msg {
+                    RangeSliderMessage::Value(value) => {
+                        let value = validate_range(value.clone(), &self.bounds);
+                        if self.value != value {
+                            self.value = value.clone();
+                            ui.send_message(RangeSliderMessage::value(
+                                self.handle,
+                                MessageDirection::FromWidget,
+                                value,
+                            ));
+                        }
+                    }
+                    RangeSliderMessage::Bounds(bounds) => {
+                        let bounds =
+                            validate_range(bounds.clone(), &(T::min_value()..T::max_value()));
+                        if self.bounds != bounds {
+                            self.bounds = bounds.clone();
+                            ui.send_message(RangeSliderMessage::bounds(
+                                self.handle,
+                                MessageDirection::FromWidget,
+                                bounds,
+                            ));
+                            // Make sure the value is still within the new bounds.
+                            ui.send_message(RangeSliderMessage::value(
+                                self.handle,
+                                MessageDirection::ToWidget,
+                                self.value.clone(),
+                            ));
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Range slider builder creates [`RangeSlider`] instances and adds them to the user interface.
+pub struct RangeSliderBuilder<T: NumericType> {
+    widget_builder: WidgetBuilder,
+    value: Range<T>,
+    bounds: Range<T>,
+}
+
+impl<T: NumericType> RangeSliderBuilder<T> {
+    /// Creates new builder instance.
+    pub fn new(widget_builder: WidgetBuilder) -> Self {
+        Self {
+            widget_builder,
+            value: T::zero()..T::one(),
+            bounds: T::zero()..T::one(),
+        }
+    }
+
+    /// Sets a desired value of the slider.
+    pub fn with_value(mut self, value: Range<T>) -> Self {
+        self.value = value;
+        self
+    }
+
+    /// Sets desired bounds of the track.
+    pub fn with_bounds(mut self, bounds: Range<T>) -> Self {
+        self.bounds = bounds;
+        self
+    }
+
+    /// Finished widget building and adds the new instance to the user interface.
+    pub fn build(mut self, ctx: &mut BuildContext) -> Handle<UiNode> {
+        if !self.widget_builder.has_background() {
+            self.widget_builder = self.widget_builder.with_background_style(Style::BRUSH_DARK);
+        }
+        if !self.widget_builder.has_foreground() {
+            self.widget_builder = self
+                .widget_builder
+                .with_foreground_style(Style::BRUSH_BRIGHT_BLUE);
+        }
+
+        let bounds = validate_range(self.bounds, &(T::min_value()..T::max_value()));
+        let slider = RangeSlider {
+            widget: self
+                .widget_builder
+                .with_min_size(Vector2::new(2.0 * THUMB_WIDTH, 0.0))
+                .build(),
+            value: validate_range(self.value, &bounds),
+            bounds,
+            dragged_thumb: None,
+        };
+
+        ctx.add_node(UiNode::new(slider))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        message::MessageDirection,
+        range_slider::{RangeSlider, RangeSliderBuilder, RangeSliderMessage},
+        widget::WidgetBuilder,
+        UserInterface,
+    };
+    use fyrox_core::algebra::Vector2;
+    use std::ops::Range;
+
+    #[test]
+    fn range_slider_validation() {
+        let mut ui = UserInterface::new(Vector2::new(100.0, 100.0));
+
+        let slider = RangeSliderBuilder::new(WidgetBuilder::new())
+            .with_bounds(0..10)
+            .with_value(2..3)
+            .build(&mut ui.build_ctx());
+
+        // Reversed ranges are fixed and the values are clamped to the bounds.
+        ui.send_message(RangeSliderMessage::value(
+            slider,
+            MessageDirection::ToWidget,
+            Range { start: 20, end: 5 },
+        ));
+        while ui.poll_message().is_some() {}
+        assert_eq!(
+            ui.node(slider)
+                .query_component::<RangeSlider<i32>>()
+                .unwrap()
+                .value,
+            5..10
+        );
+
+        // The value follows the new bounds.
+        ui.send_message(RangeSliderMessage::bounds(
+            slider,
+            MessageDirection::ToWidget,
+            0..7,
+        ));
+        while ui.poll_message().is_some() {}
+        assert_eq!(
+            ui.node(slider)
+                .query_component::<RangeSlider<i32>>()
+                .unwrap()
+                .value,
+            5..7
+        );
+    }
+}
@@ -0,0 +1,534 @@
+//! Rich text is a widget that prints a sequence of differently styled text runs and inline icons, with optional
+//! hyperlinks. See [`RichText`] docs for more info and usage examples.
+
+#![warn(missing_docs)]
+
+use crate::{
+    brush::Brush,
+    core::{algebra::Vector2, color::Color, math::Rect, pool::Handle},
+    core::{reflect::prelude::*, visitor::prelude::*},
+    define_constructor,
+    draw::{CommandTexture, Draw, DrawingContext, SharedTexture},
+    formatted_text::{FormattedText, FormattedTextBuilder},
+    message::{MessageDirection, MouseButton, UiMessage},
+    style::Style,
+    ttf::SharedFont,
+    widget::{Widget, WidgetBuilder, WidgetMessage},
+    BuildContext, Control, UiNode, UserInterface, BRUSH_BRIGHT_BLUE,
+};
+use std::{
+    any::{Any, TypeId},
+    cell::RefCell,
+    ops::{Deref, DerefMut},
+};
+
+/// A set of messages for [`RichText`] widget.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RichTextMessage {
+    /// Replaces all the elements of the widget.
+    Elements(Vec<RichTextElement>),
+    /// Adds the elements to the end of the widget, it is useful for log-like output.
+    Append(Vec<RichTextElement>),
+    /// Sent by the widget (with [`MessageDirection::FromWidget`]) when a user clicks on a hyperlink. Contains the
+    /// target of the link.
+    LinkClicked(String),
+}
+
+impl RichTextMessage {
+    define_constructor!(
+        /// Creates [`RichTextMessage::Elements`] message.
+        RichTextMessage:Elements => fn elements(Vec<RichTextElement>), layout: false
+    );
+    define_constructor!(
+        /// Creates [`RichTextMessage::Append`] message.
+        RichTextMessage:Append => fn append(Vec<RichTextElement>), layout: false
+    );
+    define_constructor!(
+        /// Creates [`RichTextMessage::LinkClicked`] message.
+        RichTextMessage:LinkClicked => fn link_clicked(String), layout: false
+    );
+}
+
+/// A piece of text with its own style.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TextRun {
+    /// Text of the run, it may contain line breaks.
+    pub text: String,
+    /// Brush of the run. The foreground brush of the widget (or the link brush for hyperlinks) is used if it is
+    /// not set.
+    pub brush: Option<Brush>,
+    /// Font of the run. The font defines both the size and the weight of the text, the font of the widget is used
+    /// if it is not set.
+    pub font: Option<SharedFont>,
+    /// Target of the hyperlink, clicking on the run will send [`RichTextMessage::LinkClicked`] message with it.
+    pub link: Option<String>,
+}
+
+impl TextRun {
+    /// Creates a new run with the given text and default style.
+    pub fn new<S: AsRef<str>>(text: S) -> Self {
+        Self {
+            text: text.as_ref().to_owned(),
+            ..Default::default()
+        }
+    }
+
+    /// Sets the brush of the run.
+    pub fn with_brush(mut self, brush: Brush) -> Self {
+        self.brush = Some(brush);
+        self
+    }
+
+    /// Sets the font of the run.
+    pub fn with_font(mut self, font: SharedFont) -> Self {
+        self.font = Some(font);
+        self
+    }
+
+    /// Makes the run a hyperlink with the given target.
+    pub fn with_link<S: AsRef<str>>(mut self, link: S) -> Self {
+        self.link = Some(link.as_ref().to_owned());
+        self
+    }
+}
+
+/// An element of [`RichText`] widget.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RichTextElement {
+    /// A styled piece of text.
+    Text(TextRun),
+    /// An inline icon.
+    Icon {
+        /// Texture of the icon.
+        texture: SharedTexture,
+        /// Size of the icon.
+        size: Vector2<f32>,
+    },
+}
+
+impl From<TextRun> for RichTextElement {
+    fn from(run: TextRun) -> Self {
+        Self::Text(run)
+    }
+}
+
+#[derive(Debug, Clone)]
+enum PieceContent {
+    Text {
+        formatted_text: FormattedText,
+        // Whether the brush of the piece is defined by the widget or not.
+        inherit_brush: bool,
+    },
+    Icon(SharedTexture),
+    LineBreak,
+}
+
+// Elements are split in pieces (words, whitespaces, icons and line breaks) which are then placed one by one
+// in lines.
+#[derive(Debug, Clone)]
+struct Piece {
+    content: PieceContent,
+    link: Option<String>,
+    size: Vector2<f32>,
+    position: Vector2<f32>,
+}
+
+fn make_text_piece(text: String, run: &TextRun, font: &SharedFont, link_brush: &Brush) -> Piece {
+    let brush = run
+        .brush
+        .clone()
+        .or_else(|| run.link.as_ref().map(|_| link_brush.clone()));
+    let mut formatted_text =
+        FormattedTextBuilder::new(run.font.clone().unwrap_or_else(|| font.clone()))
+            .with_constraint(Vector2::new(f32::INFINITY, f32::INFINITY))
+            .with_text(text)
+            .with_brush(brush.clone().unwrap_or_default())
+            .build();
+    let size = formatted_text.build();
+    Piece {
+        content: PieceContent::Text {
+            formatted_text,
+            inherit_brush: brush.is_none(),
+        },
+        link: run.link.clone(),
+        size,
+        position: Default::default(),
+    }
+}
+
+fn make_pieces(
+    elements: &[RichTextElement],
+    font: &SharedFont,
+    link_brush: &Brush,
+    pieces: &mut Vec<Piece>,
+) {
+    for element in elements {
+        match element {
+            RichTextElement::Text(run) => {
+                let mut word = String::new();
+                for c in run.text.chars() {
+                    if c == '\n' {
+                        if !word.is_empty() {
+                            pieces.push(make_text_piece(
+                                std::mem::take(&mut word),
+                                run,
+                                font,
+                                link_brush,
+                            ));
+                        }
+                        pieces.push(Piece {
+                            content: PieceContent::LineBreak,
+                            link: None,
+                            // Keeps the height of empty lines.
+                            size: Vector2::new(
+                                0.0,
+                                run.font.as_ref().unwrap_or(font).0.lock().height(),
+                            ),
+                            position: Default::default(),
+                        });
+                    } else if c != '\r' {
+                        word.push(c);
+                        // Whitespace ends a word, so the text could be wrapped on it.
+                        if c.is_whitespace() {
+                            pieces.push(make_text_piece(
+                                std::mem::take(&mut word),
+                                run,
+                                font,
+                                link_brush,
+                            ));
+                        }
+                    }
+                }
+                if !word.is_empty() {
+                    pieces.push(make_text_piece(word, run, font, link_brush));
+                }
+            }
+            RichTextElement::Icon { texture, size } => pieces.push(Piece {
+                content: PieceContent::Icon(texture.clone()),
+                link: None,
+                size: *size,
+                position: Default::default(),
+            }),
+        }
+    }
+}
+
+/// Rich text is a widget that prints a sequence of text runs with different colors, fonts (and thus sizes and
+/// weights), inline icons and hyperlinks. Text is wrapped on whitespaces when it does not fit in available width.
+/// Every line is aligned by the bottom edge of its elements.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// # use fyrox_ui::{
+/// #     core::{color::Color, pool::Handle},
+/// #     brush::Brush,
+/// #     rich_text::{RichTextBuilder, TextRun},
+/// #     widget::WidgetBuilder,
+/// #     BuildContext, UiNode,
+/// # };
+/// fn create_rich_text(ctx: &mut BuildContext) -> Handle<UiNode> {
+///     RichTextBuilder::new(WidgetBuilder::new())
+///         .with_elements(vec![
+///             TextRun::new("Failed to load ").into(),
+///             TextRun::new("data/level.rgs")
+///                 .with_brush(Brush::Solid(Color::RED))
+///                 .into(),
+///             TextRun::new(". ").into(),
+///             TextRun::new("Show details").with_link("details").into(),
+///         ])
+///         .build(ctx)
+/// }
+/// ```
+///
+/// Clicking on a hyperlink sends [`RichTextMessage::LinkClicked`] message from the widget, use it to react to
+/// clicks.
+#[derive(Clone, Visit, Reflect, Debug)]
+pub struct RichText {
+    /// Base widget of the rich text.
+    pub widget: Widget,
+    /// Elements of the rich text.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub elements: Vec<RichTextElement>,
+    /// Font, that is used for runs without a font.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub font: SharedFont,
+    /// Brush, that is used for hyperlinks without a brush.
+    pub link_brush: Brush,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pieces: RefCell<Vec<Piece>>,
+}
+
+crate::define_widget_deref!(RichText);
+
+impl RichText {
+    fn rebuild_pieces(&mut self) {
+        let pieces = self.pieces.get_mut();
+        pieces.clear();
+        make_pieces(&self.elements, &self.font, &self.link_brush, pieces);
+    }
+}
+
+impl Control for RichText {
+    fn query_component(&self, type_id: TypeId) -> Option<&dyn Any> {
+        if type_id == TypeId::of::<Self>() {
+            Some(self)
+        } else {
+            None
+        }
+    }
+
+    fn measure_override(&self, ui: &UserInterface, available_size: Vector2<f32>) -> Vector2<f32> {
+        let mut pieces = self.pieces.borrow_mut();
+
+        // Glyphs of the pieces must match the current render scale of their fonts.
+        for piece in pieces.iter_mut() {
+            if let PieceContent::Text { formatted_text, .. } = &mut piece.content {
+                formatted_text
+                    .set_font_render_scale(ui.effective_scale())
+                    .build();
+            }
+        }
+
+        let mut size = Vector2::<f32>::default();
+        let mut line_begin = 0;
+        let mut line_width = 0.0;
+        let mut line_height = 0.0f32;
+        let mut y = 0.0;
+        for i in 0..=pieces.len() {
+            let line_ended = match pieces.get(i) {
+                Some(piece) => match piece.content {
+                    PieceContent::LineBreak => true,
+                    _ => i > line_begin && line_width + piece.size.x > available_size.x,
+                },
+                None => true,
+            };
+
+            if line_ended {
+                let line_end = match pieces.get(i).map(|p| &p.content) {
+                    Some(PieceContent::LineBreak) => i + 1,
+                    _ => i,
+                };
+                for piece in pieces[line_begin..line_end].iter_mut() {
+                    line_height = line_height.max(piece.size.y);
+                }
+                let mut x = 0.0;
+                for piece in pieces[line_begin..line_end].iter_mut() {
+                    piece.position = Vector2::new(x, y + line_height - piece.size.y);
+                    x += piece.size.x;
+                }
+                size.x = size.x.max(line_width);
+                y += line_height;
+                line_begin = line_end;
+                line_width = 0.0;
+                line_height = 0.0;
+            }
+
+            if let Some(piece) = pieces.get(i) {
+                if !matches!(piece.content, PieceContent::LineBreak) {
+                    line_width += piece.size.x;
+                }
+            }
+        }
+        size.y = y;
+
+        size
+    }
+
+    fn draw(&self, drawing_context: &mut DrawingContext) {
+        let bounds = self.widget.bounding_rect();
+        let foreground = self.widget.foreground();
+
+        for piece in self.pieces.borrow_mut().iter_mut() {
+            let position = bounds.position + piece.position;
+            match piece.content {
+                PieceContent::Text {
+                    ref mut formatted_text,
+                    inherit_brush,
+                } => {
+                    if inherit_brush {
+                        formatted_text.set_brush(foreground.clone());
+                    }
+                    drawing_context.draw_text(self.clip_bounds(), position, formatted_text);
+
+                    if piece.link.is_some() {
+                        let y = position.y + piece.size.y;
+                        drawing_context.push_line(
+                            Vector2::new(position.x, y),
+                            Vector2::new(position.x + piece.size.x, y),
+                            1.0,
+                        );
+                        drawing_context.commit(
+                            self.clip_bounds(),
+                            formatted_text.brush(),
+                            CommandTexture::None,
+                            None,
+                        );
+                    }
+                }
+                PieceContent::Icon(ref texture) => {
+                    drawing_context.push_rect_filled(
+                        &Rect::new(position.x, position.y, piece.size.x, piece.size.y),
+                        None,
+                    );
+                    drawing_context.commit(
+                        self.clip_bounds(),
+                        Brush::Solid(Color::WHITE),
+                        CommandTexture::Texture(texture.clone()),
+                        None,
+                    );
+                }
+                PieceContent::LineBreak => (),
+            }
+        }
+    }
+
+    fn handle_routed_message(&mut self, ui: &mut UserInterface, message: &mut UiMessage) {
+        self.widget.handle_routed_message(ui, message);
+
+        if let Some(WidgetMessage::MouseDown { pos, button }) = message.data::<WidgetMessage>() {
+            if *button == MouseButton::Left {
+                let local_position = self.screen_to_local(*pos);
+                let link = self.pieces.borrow().iter().find_map(|piece| {
+                    piece.link.clone().filter(|_| {
+                        Rect::new(
+                            piece.position.x,
+                            piece.position.y,
+                            piece.size.x,
+                            piece.size.y,
+                        )
+                        .contains(local_position)
+                    })
+                });
+                if let Some(link) = link {
+                    ui.send_message(RichTextMessage::link_clicked(
+                        self.handle(),
+                        MessageDirection::FromWidget,
+                        link,
+                    ));
+                    message.set_handled(true);
+                }
+            }
+        } else if let Some(msg) = message.data::<RichTextMessage>() {
+            if message.destination() == self.handle()
+                && message.direction() == MessageDirection::ToWidget
+            {
+                match msg {
+                    RichTextMessage::Elements(elements) => {
+                        self.elements = elements.clone();
+                        self.rebuild_pieces();
+                        self.invalidate_layout();
+                    }
+                    RichTextMessage::Append(elements) => {
+                        self.elements.extend(elements.iter().cloned());
+                        make_pieces(
+                            elements,
+                            &self.font,
+                            &self.link_brush,
+                            self.pieces.get_mut(),
+                        );
+                        self.invalidate_layout();
+                    }
+                    RichTextMessage::LinkClicked(_) => (),
+                }
+            }
+        }
+    }
+}
+
+/// Rich text builder creates [`RichText`] widget instances and adds them to the user interface.
+pub struct RichTextBuilder {
+    widget_builder: WidgetBuilder,
+    elements: Vec<RichTextElement>,
+    font: Option<SharedFont>,
+    link_brush: Brush,
+}
+
+impl RichTextBuilder {
+    /// Creates a new rich text builder.
+    pub fn new(widget_builder: WidgetBuilder) -> Self {
+        Self {
+            widget_builder,
+            elements: Default::default(),
+            font: None,
+            link_brush: BRUSH_BRIGHT_BLUE,
+        }
+    }
+
+    /// Sets the initial elements of the rich text.
+    pub fn with_elements(mut self, elements: Vec<RichTextElement>) -> Self {
+        self.elements = elements;
+        self
+    }
+
+    /// Sets the font, that will be used for runs without a font.
+    pub fn with_font(mut self, font: SharedFont) -> Self {
+        self.font = Some(font);
+        self
+    }
+
+    /// Sets the brush, that will be used for hyperlinks without a brush.
+    pub fn with_link_brush(mut self, brush: Brush) -> Self {
+        self.link_brush = brush;
+        self
+    }
+
+    /// Finishes rich text building, adds it to the user interface and returns its handle.
+    pub fn build(mut self, ctx: &mut BuildContext) -> Handle<UiNode> {
+        if !self.widget_builder.has_foreground() {
+            self.widget_builder = self.widget_builder.with_foreground_style(Style::BRUSH_TEXT);
+        }
+
+        let mut rich_text = RichText {
+            widget: self.widget_builder.build(),
+            elements: self.elements,
+            font: self.font.unwrap_or_else(|| ctx.default_font()),
+            link_brush: self.link_brush,
+            pieces: Default::default(),
+        };
+        rich_text.rebuild_pieces();
+
+        ctx.add_node(UiNode::new(rich_text))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        message::MessageDirection,
+        rich_text::{RichText, RichTextBuilder, RichTextMessage, TextRun},
+        widget::WidgetBuilder,
+        UserInterface,
+    };
+    use fyrox_core::algebra::Vector2;
+
+    #[test]
+    fn rich_text_wrap() {
+        let mut ui = UserInterface::new(Vector2::new(100.0, 100.0));
+
+        let rich_text = RichTextBuilder::new(WidgetBuilder::new().with_width(100.0))
+            .with_elements(vec![TextRun::new("a").into()])
+            .build(&mut ui.build_ctx());
+        ui.update(Vector2::new(100.0, 100.0), 0.0);
+        let single_line_height = ui.node(rich_text).actual_local_size().y;
+
+        ui.send_message(RichTextMessage::append(
+            rich_text,
+            MessageDirection::ToWidget,
+            vec![TextRun::new(" word".repeat(50)).with_link("link").into()],
+        ));
+        while ui.poll_message().is_some() {}
+        ui.update(Vector2::new(100.0, 100.0), 0.0);
+
+        let node = ui.node(rich_text);
+        assert_eq!(
+            node.query_component::<RichText>().unwrap().elements.len(),
+            2
+        );
+        // Long text must be wrapped to fit in the available width.
+        assert!(node.actual_local_size().y > single_line_height);
+    }
+}
@@ -14,11 +14,12 @@ use crate::{
     define_constructor,
     grid::{Column, GridBuilder, Row},
     message::{MessageDirection, UiMessage},
+    style::Style,
     text::{TextBuilder, TextMessage},
     utils::{make_arrow, ArrowDirection},
     widget::{Widget, WidgetBuilder, WidgetMessage},
     BuildContext, Control, HorizontalAlignment, NodeHandleMapping, Orientation, Thickness, UiNode,
-    UserInterface, VerticalAlignment, BRUSH_DARK, BRUSH_LIGHT, BRUSH_LIGHTER, BRUSH_LIGHTEST,
+    UserInterface, VerticalAlignment,
 };
 use std::{
     any::{Any, TypeId},
@@ -551,9 +552,9 @@ impl ScrollBarBuilder {
                 )
                 .with_stroke_thickness(Thickness::uniform(1.0)),
             )
-            .with_normal_brush(BRUSH_LIGHT)
-            .with_hover_brush(BRUSH_LIGHTER)
-            .with_pressed_brush(BRUSH_LIGHTEST)
+            .with_normal_brush_style(Style::BRUSH_LIGHT)
+            .with_hover_brush_style(Style::BRUSH_LIGHTER)
+            .with_pressed_brush_style(Style::BRUSH_LIGHTEST)
             .build(ctx)
         });
 
@@ -628,7 +629,7 @@ impl ScrollBarBuilder {
         .build(ctx);
 
         let body = self.body.unwrap_or_else(|| {
-            BorderBuilder::new(WidgetBuilder::new().with_background(BRUSH_DARK))
+            BorderBuilder::new(WidgetBuilder::new().with_background_style(Style::BRUSH_DARK))
                 .with_stroke_thickness(Thickness::uniform(1.0))
                 .build(ctx)
         });
@@ -0,0 +1,504 @@
+//! A numeric widget, that changes its value by dragging the mouse horizontally over it. See [`Scrubber`] docs for
+//! more info and usage examples.
+
+#![warn(missing_docs)]
+
+use crate::{
+    core::{
+        math::Rect,
+        num_traits::{clamp, NumCast},
+        pool::Handle,
+    },
+    core::{reflect::prelude::*, visitor::prelude::*},
+    define_constructor,
+    draw::{CommandTexture, Draw, DrawingContext},
+    grid::{Column, GridBuilder, Row},
+    message::{CursorIcon, KeyCode, MessageDirection, MouseButton, UiMessage},
+    numeric::{is_integer, to_f64, NumericType},
+    style::Style,
+    text::{TextBuilder, TextMessage},
+    text_box::{TextBox, TextBoxBuilder, TextCommitMode},
+    widget::{Widget, WidgetBuilder, WidgetMessage},
+    BuildContext, Control, HorizontalAlignment, NodeHandleMapping, UiNode, UserInterface,
+    VerticalAlignment,
+};
+use std::{
+    any::{Any, TypeId},
+    ops::{Deref, DerefMut},
+    rc::Rc,
+};
+
+/// Minimal distance (in pixels) the cursor must be moved to start dragging. Smaller movements are treated as clicks.
+const DRAG_THRESHOLD: f32 = 3.0;
+
+/// A set of messages that can be used to modify [`Scrubber`] widget state (with [`MessageDirection::ToWidget`]), or to
+/// fetch changes from it (with [`MessageDirection::FromWidget`]).
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScrubberMessage<T: NumericType> {
+    /// Used to set new value of the widget (with [`MessageDirection::ToWidget`] direction). Also emitted by the widget
+    /// when the new value is set (with [`MessageDirection::FromWidget`]).
+    Value(T),
+    /// Used to set new sensitivity of the widget - how much the value changes per one pixel of cursor movement.
+    Sensitivity(f32),
+}
+
+impl<T: NumericType> ScrubberMessage<T> {
+    define_constructor!(
+        /// Creates [`ScrubberMessage::Value`] message.
+        ScrubberMessage:Value => fn value(T), layout: false
+    );
+
+    /// Creates [`ScrubberMessage::Sensitivity`] message.
+    pub fn sensitivity(
+        destination: Handle<UiNode>,
+        direction: MessageDirection,
+        sensitivity: f32,
+    ) -> UiMessage {
+        UiMessage {
+            handled: Default::default(),
+            data: Rc::new(Self::Sensitivity(sensitivity)),
+            destination,
+            direction,
+            perform_layout: Default::default(),
+            flags: 0,
+        }
+    }
+}
+
+/// Used to store drag info when dragging the cursor over the widget.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ScrubberDragContext<T: NumericType> {
+    /// Mouse button is pressed, but the cursor is not moved far enough to start dragging.
+    PreDrag {
+        /// Initial position of the cursor.
+        start_mouse_pos: f32,
+    },
+    /// Dragging is active.
+    Dragging {
+        /// Value of the widget at the moment when dragging was started.
+        start_value: T,
+        /// Initial position of the cursor.
+        start_mouse_pos: f32,
+    },
+}
+
+/// Scrubber is a numeric widget, that changes its value when a user drags the mouse horizontally over it, just like
+/// numeric fields in inspectors of many 3D editors. A click without dragging turns the widget into a text field, so
+/// a user can type an exact value. Holding `Shift` while dragging makes changes 10 times finer.
+///
+/// The value is always clamped to the hard range (see [`ScrubberBuilder::with_min_value`] and
+/// [`ScrubberBuilder::with_max_value`]). Dragging is additionally limited by the optional soft range (see
+/// [`ScrubberBuilder::with_soft_range`]), but the values outside of it still could be typed in. When the soft range is
+/// set, the widget shows the value as a filled bar.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// # use fyrox_ui::{
+/// #     core::pool::Handle, scrubber::ScrubberBuilder, widget::WidgetBuilder, BuildContext, UiNode,
+/// # };
+/// fn create_scrubber(ctx: &mut BuildContext) -> Handle<UiNode> {
+///     ScrubberBuilder::new(WidgetBuilder::new().with_height(20.0))
+///         .with_value(0.5f32)
+///         .with_min_value(0.0)
+///         .with_soft_range(0.0, 1.0)
+///         .with_sensitivity(0.005)
+///         .build(ctx)
+/// }
+/// ```
+#[derive(Clone, Visit, Reflect, Debug)]
+pub struct Scrubber<T: NumericType> {
+    /// Base widget of the scrubber.
+    pub widget: Widget,
+    /// A handle of the text, that shows current value.
+    pub text: Handle<UiNode>,
+    /// A handle of the text box, that is used to type a value.
+    pub field: Handle<UiNode>,
+    /// Current value of the widget.
+    pub value: T,
+    /// Min value of the widget.
+    pub min_value: T,
+    /// Max value of the widget.
+    pub max_value: T,
+    /// Optional soft min value, that limits dragging.
+    pub soft_min_value: Option<T>,
+    /// Optional soft max value, that limits dragging.
+    pub soft_max_value: Option<T>,
+    /// Defines how much the value changes per one pixel of cursor movement.
+    pub sensitivity: f32,
+    /// Current precision of the widget in decimal places.
+    pub precision: usize,
+    /// Internal dragging context.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub drag_context: Option<ScrubberDragContext<T>>,
+}
+
+impl<T: NumericType> Deref for Scrubber<T> {
+    type Target = Widget;
+
+    fn deref(&self) -> &Self::Target {
+        &self.widget
+    }
+}
+
+impl<T: NumericType> DerefMut for Scrubber<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.widget
+    }
+}
+
+fn format_value<T: NumericType>(value: T, precision: usize) -> String {
+    if is_integer::<T>() {
+        value.to_string()
+    } else {
+        format!("{:.1$}", value, precision)
+    }
+}
+
+impl<T: NumericType> Scrubber<T> {
+    fn clamp_value(&self, value: T) -> T {
+        clamp(value, self.min_value, self.max_value)
+    }
+
+    fn value_by_offset(&self, start_value: T, offset: f32) -> T {
+        let mut value = to_f64(start_value) + offset as f64 * self.sensitivity as f64;
+        if is_integer::<T>() {
+            value = value.round();
+        }
+        let soft_min = to_f64(self.soft_min_value.unwrap_or(self.min_value));
+        let soft_max = to_f64(self.soft_max_value.unwrap_or(self.max_value));
+        let value = value.max(soft_min).min(soft_max);
+        self.clamp_value(<T as NumCast>::from(value).unwrap_or(start_value))
+    }
+
+    fn set_editing(&self, ui: &UserInterface, editing: bool) {
+        ui.send_message(WidgetMessage::visibility(
+            self.text,
+            MessageDirection::ToWidget,
+            !editing,
+        ));
+        ui.send_message(WidgetMessage::visibility(
+            self.field,
+            MessageDirection::ToWidget,
+            editing,
+        ));
+        if editing {
+            ui.send_message(TextMessage::text(
+                self.field,
+                MessageDirection::ToWidget,
+                format_value(self.value, self.precision),
+            ));
+            ui.send_message(WidgetMessage::focus(self.field, MessageDirection::ToWidget));
+        }
+    }
+
+    fn commit_typed_value(&self, ui: &UserInterface) {
+        if let Some(field) = ui.node(self.field).cast::<TextBox>() {
+            if let Ok(value) = field.text().trim().parse::<T>() {
+                ui.send_message(ScrubberMessage::value(
+                    self.handle,
+                    MessageDirection::ToWidget,
+                    self.clamp_value(value),
+                ));
+            }
+        }
+        self.set_editing(ui, false);
+    }
+}
+
+impl<T: NumericType> Control for Scrubber<T> {
+    fn query_component(&self, type_id: TypeId) -> Option<&dyn Any> {
+        if type_id == TypeId::of::<Self>() {
+            Some(self)
+        } else {
+            None
+        }
+    }
+
+    fn resolve(&mut self, node_map: &NodeHandleMapping) {
+        node_map.resolve(&mut self.text);
+        node_map.resolve(&mut self.field);
+    }
+
+    fn draw(&self, drawing_context: &mut DrawingContext) {
+        let bounds = self.widget.bounding_rect();
+        drawing_context.push_rect_filled(&bounds, None);
+        drawing_context.commit(
+            self.clip_bounds(),
+            self.widget.background(),
+            CommandTexture::None,
+            None,
+        );
+
+        if let (Some(soft_min), Some(soft_max)) = (self.soft_min_value, self.soft_max_value) {
+            let range = to_f64(soft_max) - to_f64(soft_min);
+            if range > 0.0 {
+                let fraction =
+                    ((to_f64(self.value) - to_f64(soft_min)) / range).clamp(0.0, 1.0) as f32;
+                drawing_context.push_rect_filled(
+                    &Rect::new(bounds.x(), bounds.y(), bounds.w() * fraction, bounds.h()),
+                    None,
+                );
+                drawing_context.commit(
+                    self.clip_bounds(),
+                    self.widget.foreground(),
+                    CommandTexture::None,
+                    None,
+                );
+            }
+        }
+    }
+
+    fn handle_routed_message(&mut self, ui: &mut UserInterface, message: &mut UiMessage) {
+        self.widget.handle_routed_message(ui, message);
+
+        if let Some(msg) = message.data::<WidgetMessage>() {
+            if message.destination() == self.field {
+                match msg {
+                    WidgetMessage::Unfocus => {
+                        self.commit_typed_value(ui);
+                    }
+                    WidgetMessage::KeyDown(KeyCode::Enter | KeyCode::NumpadEnter) => {
+                        self.commit_typed_value(ui);
+                        message.set_handled(true);
+                    }
+                    WidgetMessage::KeyDown(KeyCode::Escape) => {
+                        self.set_editing(ui, false);
+                        message.set_handled(true);
+                    }
+                    _ => (),
+                }
+            } else if !ui
+                .node(self.field)
+                .has_descendant(message.destination(), ui)
+            {
+                match msg {
+                    WidgetMessage::MouseDown { button, pos } if *button == MouseButton::Left => {
+                        self.drag_context = Some(ScrubberDragContext::PreDrag {
+                            start_mouse_pos: pos.x,
+                        });
+                        ui.capture_mouse(self.handle);
+                        message.set_handled(true);
+                    }
+                    WidgetMessage::MouseMove { pos, .. } => match self.drag_context {
+                        Some(ScrubberDragContext::PreDrag { start_mouse_pos })
+                            if (pos.x - start_mouse_pos).abs() >= DRAG_THRESHOLD =>
+                        {
+                            self.drag_context = Some(ScrubberDragContext::Dragging {
+                                start_value: self.value,
+                                start_mouse_pos,
+                            });
+                        }
+                        Some(ScrubberDragContext::Dragging {
+                            start_value,
+                            start_mouse_pos,
+                        }) => {
+                            let mut offset = pos.x - start_mouse_pos;
+                            if ui.keyboard_modifiers().shift {
+                                offset *= 0.1;
+                            }
+                            ui.send_message(ScrubberMessage::value(
+                                self.handle,
+                                MessageDirection::ToWidget,
+                                self.value_by_offset(start_value, offset),
+                            ));
+                        }
+                        _ => (),
+                    },
+                    WidgetMessage::MouseUp { button, .. } if *button == MouseButton::Left => {
+                        if let Some(drag_context) = self.drag_context.take() {
+                            ui.release_mouse_capture();
+                            if let ScrubberDragContext::PreDrag { .. } = drag_context {
+                                // Click without dragging - switch to typing.
+                                self.set_editing(ui, true);
+                            }
+                            message.set_handled(true);
+                        }
+                    }
+                    _ => (),
+                }
+            }
+        } else if let Some(msg) = message.data::<ScrubberMessage<T>>() {
+            if message.direction() == MessageDirection::ToWidget
+                && message.destination() == self.handle
+            {
+                match msg {
+                    ScrubberMessage::Value(value) => {
+                        let clamped = self.clamp_value(*value);
+                        if self.value != clamped {
+                            self.value = clamped;
+
+                            ui.send_message(TextMessage::text(
+                                self.text,
+                                MessageDirection::ToWidget,
+                                format_value(clamped, self.precision),
+                            ));
+
+                            let mut msg = ScrubberMessage::value(
+                                self.handle,
+                                MessageDirection::FromWidget,
+                                self.value,
+                            );
+                            // We must maintain flags
+                            msg.set_handled(message.handled());
+                            msg.flags = message.flags;
+                            ui.send_message(msg);
+                        }
+                    }
+                    &ScrubberMessage::Sensitivity(sensitivity) => {
+                        if self.sensitivity != sensitivity {
+                            self.sensitivity = sensitivity;
+                            ui.send_message(message.reverse());
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// This builder creates new instances of [`Scrubber`] widget and adds them to the user interface.
+pub struct ScrubberBuilder<T: NumericType> {
+    widget_builder: WidgetBuilder,
+    value: T,
+    min_value: T,
+    max_value: T,
+    soft_min_value: Option<T>,
+    soft_max_value: Option<T>,
+    sensitivity: f32,
+    precision: usize,
+}
+
+impl<T: NumericType> ScrubberBuilder<T> {
+    /// Creates new builder instance with the base widget builder specified.
+    pub fn new(widget_builder: WidgetBuilder) -> Self {
+        Self {
+            widget_builder,
+            value: T::zero(),
+            min_value: T::min_value(),
+            max_value: T::max_value(),
+            soft_min_value: None,
+            soft_max_value: None,
+            sensitivity: if is_integer::<T>() { 0.1 } else { 0.01 },
+            precision: 3,
+        }
+    }
+
+    /// Sets the desired value.
+    pub fn with_value(mut self, value: T) -> Self {
+        self.value = value;
+        self
+    }
+
+    /// Sets the desired hard min value, the value of the widget cannot be less than this value.
+    pub fn with_min_value(mut self, value: T) -> Self {
+        self.min_value = value;
+        self
+    }
+
+    /// Sets the desired hard max value, the value of the widget cannot be greater than this value.
+    pub fn with_max_value(mut self, value: T) -> Self {
+        self.max_value = value;
+        self
+    }
+
+    /// Sets the desired soft range, that limits dragging. Values outside of this range still could be typed in.
+    pub fn with_soft_range(mut self, min: T, max: T) -> Self {
+        self.soft_min_value = Some(min);
+        self.soft_max_value = Some(max);
+        self
+    }
+
+    /// Sets how much the value changes per one pixel of cursor movement.
+    pub fn with_sensitivity(mut self, sensitivity: f32) -> Self {
+        self.sensitivity = sensitivity;
+        self
+    }
+
+    /// Sets the desired precision in decimal places. It is ignored for integer types.
+    pub fn with_precision(mut self, precision: usize) -> Self {
+        self.precision = precision;
+        self
+    }
+
+    /// Finishes [`Scrubber`] widget creation and adds the new instance to the user interface and returns a handle to it.
+    pub fn build(mut self, ctx: &mut BuildContext) -> Handle<UiNode> {
+        let value = clamp(self.value, self.min_value, self.max_value);
+
+        if !self.widget_builder.has_background() {
+            self.widget_builder = self.widget_builder.with_background_style(Style::BRUSH_DARK);
+        }
+        if !self.widget_builder.has_foreground() {
+            self.widget_builder = self
+                .widget_builder
+                .with_foreground_style(Style::BRUSH_BRIGHT_BLUE);
+        }
+
+        let text = TextBuilder::new(WidgetBuilder::new().on_row(0).on_column(0))
+            .with_text(format_value(value, self.precision))
+            .with_vertical_text_alignment(VerticalAlignment::Center)
+            .with_horizontal_text_alignment(HorizontalAlignment::Center)
+            .build(ctx);
+
+        let field = TextBoxBuilder::new(
+            WidgetBuilder::new()
+                .with_visibility(false)
+                .on_row(0)
+                .on_column(0),
+        )
+        .with_vertical_text_alignment(VerticalAlignment::Center)
+        .with_text_commit_mode(TextCommitMode::Immediate)
+        .build(ctx);
+
+        let grid = GridBuilder::new(WidgetBuilder::new().with_child(text).with_child(field))
+            .add_row(Row::stretch())
+            .add_column(Column::stretch())
+            .build(ctx);
+
+        let scrubber = Scrubber {
+            widget: self
+                .widget_builder
+                .with_cursor(Some(CursorIcon::EwResize))
+                .with_child(grid)
+                .build(),
+            text,
+            field,
+            value,
+            min_value: self.min_value,
+            max_value: self.max_value,
+            soft_min_value: self.soft_min_value,
+            soft_max_value: self.soft_max_value,
+            sensitivity: self.sensitivity,
+            precision: self.precision,
+            drag_context: None,
+        };
+
+        ctx.add_node(UiNode::new(scrubber))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::scrubber::{Scrubber, ScrubberBuilder};
+    use crate::{widget::WidgetBuilder, UserInterface};
+    use fyrox_core::algebra::Vector2;
+
+    #[test]
+    fn test_value_by_offset() {
+        let mut ui = UserInterface::new(Vector2::new(100.0, 100.0));
+
+        let handle = ScrubberBuilder::new(WidgetBuilder::new())
+            .with_value(5i32)
+            .with_min_value(-100)
+            .with_soft_range(0, 10)
+            .with_sensitivity(0.1)
+            .build(&mut ui.build_ctx());
+        let scrubber = ui.node(handle).query_component::<Scrubber<i32>>().unwrap();
+
+        assert_eq!(scrubber.value_by_offset(5, 20.0), 7);
+        // Dragging is limited by the soft range.
+        assert_eq!(scrubber.value_by_offset(5, 1000.0), 10);
+        assert_eq!(scrubber.value_by_offset(5, -1000.0), 0);
+    }
+}
@@ -12,23 +12,26 @@ use crate::{
     define_constructor, define_widget_deref,
     grid::{Column, GridBuilder, Row},
     message::{MessageDirection, UiMessage},
+    style::Style,
     text::TextMessage,
     text_box::{TextBoxBuilder, TextCommitMode},
     utils::make_cross,
     vector_image::{Primitive, VectorImageBuilder},
-    widget::{Widget, WidgetBuilder},
-    BuildContext, Control, Thickness, UiNode, UserInterface, VerticalAlignment, BRUSH_DARKER,
-    BRUSH_LIGHT, BRUSH_LIGHTEST,
+    widget::{Widget, WidgetBuilder, WidgetMessage},
+    BuildContext, Control, Thickness, UiNode, UserInterface, VerticalAlignment,
 };
 use std::{
     any::{Any, TypeId},
     ops::{Deref, DerefMut},
+    sync::mpsc::Sender,
 };
 
 /// A set of messages that can be used to get the state of a search bar.
 #[derive(Debug, Clone, PartialEq)]
 pub enum SearchBarMessage {
-    /// Emitted when a user types something in the search bar.
+    /// Emitted when a user types something in the search bar. If the search bar has non-zero debounce
+    /// interval (see [`SearchBarBuilder::with_debounce`]), the message is emitted only when the user stops
+    /// typing for the given amount of time. Empty text is always emitted immediately.
     Text(String),
 }
 
@@ -41,7 +44,8 @@ impl SearchBarMessage {
 
 /// Search bar widget is a text box with a "clear text" button. It is used as an input field for search functionality.
 /// Keep in mind, that it does **not** provide any built-in searching functionality by itself, you need to implement
-/// it manually. This widget provides a "standard" looking search bar with very little functionality.
+/// it manually. This widget provides a "standard" looking search bar with very little functionality. However, there
+/// is [`filter_hierarchy`] function, that could be used to filter items of list or tree widgets by a predicate.
 ///
 /// ## Examples
 ///
@@ -82,6 +86,13 @@ pub struct SearchBar {
     pub text_box: Handle<UiNode>,
     /// A handle of a button, that is used to clear the text.
     pub clear: Handle<UiNode>,
+    /// Amount of time (in seconds) the search bar waits after the last change of the text before emitting
+    /// [`SearchBarMessage::Text`] message.
+    #[visit(optional)]
+    pub debounce: f32,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pending: Option<(String, f32)>,
 }
 
 define_widget_deref!(SearchBar);
@@ -95,6 +106,22 @@ impl Control for SearchBar {
         }
     }
 
+    fn update(&mut self, dt: f32, sender: &Sender<UiMessage>) {
+        if let Some((_, timer)) = self.pending.as_mut() {
+            *timer -= dt;
+            if *timer <= 0.0 {
+                let (text, _) = self.pending.take().unwrap();
+                sender
+                    .send(SearchBarMessage::text(
+                        self.handle,
+                        MessageDirection::FromWidget,
+                        text,
+                    ))
+                    .unwrap();
+            }
+        }
+    }
+
     fn handle_routed_message(&mut self, ui: &mut UserInterface, message: &mut UiMessage) {
         self.widget.handle_routed_message(ui, message);
 
@@ -123,11 +150,16 @@ impl Control for SearchBar {
             && message.direction() == MessageDirection::FromWidget
         {
             if let Some(TextMessage::Text(text)) = message.data() {
-                ui.send_message(SearchBarMessage::text(
-                    self.handle,
-                    MessageDirection::FromWidget,
-                    text.clone(),
-                ));
+                if self.debounce > 0.0 && !text.is_empty() {
+                    self.pending = Some((text.clone(), self.debounce));
+                } else {
+                    self.pending = None;
+                    ui.send_message(SearchBarMessage::text(
+                        self.handle,
+                        MessageDirection::FromWidget,
+                        text.clone(),
+                    ));
+                }
             }
         }
     }
@@ -136,12 +168,24 @@ impl Control for SearchBar {
 /// Search bar builder creates [`SearchBar`] widget instances and adds them to the user interface.
 pub struct SearchBarBuilder {
     widget_builder: WidgetBuilder,
+    debounce: f32,
 }
 
 impl SearchBarBuilder {
     /// Creates a new builder instance.
     pub fn new(widget_builder: WidgetBuilder) -> Self {
-        Self { widget_builder }
+        Self {
+            widget_builder,
+            debounce: 0.0,
+        }
+    }
+
+    /// Sets the desired amount of time (in seconds) the search bar waits after the last change of the text
+    /// before emitting [`SearchBarMessage::Text`] message. Zero (default) means that every change is emitted
+    /// immediately.
+    pub fn with_debounce(mut self, debounce: f32) -> Self {
+        self.debounce = debounce;
+        self
     }
 
     /// Finishes search bar building and adds the new instance to the user interface.
@@ -150,8 +194,8 @@ impl SearchBarBuilder {
         let clear;
         let content = BorderBuilder::new(
             WidgetBuilder::new()
-                .with_foreground(BRUSH_LIGHT)
-                .with_background(BRUSH_DARKER)
+                .with_foreground_style(Style::BRUSH_LIGHT)
+                .with_background_style(Style::BRUSH_DARKER)
                 .with_child(
                     GridBuilder::new(
                         WidgetBuilder::new()
@@ -161,7 +205,7 @@ impl SearchBarBuilder {
                                         .with_width(12.0)
                                         .with_height(12.0)
                                         .with_vertical_alignment(VerticalAlignment::Center)
-                                        .with_foreground(BRUSH_LIGHTEST)
+                                        .with_foreground_style(Style::BRUSH_LIGHTEST)
                                         .with_margin(Thickness::left(1.0)),
                                 )
                                 .with_primitives(vec![
@@ -215,8 +259,125 @@ impl SearchBarBuilder {
             widget: self.widget_builder.with_child(content).build(),
             text_box,
             clear,
+            debounce: self.debounce,
+            pending: None,
         };
 
         ctx.add_node(UiNode::new(search_bar))
     }
 }
+
+/// Recursively filters descendant widgets of the given root widget. The predicate must return `Some(true)` for
+/// items that match the search criteria, `Some(false)` for items that does not, and `None` for every other widget
+/// (panels, decorators, etc.). An item is shown if it matches or if any of its descendant items matches, so the
+/// function works with flat lists as well as with trees. Returns `true` if there was at least one match.
+///
+/// ## Examples
+///
+/// ```rust
+/// # use fyrox_ui::{
+/// #     core::pool::Handle, searchbar::filter_hierarchy, text::Text, tree::Tree, UiNode,
+/// #     UserInterface,
+/// # };
+/// fn filter_tree(tree_root: Handle<UiNode>, search_text: &str, ui: &UserInterface) {
+///     let search_text = search_text.to_lowercase();
+///     filter_hierarchy(ui, tree_root, &|node| {
+///         node.query_component::<Tree>().map(|tree| {
+///             ui.try_get_node(tree.content)
+///                 .and_then(|content| content.query_component::<Text>())
+///                 .is_some_and(|text| text.text().to_lowercase().contains(&search_text))
+///         })
+///     });
+/// }
+/// ```
+pub fn filter_hierarchy<P>(ui: &UserInterface, root: Handle<UiNode>, predicate: &P) -> bool
+where
+    P: Fn(&UiNode) -> Option<bool>,
+{
+    let node = ui.node(root);
+
+    let mut is_any_match = false;
+    for &child in node.children() {
+        is_any_match |= filter_hierarchy(ui, child, predicate);
+    }
+
+    if let Some(is_match) = predicate(node) {
+        is_any_match |= is_match;
+
+        ui.send_message(WidgetMessage::visibility(
+            root,
+            MessageDirection::ToWidget,
+            is_any_match,
+        ));
+    }
+
+    is_any_match
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::algebra::Vector2,
+        message::MessageDirection,
+        searchbar::{SearchBar, SearchBarBuilder, SearchBarMessage},
+        text::TextMessage,
+        widget::WidgetBuilder,
+        UiNode, UserInterface,
+    };
+    use fyrox_core::pool::Handle;
+
+    fn poll_search_text(ui: &mut UserInterface, search_bar: Handle<UiNode>) -> Option<String> {
+        let mut result = None;
+        while let Some(message) = ui.poll_message() {
+            if let Some(SearchBarMessage::Text(text)) = message.data() {
+                if message.destination() == search_bar
+                    && message.direction() == MessageDirection::FromWidget
+                {
+                    result = Some(text.clone());
+                }
+            }
+        }
+        result
+    }
+
+    #[test]
+    fn search_bar_debounce() {
+        let screen_size = Vector2::new(100.0, 100.0);
+        let mut ui = UserInterface::new(screen_size);
+
+        let search_bar = SearchBarBuilder::new(WidgetBuilder::new())
+            .with_debounce(0.2)
+            .build(&mut ui.build_ctx());
+        let text_box = ui
+            .node(search_bar)
+            .query_component::<SearchBar>()
+            .unwrap()
+            .text_box;
+
+        for text in ["f", "fo", "foo"] {
+            ui.send_message(TextMessage::text(
+                text_box,
+                MessageDirection::ToWidget,
+                text.to_string(),
+            ));
+        }
+        assert_eq!(poll_search_text(&mut ui, search_bar), None);
+
+        ui.update(screen_size, 0.1);
+        assert_eq!(poll_search_text(&mut ui, search_bar), None);
+
+        ui.update(screen_size, 0.15);
+        assert_eq!(
+            poll_search_text(&mut ui, search_bar),
+            Some("foo".to_string())
+        );
+
+        // Clearing is not delayed.
+        ui.send_message(SearchBarMessage::text(
+            search_bar,
+            MessageDirection::ToWidget,
+            String::new(),
+        ));
+        assert_eq!(poll_search_text(&mut ui, search_bar), Some(String::new()));
+    }
+}
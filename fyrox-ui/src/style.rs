@@ -0,0 +1,386 @@
+//! Style is a set of named properties (brushes, thicknesses, fonts, etc.), that are used by widgets instead of
+//! hard-coded values. See [`Style`] docs for more info and usage examples.
+
+#![warn(missing_docs)]
+
+use crate::{
+    brush::Brush,
+    core::{
+        color::Color,
+        reflect::prelude::*,
+        sstorage::ImmutableString,
+        visitor::{prelude::*, Visitor},
+    },
+    ttf::SharedFont,
+    Thickness, COLOR_BRIGHT, COLOR_BRIGHT_BLUE, COLOR_COAL_BLACK, COLOR_DARK, COLOR_DARKER,
+    COLOR_DARKEST, COLOR_FOREGROUND, COLOR_LIGHT, COLOR_LIGHTER, COLOR_LIGHTEST, COLOR_PRIMARY,
+    COLOR_TEXT,
+};
+use fxhash::FxHashMap;
+use std::path::Path;
+
+/// A value of a style property.
+#[derive(Clone, Debug, PartialEq, Visit, Reflect)]
+pub enum StyleProperty {
+    /// A numeric property.
+    Number(f32),
+    /// A thickness property, that could be used for margins, border strokes, etc.
+    Thickness(Thickness),
+    /// A brush property.
+    Brush(Brush),
+}
+
+impl Default for StyleProperty {
+    fn default() -> Self {
+        Self::Number(0.0)
+    }
+}
+
+impl From<f32> for StyleProperty {
+    fn from(value: f32) -> Self {
+        Self::Number(value)
+    }
+}
+
+impl From<Thickness> for StyleProperty {
+    fn from(value: Thickness) -> Self {
+        Self::Thickness(value)
+    }
+}
+
+impl From<Brush> for StyleProperty {
+    fn from(value: Brush) -> Self {
+        Self::Brush(value)
+    }
+}
+
+impl From<Color> for StyleProperty {
+    fn from(value: Color) -> Self {
+        Self::Brush(Brush::Solid(value))
+    }
+}
+
+/// Style is a set of named properties (brushes, thicknesses, numbers and fonts), that are used by widgets
+/// instead of hard-coded values. Every user interface has a current style (see [`crate::UserInterface::style`]),
+/// widgets are bound to its properties by names, and the style could be changed at runtime using
+/// [`crate::UserInterface::set_style`] - every bound property of every widget will be updated.
+///
+/// There are two built-in styles - [`Style::dark`] (default) and [`Style::light`]. Custom styles could be
+/// created from scratch, derived from the built-in ones or loaded from a file (see [`Style::from_file`]). The
+/// engine also has a style resource (`fyrox::resource::style::StyleResource`), so styles could be loaded
+/// with the resource manager like any other asset.
+///
+/// ## Binding
+///
+/// Background and foreground brushes of a widget could be bound to a style property using
+/// [`crate::widget::WidgetBuilder::with_background_style`] and [`crate::widget::WidgetBuilder::with_foreground_style`]
+/// methods. Widgets without explicitly specified brushes are bound to [`Style::BRUSH_PRIMARY`] and
+/// [`Style::BRUSH_FOREGROUND`] respectively. Explicitly set brushes (either in a builder or with a message)
+/// are not changed when the style changes.
+///
+/// Fonts of text widgets are bound the same way using [`crate::text::TextBuilder::with_font_style`] and
+/// [`crate::text_box::TextBoxBuilder::with_font_style`], widgets without explicitly specified font are bound
+/// to [`Style::FONT_DEFAULT`]. Fonts are not serialized, they must be set with [`Style::set_font`] after
+/// loading a style.
+///
+/// ## Examples
+///
+/// ```rust
+/// # use fyrox_ui::{
+/// #     brush::Brush, core::color::Color, style::Style, widget::WidgetBuilder, border::BorderBuilder,
+/// #     UserInterface,
+/// # };
+/// # use fyrox_core::algebra::Vector2;
+/// let mut ui = UserInterface::new(Vector2::new(100.0, 100.0));
+///
+/// // The background of the border will be changed when the style changes.
+/// BorderBuilder::new(WidgetBuilder::new().with_background_style(Style::BRUSH_DARKER))
+///     .build(&mut ui.build_ctx());
+///
+/// // Switch to the light style with a custom accent color.
+/// ui.set_style(Style::light().with(Style::BRUSH_BRIGHT_BLUE, Color::opaque(200, 80, 40)));
+/// ```
+#[derive(Clone, Debug, Default, Visit, Reflect)]
+pub struct Style {
+    properties: FxHashMap<ImmutableString, StyleProperty>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    fonts: FxHashMap<ImmutableString, SharedFont>,
+}
+
+impl Style {
+    /// The darkest brush of the palette, that is used for deep backgrounds.
+    pub const BRUSH_COAL_BLACK: &'static str = "Brush.CoalBlack";
+    /// A brush, that is used for backgrounds of panels and windows.
+    pub const BRUSH_DARKEST: &'static str = "Brush.Darkest";
+    /// A brush, that is used for backgrounds of input fields and borders of decorators.
+    pub const BRUSH_DARKER: &'static str = "Brush.Darker";
+    /// A brush, that is used for backgrounds of secondary elements.
+    pub const BRUSH_DARK: &'static str = "Brush.Dark";
+    /// A brush, that is used for backgrounds of widgets by default.
+    pub const BRUSH_PRIMARY: &'static str = "Brush.Primary";
+    /// A brush, that is used for interactive elements in normal state.
+    pub const BRUSH_LIGHT: &'static str = "Brush.Light";
+    /// A brush, that is used for interactive elements in hovered state.
+    pub const BRUSH_LIGHTER: &'static str = "Brush.Lighter";
+    /// A brush, that is used for interactive elements in pressed state.
+    pub const BRUSH_LIGHTEST: &'static str = "Brush.Lightest";
+    /// A brush, that is used for interactive elements in selected state.
+    pub const BRUSH_BRIGHT: &'static str = "Brush.Bright";
+    /// An accent brush, that is used for highlights.
+    pub const BRUSH_BRIGHT_BLUE: &'static str = "Brush.BrightBlue";
+    /// A brush, that is used for text.
+    pub const BRUSH_TEXT: &'static str = "Brush.Text";
+    /// A brush, that is used for foreground of widgets by default.
+    pub const BRUSH_FOREGROUND: &'static str = "Brush.Foreground";
+    /// A font, that is used by default.
+    pub const FONT_DEFAULT: &'static str = "Font.Default";
+
+    /// Creates a new style with the given palette.
+    fn from_palette(palette: [(&str, Color); 12]) -> Self {
+        let mut style = Self::default();
+        for (name, color) in palette {
+            style.set(name, color);
+        }
+        style
+    }
+
+    /// Creates the default dark style.
+    pub fn dark() -> Self {
+        Self::from_palette([
+            (Self::BRUSH_COAL_BLACK, COLOR_COAL_BLACK),
+            (Self::BRUSH_DARKEST, COLOR_DARKEST),
+            (Self::BRUSH_DARKER, COLOR_DARKER),
+            (Self::BRUSH_DARK, COLOR_DARK),
+            (Self::BRUSH_PRIMARY, COLOR_PRIMARY),
+            (Self::BRUSH_LIGHT, COLOR_LIGHT),
+            (Self::BRUSH_LIGHTER, COLOR_LIGHTER),
+            (Self::BRUSH_LIGHTEST, COLOR_LIGHTEST),
+            (Self::BRUSH_BRIGHT, COLOR_BRIGHT),
+            (Self::BRUSH_BRIGHT_BLUE, COLOR_BRIGHT_BLUE),
+            (Self::BRUSH_TEXT, COLOR_TEXT),
+            (Self::BRUSH_FOREGROUND, COLOR_FOREGROUND),
+        ])
+    }
+
+    /// Creates a light style. Its palette is an inverted version of the dark palette, so for example
+    /// [`Self::BRUSH_DARKEST`] is the lightest color of the light style.
+    pub fn light() -> Self {
+        Self::from_palette([
+            (Self::BRUSH_COAL_BLACK, Color::opaque(250, 250, 250)),
+            (Self::BRUSH_DARKEST, Color::opaque(240, 240, 240)),
+            (Self::BRUSH_DARKER, Color::opaque(228, 228, 228)),
+            (Self::BRUSH_DARK, Color::opaque(216, 216, 216)),
+            (Self::BRUSH_PRIMARY, Color::opaque(204, 204, 204)),
+            (Self::BRUSH_LIGHT, Color::opaque(186, 186, 186)),
+            (Self::BRUSH_LIGHTER, Color::opaque(172, 172, 172)),
+            (Self::BRUSH_LIGHTEST, Color::opaque(158, 158, 158)),
+            (Self::BRUSH_BRIGHT, Color::opaque(130, 130, 130)),
+            (Self::BRUSH_BRIGHT_BLUE, Color::opaque(90, 140, 210)),
+            (Self::BRUSH_TEXT, Color::opaque(20, 20, 20)),
+            (Self::BRUSH_FOREGROUND, Color::BLACK),
+        ])
+    }
+
+    /// Sets a new value of the property with the given name.
+    pub fn set(&mut self, name: &str, property: impl Into<StyleProperty>) -> &mut Self {
+        self.properties
+            .insert(ImmutableString::new(name), property.into());
+        self
+    }
+
+    /// Sets a new value of the property with the given name and returns the style. Useful to derive custom
+    /// styles from the built-in ones.
+    pub fn with(mut self, name: &str, property: impl Into<StyleProperty>) -> Self {
+        self.set(name, property);
+        self
+    }
+
+    /// Sets a new font with the given name.
+    pub fn set_font(&mut self, name: &str, font: SharedFont) -> &mut Self {
+        self.fonts.insert(ImmutableString::new(name), font);
+        self
+    }
+
+    /// Returns a reference to the property with the given name (if any).
+    pub fn get(&self, name: &str) -> Option<&StyleProperty> {
+        self.properties.get(&ImmutableString::new(name))
+    }
+
+    /// Returns a brush with the given name. `None` is returned if there's no such property, or it is not
+    /// a brush.
+    pub fn brush(&self, name: &str) -> Option<Brush> {
+        match self.get(name)? {
+            StyleProperty::Brush(brush) => Some(brush.clone()),
+            _ => None,
+        }
+    }
+
+    /// Returns a thickness with the given name. `None` is returned if there's no such property, or it is not
+    /// a thickness.
+    pub fn thickness(&self, name: &str) -> Option<Thickness> {
+        match self.get(name)? {
+            StyleProperty::Thickness(thickness) => Some(*thickness),
+            _ => None,
+        }
+    }
+
+    /// Returns a number with the given name. `None` is returned if there's no such property, or it is not
+    /// a number.
+    pub fn number(&self, name: &str) -> Option<f32> {
+        match self.get(name)? {
+            StyleProperty::Number(number) => Some(*number),
+            _ => None,
+        }
+    }
+
+    /// Returns a font with the given name (if any).
+    pub fn font(&self, name: &str) -> Option<SharedFont> {
+        self.fonts.get(&ImmutableString::new(name)).cloned()
+    }
+
+    pub(crate) fn fonts(&self) -> impl Iterator<Item = &SharedFont> {
+        self.fonts.values()
+    }
+
+    /// Saves the properties of the style to a file. Fonts are not saved, they must be set manually after
+    /// loading.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> VisitResult {
+        let mut visitor = Visitor::new();
+        self.clone().visit("Style", &mut visitor)?;
+        visitor.save_binary(path)
+    }
+
+    /// Loads the style from a file, that was previously saved using [`Self::save`].
+    pub async fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, VisitError> {
+        let mut visitor = Visitor::load_binary(path).await?;
+        let mut style = Self::default();
+        style.visit("Style", &mut visitor)?;
+        Ok(style)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        border::{Border, BorderBuilder, BorderMessage},
+        brush::Brush,
+        core::{algebra::Vector2, color::Color, pool::Handle},
+        message::MessageDirection,
+        style::Style,
+        text::{Text, TextBuilder, TextMessage},
+        ttf::{FontBuilder, SharedFont},
+        widget::{WidgetBuilder, WidgetMessage},
+        Thickness, UiNode, UserInterface, BRUSH_DARKER,
+    };
+
+    fn stroke_thickness(ui: &UserInterface, border: Handle<UiNode>) -> Thickness {
+        ui.node(border)
+            .query_component::<Border>()
+            .unwrap()
+            .stroke_thickness
+    }
+
+    #[test]
+    fn switch_style() {
+        let mut ui = UserInterface::new(Vector2::new(100.0, 100.0));
+
+        let ctx = &mut ui.build_ctx();
+        let bound =
+            BorderBuilder::new(WidgetBuilder::new().with_background_style(Style::BRUSH_DARKER))
+                .with_stroke_thickness_style("Thickness.Border")
+                .build(ctx);
+        let explicit =
+            BorderBuilder::new(WidgetBuilder::new().with_background(BRUSH_DARKER)).build(ctx);
+        let unbound_later = BorderBuilder::new(WidgetBuilder::new())
+            .with_stroke_thickness_style("Thickness.Border")
+            .build(ctx);
+
+        assert_eq!(ui.node(bound).background(), BRUSH_DARKER);
+        // Missing properties do not change anything.
+        assert_eq!(stroke_thickness(&ui, bound), Thickness::uniform(1.0));
+
+        // Explicitly set values are no longer bound to the style.
+        ui.send_message(WidgetMessage::background(
+            unbound_later,
+            MessageDirection::ToWidget,
+            Brush::Solid(Color::RED),
+        ));
+        ui.send_message(BorderMessage::stroke_thickness(
+            unbound_later,
+            MessageDirection::ToWidget,
+            Thickness::uniform(2.0),
+        ));
+        while ui.poll_message().is_some() {}
+
+        let light = Style::light().with("Thickness.Border", Thickness::uniform(3.0));
+        let light_darker = light.brush(Style::BRUSH_DARKER).unwrap();
+        ui.set_style(light);
+
+        assert_eq!(ui.node(bound).background(), light_darker);
+        assert_eq!(stroke_thickness(&ui, bound), Thickness::uniform(3.0));
+        assert_eq!(ui.node(explicit).background(), BRUSH_DARKER);
+        assert_eq!(
+            ui.node(unbound_later).background(),
+            Brush::Solid(Color::RED)
+        );
+        assert_eq!(
+            stroke_thickness(&ui, unbound_later),
+            Thickness::uniform(2.0)
+        );
+    }
+
+    fn font(ui: &UserInterface, text: Handle<UiNode>) -> SharedFont {
+        ui.node(text).query_component::<Text>().unwrap().font()
+    }
+
+    #[test]
+    fn switch_font() {
+        let mut ui = UserInterface::new(Vector2::new(100.0, 100.0));
+        let default_font = ui.default_font.clone();
+        let big_font = SharedFont::new(
+            FontBuilder::new()
+                .with_height(30.0)
+                .build_builtin()
+                .unwrap(),
+        );
+
+        let ctx = &mut ui.build_ctx();
+        let bound = TextBuilder::new(WidgetBuilder::new()).build(ctx);
+        let custom_bound = TextBuilder::new(WidgetBuilder::new())
+            .with_font_style("Font.Header")
+            .build(ctx);
+        let explicit = TextBuilder::new(WidgetBuilder::new())
+            .with_font(default_font.clone())
+            .build(ctx);
+
+        assert_eq!(font(&ui, bound), default_font);
+
+        let mut style = Style::dark();
+        style
+            .set_font(Style::FONT_DEFAULT, big_font.clone())
+            .set_font("Font.Header", big_font.clone());
+        ui.set_style(style);
+
+        assert_eq!(font(&ui, bound), big_font);
+        assert_eq!(font(&ui, custom_bound), big_font);
+        assert_eq!(font(&ui, explicit), default_font);
+
+        // Explicitly set font is no longer bound to the style.
+        ui.send_message(TextMessage::font(
+            custom_bound,
+            MessageDirection::ToWidget,
+            default_font.clone(),
+        ));
+        while ui.poll_message().is_some() {}
+
+        // Styles without the default font fall back to the default font of the UI.
+        ui.set_style(Style::dark());
+        assert_eq!(font(&ui, bound), default_font);
+
+        let mut style = Style::dark();
+        style.set_font("Font.Header", big_font);
+        ui.set_style(style);
+        assert_eq!(font(&ui, custom_bound), default_font);
+    }
+}
@@ -39,6 +39,14 @@ pub enum TabControlMessage {
     RemoveTab(usize),
     /// Adds a new tab using its definition.
     AddTab(TabDefinition),
+    /// Moves a tab to a new position (with [`MessageDirection::ToWidget`]) or notifies that a tab was moved, for
+    /// example by dragging its header (with [`MessageDirection::FromWidget`]).
+    MoveTab {
+        /// Current index of the tab.
+        from: usize,
+        /// New index of the tab.
+        to: usize,
+    },
 }
 
 impl TabControlMessage {
@@ -58,6 +66,10 @@ impl TabControlMessage {
         /// Creates [`TabControlMessage::AddTab`] message.
         TabControlMessage:AddTab => fn add_tab(TabDefinition), layout: false
     );
+    define_constructor!(
+        /// Creates [`TabControlMessage::MoveTab`] message.
+        TabControlMessage:MoveTab => fn move_tab(from: usize, to: usize), layout: false
+    );
 }
 
 /// User-defined data of a tab.
@@ -197,6 +209,12 @@ pub struct Tab {
 /// # }
 ///
 /// ```
+///
+/// ## Reordering
+///
+/// Tabs could be reordered by dragging their headers, if it is enabled by [`TabControlBuilder::with_tab_reordering`].
+/// The tab control sends [`TabControlMessage::MoveTab`] message with [`MessageDirection::FromWidget`] direction every
+/// time a tab is moved, so any external data, that relies on tab indices, could be synced.
 #[derive(Clone, Visit, Reflect, Debug)]
 pub struct TabControl {
     /// Base widget of the tab control.
@@ -211,6 +229,13 @@ pub struct TabControl {
     pub headers_container: Handle<UiNode>,
     /// A brush, that will be used to highlight active tab.
     pub active_tab_brush: Brush,
+    /// A flag, that defines whether the tabs could be reordered by dragging their headers or not.
+    #[visit(optional)]
+    pub tab_reordering: bool,
+    /// Index of a tab, that is being dragged.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub dragged_tab: Option<usize>,
 }
 
 crate::define_widget_deref!(TabControl);
@@ -251,17 +276,47 @@ impl Control for TabControl {
                     ));
                 }
             }
-        } else if let Some(WidgetMessage::MouseDown { button, .. }) = message.data() {
-            if *button == MouseButton::Middle {
-                for (tab_index, tab) in self.tabs.iter().enumerate() {
-                    if ui.is_node_child_of(message.destination(), tab.header_button) {
-                        ui.send_message(TabControlMessage::close_tab(
-                            self.handle,
-                            MessageDirection::FromWidget,
-                            tab_index,
-                        ));
+        } else if let Some(msg) = message.data::<WidgetMessage>() {
+            match msg {
+                WidgetMessage::MouseDown { button, .. } => {
+                    let tab_index = self.tabs.iter().position(|tab| {
+                        ui.is_node_child_of(message.destination(), tab.header_button)
+                    });
+                    if let Some(tab_index) = tab_index {
+                        if *button == MouseButton::Middle {
+                            ui.send_message(TabControlMessage::close_tab(
+                                self.handle,
+                                MessageDirection::FromWidget,
+                                tab_index,
+                            ));
+                        } else if *button == MouseButton::Left && self.tab_reordering {
+                            self.dragged_tab = Some(tab_index);
+                        }
                     }
                 }
+                WidgetMessage::MouseMove { pos, .. } => {
+                    if let Some(dragged_tab) = self.dragged_tab {
+                        let hovered_tab = self.tabs.iter().position(|tab| {
+                            ui.try_get_node(tab.header_container)
+                                .is_some_and(|header| header.screen_bounds().contains(*pos))
+                        });
+                        if let Some(hovered_tab) = hovered_tab {
+                            if hovered_tab != dragged_tab {
+                                ui.send_message(TabControlMessage::move_tab(
+                                    self.handle,
+                                    MessageDirection::ToWidget,
+                                    dragged_tab,
+                                    hovered_tab,
+                                ));
+                                self.dragged_tab = Some(hovered_tab);
+                            }
+                        }
+                    }
+                }
+                WidgetMessage::MouseUp { button, .. } if *button == MouseButton::Left => {
+                    self.dragged_tab = None;
+                }
+                _ => (),
             }
         } else if let Some(msg) = message.data::<TabControlMessage>() {
             if message.destination() == self.handle()
@@ -342,6 +397,33 @@ impl Control for TabControl {
                             header_content: header.content,
                         })
                     }
+                    &TabControlMessage::MoveTab { from, to } => {
+                        if from != to && from < self.tabs.len() && to < self.tabs.len() {
+                            let tab = self.tabs.remove(from);
+                            self.tabs.insert(to, tab);
+
+                            // Re-link the headers to put them in the new order.
+                            for tab in self.tabs.iter() {
+                                ui.send_message(WidgetMessage::link(
+                                    tab.header_container,
+                                    MessageDirection::ToWidget,
+                                    self.headers_container,
+                                ));
+                            }
+
+                            if let Some(active_tab) = self.active_tab.as_mut() {
+                                if *active_tab == from {
+                                    *active_tab = to;
+                                } else if from < *active_tab && *active_tab <= to {
+                                    *active_tab -= 1;
+                                } else if to <= *active_tab && *active_tab < from {
+                                    *active_tab += 1;
+                                }
+                            }
+
+                            ui.send_message(message.reverse());
+                        }
+                    }
                 }
             }
         }
@@ -353,6 +435,7 @@ pub struct TabControlBuilder {
     widget_builder: WidgetBuilder,
     tabs: Vec<TabDefinition>,
     active_tab_brush: Brush,
+    tab_reordering: bool,
 }
 
 /// Tab definition is used to describe content of each tab for the [`TabControlBuilder`] builder.
@@ -435,9 +518,16 @@ impl TabControlBuilder {
             widget_builder,
             tabs: Default::default(),
             active_tab_brush: BRUSH_BRIGHT,
+            tab_reordering: false,
         }
     }
 
+    /// Enables or disables reordering of the tabs by dragging their headers.
+    pub fn with_tab_reordering(mut self, enabled: bool) -> Self {
+        self.tab_reordering = enabled;
+        self
+    }
+
     /// Adds a new tab to the builder.
     pub fn with_tab(mut self, tab: TabDefinition) -> Self {
         self.tabs.push(tab);
@@ -527,8 +617,112 @@ impl TabControlBuilder {
             content_container,
             headers_container,
             active_tab_brush: self.active_tab_brush,
+            tab_reordering: self.tab_reordering,
+            dragged_tab: None,
         };
 
         ctx.add_node(UiNode::new(tc))
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        border::BorderBuilder,
+        core::{algebra::Vector2, pool::Handle},
+        message::{MessageDirection, UiMessage},
+        tab_control::{TabControl, TabControlBuilder, TabControlMessage, TabDefinition},
+        widget::WidgetBuilder,
+        UiNode, UserInterface,
+    };
+
+    fn tab_control(ui: &UserInterface, handle: Handle<UiNode>) -> &TabControl {
+        ui.node(handle).query_component::<TabControl>().unwrap()
+    }
+
+    fn send(ui: &mut UserInterface, message: UiMessage) -> Vec<TabControlMessage> {
+        ui.send_message(message);
+        let mut responses = Vec::new();
+        while let Some(message) = ui.poll_message() {
+            if message.direction() == MessageDirection::FromWidget {
+                if let Some(msg) = message.data::<TabControlMessage>() {
+                    responses.push(msg.clone());
+                }
+            }
+        }
+        responses
+    }
+
+    #[test]
+    fn move_tab() {
+        let mut ui = UserInterface::new(Vector2::new(100.0, 100.0));
+        let ctx = &mut ui.build_ctx();
+        let mut contents = Vec::new();
+        let mut builder = TabControlBuilder::new(WidgetBuilder::new()).with_tab_reordering(true);
+        for _ in 0..4 {
+            let content = BorderBuilder::new(WidgetBuilder::new()).build(ctx);
+            contents.push(content);
+            builder = builder.with_tab(TabDefinition {
+                header: BorderBuilder::new(WidgetBuilder::new()).build(ctx),
+                content,
+                can_be_closed: true,
+                user_data: None,
+            });
+        }
+        let handle = builder.build(ctx);
+
+        send(
+            &mut ui,
+            TabControlMessage::active_tab(handle, MessageDirection::ToWidget, Some(2)),
+        );
+        let active_content = contents[2];
+
+        // Move the active tab, tabs before and after it, across it and back.
+        for (from, to) in [
+            (2, 0),
+            (1, 3),
+            (3, 1),
+            (0, 2),
+            (3, 0),
+            (1, 2),
+            (2, 2),
+            (0, 4),
+        ] {
+            let responses = send(
+                &mut ui,
+                TabControlMessage::move_tab(handle, MessageDirection::ToWidget, from, to),
+            );
+
+            if from != to && to < contents.len() {
+                assert_eq!(responses, vec![TabControlMessage::MoveTab { from, to }]);
+                let content = contents.remove(from);
+                contents.insert(to, content);
+            } else {
+                assert!(responses.is_empty());
+            }
+
+            let tab_control = tab_control(&ui, handle);
+            assert_eq!(
+                tab_control
+                    .tabs
+                    .iter()
+                    .map(|tab| tab.content)
+                    .collect::<Vec<_>>(),
+                contents
+            );
+            assert_eq!(
+                ui.node(tab_control.headers_container).children(),
+                tab_control
+                    .tabs
+                    .iter()
+                    .map(|tab| tab.header_container)
+                    .collect::<Vec<_>>()
+            );
+            // The same tab stays active.
+            assert_eq!(
+                tab_control.tabs[tab_control.active_tab.unwrap()].content,
+                active_content
+            );
+        }
+    }
+}
@@ -5,12 +5,14 @@
 
 use crate::{
     brush::Brush,
-    core::{algebra::Vector2, color::Color, pool::Handle},
+    core::{algebra::Vector2, color::Color, pool::Handle, sstorage::ImmutableString},
     core::{reflect::prelude::*, visitor::prelude::*},
     define_constructor,
     draw::DrawingContext,
     formatted_text::{FormattedText, FormattedTextBuilder, WrapMode},
+    localization::Localization,
     message::{MessageDirection, UiMessage},
+    style::Style,
     ttf::SharedFont,
     widget::{Widget, WidgetBuilder},
     BuildContext, Control, HorizontalAlignment, UiNode, UserInterface, VerticalAlignment,
@@ -317,6 +319,13 @@ pub struct Text {
     #[visit(skip)]
     #[reflect(hidden)]
     pub formatted_text: RefCell<FormattedText>,
+    /// Optional localization key of the text. If set, the text is taken from the localization context of the user
+    /// interface (see [`Localization`]) and updated every time when the language changes.
+    #[visit(optional)]
+    pub localization_key: Option<ImmutableString>,
+    /// Name of a style property, that is used as the font of the text. See [`Style`] docs for more info.
+    #[visit(optional)]
+    pub font_style: Option<ImmutableString>,
 }
 
 crate::define_widget_deref!(Text);
@@ -330,9 +339,32 @@ impl Control for Text {
         }
     }
 
-    fn measure_override(&self, _: &UserInterface, available_size: Vector2<f32>) -> Vector2<f32> {
+    fn apply_style(&mut self, style: &Style) {
+        self.widget.apply_style(style);
+
+        if let Some(font) = self.font_style.as_ref().and_then(|name| style.font(name)) {
+            let mut text_ref = self.formatted_text.borrow_mut();
+            if text_ref.get_font() != font {
+                text_ref.set_font(font);
+                drop(text_ref);
+                self.invalidate_layout();
+            }
+        }
+    }
+
+    fn apply_localization(&mut self, localization: &Localization) {
+        if let Some(key) = self.localization_key.as_ref() {
+            self.formatted_text
+                .borrow_mut()
+                .set_text(localization.translate(key));
+            self.invalidate_layout();
+        }
+    }
+
+    fn measure_override(&self, ui: &UserInterface, available_size: Vector2<f32>) -> Vector2<f32> {
         self.formatted_text
             .borrow_mut()
+            .set_font_render_scale(ui.effective_scale())
             .set_constraint(available_size)
             .build()
     }
@@ -359,6 +391,8 @@ impl Control for Text {
                     TextMessage::Text(text) => {
                         text_ref.set_text(text);
                         drop(text_ref);
+                        // Explicitly set text is no longer bound to the localization key.
+                        self.localization_key = None;
                         self.invalidate_layout();
                     }
                     &TextMessage::Wrap(wrap) => {
@@ -369,6 +403,8 @@ impl Control for Text {
                         }
                     }
                     TextMessage::Font(font) => {
+                        // Explicitly set font is no longer bound to the style.
+                        self.font_style = None;
                         if &text_ref.get_font() != font {
                             text_ref.set_font(font.clone());
                             drop(text_ref);
@@ -454,7 +490,9 @@ impl Text {
 pub struct TextBuilder {
     widget_builder: WidgetBuilder,
     text: Option<String>,
+    localization_key: Option<ImmutableString>,
     font: Option<SharedFont>,
+    font_style: Option<ImmutableString>,
     vertical_text_alignment: VerticalAlignment,
     horizontal_text_alignment: HorizontalAlignment,
     wrap: WrapMode,
@@ -470,7 +508,9 @@ impl TextBuilder {
         Self {
             widget_builder,
             text: None,
+            localization_key: None,
             font: None,
+            font_style: None,
             vertical_text_alignment: VerticalAlignment::Top,
             horizontal_text_alignment: HorizontalAlignment::Left,
             wrap: WrapMode::NoWrap,
@@ -487,6 +527,13 @@ impl TextBuilder {
         self
     }
 
+    /// Binds the text of the widget to the given localization key. The text will be taken from the localization
+    /// context of the user interface (see [`Localization`]) and updated automatically when the language changes.
+    pub fn with_localization_key(mut self, key: &str) -> Self {
+        self.localization_key = Some(ImmutableString::new(key));
+        self
+    }
+
     /// Sets the desired font of the widget.
     pub fn with_font(mut self, font: SharedFont) -> Self {
         self.font = Some(font);
@@ -499,6 +546,14 @@ impl TextBuilder {
         self
     }
 
+    /// Binds the font of the widget to a style property with the given name. The font will be taken from the
+    /// current style of the user interface, and it will be updated when the style changes. Widgets without
+    /// explicitly specified font are bound to [`Style::FONT_DEFAULT`].
+    pub fn with_font_style(mut self, name: &str) -> Self {
+        self.font_style = Some(ImmutableString::new(name));
+        self
+    }
+
     /// Sets the desired vertical alignment of the widget.
     pub fn with_vertical_text_alignment(mut self, valign: VerticalAlignment) -> Self {
         self.vertical_text_alignment = valign;
@@ -544,14 +599,19 @@ impl TextBuilder {
 
     /// Finishes text widget creation and registers it in the user interface, returning its handle to you.
     pub fn build(mut self, ui: &mut BuildContext) -> Handle<UiNode> {
-        let font = if let Some(font) = self.font {
-            font
-        } else {
-            ui.default_font()
-        };
-
-        if self.widget_builder.foreground.is_none() {
-            self.widget_builder.foreground = Some(Brush::Solid(Color::opaque(220, 220, 220)));
+        // Text without explicit font uses the default font of the style.
+        let font_style = self.font_style.or_else(|| {
+            self.font
+                .is_none()
+                .then(|| ImmutableString::new(Style::FONT_DEFAULT))
+        });
+        let font = self
+            .font
+            .or_else(|| ui.style().font(font_style.as_ref()?))
+            .unwrap_or_else(|| ui.default_font());
+
+        if !self.widget_builder.has_foreground() {
+            self.widget_builder = self.widget_builder.with_foreground_style(Style::BRUSH_TEXT);
         }
 
         let text = Text {
@@ -568,6 +628,8 @@ impl TextBuilder {
                     .with_shadow_offset(self.shadow_offset)
                     .build(),
             ),
+            localization_key: self.localization_key,
+            font_style,
         };
         ui.add_node(UiNode::new(text))
     }
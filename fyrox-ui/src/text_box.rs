@@ -10,19 +10,19 @@ use crate::{
         color::Color,
         math::Rect,
         pool::Handle,
+        sstorage::ImmutableString,
     },
     core::{reflect::prelude::*, visitor::prelude::*},
     define_constructor,
     draw::{CommandTexture, Draw, DrawingContext},
     formatted_text::{FormattedText, FormattedTextBuilder, WrapMode},
-    message::{CursorIcon, KeyCode, MessageDirection, MouseButton, UiMessage},
+    message::{CursorIcon, ImeEvent, KeyCode, MessageDirection, MouseButton, UiMessage},
+    style::Style,
     text::TextMessage,
     ttf::SharedFont,
     widget::{Widget, WidgetBuilder, WidgetMessage},
     BuildContext, Control, HorizontalAlignment, UiNode, UserInterface, VerticalAlignment,
-    BRUSH_DARKER, BRUSH_TEXT,
 };
-use copypasta::ClipboardProvider;
 use std::{
     any::{Any, TypeId},
     cell::RefCell,
@@ -50,6 +50,9 @@ pub enum TextBoxMessage {
     Multiline(bool),
     /// Used to enable or disable an ability to edit text box content. Use [TextBoxMessage::editable`] to create the message.
     Editable(bool),
+    /// Used to move the caret of a text box to the given position. Positions outside of the text are clamped to the end
+    /// of the text. Use [TextBoxMessage::caret_position`] to create the message.
+    CaretPosition(Position),
 }
 
 impl TextBoxMessage {
@@ -73,6 +76,10 @@ impl TextBoxMessage {
         /// Creates [`TextBoxMessage::Editable`].
         TextBoxMessage:Editable => fn editable(bool), layout: false
     );
+    define_constructor!(
+        /// Creates [`TextBoxMessage::CaretPosition`].
+        TextBoxMessage:CaretPosition => fn caret_position(Position), layout: false
+    );
 }
 
 /// Specifies a direction on horizontal axis.
@@ -289,6 +296,7 @@ pub type FilterCallback = dyn FnMut(char) -> bool;
 /// - [`TextBoxMessage::TextCommitMode`] - changes the [text commit mode](TextBox#text-commit-mode).
 /// - [`TextBoxMessage::Multiline`] - makes the TextBox either multiline (`true`) or single line (`false`)
 /// - [`TextBoxMessage::Editable`] - enables or disables editing of the text.
+/// - [`TextBoxMessage::CaretPosition`] - moves the caret to the given position.
 ///
 /// **Important:** Please keep in mind, that TextBox widget also accepts [`TextMessage`]s. An example of changing text at
 /// runtime could be something like this:
@@ -319,11 +327,13 @@ pub type FilterCallback = dyn FnMut(char) -> bool;
 ///
 /// - `Ctrl+A` - select all
 /// - `Ctrl+C` - copy selected text
+/// - `Ctrl+X` - cut selected text
 /// - `Ctrl+V` - paste text from clipboard
-/// - `Ctrl+Home` - move caret to the beginning of the text
-/// - `Ctrl+End` - move caret to the beginning of the text
-/// - `Shift+Home` - select everything from current caret position until the beginning of current line
-/// - `Shift+End` - select everything from current caret position until the end of current line
+/// - `Home`/`End` - move caret to the beginning/end of current line
+/// - `Ctrl+Home`/`Ctrl+End` - move caret to the beginning/end of the text
+/// - `Ctrl+Left`/`Ctrl+Right` - move caret to the previous/next word
+/// - `Shift` with any of the keys above or with arrows - extend selection to the new caret position
+/// - Double click - select a word under cursor
 /// - `Arrows` - move caret accordingly
 /// - `Delete` - deletes next character
 /// - `Backspace` - deletes previous character
@@ -418,6 +428,14 @@ pub struct TextBox {
     pub view_position: Vector2<f32>,
     /// A list of custom characters that will be treated as whitespace.
     pub skip_chars: Vec<u32>,
+    /// Text that is being composed by an input method editor (IME). It is shown at the caret position until
+    /// the composition is finished.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub ime_preedit: String,
+    /// Name of a style property, that is used as the font of the text box. See [`Style`] docs for more info.
+    #[visit(optional)]
+    pub font_style: Option<ImmutableString>,
 }
 
 impl Debug for TextBox {
@@ -546,6 +564,22 @@ impl TextBox {
         self.ensure_caret_visible();
     }
 
+    fn move_caret_to(&mut self, position: Position, select: bool) {
+        if select {
+            let begin = self
+                .selection_range
+                .map_or(self.caret_position, |range| range.begin);
+            self.selection_range = Some(SelectionRange {
+                begin,
+                end: position,
+            });
+        } else {
+            self.selection_range = None;
+        }
+
+        self.set_caret_position(position);
+    }
+
     fn position_to_char_index_internal(&self, position: Position, clamp: bool) -> Option<usize> {
         self.formatted_text
             .borrow()
@@ -918,6 +952,25 @@ impl TextBox {
         self.formatted_text.borrow().text()
     }
 
+    /// Returns currently selected text of text box, or `None` if nothing is selected.
+    pub fn selected_text(&self) -> Option<String> {
+        let selection_range = self.selection_range?.normalized();
+        let begin = self.position_to_char_index_unclamped(selection_range.begin)?;
+        let end = self.position_to_char_index_unclamped(selection_range.end)?;
+        let text = self.formatted_text.borrow();
+        let selected_text = text
+            .get_raw_text()
+            .get(begin..end)?
+            .iter()
+            .filter_map(|c| char::from_u32(c.char_code))
+            .collect::<String>();
+        if selected_text.is_empty() {
+            None
+        } else {
+            Some(selected_text)
+        }
+    }
+
     /// Returns current word wrapping mode of text box.
     pub fn wrap_mode(&self) -> WrapMode {
         self.formatted_text.borrow().wrap_mode()
@@ -989,9 +1042,23 @@ impl Control for TextBox {
         }
     }
 
-    fn measure_override(&self, _: &UserInterface, available_size: Vector2<f32>) -> Vector2<f32> {
+    fn apply_style(&mut self, style: &Style) {
+        self.widget.apply_style(style);
+
+        if let Some(font) = self.font_style.as_ref().and_then(|name| style.font(name)) {
+            let mut text = self.formatted_text.borrow_mut();
+            if text.get_font() != font {
+                text.set_font(font);
+                drop(text);
+                self.invalidate_layout();
+            }
+        }
+    }
+
+    fn measure_override(&self, ui: &UserInterface, available_size: Vector2<f32>) -> Vector2<f32> {
         self.formatted_text
             .borrow_mut()
+            .set_font_render_scale(ui.effective_scale())
             .set_constraint(available_size)
             .build()
     }
@@ -1086,8 +1153,44 @@ impl Control for TextBox {
             &self.formatted_text.borrow(),
         );
 
+        let mut caret_pos = self.point_to_view_pos(self.caret_local_position());
+
+        if !self.ime_preedit.is_empty() {
+            let mut preedit = FormattedTextBuilder::new(self.formatted_text.borrow().get_font())
+                .with_text(self.ime_preedit.clone())
+                .with_constraint(Vector2::repeat(f32::INFINITY))
+                .with_brush(self.widget.foreground())
+                .build();
+            let size = preedit.build();
+
+            // Hide the text behind the composition.
+            drawing_context
+                .push_rect_filled(&Rect::new(caret_pos.x, caret_pos.y, size.x, size.y), None);
+            drawing_context.commit(
+                self.clip_bounds(),
+                self.widget.background(),
+                CommandTexture::None,
+                None,
+            );
+
+            drawing_context.draw_text(self.clip_bounds(), caret_pos, &preedit);
+
+            // Composing text is underlined, as in most of text editors.
+            drawing_context.push_rect_filled(
+                &Rect::new(caret_pos.x, caret_pos.y + size.y - 1.0, size.x, 1.0),
+                None,
+            );
+            drawing_context.commit(
+                self.clip_bounds(),
+                self.widget.foreground(),
+                CommandTexture::None,
+                None,
+            );
+
+            caret_pos.x += size.x;
+        }
+
         if self.caret_visible {
-            let caret_pos = self.point_to_view_pos(self.caret_local_position());
             let caret_bounds = Rect::new(
                 caret_pos.x,
                 caret_pos.y,
@@ -1127,6 +1230,7 @@ impl Control for TextBox {
                             && !ui.keyboard_modifiers().alt
                             && self.editable =>
                     {
+                        self.ime_preedit.clear();
                         for symbol in text.chars() {
                             let insert = if let Some(filter) = self.filter.as_ref() {
                                 let filter = &mut *filter.borrow_mut();
@@ -1163,24 +1267,12 @@ impl Control for TextBox {
                             }
                             KeyCode::ArrowRight => {
                                 if ui.keyboard_modifiers.control {
-                                    let prev_position = self.caret_position;
                                     let next_word_position =
                                         self.find_next_word(self.caret_position);
-                                    self.set_caret_position(next_word_position);
-                                    self.reset_blink();
-                                    if ui.keyboard_modifiers.shift {
-                                        if let Some(selection_range) = self.selection_range.as_mut()
-                                        {
-                                            selection_range.end = next_word_position;
-                                        } else {
-                                            self.selection_range = Some(SelectionRange {
-                                                begin: prev_position,
-                                                end: next_word_position,
-                                            });
-                                        }
-                                    } else {
-                                        self.selection_range = None;
-                                    }
+                                    self.move_caret_to(
+                                        next_word_position,
+                                        ui.keyboard_modifiers.shift,
+                                    );
                                 } else {
                                     self.move_caret_x(
                                         1,
@@ -1191,23 +1283,12 @@ impl Control for TextBox {
                             }
                             KeyCode::ArrowLeft => {
                                 if ui.keyboard_modifiers.control {
-                                    let prev_position = self.caret_position;
                                     let prev_word_position =
                                         self.find_prev_word(self.caret_position);
-                                    self.set_caret_position(prev_word_position);
-                                    if ui.keyboard_modifiers.shift {
-                                        if let Some(selection_range) = self.selection_range.as_mut()
-                                        {
-                                            selection_range.end = prev_word_position;
-                                        } else {
-                                            self.selection_range = Some(SelectionRange {
-                                                begin: prev_position,
-                                                end: prev_word_position,
-                                            });
-                                        }
-                                    } else {
-                                        self.selection_range = None;
-                                    }
+                                    self.move_caret_to(
+                                        prev_word_position,
+                                        ui.keyboard_modifiers.shift,
+                                    );
                                 } else {
                                     self.move_caret_x(
                                         1,
@@ -1245,110 +1326,58 @@ impl Control for TextBox {
                                 }
                             }
                             KeyCode::End => {
-                                let text = self.formatted_text.borrow();
-                                let line = &text.get_lines()[self.caret_position.line];
-                                if ui.keyboard_modifiers().control {
-                                    let new_position = Position {
-                                        line: text.get_lines().len() - 1,
-                                        offset: line.end - line.begin,
-                                    };
-                                    drop(text);
-                                    self.set_caret_position(new_position);
-                                    self.selection_range = None;
-                                } else if ui.keyboard_modifiers().shift {
-                                    let prev_position = self.caret_position;
-                                    let new_position = Position {
-                                        line: self.caret_position.line,
-                                        offset: line.end - line.begin,
-                                    };
-                                    drop(text);
-                                    self.set_caret_position(new_position);
-                                    self.selection_range = Some(SelectionRange {
-                                        begin: prev_position,
-                                        end: Position {
-                                            line: self.caret_position.line,
-                                            offset: self.caret_position.offset,
-                                        },
-                                    });
+                                let new_position = if ui.keyboard_modifiers().control {
+                                    self.end_position()
                                 } else {
-                                    let new_position = Position {
+                                    let text = self.formatted_text.borrow();
+                                    Position {
                                         line: self.caret_position.line,
-                                        offset: line.end - line.begin,
-                                    };
-                                    drop(text);
-                                    self.set_caret_position(new_position);
-                                    self.selection_range = None;
-                                }
+                                        offset: text
+                                            .get_lines()
+                                            .get(self.caret_position.line)
+                                            .map_or(0, |line| line.len()),
+                                    }
+                                };
+                                self.move_caret_to(new_position, ui.keyboard_modifiers().shift);
                             }
                             KeyCode::Home => {
-                                if ui.keyboard_modifiers().control {
-                                    self.set_caret_position(Position { line: 0, offset: 0 });
-                                    self.selection_range = None;
-                                } else if ui.keyboard_modifiers().shift {
-                                    let prev_position = self.caret_position;
-                                    self.set_caret_position(Position {
-                                        line: self.caret_position.line,
-                                        offset: 0,
-                                    });
-                                    self.selection_range = Some(SelectionRange {
-                                        begin: self.caret_position,
-                                        end: Position {
-                                            line: prev_position.line,
-                                            offset: prev_position.offset,
-                                        },
-                                    });
+                                let new_position = if ui.keyboard_modifiers().control {
+                                    Position::default()
                                 } else {
-                                    self.set_caret_position(Position {
+                                    Position {
                                         line: self.caret_position.line,
                                         offset: 0,
-                                    });
-                                    self.selection_range = None;
-                                }
+                                    }
+                                };
+                                self.move_caret_to(new_position, ui.keyboard_modifiers().shift);
                             }
                             KeyCode::KeyA if ui.keyboard_modifiers().control => {
-                                let text = self.formatted_text.borrow();
-                                if let Some(last_line) = &text.get_lines().last() {
-                                    self.selection_range = Some(SelectionRange {
-                                        begin: Position { line: 0, offset: 0 },
-                                        end: Position {
-                                            line: text.get_lines().len() - 1,
-                                            offset: last_line.end - last_line.begin,
-                                        },
-                                    });
-                                }
+                                self.caret_position = Position::default();
+                                self.selection_range = None;
+                                self.move_caret_to(self.end_position(), true);
                             }
                             KeyCode::KeyC if ui.keyboard_modifiers().control => {
-                                if let Some(mut clipboard) = ui.clipboard_mut() {
-                                    if let Some(selection_range) = self.selection_range.as_ref() {
-                                        if let (Some(begin), Some(end)) = (
-                                            self.position_to_char_index_unclamped(
-                                                selection_range.begin,
-                                            ),
-                                            self.position_to_char_index_unclamped(
-                                                selection_range.end,
-                                            ),
-                                        ) {
-                                            let _ = clipboard.set_contents(String::from(
-                                                &self.text()[if begin < end {
-                                                    begin..end
-                                                } else {
-                                                    end..begin
-                                                }],
-                                            ));
-                                        }
-                                    }
+                                if let Some(selected_text) = self.selected_text() {
+                                    let _ = ui.clipboard_provider_mut().set_contents(selected_text);
+                                }
+                            }
+                            KeyCode::KeyX if ui.keyboard_modifiers().control && self.editable => {
+                                if let Some(selected_text) = self.selected_text() {
+                                    let _ = ui.clipboard_provider_mut().set_contents(selected_text);
+                                }
+                                if let Some(selection_range) = self.selection_range.take() {
+                                    self.remove_range(ui, selection_range);
                                 }
                             }
                             KeyCode::KeyV if ui.keyboard_modifiers().control => {
-                                if let Some(mut clipboard) = ui.clipboard_mut() {
-                                    if let Ok(content) = clipboard.get_contents() {
-                                        if let Some(selection_range) = self.selection_range {
-                                            self.remove_range(ui, selection_range);
-                                            self.selection_range = None;
-                                        }
-
-                                        self.insert_str(&content, ui);
+                                let content = ui.clipboard_provider_mut().get_contents();
+                                if let Ok(content) = content {
+                                    if let Some(selection_range) = self.selection_range {
+                                        self.remove_range(ui, selection_range);
+                                        self.selection_range = None;
                                     }
+
+                                    self.insert_str(&content, ui);
                                 }
                             }
                             _ => (),
@@ -1358,6 +1387,21 @@ impl Control for TextBox {
                         // others are used directly to enter text.
                         message.set_handled(true);
                     }
+                    WidgetMessage::Ime(ime) if self.editable => match ime {
+                        ImeEvent::Preedit { text, .. } => {
+                            // Composing text replaces selected text, as any other text input.
+                            if !text.is_empty() {
+                                if let Some(range) = self.selection_range.take() {
+                                    self.remove_range(ui, range);
+                                }
+                            }
+                            self.ime_preedit = text.clone();
+                            self.reset_blink();
+                        }
+                        ImeEvent::Enabled | ImeEvent::Commit(_) | ImeEvent::Disabled => {
+                            self.ime_preedit.clear();
+                        }
+                    },
                     WidgetMessage::Focus => {
                         if message.direction() == MessageDirection::FromWidget {
                             self.reset_blink();
@@ -1369,6 +1413,7 @@ impl Control for TextBox {
                         if message.direction() == MessageDirection::FromWidget {
                             self.selection_range = None;
                             self.has_focus = false;
+                            self.ime_preedit.clear();
 
                             if self.commit_mode == TextCommitMode::LostFocus
                                 || self.commit_mode == TextCommitMode::LostFocusPlusEnter
@@ -1469,6 +1514,8 @@ impl Control for TextBox {
                             }
                         }
                         TextMessage::Font(font) => {
+                            // Explicitly set font is no longer bound to the style.
+                            self.font_style = None;
                             if &text.get_font() != font {
                                 text.set_font(font.clone());
                                 drop(text);
@@ -1559,6 +1606,22 @@ impl Control for TextBox {
                                 ui.send_message(message.reverse());
                             }
                         }
+                        &TextBoxMessage::CaretPosition(position) => {
+                            let end = self.end_position();
+                            let position = if self.is_valid_position(position) || position == end {
+                                position
+                            } else {
+                                end
+                            };
+                            if self.caret_position != position || self.selection_range.is_some() {
+                                self.move_caret_to(position, false);
+                                ui.send_message(TextBoxMessage::caret_position(
+                                    self.handle,
+                                    MessageDirection::FromWidget,
+                                    position,
+                                ));
+                            }
+                        }
                     }
                 }
             }
@@ -1570,6 +1633,7 @@ impl Control for TextBox {
 pub struct TextBoxBuilder {
     widget_builder: WidgetBuilder,
     font: Option<SharedFont>,
+    font_style: Option<ImmutableString>,
     text: String,
     caret_brush: Brush,
     selection_brush: Brush,
@@ -1594,6 +1658,7 @@ impl TextBoxBuilder {
         Self {
             widget_builder,
             font: None,
+            font_style: None,
             text: "".to_owned(),
             caret_brush: Brush::Solid(Color::WHITE),
             selection_brush: Brush::Solid(Color::opaque(80, 118, 178)),
@@ -1619,6 +1684,14 @@ impl TextBoxBuilder {
         self
     }
 
+    /// Binds the font of the text box to a style property with the given name. The font will be taken from
+    /// the current style of the user interface, and it will be updated when the style changes. Text boxes
+    /// without explicitly specified font are bound to [`Style::FONT_DEFAULT`].
+    pub fn with_font_style(mut self, name: &str) -> Self {
+        self.font_style = Some(ImmutableString::new(name));
+        self
+    }
+
     /// Sets the desired text of the text box.
     pub fn with_text<P: AsRef<str>>(mut self, text: P) -> Self {
         self.text = text.as_ref().to_owned();
@@ -1721,16 +1794,29 @@ impl TextBoxBuilder {
 
     /// Creates a new [`TextBox`] instance and adds it to the user interface.
     pub fn build(mut self, ctx: &mut BuildContext) -> Handle<UiNode> {
-        if self.widget_builder.foreground.is_none() {
-            self.widget_builder.foreground = Some(BRUSH_TEXT);
+        if !self.widget_builder.has_foreground() {
+            self.widget_builder = self.widget_builder.with_foreground_style(Style::BRUSH_TEXT);
         }
-        if self.widget_builder.background.is_none() {
-            self.widget_builder.background = Some(BRUSH_DARKER);
+        if !self.widget_builder.has_background() {
+            self.widget_builder = self
+                .widget_builder
+                .with_background_style(Style::BRUSH_DARKER);
         }
         if self.widget_builder.cursor.is_none() {
             self.widget_builder.cursor = Some(CursorIcon::Text);
         }
 
+        // Text box without explicit font uses the default font of the style.
+        let font_style = self.font_style.or_else(|| {
+            self.font
+                .is_none()
+                .then(|| ImmutableString::new(Style::FONT_DEFAULT))
+        });
+        let font = self
+            .font
+            .or_else(|| ctx.style().font(font_style.as_ref()?))
+            .unwrap_or_else(|| ctx.default_font());
+
         let text_box = TextBox {
             widget: self.widget_builder.build(),
             caret_position: Position::default(),
@@ -1738,7 +1824,7 @@ impl TextBoxBuilder {
             blink_timer: 0.0,
             blink_interval: 0.5,
             formatted_text: RefCell::new(
-                FormattedTextBuilder::new(self.font.unwrap_or_else(|| ctx.default_font()))
+                FormattedTextBuilder::new(font)
                     .with_text(self.text)
                     .with_horizontal_alignment(self.horizontal_alignment)
                     .with_vertical_alignment(self.vertical_alignment)
@@ -1761,8 +1847,242 @@ impl TextBoxBuilder {
             editable: self.editable,
             view_position: Default::default(),
             skip_chars: self.skip_chars,
+            ime_preedit: Default::default(),
+            font_style,
         };
 
         ctx.add_node(UiNode::new(text_box))
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        clipboard::LocalClipboard,
+        core::{algebra::Vector2, pool::Handle},
+        message::{ImeEvent, KeyCode, KeyboardModifiers, MessageDirection, OsEvent},
+        text_box::{Position, SelectionRange, TextBox, TextBoxBuilder},
+        widget::{WidgetBuilder, WidgetMessage},
+        UiNode, UserInterface,
+    };
+
+    fn text_box(ui: &UserInterface, handle: Handle<UiNode>) -> &TextBox {
+        ui.node(handle).query_component::<TextBox>().unwrap()
+    }
+
+    fn key(
+        ui: &mut UserInterface,
+        handle: Handle<UiNode>,
+        key: KeyCode,
+        shift: bool,
+        control: bool,
+    ) {
+        ui.process_os_event(&OsEvent::KeyboardModifiers(KeyboardModifiers {
+            shift,
+            control,
+            ..Default::default()
+        }));
+        ui.send_message(WidgetMessage::key_down(
+            handle,
+            MessageDirection::FromWidget,
+            key,
+        ));
+        while ui.poll_message().is_some() {}
+    }
+
+    fn focused_text_box(ui: &mut UserInterface, builder: TextBoxBuilder) -> Handle<UiNode> {
+        let handle = builder.build(&mut ui.build_ctx());
+        ui.update(Vector2::new(100.0, 100.0), 0.0);
+        ui.send_message(WidgetMessage::focus(handle, MessageDirection::ToWidget));
+        while ui.poll_message().is_some() {}
+        handle
+    }
+
+    fn ime(ui: &mut UserInterface, event: ImeEvent) {
+        ui.process_os_event(&OsEvent::Ime(event));
+        while ui.poll_message().is_some() {}
+    }
+
+    #[test]
+    fn ime_composition() {
+        let mut ui = UserInterface::new(Vector2::new(100.0, 100.0));
+        let handle = TextBoxBuilder::new(WidgetBuilder::new())
+            .with_text("Foo")
+            .build(&mut ui.build_ctx());
+        ui.update(Vector2::new(100.0, 100.0), 0.0);
+
+        ui.send_message(WidgetMessage::focus(handle, MessageDirection::ToWidget));
+        while ui.poll_message().is_some() {}
+        ui.send_message(WidgetMessage::key_down(
+            handle,
+            MessageDirection::FromWidget,
+            KeyCode::End,
+        ));
+        while ui.poll_message().is_some() {}
+
+        ime(&mut ui, ImeEvent::Enabled);
+        ime(
+            &mut ui,
+            ImeEvent::Preedit {
+                text: "ni".to_string(),
+                cursor: Some((2, 2)),
+            },
+        );
+        // Composing text is not a part of the text until it is committed.
+        assert_eq!(text_box(&ui, handle).ime_preedit, "ni");
+        assert_eq!(text_box(&ui, handle).text(), "Foo");
+        ui.draw();
+
+        ime(
+            &mut ui,
+            ImeEvent::Preedit {
+                text: String::new(),
+                cursor: None,
+            },
+        );
+        ime(&mut ui, ImeEvent::Commit("你".to_string()));
+        assert_eq!(text_box(&ui, handle).ime_preedit, "");
+        assert_eq!(text_box(&ui, handle).text(), "Foo你");
+
+        // Unfinished composition is discarded when the text box loses focus.
+        ime(
+            &mut ui,
+            ImeEvent::Preedit {
+                text: "hao".to_string(),
+                cursor: Some((3, 3)),
+            },
+        );
+        ui.send_message(WidgetMessage::unfocus(handle, MessageDirection::ToWidget));
+        while ui.poll_message().is_some() {}
+        assert_eq!(text_box(&ui, handle).ime_preedit, "");
+        assert_eq!(text_box(&ui, handle).text(), "Foo你");
+    }
+
+    #[test]
+    fn caret_movement_selection() {
+        let mut ui = UserInterface::new(Vector2::new(100.0, 100.0));
+        let handle = focused_text_box(
+            &mut ui,
+            TextBoxBuilder::new(WidgetBuilder::new()).with_text("Foo bar baz"),
+        );
+
+        key(&mut ui, handle, KeyCode::Home, false, false);
+        assert_eq!(text_box(&ui, handle).selected_text(), None);
+
+        // Selection keeps its beginning while the caret is moved with Shift.
+        key(&mut ui, handle, KeyCode::ArrowRight, true, true);
+        assert_eq!(
+            text_box(&ui, handle).selected_text().as_deref(),
+            Some("Foo ")
+        );
+        key(&mut ui, handle, KeyCode::ArrowRight, true, true);
+        assert_eq!(
+            text_box(&ui, handle).selected_text().as_deref(),
+            Some("Foo bar ")
+        );
+        key(&mut ui, handle, KeyCode::End, true, false);
+        assert_eq!(
+            text_box(&ui, handle).selected_text().as_deref(),
+            Some("Foo bar baz")
+        );
+        key(&mut ui, handle, KeyCode::ArrowLeft, true, true);
+        assert_eq!(
+            text_box(&ui, handle).selected_text().as_deref(),
+            Some("Foo bar")
+        );
+
+        // Selection towards the beginning of the text has its beginning after its end.
+        key(&mut ui, handle, KeyCode::Home, true, false);
+        assert_eq!(text_box(&ui, handle).selected_text(), None);
+        key(&mut ui, handle, KeyCode::End, false, false);
+        key(&mut ui, handle, KeyCode::ArrowLeft, false, true);
+        key(&mut ui, handle, KeyCode::Home, true, false);
+        assert_eq!(
+            text_box(&ui, handle).selection_range,
+            Some(SelectionRange {
+                begin: Position { line: 0, offset: 7 },
+                end: Position { line: 0, offset: 0 },
+            })
+        );
+        assert_eq!(
+            text_box(&ui, handle).selected_text().as_deref(),
+            Some("Foo bar")
+        );
+
+        // Moving without Shift drops the selection.
+        key(&mut ui, handle, KeyCode::End, false, true);
+        assert_eq!(text_box(&ui, handle).selection_range, None);
+        assert_eq!(
+            text_box(&ui, handle).caret_position,
+            Position {
+                line: 0,
+                offset: 11
+            }
+        );
+    }
+
+    #[test]
+    fn select_all() {
+        let mut ui = UserInterface::new(Vector2::new(100.0, 100.0));
+        let handle = focused_text_box(
+            &mut ui,
+            TextBoxBuilder::new(WidgetBuilder::new())
+                .with_multiline(true)
+                .with_text("Foo\nBar"),
+        );
+
+        key(&mut ui, handle, KeyCode::ArrowRight, false, false);
+        key(&mut ui, handle, KeyCode::KeyA, false, true);
+        assert_eq!(
+            text_box(&ui, handle).selection_range,
+            Some(SelectionRange {
+                begin: Position::default(),
+                end: Position { line: 1, offset: 3 },
+            })
+        );
+        assert_eq!(
+            text_box(&ui, handle).caret_position,
+            Position { line: 1, offset: 3 }
+        );
+        assert_eq!(
+            text_box(&ui, handle).selected_text().as_deref(),
+            Some("Foo\nBar")
+        );
+    }
+
+    #[test]
+    fn cut() {
+        let mut ui = UserInterface::new(Vector2::new(100.0, 100.0));
+        ui.set_clipboard(Box::<LocalClipboard>::default());
+        let handle = focused_text_box(
+            &mut ui,
+            TextBoxBuilder::new(WidgetBuilder::new()).with_text("Foobar"),
+        );
+
+        // Nothing is selected, nothing to cut.
+        key(&mut ui, handle, KeyCode::End, false, false);
+        key(&mut ui, handle, KeyCode::KeyX, false, true);
+        assert_eq!(text_box(&ui, handle).text(), "Foobar");
+
+        for _ in 0..3 {
+            key(&mut ui, handle, KeyCode::ArrowLeft, true, false);
+        }
+        key(&mut ui, handle, KeyCode::KeyX, false, true);
+        assert_eq!(text_box(&ui, handle).text(), "Foo");
+        assert_eq!(text_box(&ui, handle).selection_range, None);
+        assert_eq!(ui.clipboard_provider_mut().get_contents().unwrap(), "bar");
+
+        // Read-only text boxes allow to copy, but not to cut.
+        let handle = focused_text_box(
+            &mut ui,
+            TextBoxBuilder::new(WidgetBuilder::new())
+                .with_editable(false)
+                .with_text("Baz"),
+        );
+        key(&mut ui, handle, KeyCode::KeyA, false, true);
+        key(&mut ui, handle, KeyCode::KeyX, false, true);
+        assert_eq!(text_box(&ui, handle).text(), "Baz");
+        key(&mut ui, handle, KeyCode::KeyC, false, true);
+        assert_eq!(ui.clipboard_provider_mut().get_contents().unwrap(), "Baz");
+    }
+}
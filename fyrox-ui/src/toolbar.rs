@@ -0,0 +1,462 @@
+//! Toolbar is a horizontal container for buttons, toggles and separators, that hides items that do not fit into an
+//! overflow drop-down menu. See [`Toolbar`] docs for more info and usage examples.
+
+#![warn(missing_docs)]
+
+use crate::{
+    border::BorderBuilder,
+    button::{ButtonBuilder, ButtonMessage},
+    check_box::CheckBoxMessage,
+    core::{algebra::Vector2, math::Rect, pool::Handle},
+    core::{reflect::prelude::*, visitor::prelude::*},
+    define_constructor,
+    message::{MessageDirection, UiMessage},
+    popup::{Placement, PopupBuilder, PopupMessage},
+    stack_panel::StackPanelBuilder,
+    style::Style,
+    utils::{make_arrow, ArrowDirection},
+    widget::{Widget, WidgetBuilder, WidgetMessage},
+    BuildContext, Control, NodeHandleMapping, Thickness, UiNode, UserInterface,
+};
+use std::{
+    any::{Any, TypeId},
+    cell::Cell,
+    ops::{Deref, DerefMut},
+    sync::mpsc::Sender,
+};
+
+/// Width of the overflow button in pixels.
+const OVERFLOW_BUTTON_WIDTH: f32 = 16.0;
+
+/// A set of messages, that can be used to modify the state of a [`Toolbar`] widget instance.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ToolbarMessage {
+    /// Used to set new items of the toolbar. Old items will be removed.
+    Items(Vec<Handle<UiNode>>),
+    /// Used to set new toggle groups of the toolbar. See [`ToolbarBuilder::with_toggle_group`] for more info.
+    ToggleGroups(Vec<Vec<Handle<UiNode>>>),
+}
+
+impl ToolbarMessage {
+    define_constructor!(
+        /// Creates [`ToolbarMessage::Items`] message.
+        ToolbarMessage:Items => fn items(Vec<Handle<UiNode>>), layout: false
+    );
+    define_constructor!(
+        /// Creates [`ToolbarMessage::ToggleGroups`] message.
+        ToolbarMessage:ToggleGroups => fn toggle_groups(Vec<Vec<Handle<UiNode>>>), layout: false
+    );
+}
+
+/// Toolbar is a container, that arranges its items (buttons, toggles, separators, etc.) in a row from left to right.
+/// Items that do not fit into the toolbar are moved into a drop-down menu, that can be opened by clicking on the
+/// overflow button at the right side of the toolbar. Items are moved back when there is enough space for them.
+///
+/// Toolbar also supports toggle groups - a set of check boxes, where only one check box can be checked at a time
+/// (for example, a set of interaction modes of a scene viewport).
+///
+/// ## Example
+///
+/// ```rust
+/// # use fyrox_ui::{
+/// #     button::ButtonBuilder, check_box::CheckBoxBuilder, core::pool::Handle,
+/// #     toolbar::{make_toolbar_separator, ToolbarBuilder}, widget::WidgetBuilder, BuildContext, UiNode,
+/// # };
+/// fn create_toolbar(ctx: &mut BuildContext) -> Handle<UiNode> {
+///     let save = ButtonBuilder::new(WidgetBuilder::new()).with_text("Save").build(ctx);
+///     let separator = make_toolbar_separator(ctx);
+///     let select = CheckBoxBuilder::new(WidgetBuilder::new())
+///         .checked(Some(true))
+///         .build(ctx);
+///     let move_ = CheckBoxBuilder::new(WidgetBuilder::new())
+///         .checked(Some(false))
+///         .build(ctx);
+///
+///     ToolbarBuilder::new(WidgetBuilder::new())
+///         .with_items(vec![save, separator, select, move_])
+///         .with_toggle_group(vec![select, move_])
+///         .build(ctx)
+/// }
+/// ```
+#[derive(Clone, Debug, Visit, Reflect)]
+pub struct Toolbar {
+    /// Base widget of the toolbar.
+    pub widget: Widget,
+    /// Items of the toolbar in the order of appearance.
+    pub items: Vec<Handle<UiNode>>,
+    /// Toggle groups of the toolbar.
+    pub toggle_groups: Vec<Vec<Handle<UiNode>>>,
+    /// A button, that opens the overflow menu.
+    pub overflow_button: Handle<UiNode>,
+    /// A popup with the items, that do not fit into the toolbar.
+    pub overflow_popup: Handle<UiNode>,
+    /// A panel of the overflow popup, that holds the items, that do not fit into the toolbar.
+    pub overflow_panel: Handle<UiNode>,
+    /// Index of the first item, that was moved into the overflow menu.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub overflow_start: Cell<usize>,
+}
+
+crate::define_widget_deref!(Toolbar);
+
+impl Toolbar {
+    fn item_width(&self, ui: &UserInterface, item: Handle<UiNode>) -> f32 {
+        ui.try_get_node(item)
+            .map(|item| item.desired_size().x)
+            .unwrap_or_default()
+    }
+
+    fn move_items(&self, ui: &UserInterface, overflow_start: usize) {
+        for (i, &item) in self.items.iter().enumerate() {
+            let parent = if i < overflow_start {
+                self.handle
+            } else {
+                self.overflow_panel
+            };
+            // Re-linking all the items keeps their order in both containers.
+            ui.send_message(WidgetMessage::link(
+                item,
+                MessageDirection::ToWidget,
+                parent,
+            ));
+        }
+        // The overflow button must always be the last child of the toolbar.
+        ui.send_message(WidgetMessage::link(
+            self.overflow_button,
+            MessageDirection::ToWidget,
+            self.handle,
+        ));
+        ui.send_message(WidgetMessage::visibility(
+            self.overflow_button,
+            MessageDirection::ToWidget,
+            overflow_start < self.items.len(),
+        ));
+        if overflow_start >= self.items.len() {
+            ui.send_message(PopupMessage::close(
+                self.overflow_popup,
+                MessageDirection::ToWidget,
+            ));
+        }
+    }
+}
+
+impl Control for Toolbar {
+    fn query_component(&self, type_id: TypeId) -> Option<&dyn Any> {
+        if type_id == TypeId::of::<Self>() {
+            Some(self)
+        } else {
+            None
+        }
+    }
+
+    fn on_remove(&self, sender: &Sender<UiMessage>) {
+        // Popup is not the child of the toolbar, so it has to be removed manually.
+        sender
+            .send(WidgetMessage::remove(
+                self.overflow_popup,
+                MessageDirection::ToWidget,
+            ))
+            .unwrap();
+    }
+
+    fn resolve(&mut self, node_map: &NodeHandleMapping) {
+        node_map.resolve_slice(&mut self.items);
+        for group in self.toggle_groups.iter_mut() {
+            node_map.resolve_slice(group);
+        }
+        node_map.resolve(&mut self.overflow_button);
+        node_map.resolve(&mut self.overflow_popup);
+        node_map.resolve(&mut self.overflow_panel);
+    }
+
+    fn measure_override(&self, ui: &UserInterface, available_size: Vector2<f32>) -> Vector2<f32> {
+        let child_constraint = Vector2::new(f32::INFINITY, available_size.y);
+
+        let mut desired_size = Vector2::<f32>::default();
+        for &child in self.children() {
+            ui.measure_node(child, child_constraint);
+        }
+        // Items inside the overflow menu are measured by the menu, but the toolbar still wants to have enough
+        // space for them.
+        for &item in self.items.iter() {
+            if let Some(item_ref) = ui.try_get_node(item) {
+                let size = item_ref.desired_size();
+                desired_size.x += size.x;
+                desired_size.y = desired_size.y.max(size.y);
+            }
+        }
+
+        desired_size
+    }
+
+    fn arrange_override(&self, ui: &UserInterface, final_size: Vector2<f32>) -> Vector2<f32> {
+        let total_width = self
+            .items
+            .iter()
+            .map(|&item| self.item_width(ui, item))
+            .sum::<f32>();
+
+        let overflow_start = if total_width <= final_size.x {
+            self.items.len()
+        } else {
+            // Reserve some space for the overflow button.
+            let available_width = final_size.x - OVERFLOW_BUTTON_WIDTH;
+            let mut width = 0.0;
+            let mut overflow_start = 0;
+            for &item in self.items.iter() {
+                width += self.item_width(ui, item);
+                if width > available_width {
+                    break;
+                }
+                overflow_start += 1;
+            }
+            overflow_start
+        };
+
+        if overflow_start != self.overflow_start.get() {
+            self.overflow_start.set(overflow_start);
+            self.move_items(ui, overflow_start);
+        }
+
+        let mut x = 0.0;
+        for &item in self.items.iter().take(overflow_start) {
+            if self.children().contains(&item) {
+                let width = self.item_width(ui, item);
+                ui.arrange_node(item, &Rect::new(x, 0.0, width, final_size.y));
+                x += width;
+            }
+        }
+
+        ui.arrange_node(
+            self.overflow_button,
+            &Rect::new(
+                (final_size.x - OVERFLOW_BUTTON_WIDTH).max(x),
+                0.0,
+                OVERFLOW_BUTTON_WIDTH,
+                final_size.y,
+            ),
+        );
+
+        final_size
+    }
+
+    fn handle_routed_message(&mut self, ui: &mut UserInterface, message: &mut UiMessage) {
+        self.widget.handle_routed_message(ui, message);
+
+        if let Some(ButtonMessage::Click) = message.data() {
+            if message.destination() == self.overflow_button {
+                ui.send_message(PopupMessage::placement(
+                    self.overflow_popup,
+                    MessageDirection::ToWidget,
+                    Placement::RightBottom(self.overflow_button),
+                ));
+                ui.send_message(PopupMessage::open(
+                    self.overflow_popup,
+                    MessageDirection::ToWidget,
+                ));
+            }
+        } else if let Some(msg) = message.data::<ToolbarMessage>() {
+            if message.destination() == self.handle
+                && message.direction() == MessageDirection::ToWidget
+            {
+                match msg {
+                    ToolbarMessage::Items(items) => {
+                        for &item in self.items.iter() {
+                            ui.send_message(WidgetMessage::remove(
+                                item,
+                                MessageDirection::ToWidget,
+                            ));
+                        }
+                        self.items = items.clone();
+                        // Force the toolbar to re-distribute the new items.
+                        self.overflow_start.set(usize::MAX);
+                        self.move_items(ui, self.items.len());
+                        self.invalidate_layout();
+                    }
+                    ToolbarMessage::ToggleGroups(groups) => {
+                        self.toggle_groups = groups.clone();
+                    }
+                }
+            }
+        }
+    }
+
+    fn preview_message(&self, ui: &UserInterface, message: &mut UiMessage) {
+        // Items in the overflow menu are not the descendants of the toolbar, so their messages can be caught
+        // only here.
+        if let Some(CheckBoxMessage::Check(Some(true))) = message.data() {
+            if message.direction() == MessageDirection::FromWidget {
+                let destination = message.destination();
+                if let Some(group) = self
+                    .toggle_groups
+                    .iter()
+                    .find(|group| group.contains(&destination))
+                {
+                    for &other in group.iter().filter(|other| **other != destination) {
+                        ui.send_message(CheckBoxMessage::checked(
+                            other,
+                            MessageDirection::ToWidget,
+                            Some(false),
+                        ));
+                    }
+                }
+            }
+        } else if let Some(ButtonMessage::Click) = message.data() {
+            if message.destination() != self.overflow_button
+                && ui
+                    .node(self.overflow_panel)
+                    .has_descendant(message.destination(), ui)
+            {
+                ui.send_message(PopupMessage::close(
+                    self.overflow_popup,
+                    MessageDirection::ToWidget,
+                ));
+            }
+        }
+    }
+}
+
+/// Creates a thin vertical line, that could be used to separate groups of items of a [`Toolbar`].
+pub fn make_toolbar_separator(ctx: &mut BuildContext) -> Handle<UiNode> {
+    BorderBuilder::new(
+        WidgetBuilder::new()
+            .with_width(1.0)
+            .with_margin(Thickness::left_right(2.0))
+            .with_background_style(Style::BRUSH_LIGHT),
+    )
+    .with_stroke_thickness(Thickness::zero())
+    .build(ctx)
+}
+
+/// Toolbar builder creates [`Toolbar`] instances and adds them to the user interface.
+pub struct ToolbarBuilder {
+    widget_builder: WidgetBuilder,
+    items: Vec<Handle<UiNode>>,
+    toggle_groups: Vec<Vec<Handle<UiNode>>>,
+}
+
+impl ToolbarBuilder {
+    /// Creates new builder instance.
+    pub fn new(widget_builder: WidgetBuilder) -> Self {
+        Self {
+            widget_builder,
+            items: Default::default(),
+            toggle_groups: Default::default(),
+        }
+    }
+
+    /// Sets the desired items of the toolbar.
+    pub fn with_items(mut self, items: Vec<Handle<UiNode>>) -> Self {
+        self.items = items;
+        self
+    }
+
+    /// Adds a new toggle group. Toggle group is a set of check boxes, where only one check box can be checked at a
+    /// time - when a check box of the group is checked, every other check box of the group will be unchecked.
+    pub fn with_toggle_group(mut self, group: Vec<Handle<UiNode>>) -> Self {
+        self.toggle_groups.push(group);
+        self
+    }
+
+    /// Finishes toolbar building and adds it to the user interface.
+    pub fn build(self, ctx: &mut BuildContext) -> Handle<UiNode> {
+        let overflow_panel = StackPanelBuilder::new(WidgetBuilder::new()).build(ctx);
+
+        let overflow_popup = PopupBuilder::new(WidgetBuilder::new())
+            .with_content(overflow_panel)
+            .build(ctx);
+
+        let overflow_button = ButtonBuilder::new(
+            WidgetBuilder::new()
+                .with_visibility(false)
+                .with_width(OVERFLOW_BUTTON_WIDTH),
+        )
+        .with_content(make_arrow(ctx, ArrowDirection::Bottom, 6.0))
+        .build(ctx);
+
+        let toolbar = Toolbar {
+            widget: self
+                .widget_builder
+                .with_preview_messages(true)
+                .with_children(self.items.iter().cloned())
+                .with_child(overflow_button)
+                .build(),
+            overflow_start: Cell::new(self.items.len()),
+            items: self.items,
+            toggle_groups: self.toggle_groups,
+            overflow_button,
+            overflow_popup,
+            overflow_panel,
+        };
+
+        ctx.add_node(UiNode::new(toolbar))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        button::ButtonBuilder,
+        check_box::{CheckBox, CheckBoxBuilder, CheckBoxMessage},
+        core::algebra::Vector2,
+        message::MessageDirection,
+        toolbar::{Toolbar, ToolbarBuilder},
+        widget::WidgetBuilder,
+        UserInterface,
+    };
+
+    #[test]
+    fn toolbar_overflow() {
+        let screen_size = Vector2::new(100.0, 100.0);
+        let mut ui = UserInterface::new(screen_size);
+
+        let items = (0..4)
+            .map(|_| {
+                ButtonBuilder::new(WidgetBuilder::new().with_width(30.0)).build(&mut ui.build_ctx())
+            })
+            .collect::<Vec<_>>();
+        let toolbar = ToolbarBuilder::new(WidgetBuilder::new().with_width(80.0))
+            .with_items(items.clone())
+            .build(&mut ui.build_ctx());
+
+        ui.update(screen_size, 0.0);
+        while ui.poll_message().is_some() {}
+
+        let toolbar_ref = ui.node(toolbar).query_component::<Toolbar>().unwrap();
+        // Two items and the overflow button fit into the toolbar.
+        assert_eq!(toolbar_ref.overflow_start.get(), 2);
+        let overflow_panel = toolbar_ref.overflow_panel;
+        assert_eq!(ui.node(overflow_panel).children(), &items[2..]);
+        assert_eq!(ui.node(items[0]).parent(), toolbar);
+    }
+
+    #[test]
+    fn toolbar_toggle_group() {
+        let screen_size = Vector2::new(100.0, 100.0);
+        let mut ui = UserInterface::new(screen_size);
+
+        let a = CheckBoxBuilder::new(WidgetBuilder::new())
+            .checked(Some(true))
+            .build(&mut ui.build_ctx());
+        let b = CheckBoxBuilder::new(WidgetBuilder::new())
+            .checked(Some(false))
+            .build(&mut ui.build_ctx());
+        ToolbarBuilder::new(WidgetBuilder::new().with_width(100.0))
+            .with_items(vec![a, b])
+            .with_toggle_group(vec![a, b])
+            .build(&mut ui.build_ctx());
+
+        ui.send_message(CheckBoxMessage::checked(
+            b,
+            MessageDirection::ToWidget,
+            Some(true),
+        ));
+        while ui.poll_message().is_some() {}
+
+        let checked =
+            |ui: &UserInterface, h| ui.node(h).query_component::<CheckBox>().unwrap().checked;
+        assert_eq!(checked(&ui, a), Some(false));
+        assert_eq!(checked(&ui, b), Some(true));
+    }
+}
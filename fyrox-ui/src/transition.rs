@@ -0,0 +1,352 @@
+//! Transitions are used to smoothly change (tween) properties of widgets over time, which could be used for
+//! fade-ins, sliding panels, etc. without any per-frame user code. See [`TransitionMessage`] docs for more info.
+
+#![warn(missing_docs)]
+
+use crate::{
+    brush::Brush,
+    core::{algebra::Vector2, color::Color, pool::Handle},
+    define_constructor,
+    message::{MessageDirection, UiMessage},
+    UiNode,
+};
+use std::mem;
+
+/// Easing function defines how a value changes over time during a transition.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub enum Easing {
+    /// Constant speed from the start to the end.
+    #[default]
+    Linear,
+    /// Slow start, fast end (quadratic).
+    QuadIn,
+    /// Fast start, slow end (quadratic).
+    QuadOut,
+    /// Slow start and end, fast middle (quadratic).
+    QuadInOut,
+    /// Slow start, fast end (cubic).
+    CubicIn,
+    /// Fast start, slow end (cubic).
+    CubicOut,
+    /// Slow start and end, fast middle (cubic).
+    CubicInOut,
+}
+
+impl Easing {
+    /// Maps normalized time `t` (in `[0; 1]` range) to normalized progress of a transition.
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::QuadIn => t * t,
+            Easing::QuadOut => 1.0 - (1.0 - t) * (1.0 - t),
+            Easing::QuadInOut => {
+                if t < 0.5 {
+                    2.0 * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(2) * 0.5
+                }
+            }
+            Easing::CubicIn => t * t * t,
+            Easing::CubicOut => 1.0 - (1.0 - t).powi(3),
+            Easing::CubicInOut => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(3) * 0.5
+                }
+            }
+        }
+    }
+}
+
+/// A property of a widget, that could be animated, together with its final value.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum TransitionProperty {
+    /// Opacity of the widget.
+    Opacity(f32),
+    /// Desired position of the widget (see [`crate::widget::WidgetMessage::DesiredPosition`]).
+    DesiredPosition(Vector2<f32>),
+    /// Width of the widget.
+    Width(f32),
+    /// Height of the widget.
+    Height(f32),
+    /// Background of the widget. Only solid brushes are animated smoothly, any other brush is replaced with
+    /// the final color immediately.
+    Background(Color),
+    /// Foreground of the widget. Only solid brushes are animated smoothly, any other brush is replaced with
+    /// the final color immediately.
+    Foreground(Color),
+}
+
+impl TransitionProperty {
+    fn is_same_kind(&self, other: &Self) -> bool {
+        mem::discriminant(self) == mem::discriminant(other)
+    }
+
+    /// Fetches current value of the same property from the given widget.
+    fn current(&self, node: &UiNode) -> Self {
+        fn solid_color(brush: Brush, default: Color) -> Color {
+            match brush {
+                Brush::Solid(color) => color,
+                _ => default,
+            }
+        }
+
+        match *self {
+            TransitionProperty::Opacity(_) => {
+                TransitionProperty::Opacity(node.opacity().unwrap_or(1.0))
+            }
+            TransitionProperty::DesiredPosition(_) => {
+                TransitionProperty::DesiredPosition(node.desired_local_position())
+            }
+            TransitionProperty::Width(_) => TransitionProperty::Width(if node.width().is_nan() {
+                node.actual_local_size().x
+            } else {
+                node.width()
+            }),
+            TransitionProperty::Height(_) => {
+                TransitionProperty::Height(if node.height().is_nan() {
+                    node.actual_local_size().y
+                } else {
+                    node.height()
+                })
+            }
+            TransitionProperty::Background(color) => {
+                TransitionProperty::Background(solid_color(node.background(), color))
+            }
+            TransitionProperty::Foreground(color) => {
+                TransitionProperty::Foreground(solid_color(node.foreground(), color))
+            }
+        }
+    }
+
+    /// Interpolates between `self` and `other` (must be of the same kind) and applies the value to the widget.
+    fn apply(&self, other: &Self, t: f32, node: &mut UiNode) {
+        let lerp = |a: f32, b: f32| a + (b - a) * t;
+        match (*self, *other) {
+            (TransitionProperty::Opacity(a), TransitionProperty::Opacity(b)) => {
+                node.set_opacity(Some(lerp(a, b)));
+            }
+            (TransitionProperty::DesiredPosition(a), TransitionProperty::DesiredPosition(b)) => {
+                node.set_desired_local_position(a.lerp(&b, t));
+                node.invalidate_layout();
+            }
+            (TransitionProperty::Width(a), TransitionProperty::Width(b)) => {
+                node.set_width(lerp(a, b));
+                node.invalidate_layout();
+            }
+            (TransitionProperty::Height(a), TransitionProperty::Height(b)) => {
+                node.set_height(lerp(a, b));
+                node.invalidate_layout();
+            }
+            (TransitionProperty::Background(a), TransitionProperty::Background(b)) => {
+                // Animated brush is no longer bound to the style.
+                node.background_style = None;
+                node.set_background(Brush::Solid(a.lerp(b, t)));
+            }
+            (TransitionProperty::Foreground(a), TransitionProperty::Foreground(b)) => {
+                node.foreground_style = None;
+                node.set_foreground(Brush::Solid(a.lerp(b, t)));
+            }
+            _ => (),
+        }
+    }
+}
+
+/// Transition describes how a property of a widget should change over time.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Transition {
+    /// A property to animate with its final value.
+    pub property: TransitionProperty,
+    /// Duration of the transition in seconds.
+    pub duration: f32,
+    /// Easing function of the transition.
+    pub easing: Easing,
+}
+
+impl Transition {
+    /// Creates new transition of the given property to its final value with linear easing.
+    pub fn new(property: TransitionProperty, duration: f32) -> Self {
+        Self {
+            property,
+            duration,
+            easing: Default::default(),
+        }
+    }
+
+    /// Sets the desired easing function of the transition.
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+}
+
+/// A set of messages, that is used to animate properties of any widget. These messages are handled by the
+/// user interface itself, so they work with every widget.
+///
+/// ## Examples
+///
+/// The following example fades in a widget and slides it to the top-left corner of its parent in half a second:
+///
+/// ```rust
+/// # use fyrox_ui::{
+/// #     core::{algebra::Vector2, pool::Handle},
+/// #     message::MessageDirection,
+/// #     transition::{Easing, Transition, TransitionMessage, TransitionProperty},
+/// #     UiNode, UserInterface,
+/// # };
+/// #
+/// fn show_panel(panel: Handle<UiNode>, ui: &UserInterface) {
+///     for property in [
+///         TransitionProperty::Opacity(1.0),
+///         TransitionProperty::DesiredPosition(Vector2::new(0.0, 0.0)),
+///     ] {
+///         ui.send_message(TransitionMessage::start(
+///             panel,
+///             MessageDirection::ToWidget,
+///             Transition::new(property, 0.5).with_easing(Easing::CubicOut),
+///         ));
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum TransitionMessage {
+    /// Starts a new transition of a property of a widget from its current value. A running transition of the
+    /// same property of the widget is replaced with the new one.
+    ///
+    /// Direction: **To UI**.
+    Start(Transition),
+    /// Stops all transitions of a widget, animated properties keep their current values.
+    ///
+    /// Direction: **To UI**.
+    Stop,
+    /// Emitted when a transition of a widget is finished. Could be used to chain transitions or to remove a
+    /// widget when it has faded out.
+    ///
+    /// Direction: **From UI**.
+    Finished(Transition),
+}
+
+impl TransitionMessage {
+    define_constructor!(
+        /// Creates [`TransitionMessage::Start`] message.
+        TransitionMessage:Start => fn start(Transition), layout: false
+    );
+    define_constructor!(
+        /// Creates [`TransitionMessage::Stop`] message.
+        TransitionMessage:Stop => fn stop(), layout: false
+    );
+    define_constructor!(
+        /// Creates [`TransitionMessage::Finished`] message.
+        TransitionMessage:Finished => fn finished(Transition), layout: false
+    );
+}
+
+/// A transition, that is currently running on a widget.
+#[derive(Clone, Debug)]
+pub(crate) struct ActiveTransition {
+    pub node: Handle<UiNode>,
+    pub transition: Transition,
+    from: TransitionProperty,
+    elapsed: f32,
+}
+
+impl ActiveTransition {
+    pub fn new(node: Handle<UiNode>, node_ref: &UiNode, transition: Transition) -> Self {
+        Self {
+            node,
+            from: transition.property.current(node_ref),
+            transition,
+            elapsed: 0.0,
+        }
+    }
+
+    pub fn is_same_property(&self, node: Handle<UiNode>, transition: &Transition) -> bool {
+        self.node == node && self.transition.property.is_same_kind(&transition.property)
+    }
+
+    /// Advances the transition and applies new value of the property to the widget. Returns `true` if the
+    /// transition is finished.
+    pub fn update(&mut self, dt: f32, node: &mut UiNode) -> bool {
+        self.elapsed += dt;
+        let t = if self.transition.duration > 0.0 {
+            (self.elapsed / self.transition.duration).min(1.0)
+        } else {
+            1.0
+        };
+        self.from.apply(
+            &self.transition.property,
+            self.transition.easing.apply(t),
+            node,
+        );
+        t >= 1.0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        border::BorderBuilder,
+        core::algebra::Vector2,
+        message::MessageDirection,
+        transition::{Easing, Transition, TransitionMessage, TransitionProperty},
+        widget::WidgetBuilder,
+        UserInterface,
+    };
+
+    #[test]
+    fn easing() {
+        for easing in [
+            Easing::Linear,
+            Easing::QuadIn,
+            Easing::QuadOut,
+            Easing::QuadInOut,
+            Easing::CubicIn,
+            Easing::CubicOut,
+            Easing::CubicInOut,
+        ] {
+            assert_eq!(easing.apply(0.0), 0.0);
+            assert_eq!(
+                easing.apply(0.5),
+                [0.5, 0.25, 0.75, 0.5, 0.125, 0.875, 0.5][easing as usize]
+            );
+            assert_eq!(easing.apply(1.0), 1.0);
+        }
+    }
+
+    #[test]
+    fn fade_in() {
+        let screen_size = Vector2::new(100.0, 100.0);
+        let mut ui = UserInterface::new(screen_size);
+        let widget = BorderBuilder::new(WidgetBuilder::new().with_opacity(Some(0.0)))
+            .build(&mut ui.build_ctx());
+
+        let transition = Transition::new(TransitionProperty::Opacity(1.0), 1.0);
+        ui.send_message(TransitionMessage::start(
+            widget,
+            MessageDirection::ToWidget,
+            transition,
+        ));
+        while ui.poll_message().is_some() {}
+
+        ui.update(screen_size, 0.5);
+        assert_eq!(ui.node(widget).opacity(), Some(0.5));
+        assert!(ui.poll_message().is_none());
+
+        ui.update(screen_size, 0.6);
+        assert_eq!(ui.node(widget).opacity(), Some(1.0));
+        assert_eq!(
+            ui.poll_message(),
+            Some(TransitionMessage::finished(
+                widget,
+                MessageDirection::FromWidget,
+                transition
+            ))
+        );
+
+        // The transition must not be applied anymore.
+        ui.update(screen_size, 0.5);
+        assert!(ui.poll_message().is_none());
+    }
+}
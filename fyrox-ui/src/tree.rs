@@ -4,7 +4,7 @@
 #![warn(missing_docs)]
 
 use crate::{
-    border::BorderBuilder,
+    border::{BorderBuilder, BorderMessage},
     brush::Brush,
     check_box::{CheckBoxBuilder, CheckBoxMessage},
     core::{algebra::Vector2, color::Color, pool::Handle},
@@ -14,10 +14,11 @@ use crate::{
     grid::{Column, GridBuilder, Row},
     message::{MessageDirection, UiMessage},
     stack_panel::StackPanelBuilder,
+    style::Style,
     utils::{make_arrow, ArrowDirection},
     widget::{Widget, WidgetBuilder, WidgetMessage},
     BuildContext, Control, MouseButton, NodeHandleMapping, Thickness, UiNode, UserInterface,
-    VerticalAlignment, BRUSH_DARK, BRUSH_DARKEST,
+    VerticalAlignment,
 };
 use std::{
     any::{Any, TypeId},
@@ -39,6 +40,17 @@ pub enum TreeExpansionStrategy {
     RecursiveAncestors,
 }
 
+/// Defines where a dragged item should be placed relative to a [`Tree`] it was dropped on.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum TreeDropPosition {
+    /// The item should be placed right before the tree (as its previous sibling).
+    Before,
+    /// The item should be placed inside the tree (as its child item).
+    Inside,
+    /// The item should be placed right after the tree (as its next sibling).
+    After,
+}
+
 /// A set of messages, that could be used to alternate the state of a [`Tree`] widget.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum TreeMessage {
@@ -59,6 +71,15 @@ pub enum TreeMessage {
     SetExpanderShown(bool),
     /// A message, that is use to specify a new set of children items of a tree.
     SetItems(Vec<Handle<UiNode>>),
+    /// A message, that is emitted by a tree (with [`MessageDirection::FromWidget`] direction) when
+    /// some widget was dragged and dropped on it. The tree, that accepted the drop, is the destination
+    /// of the message.
+    Drop {
+        /// A handle of the widget, that was dropped on the tree.
+        what: Handle<UiNode>,
+        /// Where the dropped widget should be placed relative to the tree.
+        position: TreeDropPosition,
+    },
     // Private, do not use. For internal needs only. Use TreeRootMessage::Selected.
     #[doc(hidden)]
     Select(SelectionState),
@@ -85,6 +106,10 @@ impl TreeMessage {
         /// Creates [`TreeMessage::SetItems`] message.
         TreeMessage:SetItems => fn set_items(Vec<Handle<UiNode >>), layout: false
     );
+    define_constructor!(
+        /// Creates [`TreeMessage::Drop`] message.
+        TreeMessage:Drop => fn drop(what: Handle<UiNode>, position: TreeDropPosition), layout: false
+    );
     define_constructor!(
         /// Creates [`TreeMessage::Select`] message.
         TreeMessage:Select => fn select(SelectionState), layout: false
@@ -199,6 +224,14 @@ impl TreeRootMessage {
 /// `Ctrl+Click` - enables multi-selection.
 /// `Alt+Click` - prevents selection allowing you to use drag'n'drop.
 /// `Shift+Click` - selects a span of items.
+///
+/// ## Drag and drop
+///
+/// Any widget could be dragged over a tree item. While dragging, the tree shows an indicator of where
+/// the dragged widget will be placed - before the item (upper part of the item), after the item (lower
+/// part of the item) or inside it (the rest). When the widget is dropped, the tree emits
+/// [`TreeMessage::Drop`] message with the dragged widget and the [`TreeDropPosition`]. It is up to
+/// you to move the actual data (and the tree items) in response to this message.
 #[derive(Debug, Clone, Visit, Reflect)]
 pub struct Tree {
     /// Base widget of the tree.
@@ -220,6 +253,12 @@ pub struct Tree {
     /// A flag, that defines whether the tree should always show its expander, even if there's no
     /// children elements, or not.
     pub always_show_expander: bool,
+    /// Current drop indicator of the tree, it is shown when some widget is dragged over the tree.
+    #[visit(optional)]
+    pub drop_indicator: Handle<UiNode>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    drop_position: Option<TreeDropPosition>,
 }
 
 crate::define_widget_deref!(Tree);
@@ -238,6 +277,7 @@ impl Control for Tree {
         node_map.resolve(&mut self.expander);
         node_map.resolve(&mut self.panel);
         node_map.resolve(&mut self.background);
+        node_map.resolve(&mut self.drop_indicator);
     }
 
     fn arrange_override(&self, ui: &UserInterface, final_size: Vector2<f32>) -> Vector2<f32> {
@@ -386,6 +426,24 @@ impl Control for Tree {
                             message.set_handled(true);
                         }
                     }
+                    &WidgetMessage::DragOver(dragged) if self.owns(ui, message.destination()) => {
+                        let position = self.calc_drop_position(ui, dragged);
+                        self.set_drop_position(ui, position);
+                    }
+                    WidgetMessage::MouseLeave if self.owns(ui, message.destination()) => {
+                        self.set_drop_position(ui, None);
+                    }
+                    &WidgetMessage::Drop(dropped) if message.destination() == self.handle => {
+                        if let Some(position) = self.drop_position {
+                            ui.send_message(TreeMessage::drop(
+                                self.handle,
+                                MessageDirection::FromWidget,
+                                dropped,
+                                position,
+                            ));
+                        }
+                        self.set_drop_position(ui, None);
+                    }
                     _ => (),
                 }
             }
@@ -481,6 +539,9 @@ impl Control for Tree {
                         }
                         self.items = items.clone();
                     }
+                    TreeMessage::Drop { .. } => {
+                        // Emitted by the tree itself, nothing to do here.
+                    }
                     &TreeMessage::Select(state) => {
                         if self.is_selected != state.0 {
                             self.is_selected = state.0;
@@ -507,6 +568,83 @@ impl Tree {
             ctx.link(item, panel);
         }
     }
+
+    /// Checks whether the given widget belongs to this tree's own item (header) and not to some of
+    /// its descendant trees.
+    fn owns(&self, ui: &UserInterface, mut handle: Handle<UiNode>) -> bool {
+        while handle.is_some() && handle != self.handle {
+            let node = ui.node(handle);
+            if node.cast::<Tree>().is_some() {
+                return false;
+            }
+            handle = node.parent();
+        }
+        handle == self.handle
+    }
+
+    fn calc_drop_position(
+        &self,
+        ui: &UserInterface,
+        dragged: Handle<UiNode>,
+    ) -> Option<TreeDropPosition> {
+        // Prevent dropping a tree on itself or on any of its descendants.
+        if dragged == self.handle {
+            return None;
+        }
+        let mut parent = self.parent();
+        while parent.is_some() {
+            if parent == dragged {
+                return None;
+            }
+            parent = ui.node(parent).parent();
+        }
+
+        let bounds = ui.node(self.background).screen_bounds();
+        let cursor = ui.cursor_position();
+        if !bounds.contains(cursor) {
+            return None;
+        }
+
+        let edge = bounds.h() * 0.25;
+        Some(if cursor.y < bounds.y() + edge {
+            TreeDropPosition::Before
+        } else if cursor.y > bounds.y() + bounds.h() - edge {
+            TreeDropPosition::After
+        } else {
+            TreeDropPosition::Inside
+        })
+    }
+
+    fn set_drop_position(&mut self, ui: &UserInterface, position: Option<TreeDropPosition>) {
+        if self.drop_position == position {
+            return;
+        }
+
+        self.drop_position = position;
+
+        ui.send_message(WidgetMessage::visibility(
+            self.drop_indicator,
+            MessageDirection::ToWidget,
+            position.is_some(),
+        ));
+
+        if let Some(position) = position {
+            ui.send_message(BorderMessage::stroke_thickness(
+                self.drop_indicator,
+                MessageDirection::ToWidget,
+                match position {
+                    TreeDropPosition::Before => Thickness::top(2.0),
+                    TreeDropPosition::Inside => Thickness::uniform(1.0),
+                    TreeDropPosition::After => Thickness::bottom(2.0),
+                },
+            ));
+        }
+    }
+
+    /// Returns current drop position, that is shown while some widget is dragged over the tree.
+    pub fn drop_position(&self) -> Option<TreeDropPosition> {
+        self.drop_position
+    }
 }
 
 /// Tree builder creates [`Tree`] widget instances and adds them to the user interface.
@@ -600,8 +738,8 @@ impl TreeBuilder {
                     .with_foreground(Brush::Solid(Color::TRANSPARENT))
                     .with_background(Brush::Solid(Color::TRANSPARENT)),
             ))
-            .with_selected_brush(BRUSH_DARKEST)
-            .with_hover_brush(BRUSH_DARK)
+            .with_selected_brush_style(Style::BRUSH_DARKEST)
+            .with_hover_brush_style(Style::BRUSH_DARK)
             .with_normal_brush(Brush::Solid(Color::TRANSPARENT))
             .with_pressed_brush(Brush::Solid(Color::TRANSPARENT))
             .with_pressable(false)
@@ -610,10 +748,22 @@ impl TreeBuilder {
 
         ctx.link(internals, item_background);
 
+        let drop_indicator = BorderBuilder::new(
+            WidgetBuilder::new()
+                .on_row(0)
+                .on_column(0)
+                .with_visibility(false)
+                .with_hit_test_visibility(false)
+                .with_foreground_style(Style::BRUSH_BRIGHT_BLUE)
+                .with_background(Brush::Solid(Color::TRANSPARENT)),
+        )
+        .build(ctx);
+
         let panel;
         let grid = GridBuilder::new(
             WidgetBuilder::new()
                 .with_child(item_background)
+                .with_child(drop_indicator)
                 .with_child({
                     panel = StackPanelBuilder::new(
                         WidgetBuilder::new()
@@ -647,6 +797,8 @@ impl TreeBuilder {
             items: self.items,
             is_selected: false,
             always_show_expander: self.always_show_expander,
+            drop_indicator,
+            drop_position: None,
         }
     }
 
@@ -870,3 +1022,112 @@ impl TreeRootBuilder {
         ctx.add_node(UiNode::new(tree))
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::algebra::Vector2,
+        message::{ButtonState, MessageDirection, OsEvent},
+        text::TextBuilder,
+        tree::{Tree, TreeBuilder, TreeDropPosition, TreeMessage, TreeRootBuilder},
+        widget::WidgetBuilder,
+        MouseButton, UiNode, UserInterface,
+    };
+    use fyrox_core::pool::Handle;
+
+    fn make_tree(ui: &mut UserInterface, text: &str) -> Handle<UiNode> {
+        let ctx = &mut ui.build_ctx();
+        let content = TextBuilder::new(WidgetBuilder::new())
+            .with_text(text)
+            .build(ctx);
+        TreeBuilder::new(WidgetBuilder::new())
+            .with_content(content)
+            .build(ctx)
+    }
+
+    fn header_point(ui: &UserInterface, tree: Handle<UiNode>, fraction: f32) -> Vector2<f32> {
+        let background = ui.node(tree).query_component::<Tree>().unwrap().background;
+        let bounds = ui.node(background).screen_bounds();
+        Vector2::new(bounds.x() + 20.0, bounds.y() + bounds.h() * fraction)
+    }
+
+    fn move_cursor(ui: &mut UserInterface, tree: Handle<UiNode>, fraction: f32) {
+        let position = header_point(ui, tree, fraction);
+        // Picking relies on drawing commands, so they must be up-to-date.
+        ui.draw();
+        ui.process_os_event(&OsEvent::CursorMoved { position });
+        poll_tree_messages(ui);
+    }
+
+    fn poll_tree_messages(ui: &mut UserInterface) -> Vec<TreeMessage> {
+        let mut messages = Vec::new();
+        while let Some(message) = ui.poll_message() {
+            if let Some(msg) = message.data::<TreeMessage>() {
+                if message.direction() == MessageDirection::FromWidget {
+                    messages.push(msg.clone());
+                }
+            }
+        }
+        messages
+    }
+
+    #[test]
+    fn tree_drag_and_drop() {
+        let screen_size = Vector2::new(200.0, 200.0);
+        let mut ui = UserInterface::new(screen_size);
+
+        let a = make_tree(&mut ui, "A");
+        let b = make_tree(&mut ui, "B");
+        TreeRootBuilder::new(WidgetBuilder::new().with_width(200.0))
+            .with_items(vec![a, b])
+            .build(&mut ui.build_ctx());
+
+        ui.update(screen_size, 0.0);
+        while ui.poll_message().is_some() {}
+
+        // Start dragging `a`.
+        move_cursor(&mut ui, a, 0.5);
+        ui.process_os_event(&OsEvent::MouseInput {
+            button: MouseButton::Left,
+            state: ButtonState::Pressed,
+        });
+        poll_tree_messages(&mut ui);
+
+        // Upper part of `b` - insert before.
+        move_cursor(&mut ui, b, 0.1);
+        let drop_position = |ui: &UserInterface, tree| {
+            ui.node(tree)
+                .query_component::<Tree>()
+                .unwrap()
+                .drop_position()
+        };
+        assert_eq!(drop_position(&ui, b), Some(TreeDropPosition::Before));
+
+        // Lower part of `b` - insert after.
+        move_cursor(&mut ui, b, 0.9);
+        assert_eq!(drop_position(&ui, b), Some(TreeDropPosition::After));
+
+        // Middle of `b` - insert inside.
+        move_cursor(&mut ui, b, 0.5);
+        assert_eq!(drop_position(&ui, b), Some(TreeDropPosition::Inside));
+
+        // A tree cannot be dropped on itself.
+        move_cursor(&mut ui, a, 0.5);
+        assert_eq!(drop_position(&ui, a), None);
+        assert_eq!(drop_position(&ui, b), None);
+
+        move_cursor(&mut ui, b, 0.5);
+        ui.process_os_event(&OsEvent::MouseInput {
+            button: MouseButton::Left,
+            state: ButtonState::Released,
+        });
+        assert_eq!(
+            poll_tree_messages(&mut ui),
+            vec![TreeMessage::Drop {
+                what: a,
+                position: TreeDropPosition::Inside
+            }]
+        );
+        assert_eq!(drop_position(&ui, b), None);
+    }
+}
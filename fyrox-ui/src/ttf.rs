@@ -12,6 +12,8 @@ use std::{
     sync::Arc,
 };
 
+/// A glyph of a font. Its metrics (`top`, `left`, `advance`) are in logical units (i.e. they do not depend on
+/// the [render scale](Font::render_scale) of the font), while its bitmap size is in pixels of the atlas.
 #[derive(Debug)]
 pub struct FontGlyph {
     pub top: f32,
@@ -32,6 +34,9 @@ pub struct Font {
     pub atlas: Vec<u8>,
     pub atlas_size: usize,
     pub texture: Option<SharedTexture>,
+    // Source font face, it is used to rasterize the glyphs again when the render scale changes.
+    face: Option<fontdue::Font>,
+    render_scale: f32,
 }
 
 #[derive(Debug, Clone)]
@@ -162,23 +167,16 @@ impl Font {
             atlas: Vec::new(),
             atlas_size: 0,
             texture: None,
+            face: None,
+            render_scale: 1.0,
         };
 
         let mut index = 0;
         for range in char_set {
             for unicode in range.start..range.end {
                 if let Some(character) = std::char::from_u32(unicode) {
-                    let (metrics, bitmap) = fontdue_font.rasterize(character, height);
-
-                    font.glyphs.push(FontGlyph {
-                        left: metrics.xmin as f32,
-                        top: metrics.ymin as f32,
-                        pixels: bitmap,
-                        advance: metrics.advance_width,
-                        tex_coords: Default::default(),
-                        bitmap_width: metrics.width,
-                        bitmap_height: metrics.height,
-                    });
+                    font.glyphs
+                        .push(rasterize_glyph(&fontdue_font, character, height, 1.0));
 
                     font.char_map.insert(unicode, index);
                     index += 1;
@@ -186,6 +184,8 @@ impl Font {
             }
         }
 
+        font.face = Some(fontdue_font);
+
         font.pack();
 
         Ok(font)
@@ -226,6 +226,39 @@ impl Font {
         self.height
     }
 
+    /// Returns current render scale of the font. See [`Self::set_render_scale`] for more info.
+    #[inline]
+    pub fn render_scale(&self) -> f32 {
+        self.render_scale
+    }
+
+    /// Rasterizes every glyph of the font at `height * scale` pixels, so the text stays crisp when it is
+    /// drawn with the given scale (for example, when the user interface is scaled, see
+    /// [`crate::UserInterface::set_scale`]). The metrics of the font stay in logical units, so the layout
+    /// of the text does not depend on the render scale. The atlas texture is re-created on next draw.
+    /// Values less or equal to zero are ignored.
+    pub fn set_render_scale(&mut self, scale: f32) {
+        if scale <= 0.0 || self.render_scale == scale {
+            return;
+        }
+
+        let Some(face) = self.face.as_ref() else {
+            return;
+        };
+
+        for (&unicode, &index) in self.char_map.iter() {
+            if let (Some(character), Some(glyph)) =
+                (std::char::from_u32(unicode), self.glyphs.get_mut(index))
+            {
+                *glyph = rasterize_glyph(face, character, self.height, scale);
+            }
+        }
+
+        self.render_scale = scale;
+        self.texture = None;
+        self.pack();
+    }
+
     #[inline]
     pub fn ascender(&self) -> f32 {
         self.ascender
@@ -318,6 +351,21 @@ impl Font {
     }
 }
 
+fn rasterize_glyph(face: &fontdue::Font, character: char, height: f32, scale: f32) -> FontGlyph {
+    let (metrics, bitmap) = face.rasterize(character, height * scale);
+
+    FontGlyph {
+        left: metrics.xmin as f32 / scale,
+        top: metrics.ymin as f32 / scale,
+        pixels: bitmap,
+        // Advance is taken at the logical height, so the layout of text stays the same at any scale.
+        advance: face.metrics(character, height).advance_width,
+        tex_coords: Default::default(),
+        bitmap_width: metrics.width,
+        bitmap_height: metrics.height,
+    }
+}
+
 /// Font builder allows you to load fonts in declarative manner.
 pub struct FontBuilder<'a> {
     height: Option<f32>,
@@ -2,11 +2,11 @@ use crate::{
     border::BorderBuilder,
     core::{algebra::Vector2, color::Color, pool::Handle},
     formatted_text::WrapMode,
+    style::Style,
     text::TextBuilder,
     vector_image::{Primitive, VectorImageBuilder},
     widget::WidgetBuilder,
     Brush, BuildContext, HorizontalAlignment, RcUiNodeHandle, Thickness, UiNode, VerticalAlignment,
-    BRUSH_BRIGHT, BRUSH_DARKER, BRUSH_DARKEST,
 };
 
 pub enum ArrowDirection {
@@ -56,7 +56,7 @@ pub fn make_arrow(
 ) -> Handle<UiNode> {
     VectorImageBuilder::new(
         WidgetBuilder::new()
-            .with_foreground(BRUSH_BRIGHT)
+            .with_foreground_style(Style::BRUSH_BRIGHT)
             .with_horizontal_alignment(HorizontalAlignment::Center)
             .with_vertical_alignment(VerticalAlignment::Center),
     )
@@ -69,7 +69,7 @@ pub fn make_cross(ctx: &mut BuildContext, size: f32, thickness: f32) -> Handle<U
         WidgetBuilder::new()
             .with_horizontal_alignment(HorizontalAlignment::Center)
             .with_vertical_alignment(VerticalAlignment::Center)
-            .with_foreground(BRUSH_BRIGHT),
+            .with_foreground_style(Style::BRUSH_BRIGHT),
     )
     .with_primitives(vec![
         Primitive::Line {
@@ -90,14 +90,14 @@ pub fn make_simple_tooltip(ctx: &mut BuildContext, text: &str) -> RcUiNodeHandle
     let handle = BorderBuilder::new(
         WidgetBuilder::new()
             .with_visibility(false)
-            .with_foreground(BRUSH_DARKEST)
+            .with_foreground_style(Style::BRUSH_DARKEST)
             .with_background(Brush::Solid(Color::opaque(230, 230, 230)))
             .with_max_size(Vector2::new(300.0, f32::INFINITY))
             .with_child(
                 TextBuilder::new(
                     WidgetBuilder::new()
                         .with_margin(Thickness::uniform(2.0))
-                        .with_foreground(BRUSH_DARKER),
+                        .with_foreground_style(Style::BRUSH_DARKER),
                 )
                 .with_wrap(WrapMode::Word)
                 .with_text(text)
@@ -0,0 +1,583 @@
+//! Virtual list view is a list view, that creates widgets only for visible items and re-uses them while scrolling. It
+//! is used to show lists with huge amount of items. See [`VirtualListView`] docs for more info and usage examples.
+
+#![warn(missing_docs)]
+
+use crate::{
+    border::BorderBuilder,
+    core::{algebra::Vector2, math::Rect, pool::Handle},
+    core::{reflect::prelude::*, visitor::prelude::*},
+    decorator::{DecoratorBuilder, DecoratorMessage},
+    define_constructor,
+    message::{MessageDirection, UiMessage},
+    scroll_bar::{ScrollBarBuilder, ScrollBarMessage},
+    widget::{Widget, WidgetBuilder, WidgetMessage},
+    BuildContext, Control, NodeHandleMapping, Orientation, UiNode, UserInterface,
+};
+use std::{
+    any::{Any, TypeId},
+    cell::Cell,
+    fmt::Debug,
+    ops::{Deref, DerefMut},
+    rc::Rc,
+};
+
+/// Width of the vertical scroll bar in pixels.
+const SCROLL_BAR_WIDTH: f32 = 16.0;
+
+/// Items source provides data for a [`VirtualListView`]. The list view never asks for all items at once, instead it
+/// creates a few "blank" item widgets and then binds them to the actual items while scrolling.
+pub trait ItemsSource: Debug {
+    /// Returns total amount of items.
+    fn count(&self) -> usize;
+
+    /// Creates new "blank" item widget. The same widget will be used to show different items.
+    fn make_item(&self, ctx: &mut BuildContext) -> Handle<UiNode>;
+
+    /// Fills the given item widget with the data of the item at the given index. Typically it is done by sending
+    /// messages to the item widget (or its descendants).
+    fn bind_item(&self, index: usize, item: Handle<UiNode>, ui: &UserInterface);
+}
+
+/// A set of messages, that can be used to modify/fetch the state of a [`VirtualListView`] widget instance.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VirtualListViewMessage {
+    /// Forces the list view to re-fetch the amount of items and to re-bind every visible item. It must be sent
+    /// every time when the data of the items source has changed.
+    Refresh,
+    /// Used to set new selection (with [`MessageDirection::ToWidget`] direction). Also emitted by the widget (with
+    /// [`MessageDirection::FromWidget`]) when the selection has changed.
+    SelectionChanged(Option<usize>),
+    /// Scrolls the list view, so the item with the given index will be visible.
+    BringItemIntoView(usize),
+}
+
+impl VirtualListViewMessage {
+    define_constructor!(
+        /// Creates [`VirtualListViewMessage::Refresh`] message.
+        VirtualListViewMessage:Refresh => fn refresh(), layout: false
+    );
+    define_constructor!(
+        /// Creates [`VirtualListViewMessage::SelectionChanged`] message.
+        VirtualListViewMessage:SelectionChanged => fn selection(Option<usize>), layout: false
+    );
+    define_constructor!(
+        /// Creates [`VirtualListViewMessage::BringItemIntoView`] message.
+        VirtualListViewMessage:BringItemIntoView => fn bring_item_into_view(usize), layout: false
+    );
+}
+
+/// Virtual list view is a list view for very large amounts of items (tens of thousands and more), for example a list of
+/// assets or a log. Unlike [`crate::list_view::ListView`], it does not create a widget per item. Instead, it creates
+/// widgets only for visible rows and re-uses them while scrolling. The data is provided by an [`ItemsSource`], all items
+/// must have the same height.
+///
+/// ## Example
+///
+/// ```rust
+/// # use fyrox_ui::{
+/// #     core::pool::Handle,
+/// #     message::MessageDirection,
+/// #     text::{TextBuilder, TextMessage},
+/// #     virtual_list_view::{ItemsSource, VirtualListViewBuilder},
+/// #     widget::WidgetBuilder,
+/// #     BuildContext, UiNode, UserInterface,
+/// # };
+/// # use std::rc::Rc;
+/// #[derive(Debug)]
+/// struct Log {
+///     lines: Vec<String>,
+/// }
+///
+/// impl ItemsSource for Log {
+///     fn count(&self) -> usize {
+///         self.lines.len()
+///     }
+///
+///     fn make_item(&self, ctx: &mut BuildContext) -> Handle<UiNode> {
+///         TextBuilder::new(WidgetBuilder::new()).build(ctx)
+///     }
+///
+///     fn bind_item(&self, index: usize, item: Handle<UiNode>, ui: &UserInterface) {
+///         ui.send_message(TextMessage::text(
+///             item,
+///             MessageDirection::ToWidget,
+///             self.lines[index].clone(),
+///         ));
+///     }
+/// }
+///
+/// fn create_log_view(ctx: &mut BuildContext) -> Handle<UiNode> {
+///     let log = Log {
+///         lines: (0..100_000).map(|i| format!("Line {}", i)).collect(),
+///     };
+///
+///     VirtualListViewBuilder::new(WidgetBuilder::new())
+///         .with_items_source(Rc::new(log))
+///         .with_item_height(18.0)
+///         .build(ctx)
+/// }
+/// ```
+///
+/// Keep in mind, that the list view does not track changes of the data. Send [`VirtualListViewMessage::Refresh`]
+/// message to the list view every time when the data has changed.
+#[derive(Clone, Visit, Reflect, Debug)]
+pub struct VirtualListView {
+    /// Base widget of the list view.
+    pub widget: Widget,
+    /// Items source of the list view.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub items_source: Option<Rc<dyn ItemsSource>>,
+    /// Height of every item.
+    pub item_height: f32,
+    /// Current selection.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub selected_index: Option<usize>,
+    /// Containers of the items, they are re-used while scrolling.
+    pub rows: Vec<Handle<UiNode>>,
+    /// Item widgets (created by the items source) of the containers.
+    pub row_items: Vec<Handle<UiNode>>,
+    /// Indices of the items, that are currently shown by the rows.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub bound_indices: Vec<Option<usize>>,
+    /// Vertical scroll bar of the list view.
+    pub scroll_bar: Handle<UiNode>,
+    /// Current vertical scroll offset.
+    pub offset: f32,
+    /// Amount of rows, that is needed to fill the list view.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub required_rows: Cell<usize>,
+    /// Height of the area, that is used to show the items.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub viewport_height: Cell<f32>,
+}
+
+crate::define_widget_deref!(VirtualListView);
+
+impl VirtualListView {
+    fn count(&self) -> usize {
+        self.items_source
+            .as_ref()
+            .map(|source| source.count())
+            .unwrap_or_default()
+    }
+
+    fn first_visible_index(&self) -> usize {
+        (self.offset / self.item_height).floor().max(0.0) as usize
+    }
+
+    /// Returns an index of the item, that is shown by the given row.
+    pub fn row_index(&self, row: Handle<UiNode>) -> Option<usize> {
+        self.rows
+            .iter()
+            .position(|r| *r == row)
+            .and_then(|i| self.bound_indices[i])
+    }
+
+    fn sync_rows(&mut self, ui: &UserInterface) {
+        let count = self.count();
+        let row_count = self.rows.len();
+        if row_count == 0 {
+            return;
+        }
+        let first = self.first_visible_index();
+        for index in first..first + row_count {
+            // Every item maps to a fixed row (index modulo row count), this way scrolling by a single item
+            // re-binds only one row.
+            let slot = index % row_count;
+            let new_index = if index < count { Some(index) } else { None };
+            let bound_index = &mut self.bound_indices[slot];
+            if *bound_index != new_index {
+                if bound_index.is_some() != new_index.is_some() {
+                    ui.send_message(WidgetMessage::visibility(
+                        self.rows[slot],
+                        MessageDirection::ToWidget,
+                        new_index.is_some(),
+                    ));
+                }
+                if let (Some(index), Some(source)) = (new_index, self.items_source.as_ref()) {
+                    source.bind_item(index, self.row_items[slot], ui);
+                }
+                *bound_index = new_index;
+            }
+        }
+        self.sync_selection(ui);
+        self.invalidate_arrange();
+    }
+
+    fn sync_selection(&self, ui: &UserInterface) {
+        for (row, bound_index) in self.rows.iter().zip(self.bound_indices.iter()) {
+            ui.send_message(DecoratorMessage::select(
+                *row,
+                MessageDirection::ToWidget,
+                bound_index.is_some() && *bound_index == self.selected_index,
+            ));
+        }
+    }
+
+    fn max_offset(&self) -> f32 {
+        (self.count() as f32 * self.item_height - self.viewport_height.get()).max(0.0)
+    }
+}
+
+impl Control for VirtualListView {
+    fn query_component(&self, type_id: TypeId) -> Option<&dyn Any> {
+        if type_id == TypeId::of::<Self>() {
+            Some(self)
+        } else {
+            None
+        }
+    }
+
+    fn resolve(&mut self, node_map: &NodeHandleMapping) {
+        node_map.resolve_slice(&mut self.rows);
+        node_map.resolve_slice(&mut self.row_items);
+        node_map.resolve(&mut self.scroll_bar);
+    }
+
+    fn measure_override(&self, ui: &UserInterface, available_size: Vector2<f32>) -> Vector2<f32> {
+        ui.measure_node(
+            self.scroll_bar,
+            Vector2::new(SCROLL_BAR_WIDTH, available_size.y),
+        );
+
+        let row_constraint = Vector2::new(
+            (available_size.x - SCROLL_BAR_WIDTH).max(0.0),
+            self.item_height,
+        );
+        let mut desired_size = Vector2::new(0.0, self.count() as f32 * self.item_height);
+        for &row in self.rows.iter() {
+            ui.measure_node(row, row_constraint);
+            desired_size.x = desired_size.x.max(ui.node(row).desired_size().x);
+        }
+        desired_size.x += SCROLL_BAR_WIDTH;
+        desired_size.y = desired_size.y.min(available_size.y);
+
+        desired_size
+    }
+
+    fn arrange_override(&self, ui: &UserInterface, final_size: Vector2<f32>) -> Vector2<f32> {
+        let required_rows = (final_size.y / self.item_height).ceil() as usize + 1;
+        if required_rows > self.rows.len() && required_rows != self.required_rows.get() {
+            // Rows cannot be created during layout, so ask the list view to do that.
+            self.required_rows.set(required_rows);
+            ui.send_message(VirtualListViewMessage::refresh(
+                self.handle,
+                MessageDirection::ToWidget,
+            ));
+        }
+
+        if self.viewport_height.get() != final_size.y {
+            self.viewport_height.set(final_size.y);
+            ui.send_message(ScrollBarMessage::max_value(
+                self.scroll_bar,
+                MessageDirection::ToWidget,
+                self.max_offset(),
+            ));
+        }
+
+        let row_width = (final_size.x - SCROLL_BAR_WIDTH).max(0.0);
+        for (&row, bound_index) in self.rows.iter().zip(self.bound_indices.iter()) {
+            if let Some(index) = bound_index {
+                let y = *index as f32 * self.item_height - self.offset;
+                ui.arrange_node(row, &Rect::new(0.0, y, row_width, self.item_height));
+            }
+        }
+
+        ui.arrange_node(
+            self.scroll_bar,
+            &Rect::new(row_width, 0.0, SCROLL_BAR_WIDTH, final_size.y),
+        );
+
+        final_size
+    }
+
+    fn handle_routed_message(&mut self, ui: &mut UserInterface, message: &mut UiMessage) {
+        self.widget.handle_routed_message(ui, message);
+
+        if let Some(msg) = message.data::<WidgetMessage>() {
+            match msg {
+                WidgetMessage::MouseWheel { amount, .. } if !message.handled() => {
+                    ui.send_message(ScrollBarMessage::value(
+                        self.scroll_bar,
+                        MessageDirection::ToWidget,
+                        self.offset - amount * self.item_height,
+                    ));
+                    message.set_handled(true);
+                }
+                WidgetMessage::MouseDown { .. } if !message.handled() => {
+                    let destination = message.destination();
+                    if let Some(index) = self
+                        .rows
+                        .iter()
+                        .find(|row| {
+                            **row == destination || ui.node(**row).has_descendant(destination, ui)
+                        })
+                        .and_then(|row| self.row_index(*row))
+                    {
+                        ui.send_message(VirtualListViewMessage::selection(
+                            self.handle,
+                            MessageDirection::ToWidget,
+                            Some(index),
+                        ));
+                    }
+                }
+                _ => (),
+            }
+        } else if let Some(ScrollBarMessage::Value(value)) = message.data() {
+            if message.destination() == self.scroll_bar
+                && message.direction() == MessageDirection::FromWidget
+            {
+                self.offset = *value;
+                self.sync_rows(ui);
+            }
+        } else if let Some(msg) = message.data::<VirtualListViewMessage>() {
+            if message.destination() == self.handle
+                && message.direction() == MessageDirection::ToWidget
+            {
+                match msg {
+                    VirtualListViewMessage::Refresh => {
+                        if let Some(source) = self.items_source.clone() {
+                            let first_new_row = self.rows.len();
+                            let ctx = &mut ui.build_ctx();
+                            while self.rows.len() < self.required_rows.get() {
+                                let item = source.make_item(ctx);
+                                let row = DecoratorBuilder::new(BorderBuilder::new(
+                                    WidgetBuilder::new().with_visibility(false).with_child(item),
+                                ))
+                                .build(ctx);
+                                self.rows.push(row);
+                                self.row_items.push(item);
+                            }
+                            for &row in &self.rows[first_new_row..] {
+                                ui.send_message(WidgetMessage::link(
+                                    row,
+                                    MessageDirection::ToWidget,
+                                    self.handle,
+                                ));
+                            }
+                        }
+                        // Row count might change, so every row must be re-bound.
+                        for (row, bound_index) in
+                            self.rows.iter().zip(self.bound_indices.iter_mut())
+                        {
+                            if bound_index.is_some() {
+                                ui.send_message(WidgetMessage::visibility(
+                                    *row,
+                                    MessageDirection::ToWidget,
+                                    false,
+                                ));
+                            }
+                        }
+                        self.bound_indices = vec![None; self.rows.len()];
+
+                        let max_offset = self.max_offset();
+                        self.offset = self.offset.min(max_offset);
+                        ui.send_message(ScrollBarMessage::max_value(
+                            self.scroll_bar,
+                            MessageDirection::ToWidget,
+                            max_offset,
+                        ));
+                        self.sync_rows(ui);
+
+                        if self.selected_index.is_some_and(|i| i >= self.count()) {
+                            ui.send_message(VirtualListViewMessage::selection(
+                                self.handle,
+                                MessageDirection::ToWidget,
+                                None,
+                            ));
+                        }
+                    }
+                    &VirtualListViewMessage::SelectionChanged(selection) => {
+                        if self.selected_index != selection {
+                            self.selected_index = selection;
+                            self.sync_selection(ui);
+                            ui.send_message(message.reverse());
+                        }
+                    }
+                    &VirtualListViewMessage::BringItemIntoView(index) => {
+                        let item_top = index as f32 * self.item_height;
+                        let item_bottom = item_top + self.item_height;
+                        let height = self.viewport_height.get();
+                        let new_offset = if item_top < self.offset {
+                            Some(item_top)
+                        } else if item_bottom > self.offset + height {
+                            Some(item_bottom - height)
+                        } else {
+                            None
+                        };
+                        if let Some(new_offset) = new_offset {
+                            ui.send_message(ScrollBarMessage::value(
+                                self.scroll_bar,
+                                MessageDirection::ToWidget,
+                                new_offset,
+                            ));
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Virtual list view builder creates [`VirtualListView`] instances and adds them to the user interface.
+pub struct VirtualListViewBuilder {
+    widget_builder: WidgetBuilder,
+    items_source: Option<Rc<dyn ItemsSource>>,
+    item_height: f32,
+}
+
+impl VirtualListViewBuilder {
+    /// Creates new builder instance.
+    pub fn new(widget_builder: WidgetBuilder) -> Self {
+        Self {
+            widget_builder,
+            items_source: None,
+            item_height: 20.0,
+        }
+    }
+
+    /// Sets the desired items source.
+    pub fn with_items_source(mut self, items_source: Rc<dyn ItemsSource>) -> Self {
+        self.items_source = Some(items_source);
+        self
+    }
+
+    /// Sets the desired height of every item.
+    pub fn with_item_height(mut self, item_height: f32) -> Self {
+        self.item_height = item_height.max(1.0);
+        self
+    }
+
+    /// Finishes list view building and adds it to the user interface.
+    pub fn build(self, ctx: &mut BuildContext) -> Handle<UiNode> {
+        let scroll_bar = ScrollBarBuilder::new(WidgetBuilder::new())
+            .with_orientation(Orientation::Vertical)
+            .with_step(self.item_height)
+            .with_max(0.0)
+            .build(ctx);
+
+        let list_view = VirtualListView {
+            widget: self
+                .widget_builder
+                .with_clip_to_bounds(true)
+                .with_child(scroll_bar)
+                .build(),
+            items_source: self.items_source,
+            item_height: self.item_height,
+            selected_index: None,
+            rows: Default::default(),
+            row_items: Default::default(),
+            bound_indices: Default::default(),
+            scroll_bar,
+            offset: 0.0,
+            required_rows: Default::default(),
+            viewport_height: Default::default(),
+        };
+
+        ctx.add_node(UiNode::new(list_view))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::{algebra::Vector2, pool::Handle},
+        message::MessageDirection,
+        scroll_bar::ScrollBarMessage,
+        text::{Text, TextBuilder, TextMessage},
+        virtual_list_view::{ItemsSource, VirtualListView, VirtualListViewBuilder},
+        widget::WidgetBuilder,
+        BuildContext, UiNode, UserInterface,
+    };
+    use std::rc::Rc;
+
+    #[derive(Debug)]
+    struct Numbers;
+
+    impl ItemsSource for Numbers {
+        fn count(&self) -> usize {
+            100_000
+        }
+
+        fn make_item(&self, ctx: &mut BuildContext) -> Handle<UiNode> {
+            TextBuilder::new(WidgetBuilder::new()).build(ctx)
+        }
+
+        fn bind_item(&self, index: usize, item: Handle<UiNode>, ui: &UserInterface) {
+            ui.send_message(TextMessage::text(
+                item,
+                MessageDirection::ToWidget,
+                index.to_string(),
+            ));
+        }
+    }
+
+    fn update(ui: &mut UserInterface) {
+        for _ in 0..2 {
+            ui.update(Vector2::new(100.0, 100.0), 0.0);
+            while ui.poll_message().is_some() {}
+        }
+    }
+
+    fn shown_items(ui: &UserInterface, list_view: Handle<UiNode>) -> Vec<(usize, String)> {
+        let list_view_ref = ui
+            .node(list_view)
+            .query_component::<VirtualListView>()
+            .unwrap();
+        let mut items = list_view_ref
+            .bound_indices
+            .iter()
+            .zip(list_view_ref.row_items.iter())
+            .filter_map(|(index, item)| {
+                index.map(|index| {
+                    let text = ui.node(*item).query_component::<Text>().unwrap().text();
+                    (index, text)
+                })
+            })
+            .collect::<Vec<_>>();
+        items.sort();
+        items
+    }
+
+    #[test]
+    fn virtual_list_view_recycles_rows() {
+        let mut ui = UserInterface::new(Vector2::new(100.0, 100.0));
+
+        let list_view =
+            VirtualListViewBuilder::new(WidgetBuilder::new().with_width(100.0).with_height(100.0))
+                .with_items_source(Rc::new(Numbers))
+                .with_item_height(20.0)
+                .build(&mut ui.build_ctx());
+        update(&mut ui);
+
+        let list_view_ref = ui
+            .node(list_view)
+            .query_component::<VirtualListView>()
+            .unwrap();
+        // Only visible rows are created.
+        assert_eq!(list_view_ref.rows.len(), 6);
+        let scroll_bar = list_view_ref.scroll_bar;
+        assert_eq!(
+            shown_items(&ui, list_view),
+            (0..6).map(|i| (i, i.to_string())).collect::<Vec<_>>()
+        );
+
+        ui.send_message(ScrollBarMessage::value(
+            scroll_bar,
+            MessageDirection::ToWidget,
+            50_010.0,
+        ));
+        update(&mut ui);
+
+        assert_eq!(
+            shown_items(&ui, list_view),
+            (2500..2506).map(|i| (i, i.to_string())).collect::<Vec<_>>()
+        );
+    }
+}
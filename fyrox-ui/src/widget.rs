@@ -10,11 +10,13 @@ use crate::{
         math::Rect,
         pool::Handle,
         reflect::prelude::*,
+        sstorage::ImmutableString,
         uuid::Uuid,
         visitor::prelude::*,
     },
     define_constructor,
-    message::{CursorIcon, Force, KeyCode, MessageDirection, UiMessage},
+    message::{CursorIcon, Force, ImeEvent, KeyCode, MessageDirection, UiMessage},
+    style::Style,
     HorizontalAlignment, LayoutEvent, MouseButton, MouseState, RcUiNodeHandle, Thickness, UiNode,
     UserInterface, VerticalAlignment, BRUSH_FOREGROUND, BRUSH_PRIMARY,
 };
@@ -84,6 +86,13 @@ pub enum WidgetMessage {
     /// Direction: **From/To UI**.
     Text(String),
 
+    /// Initiated when widget is in focus and an input method editor (IME) starts, updates or cancels text
+    /// composition. Composed text is sent as [`WidgetMessage::Text`], so this message is needed only to show
+    /// the text that is being composed. [`ImeEvent::Commit`] is never sent with this message.
+    ///
+    /// Direction: **From UI**.
+    Ime(ImeEvent),
+
     /// Initiated when widget is in focus and user presses a button on a keyboard.
     ///
     /// Direction: **From UI**.
@@ -121,6 +130,19 @@ pub enum WidgetMessage {
     /// Direction: **From UI**.
     Drop(Handle<UiNode>),
 
+    /// Initiated when user drags a widget over a widget with drop allowed (see [`Widget::allow_drop`]) for the first time.
+    /// Could be used to highlight a drop target. Typed data of the dragged widget could be fetched using
+    /// [`Widget::drag_payload_ref`].
+    ///
+    /// Direction: **From UI**.
+    DragEnter(Handle<UiNode>),
+
+    /// Initiated when user drags a widget out of a widget with drop allowed, or when the drag is finished (after
+    /// [`WidgetMessage::Drop`] message) or cancelled. Could be used to remove highlighting of a drop target.
+    ///
+    /// Direction: **From UI**.
+    DragLeave(Handle<UiNode>),
+
     /// A request to make widget topmost. Widget can be made topmost only in the same hierarchy
     /// level only!
     ///
@@ -569,6 +591,12 @@ impl WidgetMessage {
         WidgetMessage:Text => fn text(String), layout: false
     );
 
+    define_constructor!(
+        /// Creates [`WidgetMessage::Ime`] message. This method is for internal use only, and should not
+        /// be used anywhere else.
+        WidgetMessage:Ime => fn ime(ImeEvent), layout: false
+    );
+
     define_constructor!(
         /// Creates [`WidgetMessage::KeyDown`] message. This method is for internal use only, and should not
         /// be used anywhere else.
@@ -599,6 +627,18 @@ impl WidgetMessage {
         WidgetMessage:Drop => fn drop(Handle<UiNode>), layout: false
     );
 
+    define_constructor!(
+        /// Creates [`WidgetMessage::DragEnter`] message. This method is for internal use only, and should not
+        /// be used anywhere else.
+        WidgetMessage:DragEnter => fn drag_enter(Handle<UiNode>), layout: false
+    );
+
+    define_constructor!(
+        /// Creates [`WidgetMessage::DragLeave`] message. This method is for internal use only, and should not
+        /// be used anywhere else.
+        WidgetMessage:DragLeave => fn drag_leave(Handle<UiNode>), layout: false
+    );
+
     define_constructor!(
         /// Creates [`WidgetMessage::DoubleClick`] message. This method is for internal use only, and should not
         /// be used anywhere else.
@@ -662,6 +702,12 @@ pub struct Widget {
     pub background: Brush,
     /// Foreground brush of the widget.
     pub foreground: Brush,
+    /// Name of a style property, that is used as the background brush of the widget. See [`Style`] docs for more info.
+    #[visit(optional)]
+    pub background_style: Option<ImmutableString>,
+    /// Name of a style property, that is used as the foreground brush of the widget. See [`Style`] docs for more info.
+    #[visit(optional)]
+    pub foreground_style: Option<ImmutableString>,
     /// Index of the row to which this widget belongs to. It is valid only in when used in [`crate::grid::Grid`] widget.
     pub row: usize,
     /// Index of the column to which this widget belongs to. It is valid only in when used in [`crate::grid::Grid`] widget.
@@ -700,6 +746,11 @@ pub struct Widget {
     #[reflect(hidden)]
     #[visit(skip)]
     pub user_data: Option<Rc<dyn Any>>,
+    /// Optional, typed data, that is carried by drag'n'drop operations started from the widget. Drop targets could
+    /// fetch it using [`Widget::drag_payload_ref`].
+    #[reflect(hidden)]
+    #[visit(skip)]
+    pub drag_payload: Option<Rc<dyn Any>>,
     /// A flag, that defines whether the widget should be drawn in a separate drawind pass after any other widget that draws
     /// normally.
     pub draw_on_top: bool,
@@ -992,6 +1043,25 @@ impl Widget {
         self.foreground.clone()
     }
 
+    /// Sets the style-bound properties of the widget using the properties of the given style. Properties, that
+    /// are not bound or missing in the style are left unchanged.
+    pub fn apply_style(&mut self, style: &Style) {
+        if let Some(brush) = self
+            .background_style
+            .as_ref()
+            .and_then(|name| style.brush(name))
+        {
+            self.background = brush;
+        }
+        if let Some(brush) = self
+            .foreground_style
+            .as_ref()
+            .and_then(|name| style.brush(name))
+        {
+            self.foreground = brush;
+        }
+    }
+
     /// Sets new width of the widget.
     #[inline]
     pub fn set_width(&mut self, width: f32) -> &mut Self {
@@ -1196,8 +1266,15 @@ impl Widget {
             if let Some(msg) = msg.data::<WidgetMessage>() {
                 match msg {
                     &WidgetMessage::Opacity(opacity) => self.opacity = opacity,
-                    WidgetMessage::Background(background) => self.background = background.clone(),
-                    WidgetMessage::Foreground(foreground) => self.foreground = foreground.clone(),
+                    WidgetMessage::Background(background) => {
+                        // Explicitly set brush is no longer bound to the style.
+                        self.background = background.clone();
+                        self.background_style = None;
+                    }
+                    WidgetMessage::Foreground(foreground) => {
+                        self.foreground = foreground.clone();
+                        self.foreground_style = None;
+                    }
                     WidgetMessage::Name(name) => self.name = name.clone(),
                     &WidgetMessage::Width(width) => {
                         if self.width != width {
@@ -1476,6 +1553,14 @@ impl Widget {
         self.user_data.as_ref().and_then(|v| v.downcast_ref::<T>())
     }
 
+    /// Tries to fetch drag'n'drop payload of the specified type `T`.
+    #[inline]
+    pub fn drag_payload_ref<T: 'static>(&self) -> Option<&T> {
+        self.drag_payload
+            .as_ref()
+            .and_then(|v| v.downcast_ref::<T>())
+    }
+
     /// Returns current clipping bounds of the widget. It is valid only after at least one layout pass.
     #[inline]
     pub fn clip_bounds(&self) -> Rect<f32> {
@@ -1578,6 +1663,10 @@ pub struct WidgetBuilder {
     pub background: Option<Brush>,
     /// Foreground brush of the widget.
     pub foreground: Option<Brush>,
+    /// Name of a style property, that is used as the background brush of the widget.
+    pub background_style: Option<ImmutableString>,
+    /// Name of a style property, that is used as the foreground brush of the widget.
+    pub foreground_style: Option<ImmutableString>,
     /// Row index of the widget.
     pub row: usize,
     /// Column index of the widget.
@@ -1598,6 +1687,8 @@ pub struct WidgetBuilder {
     pub allow_drop: bool,
     /// User-defined data.
     pub user_data: Option<Rc<dyn Any>>,
+    /// Drag'n'drop payload.
+    pub drag_payload: Option<Rc<dyn Any>>,
     /// Whether to draw the widget on top of any other or not.
     pub draw_on_top: bool,
     /// Whether the widget is enabled or not.
@@ -1645,6 +1736,8 @@ impl WidgetBuilder {
             min_size: None,
             background: None,
             foreground: None,
+            background_style: None,
+            foreground_style: None,
             row: 0,
             column: 0,
             margin: Thickness::zero(),
@@ -1656,6 +1749,7 @@ impl WidgetBuilder {
             allow_drag: false,
             allow_drop: false,
             user_data: None,
+            drag_payload: None,
             draw_on_top: false,
             enabled: true,
             cursor: None,
@@ -1746,6 +1840,30 @@ impl WidgetBuilder {
         self
     }
 
+    /// Binds the background brush of the widget to a style property with the given name. The brush will
+    /// be taken from the current style of the user interface, and it will be updated when the style changes.
+    pub fn with_background_style(mut self, name: &str) -> Self {
+        self.background_style = Some(ImmutableString::new(name));
+        self
+    }
+
+    /// Binds the foreground brush of the widget to a style property with the given name. The brush will
+    /// be taken from the current style of the user interface, and it will be updated when the style changes.
+    pub fn with_foreground_style(mut self, name: &str) -> Self {
+        self.foreground_style = Some(ImmutableString::new(name));
+        self
+    }
+
+    /// Returns `true` if the background is specified either explicitly or by a style property.
+    pub fn has_background(&self) -> bool {
+        self.background.is_some() || self.background_style.is_some()
+    }
+
+    /// Returns `true` if the foreground is specified either explicitly or by a style property.
+    pub fn has_foreground(&self) -> bool {
+        self.foreground.is_some() || self.foreground_style.is_some()
+    }
+
     /// Sets the desired row index of the widget.
     pub fn on_row(mut self, row: usize) -> Self {
         self.row = row;
@@ -1842,6 +1960,13 @@ impl WidgetBuilder {
         self
     }
 
+    /// Sets the desired drag'n'drop payload of the widget. It will be available for drop targets via
+    /// [`Widget::drag_payload_ref`].
+    pub fn with_drag_payload(mut self, drag_payload: Rc<dyn Any>) -> Self {
+        self.drag_payload = Some(drag_payload);
+        self
+    }
+
     /// Sets the desired widget user data.
     pub fn with_user_data(mut self, user_data: Rc<dyn Any>) -> Self {
         self.user_data = Some(user_data);
@@ -1902,6 +2027,18 @@ impl WidgetBuilder {
 
     /// Finishes building of the base widget.
     pub fn build(self) -> Widget {
+        // Widgets without explicit brushes use the default style properties.
+        let background_style = self.background_style.or_else(|| {
+            self.background
+                .is_none()
+                .then(|| ImmutableString::new(Style::BRUSH_PRIMARY))
+        });
+        let foreground_style = self.foreground_style.or_else(|| {
+            self.foreground
+                .is_none()
+                .then(|| ImmutableString::new(Style::BRUSH_FOREGROUND))
+        });
+
         Widget {
             handle: Default::default(),
             name: self.name,
@@ -1917,6 +2054,8 @@ impl WidgetBuilder {
                 .unwrap_or_else(|| Vector2::new(f32::INFINITY, f32::INFINITY)),
             background: self.background.unwrap_or_else(|| BRUSH_PRIMARY.clone()),
             foreground: self.foreground.unwrap_or_else(|| BRUSH_FOREGROUND.clone()),
+            background_style,
+            foreground_style,
             row: self.row,
             column: self.column,
             vertical_alignment: self.vertical_alignment,
@@ -1938,6 +2077,7 @@ impl WidgetBuilder {
             allow_drag: self.allow_drag,
             allow_drop: self.allow_drop,
             user_data: self.user_data.clone(),
+            drag_payload: self.drag_payload.clone(),
             draw_on_top: self.draw_on_top,
             enabled: self.enabled,
             cursor: self.cursor,
@@ -11,11 +11,12 @@ use crate::{
     define_constructor,
     grid::{Column, GridBuilder, Row},
     message::{CursorIcon, MessageDirection, UiMessage},
+    style::Style,
     text::{Text, TextBuilder, TextMessage},
     vector_image::{Primitive, VectorImageBuilder},
     widget::{Widget, WidgetBuilder, WidgetMessage},
     BuildContext, Control, HorizontalAlignment, NodeHandleMapping, RestrictionEntry, Thickness,
-    UiNode, UserInterface, VerticalAlignment, BRUSH_BRIGHT, BRUSH_LIGHT, BRUSH_LIGHTEST,
+    UiNode, UserInterface, VerticalAlignment,
 };
 use std::{
     any::{Any, TypeId},
@@ -254,6 +255,11 @@ pub struct Window {
     /// Bounds of the window before maximization, it is used to return the window to previous
     /// size when it is either "unmaximized" or dragged.
     pub prev_bounds: Option<Rect<f32>>,
+    /// A widget, that had keyboard focus before the window was opened in modal mode. The focus is returned to
+    /// the widget when the window is closed.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub prev_focus: Handle<UiNode>,
 }
 
 const GRIP_SIZE: f32 = 6.0;
@@ -576,6 +582,13 @@ impl Control for Window {
                                 handle: self.handle(),
                                 stop: true,
                             });
+                            // Move keyboard focus to the window, so the rest of the UI won't receive keyboard
+                            // input either.
+                            self.prev_focus = ui.keyboard_focus_node;
+                            ui.send_message(WidgetMessage::focus(
+                                self.handle(),
+                                MessageDirection::ToWidget,
+                            ));
                         }
                     }
                     WindowMessage::Close => {
@@ -586,6 +599,13 @@ impl Control for Window {
                                 false,
                             ));
                             ui.remove_picking_restriction(self.handle());
+                            if ui.try_get_node(self.prev_focus).is_some() {
+                                ui.send_message(WidgetMessage::focus(
+                                    self.prev_focus,
+                                    MessageDirection::ToWidget,
+                                ));
+                            }
+                            self.prev_focus = Handle::NONE;
                         }
                     }
                     &WindowMessage::Minimize(minimized) => {
@@ -612,7 +632,8 @@ impl Control for Window {
                                 current_size.y,
                             ))
                             .unwrap_or_else(|| {
-                                Rect::new(0.0, 0.0, ui.screen_size.x, ui.screen_size.y)
+                                let screen_size = ui.logical_screen_size();
+                                Rect::new(0.0, 0.0, screen_size.x, screen_size.y)
                             });
 
                         ui.send_message(WidgetMessage::desired_position(
@@ -667,11 +688,11 @@ impl Control for Window {
                             // application window, thus leaving an opportunity to drag window to some other place.
                             new_pos.x = new_pos.x.clamp(
                                 -(self.actual_local_size().x - safe_border.x).abs(),
-                                (ui.screen_size().x - safe_border.x).abs(),
+                                (ui.logical_screen_size().x - safe_border.x).abs(),
                             );
                             new_pos.y = new_pos
                                 .y
-                                .clamp(0.0, (ui.screen_size().y - safe_border.y).abs());
+                                .clamp(0.0, (ui.logical_screen_size().y - safe_border.y).abs());
                         }
 
                         if self.is_dragging && self.desired_local_position() != new_pos {
@@ -869,7 +890,7 @@ fn make_mark(ctx: &mut BuildContext, button: HeaderButton) -> Handle<UiNode> {
                 HeaderButton::Minimize => Thickness::bottom(3.0),
                 HeaderButton::Maximize => Thickness::bottom(0.0),
             })
-            .with_foreground(BRUSH_BRIGHT),
+            .with_foreground_style(Style::BRUSH_BRIGHT),
     )
     .with_primitives(match button {
         HeaderButton::Close => {
@@ -933,8 +954,8 @@ fn make_header_button(ctx: &mut BuildContext, button: HeaderButton) -> Handle<Ui
                     .with_stroke_thickness(Thickness::uniform(0.0)),
             )
             .with_normal_brush(Brush::Solid(Color::TRANSPARENT))
-            .with_hover_brush(BRUSH_LIGHT)
-            .with_pressed_brush(BRUSH_LIGHTEST)
+            .with_hover_brush_style(Style::BRUSH_LIGHT)
+            .with_pressed_brush_style(Style::BRUSH_LIGHTEST)
             .build(ctx),
         )
         .with_content(make_mark(ctx, button))
@@ -1163,6 +1184,7 @@ impl WindowBuilder {
             title,
             title_grid,
             prev_bounds: None,
+            prev_focus: Handle::NONE,
         }
     }
 
@@ -27,6 +27,19 @@ pub enum Event {
 
     /// Occurs when active transition was changed.
     ActiveTransitionChanged(Handle<Transition>),
+
+    /// Occurs when exit state of a layer has become active. See [`super::MachineLayer::set_exit_state`] for
+    /// more info.
+    Finished(Handle<State>),
+
+    /// Occurs when a custom event of a state was triggered. See [`super::state::StateEvent`] for more info.
+    StateEvent {
+        /// A state that emitted the event.
+        state: Handle<State>,
+
+        /// Name of the event.
+        name: String,
+    },
 }
 
 /// A simple event queue with fixed capacity. It is used to store a fixed amount of events and discard any
@@ -7,10 +7,10 @@ use crate::animation::AnimationEvent;
 use crate::{
     animation::{
         machine::{
-            event::FixedEventQueue, Event, LayerMask, ParameterContainer, PoseNode, State,
-            Transition,
+            event::FixedEventQueue, state::StateEventTrigger, Event, LayerMask, ParameterContainer,
+            PoseNode, State, Transition,
         },
-        Animation, AnimationContainer, AnimationPose,
+        Animation, AnimationContainer, AnimationPose, RootMotion,
     },
     core::{
         log::{Log, MessageKind},
@@ -20,6 +20,7 @@ use crate::{
     },
     utils::{self, NameProvider},
 };
+use std::cmp::Reverse;
 
 /// Layer is a separate state graph. Layers mainly used to animate different parts of humanoid (but not only) characters. For
 /// example there could a layer for upper body and a layer for lower body. Upper body layer could contain animations for aiming,
@@ -73,6 +74,9 @@ pub struct MachineLayer {
 
     mask: LayerMask,
 
+    #[visit(optional)]
+    sync_phases: bool,
+
     #[reflect(hidden)]
     nodes: Pool<PoseNode>,
 
@@ -91,6 +95,18 @@ pub struct MachineLayer {
     #[reflect(hidden)]
     active_transition: Handle<Transition>,
 
+    #[visit(optional)]
+    #[reflect(hidden)]
+    any_state: Handle<State>,
+
+    #[visit(optional)]
+    #[reflect(hidden)]
+    exit_state: Handle<State>,
+
+    #[visit(optional)]
+    #[reflect(hidden)]
+    active_transition_source: Handle<State>,
+
     #[visit(skip)]
     #[reflect(hidden)]
     final_pose: AnimationPose,
@@ -99,6 +115,14 @@ pub struct MachineLayer {
     #[reflect(hidden)]
     events: FixedEventQueue,
 
+    #[visit(skip)]
+    #[reflect(hidden)]
+    time_events_state: Handle<State>,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    time_events_prev_time: f32,
+
     #[visit(skip)]
     #[reflect(hidden)]
     debug: bool,
@@ -161,10 +185,16 @@ impl MachineLayer {
             active_state: Default::default(),
             entry_state: Default::default(),
             active_transition: Default::default(),
+            any_state: Default::default(),
+            exit_state: Default::default(),
+            active_transition_source: Default::default(),
             weight: 1.0,
             events: FixedEventQueue::new(2048),
+            time_events_state: Default::default(),
+            time_events_prev_time: 0.0,
             debug: false,
             mask: Default::default(),
+            sync_phases: false,
         }
     }
 
@@ -200,6 +230,41 @@ impl MachineLayer {
         self.entry_state
     }
 
+    /// Sets new "any state" of the layer. Transitions that start from this state are considered while any other
+    /// state is active, which allows to define global transitions (for example, to a death state) without adding
+    /// a separate transition for every state of the layer. The "any state" itself is never blended, so its pose
+    /// could be left empty. Pass [`Handle::NONE`] to disable global transitions.
+    #[inline]
+    pub fn set_any_state(&mut self, any_state: Handle<State>) {
+        self.any_state = any_state;
+    }
+
+    /// Returns a handle of current "any state".
+    #[inline]
+    pub fn any_state(&self) -> Handle<State> {
+        self.any_state
+    }
+
+    /// Sets new exit state of the layer. When the exit state becomes active, the layer emits [`Event::Finished`]
+    /// event, which could be used to detect that the layer has done its work (for example, that a one-shot
+    /// action has ended). Pass [`Handle::NONE`] to disable it.
+    #[inline]
+    pub fn set_exit_state(&mut self, exit_state: Handle<State>) {
+        self.exit_state = exit_state;
+    }
+
+    /// Returns a handle of current exit state.
+    #[inline]
+    pub fn exit_state(&self) -> Handle<State> {
+        self.exit_state
+    }
+
+    /// Returns `true` if the exit state of the layer is currently active, `false` - otherwise.
+    #[inline]
+    pub fn is_finished(&self) -> bool {
+        self.exit_state.is_some() && self.active_state == self.exit_state
+    }
+
     /// Turns on/off the debug mode. Debug mode forces to log all events happening in the layer. For example when a
     /// state changes, there will be a respective message in the log.
     #[inline]
@@ -259,6 +324,12 @@ impl MachineLayer {
     ///         Event::ActiveTransitionChanged(transition_handle) => {
     ///             // Occurs when active transition has changed.
     ///         }
+    ///         Event::Finished(state_handle) => {
+    ///             // Occurs when exit state has become active.
+    ///         }
+    ///         Event::StateEvent { state, name } => {
+    ///             // Occurs when a custom event of a state was triggered.
+    ///         }
     ///     }
     /// }
     /// ```
@@ -275,6 +346,7 @@ impl MachineLayer {
         }
 
         self.active_state = self.entry_state;
+        self.active_transition_source = Handle::NONE;
     }
 
     /// Fetches animation events from an active state (or a transition). It could be used to fetch animation events from a layer
@@ -308,7 +380,8 @@ impl MachineLayer {
             };
         } else if let Some(transition) = self.transitions.try_borrow(self.active_transition) {
             if let (Some(source_state), Some(dest_state)) = (
-                self.states.try_borrow(transition.source()),
+                self.states
+                    .try_borrow(self.active_transition_source(transition)),
                 self.states.try_borrow(transition.dest()),
             ) {
                 let mut events = Vec::new();
@@ -362,13 +435,9 @@ impl MachineLayer {
                 return LayerAnimationEventsCollection {
                     source: AnimationEventsSource::Transition {
                         handle: self.active_transition,
-                        source_state_handle: transition.source,
+                        source_state_handle: self.active_transition_source(transition),
                         dest_state_handle: transition.dest,
-                        source_state_name: self
-                            .states
-                            .try_borrow(transition.source)
-                            .map(|s| s.name.clone())
-                            .unwrap_or_default(),
+                        source_state_name: source_state.name.clone(),
                         dest_state_name: self
                             .states
                             .try_borrow(transition.dest)
@@ -530,6 +599,22 @@ impl MachineLayer {
         &self.mask
     }
 
+    /// Enables or disables phase synchronization of the animations of the layer. When enabled, every animation of
+    /// the active state (or both states of an active transition) is time-warped so its sync markers are aligned
+    /// with the markers of the animation with the largest weight. See [`crate::animation::SyncMarker`] docs for
+    /// more info. Disabled by default. Sync markers of states (see [`State::sync_markers`]) are always used,
+    /// regardless of this option.
+    #[inline]
+    pub fn set_sync_phases(&mut self, sync_phases: bool) {
+        self.sync_phases = sync_phases;
+    }
+
+    /// Returns `true` if the phase synchronization is enabled, `false` - otherwise.
+    #[inline]
+    pub fn is_sync_phases(&self) -> bool {
+        self.sync_phases
+    }
+
     /// Returns final pose of the layer.
     #[inline]
     pub fn pose(&self) -> &AnimationPose {
@@ -567,76 +652,377 @@ impl MachineLayer {
             .all(|a| a.has_ended())
     }
 
-    #[inline]
-    pub(super) fn evaluate_pose(
+    /// Returns a state from which the active transition has started. It differs from the source of the transition
+    /// for transitions that start from the "any state".
+    fn active_transition_source(&self, transition: &Transition) -> Handle<State> {
+        if self.active_transition_source.is_some() {
+            self.active_transition_source
+        } else {
+            transition.source()
+        }
+    }
+
+    /// Returns `true` if the dominant animation of the given state has reached the exit time of the given
+    /// transition. States without animations are considered as finished.
+    fn is_exit_time_reached(
+        &self,
+        transition: &Transition,
+        state: Handle<State>,
+        parameters: &ParameterContainer,
+        animations: &AnimationContainer,
+    ) -> bool {
+        let exit_time = match transition.exit_time() {
+            Some(exit_time) => exit_time,
+            None => return true,
+        };
+
+        self.states
+            .try_borrow(state)
+            .and_then(|state| self.nodes.try_borrow(state.root))
+            .and_then(|root| root.dominant_animation(&self.nodes, parameters))
+            .and_then(|animation| animations.try_get(animation))
+            .map_or(true, |animation| {
+                animation.normalized_time_position() >= exit_time
+            })
+    }
+
+    /// Searches for a transition that could be activated while the given state is active. Transitions from
+    /// the "any state" are checked first, so global transitions win over the transitions of the state. Within
+    /// each group, transitions are checked in order of their priority.
+    fn find_transition(
+        &self,
+        state: Handle<State>,
+        parameters: &ParameterContainer,
+        animations: &AnimationContainer,
+    ) -> Option<Handle<Transition>> {
+        for source in [self.any_state, state] {
+            if source.is_none() {
+                continue;
+            }
+
+            let mut candidates = self
+                .transitions
+                .pair_iter()
+                .filter(|(handle, transition)| {
+                    *handle != self.active_transition
+                        && transition.source() == source
+                        && transition.dest() != state
+                })
+                .collect::<Vec<_>>();
+
+            // Stable sort keeps the creation order of transitions with the same priority.
+            candidates.sort_by_key(|(_, transition)| Reverse(transition.priority()));
+
+            for (handle, transition) in candidates {
+                if self.is_exit_time_reached(transition, state, parameters, animations)
+                    && transition.condition.calculate_value(parameters, animations)
+                {
+                    return Some(handle);
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Activates the given transition. `leaving_state` is the state that will be left (it receives leave
+    /// actions and events), `blend_source` is the state whose pose will be blended with the destination state.
+    fn activate_transition(
         &mut self,
+        handle: Handle<Transition>,
+        leaving_state: Handle<State>,
+        blend_source: Handle<State>,
+        parameters: &ParameterContainer,
         animations: &mut AnimationContainer,
+        consumed_triggers: &mut Vec<String>,
+    ) {
+        let transition = &self.transitions[handle];
+        let dest = transition.dest();
+
+        transition
+            .condition
+            .collect_triggers(parameters, consumed_triggers);
+
+        if let Some(state) = self.states.try_borrow(leaving_state) {
+            for action in state.on_leave_actions.iter() {
+                action.apply(animations);
+            }
+
+            if self.debug {
+                Log::writeln(
+                    MessageKind::Information,
+                    format!("Leaving state: {}", state.name),
+                );
+            }
+        }
+        self.events.push(Event::StateLeave(leaving_state));
+        self.emit_state_events(leaving_state, |trigger| {
+            matches!(trigger, StateEventTrigger::Leave)
+        });
+
+        if let Some(state) = self.states.try_borrow(dest) {
+            for action in state.on_enter_actions.iter() {
+                action.apply(animations);
+            }
+
+            if self.debug {
+                Log::writeln(
+                    MessageKind::Information,
+                    format!("Entering state: {}", state.name),
+                );
+            }
+        }
+        self.events.push(Event::StateEnter(dest));
+        self.emit_state_events(dest, |trigger| matches!(trigger, StateEventTrigger::Enter));
+
+        self.active_transition_source = blend_source;
+        self.active_state = Handle::NONE;
+
+        self.active_transition = handle;
+        self.events
+            .push(Event::ActiveTransitionChanged(self.active_transition));
+    }
+
+    fn emit_state_events<F>(&mut self, state: Handle<State>, mut filter: F)
+    where
+        F: FnMut(&StateEventTrigger) -> bool,
+    {
+        if let Some(state_ref) = self.states.try_borrow(state) {
+            for event in state_ref.events.iter() {
+                if filter(&event.trigger) {
+                    self.events.push(Event::StateEvent {
+                        state,
+                        name: event.name.clone(),
+                    });
+                }
+            }
+        }
+    }
+
+    /// Emits time events of the current state (or the destination state of the active transition) which were
+    /// passed by its dominant animation since the previous frame.
+    fn emit_time_events(
+        &mut self,
         parameters: &ParameterContainer,
-        dt: f32,
-    ) -> &AnimationPose {
-        self.final_pose.reset();
+        animations: &AnimationContainer,
+    ) {
+        let state = match self.transitions.try_borrow(self.active_transition) {
+            Some(transition) => transition.dest(),
+            None => self.active_state,
+        };
+
+        let time = match self
+            .states
+            .try_borrow(state)
+            .and_then(|state| self.nodes.try_borrow(state.root))
+            .and_then(|root| root.dominant_animation(&self.nodes, parameters))
+            .and_then(|animation| animations.try_get(animation))
+        {
+            Some(animation) => animation.normalized_time_position(),
+            None => {
+                self.time_events_state = Handle::NONE;
+                return;
+            }
+        };
 
-        if self.active_state.is_some() || self.active_transition.is_some() {
-            // Gather actual poses for each state.
-            for state in self.states.iter_mut() {
-                state.update(&self.nodes, parameters, animations, dt);
+        if self.time_events_state != state {
+            // Start tracking a new state, there is nothing to compare with yet.
+            self.time_events_state = state;
+            self.time_events_prev_time = time;
+            return;
+        }
+
+        let prev_time = std::mem::replace(&mut self.time_events_prev_time, time);
+        self.emit_state_events(state, |trigger| match trigger {
+            StateEventTrigger::Time(event_time) => {
+                if time >= prev_time {
+                    prev_time <= *event_time && *event_time < time
+                } else {
+                    // Looped animation has wrapped around.
+                    *event_time >= prev_time || *event_time < time
+                }
             }
+            _ => false,
+        });
+    }
 
-            if self.active_transition.is_none() {
-                // Find transition.
-                for (handle, transition) in self.transitions.pair_iter_mut() {
-                    if transition.dest() == self.active_state
-                        || transition.source() != self.active_state
-                    {
-                        continue;
+    /// Applies speed multipliers of the played states to their animations. The animations are already advanced by
+    /// the animation player using their own speed, so only the difference is applied here.
+    fn apply_state_speeds(&self, animations: &mut AnimationContainer, dt: f32) {
+        let played_states =
+            if let Some(transition) = self.transitions.try_borrow(self.active_transition) {
+                [self.active_transition_source(transition), transition.dest()]
+            } else {
+                [self.active_state, Handle::NONE]
+            };
+
+        let mut processed = Vec::new();
+        for state in played_states {
+            let state_ref = match self.states.try_borrow(state) {
+                Some(state_ref) if state_ref.speed != 1.0 => state_ref,
+                _ => continue,
+            };
+
+            for handle in self.animations_of_state(state) {
+                // An animation could be shared between the source and the destination states.
+                if processed.contains(&handle) {
+                    continue;
+                }
+                processed.push(handle);
+
+                if let Some(animation) = animations.try_get_mut(handle) {
+                    // Ended animations are kept at their end, otherwise they will be moved back and forth.
+                    if animation.is_enabled() && !animation.has_ended() {
+                        animation.tick(dt * (state_ref.speed - 1.0));
+                        animation.update_pose();
                     }
+                }
+            }
+        }
+    }
 
-                    if transition.condition.calculate_value(parameters, animations) {
-                        if let Some(active_state) = self.states.try_borrow(self.active_state) {
-                            for action in active_state.on_leave_actions.iter() {
-                                action.apply(animations);
-                            }
-                        }
+    fn sync_animation_phases(
+        &self,
+        animations: &mut AnimationContainer,
+        parameters: &ParameterContainer,
+    ) {
+        let (leading_state, following_state) =
+            if let Some(transition) = self.transitions.try_borrow(self.active_transition) {
+                let source = self.active_transition_source(transition);
+                if transition.blend_factor() < 0.5 {
+                    (source, transition.dest())
+                } else {
+                    (transition.dest(), source)
+                }
+            } else {
+                (self.active_state, Handle::NONE)
+            };
 
-                        self.events.push(Event::StateLeave(self.active_state));
-                        if self.debug {
-                            Log::writeln(
-                                MessageKind::Information,
-                                format!("Leaving state: {}", self.states[self.active_state].name),
-                            );
-                        }
+        // Only the blended states are checked for markers, the rest of the states do not affect the phase.
+        if !self.sync_phases
+            && ![leading_state, following_state].into_iter().any(|state| {
+                self.states
+                    .try_borrow(state)
+                    .is_some_and(|state| !state.sync_markers.is_empty())
+            })
+        {
+            return;
+        }
 
-                        if let Some(source) = self.states.try_borrow(transition.dest()) {
-                            for action in source.on_enter_actions.iter() {
-                                action.apply(animations);
+        let leader = match self
+            .states
+            .try_borrow(leading_state)
+            .and_then(|state| self.nodes.try_borrow(state.root))
+            .and_then(|root| root.dominant_animation(&self.nodes, parameters))
+        {
+            Some(leader) => leader,
+            None => return,
+        };
+
+        // Sync markers of the state take precedence over the markers of its animations.
+        let leader_state_ref = &self.states[leading_state];
+        let phase = match animations.try_get(leader).and_then(|a| {
+            if leader_state_ref.sync_markers.is_empty() {
+                a.sync_phase().filter(|_| self.sync_phases)
+            } else {
+                leader_state_ref.sync_phase(a.normalized_time_position())
+            }
+        }) {
+            Some(phase) => phase,
+            None => return,
+        };
+
+        for state in [leading_state, following_state] {
+            let state_ref = match self.states.try_borrow(state) {
+                Some(state_ref) => state_ref,
+                None => continue,
+            };
+
+            for animation in self.animations_of_state(state) {
+                if animation != leader {
+                    if let Some(animation) = animations.try_get_mut(animation) {
+                        if state_ref.sync_markers.is_empty() {
+                            if self.sync_phases {
+                                animation.align_to_phase(&phase);
                             }
+                        } else if let Some(time) = state_ref.normalized_time_of_phase(&phase) {
+                            animation.set_normalized_time_position(time);
+                            animation.update_pose();
                         }
+                    }
+                }
+            }
+        }
+    }
 
-                        self.events.push(Event::StateEnter(transition.dest()));
-                        if self.debug {
-                            Log::writeln(
-                                MessageKind::Information,
-                                format!("Entering state: {}", self.states[transition.dest()].name),
-                            );
-                        }
+    #[inline]
+    pub(super) fn evaluate_pose(
+        &mut self,
+        animations: &mut AnimationContainer,
+        parameters: &ParameterContainer,
+        dt: f32,
+        consumed_triggers: &mut Vec<String>,
+    ) -> &AnimationPose {
+        self.final_pose.reset();
 
-                        self.active_state = Handle::NONE;
+        if self.active_state.is_some() || self.active_transition.is_some() {
+            self.apply_state_speeds(animations, dt);
+            self.sync_animation_phases(animations, parameters);
 
-                        self.active_transition = handle;
-                        self.events
-                            .push(Event::ActiveTransitionChanged(self.active_transition));
+            // Gather actual poses for each state.
+            for state in self.states.iter_mut() {
+                state.update(&self.nodes, parameters, animations, dt);
+            }
 
-                        break;
+            if self.active_transition.is_none() {
+                if let Some(handle) =
+                    self.find_transition(self.active_state, parameters, animations)
+                {
+                    self.activate_transition(
+                        handle,
+                        self.active_state,
+                        self.active_state,
+                        parameters,
+                        animations,
+                        consumed_triggers,
+                    );
+                }
+            } else if let Some(active_transition) =
+                self.transitions.try_borrow(self.active_transition)
+            {
+                if active_transition.can_be_interrupted() {
+                    let dest = active_transition.dest();
+                    // Blend from the state that currently prevails, this reduces the pose jump.
+                    let blend_source = if active_transition.blend_factor() < 0.5 {
+                        self.active_transition_source(active_transition)
+                    } else {
+                        dest
+                    };
+
+                    if let Some(handle) = self.find_transition(dest, parameters, animations) {
+                        self.transitions[self.active_transition].reset();
+                        self.activate_transition(
+                            handle,
+                            dest,
+                            blend_source,
+                            parameters,
+                            animations,
+                            consumed_triggers,
+                        );
                     }
                 }
             }
 
+            self.emit_time_events(parameters, animations);
+
             // Double check for active transition because we can have empty machine.
             if self.active_transition.is_some() {
+                let source =
+                    self.active_transition_source(&self.transitions[self.active_transition]);
                 let transition = &mut self.transitions[self.active_transition];
 
                 // Blend between source and dest states.
-                if let Some(source_pose) = self.states[transition.source()].pose(&self.nodes) {
+                if let Some(source_pose) = self.states[source].pose(&self.nodes) {
                     self.final_pose
                         .blend_with(&source_pose, 1.0 - transition.blend_factor());
                 }
@@ -645,6 +1031,21 @@ impl MachineLayer {
                         .blend_with(&dest_pose, transition.blend_factor());
                 }
 
+                // Blend root motion separately, because it must be filtered by the settings of each state.
+                let mut root_motion = RootMotion::default();
+                for (state, weight) in [
+                    (source, 1.0 - transition.blend_factor()),
+                    (transition.dest(), transition.blend_factor()),
+                ] {
+                    let state = &self.states[state];
+                    let state_root_motion = state
+                        .pose(&self.nodes)
+                        .and_then(|pose| state.filter_root_motion(&pose))
+                        .unwrap_or_default();
+                    root_motion.blend_with(&state_root_motion, weight);
+                }
+                self.final_pose.set_root_motion(Some(root_motion));
+
                 transition.update(dt);
 
                 if transition.is_done() {
@@ -654,12 +1055,17 @@ impl MachineLayer {
                     self.events
                         .push(Event::ActiveTransitionChanged(self.active_transition));
 
+                    self.active_transition_source = Handle::NONE;
                     self.active_state = transition.dest();
                     self.events.push(Event::ActiveStateChanged {
-                        prev: transition.source(),
+                        prev: source,
                         new: transition.dest(),
                     });
 
+                    if self.is_finished() {
+                        self.events.push(Event::Finished(self.active_state));
+                    }
+
                     if self.debug {
                         Log::writeln(
                             MessageKind::Information,
@@ -673,8 +1079,11 @@ impl MachineLayer {
             } else {
                 // We must have active state all the time when we do not have any active transition.
                 // Just get pose from active state.
-                if let Some(active_state_pose) = self.states[self.active_state].pose(&self.nodes) {
+                let active_state = &self.states[self.active_state];
+                if let Some(active_state_pose) = active_state.pose(&self.nodes) {
                     active_state_pose.clone_into(&mut self.final_pose);
+                    self.final_pose
+                        .set_root_motion(active_state.filter_root_motion(&active_state_pose));
                 }
             }
         }
@@ -6,13 +6,15 @@
 #![warn(missing_docs)]
 
 use crate::{
-    animation::{AnimationContainer, AnimationPose},
+    animation::{AnimationContainer, AnimationPose, RootMotion},
+    asset::io::ResourceIo,
     core::{
         reflect::prelude::*,
-        visitor::{Visit, VisitResult, Visitor},
+        visitor::{Visit, VisitError, VisitResult, Visitor},
     },
     utils,
 };
+use std::path::Path;
 
 pub use event::Event;
 pub use layer::MachineLayer;
@@ -176,6 +178,10 @@ pub struct Machine {
     #[visit(skip)]
     #[reflect(hidden)]
     final_pose: AnimationPose,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    accumulated_root_motion: RootMotion,
 }
 
 impl Machine {
@@ -186,6 +192,7 @@ impl Machine {
             parameters: Default::default(),
             layers: vec![MachineLayer::new()],
             final_pose: Default::default(),
+            accumulated_root_motion: Default::default(),
         }
     }
 
@@ -284,6 +291,39 @@ impl Machine {
         &self.final_pose
     }
 
+    /// Returns root motion accumulated by the machine since the last call of this method and resets it. Root
+    /// motion of each state is filtered by its root motion settings (see [`state::StateRootMotionSettings`]).
+    /// The accumulated motion could be used to drive a rigid body of a character, which is useful when the
+    /// machine is updated more often than the physics of a character.
+    #[inline]
+    pub fn take_accumulated_root_motion(&mut self) -> RootMotion {
+        std::mem::take(&mut self.accumulated_root_motion)
+    }
+
+    /// Saves the machine to the specified file path, use [`Self::load_from_file`] to load it back.
+    ///
+    /// # Important notes
+    ///
+    /// Animations ([`PlayAnimation`] nodes) and layer masks are stored as handles, which are only valid in
+    /// the scene the machine was created in. The file could be shared only between scenes with the same
+    /// animation player and node hierarchy (for example, between instances of the same character model),
+    /// handles must be re-assigned manually in other cases.
+    pub fn save(&mut self, path: &Path) -> VisitResult {
+        let mut visitor = Visitor::new();
+        self.visit("Machine", &mut visitor)?;
+        visitor.save_binary(path)
+    }
+
+    /// Loads a machine from the specified file path, the file must be created by [`Self::save`]. See
+    /// [`Self::save`] docs for the limitations.
+    pub async fn load_from_file(path: &Path, io: &dyn ResourceIo) -> Result<Self, VisitError> {
+        let bytes = io.load_file(path).await?;
+        let mut visitor = Visitor::load_from_memory(&bytes)?;
+        let mut machine = Machine::default();
+        machine.visit("Machine", &mut visitor)?;
+        Ok(machine)
+    }
+
     /// Computes final animation pose that could be then applied to a scene graph.
     #[inline]
     pub fn evaluate_pose(
@@ -293,13 +333,354 @@ impl Machine {
     ) -> &AnimationPose {
         self.final_pose.reset();
 
+        let mut consumed_triggers = Vec::new();
         for layer in self.layers.iter_mut() {
             let weight = layer.weight();
-            let pose = layer.evaluate_pose(animations, &self.parameters, dt);
+            let pose =
+                layer.evaluate_pose(animations, &self.parameters, dt, &mut consumed_triggers);
 
             self.final_pose.blend_with(pose, weight);
         }
 
+        if let Some(root_motion) = self.final_pose.root_motion() {
+            self.accumulated_root_motion.delta_position += root_motion.delta_position;
+            self.accumulated_root_motion.delta_rotation *= root_motion.delta_rotation;
+        }
+
+        for name in consumed_triggers {
+            if let Some(Parameter::Trigger(value)) = self.parameters.get_mut(&name) {
+                *value = false;
+            }
+        }
+
         &self.final_pose
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::animation::{
+        machine::{
+            state::{StateEvent, StateEventTrigger, StateRootMotionSettings},
+            Event, Machine, Parameter, PlayAnimation, PoseNode, State, Transition,
+        },
+        Animation, AnimationContainer, RootMotion, SyncMarker,
+    };
+    use crate::asset::io::FsResourceIo;
+    use crate::core::algebra::{UnitQuaternion, Vector3};
+    use crate::core::futures::executor::block_on;
+    use crate::core::uuid::Uuid;
+
+    #[test]
+    fn test_save_load() {
+        let mut machine = Machine::new();
+        machine.set_parameter("Run", Parameter::Rule(true));
+
+        let layer = &mut machine.layers_mut()[0];
+        let idle = layer.add_node(PoseNode::PlayAnimation(PlayAnimation::default()));
+        let idle = layer.add_state(State::new("Idle", idle));
+        let run = layer.add_node(PoseNode::PlayAnimation(PlayAnimation::default()));
+        let run = layer.add_state(State::new("Run", run));
+        layer.set_entry_state(idle);
+        layer.add_transition(Transition::new("Idle->Run", idle, run, 0.5, "Run"));
+
+        let path = std::env::temp_dir().join("fyrox_test_machine_save_load.absm");
+        machine.save(&path).unwrap();
+        let loaded = block_on(Machine::load_from_file(&path, &FsResourceIo)).unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(loaded.parameters(), machine.parameters());
+        assert_eq!(loaded.layers().len(), 1);
+        let layer = &loaded.layers()[0];
+        assert_eq!(layer.states().alive_count(), 2);
+        assert_eq!(layer.transitions().alive_count(), 1);
+        assert_eq!(layer.entry_state(), idle);
+    }
+
+    #[test]
+    fn test_any_state_and_exit_state() {
+        let mut machine = Machine::new();
+        machine.set_parameter("Die", Parameter::Rule(false));
+
+        let layer = &mut machine.layers_mut()[0];
+        let idle = layer.add_node(PoseNode::PlayAnimation(PlayAnimation::default()));
+        let idle = layer.add_state(State::new("Idle", idle));
+        let run = layer.add_node(PoseNode::PlayAnimation(PlayAnimation::default()));
+        let run = layer.add_state(State::new("Run", run));
+        let death = layer.add_node(PoseNode::PlayAnimation(PlayAnimation::default()));
+        let death = layer.add_state(State::new("Death", death));
+        let any = layer.add_state(State::new("Any State", Default::default()));
+        layer.set_entry_state(run);
+        layer.set_any_state(any);
+        layer.set_exit_state(death);
+        layer.add_transition(Transition::new("Idle->Run", idle, run, 0.1, "Run"));
+        layer.add_transition(Transition::new("Any->Death", any, death, 0.1, "Die"));
+
+        let mut animations = AnimationContainer::new();
+
+        machine.evaluate_pose(&mut animations, 0.05);
+        assert_eq!(machine.layers()[0].active_state(), run);
+
+        machine.set_parameter("Die", Parameter::Rule(true));
+        for _ in 0..4 {
+            machine.evaluate_pose(&mut animations, 0.05);
+        }
+
+        let layer = &mut machine.layers_mut()[0];
+        assert_eq!(layer.active_state(), death);
+        assert!(layer.is_finished());
+
+        let mut events = Vec::new();
+        while let Some(event) = layer.pop_event() {
+            events.push(event);
+        }
+        assert!(events.contains(&Event::ActiveStateChanged {
+            prev: run,
+            new: death
+        }));
+        assert_eq!(events.last(), Some(&Event::Finished(death)));
+    }
+
+    #[test]
+    fn test_transition_priority_and_exit_time() {
+        let mut animations = AnimationContainer::new();
+        let mut animation = Animation::default();
+        animation.set_time_slice(0.0..1.0);
+        let attack_animation = animations.add(animation);
+
+        let mut machine = Machine::new();
+        machine.set_parameter("Go", Parameter::Rule(true));
+
+        let layer = &mut machine.layers_mut()[0];
+        let attack = layer.add_node(PoseNode::PlayAnimation(PlayAnimation::new(
+            attack_animation,
+        )));
+        let attack = layer.add_state(State::new("Attack", attack));
+        let idle = layer.add_node(PoseNode::PlayAnimation(PlayAnimation::default()));
+        let idle = layer.add_state(State::new("Idle", idle));
+        let run = layer.add_node(PoseNode::PlayAnimation(PlayAnimation::default()));
+        let run = layer.add_state(State::new("Run", run));
+        layer.set_entry_state(attack);
+
+        let mut to_idle = Transition::new("Attack->Idle", attack, idle, 0.1, "Go");
+        to_idle.set_exit_time(Some(0.8));
+        let to_idle = layer.add_transition(to_idle);
+        let mut to_run = Transition::new("Attack->Run", attack, run, 0.1, "Go");
+        to_run.set_exit_time(Some(0.8));
+        to_run.set_priority(1);
+        let to_run = layer.add_transition(to_run);
+
+        // Exit time is not reached yet.
+        animations[attack_animation].set_time_position(0.5);
+        machine.evaluate_pose(&mut animations, 0.01);
+        assert_eq!(machine.layers()[0].active_state(), attack);
+        assert!(machine.layers()[0].active_transition().is_none());
+
+        // Both transitions could be activated, the one with higher priority wins.
+        animations[attack_animation].set_time_position(0.9);
+        machine.evaluate_pose(&mut animations, 0.01);
+        assert_eq!(machine.layers()[0].active_transition(), to_run);
+        assert_ne!(machine.layers()[0].active_transition(), to_idle);
+    }
+
+    #[test]
+    fn test_transition_interruption() {
+        let mut machine = Machine::new();
+        machine.set_parameter("Run", Parameter::Rule(false));
+        machine.set_parameter("Jump", Parameter::Rule(false));
+
+        let layer = &mut machine.layers_mut()[0];
+        let idle = layer.add_node(PoseNode::PlayAnimation(PlayAnimation::default()));
+        let idle = layer.add_state(State::new("Idle", idle));
+        let run = layer.add_node(PoseNode::PlayAnimation(PlayAnimation::default()));
+        let run = layer.add_state(State::new("Run", run));
+        let jump = layer.add_node(PoseNode::PlayAnimation(PlayAnimation::default()));
+        let jump = layer.add_state(State::new("Jump", jump));
+        layer.set_entry_state(idle);
+
+        let mut idle_to_run = Transition::new("Idle->Run", idle, run, 1.0, "Run");
+        idle_to_run.set_can_be_interrupted(true);
+        let idle_to_run = layer.add_transition(idle_to_run);
+        let run_to_jump =
+            layer.add_transition(Transition::new("Run->Jump", run, jump, 1.0, "Jump"));
+
+        let mut animations = AnimationContainer::new();
+
+        machine.set_parameter("Run", Parameter::Rule(true));
+        machine.evaluate_pose(&mut animations, 0.1);
+        assert_eq!(machine.layers()[0].active_transition(), idle_to_run);
+
+        machine.set_parameter("Jump", Parameter::Rule(true));
+        machine.evaluate_pose(&mut animations, 0.1);
+        assert_eq!(machine.layers()[0].active_transition(), run_to_jump);
+
+        // The interrupting transition is not interruptible itself.
+        machine.set_parameter("Run", Parameter::Rule(false));
+        machine.evaluate_pose(&mut animations, 0.1);
+        assert_eq!(machine.layers()[0].active_transition(), run_to_jump);
+    }
+
+    #[test]
+    fn test_state_events() {
+        let mut animations = AnimationContainer::new();
+        let mut animation = Animation::default();
+        animation.set_time_slice(0.0..1.0);
+        let walk_animation = animations.add(animation);
+
+        let mut machine = Machine::new();
+        machine.set_parameter("Stop", Parameter::Rule(false));
+
+        let layer = &mut machine.layers_mut()[0];
+        let walk = layer.add_node(PoseNode::PlayAnimation(PlayAnimation::new(walk_animation)));
+        let mut walk_state = State::new("Walk", walk);
+        walk_state.events = vec![
+            StateEvent::new("Footstep", StateEventTrigger::Time(0.5)),
+            StateEvent::new("StopWalking", StateEventTrigger::Leave),
+        ];
+        let walk = layer.add_state(walk_state);
+        let idle = layer.add_node(PoseNode::PlayAnimation(PlayAnimation::default()));
+        let mut idle_state = State::new("Idle", idle);
+        idle_state.events = vec![StateEvent::new("StartIdle", StateEventTrigger::Enter)];
+        let idle = layer.add_state(idle_state);
+        layer.set_entry_state(walk);
+        layer.add_transition(Transition::new("Walk->Idle", walk, idle, 0.1, "Stop"));
+
+        let collect_events = |machine: &mut Machine| {
+            let mut events = Vec::new();
+            while let Some(event) = machine.layers_mut()[0].pop_event() {
+                if let Event::StateEvent { state, name } = event {
+                    events.push((state, name));
+                }
+            }
+            events
+        };
+
+        for time in [0.2, 0.4] {
+            animations[walk_animation].set_time_position(time);
+            machine.evaluate_pose(&mut animations, 0.01);
+        }
+        assert!(collect_events(&mut machine).is_empty());
+
+        animations[walk_animation].set_time_position(0.6);
+        machine.evaluate_pose(&mut animations, 0.01);
+        assert_eq!(
+            collect_events(&mut machine),
+            vec![(walk, "Footstep".to_string())]
+        );
+
+        machine.set_parameter("Stop", Parameter::Rule(true));
+        machine.evaluate_pose(&mut animations, 0.01);
+        assert_eq!(
+            collect_events(&mut machine),
+            vec![
+                (walk, "StopWalking".to_string()),
+                (idle, "StartIdle".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_state_root_motion_filter() {
+        let root_motion = RootMotion {
+            delta_position: Vector3::new(1.0, 2.0, 3.0),
+            delta_rotation: UnitQuaternion::from_axis_angle(&Vector3::y_axis(), 1.0),
+            ..Default::default()
+        };
+
+        let filtered = StateRootMotionSettings {
+            ignore_y_movement: true,
+            extract_rotation: false,
+            ..Default::default()
+        }
+        .filter(&root_motion);
+        assert_eq!(filtered.delta_position, Vector3::new(1.0, 0.0, 3.0));
+        assert_eq!(filtered.delta_rotation, UnitQuaternion::default());
+
+        let filtered = StateRootMotionSettings {
+            extract_translation: false,
+            ..Default::default()
+        }
+        .filter(&root_motion);
+        assert_eq!(filtered.delta_position, Vector3::default());
+        assert_eq!(filtered.delta_rotation, root_motion.delta_rotation);
+    }
+
+    #[test]
+    fn test_state_sync_markers() {
+        let mut animations = AnimationContainer::new();
+        let mut walk_animation = Animation::default();
+        walk_animation.set_time_slice(0.0..2.0);
+        let walk_animation = animations.add(walk_animation);
+        let mut run_animation = Animation::default();
+        run_animation.set_time_slice(0.0..1.0);
+        let run_animation = animations.add(run_animation);
+
+        let mut machine = Machine::new();
+        machine.set_parameter("Run", Parameter::Rule(false));
+
+        let layer = &mut machine.layers_mut()[0];
+        let walk_node = layer.add_node(PoseNode::PlayAnimation(PlayAnimation::new(walk_animation)));
+        let mut walk = State::new("Walk", walk_node);
+        walk.sync_markers = vec![
+            SyncMarker::new(Uuid::new_v4(), "Left", 0.25),
+            SyncMarker::new(Uuid::new_v4(), "Right", 0.75),
+        ];
+        let walk = layer.add_state(walk);
+        layer.node_mut(walk_node).parent_state = walk;
+        let run_node = layer.add_node(PoseNode::PlayAnimation(PlayAnimation::new(run_animation)));
+        let mut run = State::new("Run", run_node);
+        run.sync_markers = vec![
+            SyncMarker::new(Uuid::new_v4(), "Left", 0.1),
+            SyncMarker::new(Uuid::new_v4(), "Right", 0.6),
+        ];
+        let run = layer.add_state(run);
+        layer.node_mut(run_node).parent_state = run;
+        layer.set_entry_state(walk);
+        layer.add_transition(Transition::new("Walk->Run", walk, run, 1.0, "Run"));
+
+        // Halfway between the markers of the walk state.
+        animations[walk_animation].set_time_position(1.0);
+
+        machine.set_parameter("Run", Parameter::Rule(true));
+        machine.evaluate_pose(&mut animations, 0.0);
+        machine.evaluate_pose(&mut animations, 0.0);
+
+        // The run animation must be moved to the same phase - halfway between its markers.
+        assert!((animations[run_animation].normalized_time_position() - 0.35).abs() <= 0.0001);
+    }
+
+    #[test]
+    fn test_state_speed() {
+        let mut animations = AnimationContainer::new();
+        let mut animation = Animation::default();
+        animation.set_time_slice(0.0..10.0);
+        let slow_animation = animations.add(animation.clone());
+        let other_animation = animations.add(animation);
+
+        let mut machine = Machine::new();
+        let layer = &mut machine.layers_mut()[0];
+        let slow_node = layer.add_node(PoseNode::PlayAnimation(PlayAnimation::new(slow_animation)));
+        let mut slow = State::new("Slow", slow_node);
+        slow.speed = 0.5;
+        let slow = layer.add_state(slow);
+        layer.node_mut(slow_node).parent_state = slow;
+        let other_node =
+            layer.add_node(PoseNode::PlayAnimation(PlayAnimation::new(other_animation)));
+        let other = layer.add_state(State::new("Other", other_node));
+        layer.node_mut(other_node).parent_state = other;
+        layer.set_entry_state(slow);
+
+        assert_eq!(State::default().speed, 1.0);
+
+        // Emulate the animation player, that advances all the animations before the machine.
+        for _ in 0..4 {
+            for animation in [slow_animation, other_animation] {
+                animations[animation].tick(0.5);
+            }
+            machine.evaluate_pose(&mut animations, 0.5);
+        }
+
+        assert!((animations[slow_animation].time_position() - 1.0).abs() <= 0.0001);
+        assert!((animations[other_animation].time_position() - 2.0).abs() <= 0.0001);
+    }
+}
@@ -229,6 +229,7 @@ pub struct BlendAnimationsByIndex {
     pub base: BasePoseNode,
 
     /// A name of index parameter that will be used to switch between input poses.
+    #[reflect(tag = "ParameterName")]
     pub index_parameter: String,
 
     /// A set of input poses.
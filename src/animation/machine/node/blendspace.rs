@@ -55,7 +55,7 @@ pub struct BlendSpace {
     #[reflect(setter = "set_snap_step")]
     snap_step: Vector2<f32>,
 
-    #[reflect(setter = "set_sampling_parameter")]
+    #[reflect(setter = "set_sampling_parameter", tag = "ParameterName")]
     sampling_parameter: String,
 
     #[reflect(hidden)]
@@ -325,18 +325,14 @@ impl BlendSpace {
             return Some([(0, 1.0), (0, 0.0), (0, 0.0)]);
         }
 
-        // Check if there's an edge that contains a projection of the sampling point.
-        if self.points.len() == 2 {
-            let edge = self.points[1].position - self.points[0].position;
-            let to_point = sampling_point - self.points[0].position;
-            let t = to_point.dot(&edge) / edge.dot(&edge);
-            if (0.0..=1.0).contains(&t) {
-                return Some([(0, (1.0 - t)), (1, t), (0, 0.0)]);
-            }
-        }
-
         let triangles = &self.triangles;
 
+        // There are no triangles when all the points lie on a single line (two points is a special
+        // case of this), which makes the blend space one-dimensional.
+        if triangles.is_empty() {
+            return self.fetch_weights_1d(sampling_point);
+        }
+
         // Try to find a triangle that contains the sampling point.
         for triangle in triangles.iter() {
             let ia = triangle[0] as usize;
@@ -393,6 +389,55 @@ impl BlendSpace {
         weights
     }
 
+    /// Calculates weights for a blend space with the points lying on a single line. The sampling point is
+    /// projected on the line and the weights are calculated using the closest points on both sides of the
+    /// projection. If the projection lies outside of the points, the closest outermost point is used.
+    fn fetch_weights_1d(&self, sampling_point: Vector2<f32>) -> Option<[(usize, f32); 3]> {
+        let origin = self.points[0].position;
+        let axis = self
+            .points
+            .iter()
+            .map(|p| p.position - origin)
+            .max_by(|a, b| a.norm_squared().total_cmp(&b.norm_squared()))?;
+        let axis_length_squared = axis.norm_squared();
+        if axis_length_squared <= f32::EPSILON {
+            return Some([(0, 1.0), (0, 0.0), (0, 0.0)]);
+        }
+
+        let project = |position: Vector2<f32>| (position - origin).dot(&axis) / axis_length_squared;
+
+        let mut sorted = self
+            .points
+            .iter()
+            .enumerate()
+            .map(|(i, p)| (i, project(p.position)))
+            .collect::<Vec<_>>();
+        sorted.sort_by(|(_, a), (_, b)| a.total_cmp(b));
+
+        let t = project(sampling_point);
+
+        let (first, first_t) = sorted[0];
+        if t < first_t {
+            return Some([(first, 1.0), (first, 0.0), (first, 0.0)]);
+        }
+
+        for pair in sorted.windows(2) {
+            let (a, a_t) = pair[0];
+            let (b, b_t) = pair[1];
+            if t <= b_t {
+                let k = if b_t - a_t > f32::EPSILON {
+                    (t - a_t) / (b_t - a_t)
+                } else {
+                    0.0
+                };
+                return Some([(a, 1.0 - k), (b, k), (a, 0.0)]);
+            }
+        }
+
+        let (last, _) = sorted[sorted.len() - 1];
+        Some([(last, 1.0), (last, 0.0), (last, 0.0)])
+    }
+
     fn triangulate(&mut self) -> bool {
         self.triangles.clear();
 
@@ -514,4 +559,42 @@ mod test {
             Some([(0, 0.0), (1, 1.0), (0, 0.0)])
         );
     }
+
+    #[test]
+    fn test_one_dimensional_blend_space_sampling() {
+        let mut blend_space = BlendSpace::default();
+
+        blend_space.set_points(
+            [0.0, 1.0, 0.5]
+                .into_iter()
+                .map(|x| BlendSpacePoint {
+                    position: Vector2::new(x, 0.0),
+                    pose_source: Default::default(),
+                })
+                .collect(),
+        );
+
+        assert!(blend_space.triangles.is_empty());
+
+        assert_eq!(
+            blend_space.fetch_weights(Vector2::new(0.25, 0.0)),
+            Some([(0, 0.5), (2, 0.5), (0, 0.0)])
+        );
+
+        assert_eq!(
+            blend_space.fetch_weights(Vector2::new(0.75, 0.3)),
+            Some([(2, 0.5), (1, 0.5), (2, 0.0)])
+        );
+
+        // Sampling points outside of the line segment are clamped to the outermost points.
+        assert_eq!(
+            blend_space.fetch_weights(Vector2::new(-1.0, 0.0)),
+            Some([(0, 1.0), (0, 0.0), (0, 0.0)])
+        );
+
+        assert_eq!(
+            blend_space.fetch_weights(Vector2::new(2.0, 0.0)),
+            Some([(1, 1.0), (1, 0.0), (1, 0.0)])
+        );
+    }
 }
@@ -6,7 +6,8 @@ use crate::{
     animation::{
         machine::{
             node::{blend::BlendAnimations, blendspace::BlendSpace, play::PlayAnimation},
-            BlendAnimationsByIndex, BlendPose, IndexedBlendInput, ParameterContainer, State,
+            BlendAnimationsByIndex, BlendPose, IndexedBlendInput, Parameter, ParameterContainer,
+            State,
         },
         Animation, AnimationContainer, AnimationPose,
     },
@@ -91,6 +92,68 @@ impl PoseNode {
             Self::BlendSpace(blend_space) => blend_space.children(),
         }
     }
+
+    /// Returns a handle of an animation that has the largest weight in the sub-tree of the node. It is used as
+    /// a leader when synchronizing phases of blended animations, see [`crate::animation::SyncMarker`] docs for
+    /// more info.
+    pub fn dominant_animation(
+        &self,
+        nodes: &Pool<PoseNode>,
+        params: &ParameterContainer,
+    ) -> Option<Handle<Animation>> {
+        let source = match self {
+            Self::PlayAnimation(play_animation) => return Some(play_animation.animation),
+            Self::BlendAnimations(blend_animations) => blend_animations
+                .pose_sources
+                .iter()
+                .filter_map(|s| s.weight.value(params).map(|w| (s.pose_source, w)))
+                .max_by(|(_, w1), (_, w2)| w1.total_cmp(w2))
+                .map(|(source, _)| source),
+            Self::BlendAnimationsByIndex(blend_by_index) => {
+                match params.get(&blend_by_index.index_parameter) {
+                    Some(Parameter::Index(current_index)) => {
+                        let index = match blend_by_index.prev_index.get() {
+                            Some(prev_index) if prev_index != *current_index => {
+                                let blend_time = blend_by_index
+                                    .inputs
+                                    .get(*current_index as usize)
+                                    .map_or(0.0, |input| input.blend_time);
+                                if blend_time > 0.0
+                                    && blend_by_index.blend_time.get() / blend_time < 0.5
+                                {
+                                    prev_index
+                                } else {
+                                    *current_index
+                                }
+                            }
+                            _ => *current_index,
+                        };
+                        blend_by_index
+                            .inputs
+                            .get(index as usize)
+                            .map(|input| input.pose_source)
+                    }
+                    _ => None,
+                }
+            }
+            Self::BlendSpace(blend_space) => match params.get(blend_space.sampling_parameter()) {
+                Some(Parameter::SamplingPoint(sampling_point)) => blend_space
+                    .fetch_weights(*sampling_point)
+                    .and_then(|weights| {
+                        weights
+                            .iter()
+                            .max_by(|(_, w1), (_, w2)| w1.total_cmp(w2))
+                            .and_then(|(index, _)| blend_space.points().get(*index))
+                    })
+                    .map(|point| point.pose_source),
+                _ => None,
+            },
+        };
+
+        source
+            .and_then(|source| nodes.try_borrow(source))
+            .and_then(|source| source.dominant_animation(nodes, params))
+    }
 }
 
 macro_rules! static_dispatch {
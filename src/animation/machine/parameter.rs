@@ -8,6 +8,10 @@ use std::{
 };
 use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
 
+/// Reflection tag (`#[reflect(tag = "ParameterName")]`) of string properties, that reference a parameter of a
+/// machine by its name. The editor shows a list of the parameters of the machine for such properties.
+pub const PARAMETER_NAME_TAG: &str = "ParameterName";
+
 /// Machine parameter is a named variable of a fixed type. Machine uses various parameters for specific actions. For example
 /// Rule parameter is used to check where transition from a state to state is possible, `Weight` parameters are used to be
 /// a source real numbers that are used to calculate blend weights, etc.
@@ -19,6 +23,10 @@ pub enum Parameter {
     /// Rule parameter is used to check where transition from a state to state is possible.
     Rule(bool),
 
+    /// Trigger parameter is a rule, that is automatically reset to `false` when a transition that uses it is
+    /// activated. It is useful for one-shot actions, such as jumps or attacks.
+    Trigger(bool),
+
     /// An index of a pose.
     Index(u32),
 
@@ -39,7 +47,7 @@ pub enum PoseWeight {
     Constant(f32),
 
     /// Reference to Weight parameter with given name.
-    Parameter(String),
+    Parameter(#[reflect(tag = "ParameterName")] String),
 }
 
 impl PoseWeight {
@@ -155,6 +163,11 @@ impl ParameterContainer {
         })
     }
 
+    /// Returns an iterator over all parameter definitions in the container.
+    pub fn iter(&self) -> impl Iterator<Item = &ParameterDefinition> {
+        self.parameters.parameters.iter()
+    }
+
     /// Tries to borrow a parameter by its name. The method has O(1) complexity.
     pub fn get(&self, name: &str) -> Option<&Parameter> {
         self.update_index();
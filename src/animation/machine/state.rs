@@ -3,7 +3,8 @@
 use crate::{
     animation::{
         machine::{AnimationPoseSource, ParameterContainer, PoseNode},
-        Animation, AnimationContainer, AnimationPose,
+        sync::{self, SyncMarker, SyncPhase},
+        Animation, AnimationContainer, AnimationPose, RootMotion,
     },
     core::{
         algebra::Vector2,
@@ -95,9 +96,101 @@ impl StateAction {
     }
 }
 
+/// Defines a moment when a [`StateEvent`] will be emitted.
+#[derive(
+    Default, Debug, Visit, Reflect, Clone, PartialEq, EnumVariantNames, EnumString, AsRefStr,
+)]
+pub enum StateEventTrigger {
+    /// The event is emitted when entering the state.
+    #[default]
+    Enter,
+    /// The event is emitted when leaving the state.
+    Leave,
+    /// The event is emitted when the dominant animation of the state passes the given normalized time (in `[0; 1]`
+    /// range). It could be used to react on some specific moments of animations, such as footsteps or attack
+    /// windows.
+    Time(f32),
+}
+
+/// A named event of a state. When triggered, it is emitted as [`super::Event::StateEvent`] by the layer of the
+/// state, so gameplay code could react to it.
+#[derive(Default, Debug, Visit, Reflect, Clone, PartialEq)]
+pub struct StateEvent {
+    /// Name of the event.
+    pub name: String,
+    /// A moment when the event will be emitted.
+    pub trigger: StateEventTrigger,
+}
+
+impl StateEvent {
+    /// Creates a new state event with the given name and trigger.
+    pub fn new(name: &str, trigger: StateEventTrigger) -> Self {
+        Self {
+            name: name.to_owned(),
+            trigger,
+        }
+    }
+}
+
+/// Root motion settings of a state. They are applied on top of the root motion of the animations of the state
+/// and allow to filter out some parts of the motion when the state is active. For example, a jump state may
+/// ignore vertical movement, while a turn-in-place state may extract only rotations.
+#[derive(Debug, Visit, Reflect, Clone, PartialEq)]
+pub struct StateRootMotionSettings {
+    /// Keeps translational part of the motion.
+    pub extract_translation: bool,
+    /// Keeps rotational part of the motion.
+    pub extract_rotation: bool,
+    /// Removes X part of the translational part of the motion.
+    pub ignore_x_movement: bool,
+    /// Removes Y part of the translational part of the motion.
+    pub ignore_y_movement: bool,
+    /// Removes Z part of the translational part of the motion.
+    pub ignore_z_movement: bool,
+}
+
+impl Default for StateRootMotionSettings {
+    fn default() -> Self {
+        Self {
+            extract_translation: true,
+            extract_rotation: true,
+            ignore_x_movement: false,
+            ignore_y_movement: false,
+            ignore_z_movement: false,
+        }
+    }
+}
+
+impl StateRootMotionSettings {
+    /// Returns a copy of the given root motion with the parts, that are disabled by the settings, removed.
+    pub fn filter(&self, root_motion: &RootMotion) -> RootMotion {
+        let mut filtered = root_motion.clone();
+
+        if self.extract_translation {
+            if self.ignore_x_movement {
+                filtered.delta_position.x = 0.0;
+            }
+            if self.ignore_y_movement {
+                filtered.delta_position.y = 0.0;
+            }
+            if self.ignore_z_movement {
+                filtered.delta_position.z = 0.0;
+            }
+        } else {
+            filtered.delta_position = Default::default();
+        }
+
+        if !self.extract_rotation {
+            filtered.delta_rotation = Default::default();
+        }
+
+        filtered
+    }
+}
+
 /// State is a final "container" for animation pose. It has backing pose node which provides a set of values.
 /// States can be connected with each other using _transitions_, states with transitions form a state graph.
-#[derive(Default, Debug, Visit, Clone, Reflect, PartialEq)]
+#[derive(Debug, Visit, Clone, Reflect, PartialEq)]
 pub struct State {
     /// Position of state on the canvas. It is editor-specific data.
     pub position: Vector2<f32>,
@@ -113,6 +206,30 @@ pub struct State {
     #[visit(optional)]
     pub on_leave_actions: Vec<StateActionWrapper>,
 
+    /// A set of events that will be emitted by the state. See [`StateEvent`] docs for more info.
+    #[visit(optional)]
+    pub events: Vec<StateEvent>,
+
+    /// Root motion settings of the state. `None` - the root motion of the animations of the state is used as is.
+    #[visit(optional)]
+    pub root_motion: Option<StateRootMotionSettings>,
+
+    /// Sync markers of the state, they are used to align phases of the animations when blending with other
+    /// states. Unlike the markers of animations, time positions of the markers are normalized (`[0; 1]` range)
+    /// and relative to the dominant animation of the state, so the same markers could be used with any
+    /// animations. When a transition blends two states with matching markers (the same names in the same order),
+    /// the animations of the states are time-warped so their phases are aligned. See [`SyncMarker`] docs for more
+    /// info.
+    #[visit(optional)]
+    pub sync_markers: Vec<SyncMarker>,
+
+    /// Playback speed multiplier of the animations of the state, it is applied on top of the speed of each
+    /// animation while the state is active (or blended by a transition). For example, `0.5` plays the animations
+    /// of the state twice as slow.
+    #[visit(optional)]
+    #[reflect(min_value = 0.0, step = 0.05)]
+    pub speed: f32,
+
     /// Root node of the state that provides the state with animation data.
     #[reflect(read_only)]
     pub root: Handle<PoseNode>,
@@ -124,6 +241,12 @@ impl NameProvider for State {
     }
 }
 
+impl Default for State {
+    fn default() -> Self {
+        Self::new("", Handle::NONE)
+    }
+}
+
 impl State {
     /// Creates new instance of state with a given pose.
     pub fn new(name: &str, root: Handle<PoseNode>) -> Self {
@@ -132,10 +255,39 @@ impl State {
             name: name.to_owned(),
             on_enter_actions: Default::default(),
             on_leave_actions: Default::default(),
+            events: Default::default(),
+            root_motion: None,
+            sync_markers: Default::default(),
+            speed: 1.0,
             root,
         }
     }
 
+    fn sorted_sync_markers(&self) -> Vec<(&str, f32)> {
+        sync::sorted_markers(self.sync_markers.iter())
+    }
+
+    /// Calculates the phase of the state at the given normalized time position using the sync markers of the
+    /// state. Returns `None` if the state does not have any sync markers.
+    pub fn sync_phase(&self, normalized_time: f32) -> Option<SyncPhase> {
+        sync::phase_at(&self.sorted_sync_markers(), normalized_time, 1.0)
+    }
+
+    /// Returns a normalized time position that matches the given phase, or `None` if the phase cannot be matched
+    /// with the sync markers of the state.
+    pub fn normalized_time_of_phase(&self, phase: &SyncPhase) -> Option<f32> {
+        sync::time_of_phase(&self.sorted_sync_markers(), phase, 1.0)
+    }
+
+    /// Returns root motion of the given pose of the state with the root motion settings of the state applied.
+    pub fn filter_root_motion(&self, pose: &AnimationPose) -> Option<RootMotion> {
+        let root_motion = pose.root_motion()?;
+        Some(match self.root_motion.as_ref() {
+            Some(settings) => settings.filter(root_motion),
+            None => root_motion.clone(),
+        })
+    }
+
     /// Returns a final pose of the state.
     pub fn pose<'a>(&self, nodes: &'a Pool<PoseNode>) -> Option<Ref<'a, AnimationPose>> {
         nodes.try_borrow(self.root).map(|root| root.pose())
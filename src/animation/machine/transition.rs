@@ -5,7 +5,7 @@ use crate::{
         machine::{Parameter, ParameterContainer, State},
         Animation, AnimationContainer,
     },
-    core::{pool::Handle, reflect::prelude::*, visitor::prelude::*},
+    core::{algebra::Vector2, pool::Handle, reflect::prelude::*, visitor::prelude::*},
     utils::NameProvider,
 };
 use std::any::{type_name, Any, TypeId};
@@ -67,6 +67,7 @@ macro_rules! define_two_args_node {
                         max_value: None,
                         step: None,
                         precision: None,
+                        tag: "",
                         doc: "",
                     },
                     FieldInfo {
@@ -82,7 +83,9 @@ macro_rules! define_two_args_node {
                         min_value: None,
                         max_value: None,
                         step: None,
-                        precision: None,doc: "",
+                        precision: None,
+                        tag: "",
+                        doc: "",
                     },
                 ])
             }
@@ -159,6 +162,75 @@ define_two_args_node!(
     XorNode
 );
 
+/// An operator that is used to compare a parameter with a value, see [`CompareNode`].
+#[derive(
+    Debug,
+    Visit,
+    Reflect,
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    Default,
+    AsRefStr,
+    EnumString,
+    EnumVariantNames,
+)]
+pub enum ComparisonOperator {
+    /// `parameter < value`
+    Less,
+    /// `parameter <= value`
+    LessOrEqual,
+    /// `parameter == value`
+    #[default]
+    Equal,
+    /// `parameter != value`
+    NotEqual,
+    /// `parameter >= value`
+    GreaterOrEqual,
+    /// `parameter > value`
+    Greater,
+}
+
+impl ComparisonOperator {
+    /// Compares two numbers using the operator.
+    pub fn compare(self, lhs: f32, rhs: f32) -> bool {
+        match self {
+            Self::Less => lhs < rhs,
+            Self::LessOrEqual => lhs <= rhs,
+            Self::Equal => lhs == rhs,
+            Self::NotEqual => lhs != rhs,
+            Self::GreaterOrEqual => lhs >= rhs,
+            Self::Greater => lhs > rhs,
+        }
+    }
+}
+
+/// Compares a value of `Weight` or `Index` parameter with a constant value. Output value will be `false` if the
+/// parameter is not found or has any other type.
+#[derive(Debug, Visit, Reflect, Clone, PartialEq, Default)]
+pub struct CompareNode {
+    /// A name of the parameter to compare.
+    #[reflect(tag = "ParameterName")]
+    pub parameter: String,
+    /// Comparison operator.
+    pub operator: ComparisonOperator,
+    /// A value to compare the parameter with.
+    pub value: f32,
+}
+
+impl CompareNode {
+    /// Compares the parameter with the value.
+    pub fn calculate_value(&self, parameters: &ParameterContainer) -> bool {
+        let parameter = match parameters.get(&self.parameter) {
+            Some(Parameter::Weight(weight)) => *weight,
+            Some(Parameter::Index(index)) => *index as f32,
+            _ => return false,
+        };
+        self.operator.compare(parameter, self.value)
+    }
+}
+
 /// Calculates logical NOT of an argument. Output value will be `true` if the value of the argument is `false`.
 #[derive(Debug, Clone, PartialEq)]
 pub struct NotNode {
@@ -208,6 +280,7 @@ impl Reflect for NotNode {
             max_value: None,
             step: None,
             precision: None,
+            tag: "",
             doc: "",
         }])
     }
@@ -287,8 +360,9 @@ impl Reflect for NotNode {
 /// ```
 #[derive(Debug, Visit, Clone, Reflect, PartialEq, AsRefStr, EnumString, EnumVariantNames)]
 pub enum LogicNode {
-    /// Fetches a value of `Rule` parameter and returns its value. `false` if the parameter is not found.
-    Parameter(String),
+    /// Fetches a value of `Rule` or `Trigger` parameter and returns its value. `false` if the parameter is not found.
+    /// Trigger parameters are reset to `false` when the transition is activated.
+    Parameter(#[reflect(tag = "ParameterName")] String),
     /// Calculates logical AND between two arguments. Output value will be `true` iff both of the arguments is `true`.
     And(AndNode),
     /// Calculates logical OR between two arguments. Output value will be `true` iff any of the arguments is `true`.
@@ -299,6 +373,8 @@ pub enum LogicNode {
     Not(NotNode),
     /// Returns `true` if the animation has ended, `false` - otherwise.
     IsAnimationEnded(Handle<Animation>),
+    /// Compares a value of `Weight` or `Index` parameter with a constant value, for example `Speed > 0.5`.
+    Compare(CompareNode),
 }
 
 impl Default for LogicNode {
@@ -316,7 +392,7 @@ impl LogicNode {
     ) -> bool {
         match self {
             LogicNode::Parameter(rule_name) => parameters.get(rule_name).map_or(false, |p| {
-                if let Parameter::Rule(rule_value) = p {
+                if let Parameter::Rule(rule_value) | Parameter::Trigger(rule_value) = p {
                     *rule_value
                 } else {
                     false
@@ -341,6 +417,26 @@ impl LogicNode {
             LogicNode::IsAnimationEnded(animation) => animations
                 .try_get(*animation)
                 .map_or(true, |a| a.has_ended()),
+            LogicNode::Compare(compare) => compare.calculate_value(parameters),
+        }
+    }
+
+    /// Collects names of all `Trigger` parameters used by the node and its descendants.
+    pub fn collect_triggers(&self, parameters: &ParameterContainer, triggers: &mut Vec<String>) {
+        match self {
+            LogicNode::Parameter(name) => {
+                if let Some(Parameter::Trigger(_)) = parameters.get(name) {
+                    triggers.push(name.clone());
+                }
+            }
+            LogicNode::And(AndNode { lhs, rhs })
+            | LogicNode::Or(OrNode { lhs, rhs })
+            | LogicNode::Xor(XorNode { lhs, rhs }) => {
+                lhs.collect_triggers(parameters, triggers);
+                rhs.collect_triggers(parameters, triggers);
+            }
+            LogicNode::Not(NotNode { lhs }) => lhs.collect_triggers(parameters, triggers),
+            LogicNode::IsAnimationEnded(_) | LogicNode::Compare(_) => (),
         }
     }
 }
@@ -372,6 +468,26 @@ pub struct Transition {
 
     /// 0 - evaluates `src` pose, 1 - `dest`, 0..1 - blends `src` and `dest`
     pub(crate) blend_factor: f32,
+
+    #[reflect(
+        description = "Transitions with higher priority are checked first when a few transitions could be activated at once."
+    )]
+    pub(crate) priority: i32,
+
+    #[reflect(
+        description = "Whether the transition could be interrupted by a transition from its destination state."
+    )]
+    pub(crate) can_be_interrupted: bool,
+
+    #[reflect(
+        description = "Normalized time (0..1) of the dominant animation of the source state after which the transition \
+        could be activated. None - the transition could be activated at any time."
+    )]
+    pub(crate) exit_time: Option<f32>,
+
+    /// Intermediate points of the transition on the canvas. It is editor-specific data.
+    #[reflect(hidden)]
+    pub(crate) reroute_points: Vec<Vector2<f32>>,
 }
 
 impl Visit for Transition {
@@ -383,6 +499,12 @@ impl Visit for Transition {
         self.source.visit("Source", &mut guard)?;
         self.dest.visit("Dest", &mut guard)?;
         self.blend_factor.visit("BlendFactor", &mut guard)?;
+        let _ = self.priority.visit("Priority", &mut guard); // Backward compatibility.
+        let _ = self
+            .can_be_interrupted
+            .visit("CanBeInterrupted", &mut guard); // Backward compatibility.
+        let _ = self.exit_time.visit("ExitTime", &mut guard); // Backward compatibility.
+        let _ = self.reroute_points.visit("ReroutePoints", &mut guard); // Backward compatibility.
 
         if guard.is_reading() {
             if self.condition.visit("Condition", &mut guard).is_err() {
@@ -433,6 +555,10 @@ impl Transition {
             dest,
             blend_factor: 0.0,
             condition: LogicNode::Parameter(rule.to_owned()),
+            priority: 0,
+            can_be_interrupted: false,
+            exit_time: None,
+            reroute_points: Default::default(),
         }
     }
 
@@ -470,6 +596,51 @@ impl Transition {
         &self.condition
     }
 
+    /// Sets new priority of the transition. When a few transitions could be activated at the same time, the one
+    /// with the highest priority wins. Transitions with the same priority are checked in order of their creation.
+    pub fn set_priority(&mut self, priority: i32) {
+        self.priority = priority;
+    }
+
+    /// Returns current priority of the transition.
+    pub fn priority(&self) -> i32 {
+        self.priority
+    }
+
+    /// Defines whether the transition could be interrupted by another transition that starts from its
+    /// destination state (or from the "any state" of the layer). By default, transitions cannot be interrupted.
+    pub fn set_can_be_interrupted(&mut self, can_be_interrupted: bool) {
+        self.can_be_interrupted = can_be_interrupted;
+    }
+
+    /// Returns `true` if the transition could be interrupted, `false` - otherwise.
+    pub fn can_be_interrupted(&self) -> bool {
+        self.can_be_interrupted
+    }
+
+    /// Sets normalized (in `[0; 1]` range) exit time of the transition. The transition could be activated only
+    /// when the dominant animation of its source state has reached the exit time. `None` disables the check, so
+    /// the transition could be activated at any time.
+    pub fn set_exit_time(&mut self, exit_time: Option<f32>) {
+        self.exit_time = exit_time.map(|t| t.clamp(0.0, 1.0));
+    }
+
+    /// Returns current normalized exit time of the transition.
+    pub fn exit_time(&self) -> Option<f32> {
+        self.exit_time
+    }
+
+    /// Sets new intermediate points of the transition, they are used by the editor to route the transition
+    /// around other states. It is editor-specific data and it does not affect the transition at runtime.
+    pub fn set_reroute_points(&mut self, reroute_points: Vec<Vector2<f32>>) {
+        self.reroute_points = reroute_points;
+    }
+
+    /// Returns current intermediate points of the transition.
+    pub fn reroute_points(&self) -> &[Vector2<f32>] {
+        &self.reroute_points
+    }
+
     /// Returns true if the transition from the source to the destination state was finished.
     #[inline]
     pub fn is_done(&self) -> bool {
@@ -495,3 +666,114 @@ impl Transition {
         self.blend_factor = self.elapsed_time / self.transition_time;
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        animation::{
+            machine::{
+                node::{blend::BlendAnimationsByIndex, blendspace::BlendSpace},
+                parameter::PARAMETER_NAME_TAG,
+                transition::{AndNode, CompareNode, ComparisonOperator, LogicNode, NotNode},
+                Parameter, ParameterContainer, PoseWeight,
+            },
+            AnimationContainer,
+        },
+        core::reflect::prelude::*,
+    };
+
+    #[test]
+    fn test_triggers() {
+        let mut parameters = ParameterContainer::default();
+        parameters.add("Jump", Parameter::Trigger(true));
+        parameters.add("Grounded", Parameter::Rule(true));
+        parameters.add("Attack", Parameter::Trigger(false));
+
+        // Jump && Grounded && !Attack
+        let logic = LogicNode::And(AndNode {
+            lhs: Box::new(LogicNode::Parameter("Jump".into())),
+            rhs: Box::new(LogicNode::And(AndNode {
+                lhs: Box::new(LogicNode::Parameter("Grounded".into())),
+                rhs: Box::new(LogicNode::Not(NotNode {
+                    lhs: Box::new(LogicNode::Parameter("Attack".into())),
+                })),
+            })),
+        });
+        assert!(logic.calculate_value(&parameters, &AnimationContainer::default()));
+
+        let mut triggers = Vec::new();
+        logic.collect_triggers(&parameters, &mut triggers);
+        assert_eq!(triggers, vec!["Jump".to_string(), "Attack".to_string()]);
+    }
+
+    #[test]
+    fn test_compare() {
+        let mut parameters = ParameterContainer::default();
+        parameters.add("Speed", Parameter::Weight(0.75));
+        parameters.add("Weapon", Parameter::Index(2));
+        parameters.add("Run", Parameter::Rule(true));
+
+        let compare = |parameter: &str, operator, value| {
+            LogicNode::Compare(CompareNode {
+                parameter: parameter.into(),
+                operator,
+                value,
+            })
+            .calculate_value(&parameters, &AnimationContainer::default())
+        };
+
+        assert!(compare("Speed", ComparisonOperator::Greater, 0.5));
+        assert!(!compare("Speed", ComparisonOperator::Less, 0.5));
+        assert!(compare("Weapon", ComparisonOperator::Equal, 2.0));
+        assert!(compare("Weapon", ComparisonOperator::NotEqual, 1.0));
+        assert!(compare("Weapon", ComparisonOperator::LessOrEqual, 2.0));
+        assert!(!compare("Run", ComparisonOperator::Equal, 1.0));
+        assert!(!compare("Missing", ComparisonOperator::NotEqual, 0.0));
+    }
+
+    #[test]
+    fn test_parameter_name_tags() {
+        fn tag(object: &dyn Reflect, name: &str) -> String {
+            let mut tag = String::new();
+            object.fields_info(&mut |fields| {
+                tag = fields
+                    .iter()
+                    .find(|field| field.name == name)
+                    .map(|field| field.tag.to_string())
+                    .unwrap_or_default();
+            });
+            tag
+        }
+
+        assert_eq!(
+            tag(
+                &LogicNode::Parameter(Default::default()),
+                LogicNode::PARAMETER_F_0
+            ),
+            PARAMETER_NAME_TAG
+        );
+        assert_eq!(
+            tag(
+                &PoseWeight::Parameter(Default::default()),
+                PoseWeight::PARAMETER_F_0
+            ),
+            PARAMETER_NAME_TAG
+        );
+        assert_eq!(
+            tag(&CompareNode::default(), CompareNode::PARAMETER),
+            PARAMETER_NAME_TAG
+        );
+        assert_eq!(
+            tag(
+                &BlendAnimationsByIndex::default(),
+                BlendAnimationsByIndex::INDEX_PARAMETER
+            ),
+            PARAMETER_NAME_TAG
+        );
+        assert_eq!(
+            tag(&BlendSpace::default(), BlendSpace::SAMPLING_PARAMETER),
+            PARAMETER_NAME_TAG
+        );
+        assert_eq!(tag(&CompareNode::default(), CompareNode::VALUE), "");
+    }
+}
@@ -28,12 +28,14 @@ use std::{
 use crate::animation::value::{TrackValue, ValueBinding};
 pub use pose::{AnimationPose, NodePose};
 pub use signal::{AnimationEvent, AnimationSignal};
+pub use sync::{SyncMarker, SyncPhase};
 
 pub mod container;
 pub mod machine;
 pub mod pose;
 pub mod signal;
 pub mod spritesheet;
+pub mod sync;
 pub mod track;
 pub mod value;
 
@@ -188,6 +190,9 @@ pub struct Animation {
     enabled: bool,
     signals: Vec<AnimationSignal>,
 
+    #[visit(optional)]
+    sync_markers: Vec<SyncMarker>,
+
     #[visit(optional)]
     root_motion_settings: Option<RootMotionSettings>,
 
@@ -262,6 +267,7 @@ impl Clone for Animation {
             enabled: self.enabled,
             pose: Default::default(),
             signals: self.signals.clone(),
+            sync_markers: self.sync_markers.clone(),
             root_motion_settings: self.root_motion_settings.clone(),
             events: Default::default(),
             time_slice: self.time_slice.clone(),
@@ -594,6 +600,23 @@ impl Animation {
         self.time_position
     }
 
+    /// Returns current time position of the animation relative to its time slice, in `[0; 1]` range. Returns
+    /// zero for animations with empty time slice.
+    pub fn normalized_time_position(&self) -> f32 {
+        let length = self.length();
+        if length > 0.0 {
+            ((self.time_position - self.time_slice.start) / length).clamp(0.0, 1.0)
+        } else {
+            0.0
+        }
+    }
+
+    /// Sets new time position of the animation relative to its time slice, the value is in `[0; 1]` range (values
+    /// outside of the range are wrapped or clamped the same way as in [`Self::set_time_position`]).
+    pub fn set_normalized_time_position(&mut self, time: f32) -> &mut Self {
+        self.set_time_position(self.time_slice.start + time * self.length())
+    }
+
     /// Sets new speed multiplier for the animation. By default it is set to 1.0. Negative values can be used
     /// to play the animation in reverse.
     pub fn set_speed(&mut self, speed: f32) -> &mut Self {
@@ -671,6 +694,67 @@ impl Animation {
         &mut self.signals
     }
 
+    /// Adds a new sync marker to the animation. See [`SyncMarker`] docs for more info.
+    pub fn add_sync_marker(&mut self, marker: SyncMarker) -> &mut Self {
+        self.sync_markers.push(marker);
+        self
+    }
+
+    /// Removes a sync marker at given index.
+    pub fn remove_sync_marker(&mut self, index: usize) -> SyncMarker {
+        self.sync_markers.remove(index)
+    }
+
+    /// Returns a reference to the sync markers container.
+    pub fn sync_markers(&self) -> &[SyncMarker] {
+        &self.sync_markers
+    }
+
+    /// Returns a mutable reference to the inner sync markers container, allowing you to modify the markers.
+    pub fn sync_markers_mut(&mut self) -> &mut [SyncMarker] {
+        &mut self.sync_markers
+    }
+
+    /// Returns `true` if the animation has at least one sync marker inside its time slice.
+    pub fn has_sync_markers(&self) -> bool {
+        self.sync_markers
+            .iter()
+            .any(|m| m.time >= self.time_slice.start && m.time <= self.time_slice.end)
+    }
+
+    fn sorted_sync_markers(&self) -> Vec<(&str, f32)> {
+        sync::sorted_markers(
+            self.sync_markers
+                .iter()
+                .filter(|m| m.time >= self.time_slice.start && m.time <= self.time_slice.end),
+        )
+    }
+
+    /// Calculates current phase of the animation using its sync markers. Returns `None` if the animation does not
+    /// have any sync markers. See [`SyncPhase`] docs for more info.
+    pub fn sync_phase(&self) -> Option<SyncPhase> {
+        sync::phase_at(
+            &self.sorted_sync_markers(),
+            self.time_position,
+            self.length(),
+        )
+    }
+
+    /// Time-warps the animation so its current phase matches the given one. It searches for a pair of consecutive
+    /// markers with the same names as in the phase and moves the time position to the same normalized position
+    /// between them. The output pose of the animation is updated immediately. Returns `true` if the phase was
+    /// matched, `false` - otherwise.
+    pub fn align_to_phase(&mut self, phase: &SyncPhase) -> bool {
+        match sync::time_of_phase(&self.sorted_sync_markers(), phase, self.length()) {
+            Some(time) => {
+                self.set_time_position(time);
+                self.update_pose();
+                true
+            }
+            None => false,
+        }
+    }
+
     /// Removes all tracks from the animation for which the given `filter` closure returns `false`. Could be useful
     /// to remove undesired animation tracks.
     pub fn retain_tracks<F>(&mut self, filter: F)
@@ -792,6 +876,7 @@ impl Default for Animation {
             looped: true,
             pose: Default::default(),
             signals: Default::default(),
+            sync_markers: Default::default(),
             root_motion_settings: None,
             events: Default::default(),
             time_slice: Default::default(),
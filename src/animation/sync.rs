@@ -0,0 +1,224 @@
+//! Sync marker is a named marker on the animation timeline that is used to synchronize phases of multiple animations
+//! when blending them. See [`SyncMarker`] docs for more info.
+
+use crate::{
+    core::{reflect::prelude::*, uuid::Uuid, visitor::prelude::*},
+    utils::NameProvider,
+};
+
+/// Sync marker is a named marker on specific time position on the animation timeline, that describes a "phase" of the
+/// animation. Typical example is foot contacts in locomotion animations - walk and run cycles have different lengths,
+/// but both have `LeftFoot` and `RightFoot` contacts. When such animations are blended together (in a transition or a
+/// blend space), the follower animations are time-warped so their markers are aligned with the markers of the leading
+/// (the one with the largest weight) animation. This eliminates foot sliding during cross-fades.
+///
+/// Markers of every animation that participates in phase synchronization should have the same names and the same order,
+/// otherwise the phase cannot be matched and the animation will play unsynchronized.
+#[derive(Clone, Debug, Visit, Reflect, PartialEq)]
+pub struct SyncMarker {
+    /// An id of the sync marker.
+    pub id: Uuid,
+
+    /// Name of the sync marker. Markers with the same name in different animations are considered to be the same phase.
+    pub name: String,
+
+    /// A position (in seconds) on an animation time line.
+    pub time: f32,
+}
+
+impl NameProvider for SyncMarker {
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl SyncMarker {
+    /// Creates a new sync marker with a given id, name and time position.
+    pub fn new(id: Uuid, name: &str, time: f32) -> Self {
+        Self {
+            id,
+            name: name.to_owned(),
+            time,
+        }
+    }
+}
+
+impl Default for SyncMarker {
+    fn default() -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            name: Default::default(),
+            time: 0.0,
+        }
+    }
+}
+
+/// Phase of an animation defined in terms of its sync markers. It contains names of two consecutive markers and a
+/// normalized position between them. The phase is independent of the actual length of an animation, so it could be
+/// used to match two animations with different lengths.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SyncPhase {
+    /// Name of a marker that was passed last.
+    pub from: String,
+
+    /// Name of a marker that will be passed next.
+    pub to: String,
+
+    /// Normalized (`[0; 1]` range) position between the two markers.
+    pub fraction: f32,
+}
+
+/// Collects pairs of names and times of the given markers sorted by time, in the form that is expected by
+/// [`phase_at`] and [`time_of_phase`].
+pub(crate) fn sorted_markers<'a>(
+    markers: impl Iterator<Item = &'a SyncMarker>,
+) -> Vec<(&'a str, f32)> {
+    let mut markers = markers
+        .map(|m| (m.name.as_str(), m.time))
+        .collect::<Vec<_>>();
+    markers.sort_by(|a, b| a.1.total_cmp(&b.1));
+    markers
+}
+
+/// Returns indices of two consecutive markers (in time order) that surround the given time position, along with the
+/// times of the markers "unwrapped" around the time position (previous marker time is always less or equal to the
+/// time position and the next one is always greater). `markers` are pairs of names and times and must be sorted by
+/// time.
+pub(crate) fn surrounding_markers(
+    markers: &[(&str, f32)],
+    time: f32,
+    length: f32,
+) -> Option<(usize, f32, usize, f32)> {
+    if markers.is_empty() {
+        return None;
+    }
+
+    let count = markers.len();
+
+    let prev = match markers.iter().rposition(|(_, t)| *t <= time) {
+        Some(index) => index,
+        // The time position is before the first marker, so the phase starts on the last marker of previous cycle.
+        None => count - 1,
+    };
+    let next = (prev + 1) % count;
+
+    let mut prev_time = markers[prev].1;
+    if prev_time > time {
+        prev_time -= length;
+    }
+    let mut next_time = markers[next].1;
+    if next_time <= time {
+        next_time += length;
+    }
+
+    Some((prev, prev_time, next, next_time))
+}
+
+/// Calculates a phase at the given time position of a cycle with the given length. `markers` are pairs of names and
+/// times and must be sorted by time. Returns `None` if there are no markers.
+pub(crate) fn phase_at(markers: &[(&str, f32)], time: f32, length: f32) -> Option<SyncPhase> {
+    let (prev, prev_time, next, next_time) = surrounding_markers(markers, time, length)?;
+
+    let span = next_time - prev_time;
+    let fraction = if span > f32::EPSILON {
+        (time - prev_time) / span
+    } else {
+        0.0
+    };
+
+    Some(SyncPhase {
+        from: markers[prev].0.to_string(),
+        to: markers[next].0.to_string(),
+        fraction: fraction.clamp(0.0, 1.0),
+    })
+}
+
+/// Searches for a pair of consecutive markers with the same names as in the phase and returns a time position at the
+/// same normalized position between them. The result could be out of the cycle bounds when the phase wraps around
+/// the end of the cycle. `markers` are pairs of names and times and must be sorted by time. Returns `None` if the
+/// phase cannot be matched.
+pub(crate) fn time_of_phase(
+    markers: &[(&str, f32)],
+    phase: &SyncPhase,
+    length: f32,
+) -> Option<f32> {
+    let count = markers.len();
+
+    let prev = (0..count)
+        .find(|i| markers[*i].0 == phase.from && markers[(*i + 1) % count].0 == phase.to)?;
+
+    let prev_time = markers[prev].1;
+    let mut next_time = markers[(prev + 1) % count].1;
+    if next_time <= prev_time {
+        next_time += length;
+    }
+
+    Some(prev_time + (next_time - prev_time) * phase.fraction)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        animation::{
+            sync::{SyncMarker, SyncPhase},
+            Animation,
+        },
+        core::uuid::Uuid,
+    };
+
+    fn make_animation(length: f32, markers: &[(&str, f32)]) -> Animation {
+        let mut animation = Animation::default();
+        animation.set_time_slice(0.0..length);
+        for (name, time) in markers {
+            animation.add_sync_marker(SyncMarker::new(Uuid::new_v4(), name, *time));
+        }
+        animation
+    }
+
+    #[test]
+    fn test_sync_phase() {
+        let mut walk = make_animation(2.0, &[("Left", 0.5), ("Right", 1.5)]);
+
+        walk.set_time_position(1.0);
+        assert_eq!(
+            walk.sync_phase(),
+            Some(SyncPhase {
+                from: "Left".to_string(),
+                to: "Right".to_string(),
+                fraction: 0.5
+            })
+        );
+
+        // Wrapping around the end of the cycle.
+        walk.set_time_position(0.0);
+        assert_eq!(
+            walk.sync_phase(),
+            Some(SyncPhase {
+                from: "Right".to_string(),
+                to: "Left".to_string(),
+                fraction: 0.5
+            })
+        );
+
+        let no_markers = make_animation(1.0, &[]);
+        assert_eq!(no_markers.sync_phase(), None);
+    }
+
+    #[test]
+    fn test_align_to_phase() {
+        let mut walk = make_animation(2.0, &[("Left", 0.5), ("Right", 1.5)]);
+        let mut run = make_animation(1.0, &[("Left", 0.1), ("Right", 0.6)]);
+
+        walk.set_time_position(1.25);
+        assert!(run.align_to_phase(&walk.sync_phase().unwrap()));
+        assert!((run.time_position() - 0.475).abs() <= f32::EPSILON);
+
+        walk.set_time_position(0.0);
+        assert!(run.align_to_phase(&walk.sync_phase().unwrap()));
+        assert!((run.time_position() - 0.85).abs() <= 0.0001);
+
+        // Unknown marker names cannot be matched.
+        let mut other = make_animation(1.0, &[("A", 0.1), ("B", 0.6)]);
+        assert!(!other.align_to_phase(&walk.sync_phase().unwrap()));
+    }
+}
@@ -25,6 +25,8 @@ use crate::{
     resource::{
         curve::{loader::CurveLoader, CurveResourceState},
         model::{loader::ModelLoader, Model, ModelResource},
+        sound_bank::{loader::SoundBankLoader, SoundBank},
+        style::{loader::StyleLoader, StyleResourceState},
         texture::{loader::TextureLoader, Texture, TextureKind},
     },
     scene::{
@@ -1032,6 +1034,8 @@ pub(crate) fn initialize_resource_manager_loaders(
     state.constructors_container.add::<CurveResourceState>();
     state.constructors_container.add::<SoundBuffer>();
     state.constructors_container.add::<HrirSphereResourceData>();
+    state.constructors_container.add::<SoundBank>();
+    state.constructors_container.add::<StyleResourceState>();
 
     let loaders = &mut state.loaders;
     loaders.set(model_loader);
@@ -1043,7 +1047,11 @@ pub(crate) fn initialize_resource_manager_loaders(
     });
     loaders.set(ShaderLoader);
     loaders.set(CurveLoader);
+    loaders.set(StyleLoader);
     loaders.set(HrirSphereLoader);
+    loaders.set(SoundBankLoader {
+        resource_manager: resource_manager.clone(),
+    });
 }
 
 impl Engine {
@@ -1291,6 +1299,9 @@ impl Engine {
                 )
             };
 
+            // Allow input method editors, so text of any language could be entered in text fields.
+            window.set_ime_allowed(true);
+
             self.user_interface.set_screen_size(Vector2::new(
                 window.inner_size().width as f32,
                 window.inner_size().height as f32,
@@ -1305,6 +1316,9 @@ impl Engine {
                     .unwrap_or_else(|| NonZeroU32::new(1).unwrap()),
             );
 
+            self.user_interface
+                .set_scale_factor(window.scale_factor() as f32);
+
             self.graphics_context = GraphicsContext::Initialized(InitializedGraphicsContext {
                 #[cfg(not(target_arch = "wasm32"))]
                 gl_context,
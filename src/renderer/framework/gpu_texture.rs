@@ -133,6 +133,8 @@ impl From<TexturePixelKind> for PixelKind {
             TexturePixelKind::LuminanceAlpha16 => Self::LA16,
             TexturePixelKind::R32F => Self::R32F,
             TexturePixelKind::R16F => Self::R16F,
+            TexturePixelKind::SRGB8 => Self::SRGB8,
+            TexturePixelKind::SRGBA8 => Self::SRGBA8,
         }
     }
 }
@@ -2,9 +2,16 @@ use crate::core::sstorage::ImmutableString;
 use crate::renderer::framework::framebuffer::BlendParameters;
 use crate::renderer::framework::geometry_buffer::ElementRange;
 use crate::renderer::framework::state::{BlendFactor, BlendFunc};
-use crate::scene::particle_system::ParticleSystem;
+use crate::scene::particle_system::{gpu, ParticleSystem};
 use crate::{
-    core::{algebra::Vector2, math::Matrix4Ext, math::Rect, scope_profile},
+    core::{
+        algebra::{Vector2, Vector4},
+        log::Log,
+        math::Matrix4Ext,
+        math::Rect,
+        math::TriangleDefinition,
+        scope_profile,
+    },
     renderer::framework::{
         error::FrameworkError,
         framebuffer::{DrawParameters, FrameBuffer},
@@ -66,10 +73,114 @@ impl ParticleSystemShader {
     }
 }
 
+struct GpuParticleSystemShader {
+    program: GpuProgram,
+    view_projection_matrix: UniformLocation,
+    world_matrix: UniformLocation,
+    camera_side_vector: UniformLocation,
+    camera_up_vector: UniformLocation,
+    diffuse_texture: UniformLocation,
+    depth_buffer_texture: UniformLocation,
+    inv_screen_size: UniformLocation,
+    proj_params: UniformLocation,
+    soft_boundary_sharpness_factor: UniformLocation,
+    time: UniformLocation,
+    dt: UniformLocation,
+    acceleration: UniformLocation,
+    seed: UniformLocation,
+    emitter_index: UniformLocation,
+    gradient_locations: UniformLocation,
+    gradient_colors: UniformLocation,
+    gradient_point_count: UniformLocation,
+    emitter_position: UniformLocation,
+    spawn_rate: UniformLocation,
+    period: UniformLocation,
+    resurrect: UniformLocation,
+    lifetime_range: UniformLocation,
+    size_range: UniformLocation,
+    size_modifier_range: UniformLocation,
+    x_velocity_range: UniformLocation,
+    y_velocity_range: UniformLocation,
+    z_velocity_range: UniformLocation,
+    rotation_range: UniformLocation,
+    rotation_speed_range: UniformLocation,
+    shape_kind: UniformLocation,
+    shape_params: UniformLocation,
+}
+
+impl GpuParticleSystemShader {
+    fn new(state: &mut PipelineState) -> Result<Self, FrameworkError> {
+        let vertex_source = include_str!("shaders/particle_system_gpu_vs.glsl");
+        let fragment_source = include_str!("shaders/particle_system_fs.glsl");
+        let program = GpuProgram::from_source(
+            state,
+            "GpuParticleSystemShader",
+            vertex_source,
+            fragment_source,
+        )?;
+        Ok(Self {
+            view_projection_matrix: program
+                .uniform_location(state, &ImmutableString::new("viewProjectionMatrix"))?,
+            world_matrix: program.uniform_location(state, &ImmutableString::new("worldMatrix"))?,
+            camera_side_vector: program
+                .uniform_location(state, &ImmutableString::new("cameraSideVector"))?,
+            camera_up_vector: program
+                .uniform_location(state, &ImmutableString::new("cameraUpVector"))?,
+            diffuse_texture: program
+                .uniform_location(state, &ImmutableString::new("diffuseTexture"))?,
+            depth_buffer_texture: program
+                .uniform_location(state, &ImmutableString::new("depthBufferTexture"))?,
+            inv_screen_size: program
+                .uniform_location(state, &ImmutableString::new("invScreenSize"))?,
+            proj_params: program.uniform_location(state, &ImmutableString::new("projParams"))?,
+            soft_boundary_sharpness_factor: program
+                .uniform_location(state, &ImmutableString::new("softBoundarySharpnessFactor"))?,
+            time: program.uniform_location(state, &ImmutableString::new("time"))?,
+            dt: program.uniform_location(state, &ImmutableString::new("dt"))?,
+            acceleration: program.uniform_location(state, &ImmutableString::new("acceleration"))?,
+            seed: program.uniform_location(state, &ImmutableString::new("seed"))?,
+            emitter_index: program
+                .uniform_location(state, &ImmutableString::new("emitterIndex"))?,
+            gradient_locations: program
+                .uniform_location(state, &ImmutableString::new("gradientLocations"))?,
+            gradient_colors: program
+                .uniform_location(state, &ImmutableString::new("gradientColors"))?,
+            gradient_point_count: program
+                .uniform_location(state, &ImmutableString::new("gradientPointCount"))?,
+            emitter_position: program
+                .uniform_location(state, &ImmutableString::new("emitterPosition"))?,
+            spawn_rate: program.uniform_location(state, &ImmutableString::new("spawnRate"))?,
+            period: program.uniform_location(state, &ImmutableString::new("period"))?,
+            resurrect: program.uniform_location(state, &ImmutableString::new("resurrect"))?,
+            lifetime_range: program
+                .uniform_location(state, &ImmutableString::new("lifetimeRange"))?,
+            size_range: program.uniform_location(state, &ImmutableString::new("sizeRange"))?,
+            size_modifier_range: program
+                .uniform_location(state, &ImmutableString::new("sizeModifierRange"))?,
+            x_velocity_range: program
+                .uniform_location(state, &ImmutableString::new("xVelocityRange"))?,
+            y_velocity_range: program
+                .uniform_location(state, &ImmutableString::new("yVelocityRange"))?,
+            z_velocity_range: program
+                .uniform_location(state, &ImmutableString::new("zVelocityRange"))?,
+            rotation_range: program
+                .uniform_location(state, &ImmutableString::new("rotationRange"))?,
+            rotation_speed_range: program
+                .uniform_location(state, &ImmutableString::new("rotationSpeedRange"))?,
+            shape_kind: program.uniform_location(state, &ImmutableString::new("shapeKind"))?,
+            shape_params: program.uniform_location(state, &ImmutableString::new("shapeParams"))?,
+            program,
+        })
+    }
+}
+
 pub struct ParticleSystemRenderer {
     shader: ParticleSystemShader,
+    // GPU simulation is optional, particle systems are simulated on CPU if the shader is not supported.
+    gpu_shader: Option<GpuParticleSystemShader>,
     draw_data: particle_system::draw::DrawData,
     geometry_buffer: GeometryBuffer,
+    quad: GeometryBuffer,
     sorted_particles: Vec<u32>,
 }
 
@@ -123,14 +234,67 @@ impl ParticleSystemRenderer {
                     kind: AttributeKind::UnsignedByte4,
                     normalized: true,
                     divisor: 0,
+                })
+                .with_attribute(AttributeDefinition {
+                    location: 5,
+                    kind: AttributeKind::Float2,
+                    normalized: false,
+                    divisor: 0,
+                })
+                .with_attribute(AttributeDefinition {
+                    location: 6,
+                    kind: AttributeKind::Float2,
+                    normalized: false,
+                    divisor: 0,
+                })
+                .with_attribute(AttributeDefinition {
+                    location: 7,
+                    kind: AttributeKind::Float,
+                    normalized: false,
+                    divisor: 0,
                 }),
             )
             .build(state)?;
 
+        let quad_tex_coords = [
+            Vector2::<f32>::new(0.0, 0.0),
+            Vector2::new(1.0, 0.0),
+            Vector2::new(1.0, 1.0),
+            Vector2::new(0.0, 1.0),
+        ];
+        let quad = GeometryBufferBuilder::new(ElementKind::Triangle)
+            .with_buffer_builder(
+                BufferBuilder::new(GeometryBufferKind::StaticDraw, Some(&quad_tex_coords))
+                    .with_attribute(AttributeDefinition {
+                        location: 0,
+                        kind: AttributeKind::Float2,
+                        normalized: false,
+                        divisor: 0,
+                    }),
+            )
+            .build(state)?;
+        quad.bind(state)
+            .set_triangles(&[TriangleDefinition([0, 1, 2]), TriangleDefinition([0, 2, 3])]);
+
+        let gpu_shader = match GpuParticleSystemShader::new(state) {
+            Ok(shader) => Some(shader),
+            Err(e) => {
+                Log::warn(format!(
+                    "Unable to create GPU particle system shader, particle systems will be \
+                    simulated on CPU. Reason: {:?}",
+                    e
+                ));
+                None
+            }
+        };
+        gpu::set_gpu_simulation_supported(gpu_shader.is_some());
+
         Ok(Self {
             shader: ParticleSystemShader::new(state)?,
+            gpu_shader,
             draw_data: Default::default(),
             geometry_buffer,
+            quad,
             sorted_particles: Vec::new(),
         })
     }
@@ -165,10 +329,100 @@ impl ParticleSystemRenderer {
         let inv_screen_size = Vector2::new(1.0 / frame_width, 1.0 / frame_height);
         let proj_params = Vector2::new(camera.projection().z_far(), camera.projection().z_near());
 
+        let draw_params = DrawParameters {
+            cull_face: None,
+            color_write: Default::default(),
+            depth_write: false,
+            stencil_test: None,
+            depth_test: true,
+            blend: Some(BlendParameters {
+                func: BlendFunc::new(BlendFactor::SrcAlpha, BlendFactor::OneMinusSrcAlpha),
+                ..Default::default()
+            }),
+            stencil_op: Default::default(),
+        };
+
         for particle_system in graph
             .linear_iter()
             .filter_map(|n| n.cast::<ParticleSystem>())
         {
+            let global_transform = particle_system.global_transform();
+
+            let diffuse_texture = particle_system
+                .texture_ref()
+                .and_then(|t| texture_cache.get(state, t))
+                .unwrap_or_else(|| white_dummy.clone());
+
+            if let (Some(shader), Some(emitters)) = (
+                self.gpu_shader.as_ref(),
+                particle_system.gpu_emitter_params(),
+            ) {
+                let (time, dt) = particle_system.simulation_time();
+                let seed = particle_system.gpu_seed();
+
+                let mut gradient_locations = [0.0; gpu::MAX_GRADIENT_POINTS];
+                let mut gradient_colors = [Vector4::default(); gpu::MAX_GRADIENT_POINTS];
+                let gradient_point_count = gpu::gradient_to_arrays(
+                    particle_system.color_over_lifetime_gradient(),
+                    &mut gradient_locations,
+                    &mut gradient_colors,
+                );
+
+                for (emitter_index, emitter) in emitters.iter().enumerate() {
+                    if emitter.capacity == 0 {
+                        continue;
+                    }
+
+                    statistics += framebuffer.draw_instances(
+                        emitter.capacity as usize,
+                        &self.quad,
+                        state,
+                        viewport,
+                        &shader.program,
+                        &draw_params,
+                        |mut program_binding| {
+                            program_binding
+                                .set_texture(&shader.depth_buffer_texture, &depth)
+                                .set_texture(&shader.diffuse_texture, &diffuse_texture)
+                                .set_vector3(&shader.camera_side_vector, &camera_side)
+                                .set_vector3(&shader.camera_up_vector, &camera_up)
+                                .set_matrix4(&shader.view_projection_matrix, &view_proj)
+                                .set_matrix4(&shader.world_matrix, &global_transform)
+                                .set_vector2(&shader.inv_screen_size, &inv_screen_size)
+                                .set_vector2(&shader.proj_params, &proj_params)
+                                .set_f32(
+                                    &shader.soft_boundary_sharpness_factor,
+                                    particle_system.soft_boundary_sharpness_factor(),
+                                )
+                                .set_f32(&shader.time, time)
+                                .set_f32(&shader.dt, dt)
+                                .set_vector3(&shader.acceleration, &particle_system.acceleration())
+                                .set_u32(&shader.seed, seed)
+                                .set_u32(&shader.emitter_index, emitter_index as u32)
+                                .set_f32_slice(&shader.gradient_locations, &gradient_locations)
+                                .set_vector4_slice(&shader.gradient_colors, &gradient_colors)
+                                .set_i32(&shader.gradient_point_count, gradient_point_count as i32)
+                                .set_vector3(&shader.emitter_position, &emitter.position)
+                                .set_f32(&shader.spawn_rate, emitter.spawn_rate)
+                                .set_f32(&shader.period, emitter.period)
+                                .set_bool(&shader.resurrect, emitter.resurrect)
+                                .set_vector2(&shader.lifetime_range, &emitter.lifetime)
+                                .set_vector2(&shader.size_range, &emitter.size)
+                                .set_vector2(&shader.size_modifier_range, &emitter.size_modifier)
+                                .set_vector2(&shader.x_velocity_range, &emitter.x_velocity)
+                                .set_vector2(&shader.y_velocity_range, &emitter.y_velocity)
+                                .set_vector2(&shader.z_velocity_range, &emitter.z_velocity)
+                                .set_vector2(&shader.rotation_range, &emitter.rotation)
+                                .set_vector2(&shader.rotation_speed_range, &emitter.rotation_speed)
+                                .set_i32(&shader.shape_kind, emitter.shape_kind)
+                                .set_vector4(&shader.shape_params, &emitter.shape_params);
+                        },
+                    );
+                }
+
+                continue;
+            }
+
             particle_system.generate_draw_data(
                 &mut self.sorted_particles,
                 &mut self.draw_data,
@@ -181,26 +435,6 @@ impl ParticleSystemRenderer {
                 .bind(state)
                 .set_triangles(self.draw_data.triangles());
 
-            let global_transform = particle_system.global_transform();
-
-            let draw_params = DrawParameters {
-                cull_face: None,
-                color_write: Default::default(),
-                depth_write: false,
-                stencil_test: None,
-                depth_test: true,
-                blend: Some(BlendParameters {
-                    func: BlendFunc::new(BlendFactor::SrcAlpha, BlendFactor::OneMinusSrcAlpha),
-                    ..Default::default()
-                }),
-                stencil_op: Default::default(),
-            };
-
-            let diffuse_texture = particle_system
-                .texture_ref()
-                .and_then(|t| texture_cache.get(state, t))
-                .unwrap_or_else(|| white_dummy.clone());
-
             statistics += framebuffer.draw(
                 &self.geometry_buffer,
                 state,
@@ -6,6 +6,8 @@ uniform float softBoundarySharpnessFactor;
 
 out vec4 FragColor;
 in vec2 texCoord;
+in vec2 nextTexCoord;
+in float blend;
 in vec4 color;
 
 float toProjSpace(float z)
@@ -20,6 +22,7 @@ void main()
     float sceneDepth = toProjSpace(texture(depthBufferTexture, gl_FragCoord.xy * invScreenSize).r);
     float fragmentDepth = toProjSpace(gl_FragCoord.z);
     float depthOpacity = smoothstep((sceneDepth - fragmentDepth) * softBoundarySharpnessFactor, 0.0, 1.0);
-    FragColor = color * S_SRGBToLinear(texture(diffuseTexture, texCoord)).r;
+    vec4 diffuseColor = mix(texture(diffuseTexture, texCoord), texture(diffuseTexture, nextTexCoord), blend);
+    FragColor = color * S_SRGBToLinear(diffuseColor).r;
     FragColor.a *= depthOpacity;
 }
\ No newline at end of file
@@ -0,0 +1,177 @@
+// Stateless GPU simulation of particles, every instance is a particle of the pool of an emitter. This
+// shader mirrors `scene/particle_system/gpu.rs`, so both must be changed together.
+
+layout(location = 0) in vec2 vertexTexCoord;
+
+uniform mat4 viewProjectionMatrix;
+uniform mat4 worldMatrix;
+uniform vec3 cameraUpVector;
+uniform vec3 cameraSideVector;
+
+uniform float time;
+uniform float dt;
+uniform vec3 acceleration;
+uniform uint seed;
+uniform uint emitterIndex;
+
+uniform float gradientLocations[16];
+uniform vec4 gradientColors[16];
+uniform int gradientPointCount;
+
+uniform vec3 emitterPosition;
+uniform float spawnRate;
+uniform float period;
+uniform bool resurrect;
+uniform vec2 lifetimeRange;
+uniform vec2 sizeRange;
+uniform vec2 sizeModifierRange;
+uniform vec2 xVelocityRange;
+uniform vec2 yVelocityRange;
+uniform vec2 zVelocityRange;
+uniform vec2 rotationRange;
+uniform vec2 rotationSpeedRange;
+uniform int shapeKind;
+uniform vec4 shapeParams;
+
+out vec2 texCoord;
+out vec2 nextTexCoord;
+out float blend;
+out vec4 color;
+
+const float PI = 3.14159265359;
+
+uint pcgHash(uint value)
+{
+    uint state = value * 747796405u + 2891336453u;
+    uint word = ((state >> ((state >> 28u) + 4u)) ^ state) * 277803737u;
+    return (word >> 22u) ^ word;
+}
+
+float rngNext(inout uint state)
+{
+    state = pcgHash(state);
+    return float(state) / 4294967295.0;
+}
+
+float rngRange(inout uint state, vec2 range)
+{
+    return range.x + (range.y - range.x) * rngNext(state);
+}
+
+vec3 shapePosition(inout uint state)
+{
+    vec4 p = shapeParams;
+    vec3 offset = vec3(0.0);
+    if (shapeKind == 1) {
+        // Cuboid.
+        offset.x = rngRange(state, vec2(-p.x, p.x));
+        offset.y = rngRange(state, vec2(-p.y, p.y));
+        offset.z = rngRange(state, vec2(-p.z, p.z));
+    } else if (shapeKind == 2) {
+        // Sphere.
+        float phi = rngRange(state, vec2(0.0, PI));
+        float theta = rngRange(state, vec2(0.0, 2.0 * PI));
+        float radius = rngRange(state, vec2(0.0, p.x));
+        offset = vec3(radius * sin(theta) * cos(phi), radius * sin(theta) * sin(phi), radius * cos(theta));
+    } else if (shapeKind == 3) {
+        // Cylinder.
+        float scale = rngNext(state);
+        float theta = rngRange(state, vec2(0.0, 2.0 * PI));
+        float z = rngRange(state, vec2(0.0, p.y));
+        float radius = sqrt(scale) * p.x;
+        offset = vec3(radius * cos(theta), radius * sin(theta), z);
+    } else if (shapeKind == 4) {
+        // Cone.
+        float y = pow(rngNext(state), 1.0 / 3.0) * p.x;
+        float scale = rngNext(state);
+        float theta = rngRange(state, vec2(0.0, 2.0 * PI));
+        float radius = sqrt(scale) * y * p.y;
+        offset = vec3(radius * cos(theta), y, radius * sin(theta));
+    } else if (shapeKind == 5) {
+        // Torus.
+        float scale = rngNext(state);
+        float theta = rngRange(state, vec2(0.0, 2.0 * PI));
+        float phi = rngRange(state, vec2(0.0, 2.0 * PI));
+        float tubeRadius = sqrt(scale) * p.y;
+        float radius = p.x + tubeRadius * cos(phi);
+        offset = vec3(radius * cos(theta), tubeRadius * sin(phi), radius * sin(theta));
+    }
+    return emitterPosition + offset;
+}
+
+vec4 gradientColor(float location)
+{
+    vec4 result = gradientColors[0];
+    if (location >= gradientLocations[gradientPointCount - 1]) {
+        result = gradientColors[gradientPointCount - 1];
+    } else {
+        for (int i = 0; i < gradientPointCount - 1; ++i) {
+            float a = gradientLocations[i];
+            float b = gradientLocations[i + 1];
+            if (location >= a && location <= b) {
+                result = mix(gradientColors[i], gradientColors[i + 1], (location - a) / max(b - a, 0.00001));
+                break;
+            }
+        }
+    }
+    // Same conversion as `Color::srgb_to_linear`.
+    return vec4(clamp(pow(result.rgb, vec3(2.2)), 0.0, 1.0), result.a);
+}
+
+vec2 rotateVec2(vec2 v, float angle)
+{
+    float c = cos(angle);
+    float s = sin(angle);
+    mat2 m = mat2(c, -s, s, c);
+    return m * v;
+}
+
+void main()
+{
+    texCoord = vertexTexCoord;
+    nextTexCoord = vertexTexCoord;
+    blend = 0.0;
+    color = vec4(0.0);
+
+    // Dead particles are moved outside of the clip space.
+    gl_Position = vec4(2.0, 2.0, 2.0, 1.0);
+
+    uint particleIndex = uint(gl_InstanceID);
+
+    float localTime = time - float(particleIndex) / spawnRate;
+    if (spawnRate <= 0.0 || period <= 0.0 || localTime < 0.0) {
+        return;
+    }
+
+    float cycle = floor(localTime / period);
+    if (!resurrect && cycle >= 1.0) {
+        return;
+    }
+    float age = localTime - cycle * period;
+
+    uint state = pcgHash(seed ^ pcgHash(emitterIndex * 0x9E3779B9u ^ pcgHash(particleIndex ^ pcgHash(uint(cycle)))));
+
+    float lifetime = rngRange(state, lifetimeRange);
+    float size = rngRange(state, sizeRange);
+    float sizeModifier = rngRange(state, sizeModifierRange);
+    vec3 velocity = vec3(rngRange(state, xVelocityRange), rngRange(state, yVelocityRange), rngRange(state, zVelocityRange));
+    float rotation = rngRange(state, rotationRange);
+    float rotationSpeed = rngRange(state, rotationSpeedRange);
+    vec3 position = shapePosition(state);
+
+    if (age >= lifetime) {
+        return;
+    }
+
+    float ticks = age / max(dt, 0.0000001);
+    position += velocity * ticks + acceleration * (dt * dt * ticks * (ticks + 1.0) * 0.5);
+    size = max(size + sizeModifier * age, 0.0);
+    rotation += rotationSpeed * age;
+
+    color = gradientColor(age / lifetime);
+
+    vec2 vertexOffset = rotateVec2(vertexTexCoord * 2.0 - 1.0, rotation);
+    vec4 worldPosition = worldMatrix * vec4(position, 1.0);
+    vec3 offset = (vertexOffset.x * cameraSideVector + vertexOffset.y * cameraUpVector) * size;
+    gl_Position = viewProjectionMatrix * (worldPosition + vec4(offset, 0.0));
+}
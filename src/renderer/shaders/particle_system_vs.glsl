@@ -3,6 +3,9 @@ layout(location = 1) in vec2 vertexTexCoord;
 layout(location = 2) in float particleSize;
 layout(location = 3) in float particleRotation;
 layout(location = 4) in vec4 vertexColor;
+layout(location = 5) in vec2 frameTexCoord;
+layout(location = 6) in vec2 nextFrameTexCoord;
+layout(location = 7) in float frameBlend;
 
 uniform mat4 viewProjectionMatrix;
 uniform mat4 worldMatrix;
@@ -10,6 +13,8 @@ uniform vec3 cameraUpVector;
 uniform vec3 cameraSideVector;
 
 out vec2 texCoord;
+out vec2 nextTexCoord;
+out float blend;
 out vec4 color;
 
 vec2 rotateVec2(vec2 v, float angle)
@@ -23,7 +28,9 @@ vec2 rotateVec2(vec2 v, float angle)
 void main()
 {
     color = vertexColor;
-    texCoord = vertexTexCoord;
+    texCoord = frameTexCoord;
+    nextTexCoord = nextFrameTexCoord;
+    blend = frameBlend;
     vec2 vertexOffset = rotateVec2(vertexTexCoord * 2.0 - 1.0, particleRotation);
     vec4 worldPosition = worldMatrix * vec4(vertexPosition, 1.0);
     vec3 offset = (vertexOffset.x * cameraSideVector + vertexOffset.y * cameraUpVector) * particleSize;
@@ -64,6 +64,7 @@ use std::{
     cmp::Ordering,
     collections::hash_map::DefaultHasher,
     hash::{Hash, Hasher},
+    ops::Range,
     path::Path,
 };
 
@@ -634,42 +635,19 @@ fn convert_model_to_base(model: &FbxModel) -> BaseBuilder {
         )
 }
 
-async fn convert_model(
+fn convert_model_animation(
     fbx_scene: &FbxScene,
     model: &FbxModel,
-    resource_manager: ResourceManager,
-    graph: &mut Graph,
+    node_handle: Handle<Node>,
+    curve_nodes: &[Handle<FbxComponent>],
     animation: &mut Animation,
-    model_path: &Path,
-    model_import_options: &ModelImportOptions,
-) -> Result<Handle<Node>, FbxError> {
-    let base = convert_model_to_base(model);
-
-    // Create node with correct kind.
-    let node_handle = if !model.geoms.is_empty() {
-        convert_mesh(
-            base,
-            fbx_scene,
-            resource_manager,
-            model,
-            graph,
-            model_path,
-            model_import_options,
-        )
-        .await?
-    } else if model.light.is_some() {
-        fbx_scene.get(model.light).as_light()?.convert(base, graph)
-    } else {
-        PivotBuilder::new(base).build(graph)
-    };
-
-    // Convert animations
-    if !model.animation_curve_nodes.is_empty() {
+) {
+    if !curve_nodes.is_empty() {
         // Find supported curve nodes (translation, rotation, scale)
         let mut lcl_translation = None;
         let mut lcl_rotation = None;
         let mut lcl_scale = None;
-        for &anim_curve_node_handle in model.animation_curve_nodes.iter() {
+        for &anim_curve_node_handle in curve_nodes.iter() {
             let component = fbx_scene.get(anim_curve_node_handle);
             if let FbxComponent::AnimationCurveNode(curve_node) = component {
                 if curve_node.actual_type == FbxAnimationCurveNodeType::Rotation {
@@ -779,12 +757,110 @@ async fn convert_model(
         animation.add_track(rotation_track);
         animation.add_track(scale_track);
     }
+}
 
-    animation.fit_length_to_content();
+async fn convert_model(
+    fbx_scene: &FbxScene,
+    model: &FbxModel,
+    resource_manager: ResourceManager,
+    graph: &mut Graph,
+    takes: &mut [ImportedTake],
+    model_path: &Path,
+    model_import_options: &ModelImportOptions,
+) -> Result<Handle<Node>, FbxError> {
+    let base = convert_model_to_base(model);
+
+    // Create node with correct kind.
+    let node_handle = if !model.geoms.is_empty() {
+        convert_mesh(
+            base,
+            fbx_scene,
+            resource_manager,
+            model,
+            graph,
+            model_path,
+            model_import_options,
+        )
+        .await?
+    } else if model.light.is_some() {
+        fbx_scene.get(model.light).as_light()?.convert(base, graph)
+    } else {
+        PivotBuilder::new(base).build(graph)
+    };
+
+    // Convert animations
+    for take in takes.iter_mut() {
+        let curve_nodes = model
+            .animation_curve_nodes
+            .iter()
+            .filter(|handle| {
+                take.curve_nodes
+                    .as_ref()
+                    .map_or(true, |curve_nodes| curve_nodes.contains(handle))
+            })
+            .copied()
+            .collect::<Vec<_>>();
+        convert_model_animation(
+            fbx_scene,
+            model,
+            node_handle,
+            &curve_nodes,
+            &mut take.animation,
+        );
+    }
 
     Ok(node_handle)
 }
 
+/// Animation that is being imported from an animation stack (take) of a file.
+struct ImportedTake {
+    animation: Animation,
+    time_span: Option<Range<f32>>,
+    /// Curve nodes of the take, `None` means that every curve node belongs to the take.
+    curve_nodes: Option<FxHashSet<Handle<FbxComponent>>>,
+}
+
+/// Collects every animation stack (take) of the file, so each of them could be imported as a separate
+/// animation. Files without animation stacks produce a single take with every curve node.
+fn collect_takes(fbx_scene: &FbxScene) -> Vec<ImportedTake> {
+    let mut takes = Vec::new();
+    for (_, component) in fbx_scene.pair_iter() {
+        if let FbxComponent::AnimationStack(stack) = component {
+            let mut curve_nodes = FxHashSet::default();
+            for &layer_handle in stack.layers.iter() {
+                if let FbxComponent::AnimationLayer(layer) = fbx_scene.get(layer_handle) {
+                    curve_nodes.extend(layer.curve_nodes.iter().copied());
+                }
+            }
+
+            let mut animation = Animation::default();
+            animation.set_name(if stack.name.is_empty() {
+                "Animation"
+            } else {
+                stack.name.as_str()
+            });
+
+            takes.push(ImportedTake {
+                animation,
+                time_span: stack.time_span.clone(),
+                curve_nodes: Some(curve_nodes),
+            });
+        }
+    }
+
+    if takes.is_empty() {
+        let mut animation = Animation::default();
+        animation.set_name("Animation");
+        takes.push(ImportedTake {
+            animation,
+            time_span: None,
+            curve_nodes: None,
+        });
+    }
+
+    takes
+}
+
 ///
 /// Converts FBX DOM to native engine representation.
 ///
@@ -797,8 +873,7 @@ async fn convert(
 ) -> Result<(), FbxError> {
     let root = scene.graph.get_root();
 
-    let mut animation = Animation::default();
-    animation.set_name("Animation");
+    let mut takes = collect_takes(fbx_scene);
 
     let mut fbx_model_to_node_map = FxHashMap::default();
     for (component_handle, component) in fbx_scene.pair_iter() {
@@ -808,7 +883,7 @@ async fn convert(
                 model,
                 resource_manager.clone(),
                 &mut scene.graph,
-                &mut animation,
+                &mut takes,
                 model_path,
                 model_import_options,
             )
@@ -819,9 +894,18 @@ async fn convert(
     }
 
     // Do not create animation player if there's no animation content.
-    if !animation.tracks().is_empty() {
-        let mut animations_container = AnimationContainer::new();
-        animations_container.add(animation);
+    let mut animations_container = AnimationContainer::new();
+    for take in takes {
+        let mut animation = take.animation;
+        if !animation.tracks().is_empty() {
+            match take.time_span {
+                Some(time_span) => animation.set_time_slice(time_span),
+                None => animation.fit_length_to_content(),
+            }
+            animations_container.add(animation);
+        }
+    }
+    if animations_container.alive_count() > 0 {
         AnimationPlayerBuilder::new(BaseBuilder::new().with_name("AnimationPlayer"))
             .with_animations(animations_container)
             .build(&mut scene.graph);
@@ -956,3 +1040,104 @@ pub async fn load_to_scene<P: AsRef<Path>>(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        asset::io::FsResourceIo,
+        core::futures::executor::block_on,
+        resource::fbx::{
+            collect_takes,
+            document::FbxDocument,
+            scene::{animation::FbxAnimationCurveNodeType, FbxComponent, FbxScene},
+        },
+    };
+
+    fn load_scene(file_name: &str, objects: &str, connections: &str) -> FbxScene {
+        let path = std::env::temp_dir().join(file_name);
+        std::fs::write(
+            &path,
+            format!(
+                "FBXHeaderExtension:  {{\n    FBXVersion: 7400\n}}\n\
+                Objects:  {{\n{objects}}}\n\
+                Connections:  {{\n{connections}}}\n"
+            ),
+        )
+        .unwrap();
+        let document = block_on(FbxDocument::new(&path, &FsResourceIo)).unwrap();
+        let _ = std::fs::remove_file(path);
+        FbxScene::new(&document).unwrap()
+    }
+
+    #[test]
+    fn test_collect_takes() {
+        let scene = load_scene(
+            "fyrox_test_fbx_takes.fbx",
+            r#"    AnimationStack: 1, "AnimStack::Walk", "" {
+        Properties70:  {
+            P: "LocalStart", "KTime", "Time", "",0
+            P: "LocalStop", "KTime", "Time", "",46186158000
+            P:
+            P: "LocalStop"
+        }
+    }
+    AnimationStack: 2, "AnimStack::Run", "" {
+    }
+    AnimationLayer: 3, "AnimLayer::BaseLayer", "" {
+    }
+    AnimationLayer: 4, "AnimLayer::BaseLayer", "" {
+    }
+    AnimationCurveNode: 5, "AnimCurveNode::T", "" {
+    }
+    AnimationCurveNode: 6, "AnimCurveNode::R", "" {
+    }
+"#,
+            r#"    C: "OO",3,1
+    C: "OO",4,2
+    C: "OO",5,3
+    C: "OO",6,4
+"#,
+        );
+
+        let assert_curve_node =
+            |take: &super::ImportedTake, expected: FbxAnimationCurveNodeType| {
+                let curve_nodes = take.curve_nodes.as_ref().unwrap();
+                assert_eq!(curve_nodes.len(), 1);
+                match scene.get(*curve_nodes.iter().next().unwrap()) {
+                    FbxComponent::AnimationCurveNode(curve_node) => {
+                        assert!(curve_node.actual_type == expected)
+                    }
+                    _ => panic!("Curve node expected!"),
+                }
+            };
+
+        let takes = collect_takes(&scene);
+        assert_eq!(takes.len(), 2);
+
+        // Malformed properties of the stack are skipped instead of failing the import.
+        assert_eq!(takes[0].animation.name(), "Walk");
+        assert_eq!(takes[0].time_span, Some(0.0..1.0));
+        assert_curve_node(&takes[0], FbxAnimationCurveNodeType::Translation);
+
+        assert_eq!(takes[1].animation.name(), "Run");
+        assert_eq!(takes[1].time_span, None);
+        assert_curve_node(&takes[1], FbxAnimationCurveNodeType::Rotation);
+    }
+
+    #[test]
+    fn test_collect_takes_without_stacks() {
+        let scene = load_scene(
+            "fyrox_test_fbx_no_takes.fbx",
+            r#"    AnimationCurveNode: 1, "AnimCurveNode::T", "" {
+    }
+"#,
+            "",
+        );
+
+        let takes = collect_takes(&scene);
+        assert_eq!(takes.len(), 1);
+        assert_eq!(takes[0].animation.name(), "Animation");
+        assert_eq!(takes[0].time_span, None);
+        assert!(takes[0].curve_nodes.is_none());
+    }
+}
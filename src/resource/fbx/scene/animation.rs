@@ -1,11 +1,12 @@
 use crate::{
-    core::pool::Handle,
+    core::{log::Log, pool::Handle},
     resource::fbx::{
         document::{FbxNode, FbxNodeContainer},
         scene::{FbxComponent, FBX_TIME_UNIT},
     },
 };
 use fxhash::FxHashMap;
+use std::ops::Range;
 
 pub struct FbxTimeValuePair {
     pub time: f32,
@@ -75,3 +76,64 @@ impl FbxAnimationCurveNode {
         })
     }
 }
+
+/// Animation stack (also known as "take") is a named set of animation layers. A file could contain
+/// multiple stacks, each of them is imported as a separate animation.
+pub struct FbxAnimationStack {
+    pub name: String,
+    /// Time range of the stack in seconds (if specified).
+    pub time_span: Option<Range<f32>>,
+    pub layers: Vec<Handle<FbxComponent>>,
+}
+
+impl FbxAnimationStack {
+    pub fn read(node_handle: Handle<FbxNode>, nodes: &FbxNodeContainer) -> Result<Self, String> {
+        let mut name = nodes.get(node_handle).get_attrib(1)?.as_string();
+
+        // Remove ASCII prefix (`AnimStack::Name`) or binary suffix (`Name\0\x01AnimStack`).
+        if let Some(without_prefix) = name.strip_prefix("AnimStack::") {
+            name = without_prefix.to_string();
+        }
+        if let Some(end) = name.find('\0') {
+            name.truncate(end);
+        }
+
+        let mut local_start = None;
+        let mut local_stop = None;
+        if let Ok(properties) = nodes.get_by_name(node_handle, "Properties70") {
+            for property_handle in properties.children() {
+                let property = nodes.get(*property_handle);
+                // Malformed properties are skipped, the time span is optional anyway.
+                let time = || -> Result<f32, String> {
+                    Ok(((property.get_attrib(4)?.as_i64()? as f64) * FBX_TIME_UNIT) as f32)
+                };
+                let result = property.get_attrib(0).and_then(|name| {
+                    match name.as_string().as_str() {
+                        "LocalStart" => local_start = Some(time()?),
+                        "LocalStop" => local_stop = Some(time()?),
+                        _ => (),
+                    }
+                    Ok(())
+                });
+                if let Err(err) = result {
+                    Log::warn(format!(
+                        "FBX: Skipping malformed property of {name} animation stack. Reason: {err}"
+                    ));
+                }
+            }
+        }
+
+        Ok(Self {
+            name,
+            time_span: match (local_start, local_stop) {
+                (Some(start), Some(stop)) if start < stop => Some(start..stop),
+                _ => None,
+            },
+            layers: Default::default(),
+        })
+    }
+}
+
+pub struct FbxAnimationLayer {
+    pub curve_nodes: Vec<Handle<FbxComponent>>,
+}
@@ -9,7 +9,9 @@ use crate::{
         error::FbxError,
         fix_index,
         scene::{
-            animation::{FbxAnimationCurve, FbxAnimationCurveNode},
+            animation::{
+                FbxAnimationCurve, FbxAnimationCurveNode, FbxAnimationLayer, FbxAnimationStack,
+            },
             geometry::{FbxMeshGeometry, FbxShapeGeometry},
             light::FbxLight,
             model::FbxModel,
@@ -94,6 +96,17 @@ impl FbxScene {
                         FbxAnimationCurve::read(*object_handle, nodes)?,
                     ));
                 }
+                "AnimationStack" => {
+                    component_handle = components.spawn(FbxComponent::AnimationStack(
+                        FbxAnimationStack::read(*object_handle, nodes)?,
+                    ));
+                }
+                "AnimationLayer" => {
+                    component_handle =
+                        components.spawn(FbxComponent::AnimationLayer(FbxAnimationLayer {
+                            curve_nodes: Default::default(),
+                        }));
+                }
                 "AnimationCurveNode" => {
                     component_handle = components.spawn(FbxComponent::AnimationCurveNode(
                         FbxAnimationCurveNode::read(*object_handle, nodes)?,
@@ -177,6 +190,18 @@ fn link_child_with_parent_component(
                 material.textures.push((property, child_handle));
             }
         }
+        // Link animation stack with animation layers
+        FbxComponent::AnimationStack(stack) => {
+            if let FbxComponent::AnimationLayer(_) = child {
+                stack.layers.push(child_handle);
+            }
+        }
+        // Link animation layer with animation curve nodes
+        FbxComponent::AnimationLayer(layer) => {
+            if let FbxComponent::AnimationCurveNode(_) = child {
+                layer.curve_nodes.push(child_handle);
+            }
+        }
         // Link animation curve node with animation curve
         FbxComponent::AnimationCurveNode(anim_curve_node) => {
             if let FbxComponent::AnimationCurve(_) = child {
@@ -220,6 +245,8 @@ pub enum FbxComponent {
     Light(FbxLight),
     Model(Box<FbxModel>),
     Material(FbxMaterial),
+    AnimationStack(FbxAnimationStack),
+    AnimationLayer(FbxAnimationLayer),
     AnimationCurveNode(FbxAnimationCurveNode),
     AnimationCurve(FbxAnimationCurve),
     MeshGeometry(Box<FbxMeshGeometry>),
@@ -0,0 +1,1006 @@
+//! glTF document model. It contains only the parts of the format that are used by the importer, every index
+//! of the document is validated during parsing, so it is safe to use them to access the respective arrays.
+
+use crate::{
+    core::{
+        algebra::{Matrix4, Quaternion, UnitQuaternion, Vector3},
+        json::JsonValue,
+    },
+    resource::gltf::error::GltfError,
+};
+
+/// `glTF` magic of binary files.
+const GLB_MAGIC: &[u8; 4] = b"glTF";
+/// Type of the chunk with JSON content of binary files.
+const GLB_JSON_CHUNK: &[u8; 4] = b"JSON";
+/// Type of the chunk with binary buffer of binary files.
+const GLB_BIN_CHUNK: &[u8; 4] = b"BIN\0";
+
+/// Primitive mode that defines a list of triangles.
+pub const TRIANGLES_MODE: u32 = 4;
+
+pub struct GltfBuffer {
+    /// `None` means that the buffer refers to the binary chunk of a GLB file.
+    pub uri: Option<String>,
+}
+
+pub struct GltfBufferView {
+    pub buffer: usize,
+    pub byte_offset: usize,
+    pub byte_length: usize,
+    pub byte_stride: Option<usize>,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ComponentType {
+    I8,
+    U8,
+    I16,
+    U16,
+    U32,
+    F32,
+}
+
+impl ComponentType {
+    fn from_code(code: usize) -> Option<Self> {
+        Some(match code {
+            5120 => Self::I8,
+            5121 => Self::U8,
+            5122 => Self::I16,
+            5123 => Self::U16,
+            5125 => Self::U32,
+            5126 => Self::F32,
+            _ => return None,
+        })
+    }
+
+    fn size(self) -> usize {
+        match self {
+            Self::I8 | Self::U8 => 1,
+            Self::I16 | Self::U16 => 2,
+            Self::U32 | Self::F32 => 4,
+        }
+    }
+
+    fn read(self, bytes: &[u8], normalized: bool) -> f32 {
+        // Normalization rules are defined by the specification (section 3.11).
+        match self {
+            Self::I8 if normalized => (bytes[0] as i8 as f32 / 127.0).max(-1.0),
+            Self::I8 => bytes[0] as i8 as f32,
+            Self::U8 if normalized => bytes[0] as f32 / 255.0,
+            Self::U8 => bytes[0] as f32,
+            Self::I16 if normalized => {
+                (i16::from_le_bytes([bytes[0], bytes[1]]) as f32 / 32767.0).max(-1.0)
+            }
+            Self::I16 => i16::from_le_bytes([bytes[0], bytes[1]]) as f32,
+            Self::U16 if normalized => u16::from_le_bytes([bytes[0], bytes[1]]) as f32 / 65535.0,
+            Self::U16 => u16::from_le_bytes([bytes[0], bytes[1]]) as f32,
+            Self::U32 => u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as f32,
+            Self::F32 => f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+        }
+    }
+
+    fn read_integer(self, bytes: &[u8]) -> Option<u32> {
+        match self {
+            Self::U8 => Some(bytes[0] as u32),
+            Self::U16 => Some(u16::from_le_bytes([bytes[0], bytes[1]]) as u32),
+            Self::U32 => Some(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])),
+            Self::I8 | Self::I16 | Self::F32 => None,
+        }
+    }
+}
+
+pub struct GltfSparse {
+    pub count: usize,
+    pub indices_view: usize,
+    pub indices_offset: usize,
+    pub indices_type: ComponentType,
+    pub values_view: usize,
+    pub values_offset: usize,
+}
+
+pub struct GltfAccessor {
+    pub buffer_view: Option<usize>,
+    pub byte_offset: usize,
+    pub component_type: ComponentType,
+    pub normalized: bool,
+    pub count: usize,
+    /// Amount of components per element (1 for scalars, 3 for 3D vectors, 16 for 4x4 matrices and so on).
+    pub components: usize,
+    pub sparse: Option<GltfSparse>,
+}
+
+pub struct GltfImage {
+    pub uri: Option<String>,
+    pub buffer_view: Option<usize>,
+}
+
+pub struct GltfTexture {
+    /// Index of an image, it could be missing if the image is provided by an unsupported extension.
+    pub source: Option<usize>,
+}
+
+pub struct GltfMaterial {
+    pub double_sided: bool,
+    pub base_color_factor: [f32; 4],
+    pub base_color_texture: Option<usize>,
+    pub metallic_factor: f32,
+    pub roughness_factor: f32,
+    pub metallic_roughness_texture: Option<usize>,
+    pub normal_texture: Option<usize>,
+    pub occlusion_texture: Option<usize>,
+    pub emissive_texture: Option<usize>,
+    pub emissive_factor: [f32; 3],
+}
+
+/// Accessors of vertex attributes (or of their displacements for morph targets).
+#[derive(Default)]
+pub struct GltfAttributes {
+    pub positions: Option<usize>,
+    pub normals: Option<usize>,
+    pub tangents: Option<usize>,
+    pub tex_coords: Option<usize>,
+    pub joints: Option<usize>,
+    pub weights: Option<usize>,
+}
+
+pub struct GltfPrimitive {
+    pub attributes: GltfAttributes,
+    pub indices: Option<usize>,
+    pub material: Option<usize>,
+    pub mode: u32,
+    pub targets: Vec<GltfAttributes>,
+}
+
+pub struct GltfMesh {
+    pub primitives: Vec<GltfPrimitive>,
+    pub weights: Option<Vec<f32>>,
+}
+
+pub enum GltfTransform {
+    /// Column-major 4x4 matrix.
+    Matrix([f32; 16]),
+    Decomposed {
+        translation: [f32; 3],
+        /// Quaternion in `[x, y, z, w]` order.
+        rotation: [f32; 4],
+        scale: [f32; 3],
+    },
+}
+
+impl GltfTransform {
+    /// Returns translation, rotation and scale of the transform. Matrices are decomposed, negative determinant
+    /// of a matrix is moved to the Z scale.
+    pub fn decomposed(&self) -> (Vector3<f32>, UnitQuaternion<f32>, Vector3<f32>) {
+        match self {
+            GltfTransform::Matrix(matrix) => {
+                let matrix = Matrix4::from_column_slice(matrix);
+                let translation = matrix.fixed_view::<3, 1>(0, 3).into_owned();
+                let mut basis = matrix.fixed_view::<3, 3>(0, 0).into_owned();
+                let scale = Vector3::new(
+                    basis.column(0).norm(),
+                    basis.column(1).norm(),
+                    basis.determinant().signum() * basis.column(2).norm(),
+                );
+                for (mut column, scale) in basis.column_iter_mut().zip(scale.iter()) {
+                    if *scale != 0.0 {
+                        column /= *scale;
+                    }
+                }
+                (translation, UnitQuaternion::from_matrix(&basis), scale)
+            }
+            GltfTransform::Decomposed {
+                translation,
+                rotation,
+                scale,
+            } => (
+                Vector3::from(*translation),
+                UnitQuaternion::from_quaternion(Quaternion::new(
+                    rotation[3],
+                    rotation[0],
+                    rotation[1],
+                    rotation[2],
+                )),
+                Vector3::from(*scale),
+            ),
+        }
+    }
+}
+
+pub struct GltfNode {
+    pub name: Option<String>,
+    pub children: Vec<usize>,
+    pub mesh: Option<usize>,
+    pub skin: Option<usize>,
+    pub weights: Option<Vec<f32>>,
+    pub transform: GltfTransform,
+}
+
+pub struct GltfSkin {
+    pub joints: Vec<usize>,
+    pub inverse_bind_matrices: Option<usize>,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Interpolation {
+    Linear,
+    Step,
+    CubicSpline,
+}
+
+pub struct GltfAnimationSampler {
+    pub input: usize,
+    pub output: usize,
+    pub interpolation: Interpolation,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum AnimationPath {
+    Translation,
+    Rotation,
+    Scale,
+    Weights,
+}
+
+pub struct GltfChannel {
+    pub sampler: usize,
+    /// Target node, it could be missing if the target is provided by an unsupported extension.
+    pub node: Option<usize>,
+    /// Animated property, `None` for unsupported properties (provided by extensions).
+    pub path: Option<AnimationPath>,
+}
+
+pub struct GltfAnimation {
+    pub name: Option<String>,
+    pub channels: Vec<GltfChannel>,
+    pub samplers: Vec<GltfAnimationSampler>,
+}
+
+pub struct GltfDocument {
+    pub buffers: Vec<GltfBuffer>,
+    pub buffer_views: Vec<GltfBufferView>,
+    pub accessors: Vec<GltfAccessor>,
+    pub images: Vec<GltfImage>,
+    pub textures: Vec<GltfTexture>,
+    pub materials: Vec<GltfMaterial>,
+    pub meshes: Vec<GltfMesh>,
+    pub nodes: Vec<GltfNode>,
+    pub skins: Vec<GltfSkin>,
+    pub animations: Vec<GltfAnimation>,
+}
+
+fn invalid(message: impl Into<String>) -> GltfError {
+    GltfError::InvalidDocument(message.into())
+}
+
+/// Wraps a JSON object and provides typed access to its properties, `name` is used for error messages.
+#[derive(Copy, Clone)]
+struct Object<'a> {
+    json: &'a JsonValue,
+    name: &'a str,
+}
+
+impl<'a> Object<'a> {
+    fn new(json: &'a JsonValue, name: &'a str) -> Result<Self, GltfError> {
+        if json.as_object().is_some() {
+            Ok(Self { json, name })
+        } else {
+            Err(invalid(format!("{} must be an object", name)))
+        }
+    }
+
+    fn invalid(&self, property: &str) -> GltfError {
+        invalid(format!("{} has invalid {} property", self.name, property))
+    }
+
+    fn get(&self, property: &str) -> Option<&'a JsonValue> {
+        self.json.get(property).filter(|value| !value.is_null())
+    }
+
+    fn object(&self, property: &'a str) -> Result<Option<Object<'a>>, GltfError> {
+        self.get(property)
+            .map(|json| Object::new(json, property))
+            .transpose()
+    }
+
+    fn array(&self, property: &str) -> Result<&'a [JsonValue], GltfError> {
+        match self.get(property) {
+            Some(json) => json.as_array().ok_or_else(|| self.invalid(property)),
+            None => Ok(&[]),
+        }
+    }
+
+    fn objects(&self, property: &'a str) -> Result<Vec<Object<'a>>, GltfError> {
+        self.array(property)?
+            .iter()
+            .map(|json| Object::new(json, property))
+            .collect()
+    }
+
+    fn index(&self, property: &str) -> Result<Option<usize>, GltfError> {
+        self.get(property)
+            .map(|json| json.as_usize().ok_or_else(|| self.invalid(property)))
+            .transpose()
+    }
+
+    fn required_index(&self, property: &str) -> Result<usize, GltfError> {
+        self.index(property)?
+            .ok_or_else(|| invalid(format!("{} has no {} property", self.name, property)))
+    }
+
+    fn indices(&self, property: &str) -> Result<Vec<usize>, GltfError> {
+        self.array(property)?
+            .iter()
+            .map(|json| json.as_usize().ok_or_else(|| self.invalid(property)))
+            .collect()
+    }
+
+    fn number(&self, property: &str, default: f32) -> Result<f32, GltfError> {
+        match self.get(property) {
+            Some(json) => json.as_f32().ok_or_else(|| self.invalid(property)),
+            None => Ok(default),
+        }
+    }
+
+    fn numbers(&self, property: &str) -> Result<Option<Vec<f32>>, GltfError> {
+        self.get(property)
+            .map(|json| {
+                json.as_array()
+                    .ok_or_else(|| self.invalid(property))?
+                    .iter()
+                    .map(|json| json.as_f32().ok_or_else(|| self.invalid(property)))
+                    .collect()
+            })
+            .transpose()
+    }
+
+    fn vector<const N: usize>(
+        &self,
+        property: &str,
+        default: [f32; N],
+    ) -> Result<[f32; N], GltfError> {
+        match self.numbers(property)? {
+            Some(numbers) => numbers.try_into().map_err(|_| self.invalid(property)),
+            None => Ok(default),
+        }
+    }
+
+    fn boolean(&self, property: &str) -> Result<bool, GltfError> {
+        match self.get(property) {
+            Some(json) => json.as_bool().ok_or_else(|| self.invalid(property)),
+            None => Ok(false),
+        }
+    }
+
+    fn string(&self, property: &str) -> Result<Option<&'a str>, GltfError> {
+        self.get(property)
+            .map(|json| json.as_str().ok_or_else(|| self.invalid(property)))
+            .transpose()
+    }
+
+    /// Reads an index of a texture from a texture info object (`{ "index": 0, "texCoord": 0 }`).
+    fn texture(&self, property: &'a str) -> Result<Option<usize>, GltfError> {
+        match self.object(property)? {
+            Some(info) => info.required_index("index").map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
+fn parse_buffer_view(view: Object) -> Result<GltfBufferView, GltfError> {
+    Ok(GltfBufferView {
+        buffer: view.required_index("buffer")?,
+        byte_offset: view.index("byteOffset")?.unwrap_or_default(),
+        byte_length: view.required_index("byteLength")?,
+        byte_stride: view.index("byteStride")?,
+    })
+}
+
+fn parse_accessor(accessor: Object) -> Result<GltfAccessor, GltfError> {
+    let component_type = ComponentType::from_code(accessor.required_index("componentType")?)
+        .ok_or_else(|| accessor.invalid("componentType"))?;
+    let components = match accessor.string("type")? {
+        Some("SCALAR") => 1,
+        Some("VEC2") => 2,
+        Some("VEC3") => 3,
+        Some("VEC4") | Some("MAT2") => 4,
+        Some("MAT3") => 9,
+        Some("MAT4") => 16,
+        _ => return Err(accessor.invalid("type")),
+    };
+    let sparse = match accessor.object("sparse")? {
+        Some(sparse) => {
+            let indices = sparse
+                .object("indices")?
+                .ok_or_else(|| sparse.invalid("indices"))?;
+            let values = sparse
+                .object("values")?
+                .ok_or_else(|| sparse.invalid("values"))?;
+            Some(GltfSparse {
+                count: sparse.required_index("count")?,
+                indices_view: indices.required_index("bufferView")?,
+                indices_offset: indices.index("byteOffset")?.unwrap_or_default(),
+                indices_type: ComponentType::from_code(indices.required_index("componentType")?)
+                    .filter(|ty| ty.read_integer(&[0; 4]).is_some())
+                    .ok_or_else(|| indices.invalid("componentType"))?,
+                values_view: values.required_index("bufferView")?,
+                values_offset: values.index("byteOffset")?.unwrap_or_default(),
+            })
+        }
+        None => None,
+    };
+    Ok(GltfAccessor {
+        buffer_view: accessor.index("bufferView")?,
+        byte_offset: accessor.index("byteOffset")?.unwrap_or_default(),
+        component_type,
+        normalized: accessor.boolean("normalized")?,
+        count: accessor.required_index("count")?,
+        components,
+        sparse,
+    })
+}
+
+fn parse_material(material: Object) -> Result<GltfMaterial, GltfError> {
+    let pbr = material.object("pbrMetallicRoughness")?;
+    let pbr_texture = |property| pbr.map_or(Ok(None), |pbr| pbr.texture(property));
+    let pbr_number =
+        |property, default| pbr.map_or(Ok(default), |pbr| pbr.number(property, default));
+    Ok(GltfMaterial {
+        double_sided: material.boolean("doubleSided")?,
+        base_color_factor: match pbr {
+            Some(pbr) => pbr.vector("baseColorFactor", [1.0; 4])?,
+            None => [1.0; 4],
+        },
+        base_color_texture: pbr_texture("baseColorTexture")?,
+        metallic_factor: pbr_number("metallicFactor", 1.0)?,
+        roughness_factor: pbr_number("roughnessFactor", 1.0)?,
+        metallic_roughness_texture: pbr_texture("metallicRoughnessTexture")?,
+        normal_texture: material.texture("normalTexture")?,
+        occlusion_texture: material.texture("occlusionTexture")?,
+        emissive_texture: material.texture("emissiveTexture")?,
+        emissive_factor: material.vector("emissiveFactor", [0.0; 3])?,
+    })
+}
+
+fn parse_attributes(attributes: Object) -> Result<GltfAttributes, GltfError> {
+    Ok(GltfAttributes {
+        positions: attributes.index("POSITION")?,
+        normals: attributes.index("NORMAL")?,
+        tangents: attributes.index("TANGENT")?,
+        tex_coords: attributes.index("TEXCOORD_0")?,
+        joints: attributes.index("JOINTS_0")?,
+        weights: attributes.index("WEIGHTS_0")?,
+    })
+}
+
+fn parse_mesh(mesh: Object) -> Result<GltfMesh, GltfError> {
+    let primitives = mesh
+        .objects("primitives")?
+        .into_iter()
+        .map(|primitive| {
+            Ok(GltfPrimitive {
+                attributes: parse_attributes(
+                    primitive
+                        .object("attributes")?
+                        .ok_or_else(|| primitive.invalid("attributes"))?,
+                )?,
+                indices: primitive.index("indices")?,
+                material: primitive.index("material")?,
+                mode: primitive
+                    .index("mode")?
+                    .map_or(TRIANGLES_MODE, |mode| mode as u32),
+                targets: primitive
+                    .objects("targets")?
+                    .into_iter()
+                    .map(parse_attributes)
+                    .collect::<Result<_, _>>()?,
+            })
+        })
+        .collect::<Result<_, GltfError>>()?;
+    Ok(GltfMesh {
+        primitives,
+        weights: mesh.numbers("weights")?,
+    })
+}
+
+fn parse_node(node: Object) -> Result<GltfNode, GltfError> {
+    let transform = match node.vector("matrix", [0.0; 16]) {
+        Ok(matrix) if node.get("matrix").is_some() => GltfTransform::Matrix(matrix),
+        Ok(_) => GltfTransform::Decomposed {
+            translation: node.vector("translation", [0.0; 3])?,
+            rotation: node.vector("rotation", [0.0, 0.0, 0.0, 1.0])?,
+            scale: node.vector("scale", [1.0; 3])?,
+        },
+        Err(e) => return Err(e),
+    };
+    Ok(GltfNode {
+        name: node.string("name")?.map(|name| name.to_owned()),
+        children: node.indices("children")?,
+        mesh: node.index("mesh")?,
+        skin: node.index("skin")?,
+        weights: node.numbers("weights")?,
+        transform,
+    })
+}
+
+fn parse_animation(animation: Object) -> Result<GltfAnimation, GltfError> {
+    let channels = animation
+        .objects("channels")?
+        .into_iter()
+        .map(|channel| {
+            let target = channel
+                .object("target")?
+                .ok_or_else(|| channel.invalid("target"))?;
+            Ok(GltfChannel {
+                sampler: channel.required_index("sampler")?,
+                node: target.index("node")?,
+                path: match target.string("path")? {
+                    Some("translation") => Some(AnimationPath::Translation),
+                    Some("rotation") => Some(AnimationPath::Rotation),
+                    Some("scale") => Some(AnimationPath::Scale),
+                    Some("weights") => Some(AnimationPath::Weights),
+                    _ => None,
+                },
+            })
+        })
+        .collect::<Result<_, GltfError>>()?;
+    let samplers = animation
+        .objects("samplers")?
+        .into_iter()
+        .map(|sampler| {
+            Ok(GltfAnimationSampler {
+                input: sampler.required_index("input")?,
+                output: sampler.required_index("output")?,
+                interpolation: match sampler.string("interpolation")? {
+                    None | Some("LINEAR") => Interpolation::Linear,
+                    Some("STEP") => Interpolation::Step,
+                    Some("CUBICSPLINE") => Interpolation::CubicSpline,
+                    Some(_) => return Err(sampler.invalid("interpolation")),
+                },
+            })
+        })
+        .collect::<Result<_, GltfError>>()?;
+    Ok(GltfAnimation {
+        name: animation.string("name")?.map(|name| name.to_owned()),
+        channels,
+        samplers,
+    })
+}
+
+fn parse_all<'a, T>(
+    root: Object<'a>,
+    property: &'a str,
+    parse: impl FnMut(Object<'a>) -> Result<T, GltfError>,
+) -> Result<Vec<T>, GltfError> {
+    root.objects(property)?.into_iter().map(parse).collect()
+}
+
+/// Splits binary glTF (GLB) to its JSON and binary chunks.
+fn split_glb(data: &[u8]) -> Result<(&[u8], Option<&[u8]>), GltfError> {
+    fn chunk<'a>(data: &mut &'a [u8], chunk_type: &[u8; 4]) -> Result<&'a [u8], GltfError> {
+        if data.len() < 8 || &data[4..8] != chunk_type {
+            return Err(invalid("GLB chunk is missing or has unexpected type"));
+        }
+        let length = u32::from_le_bytes([data[0], data[1], data[2], data[3]]) as usize;
+        let content = data
+            .get(8..8 + length)
+            .ok_or_else(|| invalid("GLB chunk is truncated"))?;
+        *data = &data[8 + length..];
+        Ok(content)
+    }
+
+    let mut chunks = data
+        .get(12..)
+        .ok_or_else(|| invalid("GLB header is truncated"))?;
+    let version = u32::from_le_bytes([data[4], data[5], data[6], data[7]]);
+    if version != 2 {
+        return Err(invalid(format!("Unsupported GLB version {}", version)));
+    }
+    let json = chunk(&mut chunks, GLB_JSON_CHUNK)?;
+    let bin = if chunks.is_empty() {
+        None
+    } else {
+        Some(chunk(&mut chunks, GLB_BIN_CHUNK)?)
+    };
+    Ok((json, bin))
+}
+
+impl GltfDocument {
+    /// Parses either a text (`.gltf`) or a binary (`.glb`) glTF file. Returns the document and the content of
+    /// the binary chunk of GLB files.
+    pub fn parse(data: &[u8]) -> Result<(Self, Option<Vec<u8>>), GltfError> {
+        let (json, blob) = if data.starts_with(GLB_MAGIC) {
+            split_glb(data)?
+        } else {
+            (data, None)
+        };
+        let text = std::str::from_utf8(json).map_err(|_| invalid("JSON is not valid UTF-8"))?;
+        // Text files could start with a byte order mark.
+        let json = JsonValue::parse(text.trim_start_matches('\u{feff}'))?;
+        let root = Object::new(&json, "document")?;
+
+        let version = root
+            .object("asset")?
+            .ok_or_else(|| invalid("Asset info is missing"))?
+            .string("version")?
+            .unwrap_or_default();
+        if !version.starts_with("2.") {
+            return Err(invalid(format!("Unsupported glTF version {}", version)));
+        }
+
+        let document = Self {
+            buffers: parse_all(root, "buffers", |buffer| {
+                Ok(GltfBuffer {
+                    uri: buffer.string("uri")?.map(|uri| uri.to_owned()),
+                })
+            })?,
+            buffer_views: parse_all(root, "bufferViews", parse_buffer_view)?,
+            accessors: parse_all(root, "accessors", parse_accessor)?,
+            images: parse_all(root, "images", |image| {
+                Ok(GltfImage {
+                    uri: image.string("uri")?.map(|uri| uri.to_owned()),
+                    buffer_view: image.index("bufferView")?,
+                })
+            })?,
+            textures: parse_all(root, "textures", |texture| {
+                Ok(GltfTexture {
+                    source: texture.index("source")?,
+                })
+            })?,
+            materials: parse_all(root, "materials", parse_material)?,
+            meshes: parse_all(root, "meshes", parse_mesh)?,
+            nodes: parse_all(root, "nodes", parse_node)?,
+            skins: parse_all(root, "skins", |skin| {
+                Ok(GltfSkin {
+                    joints: skin.indices("joints")?,
+                    inverse_bind_matrices: skin.index("inverseBindMatrices")?,
+                })
+            })?,
+            animations: parse_all(root, "animations", parse_animation)?,
+        };
+        document.validate()?;
+
+        Ok((document, blob.map(|blob| blob.to_vec())))
+    }
+
+    /// Checks that every index of the document refers to an existing object.
+    fn validate(&self) -> Result<(), GltfError> {
+        fn check<'a>(
+            indices: impl IntoIterator<Item = &'a usize>,
+            len: usize,
+            what: &str,
+        ) -> Result<(), GltfError> {
+            match indices.into_iter().find(|index| **index >= len) {
+                Some(index) => Err(invalid(format!("Invalid {} index {}", what, index))),
+                None => Ok(()),
+            }
+        }
+
+        let accessors = self.accessors.len();
+        let views = self.buffer_views.len();
+
+        check(
+            self.buffer_views.iter().map(|view| &view.buffer),
+            self.buffers.len(),
+            "buffer",
+        )?;
+        for accessor in self.accessors.iter() {
+            check(&accessor.buffer_view, views, "buffer view")?;
+            if let Some(sparse) = accessor.sparse.as_ref() {
+                check(
+                    [&sparse.indices_view, &sparse.values_view],
+                    views,
+                    "buffer view",
+                )?;
+            }
+        }
+        check(
+            self.images
+                .iter()
+                .filter_map(|image| image.buffer_view.as_ref()),
+            views,
+            "buffer view",
+        )?;
+        check(
+            self.textures
+                .iter()
+                .filter_map(|texture| texture.source.as_ref()),
+            self.images.len(),
+            "image",
+        )?;
+        for material in self.materials.iter() {
+            check(
+                [
+                    &material.base_color_texture,
+                    &material.metallic_roughness_texture,
+                    &material.normal_texture,
+                    &material.occlusion_texture,
+                    &material.emissive_texture,
+                ]
+                .into_iter()
+                .flatten(),
+                self.textures.len(),
+                "texture",
+            )?;
+        }
+        for primitive in self.meshes.iter().flat_map(|mesh| mesh.primitives.iter()) {
+            for attributes in std::iter::once(&primitive.attributes).chain(primitive.targets.iter())
+            {
+                check(
+                    [
+                        &attributes.positions,
+                        &attributes.normals,
+                        &attributes.tangents,
+                        &attributes.tex_coords,
+                        &attributes.joints,
+                        &attributes.weights,
+                    ]
+                    .into_iter()
+                    .flatten(),
+                    accessors,
+                    "accessor",
+                )?;
+            }
+            check(&primitive.indices, accessors, "accessor")?;
+            check(&primitive.material, self.materials.len(), "material")?;
+        }
+        for node in self.nodes.iter() {
+            check(&node.children, self.nodes.len(), "node")?;
+            check(&node.mesh, self.meshes.len(), "mesh")?;
+            check(&node.skin, self.skins.len(), "skin")?;
+        }
+        for skin in self.skins.iter() {
+            check(&skin.joints, self.nodes.len(), "node")?;
+            check(&skin.inverse_bind_matrices, accessors, "accessor")?;
+        }
+        for animation in self.animations.iter() {
+            for sampler in animation.samplers.iter() {
+                check([&sampler.input, &sampler.output], accessors, "accessor")?;
+            }
+            for channel in animation.channels.iter() {
+                check(&channel.node, self.nodes.len(), "node")?;
+                check(&[channel.sampler], animation.samplers.len(), "sampler")?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the bytes of a buffer view.
+    pub fn view_bytes<'a>(
+        &self,
+        buffers: &'a [Vec<u8>],
+        view: usize,
+    ) -> Result<&'a [u8], GltfError> {
+        let view = &self.buffer_views[view];
+        buffers
+            .get(view.buffer)
+            .and_then(|buffer| buffer.get(view.byte_offset..view.byte_offset + view.byte_length))
+            .ok_or_else(|| invalid("Buffer view is out of bounds"))
+    }
+
+    /// Calls the given function for the bytes of every component of every element of an accessor, sparse
+    /// substitution is applied. The function gets the index of an element and the index of a component.
+    fn visit_components(
+        &self,
+        buffers: &[Vec<u8>],
+        accessor: &GltfAccessor,
+        mut func: impl FnMut(usize, usize, &[u8]),
+    ) -> Result<(), GltfError> {
+        let component_size = accessor.component_type.size();
+        let element_size = component_size * accessor.components;
+
+        let mut visit_elements = |bytes: &[u8],
+                                  offset: usize,
+                                  stride: usize,
+                                  indices: &mut dyn Iterator<Item = (usize, usize)>|
+         -> Result<(), GltfError> {
+            for (element, position) in indices {
+                let start = offset + position * stride;
+                let element_bytes = bytes
+                    .get(start..start + element_size)
+                    .ok_or_else(|| invalid("Accessor is out of bounds"))?;
+                for (component, bytes) in element_bytes.chunks_exact(component_size).enumerate() {
+                    func(element, component, bytes);
+                }
+            }
+            Ok(())
+        };
+
+        match accessor.buffer_view {
+            Some(view) => {
+                let stride = self.buffer_views[view].byte_stride.unwrap_or(element_size);
+                visit_elements(
+                    self.view_bytes(buffers, view)?,
+                    accessor.byte_offset,
+                    stride.max(element_size),
+                    &mut (0..accessor.count).map(|i| (i, i)),
+                )?;
+            }
+            // Accessor without a buffer view is filled with zeros.
+            None => {
+                let zeros = vec![0; element_size];
+                visit_elements(&zeros, 0, 0, &mut (0..accessor.count).map(|i| (i, i)))?;
+            }
+        }
+
+        if let Some(sparse) = accessor.sparse.as_ref() {
+            let indices_bytes = self.view_bytes(buffers, sparse.indices_view)?;
+            let index_size = sparse.indices_type.size();
+            let indices = (0..sparse.count)
+                .map(|i| {
+                    let start = sparse.indices_offset + i * index_size;
+                    indices_bytes
+                        .get(start..start + index_size)
+                        .and_then(|bytes| sparse.indices_type.read_integer(bytes))
+                        .map(|index| (index as usize, i))
+                        .filter(|(index, _)| *index < accessor.count)
+                        .ok_or_else(|| invalid("Sparse accessor has invalid indices"))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            visit_elements(
+                self.view_bytes(buffers, sparse.values_view)?,
+                sparse.values_offset,
+                element_size,
+                &mut indices.into_iter(),
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Reads elements of an accessor with `N` components each as floating-point numbers. Integer components are
+    /// normalized if the accessor is normalized or if `normalize` is set (some attributes, texture coordinates for
+    /// example, are always normalized).
+    pub fn read_floats<const N: usize>(
+        &self,
+        buffers: &[Vec<u8>],
+        accessor: usize,
+        normalize: bool,
+    ) -> Result<Vec<[f32; N]>, GltfError> {
+        let accessor = &self.accessors[accessor];
+        if accessor.components != N {
+            return Err(invalid(format!(
+                "Accessor has {} components, {} expected",
+                accessor.components, N
+            )));
+        }
+        let normalized = accessor.normalized || normalize;
+        let mut elements = vec![[0.0; N]; accessor.count];
+        self.visit_components(buffers, accessor, |element, component, bytes| {
+            elements[element][component] = accessor.component_type.read(bytes, normalized);
+        })?;
+        Ok(elements)
+    }
+
+    /// Reads elements of an accessor with `N` components each as unsigned integers (indices, joints, etc.)
+    pub fn read_integers<const N: usize>(
+        &self,
+        buffers: &[Vec<u8>],
+        accessor: usize,
+    ) -> Result<Vec<[u32; N]>, GltfError> {
+        let accessor = &self.accessors[accessor];
+        if accessor.components != N || accessor.component_type.read_integer(&[0; 4]).is_none() {
+            return Err(invalid("Accessor must have unsigned integer components"));
+        }
+        let mut elements = vec![[0; N]; accessor.count];
+        self.visit_components(buffers, accessor, |element, component, bytes| {
+            elements[element][component] = accessor
+                .component_type
+                .read_integer(bytes)
+                .unwrap_or_default();
+        })?;
+        Ok(elements)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::algebra::{UnitQuaternion, Vector3},
+        resource::gltf::document::{GltfDocument, GltfTransform},
+    };
+
+    const MINIMAL: &str = r#"{ "asset": { "version": "2.0" }, "nodes": [ { "matrix": [
+        1, 0, 0, 0, 0, 1, 0, 0, 0, 0, 1, 0, 1, 2, 3, 1 ] } ] }"#;
+
+    #[test]
+    fn test_parse_glb() {
+        let json = MINIMAL.as_bytes();
+        let bin = [1u8, 2, 3, 4];
+        let mut glb = Vec::new();
+        glb.extend_from_slice(b"glTF");
+        glb.extend_from_slice(&2u32.to_le_bytes());
+        glb.extend_from_slice(&((12 + 8 + json.len() + 8 + bin.len()) as u32).to_le_bytes());
+        glb.extend_from_slice(&(json.len() as u32).to_le_bytes());
+        glb.extend_from_slice(b"JSON");
+        glb.extend_from_slice(json);
+        glb.extend_from_slice(&(bin.len() as u32).to_le_bytes());
+        glb.extend_from_slice(b"BIN\0");
+        glb.extend_from_slice(&bin);
+
+        let (document, blob) = GltfDocument::parse(&glb).unwrap();
+        assert_eq!(blob.unwrap(), bin);
+        assert!(matches!(
+            document.nodes[0].transform,
+            GltfTransform::Matrix(matrix) if matrix[12..15] == [1.0, 2.0, 3.0]
+        ));
+
+        // Truncated binary chunk.
+        assert!(GltfDocument::parse(&glb[..glb.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn test_matrix_decomposition() {
+        // Rotation by 90 degrees around Y, mirrored scale (2, 3, -4) and translation (1, 2, 3).
+        let transform = GltfTransform::Matrix([
+            0.0, 0.0, -2.0, 0.0, 0.0, 3.0, 0.0, 0.0, -4.0, 0.0, 0.0, 0.0, 1.0, 2.0, 3.0, 1.0,
+        ]);
+        let (translation, rotation, scale) = transform.decomposed();
+        assert_eq!(translation, Vector3::new(1.0, 2.0, 3.0));
+        assert_eq!(scale, Vector3::new(2.0, 3.0, -4.0));
+        let expected = UnitQuaternion::from_axis_angle(&Vector3::y_axis(), 90.0f32.to_radians());
+        assert!(rotation.angle_to(&expected) < 0.001);
+    }
+
+    #[test]
+    fn test_validation() {
+        assert!(GltfDocument::parse(MINIMAL.as_bytes()).is_ok());
+        for document in [
+            r#"{ "asset": { "version": "1.0" } }"#,
+            r#"{ "nodes": [] }"#,
+            r#"{ "asset": { "version": "2.0" }, "nodes": [ { "children": [ 1 ] } ] }"#,
+            r#"{ "asset": { "version": "2.0" }, "nodes": [ { "mesh": 0 } ] }"#,
+            r#"{ "asset": { "version": "2.0" }, "accessors": [ { "componentType": 5126, "count": 1, "type": "VEC5" } ] }"#,
+            r#"{ "asset": { "version": "2.0" }, "nodes": [ { "translation": [ 1, 2 ] } ] }"#,
+        ] {
+            assert!(
+                GltfDocument::parse(document.as_bytes()).is_err(),
+                "{document}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_read_accessors() {
+        // Two interleaved normalized u8 texture coordinates (stride 4) and a sparse accessor that replaces the
+        // second element of a zero-filled scalar accessor.
+        let (document, _) = GltfDocument::parse(
+            br#"{
+                "asset": { "version": "2.0" },
+                "buffers": [ { "byteLength": 14 } ],
+                "bufferViews": [
+                    { "buffer": 0, "byteOffset": 0, "byteLength": 8, "byteStride": 4 },
+                    { "buffer": 0, "byteOffset": 8, "byteLength": 2 },
+                    { "buffer": 0, "byteOffset": 10, "byteLength": 4 }
+                ],
+                "accessors": [
+                    { "bufferView": 0, "componentType": 5121, "normalized": true, "count": 2, "type": "VEC2" },
+                    { "componentType": 5126, "count": 3, "type": "SCALAR", "sparse": {
+                        "count": 1,
+                        "indices": { "bufferView": 1, "componentType": 5123 },
+                        "values": { "bufferView": 2 }
+                    } }
+                ]
+            }"#,
+        )
+        .unwrap();
+        let mut buffer = vec![255, 0, 9, 9, 0, 255, 9, 9, 1, 0];
+        buffer.extend_from_slice(&5.0f32.to_le_bytes());
+        let buffers = [buffer];
+
+        assert_eq!(
+            document.read_floats::<2>(&buffers, 0, false).unwrap(),
+            vec![[1.0, 0.0], [0.0, 1.0]]
+        );
+        assert_eq!(
+            document.read_integers::<2>(&buffers, 0).unwrap(),
+            vec![[255, 0], [0, 255]]
+        );
+        assert_eq!(
+            document.read_floats::<1>(&buffers, 1, false).unwrap(),
+            vec![[0.0], [5.0], [0.0]]
+        );
+        assert!(document.read_floats::<3>(&buffers, 0, false).is_err());
+        assert!(document.read_floats::<2>(&[vec![0; 4]], 0, false).is_err());
+    }
+}
@@ -0,0 +1,87 @@
+//! Contains all possible errors that can occur during glTF parsing and conversion.
+
+use crate::core::{io::FileLoadError, json::JsonError};
+use std::fmt::{Display, Formatter};
+
+/// See module docs.
+#[derive(Debug)]
+pub enum GltfError {
+    /// glTF document is not a valid JSON.
+    Json(JsonError),
+
+    /// glTF document has invalid structure (missing properties, out-of-bounds indices, invalid GLB header, etc.)
+    InvalidDocument(String),
+
+    /// An error occurred during file loading.
+    FileLoadError(FileLoadError),
+
+    /// A buffer or an image uses a data uri that cannot be decoded.
+    InvalidDataUri(String),
+
+    /// A buffer has no data source (`bin` chunk is missing in a GLB file).
+    MissingBlob,
+
+    /// An image cannot be decoded.
+    Image(image::ImageError),
+
+    /// Primitive has no vertex positions.
+    MissingPositions,
+
+    /// Arbitrary error that can have any meaning.
+    Custom(Box<String>),
+}
+
+impl Display for GltfError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GltfError::Json(v) => {
+                write!(f, "glTF: Unable to parse the document: {v}")
+            }
+            GltfError::InvalidDocument(v) => {
+                write!(f, "glTF: Invalid document: {v}")
+            }
+            GltfError::FileLoadError(v) => {
+                write!(f, "glTF: File load error {v:?}.")
+            }
+            GltfError::InvalidDataUri(v) => {
+                write!(f, "glTF: Unable to decode data uri: {v}")
+            }
+            GltfError::MissingBlob => {
+                write!(f, "glTF: Binary chunk is missing.")
+            }
+            GltfError::Image(v) => {
+                write!(f, "glTF: Unable to decode an image: {v}")
+            }
+            GltfError::MissingPositions => {
+                write!(f, "glTF: Primitive does not have vertex positions.")
+            }
+            GltfError::Custom(v) => {
+                write!(f, "glTF: An error has occurred: {v}")
+            }
+        }
+    }
+}
+
+impl From<JsonError> for GltfError {
+    fn from(err: JsonError) -> Self {
+        GltfError::Json(err)
+    }
+}
+
+impl From<FileLoadError> for GltfError {
+    fn from(err: FileLoadError) -> Self {
+        GltfError::FileLoadError(err)
+    }
+}
+
+impl From<image::ImageError> for GltfError {
+    fn from(err: image::ImageError) -> Self {
+        GltfError::Image(err)
+    }
+}
+
+impl From<String> for GltfError {
+    fn from(err: String) -> Self {
+        GltfError::Custom(Box::new(err))
+    }
+}
@@ -0,0 +1,1133 @@
+//! Contains all methods to load and convert glTF 2.0 model format.
+//!
+//! glTF is an open format for transmission of 3D scenes. Both text (`.gltf` with external or embedded buffers) and
+//! binary (`.glb`) flavours are supported. The importer converts meshes, PBR materials with textures, skins, morph
+//! targets and animations to respective engine entities.
+//!
+//! Normally you should never use methods from this module directly, use resource manager to load
+//! models and create their instances.
+
+mod document;
+pub mod error;
+
+use crate::{
+    animation::{
+        container::{TrackDataContainer, TrackValueKind},
+        track::Track,
+        value::{ValueBinding, ValueType},
+        Animation, AnimationContainer,
+    },
+    asset::manager::ResourceManager,
+    core::{
+        algebra::{Matrix4, Quaternion, UnitQuaternion, Vector2, Vector3, Vector4},
+        color::Color,
+        curve::{Curve, CurveKey, CurveKeyKind},
+        instant::Instant,
+        log::Log,
+        math::TriangleDefinition,
+        pool::Handle,
+        sstorage::ImmutableString,
+        uuid::Uuid,
+    },
+    material::{shader::SamplerFallback, Material, PropertyValue, SharedMaterial},
+    resource::{
+        gltf::{
+            document::{
+                AnimationPath, GltfAnimation, GltfAttributes, GltfDocument, GltfImage,
+                GltfMaterial, GltfNode, GltfPrimitive, Interpolation, TRIANGLES_MODE,
+            },
+            error::GltfError,
+        },
+        model::{MaterialSearchOptions, ModelImportOptions},
+        texture::{
+            Texture, TextureKind, TexturePixelKind, TextureResource, TextureResourceExtension,
+        },
+    },
+    scene::{
+        animation::AnimationPlayerBuilder,
+        base::{BaseBuilder, InstanceId},
+        graph::Graph,
+        mesh::{
+            buffer::{TriangleBuffer, VertexBuffer},
+            surface::{
+                BlendShape, BlendShapesContainer, InputBlendShapeData, Surface, SurfaceData,
+                SurfaceSharedData, VertexWeight, VertexWeightSet,
+            },
+            vertex::{AnimatedVertex, StaticVertex},
+            MeshBuilder,
+        },
+        node::Node,
+        pivot::PivotBuilder,
+        transform::TransformBuilder,
+        Scene,
+    },
+    utils,
+};
+use base64::Engine;
+use fxhash::FxHashMap;
+use fyrox_resource::io::ResourceIo;
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+};
+
+/// Decodes percent-encoded characters (`%20` and so on) of a relative uri.
+fn decode_uri(uri: &str) -> String {
+    let bytes = uri.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            let hex = |byte: u8| (byte as char).to_digit(16);
+            if let (Some(high), Some(low)) = (hex(bytes[i + 1]), hex(bytes[i + 2])) {
+                decoded.push((high * 16 + low) as u8);
+                i += 3;
+                continue;
+            }
+        }
+        decoded.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Tries to decode a `data:` uri, returns `None` if the uri does not contain embedded data.
+fn decode_data_uri(uri: &str) -> Option<Result<Vec<u8>, GltfError>> {
+    let data = uri.strip_prefix("data:")?;
+    Some(match data.split_once(";base64,") {
+        Some((_, payload)) => base64::engine::general_purpose::STANDARD
+            .decode(payload)
+            .map_err(|e| GltfError::InvalidDataUri(e.to_string())),
+        None => Err(GltfError::InvalidDataUri(
+            "Only base64 data uris are supported.".to_string(),
+        )),
+    })
+}
+
+async fn load_buffers(
+    document: &GltfDocument,
+    mut blob: Option<Vec<u8>>,
+    io: &dyn ResourceIo,
+    model_directory: &Path,
+) -> Result<Vec<Vec<u8>>, GltfError> {
+    let mut buffers = Vec::new();
+    for buffer in document.buffers.iter() {
+        let data = match buffer.uri.as_deref() {
+            None => blob.take().ok_or(GltfError::MissingBlob)?,
+            Some(uri) => match decode_data_uri(uri) {
+                Some(data) => data?,
+                None => io.load_file(&model_directory.join(decode_uri(uri))).await?,
+            },
+        };
+        buffers.push(data);
+    }
+    Ok(buffers)
+}
+
+/// Texture role defines how an image is used by a material.
+#[derive(Copy, Clone, Hash, PartialEq, Eq)]
+enum ImageUsage {
+    Color,
+    Metallic,
+    Roughness,
+}
+
+struct TextureContext<'a> {
+    resource_manager: ResourceManager,
+    io: &'a dyn ResourceIo,
+    document: &'a GltfDocument,
+    buffers: &'a [Vec<u8>],
+    model_directory: &'a Path,
+    model_import_options: &'a ModelImportOptions,
+    cache: FxHashMap<(usize, ImageUsage), TextureResource>,
+}
+
+impl<'a> TextureContext<'a> {
+    fn external_image_path(&self, uri: &str) -> PathBuf {
+        let relative = PathBuf::from(decode_uri(uri));
+        match self.model_import_options.material_search_options {
+            MaterialSearchOptions::MaterialsDirectory(ref directory) => {
+                match relative.file_name() {
+                    Some(file_name) => directory.join(file_name),
+                    None => self.model_directory.join(relative),
+                }
+            }
+            _ => self.model_directory.join(relative),
+        }
+    }
+
+    /// Fetches raw (encoded) bytes of an image.
+    async fn image_bytes(&self, image: &GltfImage) -> Result<Vec<u8>, GltfError> {
+        match (image.buffer_view, image.uri.as_deref()) {
+            (Some(view), _) => Ok(self.document.view_bytes(self.buffers, view)?.to_vec()),
+            (None, Some(uri)) => match decode_data_uri(uri) {
+                Some(data) => data,
+                None => Ok(self.io.load_file(&self.external_image_path(uri)).await?),
+            },
+            (None, None) => Err(GltfError::InvalidDocument(
+                "Image has neither a uri nor a buffer view".to_string(),
+            )),
+        }
+    }
+
+    /// Returns a texture for an image of a glTF texture, `None` if the texture has no image or if it cannot be
+    /// loaded.
+    async fn texture(
+        &mut self,
+        texture: usize,
+        usage: ImageUsage,
+        factor: f32,
+    ) -> Option<TextureResource> {
+        let document = self.document;
+        let index = document.textures[texture].source?;
+        let image = &document.images[index];
+        let key = (index, usage);
+        if let Some(texture) = self.cache.get(&key) {
+            return Some(texture.clone());
+        }
+
+        let texture = match (image.buffer_view, image.uri.as_deref(), usage) {
+            // External color images are requested through the resource manager, so they will be shared across
+            // all the models and correctly serialized.
+            (None, Some(uri), ImageUsage::Color) if !uri.starts_with("data:") => Some(
+                self.resource_manager
+                    .request::<Texture, _>(self.external_image_path(uri)),
+            ),
+            _ => match self.decode_embedded(image, usage, factor).await {
+                Ok(texture) => texture,
+                Err(e) => {
+                    Log::err(format!(
+                        "Unable to load glTF image {}. Reason: {}",
+                        index, e
+                    ));
+                    None
+                }
+            },
+        };
+
+        if let Some(texture) = texture.as_ref() {
+            self.cache.insert(key, texture.clone());
+        }
+
+        texture
+    }
+
+    /// Decodes an image and converts it to a texture that stores its content, so it could be serialized as a
+    /// part of a scene. glTF packs metallic and roughness maps in a single image (`B` and `G` channels respectively),
+    /// while the engine uses separate textures, so such images are split.
+    async fn decode_embedded(
+        &self,
+        image: &GltfImage,
+        usage: ImageUsage,
+        factor: f32,
+    ) -> Result<Option<TextureResource>, GltfError> {
+        let bytes = self.image_bytes(image).await?;
+        let decoded = ::image::load_from_memory(&bytes)?.to_rgba8();
+        let kind = TextureKind::Rectangle {
+            width: decoded.width(),
+            height: decoded.height(),
+        };
+        Ok(match usage {
+            ImageUsage::Color => {
+                TextureResource::from_bytes(kind, TexturePixelKind::RGBA8, decoded.into_raw(), true)
+            }
+            ImageUsage::Metallic | ImageUsage::Roughness => {
+                let channel = if usage == ImageUsage::Metallic { 2 } else { 1 };
+                let bytes = decoded
+                    .pixels()
+                    .map(|pixel| (pixel.0[channel] as f32 * factor).clamp(0.0, 255.0) as u8)
+                    .collect();
+                TextureResource::from_bytes(kind, TexturePixelKind::R8, bytes, true)
+            }
+        })
+    }
+}
+
+/// Creates 1x1 texture with a given value, it is used to emulate scalar metallic and roughness factors.
+fn make_constant_texture(value: f32) -> Option<TextureResource> {
+    TextureResource::from_bytes(
+        TextureKind::Rectangle {
+            width: 1,
+            height: 1,
+        },
+        TexturePixelKind::R8,
+        vec![(value.clamp(0.0, 1.0) * 255.0) as u8],
+        true,
+    )
+}
+
+fn set_material_property(material: &mut Material, name: &str, value: PropertyValue) {
+    if let Err(e) = material.set_property(&ImmutableString::new(name), value) {
+        Log::err(format!(
+            "Unable to set material property {} for glTF material! Reason: {:?}",
+            name, e
+        ));
+    }
+}
+
+fn set_material_texture(
+    material: &mut Material,
+    name: &str,
+    texture: Option<TextureResource>,
+    fallback: SamplerFallback,
+) {
+    if let Some(texture) = texture {
+        set_material_property(
+            material,
+            name,
+            PropertyValue::Sampler {
+                value: Some(texture),
+                fallback,
+            },
+        );
+    }
+}
+
+async fn convert_material(
+    material: &GltfMaterial,
+    textures: &mut TextureContext<'_>,
+) -> SharedMaterial {
+    let mut result = if material.double_sided {
+        Material::standard_two_sides()
+    } else {
+        Material::standard()
+    };
+
+    // glTF stores colors in linear space, the engine expects sRGB.
+    let base_color = Vector4::from(material.base_color_factor);
+    set_material_property(
+        &mut result,
+        "diffuseColor",
+        PropertyValue::Color(Color::from(Vector4::new(
+            base_color.x.powf(1.0 / 2.2),
+            base_color.y.powf(1.0 / 2.2),
+            base_color.z.powf(1.0 / 2.2),
+            base_color.w,
+        ))),
+    );
+
+    if let Some(texture) = material.base_color_texture {
+        let texture = textures.texture(texture, ImageUsage::Color, 1.0).await;
+        set_material_texture(
+            &mut result,
+            "diffuseTexture",
+            texture,
+            SamplerFallback::White,
+        );
+    }
+
+    let (metallic, roughness) = match material.metallic_roughness_texture {
+        Some(texture) => (
+            textures
+                .texture(texture, ImageUsage::Metallic, material.metallic_factor)
+                .await,
+            textures
+                .texture(texture, ImageUsage::Roughness, material.roughness_factor)
+                .await,
+        ),
+        None => (
+            make_constant_texture(material.metallic_factor),
+            make_constant_texture(material.roughness_factor),
+        ),
+    };
+    set_material_texture(
+        &mut result,
+        "metallicTexture",
+        metallic,
+        SamplerFallback::Black,
+    );
+    set_material_texture(
+        &mut result,
+        "roughnessTexture",
+        roughness,
+        SamplerFallback::White,
+    );
+
+    if let Some(normal) = material.normal_texture {
+        let texture = textures.texture(normal, ImageUsage::Color, 1.0).await;
+        set_material_texture(
+            &mut result,
+            "normalTexture",
+            texture,
+            SamplerFallback::Normal,
+        );
+    }
+
+    if let Some(occlusion) = material.occlusion_texture {
+        let texture = textures.texture(occlusion, ImageUsage::Color, 1.0).await;
+        set_material_texture(&mut result, "aoTexture", texture, SamplerFallback::White);
+    }
+
+    // Emission is a product of the factor and the texture, the texture is white when it is not specified.
+    let emissive_factor = Vector3::from(material.emissive_factor);
+    let emissive_texture = match material.emissive_texture {
+        Some(texture) => Some(textures.texture(texture, ImageUsage::Color, 1.0).await),
+        None if emissive_factor != Vector3::default() => Some(TextureResource::from_bytes(
+            TextureKind::Rectangle {
+                width: 1,
+                height: 1,
+            },
+            TexturePixelKind::RGB8,
+            vec![255, 255, 255],
+            true,
+        )),
+        None => None,
+    };
+    if let Some(texture) = emissive_texture {
+        set_material_texture(
+            &mut result,
+            "emissionTexture",
+            texture,
+            SamplerFallback::Black,
+        );
+        set_material_property(
+            &mut result,
+            "emissionStrength",
+            PropertyValue::Vector3(emissive_factor),
+        );
+    }
+
+    SharedMaterial::new(result)
+}
+
+/// Joints of a skinned primitive along with their weights, per vertex.
+type SkinData = Vec<([u16; 4], [f32; 4])>;
+
+struct ConvertedPrimitive {
+    surface: Surface,
+    skin_data: Option<SkinData>,
+}
+
+/// Reads an optional vertex attribute, see [`GltfDocument::read_floats`] for `normalize` description.
+fn read_attribute<const N: usize>(
+    document: &GltfDocument,
+    buffers: &[Vec<u8>],
+    accessor: Option<usize>,
+    normalize: bool,
+) -> Result<Option<Vec<[f32; N]>>, GltfError> {
+    accessor
+        .map(|accessor| document.read_floats::<N>(buffers, accessor, normalize))
+        .transpose()
+}
+
+fn convert_primitive(
+    document: &GltfDocument,
+    primitive: &GltfPrimitive,
+    index: usize,
+    buffers: &[Vec<u8>],
+    default_weights: &[f32],
+    skinned: bool,
+) -> Result<Option<ConvertedPrimitive>, GltfError> {
+    if primitive.mode != TRIANGLES_MODE {
+        Log::warn(format!(
+            "glTF: primitive {} uses unsupported mode {}, only triangles are supported!",
+            index, primitive.mode
+        ));
+        return Ok(None);
+    }
+
+    let attributes = &primitive.attributes;
+
+    let positions = read_attribute::<3>(document, buffers, attributes.positions, false)?
+        .ok_or(GltfError::MissingPositions)?
+        .into_iter()
+        .map(Vector3::from)
+        .collect::<Vec<_>>();
+    let vertex_count = positions.len();
+
+    let normals = read_attribute::<3>(document, buffers, attributes.normals, false)?
+        .map(|normals| normals.into_iter().map(Vector3::from).collect::<Vec<_>>());
+    let tangents = read_attribute::<4>(document, buffers, attributes.tangents, false)?
+        .map(|tangents| tangents.into_iter().map(Vector4::from).collect::<Vec<_>>());
+    // Integer texture coordinates and weights are always normalized.
+    let tex_coords = read_attribute::<2>(document, buffers, attributes.tex_coords, true)?
+        .map(|uvs| uvs.into_iter().map(Vector2::from).collect::<Vec<_>>());
+
+    let skin_data = match (attributes.joints, attributes.weights) {
+        (Some(joints), Some(weights)) if skinned => Some(
+            document
+                .read_integers::<4>(buffers, joints)?
+                .into_iter()
+                .map(|joints| joints.map(|j| j.min(u16::MAX as u32) as u16))
+                .zip(document.read_floats::<4>(buffers, weights, true)?)
+                .collect::<Vec<_>>(),
+        ),
+        _ => None,
+    };
+
+    let indices = match primitive.indices {
+        Some(indices) => document
+            .read_integers::<1>(buffers, indices)?
+            .into_iter()
+            .map(|[index]| index)
+            .collect::<Vec<_>>(),
+        None => (0..vertex_count as u32).collect(),
+    };
+    let triangles = indices
+        .chunks_exact(3)
+        .map(|t| TriangleDefinition([t[0], t[1], t[2]]))
+        .collect::<Vec<_>>();
+
+    let normal = |i: usize| {
+        normals
+            .as_ref()
+            .and_then(|n| n.get(i).cloned())
+            .unwrap_or_else(Vector3::y)
+    };
+    let tangent = |i: usize| {
+        tangents
+            .as_ref()
+            .and_then(|t| t.get(i).cloned())
+            .unwrap_or_else(|| Vector4::new(1.0, 0.0, 0.0, 1.0))
+    };
+    let tex_coord = |i: usize| {
+        tex_coords
+            .as_ref()
+            .and_then(|t| t.get(i).cloned())
+            .unwrap_or_default()
+    };
+
+    let vertex_buffer = match skin_data.as_ref() {
+        Some(skin_data) => {
+            let vertices = positions
+                .iter()
+                .enumerate()
+                .map(|(i, position)| {
+                    let (joints, weights) = skin_data.get(i).cloned().unwrap_or_default();
+                    AnimatedVertex {
+                        position: *position,
+                        tex_coord: tex_coord(i),
+                        normal: normal(i),
+                        tangent: tangent(i),
+                        bone_weights: weights,
+                        bone_indices: joints.map(|j| j.min(u8::MAX as u16) as u8),
+                    }
+                })
+                .collect::<Vec<_>>();
+            VertexBuffer::new(vertex_count, vertices)
+        }
+        None => {
+            let vertices = positions
+                .iter()
+                .enumerate()
+                .map(|(i, position)| StaticVertex {
+                    position: *position,
+                    tex_coord: tex_coord(i),
+                    normal: normal(i),
+                    tangent: tangent(i),
+                })
+                .collect::<Vec<_>>();
+            VertexBuffer::new(vertex_count, vertices)
+        }
+    }
+    .map_err(|e| GltfError::from(format!("Invalid vertex buffer: {:?}", e)))?;
+
+    let mut data = SurfaceData::new(vertex_buffer, TriangleBuffer::new(triangles), false);
+
+    let blend_shapes = primitive
+        .targets
+        .iter()
+        .enumerate()
+        .map(|(k, target)| {
+            let collect = |accessor: Option<usize>| -> Result<_, GltfError> {
+                Ok(read_attribute::<3>(document, buffers, accessor, false)?
+                    .map(|offsets| {
+                        offsets
+                            .into_iter()
+                            .enumerate()
+                            .filter(|(_, offset)| offset.iter().any(|c| *c != 0.0))
+                            .map(|(i, offset)| {
+                                (i as u32, utils::vec3_f16_from_f32(Vector3::from(offset)))
+                            })
+                            .collect::<FxHashMap<_, _>>()
+                    })
+                    .unwrap_or_default())
+            };
+
+            let GltfAttributes {
+                positions,
+                normals,
+                tangents,
+                ..
+            } = *target;
+            Ok(InputBlendShapeData {
+                default_weight: default_weights.get(k).cloned().unwrap_or_default() * 100.0,
+                name: format!("Target{}", k),
+                positions: collect(positions)?,
+                normals: collect(normals)?,
+                tangents: collect(tangents)?,
+            })
+        })
+        .collect::<Result<Vec<_>, GltfError>>()?;
+    if !blend_shapes.is_empty() {
+        data.blend_shapes_container = Some(BlendShapesContainer::from_lists(
+            &data.vertex_buffer,
+            &blend_shapes,
+        ));
+    }
+
+    if tangents.is_none() {
+        if let Err(e) = data.calculate_tangents() {
+            Log::warn(format!(
+                "glTF: unable to calculate tangents. Reason: {:?}",
+                e
+            ));
+        }
+    }
+
+    Ok(Some(ConvertedPrimitive {
+        surface: Surface::new(SurfaceSharedData::new(data)),
+        skin_data,
+    }))
+}
+
+fn make_instance_id(name: &str) -> InstanceId {
+    // Use the name to generate instance id, the same approach is used by FBX importer. See its docs for more info.
+    let mut hasher = DefaultHasher::new();
+    name.hash(&mut hasher);
+    let hash = hasher.finish();
+    InstanceId(Uuid::from_u64_pair(hash, hash))
+}
+
+fn node_name(node: &GltfNode, index: usize) -> String {
+    node.name
+        .clone()
+        .unwrap_or_else(|| format!("Node{}", index))
+}
+
+struct SkinnedMesh {
+    node: Handle<Node>,
+    skin: usize,
+    surfaces_skin_data: Vec<Option<SkinData>>,
+}
+
+fn convert_node(
+    document: &GltfDocument,
+    index: usize,
+    buffers: &[Vec<u8>],
+    materials: &[SharedMaterial],
+    graph: &mut Graph,
+    skinned_meshes: &mut Vec<SkinnedMesh>,
+) -> Result<Handle<Node>, GltfError> {
+    let node = &document.nodes[index];
+    let name = node_name(node, index);
+    let (translation, rotation, scale) = node.transform.decomposed();
+
+    let base = BaseBuilder::new()
+        .with_name(name.as_str())
+        .with_instance_id(make_instance_id(&name))
+        .with_local_transform(
+            TransformBuilder::new()
+                .with_local_position(translation)
+                .with_local_rotation(rotation)
+                .with_local_scale(scale)
+                .build(),
+        );
+
+    let mesh = match node.mesh {
+        Some(mesh) => &document.meshes[mesh],
+        None => return Ok(PivotBuilder::new(base).build(graph)),
+    };
+
+    let default_weights = node
+        .weights
+        .as_ref()
+        .or(mesh.weights.as_ref())
+        .cloned()
+        .unwrap_or_default();
+
+    let mut surfaces = Vec::new();
+    let mut surfaces_skin_data = Vec::new();
+    let mut blend_shapes_count = 0;
+    for (primitive_index, primitive) in mesh.primitives.iter().enumerate() {
+        if let Some(converted) = convert_primitive(
+            document,
+            primitive,
+            primitive_index,
+            buffers,
+            &default_weights,
+            node.skin.is_some(),
+        )? {
+            let mut surface = converted.surface;
+            if let Some(material) = primitive.material.map(|index| &materials[index]) {
+                surface.set_material(material.clone());
+            }
+            blend_shapes_count = blend_shapes_count.max(primitive.targets.len());
+            surfaces.push(surface);
+            surfaces_skin_data.push(converted.skin_data);
+        }
+    }
+
+    let blend_shapes = (0..blend_shapes_count)
+        .map(|k| BlendShape {
+            weight: default_weights.get(k).cloned().unwrap_or_default() * 100.0,
+            name: format!("Target{}", k),
+        })
+        .collect();
+
+    let handle = MeshBuilder::new(base)
+        .with_surfaces(surfaces)
+        .with_blend_shapes(blend_shapes)
+        .build(graph);
+
+    if let Some(skin) = node.skin {
+        skinned_meshes.push(SkinnedMesh {
+            node: handle,
+            skin,
+            surfaces_skin_data,
+        });
+    }
+
+    Ok(handle)
+}
+
+fn resolve_skins(
+    document: &GltfDocument,
+    buffers: &[Vec<u8>],
+    node_map: &[Handle<Node>],
+    skinned_meshes: Vec<SkinnedMesh>,
+    graph: &mut Graph,
+) -> Result<(), GltfError> {
+    for skinned_mesh in skinned_meshes {
+        let skin = &document.skins[skinned_mesh.skin];
+
+        let joints = skin
+            .joints
+            .iter()
+            .map(|joint| node_map[*joint])
+            .collect::<Vec<_>>();
+
+        if let Some(inverse_bind_matrices) = skin.inverse_bind_matrices {
+            for (joint, matrix) in joints.iter().zip(document.read_floats::<16>(
+                buffers,
+                inverse_bind_matrices,
+                false,
+            )?) {
+                graph[*joint].inv_bind_pose_transform = Matrix4::from_column_slice(&matrix);
+            }
+        }
+
+        if let Some(mesh) = graph[skinned_mesh.node].cast_mut::<crate::scene::mesh::Mesh>() {
+            for (surface, skin_data) in mesh
+                .surfaces_mut()
+                .iter_mut()
+                .zip(skinned_mesh.surfaces_skin_data)
+            {
+                surface.bones.set_value_silent(joints.clone());
+                if let Some(skin_data) = skin_data {
+                    surface.vertex_weights = skin_data
+                        .into_iter()
+                        .map(|(indices, weights)| {
+                            let mut set = VertexWeightSet::default();
+                            for (index, weight) in indices.iter().zip(weights) {
+                                if weight > 0.0 {
+                                    if let Some(joint) = joints.get(*index as usize) {
+                                        set.push(VertexWeight {
+                                            value: weight,
+                                            effector: (*joint).into(),
+                                        });
+                                    }
+                                }
+                            }
+                            set
+                        })
+                        .collect();
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn key_kind(interpolation: Interpolation) -> CurveKeyKind {
+    match interpolation {
+        Interpolation::Step => CurveKeyKind::Constant,
+        // Tangents of cubic splines are not imported, keys are interpolated linearly.
+        Interpolation::Linear | Interpolation::CubicSpline => CurveKeyKind::Linear,
+    }
+}
+
+/// Cubic spline samplers store triplets of (in-tangent, value, out-tangent), this function extracts values only.
+fn sampler_values<T>(values: Vec<T>, interpolation: Interpolation) -> Vec<T> {
+    if interpolation == Interpolation::CubicSpline {
+        values.into_iter().skip(1).step_by(3).collect()
+    } else {
+        values
+    }
+}
+
+fn fill_curves<const N: usize>(
+    curves: &mut [Curve],
+    times: &[f32],
+    values: &[[f32; N]],
+    kind: CurveKeyKind,
+) {
+    for (time, value) in times.iter().zip(values) {
+        for (curve, component) in curves.iter_mut().zip(value.iter()) {
+            curve.add_key(CurveKey::new(*time, *component, kind.clone()));
+        }
+    }
+}
+
+/// Converts quaternions to Euler angles (the engine interpolates rotations as Euler angles) and unwraps angles, so
+/// there will be no sudden jumps between `-pi` and `pi`.
+fn quaternions_to_euler(rotations: &[[f32; 4]]) -> Vec<[f32; 3]> {
+    let mut result: Vec<[f32; 3]> = Vec::with_capacity(rotations.len());
+    for rotation in rotations {
+        let (x, y, z) = UnitQuaternion::from_quaternion(Quaternion::new(
+            rotation[3],
+            rotation[0],
+            rotation[1],
+            rotation[2],
+        ))
+        .euler_angles();
+        let mut angles = [x, y, z];
+        if let Some(prev) = result.last() {
+            for (angle, prev) in angles.iter_mut().zip(prev) {
+                while *angle - prev > std::f32::consts::PI {
+                    *angle -= std::f32::consts::TAU;
+                }
+                while *angle - prev < -std::f32::consts::PI {
+                    *angle += std::f32::consts::TAU;
+                }
+            }
+        }
+        result.push(angles);
+    }
+    result
+}
+
+fn convert_animation(
+    document: &GltfDocument,
+    animation: &GltfAnimation,
+    index: usize,
+    buffers: &[Vec<u8>],
+    node_map: &[Handle<Node>],
+) -> Result<Animation, GltfError> {
+    let mut result = Animation::default();
+    result.set_name(
+        animation
+            .name
+            .clone()
+            .unwrap_or_else(|| format!("Animation{}", index)),
+    );
+
+    for (channel_index, channel) in animation.channels.iter().enumerate() {
+        let (node, path) = match (channel.node, channel.path) {
+            (Some(node), Some(path)) => (node, path),
+            _ => continue,
+        };
+        let target = node_map[node];
+        let sampler = &animation.samplers[channel.sampler];
+        let interpolation = sampler.interpolation;
+        if interpolation == Interpolation::CubicSpline {
+            Log::warn(format!(
+                "glTF: channel {} of animation {} uses cubic spline interpolation, which is not \
+                supported. Tangents are ignored and keys are interpolated linearly!",
+                channel_index,
+                result.name()
+            ));
+        }
+        let kind = key_kind(interpolation);
+
+        let times = document
+            .read_floats::<1>(buffers, sampler.input, false)?
+            .into_iter()
+            .map(|[time]| time)
+            .collect::<Vec<_>>();
+
+        match path {
+            AnimationPath::Translation => {
+                let values = sampler_values(
+                    document.read_floats::<3>(buffers, sampler.output, false)?,
+                    interpolation,
+                );
+                let mut track = Track::new_position();
+                fill_curves(
+                    track.data_container_mut().curves_mut(),
+                    &times,
+                    &values,
+                    kind,
+                );
+                track.set_target(target);
+                result.add_track(track);
+            }
+            AnimationPath::Scale => {
+                let values = sampler_values(
+                    document.read_floats::<3>(buffers, sampler.output, false)?,
+                    interpolation,
+                );
+                let mut track = Track::new_scale();
+                fill_curves(
+                    track.data_container_mut().curves_mut(),
+                    &times,
+                    &values,
+                    kind,
+                );
+                track.set_target(target);
+                result.add_track(track);
+            }
+            // Integer rotations and weights are always normalized.
+            AnimationPath::Rotation => {
+                let values = sampler_values(
+                    document.read_floats::<4>(buffers, sampler.output, true)?,
+                    interpolation,
+                );
+                let mut track = Track::new_rotation();
+                fill_curves(
+                    track.data_container_mut().curves_mut(),
+                    &times,
+                    &quaternions_to_euler(&values),
+                    kind,
+                );
+                track.set_target(target);
+                result.add_track(track);
+            }
+            AnimationPath::Weights => {
+                let weights = sampler_values(
+                    document
+                        .read_floats::<1>(buffers, sampler.output, true)?
+                        .into_iter()
+                        .map(|[w]| w)
+                        .collect(),
+                    interpolation,
+                );
+                let targets_count = if times.is_empty() {
+                    0
+                } else {
+                    weights.len() / times.len()
+                };
+                for k in 0..targets_count {
+                    let mut track = Track::new(
+                        TrackDataContainer::new(TrackValueKind::Real),
+                        ValueBinding::Property {
+                            name: format!("blend_shapes[{}].weight", k),
+                            value_type: ValueType::F32,
+                        },
+                    );
+                    let values = weights
+                        .iter()
+                        .skip(k)
+                        .step_by(targets_count)
+                        .map(|w| [*w * 100.0])
+                        .collect::<Vec<_>>();
+                    fill_curves(
+                        track.data_container_mut().curves_mut(),
+                        &times,
+                        &values,
+                        kind.clone(),
+                    );
+                    track.set_target(target);
+                    result.add_track(track);
+                }
+            }
+        }
+    }
+
+    result.fit_length_to_content();
+
+    Ok(result)
+}
+
+async fn convert(
+    document: GltfDocument,
+    blob: Option<Vec<u8>>,
+    resource_manager: ResourceManager,
+    io: &dyn ResourceIo,
+    scene: &mut Scene,
+    model_path: &Path,
+    model_import_options: &ModelImportOptions,
+) -> Result<(), GltfError> {
+    let model_directory = model_path.parent().unwrap_or_else(|| Path::new(""));
+
+    let buffers = load_buffers(&document, blob, io, model_directory).await?;
+
+    let mut textures = TextureContext {
+        resource_manager,
+        io,
+        document: &document,
+        buffers: &buffers,
+        model_directory,
+        model_import_options,
+        cache: Default::default(),
+    };
+    let mut materials = Vec::new();
+    for material in document.materials.iter() {
+        materials.push(convert_material(material, &mut textures).await);
+    }
+
+    let mut node_map = Vec::new();
+    let mut skinned_meshes = Vec::new();
+    for index in 0..document.nodes.len() {
+        node_map.push(convert_node(
+            &document,
+            index,
+            &buffers,
+            &materials,
+            &mut scene.graph,
+            &mut skinned_meshes,
+        )?);
+    }
+
+    // Link according to hierarchy.
+    for (index, node) in document.nodes.iter().enumerate() {
+        for child in node.children.iter() {
+            scene.graph.link_nodes(node_map[*child], node_map[index]);
+        }
+    }
+
+    resolve_skins(
+        &document,
+        &buffers,
+        &node_map,
+        skinned_meshes,
+        &mut scene.graph,
+    )?;
+
+    let mut animations_container = AnimationContainer::new();
+    for (index, animation) in document.animations.iter().enumerate() {
+        animations_container.add(convert_animation(
+            &document, animation, index, &buffers, &node_map,
+        )?);
+    }
+
+    // Do not create animation player if there's no animation content.
+    if animations_container.alive_count() > 0 {
+        AnimationPlayerBuilder::new(BaseBuilder::new().with_name("AnimationPlayer"))
+            .with_animations(animations_container)
+            .build(&mut scene.graph);
+    }
+
+    scene.graph.update_hierarchical_data();
+
+    Ok(())
+}
+
+/// Tries to load and convert glTF (or GLB) from given path.
+///
+/// Normally you should never use this method, use resource manager to load models.
+pub async fn load_to_scene<P: AsRef<Path>>(
+    scene: &mut Scene,
+    resource_manager: ResourceManager,
+    io: &dyn ResourceIo,
+    path: P,
+    model_import_options: &ModelImportOptions,
+) -> Result<(), GltfError> {
+    let start_time = Instant::now();
+
+    Log::info(format!("Trying to load {:?}", path.as_ref()));
+
+    let data = io.load_file(path.as_ref()).await?;
+    let (document, blob) = GltfDocument::parse(&data)?;
+
+    convert(
+        document,
+        blob,
+        resource_manager,
+        io,
+        scene,
+        path.as_ref(),
+        model_import_options,
+    )
+    .await?;
+
+    Log::info(format!(
+        "glTF {:?} loaded in {} ms",
+        path.as_ref(),
+        start_time.elapsed().as_millis()
+    ));
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        asset::{io::FsResourceIo, manager::ResourceManager},
+        core::{algebra::Vector3, futures::executor::block_on, sstorage::ImmutableString},
+        material::PropertyValue,
+        resource::gltf::{decode_data_uri, decode_uri, load_to_scene, quaternions_to_euler},
+        scene::{animation::AnimationPlayer, mesh::Mesh, Scene},
+    };
+
+    #[test]
+    fn test_decode_uri() {
+        assert_eq!(decode_uri("foo%20bar.png"), "foo bar.png");
+        assert_eq!(decode_uri("plain.png"), "plain.png");
+        assert_eq!(decode_uri("broken%2"), "broken%2");
+        assert_eq!(decode_uri("%aé.png"), "%aé.png");
+        assert_eq!(decode_uri("caf%C3%A9.png"), "café.png");
+    }
+
+    #[test]
+    fn test_import() {
+        let mut scene = Scene::new();
+        block_on(load_to_scene(
+            &mut scene,
+            ResourceManager::new(),
+            &FsResourceIo,
+            "src/resource/gltf/test_data/triangle.gltf",
+            &Default::default(),
+        ))
+        .unwrap();
+
+        let (_, triangle) = scene.graph.find_by_name_from_root("Triangle").unwrap();
+        let mesh = triangle.query_component_ref::<Mesh>().unwrap();
+        assert_eq!(mesh.surfaces().len(), 1);
+        let surface = &mesh.surfaces()[0];
+        assert_eq!(surface.data().lock().geometry_buffer.len(), 1);
+
+        // Emissive factor must be applied even if there's no emissive texture.
+        let material = surface.material().lock();
+        assert!(matches!(
+            material.property_ref(&ImmutableString::new("emissionStrength")),
+            Some(PropertyValue::Vector3(strength)) if *strength == Vector3::new(1.0, 0.5, 0.0)
+        ));
+        assert!(matches!(
+            material.property_ref(&ImmutableString::new("emissionTexture")),
+            Some(PropertyValue::Sampler { value: Some(_), .. })
+        ));
+        drop(material);
+
+        // Values of cubic spline keys are imported without tangents.
+        let (_, player) = scene
+            .graph
+            .find_by_name_from_root("AnimationPlayer")
+            .unwrap();
+        let player = player.query_component_ref::<AnimationPlayer>().unwrap();
+        let animation = player.animations().iter().next().unwrap();
+        assert_eq!(animation.name(), "Move");
+        let curves = animation.tracks()[0].data_container().curves_ref();
+        assert_eq!(curves.len(), 3);
+        for (curve, expected) in curves.iter().zip([1.0, 2.0, 3.0]) {
+            assert_eq!(curve.keys().len(), 2);
+            assert_eq!(curve.value_at(0.0), 0.0);
+            assert_eq!(curve.value_at(1.0), expected);
+        }
+    }
+
+    #[test]
+    fn test_decode_data_uri() {
+        assert!(decode_data_uri("textures/foo.png").is_none());
+        assert_eq!(
+            decode_data_uri("data:application/octet-stream;base64,AQID")
+                .unwrap()
+                .unwrap(),
+            vec![1, 2, 3]
+        );
+    }
+
+    #[test]
+    fn test_quaternions_unwrapping() {
+        // 179 and -179 degrees around Z must not produce a jump.
+        let a = (179.0f32.to_radians() * 0.5).sin_cos();
+        let b = ((-179.0f32).to_radians() * 0.5).sin_cos();
+        let angles = quaternions_to_euler(&[
+            [0.0, 0.0, 0.0, 1.0],
+            [0.0, 0.0, a.0, a.1],
+            [0.0, 0.0, b.0, b.1],
+        ]);
+        assert!((angles[2][2] - angles[1][2] - 2.0f32.to_radians()).abs() < 0.001);
+    }
+}
@@ -4,5 +4,8 @@
 
 pub mod curve;
 pub mod fbx;
+pub mod gltf;
 pub mod model;
+pub mod sound_bank;
+pub mod style;
 pub mod texture;
@@ -29,7 +29,7 @@ pub struct ModelLoader {
 
 impl ResourceLoader for ModelLoader {
     fn extensions(&self) -> &[&str] {
-        &["rgs", "fbx"]
+        &["rgs", "fbx", "gltf", "glb"]
     }
 
     fn data_type_uuid(&self) -> Uuid {
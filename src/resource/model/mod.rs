@@ -15,8 +15,8 @@
 //!
 //! # Supported formats
 //!
-//! Currently only FBX (common format in game industry for storing complex 3d models)
-//! and RGS (native Fyroxed format) formats are supported.
+//! Currently FBX (common format in game industry for storing complex 3d models), glTF 2.0
+//! (both `.gltf` and `.glb` flavours) and RGS (native Fyroxed format) formats are supported.
 
 use crate::{
     animation::Animation,
@@ -26,6 +26,7 @@ use crate::{
     },
     core::{
         algebra::{UnitQuaternion, Vector3},
+        curve::{Curve, CurveKey, CurveKeyKind},
         log::{Log, MessageKind},
         pool::Handle,
         reflect::prelude::*,
@@ -34,11 +35,17 @@ use crate::{
         TypeUuidProvider,
     },
     engine::SerializationContext,
-    resource::fbx::{self, error::FbxError},
+    resource::{
+        fbx::{self, error::FbxError},
+        gltf::{self, error::GltfError},
+    },
     scene::{
         animation::AnimationPlayer,
+        base::BaseBuilder,
         graph::{map::NodeHandleMap, Graph},
         node::Node,
+        pivot::PivotBuilder,
+        transform::{Transform, TransformBuilder},
         Scene, SceneLoader,
     },
 };
@@ -404,20 +411,206 @@ impl MaterialSearchOptions {
 ///
 /// ```text
 /// (
-///     material_search_options: RecursiveUp
+///     material_search_options: RecursiveUp,
+///     scale: 1.0,
+///     up_axis: Y,
+///     import_transform_node: false,
+///     animation_sampling_rate: None,
 /// )
 /// ```
 ///
 /// Check documentation of the field of the structure for more info about each parameter.
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Default, Reflect, Eq)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Reflect)]
 pub struct ModelImportOptions {
     /// See [`MaterialSearchOptions`] docs for more info.
     #[serde(default)]
     pub material_search_options: MaterialSearchOptions,
+    /// Uniform scale that will be applied to the imported model. It is useful to fix models that were
+    /// made in different units (centimeters, for example).
+    #[serde(default = "default_scale")]
+    pub scale: f32,
+    /// See [`ModelUpAxis`] docs for more info.
+    #[serde(default)]
+    pub up_axis: ModelUpAxis,
+    /// Defines how [`Self::scale`] and [`Self::up_axis`] are applied. By default, they are baked into the
+    /// transforms of the top-level nodes of the model, which keeps the hierarchy intact, but animated
+    /// positions of the top-level nodes will not be converted. When enabled, the top-level nodes are
+    /// attached to a separate `ImportTransform` node that holds the conversion, which works with any
+    /// animations, but adds a node to the hierarchy of the model.
+    #[serde(default)]
+    pub import_transform_node: bool,
+    /// Sampling rate (in samples per second) that will be used to resample imported animations. `None`
+    /// means that the keys of the source animations will be used as is.
+    #[serde(default)]
+    pub animation_sampling_rate: Option<f32>,
+}
+
+fn default_scale() -> f32 {
+    1.0
+}
+
+impl Default for ModelImportOptions {
+    fn default() -> Self {
+        Self {
+            material_search_options: Default::default(),
+            scale: default_scale(),
+            up_axis: Default::default(),
+            import_transform_node: false,
+            animation_sampling_rate: None,
+        }
+    }
 }
 
 impl ImportOptions for ModelImportOptions {}
 
+/// Up axis of a source model. The engine uses Y axis as up axis, models made with Z axis as up axis
+/// will be rotated to match the engine's coordinate system.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    Default,
+    Visit,
+    PartialEq,
+    Eq,
+    Deserialize,
+    Serialize,
+    Reflect,
+    AsRefStr,
+    EnumString,
+    EnumVariantNames,
+)]
+pub enum ModelUpAxis {
+    /// Y axis is up axis, no conversion is needed.
+    #[default]
+    Y,
+    /// Z axis is up axis, the model will be rotated around X axis by -90 degrees.
+    Z,
+}
+
+/// Applies the import options that are common for all external model formats to a loaded scene.
+fn apply_import_options(scene: &mut Scene, options: &ModelImportOptions) {
+    let rotation = match options.up_axis {
+        ModelUpAxis::Y => UnitQuaternion::identity(),
+        ModelUpAxis::Z => {
+            UnitQuaternion::from_axis_angle(&Vector3::x_axis(), -std::f32::consts::FRAC_PI_2)
+        }
+    };
+
+    if options.scale != 1.0 || options.up_axis != ModelUpAxis::Y {
+        // The conversion is never applied to the root, it would conflict with the transform of the
+        // instance root.
+        let root = scene.graph.get_root();
+        let children = scene.graph[root].children().to_vec();
+        if !options.import_transform_node {
+            for child in children {
+                bake_import_transform(
+                    scene.graph[child].local_transform_mut(),
+                    options.scale,
+                    rotation,
+                );
+            }
+        } else {
+            let pivot = PivotBuilder::new(
+                BaseBuilder::new()
+                    .with_name("ImportTransform")
+                    .with_local_transform(
+                        TransformBuilder::new()
+                            .with_local_scale(Vector3::repeat(options.scale))
+                            .with_local_rotation(rotation)
+                            .build(),
+                    ),
+            )
+            .build(&mut scene.graph);
+            scene.graph.link_nodes(pivot, root);
+            for child in children {
+                scene.graph.link_nodes(child, pivot);
+            }
+        }
+        scene.graph.update_hierarchical_data();
+    }
+
+    if let Some(sampling_rate) = options.animation_sampling_rate {
+        if sampling_rate > 0.0 {
+            for node in scene.graph.linear_iter_mut() {
+                if let Some(animation_player) = node.query_component_mut::<AnimationPlayer>() {
+                    for animation in animation_player
+                        .animations_mut()
+                        .get_value_mut_silent()
+                        .iter_mut()
+                    {
+                        for track in animation.tracks_mut() {
+                            for curve in track.data_container_mut().curves_mut() {
+                                resample_curve(curve, sampling_rate);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Modifies the transform so its matrix is equal to `rotation * scale * transform`. The rotation is put into
+/// the pre-rotation, so animated rotations of the node are converted too.
+fn bake_import_transform(transform: &mut Transform, scale: f32, rotation: UnitQuaternion<f32>) {
+    let position = **transform.position();
+    let pre_rotation = **transform.pre_rotation();
+    let local_scale = **transform.scale();
+    let rotation_offset = **transform.rotation_offset();
+    let rotation_pivot = **transform.rotation_pivot();
+    let scaling_offset = **transform.scaling_offset();
+    let scaling_pivot = **transform.scaling_pivot();
+
+    // Pivots are kept as is, so offsets absorb the difference.
+    let pivot_position = position + rotation_offset + rotation_pivot;
+    transform
+        .set_position(
+            rotation.transform_vector(&pivot_position).scale(scale)
+                - rotation_offset
+                - rotation_pivot,
+        )
+        .set_pre_rotation(rotation * pre_rotation)
+        .set_scale(local_scale.scale(scale))
+        .set_scaling_offset(
+            scaling_offset.scale(scale) + (scaling_pivot - rotation_pivot).scale(scale - 1.0),
+        );
+}
+
+/// Replaces keys of a curve with linear keys, uniformly distributed with the given sampling rate.
+fn resample_curve(curve: &mut Curve, sampling_rate: f32) {
+    let (Some(first), Some(last)) = (curve.keys().first(), curve.keys().last()) else {
+        return;
+    };
+    let (begin, end) = (first.location(), last.location());
+
+    let step = 1.0 / sampling_rate;
+    let sample_count = ((end - begin) / step).ceil() as usize;
+    let mut keys = Vec::with_capacity(sample_count + 1);
+    for i in 0..sample_count {
+        let location = begin + i as f32 * step;
+        // Samples that are too close to the end would duplicate the last key.
+        if end - location <= step * 0.001 {
+            break;
+        }
+        keys.push(CurveKey::new(
+            location,
+            curve.value_at(location),
+            CurveKeyKind::Linear,
+        ));
+    }
+    keys.push(CurveKey::new(
+        end,
+        curve.value_at(end),
+        CurveKeyKind::Linear,
+    ));
+
+    curve.clear();
+    for key in keys {
+        curve.add_key(key);
+    }
+}
+
 /// All possible errors that may occur while trying to load model from some
 /// data source.
 #[derive(Debug)]
@@ -428,6 +621,8 @@ pub enum ModelLoadError {
     NotSupported(String),
     /// An error occurred while loading FBX file.
     Fbx(FbxError),
+    /// An error occurred while loading glTF file.
+    Gltf(GltfError),
 }
 
 impl Display for ModelLoadError {
@@ -440,6 +635,7 @@ impl Display for ModelLoadError {
                 write!(f, "Model format is not supported: {v}")
             }
             ModelLoadError::Fbx(v) => v.fmt(f),
+            ModelLoadError::Gltf(v) => v.fmt(f),
         }
     }
 }
@@ -450,6 +646,12 @@ impl From<FbxError> for ModelLoadError {
     }
 }
 
+impl From<GltfError> for ModelLoadError {
+    fn from(gltf: GltfError) -> Self {
+        ModelLoadError::Gltf(gltf)
+    }
+}
+
 impl From<VisitError> for ModelLoadError {
     fn from(e: VisitError) -> Self {
         ModelLoadError::Visit(e)
@@ -486,10 +688,30 @@ impl Model {
                     &model_import_options,
                 )
                 .await?;
+                apply_import_options(&mut scene, &model_import_options);
                 // Set NodeMapping::UseNames as mapping here because FBX does not have
                 // any persistent unique ids, and we have to use names.
                 (scene, NodeMapping::UseNames)
             }
+            "gltf" | "glb" => {
+                let mut scene = Scene::new();
+                if let Some(filename) = path.as_ref().file_name() {
+                    let root = scene.graph.get_root();
+                    scene.graph[root].set_name(filename.to_string_lossy());
+                }
+                gltf::load_to_scene(
+                    &mut scene,
+                    resource_manager,
+                    io,
+                    path.as_ref(),
+                    &model_import_options,
+                )
+                .await?;
+                apply_import_options(&mut scene, &model_import_options);
+                // Node indices of glTF are not stable across re-exports, so names are used the
+                // same way as for FBX.
+                (scene, NodeMapping::UseNames)
+            }
             // Scene can be used directly as model resource. Such scenes can be created in
             // Fyroxed.
             "rgs" => (
@@ -538,3 +760,120 @@ impl Model {
         &mut self.scene
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::{
+            algebra::{Matrix4, UnitQuaternion, Vector3},
+            curve::{Curve, CurveKey, CurveKeyKind},
+        },
+        resource::model::{apply_import_options, resample_curve, ModelImportOptions, ModelUpAxis},
+        scene::{base::BaseBuilder, pivot::PivotBuilder, transform::TransformBuilder, Scene},
+    };
+
+    #[test]
+    fn test_resample_curve() {
+        let mut curve = Curve::from(vec![
+            CurveKey::new(0.0, 0.0, CurveKeyKind::Linear),
+            CurveKey::new(1.0, 10.0, CurveKeyKind::Linear),
+        ]);
+
+        resample_curve(&mut curve, 4.0);
+
+        assert_eq!(curve.keys().len(), 5);
+        assert_eq!(curve.keys()[2].location(), 0.5);
+        assert_eq!(curve.keys()[2].value, 5.0);
+        assert_eq!(curve.keys()[4].location(), 1.0);
+
+        // Accumulated rounding errors must not produce a duplicated end key.
+        for sampling_rate in [3.0, 10.0, 30.0, 60.0] {
+            let mut curve = Curve::from(vec![
+                CurveKey::new(0.1, 0.0, CurveKeyKind::Linear),
+                CurveKey::new(1.1, 10.0, CurveKeyKind::Linear),
+            ]);
+            resample_curve(&mut curve, sampling_rate);
+            let keys = curve.keys();
+            assert_eq!(keys.last().unwrap().location(), 1.1);
+            assert!(keys
+                .windows(2)
+                .all(|pair| pair[1].location() - pair[0].location() > 0.5 / sampling_rate));
+        }
+    }
+
+    #[test]
+    fn test_apply_import_options() {
+        let options = ModelImportOptions {
+            scale: 0.01,
+            up_axis: ModelUpAxis::Z,
+            import_transform_node: true,
+            ..Default::default()
+        };
+
+        let mut scene = Scene::new();
+        let node = PivotBuilder::new(BaseBuilder::new()).build(&mut scene.graph);
+
+        apply_import_options(&mut scene, &options);
+
+        let root = scene.graph.get_root();
+        let pivot = scene.graph[node].parent();
+        assert_eq!(scene.graph[pivot].parent(), root);
+        assert_eq!(scene.graph[pivot].name(), "ImportTransform");
+        assert_eq!(
+            **scene.graph[pivot].local_transform().scale(),
+            Vector3::repeat(0.01)
+        );
+        // Z axis of the model becomes Y axis of the engine.
+        let z = scene.graph[node].look_vector().normalize();
+        assert!((z - Vector3::y()).norm() < 0.001);
+    }
+
+    #[test]
+    fn test_apply_import_options_baked() {
+        let options = ModelImportOptions {
+            scale: 0.5,
+            up_axis: ModelUpAxis::Z,
+            ..Default::default()
+        };
+        let conversion =
+            UnitQuaternion::from_axis_angle(&Vector3::x_axis(), -std::f32::consts::FRAC_PI_2)
+                .to_homogeneous()
+                * Matrix4::new_scaling(0.5);
+
+        let mut scene = Scene::new();
+        let node = PivotBuilder::new(
+            BaseBuilder::new()
+                .with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(Vector3::new(1.0, 2.0, 3.0))
+                        .with_local_rotation(UnitQuaternion::from_euler_angles(0.3, 0.2, 0.1))
+                        .with_local_scale(Vector3::new(1.0, 2.0, 3.0))
+                        .with_pre_rotation(UnitQuaternion::from_euler_angles(0.1, 0.0, 0.4))
+                        .with_post_rotation(UnitQuaternion::from_euler_angles(0.0, 0.5, 0.0))
+                        .with_rotation_offset(Vector3::new(0.5, 0.0, 1.0))
+                        .with_rotation_pivot(Vector3::new(0.0, 1.0, 2.0))
+                        .with_scaling_offset(Vector3::new(1.0, 0.5, 0.0))
+                        .with_scaling_pivot(Vector3::new(2.0, 0.0, 1.0))
+                        .build(),
+                )
+                .with_children(&[PivotBuilder::new(
+                    BaseBuilder::new().with_local_transform(
+                        TransformBuilder::new()
+                            .with_local_position(Vector3::new(0.0, 1.0, 0.0))
+                            .build(),
+                    ),
+                )
+                .build(&mut scene.graph)]),
+        )
+        .build(&mut scene.graph);
+        let child = scene.graph[node].children()[0];
+        scene.graph.update_hierarchical_data();
+        let expected = conversion * scene.graph[child].global_transform();
+
+        apply_import_options(&mut scene, &options);
+
+        // The hierarchy is intact and the conversion is baked into the top-level node.
+        assert_eq!(scene.graph[node].parent(), scene.graph.get_root());
+        assert!((scene.graph[child].global_transform() - expected).norm() < 0.0001);
+    }
+}
@@ -0,0 +1,63 @@
+//! Sound bank loader.
+
+use std::sync::Arc;
+
+use fyrox_resource::io::ResourceIo;
+
+use crate::{
+    asset::{
+        event::ResourceEventBroadcaster,
+        loader::{BoxedLoaderFuture, ResourceLoader},
+        manager::ResourceManager,
+        untyped::UntypedResource,
+    },
+    core::{log::Log, uuid::Uuid, TypeUuidProvider},
+    resource::sound_bank::SoundBank,
+};
+
+/// Default implementation for sound bank loading.
+pub struct SoundBankLoader {
+    /// Resource manager to load sound clips of the sound bank.
+    pub resource_manager: ResourceManager,
+}
+
+impl ResourceLoader for SoundBankLoader {
+    fn extensions(&self) -> &[&str] {
+        &["soundbank"]
+    }
+
+    fn data_type_uuid(&self) -> Uuid {
+        SoundBank::type_uuid()
+    }
+
+    fn load(
+        &self,
+        sound_bank: UntypedResource,
+        event_broadcaster: ResourceEventBroadcaster,
+        reload: bool,
+        io: Arc<dyn ResourceIo>,
+    ) -> BoxedLoaderFuture {
+        let resource_manager = self.resource_manager.clone();
+
+        Box::pin(async move {
+            let path = sound_bank.path();
+            match SoundBank::from_file(&path, io.as_ref(), resource_manager).await {
+                Ok(sound_bank_state) => {
+                    Log::info(format!("Sound bank {:?} is loaded!", path));
+
+                    sound_bank.commit_ok(sound_bank_state);
+
+                    event_broadcaster.broadcast_loaded_or_reloaded(sound_bank, reload);
+                }
+                Err(error) => {
+                    Log::err(format!(
+                        "Unable to load sound bank from {:?}! Reason: {:?}",
+                        path, error
+                    ));
+
+                    sound_bank.commit_error(path, error);
+                }
+            }
+        })
+    }
+}
@@ -0,0 +1,392 @@
+//! Sound bank is a set of named sound events. See [`SoundBank`] docs for more info.
+
+use crate::{
+    asset::{manager::ResourceManager, Resource, ResourceData},
+    core::{
+        algebra::Vector3,
+        curve::Curve,
+        io::FileLoadError,
+        numeric_range::RangeExt,
+        pool::Handle,
+        rand::{seq::IteratorRandom, thread_rng},
+        reflect::prelude::*,
+        uuid::{uuid, Uuid},
+        visitor::prelude::*,
+        TypeUuidProvider,
+    },
+    scene::{
+        base::BaseBuilder,
+        graph::Graph,
+        node::Node,
+        sound::{AudioBusGraph, SoundBufferResource, SoundBuilder, Status},
+        transform::TransformBuilder,
+    },
+};
+use fyrox_resource::io::ResourceIo;
+use std::{
+    any::Any,
+    borrow::Cow,
+    fmt::{Display, Formatter},
+    ops::Range,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+pub mod loader;
+
+/// An error that may occur during sound bank loading.
+#[derive(Debug)]
+pub enum SoundBankError {
+    /// An i/o error has occurred.
+    Io(FileLoadError),
+
+    /// An error that may occur due to version incompatibilities.
+    Visit(VisitError),
+}
+
+impl Display for SoundBankError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SoundBankError::Io(v) => {
+                write!(f, "A file load error has occurred {v:?}")
+            }
+            SoundBankError::Visit(v) => {
+                write!(
+                    f,
+                    "An error that may occur due to version incompatibilities. {v:?}"
+                )
+            }
+        }
+    }
+}
+
+impl From<FileLoadError> for SoundBankError {
+    fn from(e: FileLoadError) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<VisitError> for SoundBankError {
+    fn from(e: VisitError) -> Self {
+        Self::Visit(e)
+    }
+}
+
+/// Sound event is a named description of a sound, that is used to create a new sound every time the
+/// event is played. Every time the event is played, a random clip from the pool of clips is picked
+/// and gain and pitch are picked randomly from their ranges, this way repetitive sounds (footsteps,
+/// gunshots, impacts, etc.) sound less monotonous.
+#[derive(Debug, Clone, PartialEq, Visit, Reflect)]
+pub struct SoundEvent {
+    /// Name of the event, it is used to play the event. Default is empty string.
+    #[reflect(description = "Name of the event, it is used to play the event.")]
+    pub name: String,
+
+    /// A pool of sound clips, a random clip is picked every time the event is played.
+    #[reflect(
+        description = "A pool of sound clips, a random clip is picked every time the event is played."
+    )]
+    pub clips: Vec<Option<SoundBufferResource>>,
+
+    /// A range of gain (volume) of the sound. Default is `1.0..1.0`.
+    #[reflect(description = "A range of gain (volume) of the sound.")]
+    pub gain: Range<f32>,
+
+    /// A range of pitch of the sound. Default is `1.0..1.0`.
+    #[reflect(description = "A range of pitch of the sound.")]
+    pub pitch: Range<f32>,
+
+    /// A name of an audio bus to which the sound will be attached. Default is [`AudioBusGraph::PRIMARY_BUS`].
+    #[reflect(description = "A name of an audio bus to which the sound will be attached.")]
+    pub audio_bus: String,
+
+    /// Radius of the sound, see [`crate::scene::sound::Sound::set_radius`]. Default is `10.0`.
+    #[reflect(description = "Radius of the sound.", min_value = 0.0, step = 0.05)]
+    pub radius: f32,
+
+    /// Max distance of the sound, see [`crate::scene::sound::Sound::set_max_distance`]. Default is
+    /// [`f32::MAX`].
+    #[reflect(
+        description = "Max distance of the sound.",
+        min_value = 0.0,
+        step = 0.05
+    )]
+    pub max_distance: f32,
+
+    /// Rolloff factor of the sound, see [`crate::scene::sound::Sound::set_rolloff_factor`]. Default
+    /// is `1.0`.
+    #[reflect(
+        description = "Rolloff factor of the sound.",
+        min_value = 0.0,
+        step = 0.05
+    )]
+    pub rolloff_factor: f32,
+
+    /// Spatial blend factor of the sound, see [`crate::scene::sound::Sound::set_spatial_blend`].
+    /// Default is `1.0`.
+    #[reflect(
+        description = "Spatial blend factor of the sound, 0.0 - 2D, 1.0 - 3D.",
+        min_value = 0.0,
+        max_value = 1.0,
+        step = 0.05
+    )]
+    pub spatial_blend: f32,
+
+    /// Custom distance attenuation curve, see [`crate::scene::sound::Sound::set_distance_curve`].
+    /// Default is `None`.
+    #[reflect(
+        description = "Custom distance attenuation curve, maps distance to the listener to gain."
+    )]
+    pub distance_curve: Option<Curve>,
+}
+
+impl Default for SoundEvent {
+    fn default() -> Self {
+        Self {
+            name: Default::default(),
+            clips: Default::default(),
+            gain: 1.0..1.0,
+            pitch: 1.0..1.0,
+            audio_bus: AudioBusGraph::PRIMARY_BUS.to_string(),
+            radius: 10.0,
+            max_distance: f32::MAX,
+            rolloff_factor: 1.0,
+            spatial_blend: 1.0,
+            distance_curve: None,
+        }
+    }
+}
+
+impl SoundEvent {
+    /// Creates a new builder of a "play-once" sound with a random clip and randomized parameters of
+    /// the event. Returns `None` if the event has no clips.
+    pub fn make_sound(&self, base_builder: BaseBuilder) -> Option<SoundBuilder> {
+        let mut rng = thread_rng();
+        let clip = self.clips.iter().flatten().choose(&mut rng)?.clone();
+        Some(
+            SoundBuilder::new(base_builder)
+                .with_buffer(Some(clip))
+                .with_play_once(true)
+                .with_status(Status::Playing)
+                .with_gain(self.gain.random(&mut rng))
+                .with_pitch(self.pitch.random(&mut rng) as f64)
+                .with_audio_bus(self.audio_bus.clone())
+                .with_radius(self.radius)
+                .with_max_distance(self.max_distance)
+                .with_rolloff_factor(self.rolloff_factor)
+                .with_spatial_blend_factor(self.spatial_blend)
+                .with_distance_curve(self.distance_curve.clone()),
+        )
+    }
+}
+
+/// Sound bank is a set of named sound events, it allows designers to define sounds of a game in a
+/// single asset and game code to play the sounds by their names.
+///
+/// # Example
+///
+/// ```rust
+/// use fyrox::{
+///     core::{algebra::Vector3, pool::Handle},
+///     resource::sound_bank::SoundBankResource,
+///     scene::{node::Node, Scene},
+/// };
+///
+/// fn play_footstep(bank: &SoundBankResource, scene: &mut Scene, position: Vector3<f32>) -> Handle<Node> {
+///     bank.data_ref()
+///         .play("Footstep", position, &mut scene.graph)
+///         .unwrap_or_default()
+/// }
+/// ```
+#[derive(Debug, Default, Clone, Visit, Reflect)]
+pub struct SoundBank {
+    #[reflect(hidden)]
+    pub(crate) path: PathBuf,
+    /// A set of events of the bank.
+    #[reflect(description = "A set of events of the bank.")]
+    pub events: Vec<SoundEvent>,
+}
+
+impl ResourceData for SoundBank {
+    fn path(&self) -> Cow<'_, Path> {
+        Cow::Borrowed(&self.path)
+    }
+
+    fn set_path(&mut self, path: PathBuf) {
+        self.path = path;
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn type_uuid(&self) -> Uuid {
+        <Self as TypeUuidProvider>::type_uuid()
+    }
+
+    fn is_procedural(&self) -> bool {
+        false
+    }
+}
+
+impl TypeUuidProvider for SoundBank {
+    fn type_uuid() -> Uuid {
+        uuid!("c9aa9ed7-d082-4fb3-b285-2aed45f35c8e")
+    }
+}
+
+impl SoundBank {
+    /// Load a sound bank from the specific file path. Resource manager is used to request sound clips
+    /// of the events.
+    pub async fn from_file(
+        path: &Path,
+        io: &dyn ResourceIo,
+        resource_manager: ResourceManager,
+    ) -> Result<Self, SoundBankError> {
+        let bytes = io.load_file(path).await?;
+        let mut visitor = Visitor::load_from_memory(&bytes)?;
+        visitor.blackboard.register(Arc::new(resource_manager));
+        let mut events = Vec::<SoundEvent>::new();
+        events.visit("Events", &mut visitor)?;
+        Ok(Self {
+            events,
+            path: path.to_path_buf(),
+        })
+    }
+
+    /// Saves the sound bank to the specified file path.
+    pub fn save(&mut self, path: &Path) -> VisitResult {
+        let mut visitor = Visitor::new();
+        self.events.visit("Events", &mut visitor)?;
+        visitor.save_binary(path)
+    }
+
+    /// Searches for an event with the given name.
+    pub fn event(&self, name: &str) -> Option<&SoundEvent> {
+        self.events.iter().find(|event| event.name == name)
+    }
+
+    /// Plays an event with the given name at the given position. It creates a new "play-once" sound
+    /// node in the graph, the node will be removed automatically when it stops playing. Returns `None`
+    /// if there's no such event or the event has no clips.
+    pub fn play(
+        &self,
+        name: &str,
+        position: Vector3<f32>,
+        graph: &mut Graph,
+    ) -> Option<Handle<Node>> {
+        let event = self.event(name)?;
+        let sound = event.make_sound(
+            BaseBuilder::new().with_name(name).with_local_transform(
+                TransformBuilder::new()
+                    .with_local_position(position)
+                    .build(),
+            ),
+        )?;
+        Some(sound.build(graph))
+    }
+}
+
+/// Type alias for sound bank resources.
+pub type SoundBankResource = Resource<SoundBank>;
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        asset::manager::ResourceManager,
+        core::{algebra::Vector3, futures::executor::block_on, pool::Handle},
+        engine::{self, SerializationContext},
+        resource::sound_bank::{SoundBank, SoundEvent},
+        scene::{
+            graph::Graph,
+            sound::{DataSource, Sound, SoundBuffer, SoundBufferResource},
+        },
+    };
+    use fyrox_sound::buffer::SoundBufferResourceExtension;
+    use std::{fs, path::Path, sync::Arc};
+
+    #[test]
+    fn test_play_event() {
+        let buffer = SoundBufferResource::new_generic(DataSource::Raw {
+            sample_rate: 44100,
+            channel_count: 1,
+            samples: vec![0.0; 16],
+        })
+        .unwrap();
+
+        let bank = SoundBank {
+            path: Default::default(),
+            events: vec![
+                SoundEvent {
+                    name: "Footstep".to_string(),
+                    clips: vec![None, Some(buffer.clone())],
+                    gain: 0.5..0.8,
+                    pitch: 0.9..1.1,
+                    ..Default::default()
+                },
+                SoundEvent {
+                    name: "Empty".to_string(),
+                    ..Default::default()
+                },
+            ],
+        };
+
+        let mut graph = Graph::new();
+        let handle = bank
+            .play("Footstep", Vector3::new(1.0, 2.0, 3.0), &mut graph)
+            .unwrap();
+        let sound = graph[handle].query_component_ref::<Sound>().unwrap();
+        assert!(sound.is_play_once());
+        assert_eq!(sound.buffer(), Some(buffer));
+        assert!((0.5..=0.8).contains(&sound.gain()));
+        assert!((0.9..=1.1).contains(&sound.pitch()));
+        assert_eq!(
+            **sound.local_transform().position(),
+            Vector3::new(1.0, 2.0, 3.0)
+        );
+
+        assert!(bank.play("Empty", Default::default(), &mut graph).is_none());
+        assert!(bank
+            .play("Unknown", Default::default(), &mut graph)
+            .is_none());
+        assert_ne!(handle, Handle::NONE);
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let resource_manager = ResourceManager::new();
+        engine::initialize_resource_manager_loaders(
+            &resource_manager,
+            Arc::new(SerializationContext::new()),
+        );
+
+        let clip_path = Path::new("fyrox-sound/examples/data/drop.wav");
+        let clip = block_on(resource_manager.request::<SoundBuffer, _>(clip_path)).unwrap();
+
+        fs::create_dir_all("test_output").unwrap();
+        let bank_path = Path::new("test_output/test.soundbank");
+        SoundBank {
+            path: Default::default(),
+            events: vec![SoundEvent {
+                name: "Drop".to_string(),
+                clips: vec![Some(clip.clone()), None],
+                ..Default::default()
+            }],
+        }
+        .save(bank_path)
+        .unwrap();
+
+        let bank = block_on(resource_manager.request::<SoundBank, _>(bank_path)).unwrap();
+        let bank = bank.data_ref();
+        let event = bank.event("Drop").unwrap();
+        assert_eq!(event.clips.len(), 2);
+        let loaded_clip = event.clips[0].clone().unwrap();
+        assert_eq!(loaded_clip.path(), clip_path);
+        assert!(block_on(loaded_clip).is_ok());
+        assert!(event.clips[1].is_none());
+    }
+}
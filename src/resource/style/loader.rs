@@ -0,0 +1,57 @@
+//! Style loader.
+
+use std::sync::Arc;
+
+use fyrox_resource::io::ResourceIo;
+
+use crate::{
+    asset::{
+        event::ResourceEventBroadcaster,
+        loader::{BoxedLoaderFuture, ResourceLoader},
+        untyped::UntypedResource,
+    },
+    core::{log::Log, uuid::Uuid, TypeUuidProvider},
+    resource::style::StyleResourceState,
+};
+
+/// Default implementation for style loading.
+pub struct StyleLoader;
+
+impl ResourceLoader for StyleLoader {
+    fn extensions(&self) -> &[&str] {
+        &["style"]
+    }
+
+    fn data_type_uuid(&self) -> Uuid {
+        StyleResourceState::type_uuid()
+    }
+
+    fn load(
+        &self,
+        style: UntypedResource,
+        event_broadcaster: ResourceEventBroadcaster,
+        reload: bool,
+        io: Arc<dyn ResourceIo>,
+    ) -> BoxedLoaderFuture {
+        Box::pin(async move {
+            let path = style.path();
+            match StyleResourceState::from_file(&path, io.as_ref()).await {
+                Ok(style_state) => {
+                    Log::info(format!("Style {:?} is loaded!", path));
+
+                    style.commit_ok(style_state);
+
+                    event_broadcaster.broadcast_loaded_or_reloaded(style, reload);
+                }
+                Err(error) => {
+                    Log::err(format!(
+                        "Unable to load style from {:?}! Reason {:?}",
+                        path, error
+                    ));
+
+                    style.commit_error(path, error);
+                }
+            }
+        })
+    }
+}
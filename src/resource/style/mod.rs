@@ -0,0 +1,166 @@
+//! Style resource holds a [`Style`] of the user interface. See [`StyleResourceState`] docs for more info.
+
+use crate::{
+    asset::{Resource, ResourceData},
+    core::{
+        io::FileLoadError,
+        reflect::prelude::*,
+        uuid::{uuid, Uuid},
+        visitor::prelude::*,
+        TypeUuidProvider,
+    },
+    gui::style::Style,
+};
+use fyrox_resource::io::ResourceIo;
+use std::{
+    any::Any,
+    borrow::Cow,
+    fmt::{Display, Formatter},
+    path::{Path, PathBuf},
+};
+
+pub mod loader;
+
+/// An error that may occur during style resource loading.
+#[derive(Debug)]
+pub enum StyleResourceError {
+    /// An i/o error has occurred.
+    Io(FileLoadError),
+
+    /// An error that may occur due to version incompatibilities.
+    Visit(VisitError),
+}
+
+impl Display for StyleResourceError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StyleResourceError::Io(v) => {
+                write!(f, "A file load error has occurred {v:?}")
+            }
+            StyleResourceError::Visit(v) => {
+                write!(
+                    f,
+                    "An error that may occur due to version incompatibilities. {v:?}"
+                )
+            }
+        }
+    }
+}
+
+impl From<FileLoadError> for StyleResourceError {
+    fn from(e: FileLoadError) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<VisitError> for StyleResourceError {
+    fn from(e: VisitError) -> Self {
+        Self::Visit(e)
+    }
+}
+
+/// State of the [`StyleResource`]. Style files (`*.style`) are created with [`Style::save`], fonts are not
+/// stored in the files and must be set manually after loading.
+///
+/// ## Switching styles at runtime
+///
+/// A style resource could be loaded as any other resource and then set as the current style of the user
+/// interface, every style-bound property of every widget (brushes, thicknesses and fonts) will be updated:
+///
+/// ```rust
+/// # use fyrox::{
+/// #     asset::manager::ResourceManager, gui::UserInterface, resource::style::StyleResourceState,
+/// # };
+/// async fn switch_style(resource_manager: &ResourceManager, ui: &mut UserInterface) {
+///     let style = resource_manager.request::<StyleResourceState, _>("data/ui/light.style");
+///     if let Ok(style) = style.await {
+///         ui.set_style(style.data_ref().style.clone());
+///     }
+/// }
+/// ```
+#[derive(Debug, Visit, Default, Reflect)]
+pub struct StyleResourceState {
+    pub(crate) path: PathBuf,
+    /// Actual style.
+    pub style: Style,
+}
+
+impl ResourceData for StyleResourceState {
+    fn path(&self) -> Cow<'_, Path> {
+        Cow::Borrowed(&self.path)
+    }
+
+    fn set_path(&mut self, path: PathBuf) {
+        self.path = path;
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn type_uuid(&self) -> Uuid {
+        <Self as TypeUuidProvider>::type_uuid()
+    }
+
+    fn is_procedural(&self) -> bool {
+        false
+    }
+}
+
+impl TypeUuidProvider for StyleResourceState {
+    fn type_uuid() -> Uuid {
+        uuid!("c89cb79e-393c-4750-8914-37d427fe8edb")
+    }
+}
+
+impl StyleResourceState {
+    /// Load a style resource from the specific file path.
+    pub async fn from_file(path: &Path, io: &dyn ResourceIo) -> Result<Self, StyleResourceError> {
+        let bytes = io.load_file(path).await?;
+        let mut visitor = Visitor::load_from_memory(&bytes)?;
+        let mut style = Style::default();
+        style.visit("Style", &mut visitor)?;
+        Ok(Self {
+            style,
+            path: path.to_path_buf(),
+        })
+    }
+}
+
+/// Type alias for style resources.
+pub type StyleResource = Resource<StyleResourceState>;
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        asset::io::FsResourceIo,
+        core::{color::Color, futures::executor::block_on},
+        gui::{brush::Brush, style::Style},
+        resource::style::StyleResourceState,
+    };
+
+    #[test]
+    fn test_style_resource_load() {
+        let path = std::env::temp_dir().join("fyrox_test_style_resource.style");
+        Style::light()
+            .with(Style::BRUSH_BRIGHT_BLUE, Color::opaque(200, 80, 40))
+            .save(&path)
+            .unwrap();
+
+        let state = block_on(StyleResourceState::from_file(&path, &FsResourceIo)).unwrap();
+        assert_eq!(
+            state.style.brush(Style::BRUSH_BRIGHT_BLUE),
+            Some(Brush::Solid(Color::opaque(200, 80, 40)))
+        );
+        assert_eq!(
+            state.style.brush(Style::BRUSH_TEXT),
+            Style::light().brush(Style::BRUSH_TEXT)
+        );
+
+        let _ = std::fs::remove_file(path);
+    }
+}
@@ -407,6 +407,8 @@ impl MipFilter {
 ///     t_wrap_mode: ClampToEdge,
 ///     anisotropy: 8.0,
 ///     compression: NoCompression,
+///     generate_mips: true,
+///     srgb: false,
 /// )
 /// ```
 #[derive(Clone, Deserialize, Serialize, Debug, Reflect)]
@@ -427,6 +429,14 @@ pub struct TextureImportOptions {
     pub(crate) mip_filter: MipFilter,
     #[serde(default)]
     pub(crate) flip_green_channel: bool,
+    #[serde(default = "default_generate_mips")]
+    pub(crate) generate_mips: bool,
+    #[serde(default)]
+    pub(crate) srgb: bool,
+}
+
+fn default_generate_mips() -> bool {
+    true
 }
 
 impl Default for TextureImportOptions {
@@ -440,6 +450,8 @@ impl Default for TextureImportOptions {
             compression: CompressionOptions::default(),
             mip_filter: Default::default(),
             flip_green_channel: false,
+            generate_mips: true,
+            srgb: false,
         }
     }
 }
@@ -528,6 +540,34 @@ impl TextureImportOptions {
     pub fn set_compression(&mut self, compression: CompressionOptions) {
         self.compression = compression;
     }
+
+    /// Defines whether mip levels should be generated for the texture. Mip levels are generated only if
+    /// minification filter uses mip mapping.
+    pub fn with_generate_mips(mut self, generate_mips: bool) -> Self {
+        self.generate_mips = generate_mips;
+        self
+    }
+
+    /// Defines whether mip levels should be generated for the texture. Mip levels are generated only if
+    /// minification filter uses mip mapping.
+    pub fn set_generate_mips(&mut self, generate_mips: bool) {
+        self.generate_mips = generate_mips;
+    }
+
+    /// Defines whether the texture stores colors in sRGB color space. Such textures will be converted
+    /// to linear color space by GPU on fetching. Only applicable to uncompressed 8-bit RGB and RGBA
+    /// images.
+    pub fn with_srgb(mut self, srgb: bool) -> Self {
+        self.srgb = srgb;
+        self
+    }
+
+    /// Defines whether the texture stores colors in sRGB color space. Such textures will be converted
+    /// to linear color space by GPU on fetching. Only applicable to uncompressed 8-bit RGB and RGBA
+    /// images.
+    pub fn set_srgb(&mut self, srgb: bool) {
+        self.srgb = srgb;
+    }
 }
 
 /// Type alias for texture resources.
@@ -876,6 +916,14 @@ pub enum TexturePixelKind {
 
     /// Red component as 2-byte, half-precision float.
     R16F = 24,
+
+    /// Red, green, and blue components in sRGB color space, each by 1 byte. Values are converted to
+    /// linear color space on fetching.
+    SRGB8 = 25,
+
+    /// Red, green, and blue components in sRGB color space and linear alpha, each by 1 byte. Values
+    /// are converted to linear color space on fetching.
+    SRGBA8 = 26,
 }
 
 impl TexturePixelKind {
@@ -906,6 +954,8 @@ impl TexturePixelKind {
             22 => Ok(Self::RGB16F),
             23 => Ok(Self::R32F),
             24 => Ok(Self::R16F),
+            25 => Ok(Self::SRGB8),
+            26 => Ok(Self::SRGBA8),
             _ => Err(format!("Invalid texture kind {}!", id)),
         }
     }
@@ -919,8 +969,13 @@ impl TexturePixelKind {
     pub fn size_in_bytes(&self) -> Option<usize> {
         match self {
             Self::R8 | Self::Luminance8 => Some(1),
-            Self::RGB8 | Self::BGR8 => Some(3),
-            Self::RGBA8 | Self::RG16 | Self::BGRA8 | Self::LuminanceAlpha16 | Self::R32F => Some(4),
+            Self::RGB8 | Self::BGR8 | Self::SRGB8 => Some(3),
+            Self::RGBA8
+            | Self::RG16
+            | Self::BGRA8
+            | Self::LuminanceAlpha16
+            | Self::R32F
+            | Self::SRGBA8 => Some(4),
             Self::RG8 | Self::R16 | Self::LuminanceAlpha8 | Self::Luminance16 | Self::R16F => {
                 Some(2)
             }
@@ -1149,8 +1204,11 @@ fn bytes_in_mip_level(kind: TextureKind, pixel_kind: TexturePixelKind, mip: usiz
         | TexturePixelKind::Luminance16
         | TexturePixelKind::RG8
         | TexturePixelKind::R16F => 2 * pixel_count,
-        TexturePixelKind::RGB8 | TexturePixelKind::BGR8 => 3 * pixel_count,
+        TexturePixelKind::RGB8 | TexturePixelKind::BGR8 | TexturePixelKind::SRGB8 => {
+            3 * pixel_count
+        }
         TexturePixelKind::RGBA8
+        | TexturePixelKind::SRGBA8
         | TexturePixelKind::BGRA8
         | TexturePixelKind::RG16
         | TexturePixelKind::LuminanceAlpha16
@@ -1210,8 +1268,12 @@ fn mip_byte_offset(kind: TextureKind, pixel_kind: TexturePixelKind, mut mip: usi
 fn convert_pixel_type_enum(pixel_kind: TexturePixelKind) -> fr::PixelType {
     match pixel_kind {
         TexturePixelKind::R8 | TexturePixelKind::Luminance8 => fr::PixelType::U8,
-        TexturePixelKind::RGB8 | TexturePixelKind::BGR8 => fr::PixelType::U8x3,
-        TexturePixelKind::RGBA8 | TexturePixelKind::BGRA8 => fr::PixelType::U8x4,
+        TexturePixelKind::RGB8 | TexturePixelKind::BGR8 | TexturePixelKind::SRGB8 => {
+            fr::PixelType::U8x3
+        }
+        TexturePixelKind::RGBA8 | TexturePixelKind::BGRA8 | TexturePixelKind::SRGBA8 => {
+            fr::PixelType::U8x4
+        }
         TexturePixelKind::RG8 | TexturePixelKind::LuminanceAlpha8 => fr::PixelType::U8x2,
         TexturePixelKind::R16 | TexturePixelKind::Luminance16 => fr::PixelType::U16,
         TexturePixelKind::RG16 | TexturePixelKind::LuminanceAlpha16 => fr::PixelType::U16x2,
@@ -1389,7 +1451,9 @@ impl Texture {
                 width as usize * height as usize * src_pixel_kind.size_in_bytes().unwrap_or(4),
             );
 
-            if import_options.minification_filter.is_using_mip_mapping() {
+            if import_options.generate_mips
+                && import_options.minification_filter.is_using_mip_mapping()
+            {
                 let src_pixel_type = convert_pixel_type_enum(src_pixel_kind);
                 let mut level_width = width;
                 let mut level_height = height;
@@ -1459,6 +1523,14 @@ impl Texture {
                 }
             }
 
+            if import_options.srgb {
+                final_pixel_kind = match final_pixel_kind {
+                    TexturePixelKind::RGB8 => TexturePixelKind::SRGB8,
+                    TexturePixelKind::RGBA8 => TexturePixelKind::SRGBA8,
+                    other => other,
+                };
+            }
+
             Ok(Self {
                 path: Default::default(),
                 pixel_kind: final_pixel_kind,
@@ -1675,8 +1747,8 @@ impl Texture {
         let color_type = match self.pixel_kind {
             TexturePixelKind::R8 => ColorType::L8,
             TexturePixelKind::Luminance8 => ColorType::L8,
-            TexturePixelKind::RGB8 => ColorType::Rgb8,
-            TexturePixelKind::RGBA8 => ColorType::Rgba8,
+            TexturePixelKind::RGB8 | TexturePixelKind::SRGB8 => ColorType::Rgb8,
+            TexturePixelKind::RGBA8 | TexturePixelKind::SRGBA8 => ColorType::Rgba8,
             TexturePixelKind::RG8 => ColorType::La8,
             TexturePixelKind::LuminanceAlpha8 => ColorType::La8,
             TexturePixelKind::R16 => ColorType::L16,
@@ -1401,6 +1401,8 @@ impl Graph {
         for (handle, node) in self.pool.pair_iter() {
             node.sync_native(handle, &mut sync_context);
         }
+
+        self.sound_context.apply_reverb_zones();
     }
 
     fn update_node(
@@ -1408,6 +1410,7 @@ impl Graph {
         handle: Handle<Node>,
         frame_size: Vector2<f32>,
         dt: f32,
+        observer_position: Option<Vector3<f32>>,
         delete_dead_nodes: bool,
     ) {
         if let Some((ticket, mut node)) = self.pool.try_take_reserve(handle) {
@@ -1419,6 +1422,7 @@ impl Graph {
                 node.update(&mut UpdateContext {
                     frame_size,
                     dt,
+                    observer_position,
                     nodes: &mut self.pool,
                     physics: &mut self.physics,
                     physics2d: &mut self.physics2d,
@@ -1465,6 +1469,9 @@ impl Graph {
         self.sync_native(&switches);
         self.performance_statistics.sync_time = instant::Instant::now() - last_time;
 
+        self.sound_context.update_music_player(dt);
+        self.sound_context.update_mixer(dt);
+
         if switches.physics {
             self.physics.performance_statistics.reset();
             self.physics.update(dt);
@@ -1480,9 +1487,21 @@ impl Graph {
         self.performance_statistics.sound_update_time =
             self.sound_context.state().full_render_duration();
 
+        let observer_position = self.pool.iter().find_map(|node| {
+            node.cast::<Camera>()
+                .filter(|camera| camera.is_enabled() && camera.is_globally_enabled())
+                .map(|camera| camera.global_position())
+        });
+
         if let Some(overrides) = switches.node_overrides.as_ref() {
             for handle in overrides {
-                self.update_node(*handle, frame_size, dt, switches.delete_dead_nodes);
+                self.update_node(
+                    *handle,
+                    frame_size,
+                    dt,
+                    observer_position,
+                    switches.delete_dead_nodes,
+                );
             }
         } else {
             for i in 0..self.pool.get_capacity() {
@@ -1490,6 +1509,7 @@ impl Graph {
                     self.pool.handle_from_index(i),
                     frame_size,
                     dt,
+                    observer_position,
                     switches.delete_dead_nodes,
                 );
             }
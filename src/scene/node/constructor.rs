@@ -15,7 +15,7 @@ use crate::{
         particle_system::ParticleSystem,
         pivot::Pivot,
         ragdoll::Ragdoll,
-        sound::{listener::Listener, Sound},
+        sound::{listener::Listener, reverb_zone::ReverbZone, Sound},
         sprite::Sprite,
         terrain::Terrain,
     },
@@ -47,6 +47,7 @@ impl NodeConstructorContainer {
         container.add::<ParticleSystem>();
         container.add::<Sound>();
         container.add::<Listener>();
+        container.add::<ReverbZone>();
         container.add::<Camera>();
         container.add::<scene::collider::Collider>();
         container.add::<Decal>();
@@ -7,7 +7,7 @@
 use crate::resource::model::ModelResource;
 use crate::{
     core::{
-        algebra::{Matrix4, Vector2},
+        algebra::{Matrix4, Vector2, Vector3},
         math::aabb::AxisAlignedBoundingBox,
         pool::Handle,
         reflect::prelude::*,
@@ -98,6 +98,9 @@ pub struct UpdateContext<'a> {
     pub frame_size: Vector2<f32>,
     /// A time that have passed since last update call.
     pub dt: f32,
+    /// Global position of the first enabled camera in the graph (if any). It could be used for distance-based
+    /// optimizations, such as level of detail of particle systems.
+    pub observer_position: Option<Vector3<f32>>,
     /// A reference to a pool with nodes from a scene graph.
     pub nodes: &'a mut NodePool,
     /// A mutable reference to 3D physics world.
@@ -11,6 +11,9 @@ pub struct Vertex {
     pub size: f32,
     pub rotation: f32,
     pub color: Color,
+    pub frame_tex_coord: Vector2<f32>,
+    pub next_frame_tex_coord: Vector2<f32>,
+    pub frame_blend: f32,
 }
 
 /// Particle system is "rendered" into special buffer, which contains vertices and faces.
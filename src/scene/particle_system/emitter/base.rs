@@ -2,13 +2,153 @@
 
 use crate::{
     core::{
-        algebra::Vector3, color::Color, numeric_range::RangeExt, reflect::prelude::*,
-        visitor::prelude::*,
+        algebra::Vector3, color::Color, color_gradient::ColorGradient, curve::Curve,
+        numeric_range::RangeExt, reflect::prelude::*, visitor::prelude::*,
     },
     scene::particle_system::{Particle, ParticleSystemRng},
 };
 use std::ops::Range;
 
+/// A burst of particles, that is emitted at once (regardless of the spawn rate) at the given time after
+/// the start of an emitter. It could be repeated a number of times with the given interval, which is
+/// useful for explosions, pulses, fireworks and so on.
+#[derive(Clone, Debug, Visit, PartialEq, Reflect)]
+pub struct EmissionBurst {
+    /// Time (in seconds) since the start of the emitter, at which the first cycle of the burst happens.
+    #[reflect(min_value = 0.0, step = 0.1)]
+    pub time: f32,
+    /// Amount of particles emitted by each cycle of the burst.
+    pub count: u32,
+    /// Total amount of cycles of the burst, zero means infinite amount of cycles.
+    pub cycles: u32,
+    /// Time (in seconds) between the cycles of the burst.
+    #[reflect(min_value = 0.0, step = 0.1)]
+    pub interval: f32,
+    /// Probability (`[0; 1]` range) of each cycle of the burst to happen.
+    #[reflect(min_value = 0.0, max_value = 1.0, step = 0.01)]
+    pub probability: f32,
+}
+
+impl Default for EmissionBurst {
+    fn default() -> Self {
+        Self {
+            time: 0.0,
+            count: 30,
+            cycles: 1,
+            interval: 1.0,
+            probability: 1.0,
+        }
+    }
+}
+
+impl EmissionBurst {
+    /// Returns amount of particles emitted by the cycles of the burst that happen within `[from; to)` time
+    /// range.
+    fn particles_in(&self, from: f32, to: f32, rng: &mut ParticleSystemRng) -> u32 {
+        let (first, mut last) = if self.interval > f32::EPSILON {
+            (
+                ((from - self.time) / self.interval).ceil().max(0.0) as u32,
+                ((to - self.time) / self.interval).ceil().max(0.0) as u32,
+            )
+        } else {
+            // Cycles without interval are merged into the first one.
+            let cycles = if self.cycles == 0 { 1 } else { self.cycles };
+            if from <= self.time && self.time < to {
+                return self.count * cycles;
+            } else {
+                return 0;
+            }
+        };
+
+        if self.cycles != 0 {
+            last = last.min(self.cycles);
+        }
+
+        let mut count = 0;
+        for _ in first..last {
+            if self.probability >= 1.0 || (0.0..1.0).random(rng) < self.probability {
+                count += self.count;
+            }
+        }
+        count
+    }
+}
+
+/// A set of curves for each component of a vector, that is sampled by normalized lifetime (`[0; 1]` range)
+/// of a particle. Empty curve produces zero component.
+#[derive(Clone, Debug, Default, Visit, PartialEq, Reflect)]
+pub struct LifetimeVectorCurve {
+    /// Curve of X component of the vector.
+    pub x: Curve,
+    /// Curve of Y component of the vector.
+    pub y: Curve,
+    /// Curve of Z component of the vector.
+    pub z: Curve,
+}
+
+impl LifetimeVectorCurve {
+    /// Returns a vector at the given normalized lifetime.
+    pub fn value_at(&self, k: f32) -> Vector3<f32> {
+        Vector3::new(self.x.value_at(k), self.y.value_at(k), self.z.value_at(k))
+    }
+}
+
+/// Trail (ribbon) settings of an emitter. When set, every particle of the emitter leaves a ribbon of
+/// connected quads built from its position history. The ribbon is facing the camera.
+#[derive(Clone, Debug, Visit, PartialEq, Reflect)]
+pub struct ParticleTrail {
+    /// Maximum amount of points in the position history of a particle.
+    #[reflect(min_value = 1.0, step = 1.0)]
+    pub length: u32,
+    /// Minimum distance that a particle must travel to add a new point to its position history.
+    #[reflect(min_value = 0.0, step = 0.01)]
+    pub min_vertex_distance: f32,
+    /// Multiplier of the size of a particle along the trail (`0.0` - the particle, `1.0` - the end of
+    /// the trail). Empty curve means constant width equal to the size of the particle.
+    pub width_over_trail: Curve,
+    /// Color along the trail (`0.0` - the particle, `1.0` - the end of the trail), it is multiplied with
+    /// the color of the particle.
+    pub color_over_trail: ColorGradient,
+}
+
+impl Default for ParticleTrail {
+    fn default() -> Self {
+        Self {
+            length: 10,
+            min_vertex_distance: 0.1,
+            width_over_trail: Default::default(),
+            color_over_trail: Default::default(),
+        }
+    }
+}
+
+impl ParticleTrail {
+    /// Returns width of the trail at the given normalized position along the trail.
+    pub fn width_at(&self, particle_size: f32, t: f32) -> f32 {
+        if self.width_over_trail.is_empty() {
+            particle_size
+        } else {
+            particle_size * self.width_over_trail.value_at(t).max(0.0)
+        }
+    }
+
+    /// Adds current position of the particle to its position history, if the particle have moved far
+    /// enough from the last point.
+    pub(crate) fn record(&self, particle: &mut Particle) {
+        let position = particle.position;
+        let trail = &mut particle.trail;
+        if trail.last().map_or(true, |last| {
+            (position - *last).norm() >= self.min_vertex_distance
+        }) {
+            trail.push(position);
+            let length = self.length.max(1) as usize;
+            if trail.len() > length {
+                trail.drain(..trail.len() - length);
+            }
+        }
+    }
+}
+
 /// See module docs.
 #[derive(Debug, Visit, PartialEq, Reflect)]
 pub struct BaseEmitter {
@@ -38,6 +178,33 @@ pub struct BaseEmitter {
     rotation_speed: Range<f32>,
     /// Range of initial rotation for a particle
     rotation: Range<f32>,
+    /// Multiplier of initial size of a particle over its normalized lifetime
+    #[visit(optional)]
+    size_over_lifetime: Option<Curve>,
+    /// Multiplier of initial rotation speed of a particle over its normalized lifetime
+    #[visit(optional)]
+    rotation_speed_over_lifetime: Option<Curve>,
+    /// Color of a particle over its normalized lifetime, overrides the gradient of particle system
+    #[visit(optional)]
+    color_over_lifetime: Option<ColorGradient>,
+    /// Multiplier of spawn rate over time (in seconds) since the start of the emitter
+    #[visit(optional)]
+    spawn_rate_over_time: Option<Curve>,
+    /// Bursts of particles that are emitted in addition to the particles emitted with spawn rate
+    #[visit(optional)]
+    bursts: Vec<EmissionBurst>,
+    /// Velocity (in units per second) added to a particle over its normalized lifetime
+    #[visit(optional)]
+    velocity_over_lifetime: Option<LifetimeVectorCurve>,
+    /// Force (acceleration in units per second squared) applied to a particle over its normalized lifetime
+    #[visit(optional)]
+    force_over_lifetime: Option<LifetimeVectorCurve>,
+    /// Trail (ribbon) that is left by each particle
+    #[visit(optional)]
+    trail: Option<ParticleTrail>,
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pub(crate) elapsed_time: f32,
     #[reflect(hidden)]
     pub(crate) alive_particles: u32,
     #[visit(skip)]
@@ -65,6 +232,14 @@ pub struct BaseEmitterBuilder {
     z_velocity: Range<f32>,
     rotation_speed: Range<f32>,
     rotation: Range<f32>,
+    size_over_lifetime: Option<Curve>,
+    rotation_speed_over_lifetime: Option<Curve>,
+    color_over_lifetime: Option<ColorGradient>,
+    spawn_rate_over_time: Option<Curve>,
+    bursts: Vec<EmissionBurst>,
+    velocity_over_lifetime: Option<LifetimeVectorCurve>,
+    force_over_lifetime: Option<LifetimeVectorCurve>,
+    trail: Option<ParticleTrail>,
     resurrect_particles: bool,
 }
 
@@ -89,6 +264,14 @@ impl BaseEmitterBuilder {
             z_velocity: -0.001..0.001,
             rotation_speed: -0.02..0.02,
             rotation: -std::f32::consts::PI..std::f32::consts::PI,
+            size_over_lifetime: None,
+            rotation_speed_over_lifetime: None,
+            color_over_lifetime: None,
+            spawn_rate_over_time: None,
+            bursts: Default::default(),
+            velocity_over_lifetime: None,
+            force_over_lifetime: None,
+            trail: None,
             resurrect_particles: true,
         }
     }
@@ -159,6 +342,56 @@ impl BaseEmitterBuilder {
         self
     }
 
+    /// Sets desired size-over-lifetime curve. See [`BaseEmitter::set_size_over_lifetime`] for more info.
+    pub fn with_size_over_lifetime(mut self, curve: Curve) -> Self {
+        self.size_over_lifetime = Some(curve);
+        self
+    }
+
+    /// Sets desired rotation-speed-over-lifetime curve. See
+    /// [`BaseEmitter::set_rotation_speed_over_lifetime`] for more info.
+    pub fn with_rotation_speed_over_lifetime(mut self, curve: Curve) -> Self {
+        self.rotation_speed_over_lifetime = Some(curve);
+        self
+    }
+
+    /// Sets desired color gradient over lifetime. See [`BaseEmitter::set_color_over_lifetime_gradient`]
+    /// for more info.
+    pub fn with_color_over_lifetime_gradient(mut self, gradient: ColorGradient) -> Self {
+        self.color_over_lifetime = Some(gradient);
+        self
+    }
+
+    /// Sets desired spawn rate curve. See [`BaseEmitter::set_spawn_rate_over_time`] for more info.
+    pub fn with_spawn_rate_over_time(mut self, curve: Curve) -> Self {
+        self.spawn_rate_over_time = Some(curve);
+        self
+    }
+
+    /// Sets desired bursts of particles.
+    pub fn with_bursts(mut self, bursts: Vec<EmissionBurst>) -> Self {
+        self.bursts = bursts;
+        self
+    }
+
+    /// Sets desired velocity over lifetime. See [`BaseEmitter::set_velocity_over_lifetime`] for more info.
+    pub fn with_velocity_over_lifetime(mut self, curve: LifetimeVectorCurve) -> Self {
+        self.velocity_over_lifetime = Some(curve);
+        self
+    }
+
+    /// Sets desired force over lifetime. See [`BaseEmitter::set_force_over_lifetime`] for more info.
+    pub fn with_force_over_lifetime(mut self, curve: LifetimeVectorCurve) -> Self {
+        self.force_over_lifetime = Some(curve);
+        self
+    }
+
+    /// Sets desired trail settings. See [`ParticleTrail`] docs for more info.
+    pub fn with_trail(mut self, trail: ParticleTrail) -> Self {
+        self.trail = Some(trail);
+        self
+    }
+
     /// Sets whether to resurrect dead particle or not.
     pub fn resurrect_particles(mut self, value: bool) -> Self {
         self.resurrect_particles = value;
@@ -179,6 +412,15 @@ impl BaseEmitterBuilder {
             z_velocity: self.z_velocity,
             rotation_speed: self.rotation_speed,
             rotation: self.rotation,
+            size_over_lifetime: self.size_over_lifetime,
+            rotation_speed_over_lifetime: self.rotation_speed_over_lifetime,
+            color_over_lifetime: self.color_over_lifetime,
+            spawn_rate_over_time: self.spawn_rate_over_time,
+            bursts: self.bursts,
+            velocity_over_lifetime: self.velocity_over_lifetime,
+            force_over_lifetime: self.force_over_lifetime,
+            trail: self.trail,
+            elapsed_time: 0.0,
             alive_particles: 0,
             time: 0.0,
             particles_to_spawn: 0,
@@ -191,11 +433,35 @@ impl BaseEmitterBuilder {
 impl BaseEmitter {
     /// Updates emitter and emits required amount of particles each call. There is no
     /// need to call it manually, it will be automatically called by scene update call.
-    pub fn tick(&mut self, dt: f32) {
+    /// `spawn_rate_scale` scales the spawn rate (but not bursts), it is used by level of detail of
+    /// particle systems.
+    pub fn tick(&mut self, dt: f32, spawn_rate_scale: f32, rng: &mut ParticleSystemRng) {
+        let prev_elapsed_time = self.elapsed_time;
+        self.elapsed_time += dt;
+
+        let spawn_rate = spawn_rate_scale
+            * match self.spawn_rate_over_time.as_ref() {
+                Some(curve) if !curve.is_empty() => {
+                    self.particle_spawn_rate as f32 * curve.value_at(prev_elapsed_time).max(0.0)
+                }
+                _ => self.particle_spawn_rate as f32,
+            };
+
         self.time += dt;
-        let time_amount_per_particle = 1.0 / self.particle_spawn_rate as f32;
-        let mut particle_count = (self.time / time_amount_per_particle) as u32;
-        self.time -= time_amount_per_particle * particle_count as f32;
+        let mut particle_count = if spawn_rate > 0.0 {
+            let time_amount_per_particle = 1.0 / spawn_rate;
+            let count = (self.time / time_amount_per_particle) as u32;
+            self.time -= time_amount_per_particle * count as f32;
+            count
+        } else {
+            self.time = 0.0;
+            0
+        };
+
+        for burst in self.bursts.iter() {
+            particle_count += burst.particles_in(prev_elapsed_time, self.elapsed_time, rng);
+        }
+
         if let Some(max_particles) = self.max_particles {
             let alive_particles = self.alive_particles;
             if alive_particles < max_particles && alive_particles + particle_count > max_particles {
@@ -217,6 +483,7 @@ impl BaseEmitter {
         particle.initial_lifetime = self.lifetime.random(rng);
         particle.color = Color::WHITE;
         particle.size = self.size.random(rng);
+        particle.initial_size = particle.size;
         particle.size_modifier = self.size_modifier.random(rng);
         particle.velocity = Vector3::new(
             self.x_velocity.random(rng),
@@ -225,6 +492,39 @@ impl BaseEmitter {
         );
         particle.rotation = self.rotation.random(rng);
         particle.rotation_speed = self.rotation_speed.random(rng);
+        particle.initial_rotation_speed = particle.rotation_speed;
+    }
+
+    /// Applies the force and the velocity over lifetime of the emitter to the particle. `k` is normalized
+    /// lifetime of the particle.
+    pub(crate) fn apply_motion(&self, particle: &mut Particle, k: f32, dt: f32) {
+        if let Some(force) = self.force_over_lifetime.as_ref() {
+            // Velocity of a particle is a displacement per tick, see acceleration of particle system.
+            particle.velocity += force.value_at(k).scale(dt * dt);
+        }
+
+        if let Some(velocity) = self.velocity_over_lifetime.as_ref() {
+            particle.position += velocity.value_at(k).scale(dt);
+        }
+    }
+
+    /// Updates size and rotation speed of the particle using the curves of the emitter. `k` is normalized
+    /// lifetime of the particle. Returns `false` if the emitter does not have the size curve, so the size
+    /// should be changed using the size modifier.
+    pub(crate) fn apply_curves(&self, particle: &mut Particle, k: f32) -> bool {
+        if let Some(curve) = self.rotation_speed_over_lifetime.as_ref() {
+            if !curve.is_empty() {
+                particle.rotation_speed = particle.initial_rotation_speed * curve.value_at(k);
+            }
+        }
+
+        match self.size_over_lifetime.as_ref() {
+            Some(curve) if !curve.is_empty() => {
+                particle.size = (particle.initial_size * curve.value_at(k)).max(0.0);
+                true
+            }
+            _ => false,
+        }
     }
 
     /// Sets new position of emitter in local coordinates.
@@ -361,6 +661,106 @@ impl BaseEmitter {
         self.rotation.clone()
     }
 
+    /// Sets new size-over-lifetime curve. The curve defines a multiplier of initial size of a particle
+    /// over its normalized lifetime (`[0; 1]` range), so its value at `0.0` is usually `1.0`. Size modifier
+    /// is ignored when the curve is set. Empty curve is ignored.
+    pub fn set_size_over_lifetime(&mut self, curve: Option<Curve>) -> &mut Self {
+        self.size_over_lifetime = curve;
+        self
+    }
+
+    /// Returns current size-over-lifetime curve.
+    pub fn size_over_lifetime(&self) -> Option<&Curve> {
+        self.size_over_lifetime.as_ref()
+    }
+
+    /// Sets new rotation-speed-over-lifetime curve. The curve defines a multiplier of initial rotation
+    /// speed of a particle over its normalized lifetime (`[0; 1]` range). Empty curve is ignored.
+    pub fn set_rotation_speed_over_lifetime(&mut self, curve: Option<Curve>) -> &mut Self {
+        self.rotation_speed_over_lifetime = curve;
+        self
+    }
+
+    /// Returns current rotation-speed-over-lifetime curve.
+    pub fn rotation_speed_over_lifetime(&self) -> Option<&Curve> {
+        self.rotation_speed_over_lifetime.as_ref()
+    }
+
+    /// Sets new color gradient that will be sampled by normalized lifetime (`[0; 1]` range) of particles
+    /// of the emitter. When set, it is used instead of the gradient of particle system, so each emitter of
+    /// the same particle system could have its own colors.
+    pub fn set_color_over_lifetime_gradient(
+        &mut self,
+        gradient: Option<ColorGradient>,
+    ) -> &mut Self {
+        self.color_over_lifetime = gradient;
+        self
+    }
+
+    /// Returns current color gradient over lifetime of the emitter.
+    pub fn color_over_lifetime_gradient(&self) -> Option<&ColorGradient> {
+        self.color_over_lifetime.as_ref()
+    }
+
+    /// Sets new spawn rate curve. The curve defines a multiplier of spawn rate over time (in seconds) since
+    /// the start of the emitter, use curve extrapolation to repeat it. Empty curve is ignored.
+    pub fn set_spawn_rate_over_time(&mut self, curve: Option<Curve>) -> &mut Self {
+        self.spawn_rate_over_time = curve;
+        self
+    }
+
+    /// Returns current spawn rate curve.
+    pub fn spawn_rate_over_time(&self) -> Option<&Curve> {
+        self.spawn_rate_over_time.as_ref()
+    }
+
+    /// Sets new bursts of particles. See [`EmissionBurst`] docs for more info.
+    pub fn set_bursts(&mut self, bursts: Vec<EmissionBurst>) -> &mut Self {
+        self.bursts = bursts;
+        self
+    }
+
+    /// Returns current bursts of particles.
+    pub fn bursts(&self) -> &[EmissionBurst] {
+        &self.bursts
+    }
+
+    /// Sets new velocity over lifetime. The curves define a velocity (in units per second) that is added
+    /// to the velocity of a particle over its normalized lifetime (`[0; 1]` range).
+    pub fn set_velocity_over_lifetime(&mut self, curve: Option<LifetimeVectorCurve>) -> &mut Self {
+        self.velocity_over_lifetime = curve;
+        self
+    }
+
+    /// Returns current velocity over lifetime.
+    pub fn velocity_over_lifetime(&self) -> Option<&LifetimeVectorCurve> {
+        self.velocity_over_lifetime.as_ref()
+    }
+
+    /// Sets new force over lifetime. The curves define an acceleration (in units per second squared) that
+    /// is applied to a particle over its normalized lifetime (`[0; 1]` range), in addition to the
+    /// acceleration of particle system.
+    pub fn set_force_over_lifetime(&mut self, curve: Option<LifetimeVectorCurve>) -> &mut Self {
+        self.force_over_lifetime = curve;
+        self
+    }
+
+    /// Returns current force over lifetime.
+    pub fn force_over_lifetime(&self) -> Option<&LifetimeVectorCurve> {
+        self.force_over_lifetime.as_ref()
+    }
+
+    /// Sets new trail settings, `None` disables trails. See [`ParticleTrail`] docs for more info.
+    pub fn set_trail(&mut self, trail: Option<ParticleTrail>) -> &mut Self {
+        self.trail = trail;
+        self
+    }
+
+    /// Returns current trail settings.
+    pub fn trail(&self) -> Option<&ParticleTrail> {
+        self.trail.as_ref()
+    }
+
     /// Enables or disables automatic particle resurrection. Setting this option to
     /// true is useful for "endless" effects.
     pub fn enable_particle_resurrection(&mut self, state: bool) -> &mut Self {
@@ -393,6 +793,15 @@ impl Clone for BaseEmitter {
             z_velocity: self.z_velocity.clone(),
             rotation_speed: self.rotation_speed.clone(),
             rotation: self.rotation.clone(),
+            size_over_lifetime: self.size_over_lifetime.clone(),
+            rotation_speed_over_lifetime: self.rotation_speed_over_lifetime.clone(),
+            color_over_lifetime: self.color_over_lifetime.clone(),
+            spawn_rate_over_time: self.spawn_rate_over_time.clone(),
+            bursts: self.bursts.clone(),
+            velocity_over_lifetime: self.velocity_over_lifetime.clone(),
+            force_over_lifetime: self.force_over_lifetime.clone(),
+            trail: self.trail.clone(),
+            elapsed_time: self.elapsed_time,
             alive_particles: self.alive_particles,
             time: self.time,
             particles_to_spawn: 0,
@@ -416,6 +825,15 @@ impl Default for BaseEmitter {
             z_velocity: -0.001..0.001,
             rotation_speed: -0.02..0.02,
             rotation: -std::f32::consts::PI..std::f32::consts::PI,
+            size_over_lifetime: None,
+            rotation_speed_over_lifetime: None,
+            color_over_lifetime: None,
+            spawn_rate_over_time: None,
+            bursts: Default::default(),
+            velocity_over_lifetime: None,
+            force_over_lifetime: None,
+            trail: None,
+            elapsed_time: 0.0,
             alive_particles: 0,
             time: 0.0,
             particles_to_spawn: 0,
@@ -424,3 +842,88 @@ impl Default for BaseEmitter {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::curve::{Curve, CurveKey, CurveKeyKind},
+        scene::particle_system::{
+            emitter::base::{BaseEmitterBuilder, EmissionBurst},
+            ParticleSystemRng,
+        },
+    };
+
+    #[test]
+    fn test_burst_scheduling() {
+        let mut rng = ParticleSystemRng::default();
+        let burst = EmissionBurst {
+            time: 0.5,
+            count: 10,
+            cycles: 3,
+            interval: 1.0,
+            probability: 1.0,
+        };
+
+        assert_eq!(burst.particles_in(0.0, 0.5, &mut rng), 0);
+        assert_eq!(burst.particles_in(0.5, 0.75, &mut rng), 10);
+        assert_eq!(burst.particles_in(0.75, 1.5, &mut rng), 0);
+        assert_eq!(burst.particles_in(1.25, 1.75, &mut rng), 10);
+        assert_eq!(burst.particles_in(0.0, 10.0, &mut rng), 30);
+
+        // Small steps must not lose or duplicate any cycle.
+        let mut total = 0;
+        let mut time = 0.0;
+        while time < 10.0 {
+            total += burst.particles_in(time, time + 0.125, &mut rng);
+            time += 0.125;
+        }
+        assert_eq!(total, 30);
+
+        let infinite = EmissionBurst {
+            cycles: 0,
+            ..burst.clone()
+        };
+        assert_eq!(infinite.particles_in(0.0, 10.0, &mut rng), 100);
+
+        // Cycles without interval are emitted at once.
+        let merged = EmissionBurst {
+            interval: 0.0,
+            ..burst.clone()
+        };
+        assert_eq!(merged.particles_in(0.0, 0.5, &mut rng), 0);
+        assert_eq!(merged.particles_in(0.5, 0.75, &mut rng), 30);
+        assert_eq!(merged.particles_in(0.75, 10.0, &mut rng), 0);
+
+        let never = EmissionBurst {
+            probability: 0.0,
+            ..burst
+        };
+        assert_eq!(never.particles_in(0.0, 10.0, &mut rng), 0);
+    }
+
+    #[test]
+    fn test_spawn_rate_over_time() {
+        let mut rng = ParticleSystemRng::default();
+        // Half of the spawn rate during the first second, nothing after it.
+        let mut emitter = BaseEmitterBuilder::new()
+            .with_spawn_rate(16)
+            .with_spawn_rate_over_time(Curve::from(vec![
+                CurveKey::new(0.0, 0.5, CurveKeyKind::Constant),
+                CurveKey::new(1.0, 0.0, CurveKeyKind::Constant),
+            ]))
+            .build();
+
+        let mut spawned = 0;
+        for _ in 0..8 {
+            emitter.tick(0.125, 1.0, &mut rng);
+            assert_eq!(emitter.particles_to_spawn, 1);
+            spawned += emitter.particles_to_spawn;
+        }
+        assert_eq!(spawned, 8);
+
+        for _ in 0..8 {
+            emitter.tick(0.125, 1.0, &mut rng);
+            assert_eq!(emitter.particles_to_spawn, 0);
+        }
+    }
+}
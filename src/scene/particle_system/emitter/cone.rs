@@ -0,0 +1,174 @@
+//! Cone emitter places particles in a conical volume, its apex is at the position of the emitter and it
+//! is oriented along positive Y axis. Can be used for fountains, jets, sparks and so on.
+
+use crate::{
+    core::{algebra::Vector3, numeric_range::RangeExt, reflect::prelude::*, visitor::prelude::*},
+    scene::particle_system::{
+        emitter::{
+            base::{BaseEmitter, BaseEmitterBuilder},
+            Emit, Emitter,
+        },
+        particle::Particle,
+        ParticleSystemRng,
+    },
+};
+use std::ops::{Deref, DerefMut};
+
+/// Maximum half-angle of the cone (in radians), slightly less than 90 degrees to keep the cone finite.
+const MAX_ANGLE: f32 = 1.55;
+
+/// See module docs.
+#[derive(Clone, Debug, Visit, PartialEq, Reflect)]
+pub struct ConeEmitter {
+    emitter: BaseEmitter,
+    #[reflect(min_value = 0.0, step = 0.1)]
+    height: f32,
+    /// Half-angle of the cone in radians.
+    #[reflect(min_value = 0.0, max_value = 1.55, step = 0.01)]
+    angle: f32,
+}
+
+impl Default for ConeEmitter {
+    fn default() -> Self {
+        Self {
+            emitter: Default::default(),
+            height: 1.0,
+            angle: 25.0f32.to_radians(),
+        }
+    }
+}
+
+impl Deref for ConeEmitter {
+    type Target = BaseEmitter;
+
+    fn deref(&self) -> &Self::Target {
+        &self.emitter
+    }
+}
+
+impl DerefMut for ConeEmitter {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.emitter
+    }
+}
+
+impl Emit for ConeEmitter {
+    fn emit(&self, particle: &mut Particle, rng: &mut ParticleSystemRng) {
+        self.emitter.emit(particle, rng);
+        // Cube root makes the distribution uniform along the height, since the area of a section of
+        // the cone grows quadratically with the distance from the apex.
+        let height_scale: f32 = (0.0..1.0).random(rng);
+        let y = height_scale.cbrt() * self.height;
+        let scale: f32 = (0.0..1.0).random(rng);
+        let theta = (0.0..2.0 * std::f32::consts::PI).random(rng);
+        let radius = scale.sqrt() * y * self.angle.tan();
+        let x = radius * theta.cos();
+        let z = radius * theta.sin();
+        particle.position = self.position() + Vector3::new(x, y, z);
+    }
+}
+
+impl ConeEmitter {
+    /// Returns height of the cone emitter.
+    pub fn height(&self) -> f32 {
+        self.height
+    }
+
+    /// Sets height of the cone emitter.
+    pub fn set_height(&mut self, height: f32) {
+        self.height = height.max(0.0);
+    }
+
+    /// Returns half-angle (in radians) of the cone emitter.
+    pub fn angle(&self) -> f32 {
+        self.angle
+    }
+
+    /// Sets half-angle (in radians) of the cone emitter.
+    pub fn set_angle(&mut self, angle: f32) {
+        self.angle = angle.clamp(0.0, MAX_ANGLE);
+    }
+}
+
+/// Cone emitter builder allows you to construct cone emitter in declarative manner.
+/// This is typical implementation of Builder pattern.
+pub struct ConeEmitterBuilder {
+    base: BaseEmitterBuilder,
+    height: f32,
+    angle: f32,
+}
+
+impl ConeEmitterBuilder {
+    /// Creates new cone emitter builder.
+    pub fn new(base: BaseEmitterBuilder) -> Self {
+        Self {
+            base,
+            height: 1.0,
+            angle: 25.0f32.to_radians(),
+        }
+    }
+
+    /// Sets desired height of the emitter.
+    pub fn with_height(mut self, height: f32) -> Self {
+        self.height = height;
+        self
+    }
+
+    /// Sets desired half-angle (in radians) of the emitter.
+    pub fn with_angle(mut self, angle: f32) -> Self {
+        self.angle = angle;
+        self
+    }
+
+    /// Creates new cone emitter with given parameters.
+    pub fn build(self) -> Emitter {
+        Emitter::Cone(ConeEmitter {
+            emitter: self.base.build(),
+            height: self.height.max(0.0),
+            angle: self.angle.clamp(0.0, MAX_ANGLE),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::algebra::Vector3,
+        scene::particle_system::{
+            emitter::{base::BaseEmitterBuilder, cone::ConeEmitter, Emit},
+            particle::Particle,
+            ParticleSystemRng,
+        },
+    };
+
+    #[test]
+    fn test_cone_emitter_sampling() {
+        let position = Vector3::new(1.0, 2.0, 3.0);
+        let emitter = ConeEmitter {
+            emitter: BaseEmitterBuilder::new().with_position(position).build(),
+            height: 2.0,
+            angle: 0.5,
+        };
+
+        let mut rng = ParticleSystemRng::default();
+        let mut upper_half = 0;
+        let count = 10000;
+        for _ in 0..count {
+            let mut particle = Particle::default();
+            emitter.emit(&mut particle, &mut rng);
+
+            let local = particle.position - position;
+            assert!(local.y >= 0.0 && local.y <= emitter.height);
+            let radius = local.x.hypot(local.z);
+            assert!(radius <= local.y * emitter.angle.tan() + 1.0e-5);
+
+            // Half of the volume of the cone lies above this height.
+            if local.y > emitter.height * 0.5f32.cbrt() {
+                upper_half += 1;
+            }
+        }
+
+        let ratio = upper_half as f32 / count as f32;
+        assert!((ratio - 0.5).abs() < 0.05, "{ratio}");
+    }
+}
@@ -5,8 +5,8 @@ use crate::{
     core::{reflect::prelude::*, visitor::prelude::*},
     scene::particle_system::{
         emitter::{
-            base::BaseEmitter, cuboid::CuboidEmitter, cylinder::CylinderEmitter,
-            sphere::SphereEmitter,
+            base::BaseEmitter, cone::ConeEmitter, cuboid::CuboidEmitter, cylinder::CylinderEmitter,
+            sphere::SphereEmitter, torus::TorusEmitter,
         },
         Particle, ParticleSystemRng,
     },
@@ -15,9 +15,11 @@ use std::ops::{Deref, DerefMut};
 use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
 
 pub mod base;
+pub mod cone;
 pub mod cuboid;
 pub mod cylinder;
 pub mod sphere;
+pub mod torus;
 
 /// Emit trait must be implemented for any particle system emitter.
 pub trait Emit {
@@ -34,6 +36,10 @@ pub enum Emitter {
     Sphere(SphereEmitter),
     /// Cylinder emitter.
     Cylinder(CylinderEmitter),
+    /// See ConeEmitter docs.
+    Cone(ConeEmitter),
+    /// See TorusEmitter docs.
+    Torus(TorusEmitter),
 }
 
 impl Emitter {
@@ -43,6 +49,8 @@ impl Emitter {
             1 => Ok(Self::Cuboid(Default::default())),
             2 => Ok(Self::Sphere(Default::default())),
             3 => Ok(Self::Cylinder(Default::default())),
+            4 => Ok(Self::Cone(Default::default())),
+            5 => Ok(Self::Torus(Default::default())),
             _ => Err(format!("Invalid emitter id {}!", id)),
         }
     }
@@ -53,6 +61,8 @@ impl Emitter {
             Self::Cuboid(_) => 1,
             Self::Sphere(_) => 2,
             Self::Cylinder(_) => 3,
+            Self::Cone(_) => 4,
+            Self::Torus(_) => 5,
         }
     }
 }
@@ -63,6 +73,8 @@ macro_rules! static_dispatch {
             Emitter::Cuboid(v) => v.$func($($args),*),
             Emitter::Sphere(v) => v.$func($($args),*),
             Emitter::Cylinder(v) => v.$func($($args),*),
+            Emitter::Cone(v) => v.$func($($args),*),
+            Emitter::Torus(v) => v.$func($($args),*),
         }
     };
 }
@@ -91,6 +103,8 @@ impl Clone for Emitter {
             Self::Cuboid(box_emitter) => Self::Cuboid(box_emitter.clone()),
             Self::Sphere(sphere_emitter) => Self::Sphere(sphere_emitter.clone()),
             Self::Cylinder(cylinder) => Self::Cylinder(cylinder.clone()),
+            Self::Cone(cone) => Self::Cone(cone.clone()),
+            Self::Torus(torus) => Self::Torus(torus.clone()),
         }
     }
 }
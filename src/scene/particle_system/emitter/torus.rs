@@ -0,0 +1,173 @@
+//! Torus emitter places particles in a toroidal volume that lies in XZ plane around the position of the
+//! emitter. Can be used for rings, portals, shockwaves and so on.
+
+use crate::{
+    core::{algebra::Vector3, numeric_range::RangeExt, reflect::prelude::*, visitor::prelude::*},
+    scene::particle_system::{
+        emitter::{
+            base::{BaseEmitter, BaseEmitterBuilder},
+            Emit, Emitter,
+        },
+        particle::Particle,
+        ParticleSystemRng,
+    },
+};
+use std::ops::{Deref, DerefMut};
+
+/// See module docs.
+#[derive(Clone, Debug, Visit, PartialEq, Reflect)]
+pub struct TorusEmitter {
+    emitter: BaseEmitter,
+    /// Distance from the center of the torus to the center of its tube.
+    #[reflect(min_value = 0.0, step = 0.1)]
+    major_radius: f32,
+    /// Radius of the tube of the torus.
+    #[reflect(min_value = 0.0, step = 0.1)]
+    minor_radius: f32,
+}
+
+impl Default for TorusEmitter {
+    fn default() -> Self {
+        Self {
+            emitter: Default::default(),
+            major_radius: 1.0,
+            minor_radius: 0.25,
+        }
+    }
+}
+
+impl Deref for TorusEmitter {
+    type Target = BaseEmitter;
+
+    fn deref(&self) -> &Self::Target {
+        &self.emitter
+    }
+}
+
+impl DerefMut for TorusEmitter {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.emitter
+    }
+}
+
+impl Emit for TorusEmitter {
+    fn emit(&self, particle: &mut Particle, rng: &mut ParticleSystemRng) {
+        self.emitter.emit(particle, rng);
+        let scale: f32 = (0.0..1.0).random(rng);
+        let theta = (0.0..2.0 * std::f32::consts::PI).random(rng);
+        let phi = (0.0..2.0 * std::f32::consts::PI).random(rng);
+        let tube_radius = scale.sqrt() * self.minor_radius;
+        let radius = self.major_radius + tube_radius * phi.cos();
+        particle.position = self.position()
+            + Vector3::new(
+                radius * theta.cos(),
+                tube_radius * phi.sin(),
+                radius * theta.sin(),
+            );
+    }
+}
+
+impl TorusEmitter {
+    /// Returns major radius (distance from the center to the center of the tube) of the torus emitter.
+    pub fn major_radius(&self) -> f32 {
+        self.major_radius
+    }
+
+    /// Sets major radius (distance from the center to the center of the tube) of the torus emitter.
+    pub fn set_major_radius(&mut self, radius: f32) {
+        self.major_radius = radius.max(0.0);
+    }
+
+    /// Returns minor radius (radius of the tube) of the torus emitter.
+    pub fn minor_radius(&self) -> f32 {
+        self.minor_radius
+    }
+
+    /// Sets minor radius (radius of the tube) of the torus emitter.
+    pub fn set_minor_radius(&mut self, radius: f32) {
+        self.minor_radius = radius.max(0.0);
+    }
+}
+
+/// Torus emitter builder allows you to construct torus emitter in declarative manner.
+/// This is typical implementation of Builder pattern.
+pub struct TorusEmitterBuilder {
+    base: BaseEmitterBuilder,
+    major_radius: f32,
+    minor_radius: f32,
+}
+
+impl TorusEmitterBuilder {
+    /// Creates new torus emitter builder.
+    pub fn new(base: BaseEmitterBuilder) -> Self {
+        Self {
+            base,
+            major_radius: 1.0,
+            minor_radius: 0.25,
+        }
+    }
+
+    /// Sets desired major radius of the emitter.
+    pub fn with_major_radius(mut self, radius: f32) -> Self {
+        self.major_radius = radius;
+        self
+    }
+
+    /// Sets desired minor radius of the emitter.
+    pub fn with_minor_radius(mut self, radius: f32) -> Self {
+        self.minor_radius = radius;
+        self
+    }
+
+    /// Creates new torus emitter with given parameters.
+    pub fn build(self) -> Emitter {
+        Emitter::Torus(TorusEmitter {
+            emitter: self.base.build(),
+            major_radius: self.major_radius.max(0.0),
+            minor_radius: self.minor_radius.max(0.0),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::algebra::Vector3,
+        scene::particle_system::{
+            emitter::{base::BaseEmitterBuilder, torus::TorusEmitter, Emit},
+            particle::Particle,
+            ParticleSystemRng,
+        },
+    };
+
+    #[test]
+    fn test_torus_emitter_sampling() {
+        let position = Vector3::new(1.0, 2.0, 3.0);
+        let emitter = TorusEmitter {
+            emitter: BaseEmitterBuilder::new().with_position(position).build(),
+            major_radius: 2.0,
+            minor_radius: 0.5,
+        };
+
+        let mut rng = ParticleSystemRng::default();
+        let mut inner_half = 0;
+        let count = 10000;
+        for _ in 0..count {
+            let mut particle = Particle::default();
+            emitter.emit(&mut particle, &mut rng);
+
+            // Distance from the center of the tube.
+            let local = particle.position - position;
+            let tube_distance = (local.x.hypot(local.z) - emitter.major_radius).hypot(local.y);
+            assert!(tube_distance <= emitter.minor_radius + 1.0e-5);
+
+            // Half of the area of a section of the tube lies inside this radius.
+            if tube_distance < emitter.minor_radius * 0.5f32.sqrt() {
+                inner_half += 1;
+            }
+        }
+
+        let ratio = inner_half as f32 / count as f32;
+        assert!((ratio - 0.5).abs() < 0.05, "{ratio}");
+    }
+}
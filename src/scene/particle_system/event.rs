@@ -0,0 +1,53 @@
+//! Events of particle systems, they allow gameplay code to react to particles (spawn decals, play sounds,
+//! etc.). See [`ParticleEvent`] docs for more info.
+
+use crate::core::algebra::Vector3;
+use std::collections::VecDeque;
+
+/// Maximum amount of events that could be stored in a queue of a particle system, any new events are
+/// discarded when the queue is full.
+pub const MAX_EVENTS: usize = 4096;
+
+/// An event of a particle. Positions are in world coordinates.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParticleEvent {
+    /// A particle was spawned.
+    Spawned {
+        /// Index of the emitter that spawned the particle.
+        emitter_index: u32,
+        /// Position of the particle.
+        position: Vector3<f32>,
+    },
+    /// A particle has died.
+    Died {
+        /// Index of the emitter that spawned the particle.
+        emitter_index: u32,
+        /// Position of the particle at the moment of its death.
+        position: Vector3<f32>,
+    },
+}
+
+/// A queue of particle events with fixed capacity (see [`MAX_EVENTS`]).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ParticleEventQueue {
+    queue: VecDeque<ParticleEvent>,
+}
+
+impl ParticleEventQueue {
+    /// Pushes an event to the queue, the event is discarded if the queue is full.
+    pub fn push(&mut self, event: ParticleEvent) {
+        if self.queue.len() < MAX_EVENTS {
+            self.queue.push_back(event);
+        }
+    }
+
+    /// Pops an event from the queue.
+    pub fn pop(&mut self) -> Option<ParticleEvent> {
+        self.queue.pop_front()
+    }
+
+    /// Removes all events from the queue.
+    pub fn clear(&mut self) {
+        self.queue.clear();
+    }
+}
@@ -0,0 +1,111 @@
+//! Flipbook (texture sheet) animation of particles. See [`ParticleFlipbook`] docs for more info.
+
+use crate::core::{algebra::Vector2, reflect::prelude::*, visitor::prelude::*};
+
+/// Flipbook animation plays frames of a texture atlas on every particle of a particle system, it is
+/// useful for fire, smoke, explosions and similar effects. Frames of the atlas are arranged in a grid and
+/// go left-to-right, top-to-bottom.
+#[derive(Clone, Debug, Visit, Reflect, PartialEq)]
+pub struct ParticleFlipbook {
+    /// Amount of rows of the atlas.
+    #[reflect(min_value = 1.0, step = 1.0)]
+    pub rows: u32,
+    /// Amount of columns of the atlas.
+    #[reflect(min_value = 1.0, step = 1.0)]
+    pub columns: u32,
+    /// Playback speed in frames per second. Zero means that all the frames are played exactly once
+    /// over lifetime of a particle.
+    #[reflect(min_value = 0.0, step = 1.0)]
+    pub fps: f32,
+    /// If `true`, every particle starts from a random frame.
+    pub random_start_frame: bool,
+    /// If `true`, two adjacent frames will be smoothly blended, otherwise the frames will be switched
+    /// instantly.
+    pub blend_frames: bool,
+}
+
+impl Default for ParticleFlipbook {
+    fn default() -> Self {
+        Self {
+            rows: 4,
+            columns: 4,
+            fps: 0.0,
+            random_start_frame: false,
+            blend_frames: false,
+        }
+    }
+}
+
+/// Texture coordinates of two consecutive frames of a flipbook and a blend factor between them.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FlipbookFrame {
+    /// Top-left texture coordinates of the current frame.
+    pub current: Vector2<f32>,
+    /// Top-left texture coordinates of the next frame.
+    pub next: Vector2<f32>,
+    /// Blend factor between the frames.
+    pub blend: f32,
+}
+
+impl ParticleFlipbook {
+    /// Returns total amount of frames.
+    pub fn frame_count(&self) -> u32 {
+        self.rows.max(1) * self.columns.max(1)
+    }
+
+    /// Returns size of a single frame in texture coordinates.
+    pub fn frame_size(&self) -> Vector2<f32> {
+        Vector2::new(
+            1.0 / self.columns.max(1) as f32,
+            1.0 / self.rows.max(1) as f32,
+        )
+    }
+
+    fn frame_origin(&self, index: u32) -> Vector2<f32> {
+        let columns = self.columns.max(1);
+        let size = self.frame_size();
+        Vector2::new(
+            (index % columns) as f32 * size.x,
+            (index / columns) as f32 * size.y,
+        )
+    }
+
+    /// Calculates frames for a particle with the given lifetime (in seconds), normalized lifetime and
+    /// start frame offset (`[0; 1)` range, used when random start frame is enabled).
+    pub fn frame(
+        &self,
+        lifetime: f32,
+        normalized_lifetime: f32,
+        start_offset: f32,
+    ) -> FlipbookFrame {
+        let count = self.frame_count();
+
+        let mut position = if self.fps > 0.0 {
+            lifetime * self.fps
+        } else {
+            normalized_lifetime * count as f32
+        };
+        if self.random_start_frame {
+            position += start_offset * count as f32;
+        }
+
+        let position = position.max(0.0);
+        let index = position as u32 % count;
+        // Non-looping playback over lifetime must stop at the last frame.
+        let next = if self.fps > 0.0 || self.random_start_frame {
+            (index + 1) % count
+        } else {
+            (index + 1).min(count - 1)
+        };
+
+        FlipbookFrame {
+            current: self.frame_origin(index),
+            next: self.frame_origin(next),
+            blend: if self.blend_frames {
+                position.fract()
+            } else {
+                0.0
+            },
+        }
+    }
+}
@@ -0,0 +1,517 @@
+//! GPU simulation backend of particle systems. See [`ParticleSimulationMode`] docs for more info.
+//!
+//! The backend is stateless: a state of every particle is calculated in closed form from the time since
+//! the start of a particle system, the index of the particle and the parameters of its emitter. This
+//! allows to simulate particles directly in the vertex shader without storing them anywhere, so the
+//! amount of particles is limited only by the fill rate. Every emitter has a fixed pool of particles,
+//! which is enough to keep the spawn rate, and each particle of the pool is respawned periodically.
+//! Functions of this module mirror the shader code (`particle_system_gpu_vs.glsl`), so both must be
+//! changed together.
+//!
+//! The closed form is an approximation of CPU simulation, not an exact copy of it:
+//!
+//! - Random values are produced by a hash function instead of the PRNG of a particle system, so the same
+//!   particle system looks different in both modes.
+//! - Particles are spawned at evenly spaced moments of time, while CPU simulation spawns whole particles
+//!   per update. A slot of the pool is reused only when its period ends, so a particle with short lifetime
+//!   leaves its slot empty until then.
+//! - Motion is integrated as if every update had the same time step as the last one, so the results are
+//!   identical to CPU simulation only with a fixed time step.
+
+use crate::{
+    core::{
+        algebra::{Vector2, Vector3, Vector4},
+        color_gradient::ColorGradient,
+        reflect::prelude::*,
+        visitor::prelude::*,
+    },
+    scene::particle_system::{emitter::Emitter, particle::Particle},
+};
+use std::{
+    ops::Range,
+    sync::atomic::{AtomicBool, Ordering},
+};
+use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
+
+/// Maximum amount of points of color gradient that is supported by GPU simulation.
+pub const MAX_GRADIENT_POINTS: usize = 16;
+
+static GPU_SIMULATION_SUPPORTED: AtomicBool = AtomicBool::new(false);
+
+/// Returns `true` if the renderer is able to simulate particles on GPU. Until then (or if the renderer
+/// failed to create its shader) every particle system is simulated on CPU.
+pub fn is_gpu_simulation_supported() -> bool {
+    GPU_SIMULATION_SUPPORTED.load(Ordering::Relaxed)
+}
+
+pub(crate) fn set_gpu_simulation_supported(supported: bool) {
+    GPU_SIMULATION_SUPPORTED.store(supported, Ordering::Relaxed);
+}
+
+/// Defines where particles of a particle system are simulated.
+#[derive(
+    Copy,
+    Clone,
+    Default,
+    Debug,
+    PartialEq,
+    Eq,
+    Reflect,
+    Visit,
+    AsRefStr,
+    EnumString,
+    EnumVariantNames,
+)]
+#[repr(u32)]
+pub enum ParticleSimulationMode {
+    /// Particles are simulated on CPU, every feature of particle systems is supported.
+    #[default]
+    Cpu = 0,
+    /// Particles are simulated on GPU, which allows to have hundreds of thousands of particles. Only
+    /// basic features are supported: ranges of initial parameters of emitters, shapes of emitters,
+    /// acceleration and color gradient (up to [`MAX_GRADIENT_POINTS`] points) of particle system.
+    /// Particles are not sorted by distance to camera. If a particle system uses anything else (curves,
+    /// bursts, trails, noise, flipbook animation and so on) or the renderer does not support GPU simulation,
+    /// it falls back to CPU simulation. GPU simulation only approximates CPU simulation, see the module docs
+    /// for the differences.
+    Gpu = 1,
+}
+
+/// Parameters of an emitter for GPU simulation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GpuEmitterParams {
+    /// Position of the emitter.
+    pub position: Vector3<f32>,
+    /// Spawn rate of the emitter in particles per second.
+    pub spawn_rate: f32,
+    /// Size of particle pool of the emitter.
+    pub capacity: u32,
+    /// Time after which a particle of the pool is respawned.
+    pub period: f32,
+    /// Whether dead particles are respawned or not.
+    pub resurrect: bool,
+    /// Range of lifetime, `x` - min, `y` - max.
+    pub lifetime: Vector2<f32>,
+    /// Range of size.
+    pub size: Vector2<f32>,
+    /// Range of size modifier.
+    pub size_modifier: Vector2<f32>,
+    /// Range of X velocity.
+    pub x_velocity: Vector2<f32>,
+    /// Range of Y velocity.
+    pub y_velocity: Vector2<f32>,
+    /// Range of Z velocity.
+    pub z_velocity: Vector2<f32>,
+    /// Range of initial rotation.
+    pub rotation: Vector2<f32>,
+    /// Range of rotation speed.
+    pub rotation_speed: Vector2<f32>,
+    /// Kind of the shape of the emitter, it is the same as [`Emitter::id`].
+    pub shape_kind: i32,
+    /// Parameters of the shape of the emitter, depends on the kind of the shape.
+    pub shape_params: Vector4<f32>,
+}
+
+fn range_to_vec(range: Range<f32>) -> Vector2<f32> {
+    Vector2::new(range.start.min(range.end), range.start.max(range.end))
+}
+
+impl GpuEmitterParams {
+    /// Creates parameters of the given emitter. Returns `None` if the emitter uses any feature that is
+    /// not supported by GPU simulation.
+    pub fn new(emitter: &Emitter) -> Option<Self> {
+        if emitter.size_over_lifetime().is_some()
+            || emitter.rotation_speed_over_lifetime().is_some()
+            || emitter.color_over_lifetime_gradient().is_some()
+            || emitter.spawn_rate_over_time().is_some()
+            || !emitter.bursts().is_empty()
+            || emitter.velocity_over_lifetime().is_some()
+            || emitter.force_over_lifetime().is_some()
+            || emitter.trail().is_some()
+        {
+            return None;
+        }
+
+        let (shape_kind, shape_params) = match emitter {
+            Emitter::Cuboid(cuboid) => (
+                emitter.id(),
+                Vector4::new(
+                    cuboid.half_width(),
+                    cuboid.half_height(),
+                    cuboid.half_depth(),
+                    0.0,
+                ),
+            ),
+            Emitter::Sphere(sphere) => (emitter.id(), Vector4::new(sphere.radius(), 0.0, 0.0, 0.0)),
+            Emitter::Cylinder(cylinder) => (
+                emitter.id(),
+                Vector4::new(cylinder.radius(), cylinder.height(), 0.0, 0.0),
+            ),
+            Emitter::Cone(cone) => (
+                emitter.id(),
+                Vector4::new(cone.height(), cone.angle().tan(), 0.0, 0.0),
+            ),
+            Emitter::Torus(torus) => (
+                emitter.id(),
+                Vector4::new(torus.major_radius(), torus.minor_radius(), 0.0, 0.0),
+            ),
+        };
+
+        let spawn_rate = emitter.spawn_rate() as f32;
+        let lifetime = range_to_vec(emitter.life_time_range());
+
+        // The pool must be large enough to hold all the particles spawned during the max lifetime.
+        let mut capacity = (spawn_rate * lifetime.y).ceil().max(0.0) as u32;
+        if let Some(max_particles) = emitter.max_particles() {
+            capacity = capacity.min(max_particles);
+        }
+
+        Some(Self {
+            position: emitter.position(),
+            spawn_rate,
+            capacity,
+            period: if spawn_rate > 0.0 {
+                capacity as f32 / spawn_rate
+            } else {
+                0.0
+            },
+            resurrect: emitter.is_particles_resurrects(),
+            lifetime,
+            size: range_to_vec(emitter.size_range()),
+            size_modifier: range_to_vec(emitter.size_modifier_range()),
+            x_velocity: range_to_vec(emitter.x_velocity_range()),
+            y_velocity: range_to_vec(emitter.y_velocity_range()),
+            z_velocity: range_to_vec(emitter.z_velocity_range()),
+            rotation: range_to_vec(emitter.rotation_range()),
+            rotation_speed: range_to_vec(emitter.rotation_speed_range()),
+            shape_kind,
+            shape_params,
+        })
+    }
+}
+
+/// Integer hash (PCG), it is used as a pseudo-random numbers generator that gives the same results on
+/// CPU and GPU.
+pub fn pcg_hash(value: u32) -> u32 {
+    let state = value.wrapping_mul(747796405).wrapping_add(2891336453);
+    let word = ((state >> ((state >> 28) + 4)) ^ state).wrapping_mul(277803737);
+    (word >> 22) ^ word
+}
+
+/// Returns a seed of a particle with the given index in the given cycle of respawning.
+pub fn particle_seed(system_seed: u32, emitter_index: u32, particle_index: u32, cycle: u32) -> u32 {
+    pcg_hash(
+        system_seed
+            ^ pcg_hash(
+                emitter_index.wrapping_mul(0x9E3779B9) ^ pcg_hash(particle_index ^ pcg_hash(cycle)),
+            ),
+    )
+}
+
+struct GpuRng(u32);
+
+impl GpuRng {
+    fn next(&mut self) -> f32 {
+        self.0 = pcg_hash(self.0);
+        self.0 as f32 / u32::MAX as f32
+    }
+
+    fn range(&mut self, range: Vector2<f32>) -> f32 {
+        range.x + (range.y - range.x) * self.next()
+    }
+}
+
+fn shape_position(params: &GpuEmitterParams, rng: &mut GpuRng) -> Vector3<f32> {
+    let p = params.shape_params;
+    let offset = match params.shape_kind {
+        // Cuboid.
+        1 => Vector3::new(
+            rng.range(Vector2::new(-p.x, p.x)),
+            rng.range(Vector2::new(-p.y, p.y)),
+            rng.range(Vector2::new(-p.z, p.z)),
+        ),
+        // Sphere.
+        2 => {
+            let phi = rng.range(Vector2::new(0.0, std::f32::consts::PI));
+            let theta = rng.range(Vector2::new(0.0, 2.0 * std::f32::consts::PI));
+            let radius = rng.range(Vector2::new(0.0, p.x));
+            Vector3::new(
+                radius * theta.sin() * phi.cos(),
+                radius * theta.sin() * phi.sin(),
+                radius * theta.cos(),
+            )
+        }
+        // Cylinder.
+        3 => {
+            let scale = rng.next();
+            let theta = rng.range(Vector2::new(0.0, 2.0 * std::f32::consts::PI));
+            let z = rng.range(Vector2::new(0.0, p.y));
+            let radius = scale.sqrt() * p.x;
+            Vector3::new(radius * theta.cos(), radius * theta.sin(), z)
+        }
+        // Cone.
+        4 => {
+            let y = rng.next().cbrt() * p.x;
+            let scale = rng.next();
+            let theta = rng.range(Vector2::new(0.0, 2.0 * std::f32::consts::PI));
+            let radius = scale.sqrt() * y * p.y;
+            Vector3::new(radius * theta.cos(), y, radius * theta.sin())
+        }
+        // Torus.
+        5 => {
+            let scale = rng.next();
+            let theta = rng.range(Vector2::new(0.0, 2.0 * std::f32::consts::PI));
+            let phi = rng.range(Vector2::new(0.0, 2.0 * std::f32::consts::PI));
+            let tube_radius = scale.sqrt() * p.y;
+            let radius = p.x + tube_radius * phi.cos();
+            Vector3::new(
+                radius * theta.cos(),
+                tube_radius * phi.sin(),
+                radius * theta.sin(),
+            )
+        }
+        _ => Vector3::default(),
+    };
+    params.position + offset
+}
+
+/// Calculates a state of a particle with the given index at the given time since the start of a particle
+/// system. `dt` is the time step of the particle system, the motion is integrated the same way as CPU
+/// simulation does it with a fixed time step. Returns `None` if the particle is not alive at the given time.
+#[allow(clippy::too_many_arguments)]
+pub fn simulate_particle(
+    params: &GpuEmitterParams,
+    system_seed: u32,
+    emitter_index: u32,
+    particle_index: u32,
+    time: f32,
+    dt: f32,
+    acceleration: Vector3<f32>,
+    gradient: &ColorGradient,
+) -> Option<Particle> {
+    if params.spawn_rate <= 0.0 || params.period <= 0.0 {
+        return None;
+    }
+
+    let local_time = time - particle_index as f32 / params.spawn_rate;
+    if local_time < 0.0 {
+        return None;
+    }
+
+    let cycle = (local_time / params.period).floor();
+    if !params.resurrect && cycle >= 1.0 {
+        return None;
+    }
+    let age = local_time - cycle * params.period;
+
+    let mut rng = GpuRng(particle_seed(
+        system_seed,
+        emitter_index,
+        particle_index,
+        cycle as u32,
+    ));
+
+    // The order must match the shader.
+    let lifetime = rng.range(params.lifetime);
+    let size = rng.range(params.size);
+    let size_modifier = rng.range(params.size_modifier);
+    let velocity = Vector3::new(
+        rng.range(params.x_velocity),
+        rng.range(params.y_velocity),
+        rng.range(params.z_velocity),
+    );
+    let rotation = rng.range(params.rotation);
+    let rotation_speed = rng.range(params.rotation_speed);
+    let position = shape_position(params, &mut rng);
+
+    if age >= lifetime {
+        return None;
+    }
+
+    // CPU simulation adds the acceleration multiplied by squared time step to the velocity and then the
+    // velocity to the position every tick.
+    let ticks = age / dt.max(f32::EPSILON);
+
+    Some(Particle {
+        position: position
+            + velocity.scale(ticks)
+            + acceleration.scale(dt * dt * ticks * (ticks + 1.0) * 0.5),
+        velocity: velocity + acceleration.scale(dt * dt * ticks),
+        size: (size + size_modifier * age).max(0.0),
+        size_modifier,
+        initial_lifetime: lifetime,
+        rotation_speed,
+        rotation: rotation + rotation_speed * age,
+        color: gradient.get_color(age / lifetime),
+        emitter_index,
+        lifetime: age,
+        initial_size: size,
+        initial_rotation_speed: rotation_speed,
+        ..Default::default()
+    })
+}
+
+/// Converts a color gradient to arrays of locations and colors (in sRGB space, the shader interpolates them
+/// the same way as [`ColorGradient::get_color`] does and converts the result to linear space). Returns
+/// amount of points.
+pub fn gradient_to_arrays(
+    gradient: &ColorGradient,
+    locations: &mut [f32; MAX_GRADIENT_POINTS],
+    colors: &mut [Vector4<f32>; MAX_GRADIENT_POINTS],
+) -> usize {
+    let points = gradient.points();
+    if points.is_empty() {
+        locations[0] = 0.0;
+        colors[0] = ColorGradient::STUB_COLOR.as_frgba();
+        return 1;
+    }
+
+    let count = points.len().min(MAX_GRADIENT_POINTS);
+    for (i, point) in points.iter().take(count).enumerate() {
+        locations[i] = point.location();
+        colors[i] = point.color().as_frgba();
+    }
+    count
+}
+
+/// Returns `true` if the gradient can be used by GPU simulation.
+pub fn is_gradient_supported(gradient: &ColorGradient) -> bool {
+    gradient.points().len() <= MAX_GRADIENT_POINTS
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::{
+            algebra::Vector3,
+            color::Color,
+            color_gradient::{ColorGradient, GradientPoint},
+        },
+        scene::{
+            base::BaseBuilder,
+            particle_system::{
+                emitter::{base::BaseEmitterBuilder, cuboid::CuboidEmitterBuilder, Emitter},
+                gpu::{self, GpuEmitterParams},
+                ParticleSystemBuilder,
+            },
+        },
+    };
+
+    // Every range is degenerate, so both simulations have the same initial state of particles.
+    fn make_emitter(resurrect: bool) -> Emitter {
+        CuboidEmitterBuilder::new(
+            BaseEmitterBuilder::new()
+                .with_position(Vector3::new(1.0, 2.0, 3.0))
+                .with_spawn_rate(8)
+                .with_lifetime_range(1.0..1.0)
+                .with_size_range(0.5..0.5)
+                .with_size_modifier_range(0.25..0.25)
+                .with_x_velocity_range(0.01..0.01)
+                .with_y_velocity_range(0.02..0.02)
+                .with_z_velocity_range(-0.01..-0.01)
+                .with_rotation_range(0.5..0.5)
+                .with_rotation_speed_range(1.5..1.5)
+                .resurrect_particles(resurrect),
+        )
+        .with_width(0.0)
+        .with_height(0.0)
+        .with_depth(0.0)
+        .build()
+    }
+
+    fn make_gradient() -> ColorGradient {
+        let mut gradient = ColorGradient::new();
+        gradient.add_point(GradientPoint::new(0.0, Color::opaque(255, 0, 0)));
+        gradient.add_point(GradientPoint::new(1.0, Color::opaque(0, 0, 255)));
+        gradient
+    }
+
+    #[test]
+    fn test_simulate_particle_matches_cpu_simulation() {
+        // Time step and spawn rate are powers of two, so the CPU simulation spawns exactly one particle
+        // per update.
+        let dt = 0.125;
+        let acceleration = Vector3::new(0.0, -9.81, 0.0);
+        let mut particle_system = ParticleSystemBuilder::new(BaseBuilder::new())
+            .with_emitters(vec![make_emitter(true)])
+            .with_acceleration(acceleration)
+            .with_color_over_lifetime_gradient(make_gradient())
+            .build_particle_system();
+        let params = GpuEmitterParams::new(&particle_system.emitters[0]).unwrap();
+
+        // Less than the lifetime, so none of the particles is dead yet.
+        for _ in 0..6 {
+            particle_system.update_simulation(dt);
+        }
+
+        let (time, tick_dt) = particle_system.simulation_time();
+        assert_eq!(tick_dt, dt);
+        assert_eq!(particle_system.particles().len(), 6);
+
+        for (i, cpu) in particle_system.particles().iter().enumerate() {
+            let gpu = gpu::simulate_particle(
+                &params,
+                0,
+                0,
+                i as u32,
+                time,
+                dt,
+                acceleration,
+                particle_system.color_over_lifetime_gradient(),
+            )
+            .unwrap();
+
+            assert!((gpu.lifetime - cpu.lifetime).abs() < 1.0e-5);
+            assert!((gpu.position - cpu.position).norm() < 1.0e-4);
+            assert!((gpu.velocity - cpu.velocity).norm() < 1.0e-4);
+            assert!((gpu.size - cpu.size).abs() < 1.0e-5);
+            assert!((gpu.rotation - cpu.rotation).abs() < 1.0e-5);
+            assert_eq!(gpu.color, cpu.color);
+        }
+    }
+
+    #[test]
+    fn test_simulate_particle_lifetime() {
+        let gradient = make_gradient();
+        let params = GpuEmitterParams::new(&make_emitter(false)).unwrap();
+        assert_eq!(params.capacity, 8);
+        assert_eq!(params.period, 1.0);
+
+        let simulate = |particle_index, time| {
+            gpu::simulate_particle(
+                &params,
+                0,
+                0,
+                particle_index,
+                time,
+                0.125,
+                Vector3::default(),
+                &gradient,
+            )
+        };
+
+        // Not spawned yet.
+        assert!(simulate(4, 0.25).is_none());
+        // Spawned at 0.5 and alive for 1.0.
+        assert!(simulate(4, 0.75).is_some());
+        assert!(simulate(4, 1.75).is_none());
+        // Is not respawned in the next period.
+        assert!(simulate(4, 2.0).is_none());
+
+        let params = GpuEmitterParams {
+            resurrect: true,
+            ..params.clone()
+        };
+        assert!(gpu::simulate_particle(
+            &params,
+            0,
+            0,
+            4,
+            2.0,
+            0.125,
+            Vector3::default(),
+            &gradient
+        )
+        .is_some());
+    }
+}
@@ -0,0 +1,126 @@
+//! Distance-based level of detail for particle systems. See [`ParticleSystemLod`] docs for more info.
+
+use crate::core::{reflect::prelude::*, visitor::prelude::*};
+
+/// Distance-based level of detail of a particle system. Emission rate of the particle system is scaled down
+/// linearly from [`Self::fade_distance`] to [`Self::pause_distance`], and the simulation is paused completely
+/// further than [`Self::pause_distance`]. The distance is measured from the first enabled camera in the scene.
+/// It allows to have lots of effects in dense levels without wasting time on the ones that are far away.
+#[derive(Clone, Debug, Visit, Reflect, PartialEq)]
+pub struct ParticleSystemLod {
+    /// Distance at which emission rate starts to decrease.
+    #[reflect(min_value = 0.0, step = 0.1)]
+    pub fade_distance: f32,
+    /// Distance at which the simulation is paused.
+    #[reflect(min_value = 0.0, step = 0.1)]
+    pub pause_distance: f32,
+    /// Scale of emission rate right before [`Self::pause_distance`].
+    #[reflect(min_value = 0.0, max_value = 1.0, step = 0.05)]
+    pub min_emission_scale: f32,
+}
+
+impl Default for ParticleSystemLod {
+    fn default() -> Self {
+        Self {
+            fade_distance: 30.0,
+            pause_distance: 60.0,
+            min_emission_scale: 0.25,
+        }
+    }
+}
+
+impl ParticleSystemLod {
+    /// Returns scale of emission rate at the given distance, or `None` if the simulation must be paused.
+    pub fn emission_scale(&self, distance: f32) -> Option<f32> {
+        if distance >= self.pause_distance {
+            None
+        } else if distance <= self.fade_distance {
+            Some(1.0)
+        } else {
+            let t = (distance - self.fade_distance) / (self.pause_distance - self.fade_distance);
+            Some(1.0 + (self.min_emission_scale.clamp(0.0, 1.0) - 1.0) * t)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::{
+            algebra::{Vector2, Vector3},
+            pool::Handle,
+        },
+        scene::{
+            base::BaseBuilder,
+            camera::CameraBuilder,
+            graph::Graph,
+            node::Node,
+            particle_system::{
+                emitter::{base::BaseEmitterBuilder, sphere::SphereEmitterBuilder},
+                lod::ParticleSystemLod,
+                ParticleSystem, ParticleSystemBuilder,
+            },
+            transform::TransformBuilder,
+        },
+    };
+
+    #[test]
+    fn test_emission_scale() {
+        let lod = ParticleSystemLod {
+            fade_distance: 10.0,
+            pause_distance: 20.0,
+            min_emission_scale: 0.5,
+        };
+
+        assert_eq!(lod.emission_scale(0.0), Some(1.0));
+        assert_eq!(lod.emission_scale(10.0), Some(1.0));
+        assert_eq!(lod.emission_scale(15.0), Some(0.75));
+        assert!((lod.emission_scale(19.99).unwrap() - 0.5).abs() < 1.0e-3);
+        assert_eq!(lod.emission_scale(20.0), None);
+        assert_eq!(lod.emission_scale(100.0), None);
+
+        // Out of range scale is clamped.
+        let lod = ParticleSystemLod {
+            min_emission_scale: 2.0,
+            ..lod
+        };
+        assert_eq!(lod.emission_scale(15.0), Some(1.0));
+    }
+
+    #[test]
+    fn test_lod_pauses_far_particle_systems() {
+        let mut graph = Graph::new();
+        CameraBuilder::new(BaseBuilder::new()).build(&mut graph);
+
+        let mut add_particle_system = |x: f32| {
+            ParticleSystemBuilder::new(
+                BaseBuilder::new().with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(Vector3::new(x, 0.0, 0.0))
+                        .build(),
+                ),
+            )
+            .with_emitters(vec![SphereEmitterBuilder::new(
+                BaseEmitterBuilder::new().with_spawn_rate(16),
+            )
+            .build()])
+            .with_lod(ParticleSystemLod {
+                fade_distance: 10.0,
+                pause_distance: 20.0,
+                min_emission_scale: 0.5,
+            })
+            .build(&mut graph)
+        };
+        let near = add_particle_system(5.0);
+        let far = add_particle_system(30.0);
+
+        for _ in 0..8 {
+            graph.update(Vector2::new(800.0, 600.0), 0.125, Default::default());
+        }
+
+        let particle_system =
+            |handle: Handle<Node>| graph[handle].cast::<ParticleSystem>().unwrap();
+        assert_eq!(particle_system(near).particles().len(), 16);
+        assert!(particle_system(far).particles().is_empty());
+    }
+}
@@ -3,9 +3,11 @@
 
 use crate::{
     core::{
-        algebra::{Vector2, Vector3},
+        algebra::{Point3, Vector2, Vector3},
+        color::Color,
         color_gradient::ColorGradient,
         math::{aabb::AxisAlignedBoundingBox, TriangleDefinition},
+        numeric_range::RangeExt,
         pool::Handle,
         reflect::prelude::*,
         uuid::{uuid, Uuid},
@@ -21,7 +23,12 @@ use crate::{
         node::{Node, NodeTrait, UpdateContext},
         particle_system::{
             draw::{DrawData, Vertex},
-            emitter::{Emit, Emitter},
+            emitter::{base::ParticleTrail, Emit, Emitter},
+            event::{ParticleEvent, ParticleEventQueue},
+            flipbook::ParticleFlipbook,
+            gpu::{GpuEmitterParams, ParticleSimulationMode},
+            lod::ParticleSystemLod,
+            noise::ParticleNoise,
             particle::Particle,
         },
     },
@@ -31,9 +38,15 @@ use std::{
     fmt::Debug,
     ops::{Deref, DerefMut},
 };
+use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
 
 pub(crate) mod draw;
 pub mod emitter;
+pub mod event;
+pub mod flipbook;
+pub mod gpu;
+pub mod lod;
+pub mod noise;
 pub mod particle;
 
 /// Pseudo-random numbers generator for particle systems.
@@ -66,6 +79,12 @@ impl ParticleSystemRng {
     pub fn reset(&mut self) {
         self.rng = StdRng::seed_from_u64(self.rng_seed);
     }
+
+    /// Returns the seed of PRNG.
+    #[inline]
+    pub fn seed(&self) -> u64 {
+        self.rng_seed
+    }
 }
 
 impl RngCore for ParticleSystemRng {
@@ -105,6 +124,34 @@ impl Visit for ParticleSystemRng {
     }
 }
 
+/// Defines the order in which particles of a particle system are drawn. Alpha-blended particles must be
+/// sorted, otherwise they will flicker because of arbitrary draw order.
+#[derive(
+    Copy,
+    Clone,
+    Default,
+    Debug,
+    PartialEq,
+    Eq,
+    Reflect,
+    Visit,
+    AsRefStr,
+    EnumString,
+    EnumVariantNames,
+)]
+#[repr(u32)]
+pub enum ParticleSortingMode {
+    /// Particles are not sorted, it is the fastest mode which is suitable for additive blending and for
+    /// particles without transparency.
+    None = 0,
+    /// Particles are sorted by distance to camera, the farthest particles are drawn first.
+    #[default]
+    BackToFront = 1,
+    /// Particles are sorted by age, the oldest particles are drawn first, so new particles are drawn on
+    /// top of the old ones.
+    ByAge = 2,
+}
+
 /// Particle system used to create visual effects that consists of many small parts,
 /// this can be smoke, fire, dust, sparks, etc. Particle system optimized to operate
 /// on many small parts, so it is much efficient to use particle system instead of
@@ -191,9 +238,41 @@ pub struct ParticleSystem {
     #[reflect(setter = "set_color_over_lifetime_gradient")]
     color_over_lifetime: InheritableVariable<ColorGradient>,
 
+    #[visit(optional)]
+    #[reflect(setter = "set_noise")]
+    noise: InheritableVariable<Option<ParticleNoise>>,
+
+    #[visit(optional)]
+    #[reflect(setter = "set_flipbook")]
+    flipbook: InheritableVariable<Option<ParticleFlipbook>>,
+
+    #[visit(optional)]
+    #[reflect(setter = "set_sorting_mode")]
+    sorting_mode: InheritableVariable<ParticleSortingMode>,
+
+    #[visit(optional)]
+    #[reflect(setter = "set_simulation_mode")]
+    simulation_mode: InheritableVariable<ParticleSimulationMode>,
+
     #[reflect(setter = "set_soft_boundary_sharpness_factor")]
     soft_boundary_sharpness_factor: InheritableVariable<f32>,
 
+    #[visit(optional)]
+    #[reflect(setter = "set_lod")]
+    lod: InheritableVariable<Option<ParticleSystemLod>>,
+
+    #[visit(optional)]
+    #[reflect(setter = "enable_events")]
+    events_enabled: InheritableVariable<bool>,
+
+    #[visit(optional)]
+    #[reflect(setter = "set_prewarm")]
+    prewarm: InheritableVariable<bool>,
+
+    #[visit(optional)]
+    #[reflect(setter = "set_prewarm_duration", min_value = 0.0)]
+    prewarm_duration: InheritableVariable<f32>,
+
     #[reflect(setter = "play")]
     #[visit(rename = "Enabled")]
     is_playing: InheritableVariable<bool>,
@@ -206,6 +285,26 @@ pub struct ParticleSystem {
 
     #[visit(optional)]
     rng: ParticleSystemRng,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    time: f32,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    tick_dt: f32,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    is_prewarmed: bool,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    emission_scale: f32,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    events: ParticleEventQueue,
 }
 
 impl Deref for ParticleSystem {
@@ -240,12 +339,97 @@ impl ParticleSystem {
         self.acceleration.set_value_and_mark_modified(accel)
     }
 
-    /// Sets new "color curve" that will evaluate color over lifetime.
+    /// Sets new "color curve" that will evaluate color over lifetime. It is used by every emitter that does
+    /// not have its own gradient (see [`emitter::base::BaseEmitter::set_color_over_lifetime_gradient`]).
     pub fn set_color_over_lifetime_gradient(&mut self, gradient: ColorGradient) -> ColorGradient {
         self.color_over_lifetime
             .set_value_and_mark_modified(gradient)
     }
 
+    /// Returns current "color curve" of the particle system.
+    pub fn color_over_lifetime_gradient(&self) -> &ColorGradient {
+        &self.color_over_lifetime
+    }
+
+    /// Sets new noise (turbulence) force field, that will move particles along a swirling velocity field.
+    /// See [`ParticleNoise`] docs for more info.
+    pub fn set_noise(&mut self, noise: Option<ParticleNoise>) -> Option<ParticleNoise> {
+        self.noise.set_value_and_mark_modified(noise)
+    }
+
+    /// Returns current noise force field.
+    pub fn noise(&self) -> Option<&ParticleNoise> {
+        self.noise.as_ref()
+    }
+
+    /// Sets new flipbook (texture sheet) animation of particles, the texture of the particle system must
+    /// be an atlas of frames. See [`ParticleFlipbook`] docs for more info.
+    pub fn set_flipbook(&mut self, flipbook: Option<ParticleFlipbook>) -> Option<ParticleFlipbook> {
+        self.flipbook.set_value_and_mark_modified(flipbook)
+    }
+
+    /// Returns current flipbook animation of particles.
+    pub fn flipbook(&self) -> Option<&ParticleFlipbook> {
+        self.flipbook.as_ref()
+    }
+
+    /// Sets new sorting mode of the particles, that defines the order in which they are drawn. See
+    /// [`ParticleSortingMode`] docs for more info.
+    pub fn set_sorting_mode(&mut self, mode: ParticleSortingMode) -> ParticleSortingMode {
+        self.sorting_mode.set_value_and_mark_modified(mode)
+    }
+
+    /// Returns current sorting mode of the particles.
+    pub fn sorting_mode(&self) -> ParticleSortingMode {
+        *self.sorting_mode
+    }
+
+    /// Sets new simulation mode of the particle system and removes all generated particles. See
+    /// [`ParticleSimulationMode`] docs for more info.
+    pub fn set_simulation_mode(&mut self, mode: ParticleSimulationMode) -> ParticleSimulationMode {
+        self.clear_particles();
+        self.simulation_mode.set_value_and_mark_modified(mode)
+    }
+
+    /// Returns current simulation mode of the particle system.
+    pub fn simulation_mode(&self) -> ParticleSimulationMode {
+        *self.simulation_mode
+    }
+
+    /// Returns parameters of the emitters for GPU simulation. Returns `None` if the particle system is
+    /// simulated on CPU, either because of its simulation mode, because the renderer does not support GPU
+    /// simulation or because it uses a feature that is not supported by GPU simulation.
+    pub fn gpu_emitter_params(&self) -> Option<Vec<GpuEmitterParams>> {
+        if *self.simulation_mode != ParticleSimulationMode::Gpu
+            || !gpu::is_gpu_simulation_supported()
+            || self.noise.is_some()
+            || self.flipbook.is_some()
+            || !gpu::is_gradient_supported(&self.color_over_lifetime)
+        {
+            return None;
+        }
+
+        self.emitters.iter().map(GpuEmitterParams::new).collect()
+    }
+
+    /// Returns `true` if the particle system is simulated on GPU. In this case the particles are not stored
+    /// anywhere and [`Self::particles`] returns an empty slice.
+    pub fn is_gpu_simulated(&self) -> bool {
+        self.gpu_emitter_params().is_some()
+    }
+
+    /// Returns time since the start of the particle system and the time step of its last update. It is used
+    /// by GPU simulation.
+    pub fn simulation_time(&self) -> (f32, f32) {
+        (self.time, self.tick_dt)
+    }
+
+    /// Returns a seed of GPU simulation, it is derived from the seed of the particle system PRNG.
+    pub fn gpu_seed(&self) -> u32 {
+        let seed = self.rng.seed();
+        (seed ^ (seed >> 32)) as u32
+    }
+
     /// Return current soft boundary sharpness factor.
     pub fn soft_boundary_sharpness_factor(&self) -> f32 {
         *self.soft_boundary_sharpness_factor
@@ -271,6 +455,74 @@ impl ParticleSystem {
             .set_value_and_mark_modified(factor)
     }
 
+    /// Sets new distance-based level of detail of the particle system. See [`ParticleSystemLod`] docs for more
+    /// info.
+    pub fn set_lod(&mut self, lod: Option<ParticleSystemLod>) -> Option<ParticleSystemLod> {
+        self.lod.set_value_and_mark_modified(lod)
+    }
+
+    /// Returns current level of detail of the particle system.
+    pub fn lod(&self) -> Option<&ParticleSystemLod> {
+        self.lod.as_ref()
+    }
+
+    /// Enables or disables particle events (spawn and death of particles). Events are disabled by default,
+    /// because they are useless for most of particle systems. Events are not generated if the particle system
+    /// is simulated on GPU. Use [`Self::pop_event`] to fetch the events.
+    pub fn enable_events(&mut self, enabled: bool) -> bool {
+        if !enabled {
+            self.events.clear();
+        }
+        self.events_enabled.set_value_and_mark_modified(enabled)
+    }
+
+    /// Returns `true` if particle events are enabled, `false` - otherwise.
+    pub fn is_events_enabled(&self) -> bool {
+        *self.events_enabled
+    }
+
+    /// Pops a particle event from the queue of the particle system. The queue has limited capacity (see
+    /// [`event::MAX_EVENTS`]), so the events must be fetched every frame, otherwise new events will be lost.
+    ///
+    /// ```rust
+    /// use fyrox::scene::particle_system::{event::ParticleEvent, ParticleSystem};
+    ///
+    /// fn handle_events(particle_system: &mut ParticleSystem) {
+    ///     while let Some(event) = particle_system.pop_event() {
+    ///         if let ParticleEvent::Died { position, .. } = event {
+    ///             println!("A particle has died at {:?}", position);
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn pop_event(&mut self) -> Option<ParticleEvent> {
+        self.events.pop()
+    }
+
+    /// Enables or disables prewarm of the particle system. Prewarmed particle system is simulated forward for
+    /// [`Self::prewarm_duration`] seconds when it starts playing for the first time (or after
+    /// [`Self::clear_particles`]), so looping effects (waterfalls, torches, etc.) appear already in steady
+    /// state instead of visibly filling up.
+    pub fn set_prewarm(&mut self, prewarm: bool) -> bool {
+        self.prewarm.set_value_and_mark_modified(prewarm)
+    }
+
+    /// Returns `true` if the particle system is prewarmed, `false` - otherwise.
+    pub fn is_prewarm_enabled(&self) -> bool {
+        *self.prewarm
+    }
+
+    /// Sets the time (in seconds) for which the particle system will be simulated on prewarm.
+    pub fn set_prewarm_duration(&mut self, duration: f32) -> f32 {
+        self.prewarm_duration
+            .set_value_and_mark_modified(duration.max(0.0))
+    }
+
+    /// Returns the time (in seconds) for which the particle system will be simulated on prewarm.
+    pub fn prewarm_duration(&self) -> f32 {
+        *self.prewarm_duration
+    }
+
     /// Replaces the particles in the particle system with pre-generated set. It could be useful
     /// to create procedural particle effects; when particles cannot be pre-made.
     pub fn set_particles(&mut self, particles: Vec<Particle>) {
@@ -287,9 +539,12 @@ impl ParticleSystem {
     pub fn clear_particles(&mut self) {
         self.particles.clear();
         self.free_particles.clear();
+        self.time = 0.0;
+        self.is_prewarmed = false;
         for emitter in self.emitters.get_value_mut_silent().iter_mut() {
             emitter.alive_particles = 0;
             emitter.spawned_particles = 0;
+            emitter.elapsed_time = 0.0;
         }
     }
 
@@ -301,8 +556,10 @@ impl ParticleSystem {
         draw_data: &mut DrawData,
         camera_pos: &Vector3<f32>,
     ) {
+        let particles = &self.particles;
+
         sorted_particles.clear();
-        for (i, particle) in self.particles.iter().enumerate() {
+        for (i, particle) in particles.iter().enumerate() {
             if particle.alive {
                 let actual_position = particle.position + self.base.global_position();
                 particle
@@ -312,60 +569,73 @@ impl ParticleSystem {
             }
         }
 
-        let particles = &self.particles;
-
-        sorted_particles.sort_by(|a, b| {
-            let particle_a = particles.get(*a as usize).unwrap();
-            let particle_b = particles.get(*b as usize).unwrap();
-
-            // Reverse ordering because we want to sort back-to-front.
-            if particle_a.sqr_distance_to_camera < particle_b.sqr_distance_to_camera {
-                Ordering::Greater
-            } else if particle_a.sqr_distance_to_camera > particle_b.sqr_distance_to_camera {
-                Ordering::Less
-            } else {
-                Ordering::Equal
-            }
-        });
+        match *self.sorting_mode {
+            ParticleSortingMode::None => (),
+            ParticleSortingMode::BackToFront => sorted_particles.sort_by(|a, b| {
+                let particle_a = particles.get(*a as usize).unwrap();
+                let particle_b = particles.get(*b as usize).unwrap();
+
+                // Reverse ordering because we want to sort back-to-front.
+                if particle_a.sqr_distance_to_camera < particle_b.sqr_distance_to_camera {
+                    Ordering::Greater
+                } else if particle_a.sqr_distance_to_camera > particle_b.sqr_distance_to_camera {
+                    Ordering::Less
+                } else {
+                    Ordering::Equal
+                }
+            }),
+            ParticleSortingMode::ByAge => sorted_particles.sort_by(|a, b| {
+                let particle_a = particles.get(*a as usize).unwrap();
+                let particle_b = particles.get(*b as usize).unwrap();
+
+                // Reverse ordering because we want to draw the oldest particles first.
+                particle_b.lifetime.total_cmp(&particle_a.lifetime)
+            }),
+        }
 
         draw_data.clear();
 
         for (i, particle_index) in sorted_particles.iter().enumerate() {
-            let particle = self.particles.get(*particle_index as usize).unwrap();
+            let particle = particles.get(*particle_index as usize).unwrap();
 
             let linear_color = particle.color.srgb_to_linear();
 
-            draw_data.vertices.push(Vertex {
-                position: particle.position,
-                tex_coord: Vector2::default(),
-                size: particle.size,
-                rotation: particle.rotation,
-                color: linear_color,
-            });
-
-            draw_data.vertices.push(Vertex {
-                position: particle.position,
-                tex_coord: Vector2::new(1.0, 0.0),
-                size: particle.size,
-                rotation: particle.rotation,
-                color: linear_color,
+            let frame = self.flipbook.as_ref().map(|flipbook| {
+                (
+                    flipbook.frame(
+                        particle.lifetime,
+                        particle.lifetime / particle.initial_lifetime,
+                        particle.frame_offset,
+                    ),
+                    flipbook.frame_size(),
+                )
             });
 
-            draw_data.vertices.push(Vertex {
-                position: particle.position,
-                tex_coord: Vector2::new(1.0, 1.0),
-                size: particle.size,
-                rotation: particle.rotation,
-                color: linear_color,
-            });
+            for tex_coord in [
+                Vector2::new(0.0, 0.0),
+                Vector2::new(1.0, 0.0),
+                Vector2::new(1.0, 1.0),
+                Vector2::new(0.0, 1.0),
+            ] {
+                let (frame_tex_coord, next_frame_tex_coord, frame_blend) = match frame {
+                    Some((frame, frame_size)) => {
+                        let offset = tex_coord.component_mul(&frame_size);
+                        (frame.current + offset, frame.next + offset, frame.blend)
+                    }
+                    None => (tex_coord, tex_coord, 0.0),
+                };
 
-            draw_data.vertices.push(Vertex {
-                position: particle.position,
-                tex_coord: Vector2::new(0.0, 1.0),
-                size: particle.size,
-                rotation: particle.rotation,
-                color: linear_color,
-            });
+                draw_data.vertices.push(Vertex {
+                    position: particle.position,
+                    tex_coord,
+                    size: particle.size,
+                    rotation: particle.rotation,
+                    color: linear_color,
+                    frame_tex_coord,
+                    next_frame_tex_coord,
+                    frame_blend,
+                });
+            }
 
             let base_index = (i * 4) as u32;
 
@@ -380,6 +650,85 @@ impl ParticleSystem {
                 base_index + 3,
             ]));
         }
+
+        // Trails are built after the particles, because indices of the particle quads are implicit.
+        for particle_index in sorted_particles.iter() {
+            let particle = &particles[*particle_index as usize];
+            if let Some(trail) = self
+                .emitters
+                .get(particle.emitter_index as usize)
+                .and_then(|emitter| emitter.trail())
+            {
+                self.generate_trail(particle, trail, draw_data, camera_pos);
+            }
+        }
+    }
+
+    fn generate_trail(
+        &self,
+        particle: &Particle,
+        trail: &ParticleTrail,
+        draw_data: &mut DrawData,
+        camera_pos: &Vector3<f32>,
+    ) {
+        // Head of the trail is the particle itself, then goes the position history from the newest point.
+        let points = std::iter::once(particle.position)
+            .chain(particle.trail.iter().rev().cloned())
+            .collect::<Vec<_>>();
+        if points.len() < 2 {
+            return;
+        }
+
+        let global_position = self.base.global_position();
+        let last = points.len() - 1;
+        let base_index = draw_data.vertices.len() as u32;
+
+        for (j, point) in points.iter().enumerate() {
+            let t = j as f32 / last as f32;
+
+            let tangent = points[j.saturating_sub(1)] - points[(j + 1).min(last)];
+            let to_camera = camera_pos - (point + global_position);
+            let side = tangent
+                .cross(&to_camera)
+                .try_normalize(f32::EPSILON)
+                .unwrap_or_default()
+                .scale(trail.width_at(particle.size, t) * 0.5);
+
+            let color = Color::from(
+                particle
+                    .color
+                    .as_frgba()
+                    .component_mul(&trail.color_over_trail.get_color(t).as_frgba()),
+            )
+            .srgb_to_linear();
+
+            // Zero size disables billboarding in the shader, so the vertices stay where they are.
+            for (position, tex_coord) in [
+                (point + side, Vector2::new(t, 0.0)),
+                (point - side, Vector2::new(t, 1.0)),
+            ] {
+                draw_data.vertices.push(Vertex {
+                    position,
+                    tex_coord,
+                    size: 0.0,
+                    rotation: 0.0,
+                    color,
+                    frame_tex_coord: tex_coord,
+                    next_frame_tex_coord: tex_coord,
+                    frame_blend: 0.0,
+                });
+            }
+
+            if j < last {
+                let index = base_index + (j * 2) as u32;
+                draw_data
+                    .triangles
+                    .push(TriangleDefinition([index, index + 1, index + 3]));
+                draw_data
+                    .triangles
+                    .push(TriangleDefinition([index, index + 3, index + 2]));
+            }
+        }
     }
 
     /// Sets new texture for particle system.
@@ -398,10 +747,30 @@ impl ParticleSystem {
     }
 
     fn tick(&mut self, dt: f32) {
+        self.tick_dt = dt;
+
+        if self.is_gpu_simulated() {
+            // Particles are calculated from the time in the shader, so there is nothing to simulate here.
+            if !self.particles.is_empty() {
+                self.particles.clear();
+                self.free_particles.clear();
+            }
+            self.time += dt;
+            return;
+        }
+
         for emitter in self.emitters.get_value_mut_silent().iter_mut() {
-            emitter.tick(dt);
+            emitter.tick(dt, self.emission_scale, &mut self.rng);
         }
 
+        let events_enabled = *self.events_enabled;
+        let global_transform = self.global_transform();
+        let world_position = |position: Vector3<f32>| {
+            global_transform
+                .transform_point(&Point3::from(position))
+                .coords
+        };
+
         for (i, emitter) in self.emitters.get_value_mut_silent().iter_mut().enumerate() {
             for _ in 0..emitter.particles_to_spawn {
                 let mut particle = Particle {
@@ -410,6 +779,19 @@ impl ParticleSystem {
                 };
                 emitter.alive_particles += 1;
                 emitter.emit(&mut particle, &mut self.rng);
+                if self
+                    .flipbook
+                    .as_ref()
+                    .is_some_and(|flipbook| flipbook.random_start_frame)
+                {
+                    particle.frame_offset = (0.0..1.0).random(&mut self.rng);
+                }
+                if events_enabled {
+                    self.events.push(ParticleEvent::Spawned {
+                        emitter_index: particle.emitter_index,
+                        position: world_position(particle.position),
+                    });
+                }
                 if let Some(free_index) = self.free_particles.pop() {
                     self.particles[free_index as usize] = particle;
                 } else {
@@ -420,6 +802,8 @@ impl ParticleSystem {
 
         let acceleration_offset = self.acceleration.scale(dt * dt);
 
+        self.time += dt;
+
         for (i, particle) in self.particles.iter_mut().enumerate() {
             if particle.alive {
                 particle.lifetime += dt;
@@ -434,17 +818,44 @@ impl ParticleSystem {
                     }
                     particle.alive = false;
                     particle.lifetime = particle.initial_lifetime;
+                    if events_enabled {
+                        self.events.push(ParticleEvent::Died {
+                            emitter_index: particle.emitter_index,
+                            position: world_position(particle.position),
+                        });
+                    }
                 } else {
+                    let k = particle.lifetime / particle.initial_lifetime;
+
+                    let emitter = self.emitters.get(particle.emitter_index as usize);
+
                     particle.velocity += acceleration_offset;
+                    if let Some(emitter) = emitter {
+                        emitter.apply_motion(particle, k, dt);
+                    }
                     particle.position += particle.velocity;
-                    particle.size += particle.size_modifier * dt;
-                    if particle.size < 0.0 {
-                        particle.size = 0.0;
+                    if let Some(noise) = self.noise.as_ref() {
+                        particle.position +=
+                            noise.velocity_at(particle.position, self.time).scale(dt);
+                    }
+                    if let Some(trail) = emitter.and_then(|emitter| emitter.trail()) {
+                        trail.record(particle);
+                    }
+
+                    let size_applied =
+                        emitter.is_some_and(|emitter| emitter.apply_curves(particle, k));
+                    if !size_applied {
+                        particle.size += particle.size_modifier * dt;
+                        if particle.size < 0.0 {
+                            particle.size = 0.0;
+                        }
                     }
                     particle.rotation += particle.rotation_speed * dt;
 
-                    let k = particle.lifetime / particle.initial_lifetime;
-                    particle.color = self.color_over_lifetime.get_color(k);
+                    particle.color = emitter
+                        .and_then(|emitter| emitter.color_over_lifetime_gradient())
+                        .unwrap_or(&self.color_over_lifetime)
+                        .get_color(k);
                 }
             }
         }
@@ -457,6 +868,29 @@ impl ParticleSystem {
         self.rng.reset();
         self.clear_particles();
 
+        self.simulate(dt, time);
+
+        // The state is set explicitly, it must not be changed by prewarm.
+        self.is_prewarmed = true;
+    }
+
+    /// Advances the simulation by the given time step, prewarming the particle system if needed. Does nothing
+    /// if the particle system is paused. It is called automatically by the engine, but could also be used to
+    /// drive the simulation manually (for example, to preview the particle system with custom time scale).
+    pub fn update_simulation(&mut self, dt: f32) {
+        if *self.is_playing {
+            if !self.is_prewarmed {
+                self.is_prewarmed = true;
+                if *self.prewarm && dt > 0.0 {
+                    self.simulate(dt, *self.prewarm_duration);
+                }
+            }
+
+            self.tick(dt);
+        }
+    }
+
+    fn simulate(&mut self, dt: f32, time: f32) {
         let mut t = 0.0;
         while t < time {
             self.tick(dt);
@@ -488,11 +922,18 @@ impl NodeTrait for ParticleSystem {
     }
 
     fn update(&mut self, context: &mut UpdateContext) {
-        let dt = context.dt;
-
-        if *self.is_playing {
-            self.tick(dt);
+        self.emission_scale = 1.0;
+        if let (Some(lod), Some(observer_position)) = (self.lod.as_ref(), context.observer_position)
+        {
+            let distance = observer_position.metric_distance(&self.global_position());
+            match lod.emission_scale(distance) {
+                Some(scale) => self.emission_scale = scale,
+                // Too far away, the simulation is paused.
+                None => return,
+            }
         }
+
+        self.update_simulation(context.dt);
     }
 }
 
@@ -505,7 +946,15 @@ pub struct ParticleSystemBuilder {
     acceleration: Vector3<f32>,
     particles: Vec<Particle>,
     color_over_lifetime: ColorGradient,
+    noise: Option<ParticleNoise>,
+    flipbook: Option<ParticleFlipbook>,
+    sorting_mode: ParticleSortingMode,
+    simulation_mode: ParticleSimulationMode,
+    lod: Option<ParticleSystemLod>,
+    events_enabled: bool,
     soft_boundary_sharpness_factor: f32,
+    prewarm: bool,
+    prewarm_duration: f32,
     is_playing: bool,
     rng: ParticleSystemRng,
 }
@@ -520,7 +969,15 @@ impl ParticleSystemBuilder {
             particles: Default::default(),
             acceleration: Vector3::new(0.0, -9.81, 0.0),
             color_over_lifetime: Default::default(),
+            noise: None,
+            flipbook: None,
+            sorting_mode: Default::default(),
+            simulation_mode: Default::default(),
+            lod: None,
+            events_enabled: false,
             soft_boundary_sharpness_factor: 2.5,
+            prewarm: false,
+            prewarm_duration: 5.0,
             is_playing: true,
             rng: ParticleSystemRng::default(),
         }
@@ -562,6 +1019,30 @@ impl ParticleSystemBuilder {
         self
     }
 
+    /// Sets desired noise (turbulence) force field for particle system.
+    pub fn with_noise(mut self, noise: ParticleNoise) -> Self {
+        self.noise = Some(noise);
+        self
+    }
+
+    /// Sets desired flipbook (texture sheet) animation of particles.
+    pub fn with_flipbook(mut self, flipbook: ParticleFlipbook) -> Self {
+        self.flipbook = Some(flipbook);
+        self
+    }
+
+    /// Sets desired sorting mode of particles.
+    pub fn with_sorting_mode(mut self, mode: ParticleSortingMode) -> Self {
+        self.sorting_mode = mode;
+        self
+    }
+
+    /// Sets desired simulation mode of particle system.
+    pub fn with_simulation_mode(mut self, mode: ParticleSimulationMode) -> Self {
+        self.simulation_mode = mode;
+        self
+    }
+
     /// Sets an initial set of particles that not belongs to any emitter. This method
     /// could be useful if you need a custom position/velocity/etc. of each particle.
     pub fn with_particles(mut self, particles: Vec<Particle>) -> Self {
@@ -569,6 +1050,26 @@ impl ParticleSystemBuilder {
         self
     }
 
+    /// Sets desired distance-based level of detail of particle system.
+    pub fn with_lod(mut self, lod: ParticleSystemLod) -> Self {
+        self.lod = Some(lod);
+        self
+    }
+
+    /// Enables or disables particle events. See [`ParticleSystem::enable_events`] docs for more info.
+    pub fn with_events_enabled(mut self, enabled: bool) -> Self {
+        self.events_enabled = enabled;
+        self
+    }
+
+    /// Enables prewarm of particle system and sets its duration (in seconds). See
+    /// [`ParticleSystem::set_prewarm`] docs for more info.
+    pub fn with_prewarm(mut self, duration: f32) -> Self {
+        self.prewarm = true;
+        self.prewarm_duration = duration.max(0.0);
+        self
+    }
+
     /// Sets initial particle system state.
     pub fn with_playing(mut self, enabled: bool) -> Self {
         self.is_playing = enabled;
@@ -590,9 +1091,22 @@ impl ParticleSystemBuilder {
             texture: self.texture.into(),
             acceleration: self.acceleration.into(),
             color_over_lifetime: self.color_over_lifetime.into(),
+            noise: self.noise.into(),
+            flipbook: self.flipbook.into(),
+            sorting_mode: self.sorting_mode.into(),
+            simulation_mode: self.simulation_mode.into(),
             soft_boundary_sharpness_factor: self.soft_boundary_sharpness_factor.into(),
+            lod: self.lod.into(),
+            events_enabled: self.events_enabled.into(),
+            prewarm: self.prewarm.into(),
+            prewarm_duration: self.prewarm_duration.into(),
             is_playing: self.is_playing.into(),
             rng: self.rng,
+            time: 0.0,
+            tick_dt: 1.0 / 60.0,
+            is_prewarmed: false,
+            emission_scale: 1.0,
+            events: Default::default(),
         }
     }
 
@@ -606,3 +1120,111 @@ impl ParticleSystemBuilder {
         graph.add_node(self.build_node())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::algebra::Vector3,
+        scene::{
+            base::BaseBuilder,
+            particle_system::{
+                draw::DrawData,
+                emitter::{base::BaseEmitterBuilder, cuboid::CuboidEmitterBuilder},
+                event::ParticleEvent,
+                particle::Particle,
+                ParticleSortingMode, ParticleSystemBuilder,
+            },
+        },
+    };
+
+    #[test]
+    fn test_sorting_modes() {
+        let make_particle = |z: f32, lifetime: f32| Particle {
+            lifetime,
+            ..Particle::default().with_position(Vector3::new(0.0, 0.0, z))
+        };
+        // The youngest particle is the farthest one.
+        let particles = vec![
+            make_particle(2.0, 0.5),
+            make_particle(1.0, 1.5),
+            make_particle(3.0, 0.25),
+            make_particle(0.5, 1.0),
+        ];
+
+        let sorted = |mode| {
+            let particle_system = ParticleSystemBuilder::new(BaseBuilder::new())
+                .with_particles(particles.clone())
+                .with_sorting_mode(mode)
+                .build_particle_system();
+            let mut sorted_particles = Vec::new();
+            particle_system.generate_draw_data(
+                &mut sorted_particles,
+                &mut DrawData::default(),
+                &Vector3::default(),
+            );
+            sorted_particles
+        };
+
+        assert_eq!(sorted(ParticleSortingMode::None), vec![0, 1, 2, 3]);
+        assert_eq!(sorted(ParticleSortingMode::BackToFront), vec![2, 0, 1, 3]);
+        assert_eq!(sorted(ParticleSortingMode::ByAge), vec![1, 3, 0, 2]);
+    }
+
+    #[test]
+    fn test_particle_events() {
+        let position = Vector3::new(1.0, 2.0, 3.0);
+        let make_particle_system = |events_enabled| {
+            ParticleSystemBuilder::new(BaseBuilder::new())
+                .with_emitters(vec![CuboidEmitterBuilder::new(
+                    BaseEmitterBuilder::new()
+                        .with_position(position)
+                        .with_spawn_rate(8)
+                        .with_lifetime_range(0.5..0.5)
+                        .with_x_velocity_range(0.0..0.0)
+                        .with_y_velocity_range(0.0..0.0)
+                        .with_z_velocity_range(0.0..0.0),
+                )
+                .with_width(0.0)
+                .with_height(0.0)
+                .with_depth(0.0)
+                .build()])
+                .with_acceleration(Vector3::default())
+                .with_events_enabled(events_enabled)
+                .build_particle_system()
+        };
+
+        let mut particle_system = make_particle_system(true);
+        // One particle is spawned per update, the first one dies on the fourth update.
+        for _ in 0..4 {
+            particle_system.update_simulation(0.125);
+        }
+
+        let spawned = ParticleEvent::Spawned {
+            emitter_index: 0,
+            position,
+        };
+        let mut events = Vec::new();
+        while let Some(event) = particle_system.pop_event() {
+            events.push(event);
+        }
+        assert_eq!(
+            events,
+            vec![
+                spawned.clone(),
+                spawned.clone(),
+                spawned.clone(),
+                spawned,
+                ParticleEvent::Died {
+                    emitter_index: 0,
+                    position,
+                },
+            ]
+        );
+
+        let mut particle_system = make_particle_system(false);
+        for _ in 0..4 {
+            particle_system.update_simulation(0.125);
+        }
+        assert!(particle_system.pop_event().is_none());
+    }
+}
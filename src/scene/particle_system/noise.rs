@@ -0,0 +1,113 @@
+//! Noise (turbulence) force field for particle systems. See [`ParticleNoise`] docs for more info.
+
+use crate::core::{algebra::Vector3, reflect::prelude::*, visitor::prelude::*};
+
+/// Offsets of the channels of the potential field, they make the channels uncorrelated.
+const CHANNEL_OFFSETS: [Vector3<f32>; 3] = [
+    Vector3::new(0.0, 0.0, 0.0),
+    Vector3::new(31.416, -47.853, 12.793),
+    Vector3::new(-19.287, 23.518, 71.113),
+];
+
+/// Step of finite differences used to calculate the curl of the potential field (in noise space).
+const EPSILON: f32 = 0.01;
+
+/// Turbulence force field that moves particles along a curl noise velocity field. Curl noise is
+/// divergence-free, so particles swirl around without clumping in some places, which is good for
+/// smoke, fire, magic effects and so on.
+#[derive(Clone, Debug, Visit, Reflect, PartialEq)]
+pub struct ParticleNoise {
+    /// Maximum speed (in units per second) that the field adds to particles.
+    #[reflect(min_value = 0.0, step = 0.1)]
+    pub strength: f32,
+    /// Spatial frequency of the field, the higher the value, the smaller the swirls.
+    #[reflect(min_value = 0.0, step = 0.1)]
+    pub frequency: f32,
+    /// Speed (in noise space units per second) at which the field moves, it makes the motion of
+    /// particles change over time.
+    pub scroll_speed: Vector3<f32>,
+}
+
+impl Default for ParticleNoise {
+    fn default() -> Self {
+        Self {
+            strength: 1.0,
+            frequency: 1.0,
+            scroll_speed: Vector3::new(0.0, 0.5, 0.0),
+        }
+    }
+}
+
+impl ParticleNoise {
+    /// Returns velocity of the field at the given position (in local coordinates of a particle
+    /// system) at the given time (in seconds).
+    pub fn velocity_at(&self, position: Vector3<f32>, time: f32) -> Vector3<f32> {
+        let p = position.scale(self.frequency) + self.scroll_speed.scale(time);
+
+        let dx = Vector3::new(EPSILON, 0.0, 0.0);
+        let dy = Vector3::new(0.0, EPSILON, 0.0);
+        let dz = Vector3::new(0.0, 0.0, EPSILON);
+
+        let ddx = potential(p + dx) - potential(p - dx);
+        let ddy = potential(p + dy) - potential(p - dy);
+        let ddz = potential(p + dz) - potential(p - dz);
+
+        let curl = Vector3::new(ddy.z - ddz.y, ddz.x - ddx.z, ddx.y - ddy.x);
+
+        curl.scale(self.strength / (2.0 * EPSILON))
+    }
+}
+
+fn potential(p: Vector3<f32>) -> Vector3<f32> {
+    Vector3::new(
+        value_noise(p + CHANNEL_OFFSETS[0]),
+        value_noise(p + CHANNEL_OFFSETS[1]),
+        value_noise(p + CHANNEL_OFFSETS[2]),
+    )
+}
+
+fn hash(x: i32, y: i32, z: i32) -> f32 {
+    let mut h = (x as u32)
+        .wrapping_mul(0x8da6b343)
+        .wrapping_add((y as u32).wrapping_mul(0xd8163841))
+        .wrapping_add((z as u32).wrapping_mul(0xcb1ab31f));
+    h ^= h >> 13;
+    h = h.wrapping_mul(0x5bd1e995);
+    h ^= h >> 15;
+    // Map to [-1; 1] range.
+    (h as f32 / u32::MAX as f32) * 2.0 - 1.0
+}
+
+fn smoothstep(t: f32) -> f32 {
+    t * t * (3.0 - 2.0 * t)
+}
+
+fn lerp(a: f32, b: f32, t: f32) -> f32 {
+    a + (b - a) * t
+}
+
+/// Smooth 3D value noise in `[-1; 1]` range.
+fn value_noise(p: Vector3<f32>) -> f32 {
+    let (fx, fy, fz) = (p.x.floor(), p.y.floor(), p.z.floor());
+    let (x, y, z) = (fx as i32, fy as i32, fz as i32);
+    let (tx, ty, tz) = (
+        smoothstep(p.x - fx),
+        smoothstep(p.y - fy),
+        smoothstep(p.z - fz),
+    );
+
+    let c000 = hash(x, y, z);
+    let c100 = hash(x + 1, y, z);
+    let c010 = hash(x, y + 1, z);
+    let c110 = hash(x + 1, y + 1, z);
+    let c001 = hash(x, y, z + 1);
+    let c101 = hash(x + 1, y, z + 1);
+    let c011 = hash(x, y + 1, z + 1);
+    let c111 = hash(x + 1, y + 1, z + 1);
+
+    lerp(
+        lerp(lerp(c000, c100, tx), lerp(c010, c110, tx), ty),
+        lerp(lerp(c001, c101, tx), lerp(c011, c111, tx), ty),
+        tz,
+    )
+}
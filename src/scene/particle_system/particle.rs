@@ -33,6 +33,19 @@ pub struct Particle {
     pub(super) lifetime: f32,
     #[visit(skip)]
     pub(super) sqr_distance_to_camera: Cell<f32>,
+    /// Size at the moment when particle was created, it is used by size-over-lifetime curve.
+    #[visit(optional)]
+    pub(super) initial_size: f32,
+    /// Rotation speed at the moment when particle was created, it is used by rotation-speed-over-lifetime
+    /// curve.
+    #[visit(optional)]
+    pub(super) initial_rotation_speed: f32,
+    /// Offset of the first frame of flipbook animation in `[0; 1)` range.
+    #[visit(optional)]
+    pub(super) frame_offset: f32,
+    /// Position history of particle, it is used to build trails.
+    #[visit(skip)]
+    pub(super) trail: Vec<Vector3<f32>>,
 }
 
 impl Default for Particle {
@@ -50,6 +63,10 @@ impl Default for Particle {
             emitter_index: 0,
             color: Color::WHITE,
             sqr_distance_to_camera: Cell::new(0.0),
+            initial_size: 1.0,
+            initial_rotation_speed: 0.0,
+            frame_offset: 0.0,
+            trail: Default::default(),
         }
     }
 }
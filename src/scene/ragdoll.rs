@@ -57,6 +57,7 @@ impl Reflect for Limb {
                 max_value: None,
                 step: None,
                 precision: None,
+                tag: "",
                 doc: "",
             },
             FieldInfo {
@@ -73,6 +74,7 @@ impl Reflect for Limb {
                 max_value: None,
                 step: None,
                 precision: None,
+                tag: "",
                 doc: "",
             },
             FieldInfo {
@@ -89,6 +91,7 @@ impl Reflect for Limb {
                 max_value: None,
                 step: None,
                 precision: None,
+                tag: "",
                 doc: "",
             },
         ])
@@ -6,12 +6,16 @@ use crate::{
         pool::Handle,
         visitor::prelude::*,
     },
-    scene::{node::Node, sound::Sound},
+    scene::{
+        node::Node,
+        sound::{reverb_zone::ReverbZoneMixer, Sound},
+    },
 };
 use fxhash::FxHashSet;
 use fyrox_sound::{
     bus::AudioBusGraph,
     context::DistanceModel,
+    music::MusicPlayer,
     renderer::Renderer,
     source::{SoundSource, SoundSourceBuilder, Status},
 };
@@ -22,6 +26,10 @@ use std::{sync::MutexGuard, time::Duration};
 pub struct SoundContext {
     #[visit(optional)]
     pub(crate) native: fyrox_sound::context::SoundContext,
+    #[visit(skip)]
+    pub(crate) reverb_zones: ReverbZoneMixer,
+    #[visit(skip)]
+    music_player: MusicPlayer,
 }
 
 /// Proxy for guarded access to the sound context.
@@ -104,7 +112,11 @@ impl Default for SoundContext {
         // There's no need to serialize native sources, because they'll be re-created automatically.
         state.serialization_options.skip_sources = true;
         drop(state);
-        Self { native }
+        Self {
+            native,
+            reverb_zones: Default::default(),
+            music_player: Default::default(),
+        }
     }
 }
 
@@ -117,9 +129,58 @@ impl SoundContext {
     pub fn deep_clone(&self) -> Self {
         Self {
             native: self.native.deep_clone(),
+            reverb_zones: Default::default(),
+            music_player: Default::default(),
         }
     }
 
+    /// Returns a reference to the music player of the context. See [`MusicPlayer`] docs for more info.
+    pub fn music_player(&self) -> &MusicPlayer {
+        &self.music_player
+    }
+
+    /// Returns a reference to the music player of the context. It could be used to play music from
+    /// scripts or game code. See [`MusicPlayer`] docs for more info.
+    pub fn music_player_mut(&mut self) -> &mut MusicPlayer {
+        &mut self.music_player
+    }
+
+    pub(crate) fn update_music_player(&mut self, dt: f32) {
+        let mut state = self.native.state();
+        self.music_player.update(&mut state, dt);
+    }
+
+    /// Smoothly activates a mixer snapshot with the given name, for example to duck music while a
+    /// character speaks. See [`fyrox_sound::mixer::Mixer`] docs for more info.
+    pub fn activate_snapshot(&mut self, name: &str, fade_time: f32) {
+        self.native
+            .state()
+            .bus_graph_mut()
+            .mixer_mut()
+            .activate_snapshot(name, fade_time);
+    }
+
+    /// Smoothly deactivates a mixer snapshot with the given name. See [`fyrox_sound::mixer::Mixer`]
+    /// docs for more info.
+    pub fn deactivate_snapshot(&mut self, name: &str, fade_time: f32) {
+        self.native
+            .state()
+            .bus_graph_mut()
+            .mixer_mut()
+            .deactivate_snapshot(name, fade_time);
+    }
+
+    pub(crate) fn update_mixer(&mut self, dt: f32) {
+        self.native.state().bus_graph_mut().update_mixer(dt);
+    }
+
+    /// Blends the influences of every reverb zone gathered during the synchronization step and
+    /// applies the result to the reverb effects of the respective audio buses.
+    pub(crate) fn apply_reverb_zones(&mut self) {
+        let mut state = self.native.state();
+        self.reverb_zones.apply(state.bus_graph_mut());
+    }
+
     /// Returns locked inner state of the sound context.
     pub fn state(&self) -> SoundContextGuard {
         SoundContextGuard {
@@ -145,6 +206,13 @@ impl SoundContext {
         }
     }
 
+    pub(crate) fn set_sound_occlusion(&self, sound: &Sound) {
+        if let Some(source) = self.native.state().try_get_source_mut(sound.native.get()) {
+            let (gain, fc) = sound.occlusion.filter_parameters(sound.occlusion_factor);
+            source.set_occlusion(gain, fc);
+        }
+    }
+
     pub(crate) fn sync_with_sound(&self, sound: &mut Sound) {
         if let Some(source) = self.native.state().try_get_source_mut(sound.native.get()) {
             // Sync back.
@@ -216,6 +284,12 @@ impl SoundContext {
             sound.audio_bus.try_sync_model(|audio_bus| {
                 source.set_bus(audio_bus);
             });
+            sound.effects.try_sync_model(|effects| {
+                source.set_effects(effects);
+            });
+            sound.distance_curve.try_sync_model(|curve| {
+                source.set_distance_curve(curve);
+            });
         } else {
             match SoundSourceBuilder::new()
                 .with_gain(sound.gain())
@@ -230,6 +304,8 @@ impl SoundContext {
                 .with_max_distance(sound.max_distance())
                 .with_bus(sound.audio_bus())
                 .with_rolloff_factor(sound.rolloff_factor())
+                .with_effects(sound.effects().to_vec())
+                .with_distance_curve(sound.distance_curve().cloned())
                 .build()
             {
                 Ok(source) => {
@@ -3,6 +3,7 @@
 use crate::{
     core::{
         algebra::Matrix4,
+        curve::Curve,
         math::{aabb::AxisAlignedBoundingBox, m4x4_approx_eq},
         pool::Handle,
         reflect::prelude::*,
@@ -32,11 +33,13 @@ pub use fyrox_sound::{
     engine::SoundEngine,
     error::SoundError,
     hrtf::HrirSphere,
+    mixer::{BusModifier, BusSnapshot, Mixer},
+    music::{MusicPlayer, MusicTrack},
     renderer::{hrtf::*, Renderer},
-    source::Status,
+    source::{LoopRegion, Status},
 };
 
-use crate::scene::Scene;
+use crate::scene::{sound::occlusion::SoundOcclusion, Scene};
 use fyrox_resource::ResourceStateRef;
 use fyrox_sound::source::SoundSource;
 use std::{
@@ -47,6 +50,8 @@ use std::{
 
 pub mod context;
 pub mod listener;
+pub mod occlusion;
+pub mod reverb_zone;
 
 /// Sound source.
 #[derive(Visit, Reflect, Debug)]
@@ -102,6 +107,32 @@ pub struct Sound {
     )]
     audio_bus: InheritableVariable<String>,
 
+    #[visit(optional)]
+    #[reflect(
+        setter = "set_effects",
+        description = "A chain of effects that will be applied to the sound before it is mixed into the audio bus."
+    )]
+    effects: InheritableVariable<Vec<Effect>>,
+
+    #[visit(optional)]
+    #[reflect(
+        setter = "set_occlusion",
+        description = "Geometry-based occlusion settings of the sound."
+    )]
+    occlusion: InheritableVariable<SoundOcclusion>,
+
+    #[visit(optional)]
+    #[reflect(
+        setter = "set_distance_curve",
+        description = "Custom distance attenuation curve, maps distance to the listener to gain. Overrides the distance model."
+    )]
+    distance_curve: InheritableVariable<Option<Curve>>,
+
+    // Current occlusion factor, 0.0 - clear, 1.0 - fully occluded.
+    #[reflect(hidden)]
+    #[visit(skip)]
+    occlusion_factor: f32,
+
     #[reflect(hidden)]
     #[visit(skip)]
     pub(crate) native: Cell<Handle<SoundSource>>,
@@ -138,6 +169,10 @@ impl Default for Sound {
             playback_time: Default::default(),
             spatial_blend: InheritableVariable::new_modified(1.0),
             audio_bus: InheritableVariable::new_modified(AudioBusGraph::PRIMARY_BUS.to_string()),
+            effects: InheritableVariable::new_modified(Default::default()),
+            occlusion: InheritableVariable::new_modified(Default::default()),
+            distance_curve: InheritableVariable::new_modified(None),
+            occlusion_factor: 0.0,
             native: Default::default(),
         }
     }
@@ -160,6 +195,10 @@ impl Clone for Sound {
             playback_time: self.playback_time.clone(),
             spatial_blend: self.spatial_blend.clone(),
             audio_bus: self.audio_bus.clone(),
+            effects: self.effects.clone(),
+            occlusion: self.occlusion.clone(),
+            distance_curve: self.distance_curve.clone(),
+            occlusion_factor: self.occlusion_factor,
             // Do not copy. The copy will have its own native representation.
             native: Default::default(),
         }
@@ -362,6 +401,52 @@ impl Sound {
     pub fn audio_bus(&self) -> &str {
         &self.audio_bus
     }
+
+    /// Sets new chain of effects that will be applied to the sound before it is mixed into the audio
+    /// bus. Effects keep their internal state when only their parameters are changed, so the method
+    /// could be used every frame to automate the parameters.
+    pub fn set_effects(&mut self, effects: Vec<Effect>) -> Vec<Effect> {
+        self.effects.set_value_and_mark_modified(effects)
+    }
+
+    /// Returns a reference to the chain of effects of the sound.
+    pub fn effects(&self) -> &[Effect] {
+        &self.effects
+    }
+
+    /// Sets new geometry-based occlusion settings of the sound. See [`SoundOcclusion`] docs for more info.
+    pub fn set_occlusion(&mut self, occlusion: SoundOcclusion) -> SoundOcclusion {
+        self.occlusion.set_value_and_mark_modified(occlusion)
+    }
+
+    /// Returns current geometry-based occlusion settings of the sound.
+    pub fn occlusion(&self) -> &SoundOcclusion {
+        &self.occlusion
+    }
+
+    /// Sets a custom distance attenuation curve, which maps the distance between the sound and the
+    /// listener (in meters) to gain. When set, the curve overrides the distance model of the sound
+    /// context, radius, max distance and rolloff factor are ignored.
+    pub fn set_distance_curve(&mut self, curve: Option<Curve>) -> Option<Curve> {
+        self.distance_curve.set_value_and_mark_modified(curve)
+    }
+
+    /// Returns current custom distance attenuation curve (if any).
+    pub fn distance_curve(&self) -> Option<&Curve> {
+        self.distance_curve.as_ref()
+    }
+
+    /// Returns current occlusion factor of the sound, where `0.0` - the sound is clearly audible, `1.0` -
+    /// the sound is fully occluded.
+    pub fn occlusion_factor(&self) -> f32 {
+        self.occlusion_factor
+    }
+
+    /// Returns a reference to the chain of effects of the sound. It could be used to modify the chain
+    /// or parameters of the effects at runtime.
+    pub fn effects_mut(&mut self) -> &mut Vec<Effect> {
+        self.effects.get_value_mut_and_mark_modified()
+    }
 }
 
 impl NodeTrait for Sound {
@@ -410,6 +495,18 @@ impl NodeTrait for Sound {
     }
 
     fn update(&mut self, context: &mut UpdateContext) {
+        if self.occlusion.enabled || self.occlusion_factor > 0.0 {
+            let occluded = self.occlusion.enabled && {
+                let listener = context.sound_context.native.state().listener().position();
+                self.occlusion
+                    .is_occluded(context.physics, listener, self.global_position())
+            };
+            self.occlusion_factor =
+                self.occlusion
+                    .advance_factor(self.occlusion_factor, occluded, context.dt);
+            context.sound_context.set_sound_occlusion(self);
+        }
+
         context.sound_context.sync_with_sound(self);
     }
 
@@ -452,6 +549,9 @@ pub struct SoundBuilder {
     playback_time: Duration,
     spatial_blend: f32,
     audio_bus: String,
+    effects: Vec<Effect>,
+    occlusion: SoundOcclusion,
+    distance_curve: Option<Curve>,
 }
 
 impl SoundBuilder {
@@ -472,6 +572,9 @@ impl SoundBuilder {
             spatial_blend: 1.0,
             playback_time: Default::default(),
             audio_bus: AudioBusGraph::PRIMARY_BUS.to_string(),
+            effects: Default::default(),
+            occlusion: Default::default(),
+            distance_curve: None,
         }
     }
 
@@ -540,6 +643,21 @@ impl SoundBuilder {
         fn with_audio_bus(audio_bus: String)
     );
 
+    define_with!(
+        /// Sets desired chain of effects. See [`Sound::set_effects`] for more info.
+        fn with_effects(effects: Vec<Effect>)
+    );
+
+    define_with!(
+        /// Sets desired occlusion settings. See [`Sound::set_occlusion`] for more info.
+        fn with_occlusion(occlusion: SoundOcclusion)
+    );
+
+    define_with!(
+        /// Sets desired distance attenuation curve. See [`Sound::set_distance_curve`] for more info.
+        fn with_distance_curve(distance_curve: Option<Curve>)
+    );
+
     /// Creates a new [`Sound`] node.
     #[must_use]
     pub fn build_sound(self) -> Sound {
@@ -558,6 +676,10 @@ impl SoundBuilder {
             playback_time: self.playback_time.as_secs_f32().into(),
             spatial_blend: self.spatial_blend.into(),
             audio_bus: self.audio_bus.into(),
+            effects: self.effects.into(),
+            occlusion: self.occlusion.into(),
+            distance_curve: self.distance_curve.into(),
+            occlusion_factor: 0.0,
             native: Default::default(),
         }
     }
@@ -0,0 +1,236 @@
+//! Geometry-based sound occlusion. See [`SoundOcclusion`] docs for more info.
+
+use crate::scene::collider::BitMask;
+use crate::{
+    core::{
+        algebra::{Point3, Vector3},
+        arrayvec::ArrayVec,
+        math::lerpf,
+        reflect::prelude::*,
+        visitor::prelude::*,
+    },
+    scene::{
+        collider::InteractionGroups,
+        graph::physics::{Intersection, PhysicsWorld, RayCastOptions},
+    },
+};
+use fyrox_sound::context::SAMPLE_RATE;
+
+/// Collision group that is used by default to pick colliders that occlude sounds. See [`SoundOcclusion`]
+/// docs for more info.
+pub const OCCLUDER_GROUP: BitMask = BitMask(1 << 31);
+
+/// Sound occlusion settings. When enabled, the engine casts a ray between the listener and the sound
+/// every frame and if the ray hits some collider, the sound is considered occluded: it becomes quieter
+/// and muffled (low frequencies pass through obstacles much better than high frequencies). Transitions
+/// between occluded and clear states are smooth, so a sound behind a door fades gradually as the door
+/// closes.
+///
+/// Colliders that contain the listener or the sound itself (for example, a capsule of a character that
+/// plays footstep sounds) are ignored, otherwise such sounds would be always occluded.
+///
+/// # Dedicated occluders
+///
+/// By default, sounds are occluded only by the colliders that are members of [`OCCLUDER_GROUP`]. Every
+/// collider is a member of every group by default, so exclude this group from memberships of the
+/// colliders that should not occlude sounds (small props, characters, etc.). Use
+/// [`Self::collision_groups`] to pick a different set of occluders.
+#[derive(Visit, Reflect, Debug, Clone, PartialEq)]
+pub struct SoundOcclusion {
+    /// Whether the occlusion is enabled or not. Default is `false`.
+    #[reflect(description = "Whether the occlusion is enabled or not.")]
+    pub enabled: bool,
+
+    /// Gain of the sound when it is fully occluded. Default is `0.35`.
+    #[reflect(
+        description = "Gain of the sound when it is fully occluded.",
+        min_value = 0.0,
+        max_value = 1.0,
+        step = 0.05
+    )]
+    pub gain: f32,
+
+    /// Cutoff frequency (in Hertz) of a low-pass filter applied to the sound when it is fully occluded.
+    /// Default is `1000.0`.
+    #[reflect(
+        description = "Cutoff frequency (in Hertz) of a low-pass filter applied to the sound when it is fully occluded.",
+        min_value = 20.0,
+        max_value = 20000.0
+    )]
+    pub cutoff_frequency_hz: f32,
+
+    /// Time (in seconds) of the transition between occluded and clear states. Default is `0.25`.
+    #[reflect(
+        description = "Time (in seconds) of the transition between occluded and clear states.",
+        min_value = 0.0,
+        step = 0.05
+    )]
+    pub transition_time: f32,
+
+    /// Collision groups of the colliders that occlude the sound.
+    #[reflect(description = "Collision groups of the colliders that occlude the sound.")]
+    pub collision_groups: InteractionGroups,
+}
+
+impl Default for SoundOcclusion {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            gain: 0.35,
+            cutoff_frequency_hz: 1000.0,
+            transition_time: 0.25,
+            collision_groups: InteractionGroups::new(BitMask(u32::MAX), OCCLUDER_GROUP),
+        }
+    }
+}
+
+impl SoundOcclusion {
+    /// Checks whether there's an obstacle between the two points. Colliders that contain any of the
+    /// points are not considered as obstacles.
+    pub fn is_occluded(
+        &self,
+        physics: &PhysicsWorld,
+        listener: Vector3<f32>,
+        source: Vector3<f32>,
+    ) -> bool {
+        let direction = source - listener;
+        let max_len = direction.norm();
+        if max_len <= f32::EPSILON {
+            return false;
+        }
+
+        let cast = |origin: Vector3<f32>, direction: Vector3<f32>| {
+            let mut query_buffer = ArrayVec::<Intersection, 16>::new();
+            physics.cast_ray(
+                RayCastOptions {
+                    ray_origin: Point3::from(origin),
+                    ray_direction: direction,
+                    max_len,
+                    groups: self.collision_groups,
+                    sort_results: false,
+                },
+                &mut query_buffer,
+            );
+            query_buffer
+        };
+
+        // Rays are solid, so a collider that contains the origin of a ray is hit at the origin.
+        let forward = cast(listener, direction);
+        let backward = cast(source, -direction);
+        let contains_endpoint = |intersection: &Intersection| {
+            forward
+                .iter()
+                .chain(backward.iter())
+                .any(|i| i.collider == intersection.collider && i.toi <= f32::EPSILON)
+        };
+        forward.iter().any(|i| !contains_endpoint(i))
+    }
+
+    /// Moves the given occlusion factor (`0.0` - clear, `1.0` - fully occluded) towards the target
+    /// using the transition time.
+    pub fn advance_factor(&self, factor: f32, occluded: bool, dt: f32) -> f32 {
+        let target = if occluded { 1.0 } else { 0.0 };
+        if self.transition_time <= 0.0 {
+            target
+        } else {
+            let max_step = dt / self.transition_time;
+            factor + (target - factor).clamp(-max_step, max_step)
+        }
+    }
+
+    /// Calculates gain and normalized cutoff frequency of the occlusion filter for the given occlusion
+    /// factor. See [`fyrox_sound::source::SoundSource::set_occlusion`] for more info.
+    pub fn filter_parameters(&self, factor: f32) -> (f32, f32) {
+        let gain = lerpf(1.0, self.gain, factor);
+        let fc = if factor <= 0.0 {
+            0.5
+        } else {
+            // Interpolate in logarithmic scale, it is much closer to human perception.
+            let open_frequency = SAMPLE_RATE as f32 * 0.5;
+            let frequency = open_frequency
+                * (self.cutoff_frequency_hz.clamp(20.0, open_frequency) / open_frequency)
+                    .powf(factor);
+            frequency / SAMPLE_RATE as f32
+        };
+        (gain, fc)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::algebra::{Vector2, Vector3},
+        scene::{
+            base::BaseBuilder,
+            collider::{BitMask, ColliderBuilder, ColliderShape, InteractionGroups},
+            graph::Graph,
+            rigidbody::{RigidBodyBuilder, RigidBodyType},
+            sound::occlusion::{SoundOcclusion, OCCLUDER_GROUP},
+            transform::TransformBuilder,
+        },
+    };
+
+    fn add_box(graph: &mut Graph, position: Vector3<f32>, memberships: BitMask) {
+        let collider = ColliderBuilder::new(BaseBuilder::new())
+            .with_shape(ColliderShape::cuboid(0.5, 0.5, 0.5))
+            .with_collision_groups(InteractionGroups::new(memberships, BitMask(u32::MAX)))
+            .build(graph);
+        RigidBodyBuilder::new(
+            BaseBuilder::new()
+                .with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(position)
+                        .build(),
+                )
+                .with_children(&[collider]),
+        )
+        .with_body_type(RigidBodyType::Static)
+        .build(graph);
+    }
+
+    #[test]
+    fn test_is_occluded() {
+        let occlusion = SoundOcclusion {
+            enabled: true,
+            ..Default::default()
+        };
+
+        let mut graph = Graph::new();
+        // A wall between the listener and the sound.
+        add_box(&mut graph, Vector3::new(5.0, 0.0, 0.0), BitMask(u32::MAX));
+        // Colliders of the listener and the sound.
+        add_box(&mut graph, Vector3::new(0.0, 0.0, 0.0), BitMask(u32::MAX));
+        add_box(&mut graph, Vector3::new(10.0, 0.0, 0.0), BitMask(u32::MAX));
+        // A prop that does not occlude sounds.
+        add_box(&mut graph, Vector3::new(0.0, 0.0, 5.0), !OCCLUDER_GROUP);
+
+        // Need to call two times for the physics engine to sync positions of the colliders.
+        graph.update(Vector2::new(800.0, 600.0), 1.0, Default::default());
+        graph.update(Vector2::new(800.0, 600.0), 1.0, Default::default());
+
+        let listener = Vector3::new(0.0, 0.0, 0.0);
+        assert!(occlusion.is_occluded(&graph.physics, listener, Vector3::new(10.0, 0.0, 0.0)));
+        assert!(!occlusion.is_occluded(&graph.physics, listener, Vector3::new(0.0, 0.0, 10.0)));
+        assert!(!occlusion.is_occluded(&graph.physics, listener, Vector3::new(0.0, 10.0, 0.0)));
+        assert!(!occlusion.is_occluded(&graph.physics, listener, listener));
+    }
+
+    #[test]
+    fn test_occlusion_transition() {
+        let occlusion = SoundOcclusion {
+            enabled: true,
+            transition_time: 0.5,
+            ..Default::default()
+        };
+
+        let factor = occlusion.advance_factor(0.0, true, 0.25);
+        assert_eq!(factor, 0.5);
+        assert_eq!(occlusion.advance_factor(factor, true, 1.0), 1.0);
+        assert_eq!(occlusion.advance_factor(factor, false, 1.0), 0.0);
+
+        assert_eq!(occlusion.filter_parameters(0.0), (1.0, 0.5));
+        let (gain, fc) = occlusion.filter_parameters(1.0);
+        assert!((gain - occlusion.gain).abs() < f32::EPSILON);
+        assert!((fc * fyrox_sound::context::SAMPLE_RATE as f32 - 1000.0).abs() < 1.0);
+    }
+}
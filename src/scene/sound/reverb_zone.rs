@@ -0,0 +1,514 @@
+//! Reverb zone is a volume that defines acoustic properties of an environment. See [`ReverbZone`] docs for
+//! more info.
+
+use crate::{
+    core::{
+        algebra::{Point3, Vector3},
+        color::Color,
+        math::aabb::AxisAlignedBoundingBox,
+        pool::Handle,
+        reflect::prelude::*,
+        uuid::{uuid, Uuid},
+        variable::InheritableVariable,
+        visitor::prelude::*,
+        TypeUuidProvider,
+    },
+    define_with,
+    scene::{
+        base::{Base, BaseBuilder},
+        debug::SceneDrawingContext,
+        graph::Graph,
+        node::{Node, NodeTrait, SyncContext},
+    },
+};
+use fxhash::FxHashSet;
+use fyrox_sound::{
+    bus::AudioBusGraph,
+    effects::{reverb::Reverb, Effect},
+};
+use std::ops::{Deref, DerefMut};
+
+/// Reverb zone is a box-shaped volume that defines acoustic properties (reverberation) of an environment,
+/// for example a cave, a hall or a small room. The zone drives [`Reverb`] effect of an audio bus with the
+/// given name (the effect is added automatically, if the bus does not have it), so every sound source that
+/// outputs its samples to the bus will be heard with the reverberation of the environment in which the
+/// listener is.
+///
+/// # Transitions
+///
+/// The zone has full influence when the listener is inside its volume. Outside the volume, the influence
+/// smoothly fades out over [`ReverbZone::blend_distance`], which makes interior/exterior transitions
+/// seamless. When the listener is outside of every zone, the reverberation of the bus is muted. Overlapping
+/// zones are blended using their influence as weights.
+///
+/// # Example
+///
+/// ```rust
+/// # use fyrox::{
+/// #     core::{algebra::Vector3, pool::Handle},
+/// #     scene::{base::BaseBuilder, graph::Graph, node::Node, sound::reverb_zone::ReverbZoneBuilder},
+/// # };
+/// fn create_cave_zone(graph: &mut Graph) -> Handle<Node> {
+///     ReverbZoneBuilder::new(BaseBuilder::new().with_name("Cave"))
+///         .with_half_extents(Vector3::new(10.0, 4.0, 20.0))
+///         .with_blend_distance(3.0)
+///         .with_decay_time(6.0)
+///         .build(graph)
+/// }
+/// ```
+#[derive(Visit, Reflect, Debug, Clone)]
+pub struct ReverbZone {
+    base: Base,
+
+    #[reflect(
+        setter = "set_half_extents",
+        description = "Half size of the volume of the zone along each axis."
+    )]
+    half_extents: InheritableVariable<Vector3<f32>>,
+
+    #[reflect(
+        setter = "set_blend_distance",
+        min_value = 0.0,
+        step = 0.1,
+        description = "Distance from the volume of the zone at which its influence fades out completely."
+    )]
+    blend_distance: InheritableVariable<f32>,
+
+    #[reflect(
+        setter = "set_audio_bus",
+        description = "A name of an audio bus whose reverb effect is driven by the zone."
+    )]
+    audio_bus: InheritableVariable<String>,
+
+    #[reflect(setter = "set_decay_time", min_value = 0.0, step = 0.1)]
+    decay_time: InheritableVariable<f32>,
+
+    #[reflect(setter = "set_damping", min_value = 0.0, max_value = 1.0, step = 0.01)]
+    damping: InheritableVariable<f32>,
+
+    #[reflect(setter = "set_dry", min_value = 0.0, max_value = 1.0, step = 0.01)]
+    dry: InheritableVariable<f32>,
+
+    #[reflect(setter = "set_wet", min_value = 0.0, step = 0.01)]
+    wet: InheritableVariable<f32>,
+}
+
+impl Deref for ReverbZone {
+    type Target = Base;
+
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl DerefMut for ReverbZone {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.base
+    }
+}
+
+impl Default for ReverbZone {
+    fn default() -> Self {
+        ReverbZoneBuilder::new(BaseBuilder::new()).build_reverb_zone()
+    }
+}
+
+impl TypeUuidProvider for ReverbZone {
+    fn type_uuid() -> Uuid {
+        uuid!("0a7b9ad5-8c4c-45a3-b64e-4d7a2b6c3e51")
+    }
+}
+
+impl ReverbZone {
+    /// Sets half size of the volume of the zone along each axis.
+    pub fn set_half_extents(&mut self, half_extents: Vector3<f32>) -> Vector3<f32> {
+        self.half_extents
+            .set_value_and_mark_modified(half_extents.map(|v| v.max(0.0)))
+    }
+
+    /// Returns half size of the volume of the zone along each axis.
+    pub fn half_extents(&self) -> Vector3<f32> {
+        *self.half_extents
+    }
+
+    /// Sets a distance from the volume of the zone at which its influence fades out completely.
+    pub fn set_blend_distance(&mut self, distance: f32) -> f32 {
+        self.blend_distance
+            .set_value_and_mark_modified(distance.max(0.0))
+    }
+
+    /// Returns a distance from the volume of the zone at which its influence fades out completely.
+    pub fn blend_distance(&self) -> f32 {
+        *self.blend_distance
+    }
+
+    /// Sets a name of an audio bus whose reverb effect is driven by the zone.
+    pub fn set_audio_bus(&mut self, audio_bus: String) -> String {
+        self.audio_bus.set_value_and_mark_modified(audio_bus)
+    }
+
+    /// Returns a name of an audio bus whose reverb effect is driven by the zone.
+    pub fn audio_bus(&self) -> &str {
+        &self.audio_bus
+    }
+
+    /// Sets duration of reverberation in seconds. See [`Reverb::set_decay_time`] for more info.
+    pub fn set_decay_time(&mut self, decay_time: f32) -> f32 {
+        self.decay_time
+            .set_value_and_mark_modified(decay_time.max(0.0))
+    }
+
+    /// Returns duration of reverberation in seconds.
+    pub fn decay_time(&self) -> f32 {
+        *self.decay_time
+    }
+
+    /// Sets normalized cutoff frequency of reflections. See [`Reverb::set_fc`] for more info.
+    pub fn set_damping(&mut self, damping: f32) -> f32 {
+        self.damping
+            .set_value_and_mark_modified(damping.clamp(0.0, 1.0))
+    }
+
+    /// Returns normalized cutoff frequency of reflections.
+    pub fn damping(&self) -> f32 {
+        *self.damping
+    }
+
+    /// Sets how much of the input signal passes through the reverb without processing.
+    pub fn set_dry(&mut self, dry: f32) -> f32 {
+        self.dry.set_value_and_mark_modified(dry.clamp(0.0, 1.0))
+    }
+
+    /// Returns how much of the input signal passes through the reverb without processing.
+    pub fn dry(&self) -> f32 {
+        *self.dry
+    }
+
+    /// Sets gain of the reverberated signal.
+    pub fn set_wet(&mut self, wet: f32) -> f32 {
+        self.wet.set_value_and_mark_modified(wet.max(0.0))
+    }
+
+    /// Returns gain of the reverberated signal.
+    pub fn wet(&self) -> f32 {
+        *self.wet
+    }
+
+    /// Calculates influence of the zone at the given point in world coordinates. The influence is 1.0
+    /// inside the volume of the zone and linearly fades out to 0.0 over the blend distance.
+    pub fn influence(&self, point: Vector3<f32>) -> f32 {
+        let transform = self.global_transform();
+        let Some(inv_transform) = transform.try_inverse() else {
+            return 0.0;
+        };
+
+        let local_point = inv_transform.transform_point(&Point3::from(point));
+        let half_extents = *self.half_extents;
+        let closest = Point3::new(
+            local_point.x.clamp(-half_extents.x, half_extents.x),
+            local_point.y.clamp(-half_extents.y, half_extents.y),
+            local_point.z.clamp(-half_extents.z, half_extents.z),
+        );
+        if closest == local_point {
+            return 1.0;
+        }
+
+        let distance = (transform.transform_point(&closest).coords - point).norm();
+        if *self.blend_distance > 0.0 {
+            (1.0 - distance / *self.blend_distance).max(0.0)
+        } else {
+            0.0
+        }
+    }
+}
+
+impl NodeTrait for ReverbZone {
+    crate::impl_query_component!();
+
+    fn local_bounding_box(&self) -> AxisAlignedBoundingBox {
+        AxisAlignedBoundingBox::from_min_max(-*self.half_extents, *self.half_extents)
+    }
+
+    fn world_bounding_box(&self) -> AxisAlignedBoundingBox {
+        self.local_bounding_box()
+            .transform(&self.global_transform())
+    }
+
+    fn id(&self) -> Uuid {
+        Self::type_uuid()
+    }
+
+    fn sync_native(&self, _self_handle: Handle<Node>, context: &mut SyncContext) {
+        if !self.is_globally_enabled() {
+            return;
+        }
+
+        let listener_position = context.sound_context.native.state().listener().position();
+
+        context
+            .sound_context
+            .reverb_zones
+            .push(ReverbZoneInfluence {
+                audio_bus: (*self.audio_bus).clone(),
+                weight: self.influence(listener_position),
+                decay_time: *self.decay_time,
+                damping: *self.damping,
+                dry: *self.dry,
+                wet: *self.wet,
+            });
+    }
+
+    fn debug_draw(&self, ctx: &mut SceneDrawingContext) {
+        ctx.draw_oob(
+            &self.local_bounding_box(),
+            self.global_transform(),
+            Color::opaque(80, 160, 255),
+        );
+    }
+}
+
+/// Allows you to create a reverb zone in declarative manner.
+pub struct ReverbZoneBuilder {
+    base_builder: BaseBuilder,
+    half_extents: Vector3<f32>,
+    blend_distance: f32,
+    audio_bus: String,
+    decay_time: f32,
+    damping: f32,
+    dry: f32,
+    wet: f32,
+}
+
+impl ReverbZoneBuilder {
+    /// Creates new reverb zone builder.
+    pub fn new(base_builder: BaseBuilder) -> Self {
+        Self {
+            base_builder,
+            half_extents: Vector3::repeat(5.0),
+            blend_distance: 2.0,
+            audio_bus: AudioBusGraph::PRIMARY_BUS.to_string(),
+            decay_time: 2.0,
+            damping: 0.25615,
+            dry: 1.0,
+            wet: 1.0,
+        }
+    }
+
+    define_with!(
+        /// Sets desired half extents. See [`ReverbZone::set_half_extents`] for more info.
+        fn with_half_extents(half_extents: Vector3<f32>)
+    );
+
+    define_with!(
+        /// Sets desired blend distance. See [`ReverbZone::set_blend_distance`] for more info.
+        fn with_blend_distance(blend_distance: f32)
+    );
+
+    define_with!(
+        /// Sets desired audio bus. See [`ReverbZone::set_audio_bus`] for more info.
+        fn with_audio_bus(audio_bus: String)
+    );
+
+    define_with!(
+        /// Sets desired decay time. See [`ReverbZone::set_decay_time`] for more info.
+        fn with_decay_time(decay_time: f32)
+    );
+
+    define_with!(
+        /// Sets desired damping. See [`ReverbZone::set_damping`] for more info.
+        fn with_damping(damping: f32)
+    );
+
+    define_with!(
+        /// Sets desired dry part. See [`ReverbZone::set_dry`] for more info.
+        fn with_dry(dry: f32)
+    );
+
+    define_with!(
+        /// Sets desired wet part. See [`ReverbZone::set_wet`] for more info.
+        fn with_wet(wet: f32)
+    );
+
+    /// Creates a new [`ReverbZone`] instance.
+    pub fn build_reverb_zone(self) -> ReverbZone {
+        ReverbZone {
+            base: self.base_builder.build_base(),
+            half_extents: self.half_extents.into(),
+            blend_distance: self.blend_distance.into(),
+            audio_bus: self.audio_bus.into(),
+            decay_time: self.decay_time.into(),
+            damping: self.damping.into(),
+            dry: self.dry.into(),
+            wet: self.wet.into(),
+        }
+    }
+
+    /// Creates a new [`ReverbZone`] node.
+    pub fn build_node(self) -> Node {
+        Node::new(self.build_reverb_zone())
+    }
+
+    /// Creates a new [`ReverbZone`] node and adds it to the graph.
+    pub fn build(self, graph: &mut Graph) -> Handle<Node> {
+        graph.add_node(self.build_node())
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct ReverbZoneInfluence {
+    audio_bus: String,
+    weight: f32,
+    decay_time: f32,
+    damping: f32,
+    dry: f32,
+    wet: f32,
+}
+
+/// Blends influences of every reverb zone and applies them to the respective audio buses.
+#[derive(Default, Debug)]
+pub(crate) struct ReverbZoneMixer {
+    influences: Vec<ReverbZoneInfluence>,
+    driven_buses: FxHashSet<String>,
+}
+
+impl ReverbZoneMixer {
+    pub(crate) fn push(&mut self, influence: ReverbZoneInfluence) {
+        self.influences.push(influence);
+    }
+
+    pub(crate) fn apply(&mut self, bus_graph: &mut AudioBusGraph) {
+        let mut buses = self
+            .influences
+            .iter()
+            .map(|i| i.audio_bus.clone())
+            .collect::<FxHashSet<_>>();
+        // Buses that were driven by zones that no longer exist must be reset.
+        buses.extend(self.driven_buses.drain());
+
+        for bus_name in buses.iter() {
+            let influences = self
+                .influences
+                .iter()
+                .filter(|i| &i.audio_bus == bus_name)
+                .collect::<Vec<_>>();
+
+            let Some(bus) = bus_graph.buses_iter_mut().find(|b| b.name() == bus_name) else {
+                continue;
+            };
+
+            let position = bus.effects().position(|e| matches!(e, Effect::Reverb(_)));
+            let reverb = match position {
+                Some(index) => bus.effect_mut(index),
+                None if !influences.is_empty() => {
+                    let mut reverb = Reverb::new();
+                    reverb.set_gain(0.0);
+                    bus.add_effect(Effect::Reverb(reverb));
+                    bus.effects_mut().last()
+                }
+                None => None,
+            };
+            let Some(Effect::Reverb(reverb)) = reverb else {
+                continue;
+            };
+
+            let total_weight = influences.iter().map(|i| i.weight).sum::<f32>();
+            let norm = total_weight.max(1.0);
+            let outside = 1.0 - total_weight.min(1.0);
+
+            let mut dry = outside;
+            let mut wet = 0.0;
+            for influence in influences.iter() {
+                dry += influence.dry * influence.weight / norm;
+                wet += influence.wet * influence.weight / norm;
+            }
+            reverb.set_dry(dry);
+            reverb.set_gain(wet);
+
+            // Decay time and damping are meaningless without reverberated signal, so they're taken
+            // from the zones only.
+            if total_weight > 0.0 {
+                let mut decay_time = 0.0;
+                let mut damping = 0.0;
+                for influence in influences.iter() {
+                    decay_time += influence.decay_time * influence.weight / total_weight;
+                    damping += influence.damping * influence.weight / total_weight;
+                }
+                if (reverb.decay_time() - decay_time).abs() > f32::EPSILON {
+                    reverb.set_decay_time(decay_time);
+                }
+                if (reverb.fc() - damping).abs() > f32::EPSILON {
+                    reverb.set_fc(damping);
+                }
+            }
+
+            if !influences.is_empty() {
+                self.driven_buses.insert(bus_name.clone());
+            }
+        }
+
+        self.influences.clear();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::algebra::Vector3,
+        scene::{
+            base::BaseBuilder,
+            graph::Graph,
+            sound::{
+                reverb_zone::{
+                    ReverbZone, ReverbZoneBuilder, ReverbZoneInfluence, ReverbZoneMixer,
+                },
+                AudioBusGraph, Effect,
+            },
+        },
+    };
+
+    #[test]
+    fn test_reverb_zone_influence() {
+        let mut graph = Graph::new();
+        let zone = ReverbZoneBuilder::new(BaseBuilder::new())
+            .with_half_extents(Vector3::repeat(1.0))
+            .with_blend_distance(2.0)
+            .build(&mut graph);
+        graph.update_hierarchical_data();
+
+        let zone = graph[zone].cast::<ReverbZone>().unwrap();
+        assert_eq!(zone.influence(Vector3::new(0.5, 0.0, 0.0)), 1.0);
+        assert_eq!(zone.influence(Vector3::new(2.0, 0.0, 0.0)), 0.5);
+        assert_eq!(zone.influence(Vector3::new(4.0, 0.0, 0.0)), 0.0);
+    }
+
+    #[test]
+    fn test_reverb_zone_mixer() {
+        let mut bus_graph = AudioBusGraph::new();
+        let mut mixer = ReverbZoneMixer::default();
+
+        mixer.push(ReverbZoneInfluence {
+            audio_bus: AudioBusGraph::PRIMARY_BUS.to_string(),
+            weight: 0.5,
+            decay_time: 4.0,
+            damping: 0.5,
+            dry: 0.5,
+            wet: 1.0,
+        });
+        mixer.apply(&mut bus_graph);
+
+        let Some(Effect::Reverb(reverb)) = bus_graph.primary_bus_ref().effect(0) else {
+            panic!("Reverb must be added!")
+        };
+        assert_eq!(reverb.get_dry(), 0.75);
+        assert_eq!(reverb.gain(), 0.5);
+        assert_eq!(reverb.decay_time(), 4.0);
+
+        // The zone is gone - the reverb must be muted.
+        mixer.apply(&mut bus_graph);
+        let Some(Effect::Reverb(reverb)) = bus_graph.primary_bus_ref().effect(0) else {
+            panic!("Reverb must be kept!")
+        };
+        assert_eq!(reverb.get_dry(), 1.0);
+        assert_eq!(reverb.gain(), 0.0);
+    }
+}
@@ -15,7 +15,7 @@ use crate::{
     event::{ElementState, MouseScrollDelta, WindowEvent},
     gui::{
         draw, message,
-        message::{ButtonState, KeyboardModifiers, OsEvent},
+        message::{ButtonState, ImeEvent, KeyboardModifiers, OsEvent},
     },
     keyboard::{KeyCode, ModifiersState},
     resource::texture::TextureResource,
@@ -23,7 +23,10 @@ use crate::{
 use fyrox_ui::message::CursorIcon;
 use half::f16;
 use std::{any::Any, hash::Hasher, sync::Arc};
-use winit::{event::Touch, keyboard::PhysicalKey};
+use winit::{
+    event::{Ime, Touch},
+    keyboard::PhysicalKey,
+};
 
 /// Translates `winit`'s key code to `fyrox-ui`'s key code.
 pub fn translate_key_to_ui(key: KeyCode) -> message::KeyCode {
@@ -556,6 +559,18 @@ pub fn translate_event(event: &WindowEvent) -> Option<OsEvent> {
             },
             id: *id,
         }),
+        WindowEvent::Ime(ime) => Some(OsEvent::Ime(match ime {
+            Ime::Enabled => ImeEvent::Enabled,
+            Ime::Preedit(text, cursor) => ImeEvent::Preedit {
+                text: text.clone(),
+                cursor: *cursor,
+            },
+            Ime::Commit(text) => ImeEvent::Commit(text.clone()),
+            Ime::Disabled => ImeEvent::Disabled,
+        })),
+        WindowEvent::ScaleFactorChanged { scale_factor, .. } => {
+            Some(OsEvent::ScaleFactorChanged(*scale_factor as f32))
+        }
         _ => None,
     }
 }